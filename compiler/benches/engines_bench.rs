@@ -0,0 +1,160 @@
+//! Cross-engine benchmark harness. Every program under
+//! `benches/programs/` is run through each execution path built from
+//! the same source:
+//!
+//! - `interpreter` — tree-walking `interpreter::execute_program`
+//!   (parse + type-check happen once, outside the timed region).
+//! - `jit` — `compile_to_jit_main_with_options`, i.e. the AOT
+//!   lowering pipeline installed in-process via `cranelift_jit`.
+//!   Only the call into the generated `main` is timed.
+//! - `aot` — `compile_file(EmitKind::Executable)` once, then each
+//!   iteration spawns the binary. Process spawn is part of the
+//!   measurement, so compare `aot` against itself across commits
+//!   rather than against the in-process engines.
+//!
+//! Before timing, the harness checks that every engine which accepts
+//! the program agrees on `main`'s result (`& 0xff`, the exit-code
+//! convention `consistency.rs` uses), so a miscompile shows up as a
+//! panic instead of a suspiciously fast number. An engine that
+//! rejects a program (the AOT backend does not cover every stdlib
+//! string method, for instance) is skipped with a note on stderr.
+//!
+//! The interpreter's own cranelift JIT (`INTERPRETER_JIT=1`) is
+//! measured by `interpreter/benches/jit_bench.rs`; this crate depends
+//! on the interpreter without its `jit` feature.
+//!
+//! Run: `cargo bench -p compiler --bench engines_bench`.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use compiler::{compile_file, compile_to_jit_main_with_options, CompilerOptions, EmitKind, JitProgram};
+use criterion::{criterion_group, criterion_main, Criterion};
+use frontend::ast::Program;
+use interpreter::object::Object;
+use std::hint::black_box;
+use string_interner::DefaultStringInterner;
+
+const PROGRAMS: &[(&str, &str)] = &[
+    ("fib", include_str!("programs/fib.t")),
+    ("matmul", include_str!("programs/matmul.t")),
+    ("strings", include_str!("programs/strings.t")),
+];
+
+fn core_modules_dir() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../core"))
+}
+
+fn compiler_options(input: PathBuf, output: Option<PathBuf>) -> CompilerOptions {
+    CompilerOptions {
+        input,
+        output,
+        emit: EmitKind::Executable,
+        verbose: false,
+        release: true,
+        bounds_check: false,
+        core_modules_dir: Some(core_modules_dir()),
+        link_cache_dir: None,
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
+    }
+}
+
+fn prepare_interpreter(source: &str) -> Result<(Program, DefaultStringInterner), String> {
+    let mut parser = frontend::ParserWithInterner::new(source);
+    let mut program = parser.parse_program().map_err(|e| format!("parse: {e:?}"))?;
+    let core = core_modules_dir();
+    let interner = parser.get_string_interner();
+    interpreter::check_typing_with_core_modules(
+        &mut program,
+        interner,
+        Some(source),
+        Some("bench.t"),
+        Some(core.as_path()),
+    )
+    .map_err(|errors| errors.join("\n"))?;
+    Ok((program, interner.clone()))
+}
+
+fn run_interpreter(program: &Program, interner: &DefaultStringInterner) -> u64 {
+    let result = interpreter::execute_program(program, interner, None, None)
+        .expect("interpreter execution failed");
+    let value = result.borrow();
+    match &*value {
+        Object::UInt64(n) => *n,
+        Object::Int64(n) => *n as u64,
+        other => panic!("unexpected interpreter result: {other:?}"),
+    }
+}
+
+/// Build the AOT executable for `source`; returns its path.
+fn build_executable(name: &str, source: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(format!("toy_engines_bench_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let src_path = dir.join(format!("{name}.t"));
+    let exe_path = dir.join(name);
+    std::fs::write(&src_path, source).map_err(|e| e.to_string())?;
+    compile_file(&compiler_options(src_path, Some(exe_path.clone())))?;
+    Ok(exe_path)
+}
+
+fn run_executable(exe: &PathBuf) -> u64 {
+    let status = Command::new(exe).status().expect("spawn AOT executable");
+    status.code().expect("AOT executable killed by signal") as u64
+}
+
+fn bench_program(c: &mut Criterion, name: &str, source: &str) {
+    let interp = prepare_interpreter(source);
+    let jit: Result<JitProgram, String> = compile_to_jit_main_with_options(
+        source,
+        &compiler_options(PathBuf::from("<jit>"), None),
+    );
+    let aot = build_executable(name, source);
+
+    // Cross-check results before timing anything.
+    let mut results: Vec<(&str, u64)> = Vec::new();
+    match &interp {
+        Ok((program, interner)) => results.push(("interpreter", run_interpreter(program, interner) & 0xff)),
+        Err(e) => eprintln!("[{name}] interpreter: skipped ({e})"),
+    }
+    match &jit {
+        Ok(prog) => results.push(("jit", prog.run() & 0xff)),
+        Err(e) => eprintln!("[{name}] jit: skipped ({e})"),
+    }
+    match &aot {
+        Ok(exe) => results.push(("aot", run_executable(exe))),
+        Err(e) => eprintln!("[{name}] aot: skipped ({e})"),
+    }
+    if let Some((_, expected)) = results.first() {
+        for (engine, value) in &results {
+            assert_eq!(value, expected, "[{name}] {engine} disagrees with {}", results[0].0);
+        }
+    }
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+    if let Ok((program, interner)) = &interp {
+        group.bench_function("interpreter", |b| {
+            b.iter(|| run_interpreter(black_box(program), black_box(interner)))
+        });
+    }
+    if let Ok(prog) = &jit {
+        group.bench_function("jit", |b| b.iter(|| black_box(prog.run())));
+    }
+    if let Ok(exe) = &aot {
+        group.bench_function("aot", |b| b.iter(|| run_executable(black_box(exe))));
+        let _ = std::fs::remove_file(exe);
+    }
+    group.finish();
+}
+
+fn engines(c: &mut Criterion) {
+    for (name, source) in PROGRAMS {
+        bench_program(c, name, source);
+    }
+}
+
+criterion_group!(benches, engines);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+# Recursive fibonacci: call overhead and integer arithmetic.
+
+fn fib(n: u64) -> u64 {
+    if n <= 1u64 {
+        n
+    } else {
+        fib(n - 1u64) + fib(n - 2u64)
+    }
+}
+
+fn main() -> u64 {
+    fib(20u64)
+}
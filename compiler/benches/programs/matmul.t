@@ -0,0 +1,23 @@
+# 4x4 integer matrix multiply over flat arrays, repeated so the
+# inner loop dominates: array indexing and nested `for` loops.
+
+fn main() -> u64 {
+    var a: [u64; 16] = [1u64, 2u64, 3u64, 4u64, 5u64, 6u64, 0u64, 1u64, 2u64, 3u64, 4u64, 5u64, 6u64, 0u64, 1u64, 2u64]
+    var b: [u64; 16] = [4u64, 3u64, 2u64, 1u64, 0u64, 4u64, 3u64, 2u64, 1u64, 0u64, 4u64, 3u64, 2u64, 1u64, 0u64, 4u64]
+    var c: [u64; 16] = [0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64, 0u64]
+    var checksum: u64 = 0u64
+    for round in 0u64 to 100u64 {
+        for i in 0u64 to 4u64 {
+            for j in 0u64 to 4u64 {
+                var acc: u64 = 0u64
+                for k in 0u64 to 4u64 {
+                    acc = acc + a[i * 4u64 + k] * b[k * 4u64 + j]
+                }
+                c[i * 4u64 + j] = acc + round
+            }
+        }
+        a[round % 16u64] = c[15u64] % 7u64
+        checksum = checksum + c[15u64]
+    }
+    checksum % 256u64
+}
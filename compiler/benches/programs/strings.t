@@ -0,0 +1,18 @@
+# String processing: case folding, concatenation, and substring
+# search over a growing buffer.
+
+fn main() -> u64 {
+    var hits: u64 = 0u64
+    var line: str = "the quick brown fox"
+    for i in 0u64 to 200u64 {
+        val upper = line.to_upper()
+        if upper.contains("FOX") {
+            hits = hits + 1u64
+        }
+        hits = hits + line.trim().len()
+        if i % 50u64 == 0u64 {
+            line = line.concat(" jumps")
+        }
+    }
+    hits % 256u64
+}
@@ -61,8 +61,13 @@ fn main() {
                 emit: EmitKind::Object,
                 verbose: false,
                 release: false,
+                bounds_check: false,
                 core_modules_dir: Some(core.clone()),
             link_cache_dir: None,
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
             };
             let t_obj0 = Instant::now();
             compile_file(&obj_opts).expect("compile object");
@@ -78,8 +83,13 @@ fn main() {
                 emit: EmitKind::Executable,
                 verbose: false,
                 release: false,
+                bounds_check: false,
                 core_modules_dir: Some(core.clone()),
             link_cache_dir: None,
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
             };
             let t_exe0 = Instant::now();
             compile_file(&exe_opts).expect("compile exec");
@@ -36,8 +36,13 @@ fn main() {
         emit: compiler::EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: Some(core.clone()),
             link_cache_dir: None,
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     let opts_no_core = compiler::CompilerOptions {
         core_modules_dir: None,
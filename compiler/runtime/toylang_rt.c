@@ -322,3 +322,26 @@ const char *toy_to_string_i32(int32_t v) {
 const char *toy_to_string_u32(uint32_t v) {
     return toy_to_string_u64((uint64_t) v);
 }
+
+/* u64 bit manipulation backing the `__extern_*_u64` declarations in
+ * `core/std/math.t` (mapped in `lower/program::libm_import_name_for`).
+ * `__builtin_clzll` / `__builtin_ctzll` are undefined for 0, so the
+ * zero case is handled explicitly to return 64 like the interpreter /
+ * JIT. Rotate amounts are taken modulo 64. */
+uint64_t toy_count_ones_u64(uint64_t x) {
+    return (uint64_t) __builtin_popcountll(x);
+}
+uint64_t toy_leading_zeros_u64(uint64_t x) {
+    return x == 0 ? 64 : (uint64_t) __builtin_clzll(x);
+}
+uint64_t toy_trailing_zeros_u64(uint64_t x) {
+    return x == 0 ? 64 : (uint64_t) __builtin_ctzll(x);
+}
+uint64_t toy_rotate_left_u64(uint64_t x, uint64_t n) {
+    n &= 63;
+    return n == 0 ? x : (x << n) | (x >> (64 - n));
+}
+uint64_t toy_rotate_right_u64(uint64_t x, uint64_t n) {
+    n &= 63;
+    return n == 0 ? x : (x >> n) | (x << (64 - n));
+}
@@ -49,7 +49,7 @@ pub fn emit_object(
     contract_msgs: &ContractMessages,
     options: &CompilerOptions,
 ) -> Result<Vec<u8>, String> {
-    let ir_module = lower::lower_program(program, interner, contract_msgs, options.release)?;
+    let ir_module = lower_and_optimize(program, interner, contract_msgs, options)?;
     let module = build_object_module(&ir_module, interner, options)?;
     let product = module.finish();
     product
@@ -57,6 +57,23 @@ pub fn emit_object(
         .map_err(|e| format!("object emission failed: {e}"))
 }
 
+/// Lower `program` to IR and run the IR-level passes the opt level
+/// asks for: `inline::inline_module`, then `gvn::number_values` (which
+/// finds more after inlining), both skipped at `-O0`.
+pub(crate) fn lower_and_optimize(
+    program: &Program,
+    interner: &DefaultStringInterner,
+    contract_msgs: &ContractMessages,
+    options: &CompilerOptions,
+) -> Result<IrModule, String> {
+    let mut ir_module = lower::lower_program(program, interner, contract_msgs, options.release, options.bounds_check)?;
+    if cranelift_opt_level(options) != "none" {
+        crate::inline::inline_module(&mut ir_module);
+        crate::gvn::number_values(&mut ir_module);
+    }
+    Ok(ir_module)
+}
+
 /// Render the freshly-built IR as text. Used by `--emit=ir`.
 pub fn emit_ir_text(
     program: &Program,
@@ -64,7 +81,7 @@ pub fn emit_ir_text(
     contract_msgs: &ContractMessages,
     options: &CompilerOptions,
 ) -> Result<String, String> {
-    let ir_module = lower::lower_program(program, interner, contract_msgs, options.release)?;
+    let ir_module = lower_and_optimize(program, interner, contract_msgs, options)?;
     Ok(format!("{ir_module}"))
 }
 
@@ -76,8 +93,8 @@ pub fn emit_clif_text(
     contract_msgs: &ContractMessages,
     options: &CompilerOptions,
 ) -> Result<String, String> {
-    let ir_module = lower::lower_program(program, interner, contract_msgs, options.release)?;
-    let module = make_object_module()?;
+    let ir_module = lower_and_optimize(program, interner, contract_msgs, options)?;
+    let module = make_object_module(options)?;
     let mut session = CodegenSession::new(module)?;
     session.declare_all(&ir_module, interner)?;
     let mut out = String::new();
@@ -104,7 +121,7 @@ fn build_object_module(
     interner: &DefaultStringInterner,
     options: &CompilerOptions,
 ) -> Result<ObjectModule, String> {
-    let module = make_object_module()?;
+    let module = make_object_module(options)?;
     let mut session = CodegenSession::new(module)?;
     session.declare_all(ir_module, interner)?;
     for func_id in 0..ir_module.functions.len() {
@@ -250,14 +267,18 @@ pub(crate) struct CodegenSession<M: Module> {
     const_str_bytes: HashMap<Vec<u8>, DataId>,
 }
 
-/// Resolve cranelift's `opt_level` flag from the environment, defaulting
-/// to `"speed"` for production / interactive use. Setting
+/// Resolve cranelift's `opt_level` flag. An explicit `-O` preset in
+/// `options` wins; otherwise the environment decides, defaulting to
+/// `"speed"` for production / interactive use. Setting
 /// `TOYLANG_CRANELIFT_OPT_LEVEL=none` slashes per-compile time roughly
 /// 20x at the cost of slower generated code — useful for the test
 /// suite where we typically build hundreds of tiny programs and run
 /// each only once. `"speed_and_size"` is also accepted for symmetry
 /// with cranelift's setting names.
-pub(crate) fn cranelift_opt_level() -> &'static str {
+pub(crate) fn cranelift_opt_level(options: &CompilerOptions) -> &'static str {
+    if let Some(level) = options.opt_level {
+        return level.cranelift_name();
+    }
     match std::env::var("TOYLANG_CRANELIFT_OPT_LEVEL") {
         Ok(v) if v == "none" => "none",
         Ok(v) if v == "speed_and_size" => "speed_and_size",
@@ -265,16 +286,28 @@ pub(crate) fn cranelift_opt_level() -> &'static str {
     }
 }
 
-/// Construct the host-targeted ObjectModule used by the AOT pipeline.
-/// Pulled out of `CodegenSession::new` so the JIT path can build a
-/// `JITModule` with its own ISA settings and still funnel into the
-/// same generic `CodegenSession::new(module)`.
-pub(crate) fn make_object_module() -> Result<ObjectModule, String> {
-    let isa_builder = cranelift_native::builder()
-        .map_err(|e| format!("host ISA detection failed: {e}"))?;
+/// Construct the ObjectModule used by the AOT pipeline, targeting
+/// `options.target_triple` (the host when unset). Pulled out of
+/// `CodegenSession::new` so the JIT path can build a `JITModule` with
+/// its own ISA settings and still funnel into the same generic
+/// `CodegenSession::new(module)`.
+pub(crate) fn make_object_module(options: &CompilerOptions) -> Result<ObjectModule, String> {
+    let isa_builder = match &options.target_triple {
+        Some(triple) => {
+            let parsed = triple
+                .parse::<target_lexicon::Triple>()
+                .map_err(|e| format!("invalid target triple `{triple}`: {e}"))?;
+            // Only the host architecture's backend is compiled into
+            // cranelift-codegen, so other architectures fail here.
+            cranelift_codegen::isa::lookup(parsed)
+                .map_err(|e| format!("unsupported target `{triple}`: {e}"))?
+        }
+        None => cranelift_native::builder()
+            .map_err(|e| format!("host ISA detection failed: {e}"))?,
+    };
     let mut flag_builder = settings::builder();
     flag_builder
-        .set("opt_level", cranelift_opt_level())
+        .set("opt_level", cranelift_opt_level(options))
         .map_err(|e| format!("flag set: {e}"))?;
     // PIC is required by some platform linkers (notably recent macOS)
     // for relocatable objects feeding into PIE executables.
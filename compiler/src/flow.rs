@@ -0,0 +1,376 @@
+//! Control-flow analyses over one IR `Function`.
+//!
+//! Lowering hands codegen a list of blocks that already end in explicit
+//! terminators, so the graph is fully described by `Terminator::Jump` /
+//! `Terminator::Branch`. This module derives the pieces optimisation
+//! passes and diagnostics need from it:
+//!
+//! - `ControlFlowGraph`: successors / predecessors, reverse postorder
+//!   from the entry block, and which blocks are reachable at all.
+//! - `Dominators`: immediate dominators (Cooper, Harvey & Kennedy's
+//!   iterative algorithm over reverse postorder).
+//! - `Liveness`: live-in / live-out sets of `LocalId`s per block.
+//!   Values (`ValueId`) are not tracked — merges in this IR go through
+//!   locals, so locals are what a dead-store or register-pressure pass
+//!   cares about.
+//!
+//! None of these mutate the function; run them again after a pass
+//! changes the block structure.
+
+use std::collections::HashSet;
+
+use crate::ir::{BlockId, Function, InstKind, LocalId, Terminator};
+
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    entry: BlockId,
+    successors: Vec<Vec<BlockId>>,
+    predecessors: Vec<Vec<BlockId>>,
+    /// Reachable blocks only, entry first.
+    reverse_postorder: Vec<BlockId>,
+    /// Position of each block in `reverse_postorder`; `None` when the
+    /// block is unreachable from the entry.
+    rpo_index: Vec<Option<usize>>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(func: &Function) -> Self {
+        let n = func.blocks.len();
+        let mut successors = vec![Vec::new(); n];
+        let mut predecessors = vec![Vec::new(); n];
+        for block in &func.blocks {
+            let succs = match &block.terminator {
+                Some(Terminator::Jump(target)) => vec![*target],
+                // `if c { } else { }` with both arms jumping to the same
+                // block still counts as a single edge.
+                Some(Terminator::Branch { then_blk, else_blk, .. }) if then_blk == else_blk => {
+                    vec![*then_blk]
+                }
+                Some(Terminator::Branch { then_blk, else_blk, .. }) => vec![*then_blk, *else_blk],
+                Some(Terminator::Return(_))
+                | Some(Terminator::Panic { .. })
+                | Some(Terminator::Unreachable)
+                | None => Vec::new(),
+            };
+            for succ in &succs {
+                predecessors[succ.0 as usize].push(block.id);
+            }
+            successors[block.id.0 as usize] = succs;
+        }
+
+        // Iterative DFS so deeply nested loops can't overflow the stack.
+        let mut postorder = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        if n > 0 {
+            let mut stack = vec![(func.entry, 0usize)];
+            visited[func.entry.0 as usize] = true;
+            while let Some((block, next)) = stack.pop() {
+                let succs = &successors[block.0 as usize];
+                if next < succs.len() {
+                    stack.push((block, next + 1));
+                    let succ = succs[next];
+                    if !visited[succ.0 as usize] {
+                        visited[succ.0 as usize] = true;
+                        stack.push((succ, 0));
+                    }
+                } else {
+                    postorder.push(block);
+                }
+            }
+        }
+        postorder.reverse();
+        let mut rpo_index = vec![None; n];
+        for (i, block) in postorder.iter().enumerate() {
+            rpo_index[block.0 as usize] = Some(i);
+        }
+
+        Self {
+            entry: func.entry,
+            successors,
+            predecessors,
+            reverse_postorder: postorder,
+            rpo_index,
+        }
+    }
+
+    pub fn entry(&self) -> BlockId {
+        self.entry
+    }
+
+    pub fn successors(&self, block: BlockId) -> &[BlockId] {
+        &self.successors[block.0 as usize]
+    }
+
+    /// Includes edges from unreachable blocks.
+    pub fn predecessors(&self, block: BlockId) -> &[BlockId] {
+        &self.predecessors[block.0 as usize]
+    }
+
+    /// Reachable blocks in reverse postorder (entry first).
+    pub fn reverse_postorder(&self) -> &[BlockId] {
+        &self.reverse_postorder
+    }
+
+    pub fn is_reachable(&self, block: BlockId) -> bool {
+        self.rpo_index[block.0 as usize].is_some()
+    }
+
+    /// Blocks no path from the entry reaches, in block order.
+    pub fn unreachable_blocks(&self) -> Vec<BlockId> {
+        (0..self.rpo_index.len() as u32)
+            .map(BlockId)
+            .filter(|b| !self.is_reachable(*b))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Dominators {
+    entry: BlockId,
+    /// `idom[b]`; the entry maps to itself, unreachable blocks to `None`.
+    idom: Vec<Option<BlockId>>,
+}
+
+impl Dominators {
+    pub fn compute(cfg: &ControlFlowGraph) -> Self {
+        let mut idom: Vec<Option<BlockId>> = vec![None; cfg.rpo_index.len()];
+        let Some(&entry) = cfg.reverse_postorder.first() else {
+            return Self { entry: cfg.entry, idom };
+        };
+        idom[entry.0 as usize] = Some(entry);
+
+        let intersect = |idom: &[Option<BlockId>], mut a: BlockId, mut b: BlockId| {
+            let rpo = |x: BlockId| cfg.rpo_index[x.0 as usize].expect("reachable block");
+            while a != b {
+                while rpo(a) > rpo(b) {
+                    a = idom[a.0 as usize].expect("processed block");
+                }
+                while rpo(b) > rpo(a) {
+                    b = idom[b.0 as usize].expect("processed block");
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in &cfg.reverse_postorder[1..] {
+                let mut new_idom: Option<BlockId> = None;
+                for &pred in cfg.predecessors(block) {
+                    if idom[pred.0 as usize].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(current) => intersect(&idom, pred, current),
+                    });
+                }
+                if new_idom.is_some() && idom[block.0 as usize] != new_idom {
+                    idom[block.0 as usize] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        Self { entry, idom }
+    }
+
+    /// Immediate dominator. `None` for the entry and unreachable blocks.
+    pub fn idom(&self, block: BlockId) -> Option<BlockId> {
+        if block == self.entry {
+            return None;
+        }
+        self.idom[block.0 as usize]
+    }
+
+    /// Whether `a` dominates `b` (every block dominates itself).
+    /// Always `false` when `b` is unreachable.
+    pub fn dominates(&self, a: BlockId, b: BlockId) -> bool {
+        if self.idom[b.0 as usize].is_none() {
+            return false;
+        }
+        let mut cur = b;
+        loop {
+            if cur == a {
+                return true;
+            }
+            match self.idom(cur) {
+                Some(next) => cur = next,
+                None => return false,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Liveness {
+    live_in: Vec<HashSet<LocalId>>,
+    live_out: Vec<HashSet<LocalId>>,
+}
+
+impl Liveness {
+    /// Backward dataflow to a fixed point. `AddressOf` counts as a read
+    /// of the local; writes through the resulting pointer are invisible
+    /// here, so callers should treat `Function::address_taken_locals`
+    /// as live everywhere.
+    pub fn compute(func: &Function, cfg: &ControlFlowGraph) -> Self {
+        let n = func.blocks.len();
+        let mut uses = vec![HashSet::new(); n];
+        let mut defs = vec![HashSet::new(); n];
+        for block in &func.blocks {
+            let idx = block.id.0 as usize;
+            for inst in &block.instructions {
+                for local in local_reads(&inst.kind) {
+                    if !defs[idx].contains(&local) {
+                        uses[idx].insert(local);
+                    }
+                }
+                for local in local_writes(&inst.kind) {
+                    defs[idx].insert(local);
+                }
+            }
+        }
+
+        let mut live_in: Vec<HashSet<LocalId>> = vec![HashSet::new(); n];
+        let mut live_out: Vec<HashSet<LocalId>> = vec![HashSet::new(); n];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            // Postorder visits successors first, so most blocks settle
+            // in one sweep.
+            for &block in cfg.reverse_postorder().iter().rev() {
+                let idx = block.0 as usize;
+                let mut out = HashSet::new();
+                for succ in cfg.successors(block) {
+                    out.extend(live_in[succ.0 as usize].iter().copied());
+                }
+                let mut inn: HashSet<LocalId> = out.difference(&defs[idx]).copied().collect();
+                inn.extend(uses[idx].iter().copied());
+                if out != live_out[idx] || inn != live_in[idx] {
+                    live_out[idx] = out;
+                    live_in[idx] = inn;
+                    changed = true;
+                }
+            }
+        }
+        Self { live_in, live_out }
+    }
+
+    pub fn live_in(&self, block: BlockId) -> &HashSet<LocalId> {
+        &self.live_in[block.0 as usize]
+    }
+
+    pub fn live_out(&self, block: BlockId) -> &HashSet<LocalId> {
+        &self.live_out[block.0 as usize]
+    }
+}
+
+fn local_reads(kind: &InstKind) -> Vec<LocalId> {
+    match kind {
+        InstKind::LoadLocal(local) => vec![*local],
+        InstKind::AddressOf { local } => vec![*local],
+        _ => Vec::new(),
+    }
+}
+
+fn local_writes(kind: &InstKind) -> Vec<LocalId> {
+    match kind {
+        InstKind::StoreLocal { dst, .. } => vec![*dst],
+        InstKind::CallStruct { dests, .. }
+        | InstKind::CallTuple { dests, .. }
+        | InstKind::CallEnum { dests, .. } => dests.clone(),
+        InstKind::CallWithSelfWriteback { ret_dest, self_dests, .. } => {
+            ret_dest.iter().chain(self_dests.iter()).copied().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Const, FuncId, Instruction, Linkage, Module, Type, ValueId};
+
+    /// ```text
+    /// b0: v0 = load l0; branch v0, b1, b2
+    /// b1: store l1 <- v0; jump b3
+    /// b2: store l1 <- v0; jump b3
+    /// b3: jump b4
+    /// b4: v1 = load l1; branch v1, b5, b6
+    /// b5: jump b4
+    /// b6: ret
+    /// b7: v2 = true; jump b3      (unreachable)
+    /// ```
+    fn sample() -> (Module, FuncId) {
+        let mut module = Module::new();
+        let id = module.declare_function_anon("f".to_string(), Linkage::Local, vec![Type::Bool], Type::Unit);
+        let func = module.function_mut(id);
+        let l0 = func.add_local(Type::Bool);
+        let l1 = func.add_local(Type::Bool);
+        let b: Vec<BlockId> = (0..8).map(|_| func.add_block()).collect();
+        let load = |local, v| Instruction { result: Some((ValueId(v), Type::Bool)), kind: InstKind::LoadLocal(local) };
+        let store = |dst, v| Instruction { result: None, kind: InstKind::StoreLocal { dst, src: ValueId(v) } };
+
+        func.block_mut(b[0]).instructions.push(load(l0, 0));
+        func.block_mut(b[0]).terminator = Some(Terminator::Branch { cond: ValueId(0), then_blk: b[1], else_blk: b[2] });
+        func.block_mut(b[1]).instructions.push(store(l1, 0));
+        func.block_mut(b[1]).terminator = Some(Terminator::Jump(b[3]));
+        func.block_mut(b[2]).instructions.push(store(l1, 0));
+        func.block_mut(b[2]).terminator = Some(Terminator::Jump(b[3]));
+        func.block_mut(b[3]).terminator = Some(Terminator::Jump(b[4]));
+        func.block_mut(b[4]).instructions.push(load(l1, 1));
+        func.block_mut(b[4]).terminator = Some(Terminator::Branch { cond: ValueId(1), then_blk: b[5], else_blk: b[6] });
+        func.block_mut(b[5]).terminator = Some(Terminator::Jump(b[4]));
+        func.block_mut(b[6]).terminator = Some(Terminator::Return(Vec::new()));
+        func.block_mut(b[7]).instructions.push(Instruction {
+            result: Some((ValueId(2), Type::Bool)),
+            kind: InstKind::Const(Const::Bool(true)),
+        });
+        func.block_mut(b[7]).terminator = Some(Terminator::Jump(b[3]));
+        (module, id)
+    }
+
+    #[test]
+    fn edges_and_reachability() {
+        let (module, id) = sample();
+        let cfg = ControlFlowGraph::new(module.function(id));
+        assert_eq!(cfg.successors(BlockId(0)), &[BlockId(1), BlockId(2)]);
+        assert_eq!(cfg.predecessors(BlockId(3)), &[BlockId(1), BlockId(2), BlockId(7)]);
+        assert_eq!(cfg.predecessors(BlockId(4)), &[BlockId(3), BlockId(5)]);
+        assert_eq!(cfg.unreachable_blocks(), vec![BlockId(7)]);
+        assert_eq!(cfg.reverse_postorder().first(), Some(&BlockId(0)));
+        assert_eq!(cfg.reverse_postorder().len(), 7);
+    }
+
+    #[test]
+    fn dominators_ignore_unreachable_predecessors() {
+        let (module, id) = sample();
+        let cfg = ControlFlowGraph::new(module.function(id));
+        let doms = Dominators::compute(&cfg);
+        assert_eq!(doms.idom(BlockId(0)), None);
+        assert_eq!(doms.idom(BlockId(1)), Some(BlockId(0)));
+        // The join has two live predecessors, so only the entry dominates it;
+        // the dead edge from b7 must not change that.
+        assert_eq!(doms.idom(BlockId(3)), Some(BlockId(0)));
+        assert_eq!(doms.idom(BlockId(5)), Some(BlockId(4)));
+        assert!(doms.dominates(BlockId(4), BlockId(6)));
+        assert!(!doms.dominates(BlockId(1), BlockId(3)));
+        assert!(!doms.dominates(BlockId(0), BlockId(7)));
+    }
+
+    #[test]
+    fn liveness_follows_the_loop() {
+        let (module, id) = sample();
+        let func = module.function(id);
+        let cfg = ControlFlowGraph::new(func);
+        let live = Liveness::compute(func, &cfg);
+        let set = |locals: &[u32]| locals.iter().map(|l| LocalId(*l)).collect::<HashSet<_>>();
+        assert_eq!(live.live_in(BlockId(0)), &set(&[0]));
+        // Both arms overwrite l1 before the join reads it.
+        assert_eq!(live.live_in(BlockId(1)), &set(&[]));
+        assert_eq!(live.live_out(BlockId(1)), &set(&[1]));
+        // The back edge keeps l1 live around the loop.
+        assert_eq!(live.live_out(BlockId(5)), &set(&[1]));
+        assert_eq!(live.live_out(BlockId(6)), &set(&[]));
+    }
+}
@@ -0,0 +1,314 @@
+//! Global value numbering over the compiler IR.
+//!
+//! Lowering re-evaluates every source expression, so `a * b + a * b`
+//! becomes two `LoadLocal`s and a `mul` per operand pair. This pass
+//! gives each pure instruction (`InstKind::is_pure`) a key built from
+//! its kind, its (already numbered) operands and its result type, and
+//! replaces an instruction with an earlier one of the same key when
+//! the earlier one's block dominates it. Commutative operators sort
+//! their operands first, so `a * b` and `b * a` share a key.
+//!
+//! Local reads join in three ways, none of which apply to
+//! address-taken locals:
+//!
+//! - a local nothing writes (parameters, mostly) is keyed like a pure
+//!   instruction, so every read of it shares one value;
+//! - a local with exactly one `StoreLocal` is read as the stored value
+//!   wherever that store dominates the read;
+//! - inside a block, a read after a store or another read of the same
+//!   local, with no write in between, reuses that value.
+//!
+//! Codegen walks blocks in vector order, so a replacement must come
+//! from a block that is no later in the vector than its use; blocks
+//! that would need one are left alone. Like the inliner, the pass runs
+//! only when optimisation is on.
+
+use std::collections::{HashMap, HashSet};
+
+use string_interner::DefaultSymbol;
+
+use crate::flow::{ControlFlowGraph, Dominators};
+use crate::ir::{
+    BinOp, BlockId, Const, FuncId, Function, InstKind, LocalId, Module, Type, UnaryOp, ValueId,
+};
+
+/// Number every function of `module`. Returns how many instructions
+/// were removed.
+pub fn number_values(module: &mut Module) -> usize {
+    module.functions.iter_mut().map(number_function).sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    /// Constant, by bit pattern (the result type tells widths apart).
+    Const(u64),
+    BinOp(BinOp, ValueId, ValueId),
+    UnaryOp(UnaryOp, ValueId),
+    Cast(ValueId, Type, Type),
+    Str(DefaultSymbol),
+    StrBytes(Vec<u8>),
+    StrLen(ValueId),
+    PtrIsNull(ValueId),
+    PtrEq(ValueId, ValueId),
+    FuncAddr(FuncId),
+    /// Read of a local nothing writes.
+    Load(LocalId),
+}
+
+fn key_of(kind: &InstKind) -> Option<Key> {
+    Some(match kind {
+        InstKind::Const(c) => Key::Const(const_bits(*c)),
+        InstKind::BinOp { op, lhs, rhs } => {
+            let (lhs, rhs) = if is_commutative(*op) { ordered(*lhs, *rhs) } else { (*lhs, *rhs) };
+            Key::BinOp(*op, lhs, rhs)
+        }
+        InstKind::UnaryOp { op, operand } => Key::UnaryOp(*op, *operand),
+        InstKind::Cast { value, from, to } => Key::Cast(*value, *from, *to),
+        InstKind::ConstStr { message, .. } => Key::Str(*message),
+        InstKind::ConstStrBytes { bytes } => Key::StrBytes(bytes.clone()),
+        InstKind::StrLen { value } => Key::StrLen(*value),
+        InstKind::PtrIsNull { ptr } => Key::PtrIsNull(*ptr),
+        InstKind::PtrEq { a, b } => {
+            let (a, b) = ordered(*a, *b);
+            Key::PtrEq(a, b)
+        }
+        InstKind::FuncAddr { target } => Key::FuncAddr(*target),
+        _ => return None,
+    })
+}
+
+fn ordered(a: ValueId, b: ValueId) -> (ValueId, ValueId) {
+    if b.0 < a.0 { (b, a) } else { (a, b) }
+}
+
+fn const_bits(c: Const) -> u64 {
+    match c {
+        Const::I64(v) => v as u64,
+        Const::U64(v) => v,
+        Const::I8(v) => v as u64,
+        Const::U8(v) => v as u64,
+        Const::I16(v) => v as u64,
+        Const::U16(v) => v as u64,
+        Const::I32(v) => v as u64,
+        Const::U32(v) => v as u64,
+        Const::F64(v) => v.to_bits(),
+        Const::Bool(v) => v as u64,
+    }
+}
+
+fn is_commutative(op: BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Add
+            | BinOp::Mul
+            | BinOp::Eq
+            | BinOp::Ne
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::BitXor
+            | BinOp::Min
+            | BinOp::Max
+    )
+}
+
+/// How a local is written across the whole function.
+enum Writes {
+    None,
+    /// Exactly one `StoreLocal`: its block, its index and the value.
+    Once(BlockId, usize, ValueId),
+    Many,
+}
+
+fn local_writes(func: &Function) -> HashMap<LocalId, Writes> {
+    let mut writes: HashMap<LocalId, Writes> = HashMap::new();
+    for block in &func.blocks {
+        for (idx, inst) in block.instructions.iter().enumerate() {
+            if matches!(inst.kind, InstKind::LoadLocal(_)) {
+                continue;
+            }
+            let store = match inst.kind {
+                InstKind::StoreLocal { dst, src } => Some((dst, src)),
+                _ => None,
+            };
+            inst.kind.clone().for_each_local_mut(|local| {
+                let entry = writes.entry(*local).or_insert(Writes::None);
+                *entry = match (&entry, store) {
+                    (Writes::None, Some((dst, src))) if dst == *local => Writes::Once(block.id, idx, src),
+                    _ => Writes::Many,
+                };
+            });
+        }
+    }
+    writes
+}
+
+fn number_function(func: &mut Function) -> usize {
+    if func.blocks.is_empty() {
+        return 0;
+    }
+    let cfg = ControlFlowGraph::new(func);
+    let doms = Dominators::compute(&cfg);
+    let writes = local_writes(func);
+    let value_types: HashMap<ValueId, Type> =
+        func.blocks.iter().flat_map(|b| &b.instructions).filter_map(|i| i.result).collect();
+    let address_taken: HashSet<LocalId> = func.address_taken_locals.clone();
+
+    let mut replace: HashMap<ValueId, ValueId> = HashMap::new();
+    // Block each surviving value is defined in.
+    let mut def_block: HashMap<ValueId, BlockId> = HashMap::new();
+    let mut table: HashMap<(Key, Type), Vec<(BlockId, ValueId)>> = HashMap::new();
+    let mut removed = 0;
+
+    for &block_id in cfg.reverse_postorder() {
+        let block = &mut func.blocks[block_id.0 as usize];
+        let insts = std::mem::take(&mut block.instructions);
+        let mut known: HashMap<LocalId, ValueId> = HashMap::new();
+        for (idx, mut inst) in insts.into_iter().enumerate() {
+            inst.kind.for_each_value_mut(|v| *v = *replace.get(v).unwrap_or(v));
+            let Some((result, ty)) = inst.result else {
+                forget_written_locals(&inst.kind, &mut known);
+                block.instructions.push(inst);
+                continue;
+            };
+            // Whether a value from block `b` can be used here: `b`
+            // dominates this block and codegen visits it first.
+            let visible = |b: BlockId| b.0 <= block_id.0 && doms.dominates(b, block_id);
+            let usable = |v: ValueId| {
+                value_types.get(&v).is_none_or(|t| *t == ty)
+                    && def_block.get(&v).is_none_or(|b| visible(*b))
+            };
+
+            let mut key = key_of(&inst.kind);
+            if let InstKind::LoadLocal(local) = inst.kind
+                && !address_taken.contains(&local)
+            {
+                let stored = match writes.get(&local) {
+                    Some(Writes::Once(b, i, src)) if visible(*b) && (*b != block_id || *i < idx) => {
+                        Some(*replace.get(src).unwrap_or(src))
+                    }
+                    _ => None,
+                };
+                let forwarded = known.get(&local).copied().or(stored).filter(|v| usable(*v));
+                if let Some(v) = forwarded {
+                    replace.insert(result, v);
+                    known.insert(local, v);
+                    removed += 1;
+                    continue;
+                }
+                if matches!(writes.get(&local), None | Some(Writes::None)) {
+                    key = Some(Key::Load(local));
+                }
+            }
+
+            if let Some(key) = key {
+                let seen = table.entry((key, ty)).or_default();
+                if let Some(&(_, v)) = seen.iter().find(|(b, _)| visible(*b)) {
+                    replace.insert(result, v);
+                    if let InstKind::LoadLocal(local) = inst.kind {
+                        known.insert(local, v);
+                    }
+                    removed += 1;
+                    continue;
+                }
+                seen.push((block_id, result));
+            }
+            if let InstKind::LoadLocal(local) = inst.kind {
+                known.insert(local, result);
+            }
+            def_block.insert(result, block_id);
+            forget_written_locals(&inst.kind, &mut known);
+            block.instructions.push(inst);
+        }
+    }
+
+    // Terminators, and any block the walk above did not reach.
+    for block in &mut func.blocks {
+        for inst in &mut block.instructions {
+            inst.kind.for_each_value_mut(|v| *v = *replace.get(v).unwrap_or(v));
+        }
+        if let Some(term) = &mut block.terminator {
+            term.for_each_value_mut(|v| *v = *replace.get(v).unwrap_or(v));
+        }
+    }
+    removed
+}
+
+/// Drop in-block knowledge of every local `kind` may write, then
+/// record the value a `StoreLocal` leaves behind.
+fn forget_written_locals(kind: &InstKind, known: &mut HashMap<LocalId, ValueId>) {
+    match kind {
+        InstKind::LoadLocal(_) => {}
+        InstKind::StoreLocal { dst, src } => {
+            known.insert(*dst, *src);
+        }
+        _ => kind.clone().for_each_local_mut(|local| {
+            known.remove(local);
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Instruction, Linkage, Terminator};
+
+    fn inst(result: Option<(u32, Type)>, kind: InstKind) -> Instruction {
+        Instruction { result: result.map(|(v, t)| (ValueId(v), t)), kind }
+    }
+
+    /// `fn f(a: u64, b: u64) -> u64 { a * b + a * b }`, as lowering
+    /// emits it.
+    fn square_sum() -> (Module, FuncId) {
+        let mut module = Module::new();
+        let id = module.declare_function_anon("f".to_string(), Linkage::Local, vec![Type::U64; 2], Type::U64);
+        let func = module.function_mut(id);
+        let a = func.add_local(Type::U64);
+        let b = func.add_local(Type::U64);
+        let entry = func.add_block();
+        let load = |v, local| inst(Some((v, Type::U64)), InstKind::LoadLocal(local));
+        let binop = |v, op, lhs, rhs| {
+            inst(Some((v, Type::U64)), InstKind::BinOp { op, lhs: ValueId(lhs), rhs: ValueId(rhs) })
+        };
+        func.block_mut(entry).instructions = vec![
+            load(0, a),
+            load(1, b),
+            binop(2, BinOp::Mul, 0, 1),
+            load(3, b),
+            load(4, a),
+            binop(5, BinOp::Mul, 3, 4),
+            binop(6, BinOp::Add, 2, 5),
+        ];
+        func.block_mut(entry).terminator = Some(Terminator::Return(vec![ValueId(6)]));
+        (module, id)
+    }
+
+    fn mul_count(module: &Module, id: FuncId) -> usize {
+        module.function(id).blocks[0]
+            .instructions
+            .iter()
+            .filter(|i| matches!(i.kind, InstKind::BinOp { op: BinOp::Mul, .. }))
+            .count()
+    }
+
+    #[test]
+    fn repeated_product_is_computed_once() {
+        let (mut module, id) = square_sum();
+        assert_eq!(number_values(&mut module), 3);
+        assert_eq!(mul_count(&module, id), 1);
+        let last = module.function(id).blocks[0].instructions.last().unwrap();
+        let InstKind::BinOp { lhs, rhs, .. } = last.kind else {
+            panic!("expected the add last");
+        };
+        assert_eq!((lhs, rhs), (ValueId(2), ValueId(2)));
+    }
+
+    #[test]
+    fn store_between_reads_blocks_reuse() {
+        let (mut module, id) = square_sum();
+        // `a = a * b` before the second product: loads of `a` must stay.
+        let store = inst(None, InstKind::StoreLocal { dst: LocalId(0), src: ValueId(2) });
+        module.function_mut(id).blocks[0].instructions.insert(3, store);
+        number_values(&mut module);
+        assert_eq!(mul_count(&module, id), 2);
+    }
+}
@@ -0,0 +1,241 @@
+//! IR-level inlining of small functions.
+//!
+//! Cranelift does not inline, so without this pass every accessor
+//! method and one-line helper stays a real call. It runs between
+//! lowering and codegen whenever optimisation is on (everything but
+//! `-O0` / `TOYLANG_CRANELIFT_OPT_LEVEL=none`).
+//!
+//! Only direct `InstKind::Call`s are inlined. The callee must be a
+//! `Linkage::Local` function with a body that returns a scalar or
+//! `Unit`, and must not use `&mut self` writeback, array slots or
+//! address-taken locals (those need per-function stack slots). On top
+//! of that, `@noinline` callees are never inlined, `@inline` callees
+//! are inlined whatever their size, and the rest only when they have at
+//! most `AUTO_INLINE_MAX_INSTS` instructions.
+//!
+//! The caller's block is split at the call:
+//!
+//! ```text
+//! pre:   ...; store callee params <- args; jump entry'
+//! callee blocks' (locals, values and blocks renumbered;
+//!                 `return v` becomes `store ret <- v; jump cont`)
+//! cont:  result = load ret; ...rest of the original block...
+//! ```
+//!
+//! The copies are placed right after the block they were split from.
+//! Codegen walks blocks in vector order and needs every value defined
+//! before a later block uses it, which this order preserves.
+//!
+//! Inlining is one level deep per call site. Functions are visited in
+//! id order, so a callee visited earlier already has its own small
+//! calls inlined when it is copied.
+
+use std::collections::HashMap;
+
+use frontend::ast::InlineHint;
+
+use crate::ir::{
+    Block, BlockId, FuncId, Function, InstKind, Instruction, Linkage, LocalId, Module, Terminator,
+    Type,
+};
+
+/// Size limit, in IR instructions, for callees without `@inline`.
+pub const AUTO_INLINE_MAX_INSTS: usize = 16;
+
+/// Inline eligible direct calls in every function of `module`. Returns
+/// the number of call sites inlined.
+pub fn inline_module(module: &mut Module) -> usize {
+    let mut inlined = 0;
+    for caller in 0..module.functions.len() {
+        inlined += inline_calls_in(module, FuncId(caller as u32));
+    }
+    inlined
+}
+
+/// The parts of a callee that get copied into each caller.
+struct CalleeBody {
+    locals: Vec<Type>,
+    blocks: Vec<(Vec<Instruction>, Terminator)>,
+    entry: BlockId,
+    /// One past the largest `ValueId` the callee defines or uses.
+    value_count: u32,
+    /// Whether the callee's `Return`s carry exactly one value.
+    returns_value: bool,
+}
+
+fn callee_body(callee: &Function) -> Option<CalleeBody> {
+    if callee.linkage != Linkage::Local
+        || callee.blocks.is_empty()
+        || callee.inline == Some(InlineHint::Never)
+        || !callee.self_writeback_types.is_empty()
+        || !callee.array_slots.is_empty()
+        || !callee.address_taken_locals.is_empty()
+        || matches!(callee.return_type, Type::Struct(_) | Type::Tuple(_) | Type::Enum(_))
+    {
+        return None;
+    }
+    let size: usize = callee.blocks.iter().map(|b| b.instructions.len()).sum();
+    if callee.inline != Some(InlineHint::Always) && size > AUTO_INLINE_MAX_INSTS {
+        return None;
+    }
+    let mut blocks = Vec::with_capacity(callee.blocks.len());
+    let mut return_arity = None;
+    for block in &callee.blocks {
+        let term = block.terminator.clone()?;
+        if let Terminator::Return(values) = &term {
+            // Mixed arities would leave the result local unset on
+            // some paths; lowering never produces them, but don't guess.
+            if *return_arity.get_or_insert(values.len()) != values.len() {
+                return None;
+            }
+        }
+        blocks.push((block.instructions.clone(), term));
+    }
+    // A callee that never returns would leave the continuation block
+    // without predecessors; keep it as a call.
+    let arity = return_arity?;
+    if arity > 1 {
+        return None;
+    }
+    let mut value_count = 0;
+    for (insts, term) in &mut blocks {
+        for inst in insts.iter_mut() {
+            if let Some((v, _)) = inst.result {
+                value_count = value_count.max(v.0 + 1);
+            }
+            inst.kind.for_each_value_mut(|v| value_count = value_count.max(v.0 + 1));
+        }
+        term.for_each_value_mut(|v| value_count = value_count.max(v.0 + 1));
+    }
+    Some(CalleeBody {
+        locals: callee.locals.clone(),
+        blocks,
+        entry: callee.entry,
+        value_count,
+        returns_value: arity == 1,
+    })
+}
+
+fn inline_calls_in(module: &mut Module, caller_id: FuncId) -> usize {
+    if module.function(caller_id).blocks.is_empty() {
+        return 0;
+    }
+    let caller_block_count = module.function(caller_id).blocks.len();
+    let old_blocks = std::mem::take(&mut module.function_mut(caller_id).blocks);
+
+    let mut next_value = 0;
+    for block in &old_blocks {
+        for inst in &block.instructions {
+            if let Some((v, _)) = inst.result {
+                next_value = next_value.max(v.0 + 1);
+            }
+        }
+    }
+
+    let local_base = module.function(caller_id).locals.len();
+    let mut new_locals: Vec<Type> = Vec::new();
+    let mut bodies: HashMap<FuncId, Option<CalleeBody>> = HashMap::new();
+    // Blocks are collected with temporary ids (original ids first, new
+    // blocks numbered after them) and renumbered by position at the end.
+    let mut next_block = caller_block_count as u32;
+    let mut out: Vec<Block> = Vec::with_capacity(caller_block_count);
+    let mut inlined = 0;
+
+    for block in old_blocks {
+        let mut current = Block { id: block.id, instructions: Vec::new(), terminator: None };
+        for inst in block.instructions {
+            let (target, args) = match &inst.kind {
+                InstKind::Call { target, args } if *target != caller_id => (*target, args),
+                _ => {
+                    current.instructions.push(inst);
+                    continue;
+                }
+            };
+            let body = bodies
+                .entry(target)
+                .or_insert_with(|| callee_body(module.function(target)))
+                .as_ref();
+            let Some(body) = body.filter(|b| inst.result.is_none() || b.returns_value) else {
+                current.instructions.push(inst);
+                continue;
+            };
+
+            let param_base = (local_base + new_locals.len()) as u32;
+            new_locals.extend(body.locals.iter().copied());
+            for (i, arg) in args.iter().enumerate() {
+                current.instructions.push(Instruction {
+                    result: None,
+                    kind: InstKind::StoreLocal { dst: LocalId(param_base + i as u32), src: *arg },
+                });
+            }
+            let ret_local = inst.result.map(|(_, ty)| {
+                new_locals.push(ty);
+                LocalId((local_base + new_locals.len() - 1) as u32)
+            });
+            let value_base = next_value;
+            next_value += body.value_count;
+            let block_base = next_block;
+            next_block += body.blocks.len() as u32;
+            let cont = BlockId(next_block);
+            next_block += 1;
+
+            current.terminator = Some(Terminator::Jump(BlockId(block_base + body.entry.0)));
+            out.push(current);
+
+            for (i, (insts, term)) in body.blocks.iter().enumerate() {
+                let mut copy = Block {
+                    id: BlockId(block_base + i as u32),
+                    instructions: Vec::with_capacity(insts.len() + 1),
+                    terminator: None,
+                };
+                for inst in insts {
+                    let mut inst = inst.clone();
+                    if let Some((v, _)) = &mut inst.result {
+                        v.0 += value_base;
+                    }
+                    inst.kind.for_each_value_mut(|v| v.0 += value_base);
+                    inst.kind.for_each_local_mut(|l| l.0 += param_base);
+                    copy.instructions.push(inst);
+                }
+                let mut term = term.clone();
+                term.for_each_value_mut(|v| v.0 += value_base);
+                term.for_each_block_mut(|b| b.0 += block_base);
+                if let Terminator::Return(values) = term {
+                    if let (Some(dst), Some(src)) = (ret_local, values.first()) {
+                        copy.instructions.push(Instruction {
+                            result: None,
+                            kind: InstKind::StoreLocal { dst, src: *src },
+                        });
+                    }
+                    term = Terminator::Jump(cont);
+                }
+                copy.terminator = Some(term);
+                out.push(copy);
+            }
+
+            current = Block { id: cont, instructions: Vec::new(), terminator: None };
+            if let Some(local) = ret_local {
+                current.instructions.push(Instruction { result: inst.result, kind: InstKind::LoadLocal(local) });
+            }
+            inlined += 1;
+        }
+        current.terminator = block.terminator;
+        out.push(current);
+    }
+
+    let mut position = vec![0u32; next_block as usize];
+    for (pos, block) in out.iter().enumerate() {
+        position[block.id.0 as usize] = pos as u32;
+    }
+    for block in &mut out {
+        block.id = BlockId(position[block.id.0 as usize]);
+        if let Some(term) = &mut block.terminator {
+            term.for_each_block_mut(|b| *b = BlockId(position[b.0 as usize]));
+        }
+    }
+    let caller = module.function_mut(caller_id);
+    caller.entry = BlockId(position[caller.entry.0 as usize]);
+    caller.locals.extend(new_locals);
+    caller.blocks = out;
+    inlined
+}
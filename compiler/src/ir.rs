@@ -184,6 +184,7 @@ impl Module {
             address_taken_locals: std::collections::HashSet::new(),
             blocks: Vec::new(),
             entry: BlockId(0),
+            inline: None,
         });
         let key = (module_qualifier, symbol);
         if let Some(prev) = self.function_index.insert(key, id) {
@@ -228,6 +229,7 @@ impl Module {
             address_taken_locals: std::collections::HashSet::new(),
             blocks: Vec::new(),
             entry: BlockId(0),
+            inline: None,
         });
         id
     }
@@ -398,6 +400,9 @@ pub struct Function {
     pub address_taken_locals: std::collections::HashSet<LocalId>,
     pub blocks: Vec<Block>,
     pub entry: BlockId,
+    /// `@inline` / `@noinline` from the source, read by `inline.rs`.
+    /// `None` lets the size heuristic decide.
+    pub inline: Option<frontend::ast::InlineHint>,
 }
 
 /// One stack-allocated array. Holds the element type, length, and
@@ -905,6 +910,120 @@ pub enum InstKind {
     },
 }
 
+impl InstKind {
+    /// Visit every `ValueId` operand (not the instruction's result).
+    /// Used by passes that copy instructions between functions.
+    pub fn for_each_value_mut(&mut self, mut f: impl FnMut(&mut ValueId)) {
+        match self {
+            InstKind::Const(_)
+            | InstKind::LoadLocal(_)
+            | InstKind::PrintStr { .. }
+            | InstKind::ConstStr { .. }
+            | InstKind::ConstStrBytes { .. }
+            | InstKind::PrintRaw { .. }
+            | InstKind::AllocPop
+            | InstKind::AllocCurrent
+            | InstKind::AddressOf { .. }
+            | InstKind::FuncAddr { .. } => {}
+            InstKind::BinOp { lhs, rhs, .. } => {
+                f(lhs);
+                f(rhs);
+            }
+            InstKind::UnaryOp { operand, .. } => f(operand),
+            InstKind::StoreLocal { src, .. } => f(src),
+            InstKind::Call { args, .. }
+            | InstKind::CallStruct { args, .. }
+            | InstKind::CallTuple { args, .. }
+            | InstKind::CallEnum { args, .. }
+            | InstKind::CallWithSelfWriteback { args, .. } => args.iter_mut().for_each(f),
+            InstKind::Cast { value, .. }
+            | InstKind::Print { value, .. }
+            | InstKind::StrLen { value }
+            | InstKind::ToString { value, .. } => f(value),
+            InstKind::ArrayLoad { index, .. } | InstKind::ArrayElemAddr { index, .. } => f(index),
+            InstKind::ArrayStore { index, value, .. } => {
+                f(index);
+                f(value);
+            }
+            InstKind::HeapAlloc { size, .. } => f(size),
+            InstKind::HeapRealloc { ptr, new_size, .. } => {
+                f(ptr);
+                f(new_size);
+            }
+            InstKind::HeapFree { ptr, .. }
+            | InstKind::PtrIsNull { ptr }
+            | InstKind::LoadRef { ptr, .. } => f(ptr),
+            InstKind::PtrRead { ptr, offset, .. } => {
+                f(ptr);
+                f(offset);
+            }
+            InstKind::PtrWrite { ptr, offset, value, .. } => {
+                f(ptr);
+                f(offset);
+                f(value);
+            }
+            InstKind::StoreRef { ptr, value, .. } => {
+                f(ptr);
+                f(value);
+            }
+            InstKind::StrConcat { a, b } | InstKind::PtrEq { a, b } => {
+                f(a);
+                f(b);
+            }
+            InstKind::MemCopy { src, dest, size } => {
+                f(src);
+                f(dest);
+                f(size);
+            }
+            InstKind::AllocPush { handle } => f(handle),
+            InstKind::CallIndirect { callee, args, .. } => {
+                f(callee);
+                args.iter_mut().for_each(f);
+            }
+            InstKind::MakeClosure { captures, .. } => captures.iter_mut().for_each(f),
+        }
+    }
+
+    /// Visit every `LocalId` the instruction reads or writes.
+    pub fn for_each_local_mut(&mut self, mut f: impl FnMut(&mut LocalId)) {
+        match self {
+            InstKind::LoadLocal(local)
+            | InstKind::StoreLocal { dst: local, .. }
+            | InstKind::AddressOf { local } => f(local),
+            InstKind::CallStruct { dests, .. }
+            | InstKind::CallTuple { dests, .. }
+            | InstKind::CallEnum { dests, .. } => dests.iter_mut().for_each(f),
+            InstKind::CallWithSelfWriteback { ret_dest, self_dests, .. } => {
+                ret_dest.iter_mut().for_each(&mut f);
+                self_dests.iter_mut().for_each(f);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the instruction's result depends only on its operands:
+    /// no memory or local reads, no side effects, no fresh allocation.
+    /// Two pure instructions of the same kind with the same operands
+    /// can share one result (see `gvn`). `Div` / `Rem` may trap on a
+    /// zero divisor but still count: a dominating copy traps first.
+    /// `StrLen` reads memory, but str blobs are immutable.
+    pub fn is_pure(&self) -> bool {
+        matches!(
+            self,
+            InstKind::Const(_)
+                | InstKind::BinOp { .. }
+                | InstKind::UnaryOp { .. }
+                | InstKind::Cast { .. }
+                | InstKind::ConstStr { .. }
+                | InstKind::ConstStrBytes { .. }
+                | InstKind::StrLen { .. }
+                | InstKind::PtrIsNull { .. }
+                | InstKind::PtrEq { .. }
+                | InstKind::FuncAddr { .. }
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Const {
     I64(i64),
@@ -942,7 +1061,7 @@ impl Const {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BinOp {
     // Integer arithmetic. Division and modulo dispatch to signed or
     // unsigned variants based on the operand type during codegen.
@@ -989,7 +1108,7 @@ impl BinOp {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UnaryOp {
     /// Two's complement integer negation.
     Neg,
@@ -1040,6 +1159,27 @@ pub enum Terminator {
     Unreachable,
 }
 
+impl Terminator {
+    pub fn for_each_value_mut(&mut self, mut f: impl FnMut(&mut ValueId)) {
+        match self {
+            Terminator::Return(values) => values.iter_mut().for_each(f),
+            Terminator::Branch { cond, .. } => f(cond),
+            Terminator::Jump(_) | Terminator::Panic { .. } | Terminator::Unreachable => {}
+        }
+    }
+
+    pub fn for_each_block_mut(&mut self, mut f: impl FnMut(&mut BlockId)) {
+        match self {
+            Terminator::Jump(target) => f(target),
+            Terminator::Branch { then_blk, else_blk, .. } => {
+                f(then_blk);
+                f(else_blk);
+            }
+            Terminator::Return(_) | Terminator::Panic { .. } | Terminator::Unreachable => {}
+        }
+    }
+}
+
 // -------------------------------------------------------------------------
 // IDs. Each is a transparent newtype around a `u32`. They are deliberately
 // distinct types so the type system catches mix-ups (e.g. passing a Block
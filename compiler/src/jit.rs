@@ -34,7 +34,6 @@ use string_interner::DefaultStringInterner;
 
 use crate::codegen::CodegenSession;
 use crate::ir::{FuncId, Linkage};
-use crate::lower;
 use crate::{CompilerOptions, EmitKind, ContractMessages};
 
 /// Pointer to the JIT-compiled `main`. Same calling convention as
@@ -181,8 +180,13 @@ pub fn compile_to_jit_main(source: &str) -> Result<JitProgram, String> {
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
         link_cache_dir: None,
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
     };
     compile_to_jit_main_with_options(source, &options)
 }
@@ -203,12 +207,15 @@ pub fn compile_to_jit_main_with_options(
 
     let core_modules_dir =
         crate::resolve_core_modules_dir(options.core_modules_dir.clone());
-    interpreter::check_typing_with_core_modules(
+    let mut cfg = options.defines.clone();
+    cfg.define("target", frontend::cfg::TARGET_NATIVE);
+    interpreter::check_typing_with_cfg(
         &mut program,
         session.string_interner_mut(),
         Some(source),
         None,
         core_modules_dir.as_deref(),
+        &cfg,
     )
     .map_err(|errors| format!("type-check failed:\n  {}", errors.join("\n  ")))?;
 
@@ -226,7 +233,7 @@ fn compile_program_to_jit(
     options: &CompilerOptions,
 ) -> Result<JitProgram, String> {
     let ir_module =
-        lower::lower_program(program, interner, contract_msgs, options.release)?;
+        crate::codegen::lower_and_optimize(program, interner, contract_msgs, options)?;
 
     // Build the JIT module. `cranelift_native::builder()` selects
     // the host ISA the same way `make_object_module` does, but
@@ -234,7 +241,7 @@ fn compile_program_to_jit(
     // code lives in JIT-allocated memory the runtime addresses
     // absolutely.
     let mut jit_builder =
-        JITBuilder::with_flags(&[("opt_level", crate::codegen::cranelift_opt_level())], cranelift_module::default_libcall_names())
+        JITBuilder::with_flags(&[("opt_level", crate::codegen::cranelift_opt_level(options))], cranelift_module::default_libcall_names())
             .map_err(|e| format!("JITBuilder: {e}"))?;
     register_runtime_symbols(&mut jit_builder);
     let module = JITModule::new(jit_builder);
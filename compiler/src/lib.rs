@@ -28,13 +28,17 @@
 
 pub mod codegen;
 pub mod driver;
+pub mod flow;
+pub mod gvn;
+pub mod inline;
 pub mod ir;
 pub mod jit;
 pub mod lower;
+pub mod metrics;
 pub mod options;
 
 pub use jit::{compile_to_jit_main, compile_to_jit_main_with_options, JitMainFn, JitProgram};
-pub use options::{CompilerOptions, EmitKind};
+pub use options::{CompilerOptions, EmitKind, OptLevel};
 
 use std::path::Path;
 
@@ -46,6 +50,8 @@ use std::path::Path;
 pub struct ContractMessages {
     pub requires_violation: string_interner::DefaultSymbol,
     pub ensures_violation: string_interner::DefaultSymbol,
+    /// `--bounds-check` guard failure.
+    pub index_out_of_bounds: string_interner::DefaultSymbol,
 }
 
 impl ContractMessages {
@@ -53,6 +59,7 @@ impl ContractMessages {
         Self {
             requires_violation: interner.get_or_intern("requires violation"),
             ensures_violation: interner.get_or_intern("ensures violation"),
+            index_out_of_bounds: interner.get_or_intern("array index out of bounds"),
         }
     }
 }
@@ -61,7 +68,23 @@ impl ContractMessages {
 /// Returns `Ok(())` after writing whichever artefact `options.emit`
 /// requested. Errors are stringified for display.
 pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
-    let source = std::fs::read_to_string(&options.input).map_err(|e| {
+    // Linking goes through the host C compiler and the host-built
+    // runtime object, so a foreign triple can only stop at the object.
+    if options.emit == EmitKind::Executable
+        && let Some(triple) = &options.target_triple
+    {
+        let parsed = triple
+            .parse::<target_lexicon::Triple>()
+            .map_err(|e| format!("invalid target triple `{triple}`: {e}"))?;
+        if parsed != target_lexicon::Triple::host() {
+            return Err(format!(
+                "cannot link an executable for `{triple}`: use --emit=obj and link with that target's toolchain"
+            ));
+        }
+    }
+
+    // `-` reads the program from standard input (reported as `<stdin>`).
+    let (source, source_name) = compiler_core::read_source_arg(&options.input).map_err(|e| {
         format!("failed to read {}: {}", options.input.display(), e)
     })?;
 
@@ -70,7 +93,7 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
     // every other consumer of the frontend.
     let mut session = compiler_core::CompilerSession::new();
     let mut program = session
-        .parse_program(&source)
+        .parse_named_source(&source_name, &source)
         .map_err(|e| format!("parse error: {e:?}"))?;
 
     // Reuse the interpreter's check_typing so trait conformance, allocator
@@ -87,15 +110,55 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
             eprintln!("core modules: <none> (auto-load disabled)");
         }
     }
-    interpreter::check_typing_with_core_modules(
+    // Metrics describe the user's own functions, so keep the program as
+    // parsed: type-checking integrates the auto-loaded modules into it.
+    let parsed_program =
+        matches!(options.emit, EmitKind::Metrics | EmitKind::MetricsJson).then(|| program.clone());
+    let mut cfg = options.defines.clone();
+    cfg.define("target", frontend::cfg::TARGET_NATIVE);
+    let results = interpreter::check_typing_in_session(
         &mut program,
-        session.string_interner_mut(),
+        &mut session,
         Some(&source),
-        Some(options.input.to_string_lossy().as_ref()),
+        Some(&source_name),
         core_modules_dir.as_deref(),
+        &cfg,
     )
     .map_err(|errors| format!("type-check failed:\n  {}", errors.join("\n  ")))?;
 
+    if let Some(parsed) = parsed_program {
+        // Measured on unoptimised IR so inlining doesn't fold callees'
+        // branches into their callers.
+        let contract_msgs = ContractMessages::intern(session.string_interner_mut());
+        let ir_module = lower::lower_program(&program, session.string_interner(), &contract_msgs, options.release, options.bounds_check)?;
+        let report = metrics::collect(&parsed, session.string_interner(), &source, &ir_module);
+        let text = if options.emit == EmitKind::MetricsJson {
+            metrics::render_json(&report)
+        } else {
+            metrics::render_table(&report)
+        };
+        match &options.output {
+            Some(out) => std::fs::write(out, text)
+                .map_err(|e| format!("failed to write {}: {}", out.display(), e))?,
+            None => print!("{text}"),
+        }
+        report_timings(&session, options);
+        return Ok(());
+    }
+
+    let codegen_start = std::time::Instant::now();
+
+    // With optimisation on, pure calls with constant arguments are run
+    // now and replaced by their results (`interpreter::comptime`).
+    if codegen::cranelift_opt_level(options) != "none" {
+        interpreter::comptime::fold_pure_calls(
+            &mut program,
+            &results.expr_types,
+            &results.purity,
+            session.string_interner(),
+        );
+    }
+
     // Intern the canonical contract-violation messages now while the
     // session's interner is still mutable. The lowering pass uses
     // these symbols to attach a clause-specific panic message to
@@ -104,6 +167,7 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
     let contract_msgs = ContractMessages::intern(session.string_interner_mut());
 
     let object_bytes = codegen::emit_object(&program, session.string_interner(), &contract_msgs, options)?;
+    session.record_phase(compiler_core::Phase::Codegen, codegen_start.elapsed());
 
     match options.emit {
         EmitKind::Object => {
@@ -132,7 +196,7 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
             // onto the compiler's internal representation.
             let ir_text = codegen::emit_ir_text(&program, session.string_interner(), &contract_msgs, options)?;
             let out = options.output.clone().unwrap_or_else(|| {
-                let mut p = options.input.clone();
+                let mut p = output_stem(&options.input);
                 p.set_extension("ir");
                 p
             });
@@ -142,11 +206,12 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
                 eprintln!("wrote {}", out.display());
             }
         }
+        EmitKind::Metrics | EmitKind::MetricsJson => unreachable!("metrics are reported before codegen"),
         EmitKind::Clif => {
             // Cranelift IR text — for backend debugging.
             let clif_text = codegen::emit_clif_text(&program, session.string_interner(), &contract_msgs, options)?;
             let out = options.output.clone().unwrap_or_else(|| {
-                let mut p = options.input.clone();
+                let mut p = output_stem(&options.input);
                 p.set_extension("clif");
                 p
             });
@@ -157,9 +222,21 @@ pub fn compile_file(options: &CompilerOptions) -> Result<(), String> {
             }
         }
     }
+    report_timings(&session, options);
     Ok(())
 }
 
+/// Print the session's phase timings to stderr when `--timings` or
+/// `-v` asked for them. Linking is not a phase and isn't counted.
+fn report_timings(session: &compiler_core::CompilerSession, options: &CompilerOptions) {
+    let format = options
+        .timings
+        .or(options.verbose.then_some(compiler_core::TimingFormat::Text));
+    if let Some(format) = format {
+        eprint!("{}", session.timings().render(format));
+    }
+}
+
 /// Resolve the core-modules directory using the same priority chain
 /// the interpreter binary does. Mirrors
 /// `interpreter::main::resolve_core_modules_dir` so a single build of
@@ -206,13 +283,26 @@ pub fn resolve_core_modules_dir(
     None
 }
 
+/// Base path default outputs are derived from: the input file, or `a`
+/// when the program came from standard input.
+fn output_stem(input: &Path) -> std::path::PathBuf {
+    if compiler_core::is_stdin_arg(input) {
+        std::path::PathBuf::from("a")
+    } else {
+        input.to_path_buf()
+    }
+}
+
 fn default_object_path(input: &Path) -> std::path::PathBuf {
-    let mut p = input.to_path_buf();
+    let mut p = output_stem(input);
     p.set_extension("o");
     p
 }
 
 fn default_exe_path(input: &Path) -> std::path::PathBuf {
+    if compiler_core::is_stdin_arg(input) {
+        return std::path::PathBuf::from("a.out");
+    }
     let mut p = input.to_path_buf();
     p.set_extension("");
     if p.as_os_str().is_empty() {
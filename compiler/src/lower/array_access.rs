@@ -5,25 +5,28 @@
 //! `store_array_element`. `lower_slice_access` and
 //! `lower_slice_assign` handle `arr[i]` reads / writes for both
 //! constant and runtime indices, plus const-bound range slicing
-//! (`arr[start..end]`). Compound array elements (struct / tuple)
+//! (`arr[start..end]`) and range assignment (`arr[a..b] = src`). Compound array elements (struct / tuple)
 //! occupy `leaf_count` consecutive 8-byte leaf slots inside the
 //! same backing buffer; per-leaf ArrayLoad / ArrayStore sequences
 //! materialise / decompose them at the access site.
 //!
+//! Runtime indices are unchecked unless `--bounds-check` is on, in
+//! which case `emit_bounds_check` guards each access with a panic.
+//!
 //! `try_constant_index` lives here too — it folds a literal
 //! integer index (or a top-level const reference) to a `usize` so
 //! constant-index access can hit the same `ArrayLoad` instruction
 //! as runtime index without a redundant runtime `Const` round-trip.
 
-use frontend::ast::{Expr, ExprRef};
+use frontend::ast::{Expr, ExprRef, SliceInfo, SliceType};
 
-use super::array_layout::leaf_scalar_count;
+use super::array_layout::{leaf_scalar_count, leaf_type_at};
 use super::bindings::{
     flatten_struct_locals, flatten_tuple_element_locals, Binding, FieldBinding,
     TupleElementBinding,
 };
 use super::FunctionLower;
-use crate::ir::{ArraySlotId, BinOp, Const, InstKind, LocalId, Type, ValueId};
+use crate::ir::{ArraySlotId, BinOp, Const, InstKind, LocalId, Terminator, Type, ValueId};
 
 impl<'a> FunctionLower<'a> {
 
@@ -60,7 +63,7 @@ impl<'a> FunctionLower<'a> {
                     .get(expr_ref)
                     .ok_or_else(|| "array element missing".to_string())?;
                 match expr {
-                    Expr::StructLiteral(name, literal_fields) => {
+                    Expr::StructLiteral(name, literal_fields, _) => {
                         let expected = self.module.struct_def(struct_id).base_name;
                         if name != expected {
                             return Err(format!(
@@ -199,6 +202,13 @@ impl<'a> FunctionLower<'a> {
             .start
             .as_ref()
             .ok_or_else(|| "single-element slice missing index".to_string())?;
+        if let Some((element_ty, dims, slot, indices)) = self.nested_array_path(obj, index_ref) {
+            let idx_v = self.lower_nested_array_index(&dims, &indices)?;
+            return Ok(self.emit(
+                InstKind::ArrayLoad { slot, index: idx_v, elem_ty: element_ty },
+                Some(element_ty),
+            ));
+        }
         let obj_expr = self
             .program
             .expression
@@ -260,21 +270,15 @@ impl<'a> FunctionLower<'a> {
                 _ => unreachable!(),
             }
             // Element-base leaf index: const-fold or `imul(idx, leaf_count)`.
-            let base_v = if let Some(idx_const) = self.try_constant_index(index_ref) {
-                if idx_const >= length {
-                    return Err(format!(
-                        "array index {idx_const} out of bounds (length {length})"
-                    ));
-                }
+            let base_v = if let Some(idx_const) = self.resolve_constant_index(index_ref, length) {
+                let idx_const = idx_const?;
                 self.emit(
                     InstKind::Const(Const::U64((idx_const * leaf_count) as u64)),
                     Some(Type::U64),
                 )
                 .expect("Const returns a value")
             } else {
-                let raw_idx = self
-                    .lower_expr(index_ref)?
-                    .ok_or_else(|| "array index produced no value".to_string())?;
+                let raw_idx = self.lower_runtime_index(index_ref, length)?;
                 let leaf_count_v = self
                     .emit(
                         InstKind::Const(Const::U64(leaf_count as u64)),
@@ -332,17 +336,12 @@ impl<'a> FunctionLower<'a> {
         // Both forms hit the same `ArrayLoad` instruction so codegen
         // treats them uniformly. Constant-index out-of-bounds is
         // caught here.
-        let idx_v = if let Some(idx_const) = self.try_constant_index(index_ref) {
-            if idx_const >= length {
-                return Err(format!(
-                    "array index {idx_const} out of bounds (length {length})"
-                ));
-            }
+        let idx_v = if let Some(idx_const) = self.resolve_constant_index(index_ref, length) {
+            let idx_const = idx_const?;
             self.emit(InstKind::Const(Const::U64(idx_const as u64)), Some(Type::U64))
                 .expect("Const returns a value")
         } else {
-            self.lower_expr(index_ref)?
-                .ok_or_else(|| "array index produced no value".to_string())?
+            self.lower_runtime_index(index_ref, length)?
         };
         Ok(self.emit(
             InstKind::ArrayLoad { slot, index: idx_v, elem_ty: element_ty },
@@ -350,23 +349,33 @@ impl<'a> FunctionLower<'a> {
         ))
     }
 
-    /// Lower `arr[i] = v`. Phase S supports single-element write on
-    /// a bare-identifier array binding with a constant index. Range
-    /// assignment is rejected.
+    /// Lower `arr[i] = v` and `arr[a..b] = src` on a bare-identifier
+    /// array binding. Range assignment is delegated to
+    /// `lower_range_assign`.
     pub(super) fn lower_slice_assign(
         &mut self,
         obj: &ExprRef,
-        start: Option<&ExprRef>,
-        end: Option<&ExprRef>,
+        info: &SliceInfo,
         value: &ExprRef,
     ) -> Result<Option<ValueId>, String> {
-        if end.is_some() {
-            return Err(
-                "compiler MVP only supports single-element array write (`arr[i] = v`); range assignment is not implemented".to_string(),
+        let index_ref = info
+            .start
+            .as_ref()
+            .ok_or_else(|| "single-element slice write missing index".to_string());
+        if info.slice_type == SliceType::SingleElement
+            && let Some((element_ty, dims, slot, indices)) =
+                self.nested_array_path(obj, index_ref.clone()?)
+        {
+            let idx_v = self.lower_nested_array_index(&dims, &indices)?;
+            let v = self
+                .lower_expr(value)?
+                .ok_or_else(|| "array write rhs produced no value".to_string())?;
+            self.emit(
+                InstKind::ArrayStore { slot, index: idx_v, value: v, elem_ty: element_ty },
+                None,
             );
+            return Ok(None);
         }
-        let index_ref = start
-            .ok_or_else(|| "single-element slice write missing index".to_string())?;
         let obj_expr = self
             .program
             .expression
@@ -395,17 +404,16 @@ impl<'a> FunctionLower<'a> {
                 ));
             }
         };
-        let idx_v = if let Some(idx_const) = self.try_constant_index(index_ref) {
-            if idx_const >= length {
-                return Err(format!(
-                    "array index {idx_const} out of bounds (length {length})"
-                ));
-            }
+        if info.slice_type == SliceType::RangeSlice {
+            return self.lower_range_assign(slot, element_ty, length, info, value);
+        }
+        let index_ref = index_ref?;
+        let idx_v = if let Some(idx_const) = self.resolve_constant_index(index_ref, length) {
+            let idx_const = idx_const?;
             self.emit(InstKind::Const(Const::U64(idx_const as u64)), Some(Type::U64))
                 .expect("Const returns a value")
         } else {
-            self.lower_expr(index_ref)?
-                .ok_or_else(|| "array index produced no value".to_string())?
+            self.lower_runtime_index(index_ref, length)?
         };
         let v = self
             .lower_expr(value)?
@@ -417,8 +425,272 @@ impl<'a> FunctionLower<'a> {
         Ok(None)
     }
 
-    /// Fold a literal-integer index into a `usize`. Currently
-    /// accepts `Int64` / `UInt64` / `Number` literals only;
+    /// `arr[a..b] = src`: constant bounds only, folded the same way
+    /// as a range read. `src` is an array literal or another array
+    /// binding, and its length must equal `b - a`; the copy is
+    /// element-wise (per leaf for compound elements). Source leaves
+    /// are all loaded before the first store, so `arr[..] = arr`
+    /// style self-assignment sees the original values.
+    fn lower_range_assign(
+        &mut self,
+        dst_slot: ArraySlotId,
+        element_ty: Type,
+        length: usize,
+        info: &SliceInfo,
+        value: &ExprRef,
+    ) -> Result<Option<ValueId>, String> {
+        let start = match &info.start {
+            Some(s) => self.constant_slice_bound(s, length)?,
+            None => 0,
+        };
+        let end = match &info.end {
+            Some(e) => self.constant_slice_bound(e, length)?,
+            None => length,
+        };
+        if start > end || end > length {
+            return Err(format!(
+                "range slice {start}..{end} out of bounds (array length {length})"
+            ));
+        }
+        let range_len = end - start;
+        let leaf_count = leaf_scalar_count(self.module, element_ty);
+        let value_expr = self
+            .program
+            .expression
+            .get(value)
+            .ok_or_else(|| "slice assignment value missing".to_string())?;
+        match value_expr {
+            Expr::ArrayLiteral(elems) => {
+                if elems.len() != range_len {
+                    return Err(format!(
+                        "slice assignment length mismatch: range {start}..{end} has {range_len} elements, but the value has {}",
+                        elems.len()
+                    ));
+                }
+                for (i, e) in elems.iter().enumerate() {
+                    self.store_array_element(dst_slot, element_ty, start + i, leaf_count, e)?;
+                }
+            }
+            Expr::Identifier(src_sym) => {
+                let (src_len, src_slot) = match self.bindings.get(&src_sym).cloned() {
+                    Some(Binding::Array { length, slot, .. }) => (length, slot),
+                    _ => {
+                        return Err(format!(
+                            "`{}` is not an array binding",
+                            self.interner.resolve(src_sym).unwrap_or("?")
+                        ));
+                    }
+                };
+                if src_len != range_len {
+                    return Err(format!(
+                        "slice assignment length mismatch: range {start}..{end} has {range_len} elements, but the value has {src_len}"
+                    ));
+                }
+                let mut leaves = Vec::with_capacity(range_len * leaf_count);
+                for idx in 0..range_len * leaf_count {
+                    let leaf_ty = leaf_type_at(self.module, element_ty, idx % leaf_count);
+                    let idx_v = self
+                        .emit(InstKind::Const(Const::U64(idx as u64)), Some(Type::U64))
+                        .expect("Const returns a value");
+                    let v = self
+                        .emit(
+                            InstKind::ArrayLoad { slot: src_slot, index: idx_v, elem_ty: leaf_ty },
+                            Some(leaf_ty),
+                        )
+                        .expect("ArrayLoad returns");
+                    leaves.push((leaf_ty, v));
+                }
+                for (idx, (leaf_ty, v)) in leaves.into_iter().enumerate() {
+                    let idx_v = self
+                        .emit(
+                            InstKind::Const(Const::U64((start * leaf_count + idx) as u64)),
+                            Some(Type::U64),
+                        )
+                        .expect("Const returns a value");
+                    self.emit(
+                        InstKind::ArrayStore { slot: dst_slot, index: idx_v, value: v, elem_ty: leaf_ty },
+                        None,
+                    );
+                }
+            }
+            _ => {
+                return Err(
+                    "compiler MVP only supports range assignment from an array literal or array binding"
+                        .to_string(),
+                );
+            }
+        }
+        Ok(None)
+    }
+
+    /// Match `m[i][j]...` against a `Binding::NestedArray` root.
+    /// `last` is the outermost access's index; the result carries the
+    /// binding's element type, dims and slot plus every index,
+    /// outermost first. `None` when the chain isn't rooted at a
+    /// nested-array binding.
+    fn nested_array_path(
+        &self,
+        obj: &ExprRef,
+        last: &ExprRef,
+    ) -> Option<(Type, Vec<usize>, ArraySlotId, Vec<ExprRef>)> {
+        let mut indices = vec![*last];
+        let mut cur = *obj;
+        let root = loop {
+            match self.program.expression.get(&cur)? {
+                Expr::SliceAccess(inner, info) if info.slice_type == SliceType::SingleElement => {
+                    indices.push(info.start?);
+                    cur = inner;
+                }
+                Expr::Identifier(sym) => break sym,
+                _ => return None,
+            }
+        };
+        match self.bindings.get(&root)? {
+            Binding::NestedArray { element_ty, dims, slot } => {
+                indices.reverse();
+                Some((*element_ty, dims.clone(), *slot, indices))
+            }
+            _ => None,
+        }
+    }
+
+    /// Fold `m[i][j]...` into the flat row-major index
+    /// `(i * d1 + j) * d2 + ...`. Each index resolves against its own
+    /// level's length, so negative indices and `--bounds-check` work
+    /// per dimension; all-constant paths fold to a single `Const`.
+    fn lower_nested_array_index(&mut self, dims: &[usize], indices: &[ExprRef]) -> Result<ValueId, String> {
+        if indices.len() != dims.len() {
+            return Err(format!(
+                "compiler MVP only supports fully indexed nested-array access ({} indices); `m[i]` row values are not implemented",
+                dims.len()
+            ));
+        }
+        let mut flat_const = 0usize;
+        let mut flat_v: Option<ValueId> = None;
+        for (index_ref, &dim) in indices.iter().zip(dims) {
+            let idx_v = match self.resolve_constant_index(index_ref, dim) {
+                Some(idx_const) => {
+                    let idx_const = idx_const?;
+                    if flat_v.is_none() {
+                        flat_const = flat_const * dim + idx_const;
+                        continue;
+                    }
+                    self.emit(InstKind::Const(Const::U64(idx_const as u64)), Some(Type::U64))
+                        .expect("Const returns a value")
+                }
+                None => self.lower_runtime_index(index_ref, dim)?,
+            };
+            let dim_v = self
+                .emit(InstKind::Const(Const::U64(dim as u64)), Some(Type::U64))
+                .expect("Const returns a value");
+            let base_v = match flat_v {
+                Some(acc) => self
+                    .emit(InstKind::BinOp { op: BinOp::Mul, lhs: acc, rhs: dim_v }, Some(Type::U64))
+                    .expect("imul returns"),
+                None => self
+                    .emit(InstKind::Const(Const::U64((flat_const * dim) as u64)), Some(Type::U64))
+                    .expect("Const returns a value"),
+            };
+            flat_v = Some(
+                self.emit(InstKind::BinOp { op: BinOp::Add, lhs: base_v, rhs: idx_v }, Some(Type::U64))
+                    .expect("iadd returns"),
+            );
+        }
+        Ok(match flat_v {
+            Some(v) => v,
+            None => self
+                .emit(InstKind::Const(Const::U64(flat_const as u64)), Some(Type::U64))
+                .expect("Const returns a value"),
+        })
+    }
+
+    /// Lower a runtime array index. A signed index counts from the end
+    /// when negative, as in the interpreter: `i + (length & (i >> 63))`
+    /// adds `length` exactly when the sign bit is set, without a branch.
+    pub(super) fn lower_runtime_index(&mut self, index_ref: &ExprRef, length: usize) -> Result<ValueId, String> {
+        let mut idx_v = self
+            .lower_expr(index_ref)?
+            .ok_or_else(|| "array index produced no value".to_string())?;
+        if self.value_ir_type_for(idx_v) == Some(Type::I64) {
+            let shift_v = self
+                .emit(InstKind::Const(Const::I64(63)), Some(Type::I64))
+                .expect("Const returns a value");
+            let sign_v = self
+                .emit(InstKind::BinOp { op: BinOp::Shr, lhs: idx_v, rhs: shift_v }, Some(Type::I64))
+                .expect("sshr returns");
+            let length_v = self
+                .emit(InstKind::Const(Const::I64(length as i64)), Some(Type::I64))
+                .expect("Const returns a value");
+            let offset_v = self
+                .emit(InstKind::BinOp { op: BinOp::BitAnd, lhs: sign_v, rhs: length_v }, Some(Type::I64))
+                .expect("band returns");
+            idx_v = self
+                .emit(InstKind::BinOp { op: BinOp::Add, lhs: idx_v, rhs: offset_v }, Some(Type::I64))
+                .expect("iadd returns");
+        }
+        self.emit_bounds_check(idx_v, length);
+        Ok(idx_v)
+    }
+
+    /// `--bounds-check`: branch to a panic unless `index < length`.
+    /// The comparison is written `length > index` so the unsigned
+    /// `length` constant picks the unsigned compare, and a negative
+    /// `i64` index fails it like any other out-of-range value.
+    fn emit_bounds_check(&mut self, index: ValueId, length: usize) {
+        if !self.bounds_check {
+            return;
+        }
+        let length_v = self
+            .emit(InstKind::Const(Const::U64(length as u64)), Some(Type::U64))
+            .expect("Const returns a value");
+        let in_range = self
+            .emit(InstKind::BinOp { op: BinOp::Gt, lhs: length_v, rhs: index }, Some(Type::Bool))
+            .expect("icmp returns");
+        let pass = self.fresh_block();
+        let fail = self.fresh_block();
+        self.terminate(Terminator::Branch { cond: in_range, then_blk: pass, else_blk: fail });
+        self.switch_to(fail);
+        self.terminate(Terminator::Panic { message: self.contract_msgs.index_out_of_bounds });
+        self.switch_to(pass);
+    }
+
+    /// Fold a constant index against an array of `length` elements.
+    /// A negative constant counts from the end (`-1` is the last
+    /// element); `Some(Err(..))` when the folded index is out of range.
+    pub(super) fn resolve_constant_index(&self, expr_ref: &ExprRef, length: usize) -> Option<Result<usize, String>> {
+        let raw = match self.program.expression.get(expr_ref)? {
+            Expr::Int64(v) if v < 0 => v,
+            _ => return self.try_constant_index(expr_ref).map(|i| {
+                if i < length {
+                    Ok(i)
+                } else {
+                    Err(format!("array index {i} out of bounds (length {length})"))
+                }
+            }),
+        };
+        Some(match length.checked_sub(raw.unsigned_abs() as usize) {
+            Some(i) => Ok(i),
+            None => Err(format!("array index {raw} out of bounds (length {length})")),
+        })
+    }
+
+    /// Constant `start` / `end` of a range slice. Negative bounds
+    /// count from the end like single-element indices; the range
+    /// itself is validated by the caller.
+    pub(super) fn constant_slice_bound(&self, expr_ref: &ExprRef, length: usize) -> Result<usize, String> {
+        if let Some(Expr::Int64(v)) = self.program.expression.get(expr_ref)
+            && v < 0
+        {
+            return length
+                .checked_sub(v.unsigned_abs() as usize)
+                .ok_or_else(|| format!("range slice bound {v} out of bounds (array length {length})"));
+        }
+        self.try_constant_index(expr_ref)
+            .ok_or_else(|| "compiler MVP only supports constant range-slice bounds".to_string())
+    }
+
+    /// Fold a non-negative literal-integer index into a `usize`.
+    /// Currently accepts `Int64` / `UInt64` / `Number` literals only;
     /// arbitrary const-expression folding is deferred.
     pub(super) fn try_constant_index(&self, expr_ref: &ExprRef) -> Option<usize> {
         let e = self.program.expression.get(expr_ref)?;
@@ -281,7 +281,7 @@ impl<'a> FunctionLower<'a> {
                         // Already handled above.
                         unreachable!("enum reassign was peeked");
                     }
-                    Some(Binding::Array { .. }) => {
+                    Some(Binding::Array { .. } | Binding::NestedArray { .. }) => {
                         return Err(format!(
                             "compiler MVP cannot reassign an array binding `{}` whole (assign individual elements via `{}[i] = ...` instead)",
                             self.interner.resolve(sym).unwrap_or("?"),
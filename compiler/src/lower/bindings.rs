@@ -59,6 +59,16 @@ pub(super) enum Binding {
         length: usize,
         slot: ArraySlotId,
     },
+    /// Nested fixed-size array (`[[T; C]; R]`, any depth). The
+    /// scalars live row-major in one stack slot of
+    /// `dims.iter().product()` elements, and `m[i][j]` folds its
+    /// indices into a single flat `ArrayLoad` / `ArrayStore` index.
+    /// `dims` lists the per-level lengths, outermost first.
+    NestedArray {
+        element_ty: Type,
+        dims: Vec<usize>,
+        slot: ArraySlotId,
+    },
     /// Closures Phase 5b: function-pointer binding. The `local`
     /// holds a `Type::U64` value that is the runtime address of a
     /// function — produced either by `InstKind::FuncAddr` (when a
@@ -231,6 +231,7 @@ impl<'a> FunctionLower<'a> {
             .map(|(_, t)| matches!(t, frontend::type_decl::TypeDecl::Ref { .. }))
             .collect();
         self.module.function_mut(func_id).param_is_ref = param_is_ref;
+        self.module.function_mut(func_id).inline = template.inline;
         self.generic_instances
             .insert((template_name, type_args), func_id);
         self.pending_generic_work.push(PendingGenericInstance {
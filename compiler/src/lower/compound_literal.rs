@@ -89,7 +89,7 @@ impl<'a> FunctionLower<'a> {
                         .get(value_ref)
                         .ok_or_else(|| "struct field rhs missing".to_string())?;
                     let inner_literal = match inner_expr {
-                        Expr::StructLiteral(_, inner_fs) => inner_fs,
+                        Expr::StructLiteral(_, inner_fs, _) => inner_fs,
                         other => {
                             return Err(format!(
                                 "compiler MVP requires struct field `{}.{}` to be initialised by a struct literal (got {:?})",
@@ -226,8 +226,8 @@ impl<'a> FunctionLower<'a> {
                 let id = intern_tuple(self.module, element_tys);
                 Some(Type::Tuple(id))
             }
-            Expr::StructLiteral(name, _) => {
-                let id = self.resolve_struct_instance(name, None).ok()?;
+            Expr::StructLiteral(name, fields, _) => {
+                let id = self.resolve_struct_instance_for_literal(name, &fields, None).ok()?;
                 Some(Type::Struct(id))
             }
             Expr::Identifier(sym) => match self.bindings.get(&sym) {
@@ -251,7 +251,7 @@ impl<'a> FunctionLower<'a> {
                     Some(Type::Tuple(id))
                 }
                 Some(Binding::Enum(_)) => None,
-                Some(Binding::Array { .. }) => None,
+                Some(Binding::Array { .. } | Binding::NestedArray { .. }) => None,
                 Some(Binding::FunctionPtr { .. }) => Some(Type::U64),
                 None => self.const_values.get(&sym).map(|c| c.ty()),
             },
@@ -448,8 +448,8 @@ impl<'a> FunctionLower<'a> {
             }
             id
         } else {
-            // Fall back to non-generic instantiation.
-            self.resolve_struct_instance(struct_name, None)?
+            // Fall back to the field values' types.
+            self.resolve_struct_instance_for_literal(struct_name, &fields, None)?
         };
         let field_bindings = self.allocate_struct_fields(struct_id);
         self.store_struct_literal_fields(struct_id, &field_bindings, &fields)?;
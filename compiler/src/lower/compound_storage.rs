@@ -580,7 +580,7 @@ impl<'a> FunctionLower<'a> {
             .get(expr_ref)
             .ok_or_else(|| "struct-target expression missing".to_string())?;
         match expr {
-            Expr::StructLiteral(name, literal_fields) => {
+            Expr::StructLiteral(name, literal_fields, _) => {
                 let expected_base = self.module.struct_def(target_struct_id).base_name;
                 if name != expected_base {
                     return Err(format!(
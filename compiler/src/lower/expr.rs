@@ -526,7 +526,7 @@ impl<'a> FunctionLower<'a> {
                         && matches!(info.slice_type, frontend::ast::SliceType::SingleElement)
                             && let Some(Expr::Identifier(arr_sym)) =
                                 self.program.expression.get(&arr_expr)
-                                && let Some(Binding::Array { element_ty, slot, .. }) =
+                                && let Some(Binding::Array { element_ty, slot, length }) =
                                     self.bindings.get(&arr_sym).cloned()
                                     && matches!(
                                         element_ty,
@@ -535,11 +535,7 @@ impl<'a> FunctionLower<'a> {
                                             | Type::I32 | Type::U32
                                     )
                                         && let Some(idx_ref) = info.start {
-                                            let idx_v = self
-                                                .lower_expr(&idx_ref)?
-                                                .ok_or_else(|| {
-                                                    "array index produced no value".to_string()
-                                                })?;
+                                            let idx_v = self.lower_runtime_index(&idx_ref, length)?;
                                             let v = self
                                                 .emit(
                                                     InstKind::ArrayElemAddr {
@@ -695,7 +691,7 @@ impl<'a> FunctionLower<'a> {
                 // result) hit the value-required check downstream.
                 self.lower_tuple_literal_tail(elems)
             }
-            Expr::StructLiteral(struct_name, fields) => {
+            Expr::StructLiteral(struct_name, fields, _) => {
                 // Tail-position struct literal: materialise each field
                 // into a fresh local and stash the resulting field
                 // binding list as the pending struct value. The IR
@@ -710,7 +706,7 @@ impl<'a> FunctionLower<'a> {
             Expr::IfElifElse(cond, then_blk, elif_pairs, else_blk) => {
                 self.lower_if_chain(&cond, &then_blk, &elif_pairs, &else_blk)
             }
-            Expr::Call(fn_name, args_ref) => self.lower_call(fn_name, &args_ref),
+            Expr::Call(fn_name, args_ref, _) => self.lower_call(fn_name, &args_ref),
             Expr::AssociatedFunctionCall(struct_name, fn_name, args) => {
                 self.lower_expr_associated_call(struct_name, fn_name, args)
             }
@@ -722,8 +718,8 @@ impl<'a> FunctionLower<'a> {
                 self.lower_expr_builtin_method_call(method)
             }
             Expr::SliceAccess(obj, info) => self.lower_slice_access(&obj, &info),
-            Expr::SliceAssign(obj, start, end, value) => {
-                self.lower_slice_assign(&obj, start.as_ref(), end.as_ref(), &value)
+            Expr::SliceAssign(obj, info, value) => {
+                self.lower_slice_assign(&obj, &info, &value)
             }
             // NUM-W-AOT (T5 follow-up to Phase 5): narrow integer
             // literals lower to the matching `Const::*` IR
@@ -748,6 +744,14 @@ impl<'a> FunctionLower<'a> {
             // emitted it before terminating, so we just decrement
             // the depth without a duplicate pop.
             Expr::With(allocator_expr, body_expr) => self.lower_expr_with(&allocator_expr, &body_expr),
+            Expr::WithResource { name, resource, body } => {
+                self.lower_expr_with_resource(name, &resource, &body)
+            }
+            // A runtime failure in AOT code exits the process, so there
+            // is nothing for a `catch` to intercept.
+            Expr::TryCatch { .. } => Err(
+                "compiler MVP cannot lower `try` / `catch`; run it with the interpreter".to_string(),
+            ),
             // Closures Phase 5b: a `Expr::Closure` literal in
             // expression position (e.g. as a HOF argument:
             // `apply(fn(x: i64) -> i64 { x }, 5i64)`). We lift the
@@ -852,7 +856,7 @@ impl<'a> FunctionLower<'a> {
                 self.pending_enum_value = Some(storage);
                 Ok(None)
             }
-            Some(Binding::Array { .. }) => {
+            Some(Binding::Array { .. } | Binding::NestedArray { .. }) => {
                 // Bare-identifier use of an array binding is
                 // not supported in expression position yet —
                 // arrays don't flow through the IR's value
@@ -1001,6 +1005,64 @@ impl<'a> FunctionLower<'a> {
         ))
     }
 
+    /// `with name = expr { body }` lowering. Binds the resource like a
+    /// `val`, then registers a `__close__` target in a drop scope of
+    /// its own, so the close call fires on linear exit here and on
+    /// `return` / `break` / `continue` through the same
+    /// `emit_drop_scopes_to_depth` path the Drop-trait bindings use.
+    fn lower_expr_with_resource(
+        &mut self,
+        name: DefaultSymbol,
+        resource: &ExprRef,
+        body: &ExprRef,
+    ) -> Result<Option<ValueId>, String> {
+        // Like block-local `val`s, the binding stays in the flat map
+        // after the block (so `value_scalar` can still type the body)
+        // unless it shadowed an outer one, which is put back.
+        let shadowed = self.bindings.get(&name).cloned();
+        self.enter_drop_scope();
+        // `with r = existing { ... }` aliases the existing binding's
+        // locals (a struct `val` copy isn't lowered); any other
+        // resource expression binds like a `val`.
+        let aliased = match self.program.expression.get(resource) {
+            Some(Expr::Identifier(sym)) => match self.bindings.get(&sym) {
+                Some(b @ Binding::Struct { .. }) => Some(b.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        match aliased {
+            Some(binding) => {
+                self.bindings.insert(name, binding);
+            }
+            None => {
+                self.lower_let(name, None, resource)?;
+            }
+        }
+        let (struct_id, fields) = match self.bindings.get(&name) {
+            Some(Binding::Struct { struct_id, fields }) => (*struct_id, fields.clone()),
+            _ => {
+                return Err(format!(
+                    "`with {} = ...` needs a struct resource in AOT builds",
+                    self.interner.resolve(name).unwrap_or("?")
+                ))
+            }
+        };
+        if let Some(scope) = self.drop_scopes.last_mut() {
+            scope.push(super::DropTarget {
+                struct_id,
+                field_locals: flatten_struct_locals(&fields),
+                method: "__close__",
+            });
+        }
+        let body_value = self.lower_expr(body)?;
+        self.pop_and_emit_drops()?;
+        if let Some(binding) = shadowed {
+            self.bindings.insert(name, binding);
+        }
+        Ok(body_value)
+    }
+
     /// `with allocator = expr { body }` lowering. #121 Phase B-rest
     /// Item 2: push the allocator handle, increment the with-scope
     /// depth so `terminate_return` / `break` / `continue` know to emit
@@ -1543,6 +1605,9 @@ impl<'a> FunctionLower<'a> {
                     ));
                 }
                 if let Some(Type::Struct(struct_id)) = self.value_scalar(&args[0]) {
+                    if let Some(str_sym) = self.struct_str_method(struct_id) {
+                        return self.lower_method_call(&args[0], str_sym, &Vec::new());
+                    }
                     return self.lower_struct_to_string(struct_id, &args[0]);
                 }
                 // Tuple-typed identifier — `value_scalar` can't
@@ -1741,6 +1806,10 @@ impl<'a> FunctionLower<'a> {
                 }
                 Ok(self.emit(InstKind::Const(crate::ir::Const::U64(0)), Some(Type::U64)))
             }
+            // Tasks are generators driven by the interpreter's run queue.
+            BuiltinFunction::Spawn | BuiltinFunction::YieldNow => Err(
+                "compiler MVP cannot lower tasks (`spawn` / `yield_now`); run them with the interpreter".to_string(),
+            ),
             other => Err(format!(
                 "compiler MVP cannot lower builtin yet: {:?}",
                 other
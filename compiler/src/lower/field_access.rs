@@ -108,7 +108,7 @@ impl<'a> FunctionLower<'a> {
                     "compiler MVP cannot use tuple `{}` in a field-access chain",
                     self.interner.resolve(sym).unwrap_or("?")
                 )),
-                Some(Binding::Array { .. }) => Err(format!(
+                Some(Binding::Array { .. } | Binding::NestedArray { .. }) => Err(format!(
                     "compiler MVP cannot use array `{}` in a field-access chain",
                     self.interner.resolve(sym).unwrap_or("?")
                 )),
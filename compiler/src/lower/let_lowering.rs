@@ -14,6 +14,8 @@
 //!   correct stride sized from `infer_array_element_type`).
 //! - range slice rhs (`arr[start..end]`) -> sliced array shape
 //!   with leaf-index addressing (constant-bound only for now).
+//! - array comprehension rhs (`[e for x in src]`) ->
+//!   `Binding::Array` filled by a counted loop (see `loops.rs`).
 //! - scalar / call / method-call / field-access rhs ->
 //!   `Binding::Scalar`. Compound-returning calls allocate the
 //!   appropriate `Struct` / `Tuple` / `Enum` shape and use the
@@ -79,7 +81,7 @@ impl<'a> FunctionLower<'a> {
         // stack-allocated buffer).
         // Range-slice array read: `val sub = arr[start..end]`.
         // Phase Y2 supports constant bounds only — both endpoints
-        // must fold via `try_constant_index`. The result is a fresh
+        // must fold via `constant_slice_bound`. The result is a fresh
         // fixed-length array binding whose stack slot mirrors the
         // source slot's leaf layout. Each leaf scalar is copied with
         // an `ArrayLoad` + `ArrayStore` pair.
@@ -103,6 +105,9 @@ impl<'a> FunctionLower<'a> {
         if let Expr::ArrayLiteral(elems) = rhs.clone() {
             return self.lower_let_array_literal(name, elems);
         }
+        if let Expr::Comprehension { element, value, var, source, filter } = rhs.clone() {
+            return self.lower_let_comprehension(name, element, value, var, source, filter);
+        }
         // Enum-construction RHS. `Enum::Variant` (unit) parses as a
         // `QualifiedIdentifier(vec![enum, variant])`; `Enum::Variant(args)`
         // parses as `AssociatedFunctionCall(enum, variant, args)`.
@@ -136,7 +141,7 @@ impl<'a> FunctionLower<'a> {
         // into nested struct fields), evaluate each field expression,
         // store into the matching local. The IR layer never sees a
         // struct value — we decompose at the lowering boundary.
-        if let Expr::StructLiteral(struct_name, fields) = rhs {
+        if let Expr::StructLiteral(struct_name, fields, _) = rhs {
             return self.lower_let_struct_literal(name, annotation, struct_name, fields);
         }
         // DICT-AOT-NEW: `var d: Dict<i64, u64> = Dict::new()` —
@@ -233,7 +238,7 @@ impl<'a> FunctionLower<'a> {
         // `val (a, b) = make_pair()` (which becomes
         // `val tmp = make_pair(); val a = tmp.0; val b = tmp.1`) is
         // also handled here without special-casing destructuring.
-        if let Expr::Call(fn_name, args_ref) = rhs.clone()
+        if let Expr::Call(fn_name, args_ref, _) = rhs.clone()
             && let Some(result) =
                 self.lower_let_call_tuple_or_enum(name, fn_name, &args_ref)?
             {
@@ -242,7 +247,7 @@ impl<'a> FunctionLower<'a> {
         // Struct-returning call RHS: `val p = make_point()`. Allocate
         // a struct binding and use `CallStruct` so codegen can route
         // the multi-return values into the per-field locals.
-        if let Expr::Call(fn_name, args_ref) = rhs
+        if let Expr::Call(fn_name, args_ref, _) = rhs
             && let Some(result) =
                 self.lower_let_call_struct(name, fn_name, &args_ref)?
             {
@@ -276,7 +281,7 @@ impl<'a> FunctionLower<'a> {
         // binding would be a plain `Binding::Scalar { ty: U64 }`
         // and `lower_call` would then fail to find `name` in the
         // function table.
-        if let Expr::Call(callee_name, _) = rhs.clone()
+        if let Expr::Call(callee_name, _, _) = rhs.clone()
             && let Some(result) =
                 self.lower_let_call_function_pointer(name, rhs_ref, callee_name)?
             {
@@ -1200,7 +1205,8 @@ impl<'a> FunctionLower<'a> {
         fn_name: DefaultSymbol,
         args_vec: &[ExprRef],
     ) -> Result<Option<Option<ValueId>>, String> {
-        let struct_id = self.resolve_struct_instance(struct_name, annotation)?;
+        let struct_id =
+            self.resolve_struct_instance_for_call(struct_name, fn_name, args_vec, annotation)?;
         let recv_type_args = self
             .module
             .struct_def(struct_id)
@@ -1302,7 +1308,8 @@ impl<'a> FunctionLower<'a> {
         if let Some(template) = template {
             // Re-resolve to use the right type_args path for
             // generic methods.
-            let struct_id = self.resolve_struct_instance(struct_name, annotation)?;
+            let struct_id =
+                self.resolve_struct_instance_for_call(struct_name, fn_name, args_vec, annotation)?;
             let recv_type_args = self
                 .module
                 .struct_def(struct_id)
@@ -1398,10 +1405,11 @@ impl<'a> FunctionLower<'a> {
         fields: Vec<(DefaultSymbol, ExprRef)>,
     ) -> Result<Option<ValueId>, String> {
         // Resolve to the right monomorphised instance. Generic
-        // structs need an annotation to pick T; non-generic
-        // ones short-circuit to a single instance.
+        // structs take T from the annotation or, failing that,
+        // from the field values; non-generic ones short-circuit
+        // to a single instance.
         let struct_id =
-            self.resolve_struct_instance(struct_name, annotation)?;
+            self.resolve_struct_instance_for_literal(struct_name, &fields, annotation)?;
         let field_bindings = self.allocate_struct_fields(struct_id);
         // Insert the binding before evaluating field rhs
         // expressions so an inner literal that walks back to the
@@ -1530,6 +1538,9 @@ impl<'a> FunctionLower<'a> {
                 "compiler MVP cannot infer element type for empty array literal".to_string(),
             );
         }
+        if let Some(Expr::ArrayLiteral(_)) = self.program.expression.get(&elems[0]) {
+            return self.lower_let_nested_array_literal(name, elems);
+        }
         // Element type comes from the first element. Scalars
         // resolve via `value_scalar`; struct literals resolve
         // through the struct table.
@@ -1580,9 +1591,105 @@ impl<'a> FunctionLower<'a> {
         Ok(None)
     }
 
+    /// Nested array-literal RHS (`val m = [[1, 2], [3, 4]]`). The
+    /// literal must be rectangular; its scalars are stored row-major
+    /// into one slot and the binding records the per-level lengths.
+    /// Only scalar innermost elements are supported.
+    fn lower_let_nested_array_literal(
+        &mut self,
+        name: DefaultSymbol,
+        elems: Vec<ExprRef>,
+    ) -> Result<Option<ValueId>, String> {
+        let mut dims = Vec::new();
+        let mut cur = elems.clone();
+        while let Some(Expr::ArrayLiteral(inner)) = self.program.expression.get(&cur[0]) {
+            dims.push(cur.len());
+            if inner.is_empty() {
+                return Err(
+                    "compiler MVP cannot infer element type for empty array literal".to_string(),
+                );
+            }
+            cur = inner;
+        }
+        dims.push(cur.len());
+        let mut leaves = Vec::with_capacity(dims.iter().product());
+        self.flatten_nested_array_literal(&elems, &dims, &mut leaves)?;
+        let elem_ty = self.infer_array_element_type(&leaves[0])?;
+        if !matches!(
+            elem_ty,
+            Type::I64
+                | Type::U64
+                | Type::F64
+                | Type::Bool
+                | Type::I8
+                | Type::U8
+                | Type::I16
+                | Type::U16
+                | Type::I32
+                | Type::U32
+        ) {
+            return Err(format!(
+                "compiler MVP only supports scalar elements in nested arrays; got {elem_ty:?}"
+            ));
+        }
+        let stride = elem_stride_bytes(elem_ty, self.module);
+        let slot = self
+            .module
+            .function_mut(self.func_id)
+            .add_array_slot(elem_ty, leaves.len(), stride);
+        for (i, e) in leaves.iter().enumerate() {
+            self.store_array_element(slot, elem_ty, i, 1, e)?;
+        }
+        self.bindings.insert(
+            name,
+            Binding::NestedArray {
+                element_ty: elem_ty,
+                dims,
+                slot,
+            },
+        );
+        Ok(None)
+    }
+
+    /// Collect the innermost element expressions of a nested array
+    /// literal in row-major order, checking every level against
+    /// `dims` so ragged literals are rejected.
+    fn flatten_nested_array_literal(
+        &self,
+        elems: &[ExprRef],
+        dims: &[usize],
+        out: &mut Vec<ExprRef>,
+    ) -> Result<(), String> {
+        if elems.len() != dims[0] {
+            return Err(format!(
+                "nested array literal is not rectangular: expected a row of {} elements, got {}",
+                dims[0],
+                elems.len()
+            ));
+        }
+        if dims.len() == 1 {
+            out.extend_from_slice(elems);
+            return Ok(());
+        }
+        for e in elems {
+            match self.program.expression.get(e) {
+                Some(Expr::ArrayLiteral(inner)) => {
+                    self.flatten_nested_array_literal(&inner, &dims[1..], out)?;
+                }
+                _ => {
+                    return Err(
+                        "compiler MVP only supports array-literal rows in a nested array literal"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Range-slice RHS helper (`val sub = arr[start..end]`).
     /// Constant bounds only — both endpoints must fold via
-    /// `try_constant_index`. Allocates a fresh fixed-length
+    /// `constant_slice_bound`. Allocates a fresh fixed-length
     /// array binding and copies each leaf scalar with an
     /// `ArrayLoad` + `ArrayStore` pair.
     fn lower_let_range_slice(
@@ -1618,17 +1725,14 @@ impl<'a> FunctionLower<'a> {
         };
         // Defaults for omitted endpoints follow the
         // interpreter: `..end` starts at 0, `start..` ends
-        // at `length`, `..` is the whole array.
+        // at `length`, `..` is the whole array, and negative
+        // bounds count from the end.
         let start = match info.start {
-            Some(s) => self.try_constant_index(&s).ok_or_else(|| {
-                "compiler MVP only supports constant range-slice bounds".to_string()
-            })?,
+            Some(s) => self.constant_slice_bound(&s, length)?,
             None => 0,
         };
         let end = match info.end {
-            Some(e) => self.try_constant_index(&e).ok_or_else(|| {
-                "compiler MVP only supports constant range-slice bounds".to_string()
-            })?,
+            Some(e) => self.constant_slice_bound(&e, length)?,
             None => length,
         };
         if start > end || end > length {
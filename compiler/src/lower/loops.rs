@@ -8,13 +8,24 @@
 //!   loop variable, materialises start / end into per-iteration
 //!   compares, and writes back the incremented counter at the
 //!   end of each iteration.
+//! - `lower_let_comprehension`: lowers `val a = [e for x in src]`
+//!   to the same counter loop, storing each `e` into a fresh
+//!   fixed-length array slot.
 
-use frontend::ast::ExprRef;
+use frontend::ast::{Expr, ExprRef};
 use string_interner::DefaultSymbol;
 
+use super::array_layout::elem_stride_bytes;
 use super::bindings::Binding;
 use super::FunctionLower;
-use crate::ir::{BinOp, BlockId, Const, InstKind, Terminator, Type, ValueId};
+use crate::ir::{BinOp, BlockId, Const, InstKind, LocalId, Terminator, Type, ValueId};
+
+/// `loop_stack` entry: `(label, continue, break, with_depth,
+/// drop_depth, result)`. `result` is the `(local, type)` slot that
+/// `break value` stores into, allocated by the first value break that
+/// targets the loop; the loop's exit block loads it as the result.
+pub(super) type LoopFrame =
+    (Option<DefaultSymbol>, BlockId, BlockId, usize, usize, Option<(LocalId, Type)>);
 
 impl<'a> FunctionLower<'a> {
     pub(super) fn lower_while(
@@ -41,23 +52,60 @@ impl<'a> FunctionLower<'a> {
         // loop entry so `break` / `continue` inside the loop body
         // emit `AllocPop` only for `with` scopes opened *inside*
         // the loop, not the outer ones.
-        self.loop_stack.push((label, header, exit, self.with_scope_depth, self.drop_scopes.len()));
+        self.loop_stack.push((label, header, exit, self.with_scope_depth, self.drop_scopes.len(), None));
         let _ = self.lower_expr(body)?;
-        self.loop_stack.pop();
+        let frame = self.loop_stack.pop();
         if !self.is_unreachable() {
             self.terminate(Terminator::Jump(header));
         }
         self.switch_to(exit);
+        // `loop { ... break value }`: the value breaks stored into the
+        // frame's result local; reload it as the loop's value.
+        if let Some((_, _, _, _, _, Some((local, ty)))) = frame {
+            self.loop_result_types.insert(*body, ty);
+            return Ok(self.emit(InstKind::LoadLocal(local), Some(ty)));
+        }
         Ok(None)
     }
 
+    /// `break value`: lower the value and store it into the result
+    /// local of the loop `label` targets, allocating the local on the
+    /// first value break. Only scalar values are supported.
+    pub(super) fn store_loop_result(
+        &mut self,
+        label: Option<DefaultSymbol>,
+        value: &ExprRef,
+    ) -> Result<(), String> {
+        let v = self
+            .lower_expr(value)?
+            .ok_or_else(|| "break value produced no value".to_string())?;
+        let ty = self
+            .value_scalar(value)
+            .ok_or_else(|| "`break value` with a non-scalar value is not supported by the compiler yet".to_string())?;
+        let index = match label {
+            None => self.loop_stack.len().checked_sub(1),
+            Some(sym) => self.loop_stack.iter().rposition(|f| f.0 == Some(sym)),
+        }
+        .ok_or_else(|| "`break` references no enclosing loop".to_string())?;
+        let local = match self.loop_stack[index].5 {
+            Some((local, _)) => local,
+            None => {
+                let local = self.module.function_mut(self.func_id).add_local(ty);
+                self.loop_stack[index].5 = Some((local, ty));
+                local
+            }
+        };
+        self.emit(InstKind::StoreLocal { dst: local, src: v }, None);
+        Ok(())
+    }
+
     /// LABEL: walk loop_stack rev-first matching `label`. `None` returns
     /// innermost. Type checker should already guarantee resolvability.
     pub(super) fn resolve_loop_frame(
         &self,
         label: Option<DefaultSymbol>,
         kw: &str,
-    ) -> Result<&(Option<DefaultSymbol>, BlockId, BlockId, usize, usize), String> {
+    ) -> Result<&LoopFrame, String> {
         match label {
             None => self
                 .loop_stack
@@ -143,7 +191,7 @@ impl<'a> FunctionLower<'a> {
         // emit `AllocPop` only for `with` scopes opened *inside*
         // the loop, not the outer ones.
         // Continue target = step (increments before jumping back).
-        self.loop_stack.push((label, step, exit, self.with_scope_depth, self.drop_scopes.len()));
+        self.loop_stack.push((label, step, exit, self.with_scope_depth, self.drop_scopes.len(), None));
         let _ = self.lower_expr(body)?;
         self.loop_stack.pop();
         if !self.is_unreachable() {
@@ -180,4 +228,168 @@ impl<'a> FunctionLower<'a> {
         self.switch_to(exit);
         Ok(None)
     }
+
+    /// `val name = [e for x in src]`. `src` is a range with constant
+    /// endpoints or a scalar array binding, so the result length is
+    /// known up front. The loop keeps an element counter `idx`; for a
+    /// range `x` steps alongside it, for an array `x` is reloaded
+    /// from `src[idx]` each iteration. Filtered and dict
+    /// comprehensions have no fixed length and are rejected.
+    pub(super) fn lower_let_comprehension(
+        &mut self,
+        name: DefaultSymbol,
+        element: ExprRef,
+        value: Option<ExprRef>,
+        var: DefaultSymbol,
+        source: ExprRef,
+        filter: Option<ExprRef>,
+    ) -> Result<Option<ValueId>, String> {
+        if value.is_some() {
+            return Err("compiler MVP does not support dict comprehensions".to_string());
+        }
+        if filter.is_some() {
+            return Err("compiler MVP does not support filtered comprehensions".to_string());
+        }
+        let source_expr = self
+            .program
+            .expression
+            .get(&source)
+            .ok_or_else(|| "comprehension source missing".to_string())?;
+        // `Some(start)` for a range source, `None` for an array one.
+        let (item_ty, length, range_start, src_slot) = match source_expr {
+            Expr::Range(start, end) => {
+                let item_ty = self.value_scalar(&start).unwrap_or(Type::U64);
+                let (Some(s), Some(e)) = (self.constant_range_endpoint(&start), self.constant_range_endpoint(&end)) else {
+                    return Err(
+                        "compiler MVP only supports comprehensions over constant ranges".to_string(),
+                    );
+                };
+                (item_ty, e.saturating_sub(s).max(0) as usize, Some(start), None)
+            }
+            Expr::Identifier(sym) => match self.bindings.get(&sym).cloned() {
+                Some(Binding::Array { element_ty, length, slot }) if !matches!(element_ty, Type::Struct(_) | Type::Tuple(_)) => {
+                    (element_ty, length, None, Some(slot))
+                }
+                _ => {
+                    return Err(format!(
+                        "compiler MVP only supports comprehensions over scalar arrays; `{}` is not one",
+                        self.interner.resolve(sym).unwrap_or("?")
+                    ));
+                }
+            },
+            _ => {
+                return Err(
+                    "compiler MVP only supports comprehensions over a range or an array binding".to_string(),
+                );
+            }
+        };
+        if length == 0 {
+            return Err("compiler MVP cannot lower an empty comprehension".to_string());
+        }
+
+        let item = self.module.function_mut(self.func_id).add_local(item_ty);
+        let shadowed = self.bindings.insert(var, Binding::Scalar { local: item, ty: item_ty });
+        let elem_ty = self.value_scalar(&element).ok_or_else(|| {
+            "compiler MVP only supports scalar comprehension elements".to_string()
+        })?;
+        let stride = elem_stride_bytes(elem_ty, self.module);
+        let dst_slot = self
+            .module
+            .function_mut(self.func_id)
+            .add_array_slot(elem_ty, length, stride);
+
+        let idx = self.module.function_mut(self.func_id).add_local(Type::U64);
+        let zero = self.emit(InstKind::Const(Const::U64(0)), Some(Type::U64)).unwrap();
+        self.emit(InstKind::StoreLocal { dst: idx, src: zero }, None);
+        if let Some(start) = range_start {
+            let start_v = self
+                .lower_expr(&start)?
+                .ok_or_else(|| "comprehension range start produced no value".to_string())?;
+            self.emit(InstKind::StoreLocal { dst: item, src: start_v }, None);
+        }
+
+        let header = self.fresh_block();
+        let body_blk = self.fresh_block();
+        let exit = self.fresh_block();
+        self.terminate(Terminator::Jump(header));
+
+        self.switch_to(header);
+        let i = self.emit(InstKind::LoadLocal(idx), Some(Type::U64)).unwrap();
+        let len_v = self
+            .emit(InstKind::Const(Const::U64(length as u64)), Some(Type::U64))
+            .unwrap();
+        let cmp = self
+            .emit(InstKind::BinOp { op: BinOp::Lt, lhs: i, rhs: len_v }, Some(Type::Bool))
+            .unwrap();
+        self.terminate(Terminator::Branch { cond: cmp, then_blk: body_blk, else_blk: exit });
+
+        self.switch_to(body_blk);
+        if let Some(slot) = src_slot {
+            let i = self.emit(InstKind::LoadLocal(idx), Some(Type::U64)).unwrap();
+            let x = self
+                .emit(InstKind::ArrayLoad { slot, index: i, elem_ty: item_ty }, Some(item_ty))
+                .unwrap();
+            self.emit(InstKind::StoreLocal { dst: item, src: x }, None);
+        }
+        let v = self
+            .lower_expr(&element)?
+            .ok_or_else(|| "comprehension element produced no value".to_string())?;
+        let i = self.emit(InstKind::LoadLocal(idx), Some(Type::U64)).unwrap();
+        self.emit(
+            InstKind::ArrayStore { slot: dst_slot, index: i, value: v, elem_ty },
+            None,
+        );
+        self.emit_increment(idx, Type::U64);
+        if src_slot.is_none() {
+            self.emit_increment(item, item_ty);
+        }
+        self.terminate(Terminator::Jump(header));
+
+        self.switch_to(exit);
+        match shadowed {
+            Some(prev) => {
+                self.bindings.insert(var, prev);
+            }
+            None => {
+                self.bindings.remove(&var);
+            }
+        }
+        self.bindings.insert(
+            name,
+            Binding::Array { element_ty: elem_ty, length, slot: dst_slot },
+        );
+        Ok(None)
+    }
+
+    /// `local += 1` for an integer loop counter.
+    fn emit_increment(&mut self, local: LocalId, ty: Type) {
+        let cur = self.emit(InstKind::LoadLocal(local), Some(ty)).unwrap();
+        let one = self
+            .emit(
+                InstKind::Const(match ty {
+                    Type::I64 => Const::I64(1),
+                    _ => Const::U64(1),
+                }),
+                Some(ty),
+            )
+            .unwrap();
+        let next = self
+            .emit(InstKind::BinOp { op: BinOp::Add, lhs: cur, rhs: one }, Some(ty))
+            .unwrap();
+        self.emit(InstKind::StoreLocal { dst: local, src: next }, None);
+    }
+
+    /// Signed view of a literal or `const` range endpoint.
+    fn constant_range_endpoint(&self, expr_ref: &ExprRef) -> Option<i64> {
+        match self.program.expression.get(expr_ref)? {
+            Expr::Int64(v) => Some(v),
+            Expr::UInt64(v) => i64::try_from(v).ok(),
+            Expr::Identifier(sym) => match self.const_values.get(&sym)? {
+                Const::I64(v) => Some(*v),
+                Const::U64(v) => i64::try_from(*v).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
@@ -499,6 +499,7 @@ impl<'a> FunctionLower<'a> {
                     Binding::Struct { .. }
                     | Binding::Tuple { .. }
                     | Binding::Array { .. }
+                    | Binding::NestedArray { .. }
                     | Binding::FunctionPtr { .. } => {
                         return Err(format!(
                             "compiler MVP does not support `match` on struct / tuple / array / \
@@ -285,6 +285,7 @@ impl<'a> FunctionLower<'a> {
             func_id,
             template,
         );
+        self.module.function_mut(func_id).inline = template.inline;
         self.method_instances
             .insert((target_sym, method_sym, inst_args), func_id);
         // Capture the subst (including a synthetic `Self` entry when
@@ -945,7 +946,7 @@ impl<'a> FunctionLower<'a> {
                         && matches!(info.slice_type, frontend::ast::SliceType::SingleElement)
                             && let Some(Expr::Identifier(arr_sym)) =
                                 self.program.expression.get(&arr_expr)
-                                && let Some(Binding::Array { element_ty, slot, .. }) =
+                                && let Some(Binding::Array { element_ty, slot, length }) =
                                     self.bindings.get(&arr_sym).cloned()
                                     && matches!(
                                         element_ty,
@@ -954,11 +955,7 @@ impl<'a> FunctionLower<'a> {
                                             | Type::I32 | Type::U32
                                     )
                                         && let Some(idx_ref) = info.start {
-                                            let idx_v = self
-                                                .lower_expr(&idx_ref)?
-                                                .ok_or_else(|| {
-                                                    "array index produced no value".to_string()
-                                                })?;
+                                            let idx_v = self.lower_runtime_index(&idx_ref, length)?;
                                             let v = self
                                                 .emit(
                                                     InstKind::ArrayElemAddr {
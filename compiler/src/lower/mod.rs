@@ -57,6 +57,7 @@ use method_registry::{GenericMethods, MethodFuncIds, MethodInstances, MethodRegi
 mod program;
 pub use program::lower_program;
 use program::{GenericFuncs, GenericInstances, PendingGenericInstance};
+use loops::LoopFrame;
 
 mod type_inference;
 
@@ -95,10 +96,13 @@ mod expr;
 /// id (so we can look up the Drop method) and the leaf scalar
 /// locals (so we can emit the matching `CallWithSelfWriteback`
 /// pattern that `&mut self` method calls already use).
+/// `method` is `"drop"` for Drop-trait bindings and `"__close__"`
+/// for the resource of a `with name = expr { ... }` block.
 #[derive(Debug, Clone)]
 pub(super) struct DropTarget {
     pub(super) struct_id: crate::ir::StructId,
     pub(super) field_locals: Vec<(crate::ir::LocalId, crate::ir::Type)>,
+    pub(super) method: &'static str,
 }
 
 /// Per-`with` scope marker. The runtime arena / fixed_buffer
@@ -140,6 +144,10 @@ struct FunctionLower<'a> {
     /// every `requires` / `ensures` check, mirroring the interpreter's
     /// `INTERPRETER_CONTRACTS=off` behaviour.
     release: bool,
+    /// `true` under `--bounds-check`: runtime array indices are
+    /// compared against the array length before every
+    /// `ArrayLoad` / `ArrayStore`.
+    bounds_check: bool,
     /// `ensures` clauses on the function currently being lowered.
     /// Each Return site (explicit or implicit) emits these checks
     /// before the actual return so a violated postcondition aborts
@@ -172,7 +180,13 @@ struct FunctionLower<'a> {
     /// `label = Some(sym)` for `@sym: while/for`, `None` for unlabelled.
     /// `Stmt::Break(target)` / `Continue(target)` walks rev-first matching
     /// `target` (`None` means innermost).
-    loop_stack: Vec<(Option<DefaultSymbol>, BlockId, BlockId, usize, usize)>,
+    /// The sixth element is the `break value` result slot (see
+    /// `loops::LoopFrame`).
+    loop_stack: Vec<LoopFrame>,
+    /// Result type of every value-producing `loop` lowered so far,
+    /// keyed by the loop body, so `value_scalar` can type
+    /// `val x = loop { ... break v }` after the rhs is lowered.
+    loop_result_types: HashMap<ExprRef, Type>,
     /// #121 Phase B-rest Item 2: number of `with allocator = ...`
     /// scopes currently open at this point in the lowering walk.
     /// Incremented on entry to each `Expr::With` body, decremented
@@ -510,7 +524,7 @@ impl<'a> FunctionLower<'a> {
         };
         match expr {
             Expr::Identifier(s) => record(s, out, seen),
-            Expr::Call(name, args_ref) => {
+            Expr::Call(name, args_ref, _) => {
                 record(name, out, seen);
                 self.walk_closure_for_captures(&args_ref, bound, out, seen);
             }
@@ -568,7 +582,7 @@ impl<'a> FunctionLower<'a> {
                     self.walk_closure_for_captures(a, bound, out, seen);
                 }
             }
-            Expr::StructLiteral(_, fields) => {
+            Expr::StructLiteral(_, fields, _) => {
                 for (_, e) in &fields {
                     self.walk_closure_for_captures(e, bound, out, seen);
                 }
@@ -582,12 +596,12 @@ impl<'a> FunctionLower<'a> {
                     self.walk_closure_for_captures(&e, bound, out, seen);
                 }
             }
-            Expr::SliceAssign(obj, start, end, value) => {
+            Expr::SliceAssign(obj, info, value) => {
                 self.walk_closure_for_captures(&obj, bound, out, seen);
-                if let Some(s) = start {
+                if let Some(s) = info.start {
                     self.walk_closure_for_captures(&s, bound, out, seen);
                 }
-                if let Some(e) = end {
+                if let Some(e) = info.end {
                     self.walk_closure_for_captures(&e, bound, out, seen);
                 }
                 self.walk_closure_for_captures(&value, bound, out, seen);
@@ -617,11 +631,31 @@ impl<'a> FunctionLower<'a> {
                 }
                 self.walk_closure_for_captures(&body, &mut nested_bound, out, seen);
             }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                self.walk_closure_for_captures(&source, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(var);
+                for e in filter.iter().chain([&element]).chain(&value) {
+                    self.walk_closure_for_captures(e, &mut nested_bound, out, seen);
+                }
+            }
+            Expr::WithResource { name, resource, body } => {
+                self.walk_closure_for_captures(&resource, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(name);
+                self.walk_closure_for_captures(&body, &mut nested_bound, out, seen);
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.walk_closure_for_captures(&body, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(error);
+                self.walk_closure_for_captures(&handler, &mut nested_bound, out, seen);
+            }
             Expr::QualifiedIdentifier(_)
             | Expr::Int64(_) | Expr::UInt64(_) | Expr::Float64(_)
             | Expr::Int8(_) | Expr::Int16(_) | Expr::Int32(_)
             | Expr::UInt8(_) | Expr::UInt16(_) | Expr::UInt32(_)
-            | Expr::Number(_) | Expr::String(_)
+            | Expr::Number(_) | Expr::String(_) | Expr::Bytes(_)
             | Expr::True | Expr::False | Expr::Null => {}
         }
     }
@@ -662,7 +696,13 @@ impl<'a> FunctionLower<'a> {
                 self.walk_closure_for_captures(cond, bound, out, seen);
                 self.walk_closure_for_captures(body, bound, out, seen);
             }
-            Stmt::Break(_) | Stmt::Continue(_) => {}
+            Stmt::Break(_, value) => {
+                if let Some(e) = value {
+                    self.walk_closure_for_captures(e, bound, out, seen);
+                }
+            }
+            Stmt::Continue(_) => {}
+            Stmt::Yield(e) => self.walk_closure_for_captures(e, bound, out, seen),
             Stmt::StructDecl { .. }
             | Stmt::ImplBlock { .. }
             | Stmt::EnumDecl { .. }
@@ -1085,22 +1125,28 @@ impl<'a> FunctionLower<'a> {
             scope.push(DropTarget {
                 struct_id,
                 field_locals: leaves,
+                method: "drop",
             });
         }
     }
 
-    /// Synthesize and emit `<binding>.drop()` for an auto-drop
-    /// target. Looks the Drop method's `FuncId` up in the per-
-    /// `(struct, "drop")` registry, builds the receiver-leaf arg
+    /// Synthesize and emit `<binding>.drop()` (or `.__close__()`)
+    /// for an auto-drop target. Looks the method's `FuncId` up in
+    /// the per-`(struct, method)` registry, builds the receiver-leaf arg
     /// list, and emits `CallWithSelfWriteback` so the `&mut
     /// self` writeback semantics propagate any field mutation
     /// the body made.
     fn emit_drop_call(&mut self, target: &DropTarget) -> Result<(), String> {
         let struct_def = self.module.struct_def(target.struct_id);
         let struct_sym = struct_def.base_name;
-        let drop_sym = match self.interner.get("drop") {
+        let drop_sym = match self.interner.get(target.method) {
             Some(s) => s,
-            None => return Err("auto-drop: `drop` symbol missing from interner".to_string()),
+            None => {
+                return Err(format!(
+                    "auto-drop: `{}` symbol missing from interner",
+                    target.method
+                ))
+            }
         };
         // Look up the Drop method's FuncId. Use the receiver's
         // type-args so generic-struct Drop impls dispatch
@@ -1116,7 +1162,8 @@ impl<'a> FunctionLower<'a> {
             None => {
                 let s = self.interner.resolve(struct_sym).unwrap_or("?");
                 return Err(format!(
-                    "auto-drop: no `drop` FuncId registered for struct `{s}`"
+                    "auto-drop: no `{}` FuncId registered for struct `{s}`",
+                    target.method
                 ));
             }
         };
@@ -8,6 +8,7 @@
 //! live here too so the display path is self-contained.
 
 use frontend::ast::{Expr, ExprRef};
+use string_interner::DefaultSymbol;
 
 use super::bindings::{
     flatten_struct_locals, flatten_tuple_element_locals, Binding, EnumStorage, FieldBinding,
@@ -55,6 +56,16 @@ impl<'a> FunctionLower<'a> {
             && let Some(binding) = self.bindings.get(&sym).cloned() {
                 match binding {
                     Binding::Struct { struct_id, fields } => {
+                        if let Some(str_sym) = self.struct_str_method(struct_id) {
+                            let v = self
+                                .lower_method_call(&args[0], str_sym, &Vec::new())?
+                                .ok_or_else(|| "__str__ produced no value".to_string())?;
+                            self.emit(
+                                InstKind::Print { value: v, value_ty: Type::Str, newline },
+                                None,
+                            );
+                            return Ok(None);
+                        }
                         self.emit_print_struct(struct_id, &fields, newline);
                         return Ok(None);
                     }
@@ -77,6 +88,10 @@ impl<'a> FunctionLower<'a> {
                         self.emit_print_array(element_ty, length, slot, newline);
                         return Ok(None);
                     }
+                    Binding::NestedArray { element_ty, dims, slot } => {
+                        self.emit_print_nested_array(element_ty, &dims, slot, 0, newline);
+                        return Ok(None);
+                    }
                     Binding::FunctionPtr { .. } => {
                         // Closures Phase 5b: printing a function-
                         // pointer value isn't meaningful (no
@@ -99,7 +114,7 @@ impl<'a> FunctionLower<'a> {
         // `val` annotation (no annotation hint reaches this path).
         if let Some(arg_expr) = self.program.expression.get(&args[0]) {
             match arg_expr {
-                Expr::StructLiteral(struct_name, literal_fields) => {
+                Expr::StructLiteral(struct_name, literal_fields, _) => {
                     let struct_id =
                         self.resolve_struct_instance(struct_name, None)?;
                     let fields = self.allocate_struct_fields(struct_id);
@@ -159,7 +174,7 @@ impl<'a> FunctionLower<'a> {
                     self.emit_print_enum(&storage, newline)?;
                     return Ok(None);
                 }
-                Expr::Call(fn_name, args_ref)
+                Expr::Call(fn_name, args_ref, _)
                     if self
                         .module
                         .lookup_function(None, fn_name)
@@ -441,6 +456,22 @@ impl<'a> FunctionLower<'a> {
         Ok(None)
     }
 
+    /// `__str__` display protocol: the method symbol when the
+    /// struct's impl defines `__str__`. Only top-level struct
+    /// bindings consult it; a struct nested in another value still
+    /// prints field by field in this compiler MVP.
+    pub(super) fn struct_str_method(&self, struct_id: StructId) -> Option<DefaultSymbol> {
+        let str_sym = self.interner.get("__str__")?;
+        let def = self.module.struct_def(struct_id);
+        super::method_registry::lookup_method_func(
+            self.method_func_ids,
+            def.base_name,
+            str_sym,
+            &def.type_args,
+        )?;
+        Some(str_sym)
+    }
+
     /// Emit the `Name { field: value, ... }` rendering for a struct
     /// binding. Field order matches the interpreter's
     /// `Object::to_display_string`: alphabetical by name. Nested struct
@@ -576,6 +607,49 @@ impl<'a> FunctionLower<'a> {
         self.emit_print_raw_text("]".to_string(), newline);
     }
 
+    /// Print a `Binding::NestedArray` as nested brackets
+    /// (`[[1, 2], [3, 4]]`), matching the interpreter. `base` is the
+    /// flat index of the first scalar of the sub-array being printed.
+    fn emit_print_nested_array(
+        &mut self,
+        element_ty: Type,
+        dims: &[usize],
+        slot: ArraySlotId,
+        base: usize,
+        newline: bool,
+    ) {
+        let inner: usize = dims[1..].iter().product();
+        self.emit_print_raw_text("[".to_string(), false);
+        for i in 0..dims[0] {
+            if i > 0 {
+                self.emit_print_raw_text(", ".to_string(), false);
+            }
+            let flat = base + i * inner;
+            if dims.len() > 1 {
+                self.emit_print_nested_array(element_ty, &dims[1..], slot, flat, false);
+                continue;
+            }
+            let idx_v = self
+                .emit(InstKind::Const(Const::U64(flat as u64)), Some(Type::U64))
+                .expect("Const returns a value");
+            let v = self
+                .emit(
+                    InstKind::ArrayLoad { slot, index: idx_v, elem_ty: element_ty },
+                    Some(element_ty),
+                )
+                .expect("ArrayLoad returns a value");
+            self.emit(
+                InstKind::Print {
+                    value: v,
+                    value_ty: element_ty,
+                    newline: false,
+                },
+                None,
+            );
+        }
+        self.emit_print_raw_text("]".to_string(), newline);
+    }
+
     /// Render a struct's display header (`Name` or `Name<T1, T2, ...>`)
     /// for `print` / `println`. Generic instantiations include the
     /// concrete type-argument list so callers can tell `Cell<u64>`
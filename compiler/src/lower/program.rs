@@ -68,6 +68,13 @@ fn libm_import_name_for(name: &str) -> Option<&'static str> {
         // platforms we target it returns `i64::MIN` unchanged
         // (matches the legacy `BuiltinMethod::I64Abs` semantics).
         "__extern_abs_i64" => "labs",
+        // u64 bit manipulation has no libc counterpart; these live
+        // in the `runtime/toylang_rt.c` shim instead.
+        "__extern_count_ones_u64" => "toy_count_ones_u64",
+        "__extern_leading_zeros_u64" => "toy_leading_zeros_u64",
+        "__extern_trailing_zeros_u64" => "toy_trailing_zeros_u64",
+        "__extern_rotate_left_u64" => "toy_rotate_left_u64",
+        "__extern_rotate_right_u64" => "toy_rotate_right_u64",
         _ => return None,
     })
 }
@@ -270,6 +277,7 @@ pub fn lower_program(
     interner: &DefaultStringInterner,
     contract_msgs: &crate::ContractMessages,
     release: bool,
+    bounds_check: bool,
 ) -> Result<Module, String> {
     let mut module = Module::new();
 
@@ -466,6 +474,7 @@ pub fn lower_program(
             .map(|(_, t)| matches!(t, TypeDecl::Ref { .. }))
             .collect();
         module.function_mut(func_id).param_is_ref = param_is_ref;
+        module.function_mut(func_id).inline = func.inline;
         // REF-Stage-2 (ii): pre-populate the writeback shape from
         // the parameter types so callers see the correct number
         // of trailing return values regardless of whether the
@@ -669,6 +678,7 @@ pub fn lower_program(
         // body see the correct trailing-return layout. Same
         // helper used by generic-method instantiation.
         populate_method_writeback_types(&mut module, func_id, method);
+        module.function_mut(func_id).inline = method.inline;
         method_func_ids
             .entry((*target_sym, *method_sym))
             .or_default()
@@ -730,6 +740,7 @@ pub fn lower_program(
             &const_values,
             contract_msgs,
             release,
+            bounds_check,
             &method_registry,
             &method_func_ids,
             &generic_methods,
@@ -785,6 +796,7 @@ pub fn lower_program(
             &const_values,
             contract_msgs,
             release,
+            bounds_check,
             &method_registry,
             &method_func_ids,
             &generic_methods,
@@ -826,6 +838,7 @@ pub fn lower_program(
                 &const_values,
                 contract_msgs,
                 release,
+                bounds_check,
                 &method_registry,
                 &method_func_ids,
                 &generic_methods,
@@ -876,6 +889,7 @@ pub fn lower_program(
                 &const_values,
                 contract_msgs,
                 release,
+                bounds_check,
                 &method_registry,
                 &method_func_ids,
                 &generic_methods,
@@ -915,6 +929,7 @@ pub fn lower_program(
             &const_values,
             contract_msgs,
             release,
+            bounds_check,
             &method_registry,
             &method_func_ids,
             &generic_methods,
@@ -956,6 +971,7 @@ impl<'a> FunctionLower<'a> {
         const_values: &'a ConstValues,
         contract_msgs: &'a crate::ContractMessages,
         release: bool,
+        bounds_check: bool,
         method_registry: &'a MethodRegistry,
         method_func_ids: &'a MethodFuncIds,
         generic_methods: &'a GenericMethods,
@@ -973,10 +989,12 @@ impl<'a> FunctionLower<'a> {
             const_values,
             contract_msgs,
             release,
+            bounds_check,
             ensures: Vec::new(),
             result_sym: interner.get("result"),
             bindings: HashMap::new(),
             loop_stack: Vec::new(),
+            loop_result_types: HashMap::new(),
             with_scope_depth: 0,
             with_scope_arena_drops: Vec::new(),
             drop_scopes: Vec::new(),
@@ -1116,6 +1134,7 @@ impl<'a> FunctionLower<'a> {
             code: method.code,
             is_extern: false,
             visibility: method.visibility.clone(),
+            inline: method.inline,
         };
         // Stage 1 of `&` references: remember whether this body
         // is a `&mut self` method. After parameter binding (in
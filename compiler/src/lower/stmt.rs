@@ -270,8 +270,11 @@ impl<'a> FunctionLower<'a> {
                 }
                 Ok(None)
             }
-            Stmt::Break(label) => {
-                let (_lbl, _cont, brk, with_depth, drop_depth) = *self
+            Stmt::Break(label, value) => {
+                if let Some(value) = value {
+                    self.store_loop_result(label, &value)?;
+                }
+                let (_lbl, _cont, brk, with_depth, drop_depth, _) = *self
                     .resolve_loop_frame(label, "break")?;
                 // Phase 5 (汎用 RAII): emit auto-drops for any
                 // user-Drop bindings introduced inside the loop
@@ -284,7 +287,7 @@ impl<'a> FunctionLower<'a> {
                 Ok(None)
             }
             Stmt::Continue(label) => {
-                let (_lbl, cont, _brk, with_depth, drop_depth) = *self
+                let (_lbl, cont, _brk, with_depth, drop_depth, _) = *self
                     .resolve_loop_frame(label, "continue")?;
                 self.emit_drop_scopes_to_depth(drop_depth)?;
                 self.emit_with_scope_cleanup(with_depth);
@@ -304,6 +307,11 @@ impl<'a> FunctionLower<'a> {
             Stmt::ImplBlock { .. } | Stmt::EnumDecl { .. } | Stmt::TraitDecl { .. } => Err(
                 "compiler MVP cannot lower impl / enum / trait declarations yet".to_string(),
             ),
+            // Suspending mid-body needs the interpreter's saved
+            // continuations; there is no native lowering.
+            Stmt::Yield(_) => Err(
+                "compiler MVP cannot lower generator functions (`yield`); run them with the interpreter".to_string(),
+            ),
         }
     }
 }
@@ -45,6 +45,7 @@ impl<'a> FunctionLower<'a> {
             // body for type inference. This lets `val x = with ... { e }`
             // bind to the right scalar type.
             Expr::With(_, body) => self.value_scalar(&body),
+            Expr::WithResource { body, .. } => self.value_scalar(&body),
             Expr::Cast(_, target_ty) => lower_scalar(&target_ty),
             Expr::Identifier(sym) => match self.bindings.get(&sym) {
                 Some(Binding::Scalar { ty, .. }) => Some(*ty),
@@ -130,15 +131,16 @@ impl<'a> FunctionLower<'a> {
                 _ => self.value_scalar(&operand),
             },
             Expr::Block(stmts) => {
-                if let Some(last) = stmts.last()
-                    && let Some(Stmt::Expression(e)) = self.program.statement.get(last) {
-                        return self.value_scalar(&e);
-                    }
-                None
+                match stmts.last().and_then(|last| self.program.statement.get(last)) {
+                    Some(Stmt::Expression(e)) => self.value_scalar(&e),
+                    // `loop { ... break v }` tail: recorded by `lower_while`.
+                    Some(Stmt::While(_, _, body)) => self.loop_result_types.get(&body).copied(),
+                    _ => None,
+                }
             }
             Expr::IfElifElse(_, then_body, _, _) => self.value_scalar(&then_body),
             Expr::Match(_, arms) => arms.iter().find_map(|a| self.value_scalar(&a.body)),
-            Expr::Call(fn_name, _) => {
+            Expr::Call(fn_name, _, _) => {
                 // Phase 6b: a FunctionPtr binding (HOF parameter
                 // or closure-returning call result) carries its
                 // own return type; resolve through the binding
@@ -218,13 +220,18 @@ impl<'a> FunctionLower<'a> {
                 if !matches!(info.slice_type, frontend::ast::SliceType::SingleElement) {
                     return None;
                 }
-                let obj_expr = self.program.expression.get(&obj)?;
+                let mut obj_expr = self.program.expression.get(&obj)?;
+                // `m[i][j]`: walk down to the nested-array root.
+                while let Expr::SliceAccess(inner, _) = obj_expr {
+                    obj_expr = self.program.expression.get(&inner)?;
+                }
                 let arr_sym = match obj_expr {
                     Expr::Identifier(s) => s,
                     _ => return None,
                 };
                 match self.bindings.get(&arr_sym)? {
                     Binding::Array { element_ty, .. } => Some(*element_ty),
+                    Binding::NestedArray { element_ty, .. } => Some(*element_ty),
                     _ => None,
                 }
             }
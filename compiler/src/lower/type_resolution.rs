@@ -101,6 +101,112 @@ impl<'a> FunctionLower<'a> {
         )
     }
 
+    /// `resolve_struct_instance` for an associated call
+    /// (`val b = Box::new(5u64)`). Without an annotation, each type
+    /// param of a generic struct is taken from the argument whose
+    /// parameter is declared as that param (`fn new(v: T) -> Self`).
+    /// The type checker leaves `Unknown` as the annotation of such a
+    /// binding, which counts as none.
+    pub(super) fn resolve_struct_instance_for_call(
+        &mut self,
+        base_name: DefaultSymbol,
+        fn_name: DefaultSymbol,
+        args: &[ExprRef],
+        annotation: Option<&TypeDecl>,
+    ) -> Result<StructId, String> {
+        if annotation.is_none_or(|a| *a == TypeDecl::Unknown)
+            && let Some(type_args) = self.infer_struct_type_args_from_call(base_name, fn_name, args)
+        {
+            return instantiate_struct(
+                self.module,
+                self.struct_defs,
+                self.enum_defs,
+                base_name,
+                type_args,
+                self.interner,
+            );
+        }
+        self.resolve_struct_instance(base_name, annotation)
+    }
+
+    /// `resolve_struct_instance` for a struct literal
+    /// (`val p = Pair { a: 3u64, b: 4u64 }`). Without an annotation,
+    /// each type param of a generic struct is taken from a field
+    /// declared as that param — the substitution the type checker
+    /// infers for the same literal.
+    pub(super) fn resolve_struct_instance_for_literal(
+        &mut self,
+        base_name: DefaultSymbol,
+        fields: &[(DefaultSymbol, ExprRef)],
+        annotation: Option<&TypeDecl>,
+    ) -> Result<StructId, String> {
+        if annotation.is_none_or(|a| *a == TypeDecl::Unknown)
+            && let Some(type_args) = self.infer_struct_type_args_from_literal(base_name, fields)
+        {
+            return instantiate_struct(
+                self.module,
+                self.struct_defs,
+                self.enum_defs,
+                base_name,
+                type_args,
+                self.interner,
+            );
+        }
+        self.resolve_struct_instance(base_name, annotation)
+    }
+
+    fn infer_struct_type_args_from_literal(
+        &self,
+        base_name: DefaultSymbol,
+        fields: &[(DefaultSymbol, ExprRef)],
+    ) -> Option<Vec<Type>> {
+        let template = self.struct_defs.get(&base_name)?;
+        if template.generic_params.is_empty() {
+            return None;
+        }
+        template
+            .generic_params
+            .iter()
+            .map(|param| {
+                let (field_name, _) = template.fields.iter().find(|(_, ty)| {
+                    matches!(ty, TypeDecl::Generic(p) | TypeDecl::Identifier(p) if p == param)
+                })?;
+                let (_, value) = fields
+                    .iter()
+                    .find(|(sym, _)| self.interner.resolve(*sym) == Some(field_name.as_str()))?;
+                self.value_scalar(value)
+            })
+            .collect()
+    }
+
+    fn infer_struct_type_args_from_call(
+        &self,
+        base_name: DefaultSymbol,
+        fn_name: DefaultSymbol,
+        args: &[ExprRef],
+    ) -> Option<Vec<Type>> {
+        let template = self.struct_defs.get(&base_name)?;
+        if template.generic_params.is_empty() {
+            return None;
+        }
+        let method = super::method_registry::lookup_method_template(
+            self.generic_methods, base_name, fn_name, &[],
+        )?;
+        if method.has_self_param {
+            return None;
+        }
+        template
+            .generic_params
+            .iter()
+            .map(|param| {
+                let index = method.parameter.iter().position(|(_, ty)| {
+                    matches!(ty, TypeDecl::Generic(p) | TypeDecl::Identifier(p) if p == param)
+                })?;
+                self.value_scalar(args.get(index)?)
+            })
+            .collect()
+    }
+
     /// Pull a `Vec<Type>` of concrete type args from a val/var
     /// annotation that names this struct. Mirrors
     /// `extract_enum_type_args`.
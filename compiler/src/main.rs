@@ -1,16 +1,23 @@
 //! CLI front-end for the AOT compiler.
 //!
 //! Usage:
-//!   compiler <input.t> [-o <output>] [--emit ir|obj|exe] [-v]
+//!   compiler <input.t|-> [-o <output>] [--emit ir|obj|exe|metrics] [--define NAME[=value]]
+//!            [-O0|-O1|-O2|-O3|-Os] [--target-triple <triple>] [--timings[=text|json]] [-v]
 //!
 //! Default `--emit` is `exe`. `--emit=ir` writes Cranelift IR text;
 //! `--emit=obj` writes the unlinked object file. The `-o` flag is the
-//! path of the produced artefact regardless of `--emit`.
+//! path of the produced artefact regardless of `--emit`, except that
+//! `--emit=metrics` / `metrics-json` print to stdout without it. `-O`
+//! picks cranelift's `opt_level` (`-O0` = none, `-Os` = speed_and_size,
+//! the rest = speed); `--target-triple` cross-compiles to an object file.
+//! `--timings` prints the time spent parsing, resolving modules, type
+//! checking and generating code to stderr; `-v` includes it as text.
 
 use std::path::PathBuf;
 use std::process::ExitCode;
 
-use compiler::{compile_file, CompilerOptions, EmitKind};
+use compiler::{compile_file, CompilerOptions, EmitKind, OptLevel};
+use compiler_core::TimingFormat;
 
 fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().skip(1).collect();
@@ -41,7 +48,12 @@ fn parse_args(args: &[String]) -> Result<CompilerOptions, String> {
     let mut emit = EmitKind::Executable;
     let mut verbose = false;
     let mut release = false;
+    let mut bounds_check = false;
     let mut core_modules_dir: Option<PathBuf> = None;
+    let mut defines = frontend::CfgEnv::new();
+    let mut opt_level: Option<OptLevel> = None;
+    let mut target_triple: Option<String> = None;
+    let mut timings: Option<TimingFormat> = None;
     let mut i = 0;
     while i < args.len() {
         let a = &args[i];
@@ -52,6 +64,9 @@ fn parse_args(args: &[String]) -> Result<CompilerOptions, String> {
             }
             "-v" | "--verbose" => verbose = true,
             "--release" => release = true,
+            "--bounds-check" => bounds_check = true,
+            "--timings" => timings = Some(TimingFormat::Text),
+            s if s.starts_with("--timings=") => timings = Some(TimingFormat::parse(&s["--timings=".len()..])?),
             "-o" => {
                 i += 1;
                 let v = args.get(i).ok_or_else(|| "-o needs an argument".to_string())?;
@@ -75,7 +90,26 @@ fn parse_args(args: &[String]) -> Result<CompilerOptions, String> {
             s if s.starts_with("--core-modules=") => {
                 core_modules_dir = Some(PathBuf::from(&s["--core-modules=".len()..]));
             }
-            s if s.starts_with('-') => {
+            "--define" => {
+                i += 1;
+                let v = args
+                    .get(i)
+                    .ok_or_else(|| "--define needs a NAME=value argument".to_string())?;
+                defines.define_arg(v)?;
+            }
+            s if s.starts_with("--define=") => defines.define_arg(&s["--define=".len()..])?,
+            s if s.starts_with("-O") => opt_level = Some(OptLevel::parse(&s["-O".len()..])?),
+            "--target-triple" => {
+                i += 1;
+                let v = args
+                    .get(i)
+                    .ok_or_else(|| "--target-triple needs a triple argument".to_string())?;
+                target_triple = Some(v.clone());
+            }
+            s if s.starts_with("--target-triple=") => {
+                target_triple = Some(s["--target-triple=".len()..].to_string());
+            }
+            s if s.starts_with('-') && s != "-" => {
                 return Err(format!("unknown flag: {s}"));
             }
             _ => {
@@ -94,8 +128,13 @@ fn parse_args(args: &[String]) -> Result<CompilerOptions, String> {
         emit,
         verbose,
         release,
+        bounds_check,
         core_modules_dir,
         link_cache_dir: None,
+        defines,
+        opt_level,
+        target_triple,
+        timings,
     })
 }
 
@@ -105,12 +144,14 @@ fn parse_emit(s: &str) -> Result<EmitKind, String> {
         "obj" | "object" => Ok(EmitKind::Object),
         "ir" => Ok(EmitKind::Ir),
         "clif" => Ok(EmitKind::Clif),
+        "metrics" => Ok(EmitKind::Metrics),
+        "metrics-json" => Ok(EmitKind::MetricsJson),
         other => Err(format!("unknown --emit kind: {other}")),
     }
 }
 
 fn print_usage() {
     eprintln!(
-        "usage: compiler <input.t> [-o <output>] [--emit exe|obj|ir|clif] [--release] [-v]"
+        "usage: compiler <input.t|-> [-o <output>] [--emit exe|obj|ir|clif|metrics|metrics-json] [--define NAME[=value]] [-O0|-O1|-O2|-O3|-Os] [--target-triple <triple>] [--release] [--bounds-check] [--timings[=text|json]] [-v]"
     );
 }
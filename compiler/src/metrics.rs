@@ -0,0 +1,219 @@
+//! Per-function code metrics for `--emit=metrics`.
+//!
+//! Two of the numbers come from the source: how many statements a body
+//! holds and how deep its control flow nests (`if` / `match` / loops /
+//! closures, counted the same way as the `deep-nesting` lint). The third,
+//! cyclomatic complexity, is read off the lowered IR: `E - N + 2` over the
+//! blocks reachable from the entry (see `flow::ControlFlowGraph`). Because
+//! it is measured after lowering, `&&` / `||`, `?` and contract checks
+//! count as the decision points they compile to.
+//!
+//! Only the user's own functions and methods are reported — callers pass
+//! the program as parsed, before any module is integrated.
+
+use std::fmt::Write as _;
+
+use frontend::ast::{Expr, ExprRef, Program, Stmt, StmtRef};
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+
+use crate::flow::ControlFlowGraph;
+use crate::ir::{Function, Module};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionMetrics {
+    /// `name` for free functions, `Type::name` for methods.
+    pub name: String,
+    /// 1-based line of the `fn` keyword.
+    pub line: usize,
+    pub statements: usize,
+    pub max_nesting: usize,
+    /// `None` when the function has no IR body: generic functions that
+    /// were never instantiated, and methods on generic types.
+    pub cyclomatic: Option<usize>,
+}
+
+/// Measure every non-`extern` function and method declared in `program`.
+/// `ir` is the module lowered from the type-checked copy of the same
+/// program. Results are in source order.
+pub fn collect(program: &Program, interner: &DefaultStringInterner, source: &str, ir: &Module) -> Vec<FunctionMetrics> {
+    let name = |sym: DefaultSymbol| interner.resolve(sym).unwrap_or("<unknown>").to_string();
+    let line = |offset: usize| source[..offset.min(source.len())].matches('\n').count() + 1;
+    let mut out = Vec::new();
+    for func in program.function.iter().filter(|f| !f.is_extern) {
+        let fn_name = name(func.name);
+        let ir_func = match ir.function_index.get(&(None, func.name)) {
+            Some(id) => Some(ir.function(*id)),
+            // Generic functions are keyed by their mangled instance
+            // names; any instantiation has the same shape.
+            None => ir.functions.iter().find(|f| f.export_name.starts_with(&format!("toy_{fn_name}__"))),
+        };
+        out.push(measure(program, fn_name, line(func.node.start), func.code, ir_func));
+    }
+    for i in 0..program.statement.len() {
+        let Some(Stmt::ImplBlock { target_type, target_type_args, methods, .. }) = program.statement.get(&StmtRef(i as u32))
+        else {
+            continue;
+        };
+        let target = name(target_type);
+        for method in &methods {
+            let method_name = name(method.name);
+            let export_name = format!("toy_{target}__{method_name}");
+            let ir_func = if target_type_args.is_empty() {
+                ir.functions.iter().find(|f| f.export_name == export_name)
+            } else {
+                None
+            };
+            let qualified = format!("{target}::{method_name}");
+            out.push(measure(program, qualified, line(method.node.start), method.code, ir_func));
+        }
+    }
+    out.sort_by_key(|m| m.line);
+    out
+}
+
+fn measure(program: &Program, name: String, line: usize, code: StmtRef, ir_func: Option<&Function>) -> FunctionMetrics {
+    let mut walk = BodyWalk { program, depth: 0, max_nesting: 0, statements: 0 };
+    walk.stmt(&code);
+    FunctionMetrics {
+        name,
+        line,
+        statements: walk.statements,
+        max_nesting: walk.max_nesting,
+        cyclomatic: ir_func.map(cyclomatic_complexity),
+    }
+}
+
+/// McCabe's `E - N + 2` over the reachable part of `func`'s CFG.
+pub fn cyclomatic_complexity(func: &Function) -> usize {
+    let cfg = ControlFlowGraph::new(func);
+    let nodes = cfg.reverse_postorder().len();
+    let edges: usize = cfg.reverse_postorder().iter().map(|b| cfg.successors(*b).len()).sum();
+    (edges + 2).saturating_sub(nodes).max(1)
+}
+
+/// Fixed-width table, one row per function.
+pub fn render_table(metrics: &[FunctionMetrics]) -> String {
+    let width = metrics.iter().map(|m| m.name.len()).chain(["function".len()]).max().unwrap_or(0);
+    let mut out = format!("{:<width$}  {:>5}  {:>10}  {:>7}  {:>10}\n", "function", "line", "statements", "nesting", "cyclomatic");
+    for m in metrics {
+        let cyclomatic = m.cyclomatic.map_or_else(|| "-".to_string(), |c| c.to_string());
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>5}  {:>10}  {:>7}  {:>10}",
+            m.name, m.line, m.statements, m.max_nesting, cyclomatic
+        );
+    }
+    out
+}
+
+/// JSON array of objects; a missing cyclomatic complexity is `null`.
+pub fn render_json(metrics: &[FunctionMetrics]) -> String {
+    let rows: Vec<String> = metrics
+        .iter()
+        .map(|m| {
+            let cyclomatic = m.cyclomatic.map_or_else(|| "null".to_string(), |c| c.to_string());
+            format!(
+                "  {{\"function\": \"{}\", \"line\": {}, \"statements\": {}, \"nesting\": {}, \"cyclomatic\": {}}}",
+                m.name, m.line, m.statements, m.max_nesting, cyclomatic
+            )
+        })
+        .collect();
+    if rows.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+/// Statement count and control-flow nesting of one body.
+struct BodyWalk<'a> {
+    program: &'a Program,
+    depth: usize,
+    max_nesting: usize,
+    statements: usize,
+}
+
+impl BodyWalk<'_> {
+    fn nested(&mut self, exprs: &[&ExprRef]) {
+        self.depth += 1;
+        self.max_nesting = self.max_nesting.max(self.depth);
+        for e in exprs {
+            self.expr(e);
+        }
+        self.depth -= 1;
+    }
+
+    fn exprs<'e>(&mut self, exprs: impl IntoIterator<Item = &'e ExprRef>) {
+        for e in exprs {
+            self.expr(e);
+        }
+    }
+
+    fn stmt(&mut self, stmt_ref: &StmtRef) {
+        let Some(stmt) = self.program.statement.get(stmt_ref) else {
+            return;
+        };
+        match stmt {
+            Stmt::Expression(e) | Stmt::Val(_, _, e) | Stmt::Return(Some(e)) | Stmt::Break(_, Some(e)) => self.expr(&e),
+            Stmt::Var(_, _, Some(e)) => self.expr(&e),
+            Stmt::For(_, _, start, end, body) => {
+                self.exprs([&start, &end]);
+                self.nested(&[&body]);
+            }
+            Stmt::While(_, cond, body) => {
+                self.expr(&cond);
+                self.nested(&[&body]);
+            }
+            _ => {}
+        }
+    }
+
+    fn expr(&mut self, expr_ref: &ExprRef) {
+        let Some(expr) = self.program.expression.get(expr_ref) else {
+            return;
+        };
+        match expr {
+            Expr::IfElifElse(cond, then, elifs, els) => {
+                self.expr(&cond);
+                self.nested(&[&then]);
+                for (c, b) in &elifs {
+                    self.expr(c);
+                    self.nested(&[b]);
+                }
+                self.nested(&[&els]);
+            }
+            Expr::Match(scrutinee, arms) => {
+                self.expr(&scrutinee);
+                let bodies: Vec<&ExprRef> = arms.iter().flat_map(|arm| arm.guard.iter().chain([&arm.body])).collect();
+                self.nested(&bodies);
+            }
+            Expr::Closure { body, .. } => self.nested(&[&body]),
+            Expr::Block(stmts) => {
+                self.statements += stmts.len();
+                for s in &stmts {
+                    self.stmt(s);
+                }
+            }
+            Expr::Assign(l, r) | Expr::Binary(_, l, r) | Expr::Range(l, r) | Expr::With(l, r) => self.exprs([&l, &r]),
+            Expr::WithResource { resource, body, .. } => self.exprs([&resource, &body]),
+            Expr::TryCatch { body, handler, .. } => self.exprs([&body, &handler]),
+            Expr::Unary(_, e) | Expr::FieldAccess(e, _) | Expr::TupleAccess(e, _) | Expr::Cast(e, _) => self.expr(&e),
+            Expr::ExprList(es) | Expr::ArrayLiteral(es) | Expr::TupleLiteral(es) => self.exprs(&es),
+            Expr::StructLiteral(_, fields, _) => self.exprs(fields.iter().map(|(_, e)| e)),
+            Expr::DictLiteral(entries) => {
+                for (k, v) in &entries {
+                    self.exprs([k, v]);
+                }
+            }
+            Expr::SliceAccess(e, info) => self.exprs([&e].into_iter().chain(&info.start).chain(&info.end)),
+            Expr::SliceAssign(target, info, value) => {
+                self.exprs([&target].into_iter().chain(&info.start).chain(&info.end).chain([&value]))
+            }
+            Expr::Call(_, args, _) => self.expr(&args),
+            Expr::AssociatedFunctionCall(_, _, args) | Expr::BuiltinCall(_, args) => self.exprs(&args),
+            Expr::MethodCall(receiver, _, args) | Expr::BuiltinMethodCall(receiver, _, args) => {
+                self.exprs([&receiver].into_iter().chain(&args))
+            }
+            _ => {}
+        }
+    }
+}
@@ -18,6 +18,49 @@ pub enum EmitKind {
     /// Emit Cranelift IR (`.clif`) text. Useful for debugging the
     /// backend codegen (post-IR).
     Clif,
+    /// Report per-function statement count, nesting depth and
+    /// cyclomatic complexity as a table (see `metrics`). Written to
+    /// standard output unless `-o` names a file.
+    Metrics,
+    /// Same report as `Metrics`, as a JSON array.
+    MetricsJson,
+}
+
+/// `-O` preset for the Cranelift backend. Cranelift itself only has
+/// three `opt_level` settings, so `-O1`..`-O3` all select `"speed"`;
+/// the distinct presets exist so build scripts written against the
+/// usual `-O` spelling keep working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+    /// `-Os`: optimise, but prefer smaller code.
+    Os,
+}
+
+impl OptLevel {
+    /// Parse the part after `-O` (`0`, `1`, `2`, `3` or `s`).
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "0" => Ok(OptLevel::O0),
+            "1" => Ok(OptLevel::O1),
+            "2" => Ok(OptLevel::O2),
+            "3" => Ok(OptLevel::O3),
+            "s" => Ok(OptLevel::Os),
+            other => Err(format!("unknown optimisation level: -O{other}")),
+        }
+    }
+
+    /// The matching value of cranelift's `opt_level` flag.
+    pub fn cranelift_name(self) -> &'static str {
+        match self {
+            OptLevel::O0 => "none",
+            OptLevel::O1 | OptLevel::O2 | OptLevel::O3 => "speed",
+            OptLevel::Os => "speed_and_size",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +74,11 @@ pub struct CompilerOptions {
     /// when the contract overhead matters and the predicates have been
     /// validated in a checked build.
     pub release: bool,
+    /// `--bounds-check`: guard every runtime-indexed array read and
+    /// write so an out-of-range index panics instead of touching the
+    /// memory past the array. Constant indices are always checked at
+    /// compile time.
+    pub bounds_check: bool,
     /// Override for the core-modules directory. When `None`, the
     /// driver consults `TOYLANG_CORE_MODULES` and then falls back to
     /// an executable-relative search (see
@@ -46,6 +94,21 @@ pub struct CompilerOptions {
     /// repeat runs of `cargo nextest` reuse linked binaries instead
     /// of re-invoking `cc` on every test.
     pub link_cache_dir: Option<PathBuf>,
+    /// `--define NAME=value` names for `@cfg(...)` predicates. The
+    /// driver adds `target = "native"` on top before pruning.
+    pub defines: frontend::CfgEnv,
+    /// `-O0`..`-O3` / `-Os`. `None` falls back to the
+    /// `TOYLANG_CRANELIFT_OPT_LEVEL` env var, then to `"speed"`.
+    pub opt_level: Option<OptLevel>,
+    /// `--target-triple`. `None` targets the host. Only the host
+    /// architecture's Cranelift backend is built in, so a foreign
+    /// triple must share the host's architecture (e.g. a different
+    /// OS / ABI), and only `--emit=obj|clif|ir` can be used with it.
+    pub target_triple: Option<String>,
+    /// `--timings[=text|json]`: print per-phase times to stderr once
+    /// the artefact is written. `None` still prints `text` under
+    /// `verbose`.
+    pub timings: Option<compiler_core::TimingFormat>,
 }
 
 impl CompilerOptions {
@@ -56,8 +119,13 @@ impl CompilerOptions {
             emit: EmitKind::Executable,
             verbose: false,
             release: false,
+            bounds_check: false,
             core_modules_dir: None,
             link_cache_dir: None,
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
         }
     }
 }
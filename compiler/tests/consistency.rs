@@ -50,8 +50,13 @@ fn compile_jit_lazy_core(source: &str) -> Result<compiler::JitProgram, String> {
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
         link_cache_dir: None,
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
     };
     if let Ok(prog) = compile_to_jit_main_with_options(source, &lite) {
         return Ok(prog);
@@ -170,6 +175,12 @@ fn jit_exit_code(source: &str, _stem: &str, with_core: bool) -> i32 {
     let options = RunOptions {
         jit: true,
         core_modules_dir: core_dir.as_deref(),
+        capabilities: Default::default(),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
     };
     let result = match interpreter::run_source(source, "test.t", &options) {
         Ok(RunOutcome { exit_code: Some(code) }) => code & 0xff,
@@ -203,8 +214,13 @@ fn try_compiler_exit_code(source: &str, stem: &str, with_core: bool) -> Option<i
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: if with_core { Some(core_modules_dir()) } else { None },
         link_cache_dir: Some(link_cache_dir_for_tests()),
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
     };
     let compile_ok = compile_file(&options).is_ok();
     let result = if compile_ok {
@@ -281,6 +297,12 @@ fn interpreter_stdout(source: &str, _stem: &str, with_core: bool) -> String {
     let options = RunOptions {
         jit: false,
         core_modules_dir: core_dir.as_deref(),
+        capabilities: Default::default(),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
     };
     let (result, captured) = interpreter::output::with_capture(|| {
         interpreter::run_source(source, "test.t", &options)
@@ -299,6 +321,12 @@ fn jit_stdout(source: &str, _stem: &str, with_core: bool) -> String {
     let options = RunOptions {
         jit: true,
         core_modules_dir: core_dir.as_deref(),
+        capabilities: Default::default(),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
     };
     let (result, captured) = interpreter::output::with_capture(|| {
         interpreter::run_source(source, "test.t", &options)
@@ -323,8 +351,13 @@ fn try_compiler_stdout(source: &str, stem: &str, with_core: bool) -> Option<Stri
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: if with_core { Some(core_modules_dir()) } else { None },
         link_cache_dir: Some(link_cache_dir_for_tests()),
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
     };
     let result = if compile_file(&options).is_ok() {
         let out = Command::new(&exe_path).output().expect("spawn binary");
@@ -610,6 +643,32 @@ fn while_with_break_match() {
     assert_consistent(src, "while_break");
 }
 
+#[test]
+fn loop_break_value_match() {
+    let src = r#"
+        fn first_square_above(limit: u64) -> u64 {
+            var i = 0u64
+            loop {
+                i = i + 1u64
+                if i * i > limit { break i }
+            }
+        }
+
+        fn main() -> u64 {
+            var n = 0u64
+            val tens = @outer: loop {
+                n = n + 1u64
+                while true {
+                    if n == 3u64 { break @outer n * 10u64 }
+                    break
+                }
+            }
+            tens + first_square_above(50u64)
+        }
+    "#;
+    assert_consistent(src, "loop_break_value");
+}
+
 #[test]
 fn if_elif_else_match() {
     let src = r#"
@@ -638,6 +697,23 @@ fn short_circuit_match() {
     assert_consistent(src, "short_circuit");
 }
 
+#[test]
+fn short_circuit_or_skips_panicking_rhs() {
+    // `true || rhs` must not run `rhs`; a panic there would change the
+    // exit code on whichever path evaluated it.
+    let src = r#"
+        fn boom() -> bool {
+            panic("rhs evaluated")
+        }
+        fn main() -> u64 {
+            val a: bool = true || boom()
+            val b: bool = false && boom()
+            if a && !b { 7u64 } else { 3u64 }
+        }
+    "#;
+    assert_consistent(src, "short_circuit_or");
+}
+
 #[test]
 fn nested_calls_match() {
     let src = r#"
@@ -1336,6 +1412,105 @@ fn ref_stage2_array_index_mut_borrow_round_trip() {
     assert_consistent(src, "ref_stage2_array_index_mut_borrow_round_trip");
 }
 
+#[test]
+fn negative_index_counts_from_end_match() {
+    // A negative `i64` index counts from the end in every engine:
+    // constant and runtime indices, reads, writes, compound writes,
+    // `&mut arr[i]` and range-slice bounds.
+    let src = r#"
+        fn add_in_place(target: &mut u64, delta: u64) {
+            target = target + delta
+        }
+        fn main() -> u64 {
+            var arr = [10u64, 20u64, 30u64, 40u64]
+            arr[-1i64] = 41u64
+            arr[-4i64] += 1u64
+            add_in_place(&mut arr[-2i64], 2u64)
+            var back = 0u64
+            var i = -1i64
+            while i >= -4i64 {
+                back = back * 2u64 + arr[i] % 2u64
+                i = i - 1i64
+            }
+            val tail = arr[-2i64..]
+            if tail[0u64] != 32u64 { return 1u64 }
+            if tail[1u64] != 41u64 { return 2u64 }
+            back * 100u64 + arr[0u64]
+        }
+    "#;
+    assert_consistent(src, "negative_index_counts_from_end");
+}
+
+#[test]
+fn range_assignment_match() {
+    // `arr[a..b] = src` copies element-wise in both engines, with
+    // omitted and negative bounds resolved like a range read.
+    let src = r#"
+        fn main() -> u64 {
+            var arr = [1u64, 2u64, 3u64, 4u64, 5u64, 6u64]
+            val mid = [20u64, 30u64]
+            arr[1..3] = mid
+            arr[-2..] = [50u64, 60u64]
+            arr[..1] = [9u64]
+            arr[0u64] + arr[1u64] + arr[2u64] + arr[3u64] + arr[4u64] + arr[5u64]
+        }
+    "#;
+    assert_consistent(src, "range_assignment");
+}
+
+#[test]
+fn nested_array_indexing_match() {
+    // `[[T; C]; R]` literals, `m[i][j]` reads / writes / compound
+    // writes with constant, runtime and negative indices, and
+    // nested-array printing agree across engines.
+    let src = r#"
+        fn main() -> u64 {
+            var m: [[u64; 3]; 2] = [[1u64, 2u64, 3u64], [4u64, 5u64, 6u64]]
+            var c = [[[1i64, 2i64], [3i64, 4i64]], [[5i64, 6i64], [7i64, 8i64]]]
+            m[1][2] = 60u64
+            m[0][1] += 10u64
+            val k = -1i64
+            c[k][0][k] *= 10i64
+            println(m)
+            println(c)
+            var s = 0u64
+            var i = 0u64
+            while i < 2u64 {
+                var j = 0u64
+                while j < 3u64 {
+                    s = s + m[i][j]
+                    j = j + 1u64
+                }
+                i = i + 1u64
+            }
+            s + c[1][0][1] as u64
+        }
+    "#;
+    assert_consistent(src, "nested_array_indexing");
+}
+
+#[test]
+fn comprehension_match() {
+    // Unfiltered array comprehensions over a constant range, a const
+    // bound, a negative i64 range and another array binding lower to
+    // the same values the interpreter builds. The loop variable must
+    // not leak over an outer binding of the same name.
+    let src = r#"
+        const N: u64 = 5u64
+        fn main() -> u64 {
+            val x = 100u64
+            val sq = [x * x for x in 0..N]
+            val neg = [x * 3i64 for x in -2i64..2i64]
+            val halves = [v / 2u64 for v in sq]
+            println(sq)
+            println(neg)
+            println(halves)
+            x + sq[4] + halves[3]
+        }
+    "#;
+    assert_consistent(src, "comprehension");
+}
+
 #[test]
 fn ref_stage2_compound_mut_ref_propagates_round_trip() {
     // REF-Stage-2 (ii): compound `&mut T` parameter mutation
@@ -1793,6 +1968,52 @@ fn generic_raii_drop_lifo_round_trip() {
     assert_consistent(src, "generic_raii_drop_lifo_round_trip");
 }
 
+#[test]
+fn with_resource_close_round_trip() {
+    // `with name = expr { body }` calls `__close__` on the resource
+    // when the body ends, on linear exit and on `return` / `break`.
+    // The AOT path reuses the RAII drop scopes with a `__close__`
+    // target, so the close order matches the interpreter's.
+    let src = r#"
+        struct Handle { id: u64, log: ptr }
+        impl Handle {
+            fn __close__(&mut self) {
+                val cur: u64 = __builtin_ptr_read(self.log, 0u64)
+                __builtin_ptr_write(self.log, 0u64, cur * 10u64 + self.id)
+            }
+        }
+        fn early(log: ptr) -> u64 {
+            val h = Handle { id: 3u64, log: log }
+            with r = h {
+                if r.id == 3u64 {
+                    return 7u64
+                }
+                0u64
+            }
+        }
+        fn main() -> u64 {
+            val log: ptr = __builtin_heap_alloc(8u64)
+            __builtin_ptr_write(log, 0u64, 0u64)
+            val a = Handle { id: 1u64, log: log }
+            val v = with r = a {
+                r.id + 1u64
+            }
+            val e = early(log)
+            for i in 4u64 to 6u64 {
+                val h = Handle { id: i, log: log }
+                with r = h {
+                    if r.id == 5u64 {
+                        break
+                    }
+                }
+            }
+            val recorded: u64 = __builtin_ptr_read(log, 0u64)
+            recorded * 100u64 + v * 10u64 + e
+        }
+    "#;
+    assert_consistent(src, "with_resource_close_round_trip");
+}
+
 #[test]
 fn generic_raii_drop_on_early_return_round_trip() {
     // Phase 5 (汎用 RAII): early `return` from inside the
@@ -2772,6 +2993,45 @@ fn vec_user_space_round_trip() {
     assert_consistent(src, "vec_user_space_round_trip");
 }
 
+#[test]
+fn deque_user_space_round_trip() {
+    // `core/std/collections/deque.t::Deque<T>` keeps its ring
+    // buffer state in one heap block behind `state`, like
+    // `Channel<T>`, so every method takes `self: Self` and no
+    // `&mut self` writeback is involved. A pop before the first
+    // grow leaves `head` off slot 0, so the grow copies a wrapped
+    // buffer; `push_front` then wraps `head` below 0.
+    //
+    // Exit 42 means every step matched.
+    let src = r#"
+        fn main() -> u64 {
+            val q: Deque<u64> = Deque::new()
+            q.push_back(1u64)
+            q.push_back(2u64)
+            q.push_back(3u64)
+            val first: u64 = q.pop_front()
+            var i: u64 = 4u64
+            while i < 10u64 {
+                q.push_back(i)
+                i = i + 1u64
+            }
+            q.push_front(100u64)
+            val front: u64 = q.pop_front()
+            val second: u64 = q.pop_front()
+            val last: u64 = q.pop_back()
+            val mid: u64 = q.get(1u64)
+            if first != 1u64 { 1u64 }
+            elif front != 100u64 { 2u64 }
+            elif second != 2u64 { 3u64 }
+            elif last != 9u64 { 4u64 }
+            elif mid != 4u64 { 5u64 }
+            elif q.size() != 6u64 { 6u64 }
+            else { 42u64 }
+        }
+    "#;
+    assert_consistent(src, "deque_user_space_round_trip");
+}
+
 #[test]
 fn str_len_extension_method_round_trip() {
     // `core/std/str.t::Length::len(self) -> u64` returns the byte
@@ -3938,6 +4198,26 @@ fn string_interp_struct_round_trip() {
     assert_consistent(src, "string_interp_struct");
 }
 
+#[test]
+fn struct_str_protocol_round_trip() {
+    // A struct with `__str__` prints and interpolates through it on
+    // every backend instead of the field-by-field rendering.
+    let src = r#"
+        struct Point { x: i64, y: i64 }
+        impl Point {
+            fn __str__(self: Self) -> str { "({self.x}, {self.y})" }
+        }
+        fn main() -> i64 {
+            val p: Point = Point { x: 3i64, y: -5i64 }
+            println(p)
+            val s = "p = {p}"
+            println(s)
+            s.len() as i64
+        }
+    "#;
+    assert_stdout_consistent(src, "struct_str_protocol");
+}
+
 #[test]
 fn string_interp_arithmetic_round_trip() {
     let src = r#"
@@ -4362,3 +4642,66 @@ fn comparison_chain_three_ops_round_trip() {
     "#;
     assert_consistent(src, "comparison_chain_three_ops");
 }
+
+#[test]
+fn self_constructor_generic_impl_round_trip() {
+    // `Self { .. }` in a generic impl, called without an annotation:
+    // the instance's type argument comes from the constructor's
+    // argument.
+    let src = r#"
+        struct Wrapper<T> { value: T }
+        impl<T> Wrapper<T> {
+            fn new(value: T) -> Self { Self { value: value } }
+            fn get(self: Self) -> T { self.value }
+        }
+        fn main() -> u64 {
+            val w = Wrapper::new(41u64)
+            w.get() + 1u64
+        }
+    "#;
+    assert_consistent(src, "self_constructor_generic_impl");
+}
+
+#[test]
+fn generic_struct_literal_inferred_type_args_round_trip() {
+    // Two instances of one generic struct, neither annotated: the
+    // type args come from the literals' fields and pick separate
+    // monomorphised instances.
+    let src = r#"
+        struct Pair<T> { a: T, b: T }
+        impl<T> Pair<T> {
+            fn first(self: Self) -> T { self.a }
+            fn swap(self: Self) -> Pair<T> { Pair { a: self.b, b: self.a } }
+        }
+        fn main() -> u64 {
+            val p = Pair { a: 3u64, b: 4u64 }
+            val q = Pair { a: 10i64, b: 20i64 }
+            val s = p.swap()
+            val t = q.swap()
+            if t.first() == 20i64 { s.first() + p.first() } else { 0u64 }
+        }
+    "#;
+    assert_consistent(src, "generic_struct_literal_inferred_type_args");
+}
+
+#[test]
+fn newtype_struct_round_trip() {
+    // Tuple structs are rewritten into plain struct literals / field
+    // accesses by the type checker, so every backend sees ordinary
+    // structs.
+    let src = r#"
+        struct Meters(u64)
+        struct Pair(u64, i64)
+        impl Meters {
+            fn double(self: Self) -> Meters { Meters(self.0 * 2u64) }
+        }
+        fn main() -> u64 {
+            val m = Meters(21u64)
+            val d = m.double()
+            var p = Pair(1u64, -2i64)
+            p.0 = 3u64
+            d.0 + p.0 + (p.1 + 2i64) as u64
+        }
+    "#;
+    assert_consistent(src, "newtype_struct");
+}
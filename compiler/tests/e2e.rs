@@ -12,7 +12,7 @@
 use std::path::PathBuf;
 use std::process::{Command, Output};
 
-use compiler::{compile_file, CompilerOptions, EmitKind};
+use compiler::{compile_file, CompilerOptions, EmitKind, OptLevel};
 
 fn skip_e2e() -> bool {
     std::env::var("COMPILER_E2E").map(|v| v == "skip").unwrap_or(false)
@@ -80,8 +80,13 @@ fn try_compile_and_run(
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: core_dir,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     let result = if compile_file(&options).is_ok() {
         let status = Command::new(&exe_path)
@@ -126,8 +131,13 @@ fn compile_and_capture(source: &str, stem: &str) -> Output {
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&options).expect("compile_file failed");
     let output = Command::new(&exe_path).output().expect("spawn binary");
@@ -611,8 +621,13 @@ fn release_flag_skips_requires_check() {
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&opts_chk).expect("compile checked");
     let out_chk = Command::new(&exe_chk).output().expect("spawn checked");
@@ -627,8 +642,13 @@ fn release_flag_skips_requires_check() {
         emit: EmitKind::Executable,
         verbose: false,
         release: true,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&opts_rel).expect("compile release");
     let out_rel = Command::new(&exe_rel).output().expect("spawn release");
@@ -641,6 +661,319 @@ fn release_flag_skips_requires_check() {
     let _ = std::fs::remove_file(&src_path);
 }
 
+#[test]
+fn bounds_check_panics_on_runtime_index_past_the_end() {
+    // Unchecked builds index straight into the stack slot; with
+    // `--bounds-check` the first write past the end panics.
+    if skip_e2e() {
+        return;
+    }
+    let src = r#"
+        fn main() -> u64 {
+            var arr = [1u64, 2u64, 3u64]
+            var i = 0u64
+            while i < 4u64 {
+                arr[i] += 1u64
+                i = i + 1u64
+            }
+            arr[0u64]
+        }
+    "#;
+    let src_path = unique_path("bounds_chk.t");
+    std::fs::write(&src_path, src).unwrap();
+    let exe = unique_path("bounds_chk");
+    let options = CompilerOptions {
+        input: src_path.clone(),
+        output: Some(exe.clone()),
+        emit: EmitKind::Executable,
+        verbose: false,
+        release: false,
+        bounds_check: true,
+        core_modules_dir: None,
+        link_cache_dir: Some(link_cache_dir_for_tests()),
+        defines: Default::default(),
+        opt_level: None,
+        target_triple: None,
+        timings: None,
+    };
+    compile_file(&options).expect("compile with --bounds-check");
+    let out = Command::new(&exe).output().expect("spawn");
+    assert_eq!(out.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("panic: array index out of bounds"), "stdout: {stdout}");
+    let _ = std::fs::remove_file(&exe);
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn negative_constant_index_past_the_start_is_a_compile_error() {
+    if skip_e2e() {
+        return;
+    }
+    let src_path = unique_path("neg_idx.t");
+    std::fs::write(&src_path, "fn main() -> u64 {\n    val arr = [1u64, 2u64, 3u64]\n    arr[-4i64]\n}\n").unwrap();
+    let mut options = CompilerOptions::new(src_path.clone());
+    options.emit = EmitKind::Object;
+    options.output = Some(unique_path("neg_idx.o"));
+    let err = compile_file(&options).expect_err("-4 is out of range for length 3");
+    assert!(err.contains("array index -4 out of bounds (length 3)"), "{err}");
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn opt_level_presets_produce_the_same_program() {
+    if skip_e2e() {
+        return;
+    }
+    let src = r#"
+        fn fib(n: u64) -> u64 {
+            if n < 2u64 { n } else { fib(n - 1u64) + fib(n - 2u64) }
+        }
+
+        fn main() -> u64 {
+            fib(10u64)
+        }
+    "#;
+    let src_path = unique_path("opt_levels.t");
+    std::fs::write(&src_path, src).unwrap();
+    for level in [OptLevel::O0, OptLevel::O2, OptLevel::Os] {
+        let exe = unique_path("opt_levels");
+        let mut opts = CompilerOptions::new(src_path.clone());
+        opts.output = Some(exe.clone());
+        opts.link_cache_dir = Some(link_cache_dir_for_tests());
+        opts.opt_level = Some(level);
+        compile_file(&opts).unwrap_or_else(|e| panic!("compile at {level:?}: {e}"));
+        let out = Command::new(&exe).output().expect("spawn");
+        assert_eq!(out.status.code(), Some(55), "{level:?}");
+        let _ = std::fs::remove_file(&exe);
+    }
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn foreign_target_triple_emits_object_but_not_executable() {
+    if skip_e2e() {
+        return;
+    }
+    // Same architecture, different OS: the only kind of cross target
+    // the host-only cranelift build can produce.
+    let os = if cfg!(target_os = "macos") { "unknown-linux-gnu" } else { "apple-darwin" };
+    let triple = format!("{}-{os}", std::env::consts::ARCH);
+    let src_path = unique_path("cross.t");
+    std::fs::write(&src_path, "fn main() -> u64 { 7u64 }\n").unwrap();
+
+    let obj = unique_path("cross.o");
+    let mut opts = CompilerOptions::new(src_path.clone());
+    opts.output = Some(obj.clone());
+    opts.emit = EmitKind::Object;
+    opts.target_triple = Some(triple.clone());
+    compile_file(&opts).unwrap_or_else(|e| panic!("emit object for {triple}: {e}"));
+    assert!(std::fs::metadata(&obj).map(|m| m.len() > 0).unwrap_or(false));
+    let _ = std::fs::remove_file(&obj);
+
+    opts.emit = EmitKind::Executable;
+    opts.output = Some(unique_path("cross"));
+    let err = compile_file(&opts).expect_err("foreign executable must be refused");
+    assert!(err.contains("cannot link an executable"), "{err}");
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn inliner_respects_noinline_and_keeps_results() {
+    if skip_e2e() {
+        return;
+    }
+    let src = r#"
+        struct Point { x: u64, y: u64 }
+
+        impl Point {
+            fn get_x(&self) -> u64 {
+                self.x
+            }
+        }
+
+        fn sq(v: u64) -> u64 {
+            v * v
+        }
+
+        @noinline
+        fn twice(v: u64) -> u64 {
+            v + v
+        }
+
+        fn main() -> u64 {
+            val p = Point { x: 3u64, y: 4u64 }
+            var total = 0u64
+            var i = 0u64
+            while i < 3u64 {
+                total = total + sq(p.get_x()) + twice(i)
+                i = i + 1u64
+            }
+            total
+        }
+    "#;
+    let src_path = unique_path("inline.t");
+    std::fs::write(&src_path, src).unwrap();
+    let calls_at = |level: OptLevel| {
+        let ir_path = unique_path("inline.ir");
+        let mut opts = CompilerOptions::new(src_path.clone());
+        opts.output = Some(ir_path.clone());
+        opts.emit = EmitKind::Ir;
+        opts.opt_level = Some(level);
+        compile_file(&opts).unwrap_or_else(|e| panic!("emit ir at {level:?}: {e}"));
+        let text = std::fs::read_to_string(&ir_path).expect("ir file exists");
+        let _ = std::fs::remove_file(&ir_path);
+        text.matches("= call fn#").count()
+    };
+    // `-O0` leaves all three calls; otherwise only `@noinline twice` stays.
+    assert_eq!(calls_at(OptLevel::O0), 3);
+    assert_eq!(calls_at(OptLevel::O2), 1);
+
+    let exe = unique_path("inline");
+    let mut opts = CompilerOptions::new(src_path.clone());
+    opts.output = Some(exe.clone());
+    opts.link_cache_dir = Some(link_cache_dir_for_tests());
+    compile_file(&opts).expect("compile_file failed");
+    let out = Command::new(&exe).output().expect("spawn");
+    assert_eq!(out.status.code(), Some(33));
+    let _ = std::fs::remove_file(&exe);
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn repeated_subexpressions_are_computed_once() {
+    if skip_e2e() {
+        return;
+    }
+    let src = r#"
+        fn f(a: u64, b: u64) -> u64 {
+            a * b + b * a
+        }
+
+        fn main() -> u64 {
+            f(3u64, 7u64)
+        }
+    "#;
+    let src_path = unique_path("gvn.t");
+    std::fs::write(&src_path, src).unwrap();
+    let ir_path = unique_path("gvn.ir");
+    let mut opts = CompilerOptions::new(src_path.clone());
+    opts.output = Some(ir_path.clone());
+    opts.emit = EmitKind::Ir;
+    opts.opt_level = Some(OptLevel::O2);
+    compile_file(&opts).expect("emit ir");
+    let text = std::fs::read_to_string(&ir_path).expect("ir file exists");
+    let f_body = text.split("function toy_f").nth(1).and_then(|t| t.split("\n}").next()).unwrap_or_default();
+    assert_eq!(f_body.matches(" = mul ").count(), 1, "{text}");
+
+    let exe = unique_path("gvn");
+    opts.output = Some(exe.clone());
+    opts.emit = EmitKind::Executable;
+    opts.link_cache_dir = Some(link_cache_dir_for_tests());
+    compile_file(&opts).expect("compile_file failed");
+    let out = Command::new(&exe).output().expect("spawn");
+    assert_eq!(out.status.code(), Some(42));
+    let _ = std::fs::remove_file(&exe);
+    let _ = std::fs::remove_file(&ir_path);
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn metrics_report_counts_branches_and_nesting() {
+    let src = r#"
+        fn classify(n: u64) -> u64 {
+            var total = 0u64
+            for i in 0u64 to 10u64 {
+                if i % 2u64 == 0u64 {
+                    if n > i {
+                        total = total + i
+                    }
+                } elif i == 7u64 {
+                    total = total + 1u64
+                }
+            }
+            total
+        }
+
+        fn main() -> u64 {
+            classify(4u64)
+        }
+    "#;
+    let src_path = unique_path("metrics.t");
+    std::fs::write(&src_path, src).unwrap();
+    let json_path = unique_path("metrics.json");
+    let mut opts = CompilerOptions::new(src_path.clone());
+    opts.output = Some(json_path.clone());
+    opts.emit = EmitKind::MetricsJson;
+    compile_file(&opts).expect("emit metrics");
+    let text = std::fs::read_to_string(&json_path).expect("metrics file exists");
+    assert!(
+        text.contains(r#"{"function": "classify", "line": 2, "statements": 7, "nesting": 3, "cyclomatic": 5}"#),
+        "{text}"
+    );
+    assert!(
+        text.contains(r#"{"function": "main", "line": 16, "statements": 1, "nesting": 0, "cyclomatic": 1}"#),
+        "{text}"
+    );
+    let _ = std::fs::remove_file(&json_path);
+    let _ = std::fs::remove_file(&src_path);
+}
+
+#[test]
+fn pure_calls_with_constant_arguments_are_folded() {
+    if skip_e2e() {
+        return;
+    }
+    let src = r#"
+        @noinline
+        fn fact(n: u64) -> u64 {
+            if n == 0u64 { 1u64 } else { n * fact(n - 1u64) }
+        }
+
+        @noinline
+        fn half(n: u64) -> u64
+            requires n > 1u64
+        {
+            n / 2u64
+        }
+
+        fn main() -> u64 {
+            val big = fact(fact(3u64) - 1u64)
+            big - fact(5u64) + half(84u64) + half(1u64)
+        }
+    "#;
+    let src_path = unique_path("comptime.t");
+    std::fs::write(&src_path, src).unwrap();
+    let calls_at = |level: OptLevel| {
+        let ir_path = unique_path("comptime.ir");
+        let mut opts = CompilerOptions::new(src_path.clone());
+        opts.output = Some(ir_path.clone());
+        opts.emit = EmitKind::Ir;
+        opts.opt_level = Some(level);
+        compile_file(&opts).unwrap_or_else(|e| panic!("emit ir at {level:?}: {e}"));
+        let text = std::fs::read_to_string(&ir_path).expect("ir file exists");
+        let _ = std::fs::remove_file(&ir_path);
+        text.matches("= call fn#").count()
+    };
+    // Five calls in `main` plus the recursive one in `fact`. At `-O2`
+    // every call in `main` folds except `half(1u64)`, which breaks its
+    // contract and so has to fail at run time.
+    assert_eq!(calls_at(OptLevel::O0), 6);
+    assert_eq!(calls_at(OptLevel::O2), 2);
+
+    let exe = unique_path("comptime");
+    let mut opts = CompilerOptions::new(src_path.clone());
+    opts.output = Some(exe.clone());
+    opts.opt_level = Some(OptLevel::O2);
+    opts.link_cache_dir = Some(link_cache_dir_for_tests());
+    compile_file(&opts).expect("compile_file failed");
+    let out = Command::new(&exe).output().expect("spawn");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("requires violation"));
+    let _ = std::fs::remove_file(&exe);
+    let _ = std::fs::remove_file(&src_path);
+}
+
 #[test]
 fn nested_struct_field_read_and_write() {
     if skip_e2e() {
@@ -781,8 +1114,13 @@ fn emit_object_writes_o_file() {
         emit: EmitKind::Object,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&options).expect("compile_file failed");
     let metadata = std::fs::metadata(&obj_path).expect("object file exists");
@@ -808,8 +1146,13 @@ fn emit_ir_writes_compiler_ir() {
         emit: EmitKind::Ir,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&options).expect("compile_file failed");
     let text = std::fs::read_to_string(&ir_path).expect("ir file exists");
@@ -835,8 +1178,13 @@ fn emit_clif_writes_cranelift_ir() {
         emit: EmitKind::Clif,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: None,
             link_cache_dir: Some(link_cache_dir_for_tests()),
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     };
     compile_file(&options).expect("compile_file failed");
     let text = std::fs::read_to_string(&clif_path).expect("clif file exists");
@@ -92,8 +92,13 @@ fn jit_options_with_core() -> CompilerOptions {
         emit: EmitKind::Executable,
         verbose: false,
         release: false,
+        bounds_check: false,
         core_modules_dir: Some(core_modules_dir()),
             link_cache_dir: None,
+            defines: Default::default(),
+            opt_level: None,
+            target_triple: None,
+            timings: None,
     }
 }
 
@@ -2,9 +2,41 @@ use string_interner::DefaultStringInterner;
 use frontend::{ModuleResolver, Parser};
 use frontend::ast::Program;
 use frontend::parser::error::ParserResult;
-use frontend::type_checker::{TypeCheckerVisitor, TypeCheckError};
+use frontend::type_checker::{FunctionCheckCache, TypeCheckerVisitor, TypeCheckError};
 use std::path::Path;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+mod rename;
+mod symbol_index;
+mod timings;
+mod xref;
+pub use rename::{RenameError, TextEdit};
+pub use timings::{Phase, PhaseTimings, TimingFormat};
+pub use xref::{CallSite, SymbolLocation, SymbolReferences, XrefIndex};
+
+/// Source name diagnostics show for a program read from standard input.
+pub const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// Read the program a CLI input argument refers to, returning the
+/// source text and the name to report it under. `-` reads standard
+/// input and is reported as [`STDIN_SOURCE_NAME`]; anything else is a
+/// file path reported as written.
+pub fn read_source_arg(arg: &Path) -> std::io::Result<(String, String)> {
+    if is_stdin_arg(arg) {
+        let mut source = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut source)?;
+        Ok((source, STDIN_SOURCE_NAME.to_string()))
+    } else {
+        let source = std::fs::read_to_string(arg)?;
+        Ok((source, arg.to_string_lossy().into_owned()))
+    }
+}
+
+/// Whether a CLI input argument is `-` (standard input).
+pub fn is_stdin_arg(arg: &Path) -> bool {
+    arg.as_os_str() == "-"
+}
 
 /// Compiler session that serves as the central context for compilation
 /// 
@@ -17,12 +49,24 @@ pub struct CompilerSession {
     module_resolver: ModuleResolver,
     // Type checking results - stored after type checking is performed
     type_check_results: Option<TypeCheckResults>,
+    // Per-function check results reused by later check cycles in this session
+    function_check_cache: FunctionCheckCache,
+    // Largest `[T; N]` accepted by the parsers this session creates
+    max_array_len: usize,
+    // Name and text of the source parsed last, indexed by type checking
+    last_source: Option<(String, String)>,
+    // Cross-reference index built by the last type check
+    xref_index: Option<XrefIndex>,
+    // Time spent per pipeline phase since the session was created
+    timings: PhaseTimings,
 }
 
 /// Results from type checking that can be used by code generators
 pub struct TypeCheckResults {
     pub expr_types: HashMap<frontend::ast::ExprRef, frontend::type_decl::TypeDecl>,
     pub struct_types: HashMap<string_interner::DefaultSymbol, String>, // variable -> struct type name
+    /// Functions and methods with no side effects (`frontend::purity`).
+    pub purity: frontend::purity::Purity,
 }
 
 impl CompilerSession {
@@ -32,6 +76,11 @@ impl CompilerSession {
             string_interner: DefaultStringInterner::new(),
             module_resolver: ModuleResolver::new(),
             type_check_results: None,
+            function_check_cache: FunctionCheckCache::new(),
+            max_array_len: frontend::parser::DEFAULT_MAX_ARRAY_LEN,
+            last_source: None,
+            xref_index: None,
+            timings: PhaseTimings::default(),
         }
     }
     
@@ -41,6 +90,11 @@ impl CompilerSession {
             string_interner: DefaultStringInterner::new(),
             module_resolver: ModuleResolver::with_search_paths(search_paths),
             type_check_results: None,
+            function_check_cache: FunctionCheckCache::new(),
+            max_array_len: frontend::parser::DEFAULT_MAX_ARRAY_LEN,
+            last_source: None,
+            xref_index: None,
+            timings: PhaseTimings::default(),
         }
     }
     
@@ -48,25 +102,41 @@ impl CompilerSession {
     ///
     /// Uses the session's shared resources (string interner, module resolver, etc.)
     /// to parse the input and produce an AST.
+    /// Largest `N` that `[T; N]` may declare in sources this session
+    /// parses (`frontend::parser::DEFAULT_MAX_ARRAY_LEN` by default).
+    pub fn set_max_array_len(&mut self, max: usize) {
+        self.max_array_len = max;
+    }
+
     pub fn parse_program(&mut self, input: &str) -> ParserResult<Program> {
+        self.last_source = Some(("<source>".to_string(), input.to_string()));
+        let start = Instant::now();
         let mut parser = Parser::new(input, &mut self.string_interner);
-        let program = parser.parse_program()?;
-
-        Ok(program)
+        parser.set_max_array_len(self.max_array_len);
+        let program = parser.parse_program();
+        self.timings.record(Phase::Parse, start.elapsed());
+        program
     }
 
-    /// Parse a program string with an explicit source path. The path
-    /// powers the parser-level `__builtin_source_file()` substitution
-    /// (and `__builtin_dbg` / `assert_eq` headers). Other than that
-    /// it is identical to `parse_program`.
-    pub fn parse_program_with_source(
+    /// Parse a program string under a source name. The name powers the
+    /// parser-level `__builtin_source_file()` substitution (and
+    /// `__builtin_dbg` / `assert_eq` headers) and is what diagnostics
+    /// should show. It need not be a real path: generated or embedded
+    /// sources can use a virtual name such as `<stdin>` or `<repl>`.
+    /// Other than that it is identical to `parse_program`.
+    pub fn parse_named_source(
         &mut self,
-        input: &str,
-        filename: &str,
+        name: &str,
+        source: &str,
     ) -> ParserResult<Program> {
-        let mut parser = Parser::new(input, &mut self.string_interner);
-        parser.set_source_file(filename);
-        parser.parse_program()
+        self.last_source = Some((name.to_string(), source.to_string()));
+        let start = Instant::now();
+        let mut parser = Parser::new(source, &mut self.string_interner);
+        parser.set_source_file(name);
+        parser.set_max_array_len(self.max_array_len);
+        let program = parser.parse_program();
+        self.timings.record(Phase::Parse, start.elapsed());
+        program
     }
     
     /// Merge symbols from another string interner into the session's interner
@@ -114,10 +184,52 @@ impl CompilerSession {
         &mut self.module_resolver
     }
     
-    /// Type check a program and store the results in the session
+    /// Get the function check cache shared by this session's check cycles
+    pub fn function_check_cache(&self) -> &FunctionCheckCache {
+        &self.function_check_cache
+    }
+
+    /// Borrow the string interner and the function check cache together.
+    ///
+    /// Type checking needs both at once; cached entries are keyed on
+    /// symbol ids, so they are only valid with this session's interner.
+    pub fn interner_and_check_cache_mut(&mut self) -> (&mut DefaultStringInterner, &mut FunctionCheckCache) {
+        (&mut self.string_interner, &mut self.function_check_cache)
+    }
+
+    /// Time spent per phase. Parsing and [`type_check_program`](Self::type_check_program)
+    /// are timed here; drivers add the phases that run outside the
+    /// session through [`record_phase`](Self::record_phase).
+    pub fn timings(&self) -> &PhaseTimings {
+        &self.timings
+    }
+
+    /// Add `elapsed` to `phase`'s total.
+    pub fn record_phase(&mut self, phase: Phase, elapsed: Duration) {
+        self.timings.record(phase, elapsed);
+    }
+
+    /// Forget the timings so far, e.g. between the runs of a `--watch`
+    /// loop that reuses the session.
+    pub fn reset_timings(&mut self) {
+        self.timings.clear();
+    }
+
+    /// Type check a program and store the results in the session.
+    /// Also rebuilds the cross-reference index (`references`,
+    /// `callers`) from the source this session parsed last.
     pub fn type_check_program(&mut self, program: &Program) -> Result<(), Vec<TypeCheckError>> {
+        let start = Instant::now();
+        let result = self.type_check_program_untimed(program);
+        self.timings.record(Phase::TypeCheck, start.elapsed());
+        result
+    }
+
+    fn type_check_program_untimed(&mut self, program: &Program) -> Result<(), Vec<TypeCheckError>> {
         use frontend::visitor::ProgramVisitor;
-        
+
+        self.build_xref_index();
+
         // Create a mutable copy of expression pool for type checking
         let mut expr_pool = program.expression.clone();
         let mut type_checker = TypeCheckerVisitor::new(
@@ -133,12 +245,14 @@ impl CompilerSession {
                 // Extract useful type information for code generation
                 let expr_types = type_checker.get_expr_types();
                 let struct_types = type_checker.get_struct_var_mappings(&self.string_interner);
+                let purity = frontend::purity::infer_purity(program, &expr_types, &self.string_interner);
                 
                 // Note: Type information extracted for code generation
                 
                 self.type_check_results = Some(TypeCheckResults {
                     expr_types,
                     struct_types,
+                    purity,
                 });
                 
                 Ok(())
@@ -196,6 +310,34 @@ mod tests {
         assert_eq!(program.function.len(), 1);
     }
     
+    #[test]
+    fn test_parse_named_source_uses_virtual_name() {
+        let mut session = CompilerSession::new();
+        let program = session
+            .parse_named_source(STDIN_SOURCE_NAME, "fn main() -> str { __builtin_source_file() }")
+            .unwrap();
+        assert_eq!(program.function.len(), 1);
+        assert!(session.string_interner().get(STDIN_SOURCE_NAME).is_some());
+    }
+
+    #[test]
+    fn test_session_times_parse_and_type_check() {
+        let mut session = CompilerSession::new();
+        let program = session.parse_program("fn main() -> u64 { 42u64 }").unwrap();
+        session.type_check_program(&program).unwrap();
+        let phases: Vec<Phase> = session.timings().iter().map(|(p, _)| p).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::TypeCheck]);
+        session.reset_timings();
+        assert_eq!(session.timings().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_stdin_arg_detection() {
+        assert!(is_stdin_arg(Path::new("-")));
+        assert!(!is_stdin_arg(Path::new("-.t")));
+        assert!(!is_stdin_arg(Path::new("main.t")));
+    }
+
     #[test]
     fn test_string_interner_consistency() {
         let mut session = CompilerSession::new();
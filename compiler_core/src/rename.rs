@@ -0,0 +1,274 @@
+//! Rename refactoring for editors (LSP `textDocument/rename`).
+//!
+//! The symbol under the cursor and its references come from
+//! `symbol_index::FileIndex`. Renaming an item of an imported module
+//! (`module::name`) renames it in the module's own file as well. Trait
+//! methods are refused: the implementations are tied to the trait by
+//! name alone, so renaming one place would silently break the others.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use frontend::lex_with_spans;
+use frontend::token::Kind;
+use frontend::Parser;
+
+use crate::symbol_index::{FileIndex, Symbol};
+use crate::CompilerSession;
+
+/// Replace bytes `range` of `file` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub file: PathBuf,
+    pub range: Range<usize>,
+    pub new_text: String,
+}
+
+#[derive(Debug)]
+pub enum RenameError {
+    Io(PathBuf, std::io::Error),
+    /// No identifier with a declaration in reach at the offset.
+    NoSymbol,
+    /// The new name is not a plain identifier.
+    InvalidName(String),
+    /// The new name is already taken where the symbol lives.
+    Conflict(String),
+    Unsupported(String),
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::Io(path, e) => write!(f, "cannot read {}: {}", path.display(), e),
+            RenameError::NoSymbol => write!(f, "no renameable symbol at this position"),
+            RenameError::InvalidName(name) => write!(f, "`{}` is not a valid identifier", name),
+            RenameError::Conflict(message) | RenameError::Unsupported(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+impl CompilerSession {
+    /// Edits that rename the symbol under byte `offset` of `file` to
+    /// `new_name`, sorted by file and position. Nothing is written.
+    pub fn rename_symbol(
+        &mut self,
+        file: &Path,
+        offset: usize,
+        new_name: &str,
+    ) -> Result<Vec<TextEdit>, RenameError> {
+        let source = read(file)?;
+        self.rename_symbol_in_source(file, &source, offset, new_name)
+    }
+
+    /// [`rename_symbol`](Self::rename_symbol) for an unsaved buffer:
+    /// `source` stands in for the contents of `file`.
+    pub fn rename_symbol_in_source(
+        &mut self,
+        file: &Path,
+        source: &str,
+        offset: usize,
+        new_name: &str,
+    ) -> Result<Vec<TextEdit>, RenameError> {
+        if !matches!(lex_with_spans(new_name).as_slice(), [(Kind::Identifier(_), range)] if *range == (0..new_name.len())) {
+            return Err(RenameError::InvalidName(new_name.to_string()));
+        }
+        let lenient = Parser::new(source, &mut self.string_interner).parse_program_lenient();
+        let index = FileIndex::new(source, Some(&lenient), &self.string_interner);
+        let at = index
+            .tokens
+            .iter()
+            .position(|(kind, range)| matches!(kind, Kind::Identifier(_)) && range.start <= offset && offset <= range.end)
+            .ok_or(RenameError::NoSymbol)?;
+        let target = match index.classify(at) {
+            Symbol::Member(None, name) => match index.owners_of(&name).as_slice() {
+                [owner] => Symbol::Member(Some(owner.clone()), name),
+                _ => {
+                    return Err(RenameError::Unsupported(format!(
+                        "cannot tell which type's `{}` this is",
+                        name
+                    )))
+                }
+            },
+            Symbol::Unresolved => return Err(RenameError::NoSymbol),
+            symbol => symbol,
+        };
+        index.check_conflict(&target, new_name)?;
+
+        let mut edits = index.edits_for(&target, file, new_name);
+        if let Symbol::Imported(module, name) = &target {
+            let Some(import) = lenient.program.imports.iter().find(|import| {
+                let local = import.alias.or(import.module_path.last().copied());
+                local.and_then(|sym| self.string_interner.resolve(sym)) == Some(module.as_str())
+            }) else {
+                return Err(RenameError::NoSymbol);
+            };
+            let resolved = self
+                .module_resolver
+                .resolve_import(import, file.parent(), &mut self.string_interner)
+                .map_err(|e| RenameError::Unsupported(format!("cannot resolve module `{}`: {}", module, e)))?;
+            let module_source = read(&resolved.file_path)?;
+            let module_index = FileIndex::new(&module_source, None, &self.string_interner);
+            let item = Symbol::Item(name.clone());
+            module_index.check_conflict(&item, new_name)?;
+            edits.extend(module_index.edits_for(&item, &resolved.file_path, new_name));
+        }
+        if edits.is_empty() {
+            return Err(RenameError::NoSymbol);
+        }
+        edits.sort_by(|a, b| (&a.file, a.range.start).cmp(&(&b.file, b.range.start)));
+        Ok(edits)
+    }
+}
+
+fn read(path: &Path) -> Result<String, RenameError> {
+    std::fs::read_to_string(path).map_err(|e| RenameError::Io(path.to_path_buf(), e))
+}
+
+impl FileIndex<'_> {
+    fn check_conflict(&self, target: &Symbol, new_name: &str) -> Result<(), RenameError> {
+        let taken = match target {
+            Symbol::TraitMethod => {
+                return Err(RenameError::Unsupported("renaming a trait method is not supported".to_string()));
+            }
+            Symbol::Item(_) | Symbol::Imported(..) => self.items.contains_key(new_name),
+            Symbol::Member(owner, _) => self.owners_of(new_name).iter().any(|o| Some(o) == owner.as_ref()),
+            Symbol::Local(local) => {
+                let scope = &self.locals[*local].scope;
+                self.locals
+                    .iter()
+                    .any(|decl| decl.name == new_name && decl.scope.start < scope.end && scope.start < decl.scope.end)
+            }
+            Symbol::Unresolved => false,
+        };
+        if taken {
+            return Err(RenameError::Conflict(format!("`{}` is already defined here", new_name)));
+        }
+        Ok(())
+    }
+
+    fn edits_for(&self, target: &Symbol, file: &Path, new_name: &str) -> Vec<TextEdit> {
+        (0..self.tokens.len())
+            .filter(|&i| self.refers_to(i, target))
+            .map(|i| TextEdit {
+                file: file.to_path_buf(),
+                range: self.tokens[i].1.clone(),
+                new_text: new_name.to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"struct Point {
+    x: u64,
+    y: u64
+}
+
+impl Point {
+    fn new(x: u64, y: u64) -> Self {
+        Point { x: x, y: y }
+    }
+
+    fn sum(&self) -> u64 {
+        self.x + self.y
+    }
+}
+
+fn total(p: Point) -> u64 {
+    val x = p.x
+    x + p.sum()
+}
+
+fn main() -> u64 {
+    val p = Point::new(1u64, 2u64)
+    val x = 10u64
+    total(p) + x
+}
+"#;
+
+    fn rename(source: &str, needle: &str, nth: usize, new_name: &str) -> Result<String, RenameError> {
+        let offset = source.match_indices(needle).nth(nth).expect("needle in source").0;
+        let mut session = CompilerSession::new();
+        let edits = session.rename_symbol_in_source(Path::new("main.t"), source, offset, new_name)?;
+        let mut out = source.to_string();
+        for edit in edits.iter().rev() {
+            out.replace_range(edit.range.clone(), &edit.new_text);
+        }
+        Ok(out)
+    }
+
+    #[test]
+    fn renames_a_field_everywhere_it_is_typed() {
+        let out = rename(SOURCE, "x: u64,", 0, "px").unwrap();
+        assert!(out.contains("    px: u64,"));
+        assert!(out.contains("Point { px: x, y: y }"));
+        assert!(out.contains("self.px + self.y"));
+        assert!(out.contains("val x = p.px"));
+        // Locals and parameters named `x` are untouched.
+        assert!(out.contains("fn new(x: u64, y: u64)"));
+        assert!(out.contains("val x = 10u64"));
+        assert!(out.contains("total(p) + x"));
+    }
+
+    #[test]
+    fn renames_a_local_within_its_scope_only() {
+        let out = rename(SOURCE, "x = p.x", 0, "first").unwrap();
+        assert!(out.contains("val first = p.x\n    first + p.sum()"));
+        assert!(out.contains("val x = 10u64\n    total(p) + x"));
+
+        let out = rename(SOURCE, "x: u64, y", 0, "ax").unwrap();
+        assert!(out.contains("fn new(ax: u64, y: u64)"));
+        assert!(out.contains("Point { x: ax, y: y }"));
+    }
+
+    #[test]
+    fn renames_items_and_methods() {
+        let out = rename(SOURCE, "Point", 2, "Vec2").unwrap();
+        assert_eq!(out.matches("Vec2").count(), 5);
+        assert!(!out.contains("Point"));
+
+        let out = rename(SOURCE, "sum", 1, "total_sum").unwrap();
+        assert!(out.contains("fn total_sum(&self)"));
+        assert!(out.contains("x + p.total_sum()"));
+
+        let out = rename(SOURCE, "new", 1, "make").unwrap();
+        assert!(out.contains("fn make(x: u64"));
+        assert!(out.contains("Point::make(1u64"));
+    }
+
+    #[test]
+    fn rejects_bad_names_conflicts_and_unknown_symbols() {
+        assert!(matches!(rename(SOURCE, "total(p)", 0, "1x"), Err(RenameError::InvalidName(_))));
+        assert!(matches!(rename(SOURCE, "total(p)", 0, "main"), Err(RenameError::Conflict(_))));
+        assert!(matches!(rename(SOURCE, "sum(&self)", 0, "new"), Err(RenameError::Conflict(_))));
+        assert!(matches!(rename(SOURCE, "u64", 0, "w"), Err(RenameError::NoSymbol)));
+        let traits = "trait Show {\n    fn show(&self) -> u64\n}\n";
+        assert!(matches!(rename(traits, "show", 0, "display"), Err(RenameError::Unsupported(_))));
+    }
+
+    #[test]
+    fn renames_an_imported_item_in_its_module_too() {
+        let dir = std::env::temp_dir().join(format!("toylang_rename_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("geo")).unwrap();
+        let module = dir.join("geo/shapes.t");
+        std::fs::write(&module, "package geo.shapes\n\npub fn area(w: u64, h: u64) -> u64 {\n    w * h\n}\n\npub fn square(s: u64) -> u64 {\n    area(s, s)\n}\n").unwrap();
+        let main = dir.join("main.t");
+        let source = "import geo.shapes\n\nfn main() -> u64 {\n    shapes::area(2u64, 3u64)\n}\n";
+        std::fs::write(&main, source).unwrap();
+
+        let mut session = CompilerSession::with_search_paths(vec![dir.clone()]);
+        let offset = source.find("area").unwrap() + 1;
+        let edits = session.rename_symbol(&main, offset, "surface").unwrap();
+        let files: Vec<&Path> = edits.iter().map(|e| e.file.as_path()).collect();
+        assert_eq!(files.iter().filter(|f| **f == main).count(), 1);
+        assert_eq!(files.iter().filter(|f| f.ends_with("geo/shapes.t")).count(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
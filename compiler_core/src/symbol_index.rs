@@ -0,0 +1,427 @@
+//! Token-level symbol index of one source file, shared by rename and
+//! the cross-reference queries.
+//!
+//! References are found on the token stream, with block structure taken
+//! from the brackets, so a file in the middle of an edit can still be
+//! indexed. Four kinds of symbol are told apart:
+//!
+//! - local bindings (parameters, `val` / `var`, `for` variables). A use
+//!   belongs to the nearest same-named binding whose block encloses it;
+//! - top-level items (functions, structs, enums, traits, consts, type
+//!   aliases), used by bare name wherever no local shadows them;
+//! - members (struct fields, methods, enum variants), found at their
+//!   declaration, after `Owner::`, as struct-literal field names and
+//!   after a `.` whose receiver types to the owner (see
+//!   `LenientProgram::context_at`). A `.name` on an untyped receiver
+//!   counts when no other type has a member of that name;
+//! - items of an imported module, used as `module::name`.
+//!
+//! Bindings introduced by `match` patterns are not tracked.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+use frontend::lex_with_spans;
+use frontend::parser::{ExprContext, LenientProgram};
+use frontend::token::Kind;
+use frontend::type_decl::TypeDecl;
+
+/// What an identifier token refers to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Symbol {
+    /// Index into `FileIndex::locals`.
+    Local(usize),
+    Item(String),
+    /// Owner type (unknown for an untyped receiver) and member name.
+    Member(Option<String>, String),
+    /// Imported module name and item name.
+    Imported(String, String),
+    TraitMethod,
+    Unresolved,
+}
+
+#[derive(Debug)]
+pub(crate) struct LocalDecl {
+    pub(crate) name: String,
+    pub(crate) token: usize,
+    /// Offset uses may appear from.
+    pub(crate) visible_from: usize,
+    pub(crate) scope: Range<usize>,
+}
+
+/// Declarations of one file, keyed by token index.
+pub(crate) struct FileIndex<'a> {
+    pub(crate) tokens: Vec<(Kind, Range<usize>)>,
+    /// Innermost open bracket around each token.
+    pub(crate) parent: Vec<Option<usize>>,
+    /// Partner of each bracket.
+    pub(crate) matching: Vec<Option<usize>>,
+    pub(crate) locals: Vec<LocalDecl>,
+    pub(crate) items: HashMap<String, usize>,
+    /// Token index of a member declaration -> owner type.
+    pub(crate) members: HashMap<usize, String>,
+    pub(crate) trait_methods: BTreeSet<usize>,
+    /// `{` of each impl block -> target type.
+    pub(crate) impl_bodies: HashMap<usize, String>,
+    /// `{` of each named function or method body -> `name` or
+    /// `Owner::name`.
+    pub(crate) function_bodies: HashMap<usize, String>,
+    pub(crate) imports: Vec<String>,
+    pub(crate) lenient: Option<&'a LenientProgram>,
+    pub(crate) interner: &'a string_interner::DefaultStringInterner,
+}
+
+impl<'a> FileIndex<'a> {
+    pub(crate) fn new(
+        source: &str,
+        lenient: Option<&'a LenientProgram>,
+        interner: &'a string_interner::DefaultStringInterner,
+    ) -> Self {
+        let tokens: Vec<_> = lex_with_spans(source)
+            .into_iter()
+            .filter(|(kind, _)| !matches!(kind, Kind::NewLine | Kind::Comment(_)))
+            .collect();
+        let mut parent = vec![None; tokens.len()];
+        let mut matching = vec![None; tokens.len()];
+        let mut open: Vec<usize> = Vec::new();
+        for (i, (kind, _)) in tokens.iter().enumerate() {
+            if is_closer(kind)
+                && let Some(opener) = open.pop()
+            {
+                matching[opener] = Some(i);
+                matching[i] = Some(opener);
+            }
+            parent[i] = open.last().copied();
+            if is_opener(kind) {
+                open.push(i);
+            }
+        }
+        let mut index = FileIndex {
+            tokens,
+            parent,
+            matching,
+            locals: Vec::new(),
+            items: HashMap::new(),
+            members: HashMap::new(),
+            trait_methods: BTreeSet::new(),
+            impl_bodies: HashMap::new(),
+            function_bodies: HashMap::new(),
+            imports: Vec::new(),
+            lenient,
+            interner,
+        };
+        index.collect(source);
+        index
+    }
+
+    pub(crate) fn kind(&self, i: usize) -> Option<&Kind> {
+        self.tokens.get(i).map(|(kind, _)| kind)
+    }
+
+    pub(crate) fn ident(&self, i: usize) -> Option<&str> {
+        match self.kind(i) {
+            Some(Kind::Identifier(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Byte span of the bracketed group opened at token `open`.
+    fn span(&self, open: usize) -> Range<usize> {
+        let end = self.matching[open].map_or(usize::MAX, |close| self.tokens[close].1.end);
+        self.tokens[open].1.start..end
+    }
+
+    /// First `kind` token after `from` at the same nesting as `from`.
+    fn next_at_level(&self, from: usize, kind: &Kind) -> Option<usize> {
+        (from + 1..self.tokens.len())
+            .take_while(|&i| self.parent[i] == self.parent[from] || self.parent[i] > Some(from))
+            .find(|&i| self.parent[i] == self.parent[from] && self.kind(i) == Some(kind))
+    }
+
+    fn collect(&mut self, source: &str) {
+        let mut trait_bodies = BTreeSet::new();
+        for i in 0..self.tokens.len() {
+            let top_level = self.parent[i].is_none();
+            let name = self.ident(i + 1).map(str::to_string);
+            match self.kind(i) {
+                Some(Kind::Function) => {
+                    let mut qualified = None;
+                    if let Some(name) = &name {
+                        match self.parent[i] {
+                            None => {
+                                self.items.insert(name.clone(), i + 1);
+                                qualified = Some(name.clone());
+                            }
+                            Some(body) if trait_bodies.contains(&body) => {
+                                self.trait_methods.insert(i + 1);
+                            }
+                            Some(body) => {
+                                if let Some(owner) = self.impl_bodies.get(&body) {
+                                    self.members.insert(i + 1, owner.clone());
+                                    qualified = Some(format!("{}::{}", owner, name));
+                                }
+                            }
+                        }
+                    }
+                    if let Some(body) = self.collect_params(i)
+                        && let Some(qualified) = qualified
+                    {
+                        self.function_bodies.insert(body, qualified);
+                    }
+                }
+                Some(Kind::Struct | Kind::Enum | Kind::Trait | Kind::Const | Kind::Type) if top_level => {
+                    let Some(name) = name else { continue };
+                    self.items.insert(name.clone(), i + 1);
+                    let Some(body) = self.next_at_level(i, &Kind::BraceOpen) else { continue };
+                    match self.kind(i) {
+                        Some(Kind::Struct) => self.collect_fields(body, &name),
+                        Some(Kind::Enum) => self.collect_variants(body, &name),
+                        Some(Kind::Trait) => {
+                            trait_bodies.insert(body);
+                        }
+                        _ => {}
+                    }
+                }
+                Some(Kind::Impl) if top_level => {
+                    let Some(body) = self.next_at_level(i, &Kind::BraceOpen) else { continue };
+                    let header = i + 1..body;
+                    let target = match header.clone().find(|&j| self.kind(j) == Some(&Kind::For)) {
+                        Some(for_token) => {
+                            trait_bodies.insert(body);
+                            self.ident(for_token + 1)
+                        }
+                        None => header.clone().find(|&j| self.angle_depth(i, j) == 0).and_then(|j| self.ident(j)),
+                    };
+                    if let Some(target) = target {
+                        self.impl_bodies.insert(body, target.to_string());
+                    }
+                }
+                Some(Kind::Val | Kind::Var) => {
+                    let line_end = source[self.tokens[i].1.end..].find('\n').map_or(source.len(), |n| self.tokens[i].1.end + n);
+                    let scope = self.parent_block(i);
+                    let names: Vec<usize> = match self.kind(i + 1) {
+                        Some(Kind::Identifier(_)) => vec![i + 1],
+                        Some(Kind::ParenOpen) => (i + 2..self.matching[i + 1].unwrap_or(i + 2))
+                            .filter(|&j| self.parent[j] == Some(i + 1) && self.ident(j).is_some())
+                            .collect(),
+                        _ => vec![],
+                    };
+                    for token in names {
+                        self.add_local(token, line_end, scope.clone());
+                    }
+                }
+                Some(Kind::For) if self.ident(i + 1).is_some() => {
+                    if let Some(body) = self.next_at_level(i, &Kind::BraceOpen) {
+                        let scope = self.span(body);
+                        self.add_local(i + 1, scope.start, scope);
+                    }
+                }
+                Some(Kind::Import) => {
+                    let path_end = (i + 1..self.tokens.len())
+                        .take_while(|&j| matches!(self.kind(j), Some(Kind::Identifier(_) | Kind::Dot | Kind::As)))
+                        .last();
+                    if let Some(last) = path_end.and_then(|j| self.ident(j)) {
+                        self.imports.push(last.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Depth inside `<...>` at token `at`, counted from `from`.
+    fn angle_depth(&self, from: usize, at: usize) -> i32 {
+        (from + 1..=at)
+            .map(|j| match self.kind(j) {
+                Some(Kind::LT) => 1,
+                Some(Kind::GT) => -1,
+                _ => 0,
+            })
+            .sum::<i32>()
+            + if self.kind(at) == Some(&Kind::GT) { 1 } else { 0 }
+    }
+
+    /// Span of the innermost `{ ... }` around token `i`, or the file.
+    fn parent_block(&self, i: usize) -> Range<usize> {
+        let mut at = self.parent[i];
+        while let Some(open) = at {
+            if self.kind(open) == Some(&Kind::BraceOpen) {
+                return self.span(open);
+            }
+            at = self.parent[open];
+        }
+        0..usize::MAX
+    }
+
+    fn add_local(&mut self, token: usize, visible_from: usize, scope: Range<usize>) {
+        let name = self.ident(token).unwrap_or_default().to_string();
+        self.locals.push(LocalDecl { name, token, visible_from, scope });
+    }
+
+    /// Parameters of the `fn` at token `function` (named or closure).
+    /// Returns the `{` of its body.
+    fn collect_params(&mut self, function: usize) -> Option<usize> {
+        let open = self.next_at_level(function, &Kind::ParenOpen)?;
+        let close = self.matching[open]?;
+        let body = (close + 1..self.tokens.len())
+            .take_while(|&j| self.parent[j] != self.parent[function] || !matches!(self.kind(j), Some(Kind::Function | Kind::BraceClose)))
+            .find(|&j| self.parent[j] == self.parent[function] && self.kind(j) == Some(&Kind::BraceOpen))?;
+        let scope = self.span(body);
+        for j in open + 1..close {
+            if self.parent[j] == Some(open)
+                && self.ident(j).is_some()
+                && self.kind(j + 1) == Some(&Kind::Colon)
+                && matches!(self.kind(j - 1), Some(Kind::ParenOpen | Kind::Comma | Kind::Mut))
+            {
+                self.add_local(j, scope.start, scope.clone());
+            }
+        }
+        Some(body)
+    }
+
+    fn collect_fields(&mut self, body: usize, owner: &str) {
+        for j in body + 1..self.matching[body].unwrap_or(body + 1) {
+            if self.parent[j] == Some(body)
+                && self.ident(j).is_some()
+                && self.kind(j + 1) == Some(&Kind::Colon)
+                && matches!(self.kind(j - 1), Some(Kind::BraceOpen | Kind::Comma | Kind::Public))
+            {
+                self.members.insert(j, owner.to_string());
+            }
+        }
+    }
+
+    fn collect_variants(&mut self, body: usize, owner: &str) {
+        for j in body + 1..self.matching[body].unwrap_or(body + 1) {
+            if self.parent[j] == Some(body)
+                && self.ident(j).is_some()
+                && matches!(self.kind(j - 1), Some(Kind::BraceOpen | Kind::Comma))
+            {
+                self.members.insert(j, owner.to_string());
+            }
+        }
+    }
+
+    /// Local declaration the identifier at token `i` resolves to.
+    fn local_of(&self, i: usize) -> Option<usize> {
+        if let Some(decl) = self.locals.iter().position(|decl| decl.token == i) {
+            return Some(decl);
+        }
+        let name = self.ident(i)?;
+        let at = self.tokens[i].1.start;
+        self.locals
+            .iter()
+            .enumerate()
+            .filter(|(_, decl)| decl.name == name && decl.visible_from <= at && decl.scope.contains(&at))
+            .max_by_key(|(_, decl)| decl.visible_from)
+            .map(|(index, _)| index)
+    }
+
+    /// Named function or method whose body holds token `i`.
+    pub(crate) fn enclosing_function(&self, i: usize) -> Option<&String> {
+        let mut at = self.parent[i];
+        while let Some(open) = at {
+            if let Some(name) = self.function_bodies.get(&open) {
+                return Some(name);
+            }
+            at = self.parent[open];
+        }
+        None
+    }
+
+    /// Impl target enclosing token `i`, for `Self::`.
+    fn impl_target_of(&self, i: usize) -> Option<&String> {
+        let mut at = self.parent[i];
+        while let Some(open) = at {
+            if let Some(target) = self.impl_bodies.get(&open) {
+                return Some(target);
+            }
+            at = self.parent[open];
+        }
+        None
+    }
+
+    fn receiver_owner(&self, dot: usize) -> Option<String> {
+        let context = self.lenient?.context_at(self.tokens[dot].1.end);
+        let ExprContext::FieldAccessOf(mut ty) = context else { return None };
+        while let TypeDecl::Ref { inner, .. } = ty {
+            ty = *inner;
+        }
+        match ty {
+            TypeDecl::Struct(name, _) | TypeDecl::Identifier(name) | TypeDecl::Enum(name, _) => {
+                self.interner.resolve(name).map(str::to_string)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn classify(&self, i: usize) -> Symbol {
+        let Some(name) = self.ident(i) else { return Symbol::Unresolved };
+        let name = name.to_string();
+        if self.trait_methods.contains(&i) {
+            return Symbol::TraitMethod;
+        }
+        if let Some(owner) = self.members.get(&i) {
+            return Symbol::Member(Some(owner.clone()), name);
+        }
+        match i.checked_sub(1).and_then(|p| self.kind(p)) {
+            Some(Kind::Dot) => return Symbol::Member(self.receiver_owner(i - 1), name),
+            Some(Kind::DoubleColon) => {
+                return match i.checked_sub(2).and_then(|q| self.kind(q)) {
+                    Some(Kind::Self_) => Symbol::Member(self.impl_target_of(i).cloned(), name),
+                    Some(Kind::Identifier(qualifier)) if self.imports.contains(qualifier) && !self.items.contains_key(qualifier) => {
+                        Symbol::Imported(qualifier.clone(), name)
+                    }
+                    Some(Kind::Identifier(qualifier)) => Symbol::Member(Some(qualifier.clone()), name),
+                    _ => Symbol::Unresolved,
+                };
+            }
+            _ => {}
+        }
+        // `Owner { name: value }`
+        if self.kind(i + 1) == Some(&Kind::Colon)
+            && matches!(i.checked_sub(1).and_then(|p| self.kind(p)), Some(Kind::BraceOpen | Kind::Comma))
+            && let Some(brace) = self.parent[i]
+            && self.kind(brace) == Some(&Kind::BraceOpen)
+            && let Some(owner) = brace.checked_sub(1).and_then(|b| self.ident(b))
+            && self.items.contains_key(owner)
+        {
+            return Symbol::Member(Some(owner.to_string()), name);
+        }
+        if let Some(local) = self.local_of(i) {
+            return Symbol::Local(local);
+        }
+        if self.items.contains_key(&name) {
+            return Symbol::Item(name);
+        }
+        Symbol::Unresolved
+    }
+
+    /// Types declaring a member called `name`.
+    pub(crate) fn owners_of(&self, name: &str) -> Vec<String> {
+        let owners: BTreeSet<&String> = self
+            .members
+            .iter()
+            .filter(|(token, _)| self.ident(**token) == Some(name))
+            .map(|(_, owner)| owner)
+            .collect();
+        owners.into_iter().cloned().collect()
+    }
+
+    pub(crate) fn refers_to(&self, i: usize, target: &Symbol) -> bool {
+        match (self.classify(i), target) {
+            (Symbol::Member(None, name), Symbol::Member(Some(owner), target_name)) => {
+                name == *target_name && self.owners_of(&name) == [owner.clone()]
+            }
+            (symbol, target) => symbol == *target,
+        }
+    }
+}
+
+fn is_opener(kind: &Kind) -> bool {
+    matches!(kind, Kind::ParenOpen | Kind::BraceOpen | Kind::BracketOpen)
+}
+
+fn is_closer(kind: &Kind) -> bool {
+    matches!(kind, Kind::ParenClose | Kind::BraceClose | Kind::BracketClose)
+}
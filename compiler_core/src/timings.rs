@@ -0,0 +1,150 @@
+//! Wall-clock time spent in each pipeline phase, collected by
+//! [`CompilerSession`](crate::CompilerSession) and printed by the
+//! binaries under `-v` / `--timings`.
+
+use std::time::Duration;
+
+/// One stage of the pipeline, in the order they run. Not every driver
+/// goes through all of them: the interpreter has no codegen unless the
+/// JIT compiles `main`, and the AOT compiler never executes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    /// Integrating the prelude, core modules and imports, and
+    /// resolving type aliases across them.
+    ModuleResolution,
+    TypeCheck,
+    Codegen,
+    Execution,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [Phase::Parse, Phase::ModuleResolution, Phase::TypeCheck, Phase::Codegen, Phase::Execution];
+
+    /// Name used in both report formats.
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::ModuleResolution => "module_resolution",
+            Phase::TypeCheck => "type_check",
+            Phase::Codegen => "codegen",
+            Phase::Execution => "execution",
+        }
+    }
+}
+
+/// How [`PhaseTimings::render`] lays out the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimingFormat {
+    /// One aligned `phase  1.234 ms` line per phase, then the total.
+    #[default]
+    Text,
+    /// A single JSON object: `{"phases": [{"phase": .., "ms": ..}], "total_ms": ..}`.
+    Json,
+}
+
+impl TimingFormat {
+    /// Parse the value of a `--timings=<FORMAT>` flag.
+    pub fn parse(name: &str) -> Result<TimingFormat, String> {
+        match name {
+            "text" => Ok(TimingFormat::Text),
+            "json" => Ok(TimingFormat::Json),
+            other => Err(format!("unknown timings format `{other}` (expected text or json)")),
+        }
+    }
+}
+
+/// Time recorded per phase. Recording the same phase twice adds up,
+/// so a session that checks several times reports the sum.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    durations: [Option<Duration>; 5],
+}
+
+impl PhaseTimings {
+    pub fn record(&mut self, phase: Phase, elapsed: Duration) {
+        let slot = &mut self.durations[phase as usize];
+        *slot = Some(slot.unwrap_or_default() + elapsed);
+    }
+
+    /// `None` when the phase never ran.
+    pub fn get(&self, phase: Phase) -> Option<Duration> {
+        self.durations[phase as usize]
+    }
+
+    /// The phases that ran, in pipeline order.
+    pub fn iter(&self) -> impl Iterator<Item = (Phase, Duration)> + '_ {
+        Phase::ALL.into_iter().filter_map(|p| self.get(p).map(|d| (p, d)))
+    }
+
+    pub fn total(&self) -> Duration {
+        self.iter().map(|(_, d)| d).sum()
+    }
+
+    pub fn clear(&mut self) {
+        *self = PhaseTimings::default();
+    }
+
+    /// The report, ending in a newline. Phases that never ran are left
+    /// out rather than shown as zero.
+    pub fn render(&self, format: TimingFormat) -> String {
+        match format {
+            TimingFormat::Text => {
+                let mut out = String::from("Phase timings:\n");
+                for (phase, elapsed) in self.iter() {
+                    out.push_str(&format!("  {:<18} {:>10.3} ms\n", phase.name(), millis(elapsed)));
+                }
+                out.push_str(&format!("  {:<18} {:>10.3} ms\n", "total", millis(self.total())));
+                out
+            }
+            TimingFormat::Json => {
+                let phases: Vec<String> = self
+                    .iter()
+                    .map(|(phase, elapsed)| format!("{{\"phase\": \"{}\", \"ms\": {:.3}}}", phase.name(), millis(elapsed)))
+                    .collect();
+                format!("{{\"phases\": [{}], \"total_ms\": {:.3}}}\n", phases.join(", "), millis(self.total()))
+            }
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_accumulates_and_skips_missing_phases() {
+        let mut timings = PhaseTimings::default();
+        timings.record(Phase::TypeCheck, Duration::from_millis(2));
+        timings.record(Phase::Parse, Duration::from_millis(1));
+        timings.record(Phase::TypeCheck, Duration::from_millis(3));
+        let phases: Vec<_> = timings.iter().collect();
+        assert_eq!(phases, vec![(Phase::Parse, Duration::from_millis(1)), (Phase::TypeCheck, Duration::from_millis(5))]);
+        assert_eq!(timings.total(), Duration::from_millis(6));
+    }
+
+    #[test]
+    fn render_text_and_json() {
+        let mut timings = PhaseTimings::default();
+        timings.record(Phase::Parse, Duration::from_micros(1500));
+        timings.record(Phase::Execution, Duration::from_millis(2));
+        assert_eq!(
+            timings.render(TimingFormat::Text),
+            "Phase timings:\n  parse                   1.500 ms\n  execution               2.000 ms\n  total                   3.500 ms\n"
+        );
+        assert_eq!(
+            timings.render(TimingFormat::Json),
+            "{\"phases\": [{\"phase\": \"parse\", \"ms\": 1.500}, {\"phase\": \"execution\", \"ms\": 2.000}], \"total_ms\": 3.500}\n"
+        );
+    }
+
+    #[test]
+    fn timings_format_names() {
+        assert_eq!(TimingFormat::parse("json"), Ok(TimingFormat::Json));
+        assert!(TimingFormat::parse("yaml").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Cross-reference index behind find-references and the call
+//! hierarchy (LSP, `interpreter --xref`).
+//!
+//! `CompilerSession::type_check_program` builds it from the source the
+//! session parsed last; `references` and `callers` query it. Symbols
+//! are named as code names them: `main`, `Point`, or `Point::x` for a
+//! member (field, method or enum variant) and `module::name` for an
+//! item of an imported module. Local bindings are not indexed.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use frontend::parser::LenientProgram;
+use frontend::token::Kind;
+use string_interner::DefaultStringInterner;
+
+use crate::symbol_index::{FileIndex, Symbol};
+use crate::CompilerSession;
+
+/// A mention of a symbol: its byte range plus the 1-based line and
+/// column it starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolLocation {
+    pub range: Range<usize>,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolReferences {
+    /// The declaration; `None` for an item of an imported module.
+    pub definition: Option<SymbolLocation>,
+    /// Every other mention, in source order.
+    pub uses: Vec<SymbolLocation>,
+}
+
+/// A call of some function from the body of `caller`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// `name` or `Owner::name` of the calling function or method.
+    pub caller: String,
+    pub location: SymbolLocation,
+}
+
+/// Definitions, uses and call sites of every symbol of one source.
+#[derive(Debug, Default)]
+pub struct XrefIndex {
+    source_name: String,
+    symbols: BTreeMap<String, SymbolReferences>,
+    callers: BTreeMap<String, Vec<CallSite>>,
+}
+
+impl XrefIndex {
+    pub fn build(
+        source_name: &str,
+        source: &str,
+        lenient: &LenientProgram,
+        interner: &DefaultStringInterner,
+    ) -> Self {
+        let index = FileIndex::new(source, Some(lenient), interner);
+        let mut xref = XrefIndex { source_name: source_name.to_string(), ..Default::default() };
+        for (i, (_, range)) in index.tokens.iter().enumerate() {
+            let (key, definition) = match index.classify(i) {
+                Symbol::Item(name) => {
+                    let definition = index.items.get(&name) == Some(&i);
+                    (name, definition)
+                }
+                Symbol::Member(owner, name) => {
+                    let owner = match owner {
+                        Some(owner) => owner,
+                        None => match index.owners_of(&name).as_slice() {
+                            [owner] => owner.clone(),
+                            _ => continue,
+                        },
+                    };
+                    (format!("{}::{}", owner, name), index.members.contains_key(&i))
+                }
+                Symbol::Imported(module, name) => (format!("{}::{}", module, name), false),
+                Symbol::Local(_) | Symbol::TraitMethod | Symbol::Unresolved => continue,
+            };
+            let location = locate(source, range.clone());
+            if index.kind(i + 1) == Some(&Kind::ParenOpen)
+                && !definition
+                && let Some(caller) = index.enclosing_function(i)
+            {
+                xref.callers
+                    .entry(key.clone())
+                    .or_default()
+                    .push(CallSite { caller: caller.clone(), location: location.clone() });
+            }
+            let entry = xref.symbols.entry(key).or_default();
+            if definition {
+                entry.definition = Some(location);
+            } else {
+                entry.uses.push(location);
+            }
+        }
+        xref
+    }
+
+    /// Name of the source the index was built from.
+    pub fn source_name(&self) -> &str {
+        &self.source_name
+    }
+
+    pub fn references(&self, symbol: &str) -> Option<&SymbolReferences> {
+        self.symbols.get(symbol)
+    }
+
+    /// Call sites of `function` (`name` or `Owner::name`), in source
+    /// order.
+    pub fn callers(&self, function: &str) -> &[CallSite] {
+        self.callers.get(function).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every indexed symbol name, sorted.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.symbols.keys().map(String::as_str)
+    }
+}
+
+fn locate(source: &str, range: Range<usize>) -> SymbolLocation {
+    let before = &source[..range.start];
+    let line = before.matches('\n').count() as u32 + 1;
+    let column = before.rsplit('\n').next().map_or(0, |text| text.chars().count()) as u32 + 1;
+    SymbolLocation { range, line, column }
+}
+
+impl CompilerSession {
+    /// The index the last `type_check_program` built, if any.
+    pub fn xref_index(&self) -> Option<&XrefIndex> {
+        self.xref_index.as_ref()
+    }
+
+    /// Definition and uses of `symbol`; see the module docs for how
+    /// symbols are named.
+    pub fn references(&self, symbol: &str) -> Option<&SymbolReferences> {
+        self.xref_index.as_ref()?.references(symbol)
+    }
+
+    /// Where `function` is called from.
+    pub fn callers(&self, function: &str) -> &[CallSite] {
+        self.xref_index.as_ref().map_or(&[], |xref| xref.callers(function))
+    }
+
+    pub(crate) fn build_xref_index(&mut self) {
+        let Some((name, source)) = self.last_source.take() else { return };
+        let lenient = frontend::Parser::new(&source, &mut self.string_interner).parse_program_lenient();
+        self.xref_index = Some(XrefIndex::build(&name, &source, &lenient, &self.string_interner));
+        self.last_source = Some((name, source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"struct Counter {
+    n: u64
+}
+
+impl Counter {
+    fn bump(&self) -> u64 {
+        self.n + 1u64
+    }
+}
+
+fn twice(c: Counter) -> u64 {
+    c.bump() + c.bump()
+}
+
+fn main() -> u64 {
+    val c = Counter { n: 1u64 }
+    twice(c) + c.n
+}
+"#;
+
+    fn session() -> CompilerSession {
+        let mut session = CompilerSession::new();
+        let program = session.parse_named_source("main.t", SOURCE).unwrap();
+        session.type_check_program(&program).unwrap();
+        session
+    }
+
+    #[test]
+    fn references_cover_definitions_and_uses() {
+        let session = session();
+        let counter = session.references("Counter").unwrap();
+        assert_eq!(counter.definition.as_ref().map(|d| (d.line, d.column)), Some((1, 8)));
+        assert_eq!(counter.uses.len(), 3);
+
+        let field = session.references("Counter::n").unwrap();
+        assert_eq!(field.definition.as_ref().map(|d| d.line), Some(2));
+        let lines: Vec<u32> = field.uses.iter().map(|u| u.line).collect();
+        assert_eq!(lines, vec![7, 16, 17]);
+
+        assert!(session.references("c").is_none(), "locals are not indexed");
+        assert_eq!(session.xref_index().unwrap().source_name(), "main.t");
+    }
+
+    #[test]
+    fn callers_name_the_calling_function() {
+        let session = session();
+        let callers: Vec<&str> = session.callers("Counter::bump").iter().map(|c| c.caller.as_str()).collect();
+        assert_eq!(callers, vec!["twice", "twice"]);
+        let callers: Vec<&str> = session.callers("twice").iter().map(|c| c.caller.as_str()).collect();
+        assert_eq!(callers, vec!["main"]);
+        assert!(session.callers("main").is_empty());
+    }
+}
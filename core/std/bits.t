@@ -0,0 +1,66 @@
+# NOTE: no `package` line, same as `core/std/i64.t` /
+# `core/std/hash.t` — the auto-load integration derives the module
+# path from the file system (`core/std/bits.t -> ["std", "bits"]`).
+#
+# Stdlib `BitOps` extension trait for the 64-bit integer types, so
+# `x.count_ones()` / `x.leading_zeros()` / `x.trailing_zeros()` /
+# `x.rotate_left(n)` / `x.rotate_right(n)` resolve through the
+# regular extension-trait dispatch path.
+#
+# Both impls forward to the `math::*` wrappers, which in turn call
+# the `__extern_*_u64` runtime helpers. The `i64` impl reinterprets
+# its bits as `u64` (two's complement), so `(-1i64).count_ones()` is
+# 64 and rotating an `i64` moves the sign bit like any other bit.
+# Counts are returned as `u64`; rotate amounts are taken modulo 64.
+
+trait BitOps {
+    fn count_ones(self: Self) -> u64
+    fn leading_zeros(self: Self) -> u64
+    fn trailing_zeros(self: Self) -> u64
+    fn rotate_left(self: Self, n: u64) -> Self
+    fn rotate_right(self: Self, n: u64) -> Self
+}
+
+impl BitOps for u64 {
+    fn count_ones(self: Self) -> u64 {
+        math::count_ones(self)
+    }
+
+    fn leading_zeros(self: Self) -> u64 {
+        math::leading_zeros(self)
+    }
+
+    fn trailing_zeros(self: Self) -> u64 {
+        math::trailing_zeros(self)
+    }
+
+    fn rotate_left(self: Self, n: u64) -> Self {
+        math::rotate_left(self, n)
+    }
+
+    fn rotate_right(self: Self, n: u64) -> Self {
+        math::rotate_right(self, n)
+    }
+}
+
+impl BitOps for i64 {
+    fn count_ones(self: Self) -> u64 {
+        math::count_ones(self as u64)
+    }
+
+    fn leading_zeros(self: Self) -> u64 {
+        math::leading_zeros(self as u64)
+    }
+
+    fn trailing_zeros(self: Self) -> u64 {
+        math::trailing_zeros(self as u64)
+    }
+
+    fn rotate_left(self: Self, n: u64) -> Self {
+        math::rotate_left(self as u64, n) as i64
+    }
+
+    fn rotate_right(self: Self, n: u64) -> Self {
+        math::rotate_right(self as u64, n) as i64
+    }
+}
@@ -0,0 +1,130 @@
+# NOTE: no `package` line — same reason as `core/std/dict.t`.
+# Auto-load derives the module path from the file system
+# (`core/std/channel.t -> ["std", "channel"]`).
+#
+# Stdlib `Channel<T>` — FIFO queue for handing values between
+# cooperative tasks (`spawn` / `yield_now`). Built on the pointer
+# primitives like `Dict`, except that every field lives in one
+# heap block behind `state`: a channel is copied into each task
+# that uses it, and all of those copies must see the same queue.
+#
+# State block layout: 0 item buffer (ptr), 8 head, 16 tail,
+# 24 capacity in items, 32 item size (all u64), 40 closed (bool).
+# Pending items are `buffer[head..tail]`.
+#
+# `recv` is the blocking receive: while the queue is empty it
+# runs the other tasks with `yield_now()` until one of them sends,
+# closes the channel, or no task is left to run.
+
+struct Channel<T> {
+    state: ptr,
+}
+
+impl<T> Channel<T> {
+    fn new() -> Self {
+        val state = __builtin_heap_alloc(48u64)
+        __builtin_ptr_write(state, 0u64, __builtin_heap_alloc(0u64))
+        __builtin_ptr_write(state, 8u64, 0u64)
+        __builtin_ptr_write(state, 16u64, 0u64)
+        __builtin_ptr_write(state, 24u64, 0u64)
+        __builtin_ptr_write(state, 32u64, 0u64)
+        __builtin_ptr_write(state, 40u64, false)
+        Channel { state: state }
+    }
+
+    # Queue `value`. Never waits; the buffer grows as needed.
+    # Sending on a closed channel panics.
+    fn send(self: Self, value: T) {
+        if self.is_closed() {
+            panic("Channel::send on a closed channel")
+        }
+        var buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        var head: u64 = __builtin_ptr_read(self.state, 8u64)
+        var tail: u64 = __builtin_ptr_read(self.state, 16u64)
+        var cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        var size: u64 = __builtin_ptr_read(self.state, 32u64)
+        if size == 0u64 {
+            size = __builtin_sizeof(value)
+            __builtin_ptr_write(self.state, 32u64, size)
+        }
+        if tail >= cap && head > 0u64 {
+            # Slide the pending items to the front before growing.
+            var i: u64 = head
+            while i < tail {
+                val item: T = __builtin_ptr_read(buf, i * size)
+                __builtin_ptr_write(buf, (i - head) * size, item)
+                i = i + 1u64
+            }
+            tail = tail - head
+            head = 0u64
+            __builtin_ptr_write(self.state, 8u64, head)
+        }
+        if tail >= cap {
+            cap = if cap == 0u64 { 4u64 } else { cap * 2u64 }
+            buf = __builtin_heap_realloc(buf, cap * size)
+            __builtin_ptr_write(self.state, 0u64, buf)
+            __builtin_ptr_write(self.state, 24u64, cap)
+        }
+        __builtin_ptr_write(buf, tail * size, value)
+        __builtin_ptr_write(self.state, 16u64, tail + 1u64)
+    }
+
+    # Take the oldest pending value without waiting; `None` when
+    # the queue is empty.
+    fn try_recv(self: Self) -> Option<T> {
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val tail: u64 = __builtin_ptr_read(self.state, 16u64)
+        if head >= tail {
+            return Option::None
+        }
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, head * size)
+        if head + 1u64 == tail {
+            __builtin_ptr_write(self.state, 8u64, 0u64)
+            __builtin_ptr_write(self.state, 16u64, 0u64)
+        } else {
+            __builtin_ptr_write(self.state, 8u64, head + 1u64)
+        }
+        Option::Some(item)
+    }
+
+    # Take the oldest value, running the other tasks until one is
+    # sent. `None` once the channel is closed and drained, or when
+    # no task is left that could send.
+    fn recv(self: Self) -> Option<T> {
+        while true {
+            if val Option::Some(item) = self.try_recv() {
+                return Option::Some(item)
+            }
+            if self.is_closed() {
+                return Option::None
+            }
+            if !yield_now() {
+                return Option::None
+            }
+        }
+        Option::None
+    }
+
+    # Mark the channel closed. Pending values can still be received.
+    fn close(self: Self) {
+        __builtin_ptr_write(self.state, 40u64, true)
+    }
+
+    fn is_closed(self: Self) -> bool {
+        val closed: bool = __builtin_ptr_read(self.state, 40u64)
+        closed
+    }
+
+    # Number of values sent but not received yet.
+    fn len(self: Self) -> u64 {
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val tail: u64 = __builtin_ptr_read(self.state, 16u64)
+        tail - head
+    }
+
+    fn is_empty(self: Self) -> bool {
+        self.len() == 0u64
+    }
+}
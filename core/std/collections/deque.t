@@ -0,0 +1,178 @@
+# Stdlib `Deque<T>` — double-ended queue on a growable ring buffer,
+# built on the pointer primitives like `Vec<T>`
+# (`core/std/collections/vec.t`). Use it as a FIFO queue
+# (`push_back` + `pop_front`) or a LIFO stack (`push_back` +
+# `pop_back`); every end operation is amortised O(1).
+#
+# Auto-loaded from `<core>/std/collections/deque.t -> ["std",
+# "collections", "deque"]`. No `package` line, same as `vec.t`.
+#
+# API:
+#   - `Deque::new() -> Self`
+#   - `d.push_back(value)` / `d.push_front(value)`
+#   - `d.pop_back() -> T` / `d.pop_front() -> T` — panic when empty
+#   - `d.back() -> T` / `d.front() -> T` — peek, panic when empty
+#   - `d.get(i) -> T` — `i`-th element from the front (no bounds
+#     check)
+#   - `d.size() -> u64`, `d.is_empty() -> bool`, `d.clear()`
+#
+# Like `core/std/channel.t`, every field lives in one heap block
+# behind `state` and the methods take `self: Self`: copies of a
+# deque share one queue, and no method has to write fields back
+# to the caller (five of them plus a popped value do not fit the
+# AOT `&mut self` return registers).
+#
+# State block layout: 0 item buffer (ptr), 8 head, 16 length,
+# 24 capacity in items, 32 item size (all u64). Element `i` lives
+# in slot `(head + i) % capacity`. The `T`-returning methods read
+# the slot themselves instead of calling `get`: inside the generic
+# impl a call's `T` result is not resolved yet.
+
+struct Deque<T> {
+    state: ptr,
+}
+
+impl<T> Deque<T> {
+    fn new() -> Self {
+        val state = __builtin_heap_alloc(40u64)
+        __builtin_ptr_write(state, 0u64, __builtin_heap_alloc(0u64))
+        __builtin_ptr_write(state, 8u64, 0u64)
+        __builtin_ptr_write(state, 16u64, 0u64)
+        __builtin_ptr_write(state, 24u64, 0u64)
+        __builtin_ptr_write(state, 32u64, 0u64)
+        Deque { state: state }
+    }
+
+    fn size(self: Self) -> u64 {
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        len
+    }
+
+    fn is_empty(self: Self) -> bool {
+        self.size() == 0u64
+    }
+
+    # `index`-th item from the front. No bounds check — caller is
+    # responsible for `index < self.size()`.
+    fn get(self: Self, index: u64) -> T {
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, ((head + index) % cap) * size)
+        item
+    }
+
+    fn front(self: Self) -> T {
+        if self.is_empty() {
+            panic("Deque::front on an empty deque")
+        }
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, head * size)
+        item
+    }
+
+    fn back(self: Self) -> T {
+        if self.is_empty() {
+            panic("Deque::back on an empty deque")
+        }
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, ((head + len - 1u64) % cap) * size)
+        item
+    }
+
+    # Make room for one more item. A full buffer is copied in order
+    # into one twice the size, so `head` is 0 again afterwards.
+    fn reserve_one(self: Self, value: T) {
+        var size: u64 = __builtin_ptr_read(self.state, 32u64)
+        if size == 0u64 {
+            size = __builtin_sizeof(value)
+            __builtin_ptr_write(self.state, 32u64, size)
+        }
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        if len < cap {
+            return
+        }
+        val old: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val new_cap: u64 = if cap == 0u64 { 4u64 } else { cap * 2u64 }
+        val buf: ptr = __builtin_heap_alloc(new_cap * size)
+        var i: u64 = 0u64
+        while i < len {
+            val item: T = __builtin_ptr_read(old, ((head + i) % cap) * size)
+            __builtin_ptr_write(buf, i * size, item)
+            i = i + 1u64
+        }
+        __builtin_heap_free(old)
+        __builtin_ptr_write(self.state, 0u64, buf)
+        __builtin_ptr_write(self.state, 8u64, 0u64)
+        __builtin_ptr_write(self.state, 24u64, new_cap)
+    }
+
+    fn push_back(self: Self, value: T) {
+        self.reserve_one(value)
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        __builtin_ptr_write(buf, ((head + len) % cap) * size, value)
+        __builtin_ptr_write(self.state, 16u64, len + 1u64)
+    }
+
+    fn push_front(self: Self, value: T) {
+        self.reserve_one(value)
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        # `head - 1` modulo `cap`, without underflowing at 0.
+        val new_head: u64 = (head + cap - 1u64) % cap
+        __builtin_ptr_write(buf, new_head * size, value)
+        __builtin_ptr_write(self.state, 8u64, new_head)
+        __builtin_ptr_write(self.state, 16u64, len + 1u64)
+    }
+
+    fn pop_front(self: Self) -> T {
+        if self.is_empty() {
+            panic("Deque::pop_front on an empty deque")
+        }
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, head * size)
+        __builtin_ptr_write(self.state, 8u64, (head + 1u64) % cap)
+        __builtin_ptr_write(self.state, 16u64, len - 1u64)
+        item
+    }
+
+    fn pop_back(self: Self) -> T {
+        if self.is_empty() {
+            panic("Deque::pop_back on an empty deque")
+        }
+        val buf: ptr = __builtin_ptr_read(self.state, 0u64)
+        val head: u64 = __builtin_ptr_read(self.state, 8u64)
+        val len: u64 = __builtin_ptr_read(self.state, 16u64)
+        val cap: u64 = __builtin_ptr_read(self.state, 24u64)
+        val size: u64 = __builtin_ptr_read(self.state, 32u64)
+        val item: T = __builtin_ptr_read(buf, ((head + len - 1u64) % cap) * size)
+        __builtin_ptr_write(self.state, 16u64, len - 1u64)
+        item
+    }
+
+    # Drop every item; the buffer is kept for reuse.
+    fn clear(self: Self) {
+        __builtin_ptr_write(self.state, 8u64, 0u64)
+        __builtin_ptr_write(self.state, 16u64, 0u64)
+    }
+}
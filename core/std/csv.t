@@ -0,0 +1,197 @@
+package std.csv
+
+# Stdlib CSV / TSV module. Auto-loaded from `<core>/std/csv.t`, so
+# user programs call `csv::parse_csv(text)` (or the bare name) with no
+# `import` line.
+#
+# A table is a `Vec<Vec<String>>`: one inner `Vec<String>` per record,
+# one `String` per field. This is the growable stand-in for `[[str]]`
+# (fixed-size arrays can't hold a row count only known at run time).
+#
+# API:
+#   - `parse_csv(text: str) -> Vec<Vec<String>>`
+#   - `parse_tsv(text: str) -> Vec<Vec<String>>`
+#   - `parse_delimited(text: &String, sep: u8) -> Vec<Vec<String>>`
+#   - `format_csv(rows: &Vec<Vec<String>>) -> String`
+#   - `format_tsv(rows: &Vec<Vec<String>>) -> String`
+#   - `format_delimited(rows: &Vec<Vec<String>>, sep: u8) -> String`
+#   - `read_csv(path: str) -> Vec<Vec<String>>` (interpreter only)
+#   - `write_csv(path: str, rows: &Vec<Vec<String>>)` (interpreter only)
+#
+# Parsing follows RFC 4180 and is lenient where the RFC is silent:
+#   - A field wrapped in `"` may contain the separator, CR, LF, and
+#     `""` for a literal quote. A quote anywhere else in an unquoted
+#     field also starts a quoted run, as Python's `csv` module does.
+#   - Records end at LF, CRLF, or a lone CR. A line with no bytes at
+#     all is skipped, so a trailing newline doesn't add an empty
+#     record; `""` on its own line is one empty field.
+#   - An unterminated quoted field panics.
+#
+# Formatting quotes a field only when it contains the separator, a
+# quote, CR, or LF (or is the only field of its record and empty),
+# doubles the quotes inside, and ends every record with LF.
+# `parse_csv(format_csv(rows))` gives back `rows`, except that a
+# record with no fields at all is dropped.
+#
+# Everything works on byte values, so UTF-8 text passes through
+# untouched and a multi-byte separator isn't supported.
+#
+# The file helpers are `@cfg(target = "interpreter")`: `read_bytes` /
+# `write_bytes` are interpreter-only, and the AOT compiler lowers
+# every non-generic function it is handed. They go through the same
+# `fs-read` / `fs-write` capabilities as the builtins.
+
+pub fn parse_csv(text: str) -> Vec<Vec<String>> {
+    val src: String = String::from_str(text)
+    val rows: Vec<Vec<String>> = parse_delimited(src, 0x2Cu8)
+    rows
+}
+
+pub fn parse_tsv(text: str) -> Vec<Vec<String>> {
+    val src: String = String::from_str(text)
+    val rows: Vec<Vec<String>> = parse_delimited(src, 0x09u8)
+    rows
+}
+
+# One pass over the bytes. Each record and each field gets a fresh
+# binding inside its loop (rather than re-assigning one outer `var`)
+# because the AOT lowering can't re-assign a struct-valued binding
+# from a constructor call.
+pub fn parse_delimited(src: &String, sep: u8) -> Vec<Vec<String>> {
+    var rows: Vec<Vec<String>> = Vec::new()
+    val n: u64 = src.len()
+    var i: u64 = 0u64
+    while i < n {
+        val first: u8 = src.get(i)
+        if first == 0x0Au8 {
+            i = i + 1u64
+        } elif first == 0x0Du8 {
+            i = i + 1u64
+            if i < n && src.get(i) == 0x0Au8 {
+                i = i + 1u64
+            }
+        } else {
+            var row: Vec<String> = Vec::new()
+            var record_done: bool = false
+            while !record_done {
+                var field: String = String::new()
+                var in_quotes: bool = false
+                var field_done: bool = false
+                while !field_done {
+                    if i >= n {
+                        assert(!in_quotes, "parse_csv: unterminated quoted field")
+                        field_done = true
+                        record_done = true
+                    } else {
+                        val b: u8 = src.get(i)
+                        i = i + 1u64
+                        if in_quotes {
+                            if b != 0x22u8 {
+                                field.push(b)
+                            } elif i < n && src.get(i) == 0x22u8 {
+                                field.push(0x22u8)
+                                i = i + 1u64
+                            } else {
+                                in_quotes = false
+                            }
+                        } elif b == 0x22u8 {
+                            in_quotes = true
+                        } elif b == sep {
+                            field_done = true
+                        } elif b == 0x0Au8 || b == 0x0Du8 {
+                            if b == 0x0Du8 && i < n && src.get(i) == 0x0Au8 {
+                                i = i + 1u64
+                            }
+                            field_done = true
+                            record_done = true
+                        } else {
+                            field.push(b)
+                        }
+                    }
+                }
+                row.push(field)
+            }
+            rows.push(row)
+        }
+    }
+    rows
+}
+
+pub fn format_csv(rows: &Vec<Vec<String>>) -> String {
+    val out: String = format_delimited(rows, 0x2Cu8)
+    out
+}
+
+pub fn format_tsv(rows: &Vec<Vec<String>>) -> String {
+    val out: String = format_delimited(rows, 0x09u8)
+    out
+}
+
+pub fn format_delimited(rows: &Vec<Vec<String>>, sep: u8) -> String {
+    var out: String = String::new()
+    var r: u64 = 0u64
+    while r < rows.size() {
+        val row: Vec<String> = rows.get(r)
+        var c: u64 = 0u64
+        while c < row.size() {
+            if c > 0u64 {
+                out.push(sep)
+            }
+            val field: String = row.get(c)
+            # A lone empty field is quoted so the record doesn't
+            # become a blank line, which parsing skips.
+            val lone_empty: bool = row.size() == 1u64 && field.len() == 0u64
+            val quoted: bool = lone_empty || field_needs_quotes(field, sep)
+            if quoted {
+                out.push(0x22u8)
+            }
+            var i: u64 = 0u64
+            while i < field.len() {
+                val b: u8 = field.get(i)
+                if b == 0x22u8 {
+                    out.push(0x22u8)
+                }
+                out.push(b)
+                i = i + 1u64
+            }
+            if quoted {
+                out.push(0x22u8)
+            }
+            c = c + 1u64
+        }
+        out.push(0x0Au8)
+        r = r + 1u64
+    }
+    out
+}
+
+fn field_needs_quotes(field: &String, sep: u8) -> bool {
+    var i: u64 = 0u64
+    while i < field.len() {
+        val b: u8 = field.get(i)
+        if b == sep || b == 0x22u8 || b == 0x0Au8 || b == 0x0Du8 {
+            return true
+        }
+        i = i + 1u64
+    }
+    false
+}
+
+@cfg(target = "interpreter")
+pub fn read_csv(path: str) -> Vec<Vec<String>> {
+    val data: bytes = read_bytes(path)
+    var src: String = String::new()
+    var i: u64 = 0u64
+    while i < data.len() {
+        src.push(data[i] as u8)
+        i = i + 1u64
+    }
+    val rows: Vec<Vec<String>> = parse_delimited(src, 0x2Cu8)
+    rows
+}
+
+@cfg(target = "interpreter")
+pub fn write_csv(path: str, rows: &Vec<Vec<String>>) {
+    val out: String = format_delimited(rows, 0x2Cu8)
+    write_bytes(path, __builtin_bytes_from_ptr(out.as_ptr(), out.len()))
+}
@@ -38,6 +38,11 @@ extern fn __extern_sqrt_f64(x: f64) -> f64
 extern fn __extern_abs_f64(x: f64) -> f64
 extern fn __extern_abs_i64(x: i64) -> i64
 extern fn __extern_pow_f64(base: f64, exp: f64) -> f64
+extern fn __extern_count_ones_u64(x: u64) -> u64
+extern fn __extern_leading_zeros_u64(x: u64) -> u64
+extern fn __extern_trailing_zeros_u64(x: u64) -> u64
+extern fn __extern_rotate_left_u64(x: u64, n: u64) -> u64
+extern fn __extern_rotate_right_u64(x: u64, n: u64) -> u64
 
 pub fn abs(x: i64) -> i64 {
     # Forwards to the runtime `wrapping_abs` helper so `i64::MIN.abs()`
@@ -70,6 +75,43 @@ pub fn max_u64(a: u64, b: u64) -> u64 {
     __builtin_max(a, b)
 }
 
+# `clamp_*` restrict `x` to `[lo, hi]`. An inverted range panics
+# rather than silently picking one bound.
+pub fn clamp_i64(x: i64, lo: i64, hi: i64) -> i64 {
+    assert(lo <= hi, "math::clamp_i64: lo must not exceed hi")
+    __builtin_min(__builtin_max(x, lo), hi)
+}
+
+pub fn clamp_u64(x: u64, lo: u64, hi: u64) -> u64 {
+    assert(lo <= hi, "math::clamp_u64: lo must not exceed hi")
+    __builtin_min(__builtin_max(x, lo), hi)
+}
+
+# f64 variant: a NaN `x` falls through both comparisons and is
+# returned unchanged.
+pub fn clamp_f64(x: f64, lo: f64, hi: f64) -> f64 {
+    assert(lo <= hi, "math::clamp_f64: lo must not exceed hi")
+    if x < lo {
+        lo
+    } elif x > hi {
+        hi
+    } else {
+        x
+    }
+}
+
+# Greatest common divisor (Euclid). `gcd(0, 0)` is 0.
+pub fn gcd(a: u64, b: u64) -> u64 {
+    var x = a
+    var y = b
+    while y != 0u64 {
+        val t = x % y
+        x = y
+        y = t
+    }
+    x
+}
+
 pub fn pow(base: f64, exp: f64) -> f64 {
     __extern_pow_f64(base, exp)
 }
@@ -110,6 +152,30 @@ pub fn ceil(x: f64) -> f64 {
     __extern_ceil_f64(x)
 }
 
+# Bit manipulation over the raw 64-bit pattern. `i64` callers go
+# through `core/std/bits.t`, which reinterprets the value as `u64`
+# first. `leading_zeros(0)` / `trailing_zeros(0)` are 64; rotate
+# amounts are taken modulo 64.
+pub fn count_ones(x: u64) -> u64 {
+    __extern_count_ones_u64(x)
+}
+
+pub fn leading_zeros(x: u64) -> u64 {
+    __extern_leading_zeros_u64(x)
+}
+
+pub fn trailing_zeros(x: u64) -> u64 {
+    __extern_trailing_zeros_u64(x)
+}
+
+pub fn rotate_left(x: u64, n: u64) -> u64 {
+    __extern_rotate_left_u64(x, n)
+}
+
+pub fn rotate_right(x: u64, n: u64) -> u64 {
+    __extern_rotate_right_u64(x, n)
+}
+
 # Note: `math::add` / `math::multiply` are intentionally NOT
 # exported. Users name their own `fn add(...)` / `fn multiply(...)`
 # over custom types frequently enough that the stdlib should not
@@ -0,0 +1,55 @@
+package std.net
+
+# Stdlib TCP module. Auto-loaded from `<core>/std/net.t`, so user
+# programs call `net::listen(8080u64)` with no `import` line.
+#
+# Thin wrappers over the `tcp_*` builtins under shorter names. A
+# socket is a `u64` handle; listeners and connections share one
+# handle space, and `close` releases either.
+#
+# API:
+#   - `listen(port: u64) -> u64` (port 0 picks a free one)
+#   - `local_port(handle: u64) -> u64`
+#   - `accept(listener: u64) -> u64` (blocks for the next client)
+#   - `read(conn: u64) -> str` (what has arrived; empty once the
+#     peer closed)
+#   - `write(conn: u64, data: str)`
+#   - `close(handle: u64)`
+#
+# Every call needs the `net` capability (`--allow-net`). Call these
+# through `net::` — the bare names are easy to shadow, and a program
+# that defines its own `read` / `write` keeps them.
+#
+# All of it is `@cfg(target = "interpreter")`: the builtins are
+# interpreter-only, and the AOT compiler lowers every non-generic
+# function it is handed.
+
+@cfg(target = "interpreter")
+pub fn listen(port: u64) -> u64 {
+    tcp_listen(port)
+}
+
+@cfg(target = "interpreter")
+pub fn local_port(handle: u64) -> u64 {
+    tcp_local_port(handle)
+}
+
+@cfg(target = "interpreter")
+pub fn accept(listener: u64) -> u64 {
+    tcp_accept(listener)
+}
+
+@cfg(target = "interpreter")
+pub fn read(conn: u64) -> str {
+    tcp_read(conn)
+}
+
+@cfg(target = "interpreter")
+pub fn write(conn: u64, data: str) {
+    tcp_write(conn, data)
+}
+
+@cfg(target = "interpreter")
+pub fn close(handle: u64) {
+    tcp_close(handle)
+}
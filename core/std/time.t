@@ -0,0 +1,114 @@
+package std.time
+
+# Stdlib date/time module. Auto-loaded from `<core>/std/time.t`, so
+# user programs call `time::format_timestamp(t, "%F %T")` or
+# `time::year(t)` with no `import` line.
+#
+# A timestamp is an `i64` count of seconds since
+# 1970-01-01T00:00:00Z (negative before it). Everything is UTC: there
+# is no time-zone database, and leap seconds don't exist.
+#
+# API:
+#   - `format_timestamp(epoch: i64, fmt: str) -> str` (interpreter only)
+#   - `now() -> i64` (interpreter only; `now_millis() / 1000`)
+#   - `year(epoch: i64) -> i64`
+#   - `month(epoch: i64) -> i64` (1..=12)
+#   - `day(epoch: i64) -> i64` (1..=31)
+#   - `hour(epoch: i64) -> i64`, `minute`, `second`
+#   - `weekday(epoch: i64) -> i64` (0 = Sunday .. 6 = Saturday)
+#   - `day_of_year(epoch: i64) -> i64` (1..=366)
+#
+# `format_timestamp` is `strftime` in UTC, implemented natively by the
+# interpreter (`evaluation/timestamp.rs`). It supports `%Y %y %m %d
+# %e %H %M %S %j %a %A %b %B %u %w %s %F %T %D %R %Z %z %n %t %%`; any
+# other conversion panics.
+#
+# The component functions are plain toylang so they also run in AOT
+# builds. They use the same proleptic Gregorian arithmetic as the
+# native formatter (Howard Hinnant's `civil_from_days`), so
+# `year(t)` always agrees with `format_timestamp(t, "%Y")`.
+
+@cfg(target = "interpreter")
+pub fn format_timestamp(epoch: i64, fmt: str) -> str {
+    __builtin_format_timestamp(epoch, fmt)
+}
+
+@cfg(target = "interpreter")
+pub fn now() -> i64 {
+    (now_millis() / 1000u64) as i64
+}
+
+pub fn year(epoch: i64) -> i64 {
+    civil_part(floor_div(epoch, 86400i64), 0u64)
+}
+
+pub fn month(epoch: i64) -> i64 {
+    civil_part(floor_div(epoch, 86400i64), 1u64)
+}
+
+pub fn day(epoch: i64) -> i64 {
+    civil_part(floor_div(epoch, 86400i64), 2u64)
+}
+
+pub fn hour(epoch: i64) -> i64 {
+    floor_mod(epoch, 86400i64) / 3600i64
+}
+
+pub fn minute(epoch: i64) -> i64 {
+    floor_mod(epoch, 3600i64) / 60i64
+}
+
+pub fn second(epoch: i64) -> i64 {
+    floor_mod(epoch, 60i64)
+}
+
+pub fn weekday(epoch: i64) -> i64 {
+    # 1970-01-01 was a Thursday.
+    floor_mod(floor_div(epoch, 86400i64) + 4i64, 7i64)
+}
+
+pub fn day_of_year(epoch: i64) -> i64 {
+    val days: i64 = floor_div(epoch, 86400i64)
+    days - days_from_civil(civil_part(days, 0u64), 1i64, 1i64) + 1i64
+}
+
+# Year (`part` 0), month (1) or day (2) of the day `days` after
+# 1970-01-01. One helper with a selector rather than a tuple return
+# keeps it lowerable by the AOT compiler.
+fn civil_part(days: i64, part: u64) -> i64 {
+    val z: i64 = days + 719468i64
+    val era: i64 = floor_div(z, 146097i64)
+    val doe: i64 = z - era * 146097i64
+    val yoe: i64 = (doe - doe / 1460i64 + doe / 36524i64 - doe / 146096i64) / 365i64
+    val doy: i64 = doe - (365i64 * yoe + yoe / 4i64 - yoe / 100i64)
+    val mp: i64 = (5i64 * doy + 2i64) / 153i64
+    val m: i64 = if mp < 10i64 { mp + 3i64 } else { mp - 9i64 }
+    if part == 0u64 {
+        if m <= 2i64 { yoe + era * 400i64 + 1i64 } else { yoe + era * 400i64 }
+    } elif part == 1u64 {
+        m
+    } else {
+        doy - (153i64 * mp + 2i64) / 5i64 + 1i64
+    }
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    val y: i64 = if month <= 2i64 { year - 1i64 } else { year }
+    val era: i64 = floor_div(y, 400i64)
+    val yoe: i64 = y - era * 400i64
+    val mp: i64 = (month + 9i64) % 12i64
+    val doy: i64 = (153i64 * mp + 2i64) / 5i64 + day - 1i64
+    val doe: i64 = yoe * 365i64 + yoe / 4i64 - yoe / 100i64 + doy
+    era * 146097i64 + doe - 719468i64
+}
+
+# `/` and `%` truncate toward zero; calendar math wants floor so that
+# pre-1970 timestamps land on the right day.
+fn floor_div(a: i64, b: i64) -> i64 {
+    val q: i64 = a / b
+    if a % b != 0i64 && (a < 0i64) != (b < 0i64) { q - 1i64 } else { q }
+}
+
+fn floor_mod(a: i64, b: i64) -> i64 {
+    a - floor_div(a, b) * b
+}
@@ -169,6 +169,8 @@ fn rewrite_program(program: &mut Program, aliases: &AliasMap) {
     // Expression-level rewrites:
     //   - `expr as Type` — casts carry a TypeDecl that needs alias
     //     resolution.
+    //   - `f<Alias>(args)` / `Name<Alias> { .. }` — explicit type
+    //     arguments likewise.
     //   - `Alias::function(args)` — the qualifier is a bare symbol
     //     in the AST (`AssociatedFunctionCall(struct_name, fn_name,
     //     args)`); when `struct_name` resolves to an alias whose
@@ -189,6 +191,18 @@ fn rewrite_program(program: &mut Program, aliases: &AliasMap) {
                     program.expression.update(&expr_ref, Expr::Cast(target, new_ty));
                 }
             }
+            Expr::Call(name, args, type_args) if !type_args.is_empty() => {
+                let resolved: Vec<TypeDecl> = type_args.iter().map(|ty| resolve_in_type(aliases, ty)).collect();
+                if resolved != type_args {
+                    program.expression.update(&expr_ref, Expr::Call(name, args, resolved));
+                }
+            }
+            Expr::StructLiteral(name, fields, type_args) if !type_args.is_empty() => {
+                let resolved: Vec<TypeDecl> = type_args.iter().map(|ty| resolve_in_type(aliases, ty)).collect();
+                if resolved != type_args {
+                    program.expression.update(&expr_ref, Expr::StructLiteral(name, fields, resolved));
+                }
+            }
             Expr::AssociatedFunctionCall(struct_sym, fn_sym, args) => {
                 if let Some((params, target)) = aliases.get(&struct_sym)
                     && params.is_empty() {
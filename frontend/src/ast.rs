@@ -2,6 +2,7 @@ pub mod expr;
 pub mod pool;
 pub mod program;
 pub mod builder;
+pub mod dump;
 
 pub use expr::*;
 pub use pool::*;
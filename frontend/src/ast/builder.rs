@@ -176,6 +176,12 @@ impl AstBuilder {
         expr_ref
     }
 
+    pub fn bytes_expr(&mut self, symbol: DefaultSymbol, location: Option<SourceLocation>) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::Bytes(symbol));
+        self.location_pool.add_expr_location(location);
+        expr_ref
+    }
+
     pub fn number_expr(&mut self, symbol: DefaultSymbol, location: Option<SourceLocation>) -> ExprRef {
         let expr_ref = self.expr_pool.add(Expr::Number(symbol));
         self.location_pool.add_expr_location(location);
@@ -213,9 +219,14 @@ impl AstBuilder {
     }
 
     pub fn call_expr(&mut self, fn_name: DefaultSymbol, args: Vec<ExprRef>, location: Option<SourceLocation>) -> ExprRef {
+        self.generic_call_expr(fn_name, vec![], args, location)
+    }
+
+    /// `fn_name<type_args>(args)`; an empty `type_args` is a plain call.
+    pub fn generic_call_expr(&mut self, fn_name: DefaultSymbol, type_args: Vec<TypeDecl>, args: Vec<ExprRef>, location: Option<SourceLocation>) -> ExprRef {
         let args_ref = self.expr_pool.add(Expr::ExprList(args));
         self.location_pool.add_expr_location(None); // args_ref location
-        let expr_ref = self.expr_pool.add(Expr::Call(fn_name, args_ref));
+        let expr_ref = self.expr_pool.add(Expr::Call(fn_name, args_ref, type_args));
         self.location_pool.add_expr_location(location);
         expr_ref
     }
@@ -232,8 +243,8 @@ impl AstBuilder {
         expr_ref
     }
 
-    pub fn slice_assign_expr(&mut self, object: ExprRef, start: Option<ExprRef>, end: Option<ExprRef>, value: ExprRef, location: Option<SourceLocation>) -> ExprRef {
-        let expr_ref = self.expr_pool.add(Expr::SliceAssign(object, start, end, value));
+    pub fn slice_assign_expr(&mut self, object: ExprRef, slice_info: SliceInfo, value: ExprRef, location: Option<SourceLocation>) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::SliceAssign(object, slice_info, value));
         self.location_pool.add_expr_location(location);
         expr_ref
     }
@@ -280,6 +291,32 @@ impl AstBuilder {
         expr_ref
     }
 
+    pub fn with_resource_expr(&mut self, name: DefaultSymbol, resource: ExprRef, body: ExprRef, location: Option<SourceLocation>) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::WithResource { name, resource, body });
+        self.location_pool.add_expr_location(location);
+        expr_ref
+    }
+
+    pub fn try_catch_expr(&mut self, body: ExprRef, error: DefaultSymbol, handler: ExprRef, location: Option<SourceLocation>) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::TryCatch { body, error, handler });
+        self.location_pool.add_expr_location(location);
+        expr_ref
+    }
+
+    pub fn comprehension_expr(
+        &mut self,
+        element: ExprRef,
+        value: Option<ExprRef>,
+        var: DefaultSymbol,
+        source: ExprRef,
+        filter: Option<ExprRef>,
+        location: Option<SourceLocation>,
+    ) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::Comprehension { element, value, var, source, filter });
+        self.location_pool.add_expr_location(location);
+        expr_ref
+    }
+
     pub fn closure_expr(
         &mut self,
         params: crate::ast::ParameterList,
@@ -305,7 +342,12 @@ impl AstBuilder {
     }
 
     pub fn struct_literal_expr(&mut self, type_name: DefaultSymbol, fields: Vec<(DefaultSymbol, ExprRef)>, location: Option<SourceLocation>) -> ExprRef {
-        let expr_ref = self.expr_pool.add(Expr::StructLiteral(type_name, fields));
+        self.generic_struct_literal_expr(type_name, vec![], fields, location)
+    }
+
+    /// `TypeName<type_args> { fields }`; an empty `type_args` is a plain literal.
+    pub fn generic_struct_literal_expr(&mut self, type_name: DefaultSymbol, type_args: Vec<TypeDecl>, fields: Vec<(DefaultSymbol, ExprRef)>, location: Option<SourceLocation>) -> ExprRef {
+        let expr_ref = self.expr_pool.add(Expr::StructLiteral(type_name, fields, type_args));
         self.location_pool.add_expr_location(location);
         expr_ref
     }
@@ -353,13 +395,25 @@ impl AstBuilder {
         stmt_ref
     }
 
+    pub fn yield_stmt(&mut self, value: ExprRef, location: Option<SourceLocation>) -> StmtRef {
+        let stmt_ref = self.stmt_pool.add(Stmt::Yield(value));
+        self.location_pool.add_stmt_location(location);
+        stmt_ref
+    }
+
     pub fn break_stmt(&mut self, location: Option<SourceLocation>) -> StmtRef {
         self.break_stmt_with_label(None, location)
     }
 
     /// LABEL: emit `break` with an optional target label (`break @outer`).
     pub fn break_stmt_with_label(&mut self, label: Option<DefaultSymbol>, location: Option<SourceLocation>) -> StmtRef {
-        let stmt_ref = self.stmt_pool.add(Stmt::Break(label));
+        self.break_stmt_with_value(label, None, location)
+    }
+
+    /// `break value` / `break @label value`: the value becomes the
+    /// result of the targeted `loop` expression.
+    pub fn break_stmt_with_value(&mut self, label: Option<DefaultSymbol>, value: Option<ExprRef>, location: Option<SourceLocation>) -> StmtRef {
+        let stmt_ref = self.stmt_pool.add(Stmt::Break(label, value));
         self.location_pool.add_stmt_location(location);
         stmt_ref
     }
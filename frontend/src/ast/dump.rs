@@ -0,0 +1,753 @@
+//! Debug serializer for the AST.
+//!
+//! Walks a `Program` (functions, consts, and the top-level declarations
+//! stored in its `StmtPool`) and produces a generic `DumpNode` tree,
+//! which can then be rendered as an indented tree (`to_pretty`), JSON
+//! (`to_json`), or a Graphviz digraph (`to_dot`). Drivers expose these
+//! as `--emit-ast`, `--emit-ast=json`, and `--emit-ast-dot`.
+//!
+//! The dumper resolves interned symbols so the output is readable
+//! without the interner, and tolerates dangling `ExprRef` / `StmtRef`
+//! indices (printed as `<invalid ...>`) so half-built ASTs from parser
+//! experiments can still be inspected.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+
+use crate::type_decl::TypeDecl;
+use super::{
+    Expr, ExprRef, Function, MatchArm, MethodFunction, Pattern, Program, SliceType, Stmt,
+    StmtRef, TraitMethodSignature, Visibility,
+};
+
+/// One node of the rendered tree. `kind` is the AST variant name,
+/// `detail` carries the variant's scalar payload (operator, name,
+/// literal value), and `ty` is the resolved type when the dumper was
+/// given type-check results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpNode {
+    pub kind: String,
+    pub detail: Option<String>,
+    pub ty: Option<String>,
+    pub children: Vec<DumpNode>,
+}
+
+impl DumpNode {
+    fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            detail: None,
+            ty: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn with_detail(kind: &str, detail: impl Into<String>) -> Self {
+        let mut node = Self::new(kind);
+        node.detail = Some(detail.into());
+        node
+    }
+
+    fn child(mut self, node: DumpNode) -> Self {
+        self.children.push(node);
+        self
+    }
+
+    /// Wrap `children` under a synthetic grouping node (e.g. `Args`),
+    /// skipping it entirely when empty to keep the tree compact.
+    fn group(mut self, kind: &str, children: Vec<DumpNode>) -> Self {
+        if !children.is_empty() {
+            let mut group = DumpNode::new(kind);
+            group.children = children;
+            self.children.push(group);
+        }
+        self
+    }
+}
+
+pub struct AstDumper<'a> {
+    program: &'a Program,
+    interner: &'a DefaultStringInterner,
+    expr_types: Option<&'a HashMap<ExprRef, TypeDecl>>,
+    stmt_limit: Option<usize>,
+}
+
+impl<'a> AstDumper<'a> {
+    pub fn new(program: &'a Program, interner: &'a DefaultStringInterner) -> Self {
+        Self {
+            program,
+            interner,
+            expr_types: None,
+            stmt_limit: None,
+        }
+    }
+
+    /// Only scan the first `limit` statements for top-level
+    /// declarations. Module integration appends imported declarations
+    /// to the pool, so passing the pre-integration length restricts the
+    /// dump to the user's own file.
+    pub fn with_stmt_limit(mut self, limit: usize) -> Self {
+        self.stmt_limit = Some(limit);
+        self
+    }
+
+    /// Annotate every expression node with its entry in `expr_types`
+    /// (typically `TypeCheckerVisitor::get_expr_types()`).
+    pub fn with_expr_types(mut self, expr_types: &'a HashMap<ExprRef, TypeDecl>) -> Self {
+        self.expr_types = Some(expr_types);
+        self
+    }
+
+    /// Build the tree for the whole program.
+    pub fn build(&self) -> DumpNode {
+        let mut root = DumpNode::new("Program");
+        if let Some(package) = &self.program.package_decl {
+            root = root.child(DumpNode::with_detail("Package", self.path(&package.name)));
+        }
+        for import in &self.program.imports {
+            let mut node = DumpNode::with_detail("Import", self.path(&import.module_path));
+            if let Some(alias) = import.alias {
+                node = node.child(DumpNode::with_detail("Alias", self.sym(alias)));
+            }
+            root = root.child(node);
+        }
+        for decl in &self.program.consts {
+            let node = DumpNode::with_detail(
+                "Const",
+                format!("{}: {}", self.sym(decl.name), self.ty(&decl.type_decl)),
+            );
+            root = root.child(node.child(self.expr(&decl.value)));
+        }
+        let stmt_count = self
+            .stmt_limit
+            .map_or(self.program.statement.len(), |limit| limit.min(self.program.statement.len()));
+        for i in 0..stmt_count {
+            let stmt_ref = StmtRef(i as u32);
+            if let Some(stmt) = self.program.statement.get(&stmt_ref)
+                && Self::is_top_level_decl(&stmt)
+            {
+                root = root.child(self.stmt(&stmt_ref));
+            }
+        }
+        for function in &self.program.function {
+            root = root.child(self.function(function));
+        }
+        root
+    }
+
+    /// Top-level declarations live in the shared `StmtPool` next to
+    /// function-body statements; only these variants are ever emitted
+    /// at module scope.
+    fn is_top_level_decl(stmt: &Stmt) -> bool {
+        matches!(
+            stmt,
+            Stmt::StructDecl { .. }
+                | Stmt::ImplBlock { .. }
+                | Stmt::TraitDecl { .. }
+                | Stmt::EnumDecl { .. }
+                | Stmt::TypeAlias { .. }
+        )
+    }
+
+    fn function(&self, function: &Function) -> DumpNode {
+        let kind = if function.is_extern { "ExternFunction" } else { "Function" };
+        let detail = self.signature(
+            function.name,
+            &function.generic_params,
+            &function.parameter,
+            function.return_type.as_ref(),
+            &function.visibility,
+        );
+        let node = DumpNode::with_detail(kind, detail)
+            .group("Requires", function.requires.iter().map(|e| self.expr(e)).collect())
+            .group("Ensures", function.ensures.iter().map(|e| self.expr(e)).collect());
+        if function.is_extern {
+            node
+        } else {
+            node.child(self.stmt(&function.code))
+        }
+    }
+
+    fn method(&self, method: &MethodFunction) -> DumpNode {
+        let mut detail = self.signature(
+            method.name,
+            &method.generic_params,
+            &method.parameter,
+            method.return_type.as_ref(),
+            &method.visibility,
+        );
+        if method.has_self_param {
+            let receiver = if method.self_is_mut { "&mut self" } else { "&self" };
+            detail = format!("{detail} [{receiver}]");
+        }
+        DumpNode::with_detail("Method", detail)
+            .group("Requires", method.requires.iter().map(|e| self.expr(e)).collect())
+            .group("Ensures", method.ensures.iter().map(|e| self.expr(e)).collect())
+            .child(self.stmt(&method.code))
+    }
+
+    fn trait_method(&self, method: &TraitMethodSignature) -> DumpNode {
+        let detail = self.signature(
+            method.name,
+            &method.generic_params,
+            &method.parameter,
+            method.return_type.as_ref(),
+            &Visibility::Public,
+        );
+        DumpNode::with_detail("MethodSignature", detail)
+    }
+
+    fn signature(
+        &self,
+        name: DefaultSymbol,
+        generic_params: &[DefaultSymbol],
+        params: &[(DefaultSymbol, TypeDecl)],
+        return_type: Option<&TypeDecl>,
+        visibility: &Visibility,
+    ) -> String {
+        let mut out = String::new();
+        if *visibility == Visibility::Public {
+            out.push_str("pub ");
+        }
+        out.push_str(self.sym(name));
+        out.push_str(&self.generics(generic_params));
+        let params: Vec<String> = params
+            .iter()
+            .map(|(n, t)| format!("{}: {}", self.sym(*n), self.ty(t)))
+            .collect();
+        let _ = write!(out, "({})", params.join(", "));
+        if let Some(ret) = return_type {
+            let _ = write!(out, " -> {}", self.ty(ret));
+        }
+        out
+    }
+
+    fn generics(&self, params: &[DefaultSymbol]) -> String {
+        if params.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<&str> = params.iter().map(|p| self.sym(*p)).collect();
+            format!("<{}>", names.join(", "))
+        }
+    }
+
+    pub fn stmt(&self, stmt_ref: &StmtRef) -> DumpNode {
+        let Some(stmt) = self.program.statement.get(stmt_ref) else {
+            return DumpNode::with_detail("Invalid", format!("<invalid stmt {}>", stmt_ref.0));
+        };
+        match stmt {
+            Stmt::Expression(e) => self.expr(&e),
+            Stmt::Val(name, ty, value) => {
+                DumpNode::with_detail("Val", self.binding(name, ty.as_ref())).child(self.expr(&value))
+            }
+            Stmt::Var(name, ty, value) => {
+                let node = DumpNode::with_detail("Var", self.binding(name, ty.as_ref()));
+                match value {
+                    Some(v) => node.child(self.expr(&v)),
+                    None => node,
+                }
+            }
+            Stmt::Return(value) => {
+                let node = DumpNode::new("Return");
+                match value {
+                    Some(v) => node.child(self.expr(&v)),
+                    None => node,
+                }
+            }
+            Stmt::Yield(value) => DumpNode::new("Yield").child(self.expr(&value)),
+            Stmt::Break(label, value) => {
+                let node = self.labelled("Break", label);
+                match value {
+                    Some(v) => node.child(self.expr(&v)),
+                    None => node,
+                }
+            }
+            Stmt::Continue(label) => self.labelled("Continue", label),
+            Stmt::For(label, var, start, end, body) => {
+                let mut detail = self.sym(var).to_string();
+                if let Some(label) = label {
+                    detail = format!("@{}: {}", self.sym(label), detail);
+                }
+                DumpNode::with_detail("For", detail)
+                    .child(self.expr(&start))
+                    .child(self.expr(&end))
+                    .child(self.expr(&body))
+            }
+            Stmt::While(label, cond, body) => self
+                .labelled("While", label)
+                .child(self.expr(&cond))
+                .child(self.expr(&body)),
+            Stmt::StructDecl { name, generic_params, fields, visibility, .. } => {
+                let mut detail = format!("{}{}", self.sym(name), self.generics(&generic_params));
+                if visibility == Visibility::Public {
+                    detail = format!("pub {detail}");
+                }
+                let fields = fields
+                    .iter()
+                    .map(|f| DumpNode::with_detail("Field", format!("{}: {}", f.name, self.ty(&f.type_decl))))
+                    .collect();
+                DumpNode::with_detail("StructDecl", detail).group("Fields", fields)
+            }
+            Stmt::ImplBlock { target_type, target_type_args, methods, trait_name, trait_type_args } => {
+                let target = self.ty(&TypeDecl::Struct(target_type, target_type_args));
+                let detail = match trait_name {
+                    Some(t) => format!("{} for {}", self.ty(&TypeDecl::Struct(t, trait_type_args)), target),
+                    None => target,
+                };
+                let mut node = DumpNode::with_detail("ImplBlock", detail);
+                for method in &methods {
+                    node = node.child(self.method(method));
+                }
+                node
+            }
+            Stmt::TraitDecl { name, generic_params, methods, visibility } => {
+                let mut detail = format!("{}{}", self.sym(name), self.generics(&generic_params));
+                if visibility == Visibility::Public {
+                    detail = format!("pub {detail}");
+                }
+                let mut node = DumpNode::with_detail("TraitDecl", detail);
+                for method in &methods {
+                    node = node.child(self.trait_method(method));
+                }
+                node
+            }
+            Stmt::EnumDecl { name, generic_params, variants, visibility } => {
+                let mut detail = format!("{}{}", self.sym(name), self.generics(&generic_params));
+                if visibility == Visibility::Public {
+                    detail = format!("pub {detail}");
+                }
+                let mut node = DumpNode::with_detail("EnumDecl", detail);
+                for variant in &variants {
+                    let mut v = self.sym(variant.name).to_string();
+                    if !variant.payload_types.is_empty() {
+                        let payload: Vec<String> = variant.payload_types.iter().map(|t| self.ty(t)).collect();
+                        let _ = write!(v, "({})", payload.join(", "));
+                    }
+                    node = node.child(DumpNode::with_detail("Variant", v));
+                }
+                node
+            }
+            Stmt::TypeAlias { name, generic_params, target, visibility } => {
+                let mut detail = format!(
+                    "{}{} = {}",
+                    self.sym(name),
+                    self.generics(&generic_params),
+                    self.ty(&target)
+                );
+                if visibility == Visibility::Public {
+                    detail = format!("pub {detail}");
+                }
+                DumpNode::with_detail("TypeAlias", detail)
+            }
+        }
+    }
+
+    pub fn expr(&self, expr_ref: &ExprRef) -> DumpNode {
+        let Some(expr) = self.program.expression.get(expr_ref) else {
+            return DumpNode::with_detail("Invalid", format!("<invalid expr {}>", expr_ref.0));
+        };
+        let mut node = self.expr_node(expr);
+        if let Some(types) = self.expr_types
+            && let Some(ty) = types.get(expr_ref)
+        {
+            node.ty = Some(self.ty(ty));
+        }
+        node
+    }
+
+    fn exprs(&self, refs: &[ExprRef]) -> Vec<DumpNode> {
+        refs.iter().map(|e| self.expr(e)).collect()
+    }
+
+    fn expr_node(&self, expr: Expr) -> DumpNode {
+        match expr {
+            Expr::Assign(lhs, rhs) => DumpNode::new("Assign").child(self.expr(&lhs)).child(self.expr(&rhs)),
+            Expr::IfElifElse(cond, then, elifs, otherwise) => {
+                let mut node = DumpNode::new("If").child(self.expr(&cond)).child(self.expr(&then));
+                for (elif_cond, elif_body) in &elifs {
+                    node = node.child(
+                        DumpNode::new("Elif")
+                            .child(self.expr(elif_cond))
+                            .child(self.expr(elif_body)),
+                    );
+                }
+                node.child(DumpNode::new("Else").child(self.expr(&otherwise)))
+            }
+            Expr::Binary(op, lhs, rhs) => DumpNode::with_detail("Binary", format!("{op:?}"))
+                .child(self.expr(&lhs))
+                .child(self.expr(&rhs)),
+            Expr::Unary(op, operand) => {
+                DumpNode::with_detail("Unary", format!("{op:?}")).child(self.expr(&operand))
+            }
+            Expr::Block(stmts) => {
+                let mut node = DumpNode::new("Block");
+                for s in &stmts {
+                    node = node.child(self.stmt(s));
+                }
+                node
+            }
+            Expr::True => DumpNode::with_detail("Bool", "true"),
+            Expr::False => DumpNode::with_detail("Bool", "false"),
+            Expr::Int64(v) => DumpNode::with_detail("Int64", v.to_string()),
+            Expr::UInt64(v) => DumpNode::with_detail("UInt64", v.to_string()),
+            Expr::Int8(v) => DumpNode::with_detail("Int8", v.to_string()),
+            Expr::Int16(v) => DumpNode::with_detail("Int16", v.to_string()),
+            Expr::Int32(v) => DumpNode::with_detail("Int32", v.to_string()),
+            Expr::UInt8(v) => DumpNode::with_detail("UInt8", v.to_string()),
+            Expr::UInt16(v) => DumpNode::with_detail("UInt16", v.to_string()),
+            Expr::UInt32(v) => DumpNode::with_detail("UInt32", v.to_string()),
+            Expr::Float64(v) => DumpNode::with_detail("Float64", format!("{v:?}")),
+            Expr::Number(s) => DumpNode::with_detail("Number", self.sym(s)),
+            Expr::Identifier(s) => DumpNode::with_detail("Identifier", self.sym(s)),
+            Expr::Null => DumpNode::new("Null"),
+            Expr::ExprList(items) => DumpNode::new("ExprList").group("Items", self.exprs(&items)),
+            Expr::Call(name, args, _) => DumpNode::with_detail("Call", self.sym(name)).child(self.expr(&args)),
+            Expr::String(s) => DumpNode::with_detail("String", format!("{:?}", self.sym(s))),
+            Expr::Bytes(s) => DumpNode::with_detail("Bytes", format!("{:?}", self.sym(s))),
+            Expr::ArrayLiteral(items) => DumpNode::new("ArrayLiteral").group("Items", self.exprs(&items)),
+            Expr::FieldAccess(obj, field) => {
+                DumpNode::with_detail("FieldAccess", self.sym(field)).child(self.expr(&obj))
+            }
+            Expr::MethodCall(obj, method, args) => DumpNode::with_detail("MethodCall", self.sym(method))
+                .child(self.expr(&obj))
+                .group("Args", self.exprs(&args)),
+            Expr::StructLiteral(name, fields, _) => {
+                let mut node = DumpNode::with_detail("StructLiteral", self.sym(name));
+                for (field, value) in &fields {
+                    node = node.child(DumpNode::with_detail("Field", self.sym(*field)).child(self.expr(value)));
+                }
+                node
+            }
+            Expr::QualifiedIdentifier(path) => DumpNode::with_detail("QualifiedIdentifier", self.path(&path)),
+            Expr::BuiltinMethodCall(obj, method, args) => {
+                DumpNode::with_detail("BuiltinMethodCall", format!("{method:?}"))
+                    .child(self.expr(&obj))
+                    .group("Args", self.exprs(&args))
+            }
+            Expr::BuiltinCall(func, args) => {
+                DumpNode::with_detail("BuiltinCall", format!("{func:?}")).group("Args", self.exprs(&args))
+            }
+            Expr::SliceAccess(obj, info) => {
+                let kind = match info.slice_type {
+                    SliceType::SingleElement => "Index",
+                    SliceType::RangeSlice => "Slice",
+                };
+                let mut node = DumpNode::new(kind).child(self.expr(&obj));
+                if let Some(start) = &info.start {
+                    node = node.child(DumpNode::new("Start").child(self.expr(start)));
+                }
+                if let Some(end) = &info.end {
+                    node = node.child(DumpNode::new("End").child(self.expr(end)));
+                }
+                node
+            }
+            Expr::SliceAssign(obj, info, value) => {
+                let mut node = DumpNode::new("SliceAssign").child(self.expr(&obj));
+                if let Some(start) = &info.start {
+                    node = node.child(DumpNode::new("Start").child(self.expr(start)));
+                }
+                if let Some(end) = &info.end {
+                    node = node.child(DumpNode::new("End").child(self.expr(end)));
+                }
+                node.child(self.expr(&value))
+            }
+            Expr::AssociatedFunctionCall(ty, func, args) => {
+                DumpNode::with_detail("AssociatedFunctionCall", format!("{}::{}", self.sym(ty), self.sym(func)))
+                    .group("Args", self.exprs(&args))
+            }
+            Expr::DictLiteral(entries) => {
+                let mut node = DumpNode::new("DictLiteral");
+                for (k, v) in &entries {
+                    node = node.child(DumpNode::new("Entry").child(self.expr(k)).child(self.expr(v)));
+                }
+                node
+            }
+            Expr::TupleLiteral(items) => DumpNode::new("TupleLiteral").group("Items", self.exprs(&items)),
+            Expr::TupleAccess(obj, index) => {
+                DumpNode::with_detail("TupleAccess", index.to_string()).child(self.expr(&obj))
+            }
+            Expr::Cast(value, ty) => DumpNode::with_detail("Cast", self.ty(&ty)).child(self.expr(&value)),
+            Expr::With(allocator, body) => DumpNode::new("With").child(self.expr(&allocator)).child(self.expr(&body)),
+            Expr::WithResource { name, resource, body } => DumpNode::with_detail("WithResource", self.sym(name))
+                .child(self.expr(&resource))
+                .child(self.expr(&body)),
+            Expr::TryCatch { body, error, handler } => DumpNode::with_detail("TryCatch", self.sym(error))
+                .child(self.expr(&body))
+                .child(self.expr(&handler)),
+            Expr::Match(scrutinee, arms) => {
+                let mut node = DumpNode::new("Match").child(self.expr(&scrutinee));
+                for arm in &arms {
+                    node = node.child(self.arm(arm));
+                }
+                node
+            }
+            Expr::Range(start, end) => DumpNode::new("Range").child(self.expr(&start)).child(self.expr(&end)),
+            Expr::Comprehension { element, value, var, source, filter } => {
+                let mut node = DumpNode::with_detail("Comprehension", self.sym(var)).child(self.expr(&element));
+                if let Some(value) = &value {
+                    node = node.child(DumpNode::new("Value").child(self.expr(value)));
+                }
+                node = node.child(DumpNode::new("Source").child(self.expr(&source)));
+                if let Some(filter) = &filter {
+                    node = node.child(DumpNode::new("Filter").child(self.expr(filter)));
+                }
+                node
+            }
+            Expr::Closure { params, return_type, body } => {
+                let params: Vec<String> = params
+                    .iter()
+                    .map(|(n, t)| format!("{}: {}", self.sym(*n), self.ty(t)))
+                    .collect();
+                let mut detail = format!("({})", params.join(", "));
+                if let Some(ret) = &return_type {
+                    let _ = write!(detail, " -> {}", self.ty(ret));
+                }
+                DumpNode::with_detail("Closure", detail).child(self.expr(&body))
+            }
+        }
+    }
+
+    fn arm(&self, arm: &MatchArm) -> DumpNode {
+        let mut node = DumpNode::with_detail("Arm", self.pattern(&arm.pattern));
+        if let Some(guard) = &arm.guard {
+            node = node.child(DumpNode::new("Guard").child(self.expr(guard)));
+        }
+        node.child(self.expr(&arm.body))
+    }
+
+    fn pattern(&self, pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::EnumVariant(enum_name, variant, subs) => {
+                let mut out = format!("{}::{}", self.sym(*enum_name), self.sym(*variant));
+                if !subs.is_empty() {
+                    let subs: Vec<String> = subs.iter().map(|p| self.pattern(p)).collect();
+                    let _ = write!(out, "({})", subs.join(", "));
+                }
+                out
+            }
+            Pattern::Literal(e) => match self.program.expression.get(e) {
+                Some(lit) => self.expr_node(lit).detail.unwrap_or_else(|| "<literal>".to_string()),
+                None => "<literal>".to_string(),
+            },
+            Pattern::Name(name) => self.sym(*name).to_string(),
+            Pattern::Tuple(subs) => {
+                let subs: Vec<String> = subs.iter().map(|p| self.pattern(p)).collect();
+                format!("({})", subs.join(", "))
+            }
+            Pattern::Wildcard => "_".to_string(),
+        }
+    }
+
+    fn binding(&self, name: DefaultSymbol, ty: Option<&TypeDecl>) -> String {
+        // The parser records a missing annotation as `Unknown`.
+        match ty {
+            Some(ty) if *ty != TypeDecl::Unknown => format!("{}: {}", self.sym(name), self.ty(ty)),
+            _ => self.sym(name).to_string(),
+        }
+    }
+
+    fn labelled(&self, kind: &str, label: Option<DefaultSymbol>) -> DumpNode {
+        match label {
+            Some(label) => DumpNode::with_detail(kind, format!("@{}", self.sym(label))),
+            None => DumpNode::new(kind),
+        }
+    }
+
+    fn sym(&self, symbol: DefaultSymbol) -> &str {
+        self.interner.resolve(symbol).unwrap_or("<unknown>")
+    }
+
+    fn path(&self, segments: &[DefaultSymbol]) -> String {
+        let names: Vec<&str> = segments.iter().map(|s| self.sym(*s)).collect();
+        names.join("::")
+    }
+
+    fn ty(&self, ty: &TypeDecl) -> String {
+        type_to_string(ty, self.interner)
+    }
+}
+
+/// Render a `TypeDecl` in source syntax (`i64`, `[u8; 4]`, `Option<T>`,
+/// `fn (i64) -> bool`, ...). Inference placeholders print as `Number`
+/// / `Unknown` so unresolved literals remain visible in dumps.
+pub fn type_to_string(ty: &TypeDecl, interner: &DefaultStringInterner) -> String {
+    let sym = |s: &DefaultSymbol| interner.resolve(*s).unwrap_or("<unknown>").to_string();
+    let list = |tys: &[TypeDecl]| {
+        tys.iter()
+            .map(|t| type_to_string(t, interner))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match ty {
+        TypeDecl::Unknown => "Unknown".to_string(),
+        TypeDecl::Unit => "()".to_string(),
+        TypeDecl::Int64 => "i64".to_string(),
+        TypeDecl::UInt64 => "u64".to_string(),
+        TypeDecl::Float64 => "f64".to_string(),
+        TypeDecl::Bool => "bool".to_string(),
+        TypeDecl::Int8 => "i8".to_string(),
+        TypeDecl::Int16 => "i16".to_string(),
+        TypeDecl::Int32 => "i32".to_string(),
+        TypeDecl::UInt8 => "u8".to_string(),
+        TypeDecl::UInt16 => "u16".to_string(),
+        TypeDecl::UInt32 => "u32".to_string(),
+        TypeDecl::String => "str".to_string(),
+        TypeDecl::Bytes => "bytes".to_string(),
+        TypeDecl::Number => "Number".to_string(),
+        TypeDecl::Ptr => "ptr".to_string(),
+        TypeDecl::Self_ => "Self".to_string(),
+        TypeDecl::Allocator => "Allocator".to_string(),
+        TypeDecl::Identifier(s) | TypeDecl::Generic(s) => sym(s),
+        TypeDecl::Array(elems, size) => match elems.as_slice() {
+            [elem] => format!("[{}; {}]", type_to_string(elem, interner), size),
+            _ => format!("[{}; {}]", list(elems), size),
+        },
+        TypeDecl::Struct(name, args) | TypeDecl::Enum(name, args) => {
+            if args.is_empty() {
+                sym(name)
+            } else {
+                format!("{}<{}>", sym(name), list(args))
+            }
+        }
+        TypeDecl::Dict(k, v) => format!(
+            "dict[{}, {}]",
+            type_to_string(k, interner),
+            type_to_string(v, interner)
+        ),
+        TypeDecl::Tuple(elems) => format!("({})", list(elems)),
+        TypeDecl::Range(elem) => format!("Range<{}>", type_to_string(elem, interner)),
+        TypeDecl::Generator(elem) => format!("Generator<{}>", type_to_string(elem, interner)),
+        TypeDecl::Ref { is_mut, inner } => format!(
+            "&{}{}",
+            if *is_mut { "mut " } else { "" },
+            type_to_string(inner, interner)
+        ),
+        TypeDecl::Function(params, ret) => {
+            format!("fn ({}) -> {}", list(params), type_to_string(ret, interner))
+        }
+    }
+}
+
+/// Indented tree, two spaces per level:
+///
+/// ```text
+/// Function main() -> u64
+///   Block
+///     Binary IAdd
+/// ```
+pub fn to_pretty(node: &DumpNode) -> String {
+    fn go(node: &DumpNode, depth: usize, out: &mut String) {
+        let _ = write!(out, "{:width$}{}", "", node.kind, width = depth * 2);
+        if let Some(detail) = &node.detail {
+            let _ = write!(out, " {detail}");
+        }
+        if let Some(ty) = &node.ty {
+            let _ = write!(out, " : {ty}");
+        }
+        out.push('\n');
+        for child in &node.children {
+            go(child, depth + 1, out);
+        }
+    }
+    let mut out = String::new();
+    go(node, 0, &mut out);
+    out
+}
+
+/// JSON rendering. Each node is `{"kind": ..., "detail": ..., "type": ...,
+/// "children": [...]}`; `detail` / `type` are omitted when absent.
+pub fn to_json(node: &DumpNode) -> String {
+    fn go(node: &DumpNode, depth: usize, out: &mut String) {
+        let pad = "  ".repeat(depth);
+        let _ = write!(out, "{pad}{{\"kind\": {}", json_string(&node.kind));
+        if let Some(detail) = &node.detail {
+            let _ = write!(out, ", \"detail\": {}", json_string(detail));
+        }
+        if let Some(ty) = &node.ty {
+            let _ = write!(out, ", \"type\": {}", json_string(ty));
+        }
+        if node.children.is_empty() {
+            out.push_str(", \"children\": []}");
+            return;
+        }
+        out.push_str(", \"children\": [\n");
+        for (i, child) in node.children.iter().enumerate() {
+            go(child, depth + 1, out);
+            if i + 1 < node.children.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        let _ = write!(out, "{pad}]}}");
+    }
+    let mut out = String::new();
+    go(node, 0, &mut out);
+    out.push('\n');
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Graphviz rendering (`dot -Tsvg ast.dot -o ast.svg`). Nodes are
+/// numbered in pre-order; labels show kind, detail, and type on
+/// separate lines.
+pub fn to_dot(node: &DumpNode) -> String {
+    fn go(node: &DumpNode, next_id: &mut usize, out: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let mut label = node.kind.clone();
+        if let Some(detail) = &node.detail {
+            label.push('\n');
+            label.push_str(detail);
+        }
+        if let Some(ty) = &node.ty {
+            label.push_str("\n: ");
+            label.push_str(ty);
+        }
+        let _ = writeln!(out, "  n{id} [label={}];", dot_string(&label));
+        for child in &node.children {
+            let child_id = go(child, next_id, out);
+            let _ = writeln!(out, "  n{id} -> n{child_id};");
+        }
+        id
+    }
+    let mut out = String::from("digraph ast {\n  node [shape=box, fontname=\"monospace\"];\n");
+    let mut next_id = 0;
+    go(node, &mut next_id, &mut out);
+    out.push_str("}\n");
+    out
+}
+
+fn dot_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
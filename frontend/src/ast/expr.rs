@@ -50,9 +50,14 @@ pub enum Stmt {
     Val(DefaultSymbol, Option<TypeDecl>, ExprRef),
     Var(DefaultSymbol, Option<TypeDecl>, Option<ExprRef>),
     Return(Option<ExprRef>),
+    /// `yield value` inside a function returning `Generator<T>`: hands
+    /// `value` to the consumer's `next()` and suspends the body there.
+    Yield(ExprRef),
     /// Optional `Some(label_sym)` for `break @label` (LABEL feature),
     /// `None` for plain `break` which targets the innermost loop.
-    Break(Option<DefaultSymbol>),
+    /// The second field is the value of `break value`, which becomes
+    /// the result of the targeted `loop { ... }` expression.
+    Break(Option<DefaultSymbol>, Option<ExprRef>),
     /// Optional `Some(label_sym)` for `continue @label`.
     Continue(Option<DefaultSymbol>),
     /// Optional leading label for `@label: for ...` (LABEL feature).
@@ -198,17 +203,23 @@ pub enum Expr {
     Identifier(DefaultSymbol),
     Null,
     ExprList(Vec<ExprRef>),
-    Call(DefaultSymbol, ExprRef), // apply, function call, etc
+    /// `f(args)`; the `Vec` holds explicit type arguments
+    /// (`f<u64>(args)`) and is empty when none were written.
+    Call(DefaultSymbol, ExprRef, Vec<TypeDecl>),
     String(DefaultSymbol),
+    // `b"..."` byte-string literal. The interned text holds one char
+    // per byte, each in U+0000..=U+00FF (Latin-1), so `\xff` survives
+    // interning; decode with `c as u8`.
+    Bytes(DefaultSymbol),
     ArrayLiteral(Vec<ExprRef>),  // [1, 2, 3, 4, 5]
     FieldAccess(ExprRef, DefaultSymbol),  // obj.field
     MethodCall(ExprRef, DefaultSymbol, Vec<ExprRef>),  // obj.method(args)
-    StructLiteral(DefaultSymbol, Vec<(DefaultSymbol, ExprRef)>),  // Point { x: 10, y: 20 }
+    StructLiteral(DefaultSymbol, Vec<(DefaultSymbol, ExprRef)>, Vec<TypeDecl>),  // Point { x: 10, y: 20 } / Box<u64> { v: 1 }
     QualifiedIdentifier(Vec<DefaultSymbol>),  // math::add
     BuiltinMethodCall(ExprRef, BuiltinMethod, Vec<ExprRef>),  // "hello".len(), str.concat("world")
     BuiltinCall(BuiltinFunction, Vec<ExprRef>),  // __builtin_heap_alloc(), __builtin_print_ln(), etc.
     SliceAccess(ExprRef, SliceInfo),  // arr[start..end] - slice access, arr[i] as single element access
-    SliceAssign(ExprRef, Option<ExprRef>, Option<ExprRef>, ExprRef),  // arr[start..end] = value, arr[i] = value
+    SliceAssign(ExprRef, SliceInfo, ExprRef),  // arr[start..end] = value, arr[i] = value
     AssociatedFunctionCall(DefaultSymbol, DefaultSymbol, Vec<ExprRef>),  // Container::new(args) - struct_name, function_name, args
     DictLiteral(Vec<(ExprRef, ExprRef)>),  // {key1: value1, key2: value2}
     TupleLiteral(Vec<ExprRef>),  // (expr1, expr2, ...) - tuple literal
@@ -227,6 +238,34 @@ pub enum Expr {
         return_type: Option<TypeDecl>,
         body: ExprRef,
     },
+    /// `[element for var in source if filter]`, or the dict form
+    /// `dict{element: value for var in source if filter}` when
+    /// `value` is `Some`. `source` is a range (`a..b`) or an array;
+    /// `var` is bound to each item in turn while `filter`, `element`
+    /// and `value` are evaluated.
+    Comprehension {
+        element: ExprRef,
+        value: Option<ExprRef>,
+        var: DefaultSymbol,
+        source: ExprRef,
+        filter: Option<ExprRef>,
+    },
+    /// `with name = resource { body }`: `name` is bound to the resource
+    /// for the body, and the resource's `__close__` method runs when the
+    /// body exits, however it exits. `with allocator = ...` is `With`.
+    WithResource {
+        name: DefaultSymbol,
+        resource: ExprRef,
+        body: ExprRef,
+    },
+    /// `try { body } catch error { handler }`: when the body fails at
+    /// runtime, `handler` runs with `error` bound to the failure's
+    /// message (`str`) and its value replaces the body's.
+    TryCatch {
+        body: ExprRef,
+        error: DefaultSymbol,
+        handler: ExprRef,
+    },
 }
 
 impl Expr {
@@ -291,6 +330,219 @@ pub enum BuiltinFunction {
     // everyday user-facing operations, not low-level intrinsics).
     Print,   // print(value) -> unit (no trailing newline)
     Println, // println(value) -> unit (trailing newline)
+    // Leveled logging: `log_debug(msg: str)` / `log_info` / `log_warn`
+    // / `log_error` write `[LEVEL] msg`. The host, not the script,
+    // picks the minimum level and the destination (the evaluation
+    // context's log config; `TOYLANG_LOG` / `TOYLANG_LOG_FILE` for the
+    // `interpreter` binary). Interpreter only — the JIT falls back and
+    // the AOT compiler rejects the call.
+    LogDebug,
+    LogInfo,
+    LogWarn,
+    LogError,
+
+    // Binary file I/O. Whole-file granularity: `read_bytes(path: str)
+    // -> bytes` returns the file's contents unchanged and
+    // `write_bytes(path: str, data: bytes) -> unit` creates or
+    // truncates the file. An I/O failure aborts the run like `panic`
+    // (with the OS error in the message). Interpreter only — the JIT
+    // falls back and the AOT compiler rejects the call.
+    ReadBytes,
+    WriteBytes,
+    // `__builtin_bytes_from_ptr(p: ptr, len: u64) -> bytes` copies
+    // `len` bytes starting at `p` into a fresh `bytes` value — the
+    // bridge from a heap-built `String` to `write_bytes`. Interpreter
+    // only, like the rest of `bytes`.
+    BytesFromPtr,
+
+    // Standard input. `read_line() -> str` returns the next line with
+    // its trailing newline (empty string only at end of input);
+    // `read_all() -> str` returns the rest of the input. Interpreter
+    // only — the JIT falls back and the AOT compiler rejects the call.
+    ReadLine,
+    ReadAll,
+
+    // Process spawning. `exec(cmd: str, args: [str; N]) -> (i64, str,
+    // str)` runs `cmd` directly (no shell) and returns its exit
+    // status, captured stdout and captured stderr. Disabled unless the
+    // embedder opts in (`--allow-exec` on the interpreter binary);
+    // calling it otherwise aborts the run like `panic`. Interpreter
+    // only — the JIT falls back and the AOT compiler rejects the call.
+    Exec,
+
+    // HTTP. `http_get(url: str) -> (u64, str)` sends one blocking
+    // `GET` over plain `http://` and returns the status code and the
+    // body. Needs the `net` capability (`--allow-net`) and an
+    // interpreter built with the `http` feature. Interpreter only —
+    // the JIT falls back and the AOT compiler rejects the call.
+    HttpGet,
+
+    // TCP sockets, for small servers. Sockets are `u64` handles:
+    // `tcp_listen(port: u64) -> u64` binds every interface (port 0
+    // picks a free one, `tcp_local_port(h) -> u64` tells which),
+    // `tcp_accept(listener: u64) -> u64` blocks for the next client,
+    // `tcp_read(conn: u64) -> str` returns what has arrived (empty
+    // once the peer closed), `tcp_write(conn: u64, data: str)` sends
+    // all of `data` and `tcp_close(h: u64)` releases either kind.
+    // Needs the `net` capability. `core/std/net.t` wraps them as
+    // `net::listen` / `accept` / `read` / `write` / `close`.
+    // Interpreter only — the JIT falls back and the AOT compiler
+    // rejects the call.
+    TcpListen,
+    TcpAccept,
+    TcpRead,
+    TcpWrite,
+    TcpClose,
+    TcpLocalPort,
+
+    // JSON. `json_parse(text: str)` decodes a JSON document into
+    // dicts (string keys), arrays, `i64` / `f64`, `str`, `bool` and
+    // null; the result is dynamically typed (`Unknown`) and may be
+    // indexed with `v["key"]` / `v[i]`. `json_stringify(value) -> str`
+    // encodes primitives, strings, arrays, tuples, dicts and structs.
+    // Malformed input or an unencodable value aborts the run like
+    // `panic`. Interpreter only — the JIT falls back and the AOT
+    // compiler rejects the call.
+    JsonParse,
+    JsonStringify,
+
+    // Runtime type inspection for dynamic (`any`) values.
+    // `type_of(x) -> str` names the runtime type (`"i64"`, `"str"`,
+    // `"array"`, a struct or enum name, ...). `is<T>(x) -> bool` tests
+    // it against `T`, and `downcast<T>(x) -> T` returns `x` typed as
+    // `T`, aborting the run like `panic` when it isn't one. The target
+    // type lives in the variant because the parser reads it from the
+    // call's `<T>`. Interpreter only — the JIT falls back and the AOT
+    // compiler rejects the call.
+    TypeOf,
+    Is(TypeDecl),
+    Downcast(TypeDecl),
+
+    // Struct <-> dict conversion for serialization.
+    // `to_dict(s) -> dict[str, any]` copies a struct's fields into a
+    // dict keyed by field name. `from_dict<T>(d) -> Option<T>` builds
+    // struct `T` back, returning `None` unless the dict has exactly
+    // T's fields with values of the declared types (integers convert
+    // between widths when in range; dict values convert into nested
+    // struct fields). Interpreter only.
+    ToDict,
+    FromDict(TypeDecl),
+
+    // `clone(x) -> T` deep-copies `x`: arrays, tuples, dicts, structs
+    // and enum payloads are copied all the way down, keeping shared
+    // and cyclic references shared / cyclic within the copy.
+    // Interpreter only.
+    Clone,
+
+    // Seedable pseudo-random numbers. `seed(n: u64) -> unit` resets
+    // the generator, `random() -> f64` draws from `[0, 1)` and
+    // `random_range(lo, hi) -> T` draws uniformly from `[lo, hi)`
+    // for matching `i64` / `u64` operands. The generator is a
+    // SplitMix64 stream owned by the runtime (seeded with 0 at
+    // start-up), not the host RNG, so a given seed reproduces the
+    // same sequence everywhere. Interpreter only — the JIT falls
+    // back and the AOT compiler rejects the call.
+    Seed,
+    Random,
+    RandomRange,
+
+    // Time. `now_millis() -> u64` is wall-clock milliseconds since
+    // the Unix epoch, `clock() -> f64` is seconds elapsed since the
+    // run started (monotonic, for benchmarking), and
+    // `sleep(ms: u64) -> unit` blocks the current run. Interpreter
+    // only — the JIT falls back and the AOT compiler rejects the call.
+    NowMillis,
+    Clock,
+    Sleep,
+    // `__builtin_format_timestamp(epoch: i64, fmt: str) -> str` is
+    // `strftime` in UTC over seconds since the Unix epoch — the native
+    // half of `core/std/time.t`, which wraps it as
+    // `time::format_timestamp`. Pure: it never reads the clock.
+    // Interpreter only, like the other `str`-producing builtins.
+    FormatTimestamp,
+
+    // Cooperative tasks. `spawn(task: Task)` queues a suspended task
+    // (the value a `fn f(..) -> Task` call returns) on the
+    // interpreter's run queue. `yield_now() -> bool` written as a
+    // statement of a task body hands control back to the scheduler;
+    // called anywhere else it resumes every other queued task once
+    // and reports whether any of them ran. Interpreter only — the JIT
+    // falls back and the AOT compiler rejects the call.
+    Spawn,
+    YieldNow,
+
+    // Integer <-> text. `parse_u64(s: str) -> Option<u64>` and
+    // `parse_i64(s: str) -> Option<i64>` read a decimal integer (an
+    // optional sign, then digits only) and return `None` for anything
+    // else, including out-of-range values. `to_hex(n: u64) -> str` is
+    // lowercase hex without a prefix, and `format_radix(n, base: u64)
+    // -> str` writes an `i64` / `u64` in base 2..=36 (lowercase
+    // digits, leading `-` for negatives; other bases panic).
+    // Interpreter only — the JIT falls back and the AOT compiler
+    // rejects the call.
+    ParseU64,
+    ParseI64,
+    ToHex,
+    FormatRadix,
+
+    // Report-style number formatting, the same in every locale.
+    // `format(v, width: u64, pad: char) -> str` right-aligns an
+    // `i64` / `u64` / `f64` / `str` in `width` characters, filling
+    // with `pad` (with a `'0'` pad the fill goes after a leading
+    // `-`). `format_fixed(x: f64, digits: u64) -> str` writes `x` with
+    // exactly `digits` decimals and `format_exp(x: f64, digits: u64)
+    // -> str` in scientific notation (`1.50e3`); both round half to
+    // even on the exact binary value. Interpreter only — the JIT
+    // falls back and the AOT compiler rejects the call.
+    Format,
+    FormatFixed,
+    FormatExp,
+
+    // `sort(arr, less: fn (T, T) -> bool) -> [T; N]` returns a stably
+    // sorted copy of `arr`; `less(a, b)` says whether `a` goes before
+    // `b`. The comparator must take two elements of the array's type.
+    // Interpreter only — the JIT falls back and the AOT compiler
+    // rejects the call.
+    Sort,
+
+    // Array algorithms, typed by the array's element type `T`.
+    // `binary_search(arr, x) -> Option<u64>` is the index of the first
+    // element equal to `x` and `lower_bound(arr, x) -> u64` the first
+    // index whose element is not less than `x`; both assume `arr` is
+    // sorted ascending. `reverse(arr)` returns a reversed copy, and
+    // `min_of` / `max_of` / `sum_of(arr) -> T` fold the elements
+    // (`min_of` / `max_of` panic on an empty array, integer sums wrap
+    // like `+`). Ordering and sums take integer or `f64` elements.
+    // Interpreter only — the JIT falls back and the AOT compiler
+    // rejects the call.
+    BinarySearch,
+    LowerBound,
+    Reverse,
+    MinOf,
+    MaxOf,
+    SumOf,
+
+    // Lua-style pattern matching on `str`, with the classes, sets,
+    // repetitions, anchors, captures, `%b` and `%f` of Lua's
+    // `string.find`. `matches(s, pattern) -> bool` says whether the
+    // pattern matches anywhere, `find(s, pattern) -> Option<str>` is
+    // the first match's first capture (or the whole match) like
+    // `string.match`, and `replace_pattern(s, pattern, repl) -> str`
+    // replaces every match like `string.gsub` (`%0`-`%9` in `repl`).
+    // Interpreter only — the JIT falls back and the AOT compiler
+    // rejects the call.
+    Matches,
+    Find,
+    ReplacePattern,
+
+    // Inline Lua escape hatch. `lua!("raw code")` is pasted verbatim
+    // by a Lua code generator; the result type is whatever the
+    // surrounding annotation asks for (`val n: u64 = lua!("...")`),
+    // `unit` otherwise. No backend in the tree emits Lua, so the type
+    // checker rejects every call as a backend-only construct unless
+    // the checked target is `"lua"`; guard uses with
+    // `@cfg(target = "lua")`.
+    InlineLua,
 
     // Abrupt termination. `panic(msg: str)` aborts the current run with
     // the supplied message; the type-checker pretends the call returns a
@@ -341,6 +593,93 @@ pub enum BuiltinFunction {
     // those onto the same machinery.
 }
 
+impl BuiltinFunction {
+    /// Whether the builtin only computes a value from its arguments
+    /// (see `crate::purity`). Raw memory, allocators, I/O, clocks, random
+    /// numbers, process spawning, task scheduling and inline Lua are not pure.
+    pub fn is_pure(&self) -> bool {
+        match self {
+            BuiltinFunction::PtrIsNull
+            | BuiltinFunction::PtrEq
+            | BuiltinFunction::NullPtr
+            | BuiltinFunction::StrLen
+            | BuiltinFunction::DefaultAllocator
+            | BuiltinFunction::JsonParse
+            | BuiltinFunction::JsonStringify
+            | BuiltinFunction::TypeOf
+            | BuiltinFunction::Is(_)
+            | BuiltinFunction::Downcast(_)
+            | BuiltinFunction::ToDict
+            | BuiltinFunction::FromDict(_)
+            | BuiltinFunction::Clone
+            | BuiltinFunction::Panic
+            | BuiltinFunction::Assert
+            | BuiltinFunction::SizeOf
+            | BuiltinFunction::ToString
+            | BuiltinFunction::ParseU64
+            | BuiltinFunction::ParseI64
+            | BuiltinFunction::ToHex
+            | BuiltinFunction::FormatRadix
+            | BuiltinFunction::Format
+            | BuiltinFunction::FormatFixed
+            | BuiltinFunction::FormatExp
+            | BuiltinFunction::BinarySearch
+            | BuiltinFunction::LowerBound
+            | BuiltinFunction::Reverse
+            | BuiltinFunction::MinOf
+            | BuiltinFunction::MaxOf
+            | BuiltinFunction::SumOf
+            | BuiltinFunction::Matches
+            | BuiltinFunction::Find
+            | BuiltinFunction::ReplacePattern
+            | BuiltinFunction::Abs
+            | BuiltinFunction::Min
+            | BuiltinFunction::FormatTimestamp
+            | BuiltinFunction::Max => true,
+            BuiltinFunction::HeapAlloc
+            | BuiltinFunction::HeapFree
+            | BuiltinFunction::HeapRealloc
+            | BuiltinFunction::PtrRead
+            | BuiltinFunction::PtrWrite
+            | BuiltinFunction::StrToPtr
+            | BuiltinFunction::MemCopy
+            | BuiltinFunction::MemMove
+            | BuiltinFunction::MemSet
+            | BuiltinFunction::CurrentAllocator
+            | BuiltinFunction::Print
+            | BuiltinFunction::Println
+            | BuiltinFunction::LogDebug
+            | BuiltinFunction::LogInfo
+            | BuiltinFunction::LogWarn
+            | BuiltinFunction::LogError
+            | BuiltinFunction::ReadBytes
+            | BuiltinFunction::WriteBytes
+            | BuiltinFunction::BytesFromPtr
+            | BuiltinFunction::ReadLine
+            | BuiltinFunction::ReadAll
+            | BuiltinFunction::Exec
+            | BuiltinFunction::HttpGet
+            | BuiltinFunction::TcpListen
+            | BuiltinFunction::TcpAccept
+            | BuiltinFunction::TcpRead
+            | BuiltinFunction::TcpWrite
+            | BuiltinFunction::TcpClose
+            | BuiltinFunction::TcpLocalPort
+            | BuiltinFunction::Seed
+            | BuiltinFunction::Random
+            | BuiltinFunction::RandomRange
+            | BuiltinFunction::NowMillis
+            | BuiltinFunction::Clock
+            | BuiltinFunction::Sleep
+            | BuiltinFunction::Spawn
+            | BuiltinFunction::YieldNow
+            // Calls a closure, which may have effects of its own.
+            | BuiltinFunction::Sort
+            | BuiltinFunction::InlineLua => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BuiltinFunctionSymbols {
     // Memory management
@@ -371,6 +710,85 @@ pub struct BuiltinFunctionSymbols {
     // Output
     pub print: DefaultSymbol,
     pub println: DefaultSymbol,
+    pub log_debug: DefaultSymbol,
+    pub log_info: DefaultSymbol,
+    pub log_warn: DefaultSymbol,
+    pub log_error: DefaultSymbol,
+
+    // Binary file I/O
+    pub read_bytes: DefaultSymbol,
+    pub write_bytes: DefaultSymbol,
+    pub bytes_from_ptr: DefaultSymbol,
+
+    // Standard input
+    pub read_line: DefaultSymbol,
+    pub read_all: DefaultSymbol,
+
+    // Process spawning
+    pub exec: DefaultSymbol,
+    pub http_get: DefaultSymbol,
+    pub tcp_listen: DefaultSymbol,
+    pub tcp_accept: DefaultSymbol,
+    pub tcp_read: DefaultSymbol,
+    pub tcp_write: DefaultSymbol,
+    pub tcp_close: DefaultSymbol,
+    pub tcp_local_port: DefaultSymbol,
+
+    // JSON
+    pub json_parse: DefaultSymbol,
+    pub json_stringify: DefaultSymbol,
+
+    // Runtime type inspection
+    pub type_of: DefaultSymbol,
+    pub is: DefaultSymbol,
+    pub downcast: DefaultSymbol,
+
+    // Struct <-> dict conversion
+    pub to_dict: DefaultSymbol,
+    pub from_dict: DefaultSymbol,
+
+    // Deep copy
+    pub clone: DefaultSymbol,
+
+    // Seedable PRNG
+    pub seed: DefaultSymbol,
+    pub random: DefaultSymbol,
+    pub random_range: DefaultSymbol,
+
+    // Time
+    pub now_millis: DefaultSymbol,
+    pub clock: DefaultSymbol,
+    pub sleep: DefaultSymbol,
+    pub format_timestamp: DefaultSymbol,
+
+    // Cooperative tasks
+    pub spawn: DefaultSymbol,
+    pub yield_now: DefaultSymbol,
+
+    // Integer <-> text
+    pub parse_u64: DefaultSymbol,
+    pub parse_i64: DefaultSymbol,
+    pub to_hex: DefaultSymbol,
+    pub format_radix: DefaultSymbol,
+
+    // Number formatting
+    pub format: DefaultSymbol,
+    pub format_fixed: DefaultSymbol,
+    pub format_exp: DefaultSymbol,
+
+    // Sorting and array algorithms
+    pub sort: DefaultSymbol,
+    pub binary_search: DefaultSymbol,
+    pub lower_bound: DefaultSymbol,
+    pub reverse: DefaultSymbol,
+    pub min_of: DefaultSymbol,
+    pub max_of: DefaultSymbol,
+    pub sum_of: DefaultSymbol,
+
+    // Pattern matching
+    pub matches: DefaultSymbol,
+    pub find: DefaultSymbol,
+    pub replace_pattern: DefaultSymbol,
 
     // Termination
     pub panic: DefaultSymbol,
@@ -405,6 +823,10 @@ pub struct BuiltinFunctionSymbols {
     // emitting an inequality check + formatted `panic` on failure.
     pub assert_eq: DefaultSymbol,
     pub assert_ne: DefaultSymbol,
+
+    // `lua!("raw code")` — the only `name!(..)` form. Plain `lua(..)`
+    // stays an ordinary call, so this is not in `symbol_to_builtin`.
+    pub lua: DefaultSymbol,
     // NOTE: f64 math symbol fields (`pow` / `sqrt` / `sin` / `cos` /
     // `tan` / `log` / `log2` / `exp` / `floor` / `ceil`) lived here
     // before Phase 4. They were the parser-side recogniser for the
@@ -437,6 +859,57 @@ impl BuiltinFunctionSymbols {
             // for low-level memory primitives.
             print: interner.get_or_intern("print"),
             println: interner.get_or_intern("println"),
+            log_debug: interner.get_or_intern("log_debug"),
+            log_info: interner.get_or_intern("log_info"),
+            log_warn: interner.get_or_intern("log_warn"),
+            log_error: interner.get_or_intern("log_error"),
+            read_bytes: interner.get_or_intern("read_bytes"),
+            write_bytes: interner.get_or_intern("write_bytes"),
+            bytes_from_ptr: interner.get_or_intern("__builtin_bytes_from_ptr"),
+            read_line: interner.get_or_intern("read_line"),
+            read_all: interner.get_or_intern("read_all"),
+            exec: interner.get_or_intern("exec"),
+            http_get: interner.get_or_intern("http_get"),
+            tcp_listen: interner.get_or_intern("tcp_listen"),
+            tcp_accept: interner.get_or_intern("tcp_accept"),
+            tcp_read: interner.get_or_intern("tcp_read"),
+            tcp_write: interner.get_or_intern("tcp_write"),
+            tcp_close: interner.get_or_intern("tcp_close"),
+            tcp_local_port: interner.get_or_intern("tcp_local_port"),
+            json_parse: interner.get_or_intern("json_parse"),
+            json_stringify: interner.get_or_intern("json_stringify"),
+            type_of: interner.get_or_intern("type_of"),
+            is: interner.get_or_intern("is"),
+            downcast: interner.get_or_intern("downcast"),
+            to_dict: interner.get_or_intern("to_dict"),
+            from_dict: interner.get_or_intern("from_dict"),
+            clone: interner.get_or_intern("clone"),
+            seed: interner.get_or_intern("seed"),
+            random: interner.get_or_intern("random"),
+            random_range: interner.get_or_intern("random_range"),
+            now_millis: interner.get_or_intern("now_millis"),
+            clock: interner.get_or_intern("clock"),
+            sleep: interner.get_or_intern("sleep"),
+            format_timestamp: interner.get_or_intern("__builtin_format_timestamp"),
+            spawn: interner.get_or_intern("spawn"),
+            yield_now: interner.get_or_intern("yield_now"),
+            parse_u64: interner.get_or_intern("parse_u64"),
+            parse_i64: interner.get_or_intern("parse_i64"),
+            to_hex: interner.get_or_intern("to_hex"),
+            format_radix: interner.get_or_intern("format_radix"),
+            format: interner.get_or_intern("format"),
+            format_fixed: interner.get_or_intern("format_fixed"),
+            format_exp: interner.get_or_intern("format_exp"),
+            sort: interner.get_or_intern("sort"),
+            binary_search: interner.get_or_intern("binary_search"),
+            lower_bound: interner.get_or_intern("lower_bound"),
+            reverse: interner.get_or_intern("reverse"),
+            min_of: interner.get_or_intern("min_of"),
+            max_of: interner.get_or_intern("max_of"),
+            sum_of: interner.get_or_intern("sum_of"),
+            matches: interner.get_or_intern("matches"),
+            find: interner.get_or_intern("find"),
+            replace_pattern: interner.get_or_intern("replace_pattern"),
             panic: interner.get_or_intern("panic"),
             assert: interner.get_or_intern("assert"),
             sizeof: interner.get_or_intern("__builtin_sizeof"),
@@ -457,6 +930,7 @@ impl BuiltinFunctionSymbols {
             dbg: interner.get_or_intern("__builtin_dbg"),
             assert_eq: interner.get_or_intern("assert_eq"),
             assert_ne: interner.get_or_intern("assert_ne"),
+            lua: interner.get_or_intern("lua"),
         }
     }
 
@@ -478,6 +952,54 @@ impl BuiltinFunctionSymbols {
         else if symbol == self.default_allocator { Some(BuiltinFunction::DefaultAllocator) }
         else if symbol == self.print { Some(BuiltinFunction::Print) }
         else if symbol == self.println { Some(BuiltinFunction::Println) }
+        else if symbol == self.log_debug { Some(BuiltinFunction::LogDebug) }
+        else if symbol == self.log_info { Some(BuiltinFunction::LogInfo) }
+        else if symbol == self.log_warn { Some(BuiltinFunction::LogWarn) }
+        else if symbol == self.log_error { Some(BuiltinFunction::LogError) }
+        else if symbol == self.read_bytes { Some(BuiltinFunction::ReadBytes) }
+        else if symbol == self.write_bytes { Some(BuiltinFunction::WriteBytes) }
+        else if symbol == self.bytes_from_ptr { Some(BuiltinFunction::BytesFromPtr) }
+        else if symbol == self.read_line { Some(BuiltinFunction::ReadLine) }
+        else if symbol == self.read_all { Some(BuiltinFunction::ReadAll) }
+        else if symbol == self.exec { Some(BuiltinFunction::Exec) }
+        else if symbol == self.http_get { Some(BuiltinFunction::HttpGet) }
+        else if symbol == self.tcp_listen { Some(BuiltinFunction::TcpListen) }
+        else if symbol == self.tcp_accept { Some(BuiltinFunction::TcpAccept) }
+        else if symbol == self.tcp_read { Some(BuiltinFunction::TcpRead) }
+        else if symbol == self.tcp_write { Some(BuiltinFunction::TcpWrite) }
+        else if symbol == self.tcp_close { Some(BuiltinFunction::TcpClose) }
+        else if symbol == self.tcp_local_port { Some(BuiltinFunction::TcpLocalPort) }
+        else if symbol == self.json_parse { Some(BuiltinFunction::JsonParse) }
+        else if symbol == self.json_stringify { Some(BuiltinFunction::JsonStringify) }
+        else if symbol == self.type_of { Some(BuiltinFunction::TypeOf) }
+        else if symbol == self.to_dict { Some(BuiltinFunction::ToDict) }
+        else if symbol == self.clone { Some(BuiltinFunction::Clone) }
+        else if symbol == self.seed { Some(BuiltinFunction::Seed) }
+        else if symbol == self.random { Some(BuiltinFunction::Random) }
+        else if symbol == self.random_range { Some(BuiltinFunction::RandomRange) }
+        else if symbol == self.now_millis { Some(BuiltinFunction::NowMillis) }
+        else if symbol == self.clock { Some(BuiltinFunction::Clock) }
+        else if symbol == self.sleep { Some(BuiltinFunction::Sleep) }
+        else if symbol == self.format_timestamp { Some(BuiltinFunction::FormatTimestamp) }
+        else if symbol == self.spawn { Some(BuiltinFunction::Spawn) }
+        else if symbol == self.yield_now { Some(BuiltinFunction::YieldNow) }
+        else if symbol == self.parse_u64 { Some(BuiltinFunction::ParseU64) }
+        else if symbol == self.parse_i64 { Some(BuiltinFunction::ParseI64) }
+        else if symbol == self.to_hex { Some(BuiltinFunction::ToHex) }
+        else if symbol == self.format_radix { Some(BuiltinFunction::FormatRadix) }
+        else if symbol == self.format { Some(BuiltinFunction::Format) }
+        else if symbol == self.format_fixed { Some(BuiltinFunction::FormatFixed) }
+        else if symbol == self.format_exp { Some(BuiltinFunction::FormatExp) }
+        else if symbol == self.sort { Some(BuiltinFunction::Sort) }
+        else if symbol == self.binary_search { Some(BuiltinFunction::BinarySearch) }
+        else if symbol == self.lower_bound { Some(BuiltinFunction::LowerBound) }
+        else if symbol == self.reverse { Some(BuiltinFunction::Reverse) }
+        else if symbol == self.min_of { Some(BuiltinFunction::MinOf) }
+        else if symbol == self.max_of { Some(BuiltinFunction::MaxOf) }
+        else if symbol == self.sum_of { Some(BuiltinFunction::SumOf) }
+        else if symbol == self.matches { Some(BuiltinFunction::Matches) }
+        else if symbol == self.find { Some(BuiltinFunction::Find) }
+        else if symbol == self.replace_pattern { Some(BuiltinFunction::ReplacePattern) }
         else if symbol == self.panic { Some(BuiltinFunction::Panic) }
         else if symbol == self.assert { Some(BuiltinFunction::Assert) }
         else if symbol == self.sizeof { Some(BuiltinFunction::SizeOf) }
@@ -504,6 +1026,10 @@ pub enum BuiltinMethod {
     StrToUpper,   // str.to_upper() -> str
     StrToLower,   // str.to_lower() -> str
 
+    // Container methods
+    ArrayLen,     // [T; N].len() -> u64
+    BytesLen,     // bytes.len() -> u64
+
     // NOTE: `I64Abs` / `F64Abs` / `F64Sqrt` lived here as hardcoded
     // numeric value-method dispatchers. Step E (extension-trait
     // migration) replaced them with regular `impl Abs for {i64,f64}`
@@ -513,6 +1039,65 @@ pub enum BuiltinMethod {
     // extension traits go through. Step F removed the variants.
 }
 
+impl BuiltinMethod {
+    /// Every builtin method that is called through `recv.name(..)`,
+    /// keyed by the receiver shape from [`BuiltinMethod::receiver_key`]
+    /// plus the method name. The type checker's `builtin_methods`
+    /// registry and the interpreter's method-call dispatch are both
+    /// built from this list, so a new builtin method is a row here,
+    /// its [`BuiltinMethod::signature`] and its evaluation arm.
+    /// `IsNull` is absent: it applies to every receiver and is
+    /// dispatched ahead of the registry.
+    pub fn registry_entries() -> Vec<(TypeDecl, &'static str, BuiltinMethod)> {
+        vec![
+            (TypeDecl::String, "len", BuiltinMethod::StrLen),
+            (TypeDecl::String, "concat", BuiltinMethod::StrConcat),
+            (TypeDecl::String, "substring", BuiltinMethod::StrSubstring),
+            (TypeDecl::String, "contains", BuiltinMethod::StrContains),
+            (TypeDecl::String, "split", BuiltinMethod::StrSplit),
+            (TypeDecl::String, "trim", BuiltinMethod::StrTrim),
+            (TypeDecl::String, "to_upper", BuiltinMethod::StrToUpper),
+            (TypeDecl::String, "to_lower", BuiltinMethod::StrToLower),
+            (TypeDecl::Array(vec![], 0), "len", BuiltinMethod::ArrayLen),
+            (TypeDecl::Bytes, "len", BuiltinMethod::BytesLen),
+        ]
+    }
+
+    /// The registry key for a receiver type: arrays are keyed without
+    /// their element type and length so `[u64; 3]` and `[str; 0]`
+    /// share one `len`. `None` for receivers that have no builtin
+    /// methods.
+    pub fn receiver_key(receiver: &TypeDecl) -> Option<TypeDecl> {
+        match receiver {
+            TypeDecl::String => Some(TypeDecl::String),
+            TypeDecl::Array(_, _) => Some(TypeDecl::Array(vec![], 0)),
+            TypeDecl::Bytes => Some(TypeDecl::Bytes),
+            _ => None,
+        }
+    }
+
+    /// Parameter types (excluding the receiver) and return type.
+    pub fn signature(&self) -> (Vec<TypeDecl>, TypeDecl) {
+        match self {
+            BuiltinMethod::IsNull => (vec![], TypeDecl::Bool),
+            BuiltinMethod::StrLen | BuiltinMethod::ArrayLen | BuiltinMethod::BytesLen => {
+                (vec![], TypeDecl::UInt64)
+            }
+            BuiltinMethod::StrConcat => (vec![TypeDecl::String], TypeDecl::String),
+            BuiltinMethod::StrSubstring => {
+                (vec![TypeDecl::UInt64, TypeDecl::UInt64], TypeDecl::String)
+            }
+            BuiltinMethod::StrContains => (vec![TypeDecl::String], TypeDecl::Bool),
+            BuiltinMethod::StrSplit => {
+                (vec![TypeDecl::String], TypeDecl::Array(vec![TypeDecl::String], 0))
+            }
+            BuiltinMethod::StrTrim | BuiltinMethod::StrToUpper | BuiltinMethod::StrToLower => {
+                (vec![], TypeDecl::String)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UnaryOp {
     BitwiseNot,  // ~
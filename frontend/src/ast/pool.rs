@@ -18,7 +18,7 @@ impl ExprRef {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct StmtRef(pub u32);
 
 impl StmtRef {
@@ -75,6 +75,14 @@ pub enum ExprType {
     UInt32 = 38,
     /// `fn(params) -> Ret { body }` — closure / lambda literal. Phase 1.
     Closure = 39,
+    /// `b"..."` byte-string literal; payload in `symbol_val`.
+    Bytes = 40,
+    /// `[e for x in src if f]` / `dict{k: v for x in src if f}`.
+    Comprehension = 41,
+    /// `with name = resource { body }`.
+    WithResource = 42,
+    /// `try { body } catch error { handler }`.
+    TryCatch = 43,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -92,6 +100,7 @@ pub enum StmtType {
     EnumDecl = 10,
     TraitDecl = 11,
     TypeAlias = 12,
+    Yield = 13,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -134,6 +143,10 @@ pub struct ExprPool {
     /// type and `lhs` for the body `ExprRef`. Kept as a separate
     /// parallel array because no other variant needs `ParameterList`.
     pub closure_params: Vec<Option<ParameterList>>,
+    /// Explicit type arguments of a call or struct literal
+    /// (`identity<u64>(5)`, `Box<u64> { .. }`). `None` when the source
+    /// wrote none, which is the common case.
+    pub type_args: Vec<Option<Vec<TypeDecl>>>,
 }
 
 impl Default for ExprPool {
@@ -169,6 +182,7 @@ impl ExprPool {
             target_type: Vec::new(),
             match_arms: Vec::new(),
             closure_params: Vec::new(),
+            type_args: Vec::new(),
         }
     }
 
@@ -198,6 +212,7 @@ impl ExprPool {
             target_type: Vec::with_capacity(cap),
             match_arms: Vec::with_capacity(cap),
             closure_params: Vec::with_capacity(cap),
+            type_args: Vec::with_capacity(cap),
         }
     }
 
@@ -229,6 +244,7 @@ impl ExprPool {
             self.target_type.resize(current_len + extend_count, None);
             self.match_arms.resize(current_len + extend_count, None);
             self.closure_params.resize(current_len + extend_count, None);
+            self.type_args.resize(current_len + extend_count, None);
         }
     }
 
@@ -336,7 +352,7 @@ impl ExprPool {
                 self.expr_types[index] = ExprType::ExprList;
                 self.expr_list[index] = Some(exprs);
             }
-            Expr::Call(fn_name, args) => {
+            Expr::Call(fn_name, args, type_args) => {
                 // Note: Call stores its args ExprRef in `operand`, not
                 // `rhs`. Earlier the `update` path mistakenly wrote to
                 // `rhs`, leaving the slot half-populated; the dedup
@@ -345,11 +361,16 @@ impl ExprPool {
                 self.expr_types[index] = ExprType::Call;
                 self.symbol_val[index] = Some(fn_name);
                 self.operand[index] = Some(args);
+                self.type_args[index] = (!type_args.is_empty()).then_some(type_args);
             }
             Expr::String(symbol) => {
                 self.expr_types[index] = ExprType::String;
                 self.symbol_val[index] = Some(symbol);
             }
+            Expr::Bytes(symbol) => {
+                self.expr_types[index] = ExprType::Bytes;
+                self.symbol_val[index] = Some(symbol);
+            }
             Expr::ArrayLiteral(elements) => {
                 self.expr_types[index] = ExprType::ArrayLiteral;
                 self.expr_list[index] = Some(elements);
@@ -365,10 +386,11 @@ impl ExprPool {
                 self.symbol_val[index] = Some(method);
                 self.expr_list[index] = Some(args);
             }
-            Expr::StructLiteral(type_name, fields) => {
+            Expr::StructLiteral(type_name, fields, type_args) => {
                 self.expr_types[index] = ExprType::StructLiteral;
                 self.symbol_val[index] = Some(type_name);
                 self.field_list[index] = Some(fields);
+                self.type_args[index] = (!type_args.is_empty()).then_some(type_args);
             }
             Expr::QualifiedIdentifier(path) => {
                 self.expr_types[index] = ExprType::QualifiedIdentifier;
@@ -385,11 +407,10 @@ impl ExprPool {
                 self.builtin_function[index] = Some(func);
                 self.expr_list[index] = Some(args);
             }
-            Expr::SliceAssign(object, start_expr, end_expr, value) => {
+            Expr::SliceAssign(object, slice_info, value) => {
                 self.expr_types[index] = ExprType::SliceAssign;
                 self.lhs[index] = Some(object);
-                self.rhs[index] = start_expr;
-                self.operand[index] = end_expr;
+                self.slice_info[index] = Some(slice_info);
                 self.third_operand[index] = Some(value);
             }
             Expr::AssociatedFunctionCall(struct_name, function_name, args) => {
@@ -444,6 +465,29 @@ impl ExprPool {
                 self.target_type[index] = return_type;
                 self.closure_params[index] = Some(params);
             }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                // element in lhs, source in rhs, filter in operand,
+                // dict value in third_operand, loop variable in
+                // symbol_val.
+                self.expr_types[index] = ExprType::Comprehension;
+                self.lhs[index] = Some(element);
+                self.rhs[index] = Some(source);
+                self.operand[index] = filter;
+                self.third_operand[index] = value;
+                self.symbol_val[index] = Some(var);
+            }
+            Expr::WithResource { name, resource, body } => {
+                self.expr_types[index] = ExprType::WithResource;
+                self.lhs[index] = Some(resource);
+                self.rhs[index] = Some(body);
+                self.symbol_val[index] = Some(name);
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.expr_types[index] = ExprType::TryCatch;
+                self.lhs[index] = Some(body);
+                self.rhs[index] = Some(handler);
+                self.symbol_val[index] = Some(error);
+            }
         }
     }
 
@@ -475,6 +519,7 @@ impl ExprPool {
         self.match_arms[index] = None;
         self.closure_params[index] = None;
         self.target_type[index] = None;
+        self.type_args[index] = None;
     }
 
     pub fn get(&self, expr_ref: &ExprRef) -> Option<Expr> {
@@ -548,11 +593,15 @@ impl ExprPool {
                 Some(Expr::Call(
                     self.symbol_val[index]?,
                     self.operand[index]?,
+                    self.type_args[index].clone().unwrap_or_default(),
                 ))
             }
             ExprType::String => {
                 Some(Expr::String(self.symbol_val[index]?))
             }
+            ExprType::Bytes => {
+                Some(Expr::Bytes(self.symbol_val[index]?))
+            }
             ExprType::ArrayLiteral => {
                 Some(Expr::ArrayLiteral(self.expr_list[index].clone()?))
             }
@@ -573,6 +622,7 @@ impl ExprPool {
                 Some(Expr::StructLiteral(
                     self.symbol_val[index]?,
                     self.field_list[index].clone()?,
+                    self.type_args[index].clone().unwrap_or_default(),
                 ))
             }
             ExprType::QualifiedIdentifier => {
@@ -594,8 +644,7 @@ impl ExprPool {
             ExprType::SliceAssign => {
                 Some(Expr::SliceAssign(
                     self.lhs[index]?,
-                    self.rhs[index],
-                    self.operand[index],
+                    self.slice_info[index].clone()?,
                     self.third_operand[index]?,
                 ))
             }
@@ -660,6 +709,29 @@ impl ExprPool {
                     body: self.lhs[index]?,
                 })
             }
+            ExprType::Comprehension => {
+                Some(Expr::Comprehension {
+                    element: self.lhs[index]?,
+                    value: self.third_operand[index],
+                    var: self.symbol_val[index]?,
+                    source: self.rhs[index]?,
+                    filter: self.operand[index],
+                })
+            }
+            ExprType::WithResource => {
+                Some(Expr::WithResource {
+                    name: self.symbol_val[index]?,
+                    resource: self.lhs[index]?,
+                    body: self.rhs[index]?,
+                })
+            }
+            ExprType::TryCatch => {
+                Some(Expr::TryCatch {
+                    body: self.lhs[index]?,
+                    error: self.symbol_val[index]?,
+                    handler: self.rhs[index]?,
+                })
+            }
         }
     }
 
@@ -671,6 +743,66 @@ impl ExprPool {
         self.expr_types.is_empty()
     }
 
+    /// Discriminant of `expr_ref` without rebuilding the node.
+    pub fn expr_type(&self, expr_ref: &ExprRef) -> Option<ExprType> {
+        self.expr_types.get(expr_ref.to_index()).copied()
+    }
+
+    // Borrowing column readers. `get` rebuilds an owned `Expr`, which
+    // clones the `Vec` payload of blocks, argument lists, elif chains
+    // and match arms on every call; evaluators that revisit the same
+    // nodes (loop bodies, function bodies, call sites) read those
+    // payloads through these instead. Each returns `None` when the
+    // slot's variant doesn't use the column.
+
+    pub fn lhs_of(&self, expr_ref: &ExprRef) -> Option<ExprRef> {
+        self.lhs.get(expr_ref.to_index()).copied().flatten()
+    }
+
+    pub fn rhs_of(&self, expr_ref: &ExprRef) -> Option<ExprRef> {
+        self.rhs.get(expr_ref.to_index()).copied().flatten()
+    }
+
+    pub fn third_operand_of(&self, expr_ref: &ExprRef) -> Option<ExprRef> {
+        self.third_operand.get(expr_ref.to_index()).copied().flatten()
+    }
+
+    pub fn symbol_of(&self, expr_ref: &ExprRef) -> Option<DefaultSymbol> {
+        self.symbol_val.get(expr_ref.to_index()).copied().flatten()
+    }
+
+    pub fn symbol_list_of(&self, expr_ref: &ExprRef) -> Option<&[DefaultSymbol]> {
+        self.symbol_list.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn expr_list_of(&self, expr_ref: &ExprRef) -> Option<&[ExprRef]> {
+        self.expr_list.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn stmt_list_of(&self, expr_ref: &ExprRef) -> Option<&[StmtRef]> {
+        self.stmt_list.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn entry_list_of(&self, expr_ref: &ExprRef) -> Option<&[(ExprRef, ExprRef)]> {
+        self.entry_list.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn field_list_of(&self, expr_ref: &ExprRef) -> Option<&[(DefaultSymbol, ExprRef)]> {
+        self.field_list.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn match_arms_of(&self, expr_ref: &ExprRef) -> Option<&[MatchArm]> {
+        self.match_arms.get(expr_ref.to_index())?.as_deref()
+    }
+
+    pub fn builtin_method_of(&self, expr_ref: &ExprRef) -> Option<&BuiltinMethod> {
+        self.builtin_method.get(expr_ref.to_index())?.as_ref()
+    }
+
+    pub fn builtin_function_of(&self, expr_ref: &ExprRef) -> Option<&BuiltinFunction> {
+        self.builtin_function.get(expr_ref.to_index())?.as_ref()
+    }
+
     pub fn update(&mut self, expr_ref: &ExprRef, expr: Expr) {
         let index = expr_ref.to_index();
         if index >= self.expr_types.len() {
@@ -873,9 +1005,14 @@ impl StmtPool {
                 self.stmt_types[index] = StmtType::Return;
                 self.expr_val[index] = value;
             }
-            Stmt::Break(label) => {
+            Stmt::Yield(value) => {
+                self.stmt_types[index] = StmtType::Yield;
+                self.expr_val[index] = Some(value);
+            }
+            Stmt::Break(label, value) => {
                 self.stmt_types[index] = StmtType::Break;
                 self.loop_label[index] = label;
+                self.expr_val[index] = value;
             }
             Stmt::Continue(label) => {
                 self.stmt_types[index] = StmtType::Continue;
@@ -997,7 +1134,8 @@ impl StmtPool {
             StmtType::Return => {
                 Some(Stmt::Return(self.expr_val[index]))
             }
-            StmtType::Break => Some(Stmt::Break(self.loop_label[index])),
+            StmtType::Yield => Some(Stmt::Yield(self.expr_val[index]?)),
+            StmtType::Break => Some(Stmt::Break(self.loop_label[index], self.expr_val[index])),
             StmtType::Continue => Some(Stmt::Continue(self.loop_label[index])),
             StmtType::For => {
                 Some(Stmt::For(
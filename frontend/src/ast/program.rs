@@ -33,6 +33,11 @@ pub struct Program {
     /// at program startup and bound as immutable globals so any function
     /// body (including `main`) can reference them.
     pub consts: Vec<ConstDecl>,
+    /// Top-level items guarded by `@cfg(...)`. The parser keeps every
+    /// guarded item in place and records it here; `cfg::prune_cfg`
+    /// drops the ones whose predicate is false for the active
+    /// `CfgEnv` before type checking. Empty once pruned.
+    pub cfg_items: Vec<CfgItem>,
 
     pub statement: StmtPool,
     pub expression: ExprPool,
@@ -51,6 +56,39 @@ pub struct ConstDecl {
     pub visibility: Visibility,
 }
 
+/// Condition written inside `@cfg(...)`.
+///
+/// `name` holds when `name` is defined at all, `name = "value"` when it
+/// is defined with exactly that value. `not` / `all` / `any` compose
+/// the other forms. Names and values are kept as plain strings (not
+/// interned symbols) so a module's predicates stay meaningful after
+/// integration into a program with a different interner.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CfgPredicate {
+    Defined(String),
+    Equals(String, String),
+    Not(Box<CfgPredicate>),
+    All(Vec<CfgPredicate>),
+    Any(Vec<CfgPredicate>),
+}
+
+/// The top-level item a `@cfg(...)` attribute applies to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CfgTarget {
+    /// Index into `Program::function`.
+    Function(usize),
+    /// Index into `Program::consts`.
+    Const(usize),
+    /// `struct` / `enum` / `impl` / `trait` declaration statement.
+    Decl(StmtRef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CfgItem {
+    pub predicate: CfgPredicate,
+    pub target: CfgTarget,
+}
+
 impl Program {
     pub fn get(&self, expr_ref: &ExprRef) -> Option<Expr> {
         self.expression.get(expr_ref)
@@ -94,6 +132,19 @@ pub struct Function {
     /// `.t` file instead.
     pub is_extern: bool,
     pub visibility: Visibility,
+    /// `@inline` / `@noinline` written before the `fn`.
+    pub inline: Option<InlineHint>,
+}
+
+/// Inlining attribute on a function or method. Only the native
+/// compiler's IR inliner reads it; without one, small functions are
+/// inlined by size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineHint {
+    /// `@inline`: inline at every direct call site the inliner can handle.
+    Always,
+    /// `@noinline`: never inline.
+    Never,
 }
 
 pub type Parameter = (DefaultSymbol, TypeDecl);
@@ -167,6 +218,8 @@ pub struct MethodFunction {
     /// semantics on every receiver kind).
     pub self_is_mut: bool,
     pub visibility: Visibility,
+    /// `@inline` / `@noinline` written before the method's `fn`.
+    pub inline: Option<InlineHint>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
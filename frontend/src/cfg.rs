@@ -0,0 +1,127 @@
+//! Conditional compilation (`@cfg(...)`).
+//!
+//! The parser keeps every top-level item, whatever its `@cfg`
+//! predicate says, and records the guarded ones in
+//! `Program::cfg_items`. Before type checking, the driver builds a
+//! `CfgEnv` for the backend it is about to hand the program to (plus
+//! any `--define NAME=value` flags) and calls `prune_cfg`, which drops
+//! every item whose predicate is false. That lets a single source file
+//! carry backend-specific variants of the same function:
+//!
+//! ```text
+//! @cfg(target = "lua")
+//! fn now_ms() -> u64 { ... }
+//!
+//! @cfg(not(target = "lua"))
+//! fn now_ms() -> u64 { ... }
+//! ```
+//!
+//! Module programs are parsed separately; integration carries their
+//! `cfg_items` over (remapped into the main program), so one
+//! `prune_cfg` call after integration covers them too.
+
+use std::collections::HashMap;
+
+use crate::ast::{CfgPredicate, CfgTarget, Program, Stmt};
+
+/// The `target` value the tree-walking interpreter (and its JIT) use.
+pub const TARGET_INTERPRETER: &str = "interpreter";
+/// The `target` value the AOT / JIT native compiler uses.
+pub const TARGET_NATIVE: &str = "native";
+/// The `target` value for Lua output. No backend in the tree uses it
+/// yet; it is what `@cfg(target = "lua")` items and `lua!(...)` are
+/// checked against.
+pub const TARGET_LUA: &str = "lua";
+
+/// Names visible to `@cfg` predicates: `target` plus everything passed
+/// through `--define`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CfgEnv {
+    values: HashMap<String, String>,
+}
+
+impl CfgEnv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Environment with only `target` defined.
+    pub fn for_target(target: &str) -> Self {
+        let mut env = Self::new();
+        env.define("target", target);
+        env
+    }
+
+    pub fn define(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    /// Apply one `--define` argument: `NAME=value`, or a bare `NAME`
+    /// (defined with an empty value, so only `@cfg(NAME)` matches it).
+    pub fn define_arg(&mut self, arg: &str) -> Result<(), String> {
+        let (name, value) = arg.split_once('=').unwrap_or((arg, ""));
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!("invalid --define `{arg}`: expected NAME or NAME=value"));
+        }
+        self.define(name, value);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn matches(&self, predicate: &CfgPredicate) -> bool {
+        match predicate {
+            CfgPredicate::Defined(name) => self.values.contains_key(name),
+            CfgPredicate::Equals(name, value) => self.get(name) == Some(value.as_str()),
+            CfgPredicate::Not(inner) => !self.matches(inner),
+            CfgPredicate::All(preds) => preds.iter().all(|p| self.matches(p)),
+            CfgPredicate::Any(preds) => preds.iter().any(|p| self.matches(p)),
+        }
+    }
+}
+
+/// Drop every `@cfg` item of `program` whose predicate does not hold in
+/// `env`, and clear `program.cfg_items`. Returns how many items were
+/// removed.
+///
+/// Functions and consts are removed from their vectors (keeping
+/// `function_module_paths` parallel); declaration statements are
+/// overwritten with an inert `Stmt::Break` so the top-level scans in
+/// the type checker and backends no longer see them.
+pub fn prune_cfg(program: &mut Program, env: &CfgEnv) -> usize {
+    let items = std::mem::take(&mut program.cfg_items);
+    let mut dead_functions = Vec::new();
+    let mut dead_consts = Vec::new();
+    let mut removed = 0;
+    for item in items {
+        if env.matches(&item.predicate) {
+            continue;
+        }
+        removed += 1;
+        match item.target {
+            CfgTarget::Function(idx) => dead_functions.push(idx),
+            CfgTarget::Const(idx) => dead_consts.push(idx),
+            CfgTarget::Decl(stmt_ref) => program.statement.update(&stmt_ref, Stmt::Break(None, None)),
+        }
+    }
+
+    dead_functions.sort_unstable();
+    for idx in dead_functions.into_iter().rev() {
+        program.function.remove(idx);
+        if idx < program.function_module_paths.len() {
+            program.function_module_paths.remove(idx);
+        }
+    }
+    dead_consts.sort_unstable();
+    for idx in dead_consts.into_iter().rev() {
+        program.consts.remove(idx);
+    }
+    removed
+}
@@ -0,0 +1,323 @@
+//! Stable diagnostic codes for parser and type-checker errors.
+//!
+//! Every `ParserErrorKind` / `TypeCheckErrorKind` variant maps to one
+//! code (`E0001`, `E0101`, ...). The code is printed next to the
+//! diagnostic so users can look up the long-form explanation with
+//! `interpreter --explain <CODE>`. Codes are append-only: once shipped
+//! a code keeps its meaning even if the variant is renamed.
+//!
+//! Numbering: `E00xx` are parser errors, `E01xx` are type-check errors.
+
+/// Registry entry for one diagnostic code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeInfo {
+    pub code: &'static str,
+    /// One-line summary shown in the `--explain` header.
+    pub title: &'static str,
+    /// Extended explanation with an erroneous example and a fix.
+    pub explanation: &'static str,
+}
+
+pub const E_UNEXPECTED_TOKEN: &str = "E0001";
+pub const E_RECURSION_LIMIT: &str = "E0002";
+pub const E_PARSE_GENERIC: &str = "E0003";
+pub const E_IO: &str = "E0004";
+pub const E_TYPE_MISMATCH: &str = "E0101";
+pub const E_OPERAND_MISMATCH: &str = "E0102";
+pub const E_NOT_FOUND: &str = "E0103";
+pub const E_UNSUPPORTED_OPERATION: &str = "E0104";
+pub const E_CONVERSION: &str = "E0105";
+pub const E_ARRAY: &str = "E0106";
+pub const E_METHOD: &str = "E0107";
+pub const E_INVALID_LITERAL: &str = "E0108";
+pub const E_ACCESS_DENIED: &str = "E0109";
+pub const E_TYPE_CHECK_GENERIC: &str = "E0110";
+
+pub const ERROR_CODES: &[ErrorCodeInfo] = &[
+    ErrorCodeInfo {
+        code: E_UNEXPECTED_TOKEN,
+        title: "unexpected token",
+        explanation: "\
+The parser found a token that cannot appear at this position.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val fn = 1u64
+        0u64
+    }
+
+`fn` is a keyword, so it cannot name a binding. Pick another name:
+
+    fn main() -> u64 {
+        val f = 1u64
+        0u64
+    }
+
+Remember that toylang uses `elif` rather than `else if`, and that
+statements are separated by newlines, not semicolons.",
+    },
+    ErrorCodeInfo {
+        code: E_RECURSION_LIMIT,
+        title: "parser recursion limit exceeded",
+        explanation: "\
+An expression or block is nested more deeply than the parser allows.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        ((((((((((((((((((((
+        ((((((((((((((((((((
+        ((((((((((((((((((((
+        ((((((((((((((((((((
+        1u64
+        ))))))))))))))))))))
+        ))))))))))))))))))))
+        ))))))))))))))))))))
+        ))))))))))))))))))))
+    }
+
+Split the expression into intermediate `val` bindings so each level
+stays shallow.",
+    },
+    ErrorCodeInfo {
+        code: E_PARSE_GENERIC,
+        title: "syntax error",
+        explanation: "\
+The source is not valid toylang syntax. The message attached to the
+diagnostic describes the specific construct the parser rejected.
+
+Erroneous code example:
+
+    const LIMIT = 10u64
+
+    fn main() -> u64 {
+        LIMIT
+    }
+
+A top-level `const` must spell out its type:
+
+    const LIMIT: u64 = 10u64
+
+    fn main() -> u64 {
+        LIMIT
+    }",
+    },
+    ErrorCodeInfo {
+        code: E_IO,
+        title: "I/O error while reading source",
+        explanation: "\
+A source file or module could not be read from disk.
+
+Check that the path passed on the command line (or the module named
+in an `import` declaration) exists and is readable. Core modules are
+looked up through `--core-modules <DIR>` or `TOYLANG_CORE_MODULES`.",
+    },
+    ErrorCodeInfo {
+        code: E_TYPE_MISMATCH,
+        title: "mismatched types",
+        explanation: "\
+An expression has a different type from the one its context requires.
+
+Erroneous code example:
+
+    fn main() -> i64 {
+        val x: i64 = \"string\"
+        x
+    }
+
+There is no implicit conversion between unrelated types. Either fix
+the value or convert it explicitly with `as`:
+
+    fn main() -> i64 {
+        val n: u64 = 3u64
+        val x: i64 = n as i64
+        x
+    }",
+    },
+    ErrorCodeInfo {
+        code: E_OPERAND_MISMATCH,
+        title: "incompatible operand types",
+        explanation: "\
+The two operands of a binary operator have types the operator cannot
+combine.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val a: u64 = 1u64
+        val b: i64 = 2i64
+        a + b
+    }
+
+Both sides of an arithmetic or comparison operator must have the same
+type. Cast one of them:
+
+    fn main() -> u64 {
+        val a: u64 = 1u64
+        val b: i64 = 2i64
+        a + (b as u64)
+    }",
+    },
+    ErrorCodeInfo {
+        code: E_NOT_FOUND,
+        title: "name not found",
+        explanation: "\
+A variable, function, struct, field, or module was referenced but is
+not defined in the current scope.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        undefined_variable
+    }
+
+Check the spelling, declare the binding with `val` / `var` before the
+use, or add the missing `import`.",
+    },
+    ErrorCodeInfo {
+        code: E_UNSUPPORTED_OPERATION,
+        title: "operation not supported for this type",
+        explanation: "\
+The operator or builtin is not defined for the operand type.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val n = 5u64
+        n.value
+    }
+
+Fields only exist on structs (and tuples, by position). Use the value
+directly, or wrap it in a struct that has the field.",
+    },
+    ErrorCodeInfo {
+        code: E_CONVERSION,
+        title: "invalid conversion",
+        explanation: "\
+An unsuffixed integer literal does not fit the type the context gives
+it, here the `i64` of the other operand.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val a = 1i64
+        val b = a + 10000000000000000000
+        0u64
+    }
+
+Use a value that fits the type, or make the other operand a type that
+can hold it (`1u64` here).",
+    },
+    ErrorCodeInfo {
+        code: E_ARRAY,
+        title: "invalid array operation",
+        explanation: "\
+An array literal, index, or slice is malformed: elements of different
+types, a non-integer index, or a size mismatch against the declared
+`[T; N]` type.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val a: [u64; 2] = [1u64, true]
+        0u64
+    }
+
+Every element of an array must have the same type, and the literal
+length must match the annotation.",
+    },
+    ErrorCodeInfo {
+        code: E_METHOD,
+        title: "invalid method call",
+        explanation: "\
+A method does not exist on the receiver type, or it was called with
+the wrong number or types of arguments.
+
+Erroneous code example:
+
+    struct Point { x: i64 }
+
+    fn main() -> u64 {
+        val p = Point { x: 1i64 }
+        p.length()
+        0u64
+    }
+
+Define the method in an `impl Point { ... }` block, or call one that
+exists. Trait methods are only available once the type implements the
+trait with `impl Trait for Point`.",
+    },
+    ErrorCodeInfo {
+        code: E_INVALID_LITERAL,
+        title: "invalid literal",
+        explanation: "\
+A numeric literal could not be parsed for the type it was given.
+
+Erroneous code example:
+
+    fn main() -> u64 {
+        val x: u8 = 256u8
+        0u64
+    }
+
+Make sure the literal is in range for its suffix. `f64` literals must
+always carry the `f64` suffix (`1.5f64`).",
+    },
+    ErrorCodeInfo {
+        code: E_ACCESS_DENIED,
+        title: "private item accessed",
+        explanation: "\
+A non-`pub` item from another module was referenced.
+
+Erroneous code example:
+
+    # modules/geom.t
+    package geom
+
+    fn helper() -> u64 {
+        1u64
+    }
+
+    # main.t
+    import geom
+
+    fn main() -> u64 {
+        geom::helper()
+    }
+
+Mark the item `pub` in the defining module, or use a public API
+instead.",
+    },
+    ErrorCodeInfo {
+        code: E_TYPE_CHECK_GENERIC,
+        title: "type error",
+        explanation: "\
+A type-check failure that is not covered by a more specific code, such
+as a trait bound that is not satisfied, a non-exhaustive `match`, or a
+contract clause that is not `bool`. The diagnostic message describes
+the exact problem.
+
+Erroneous code example:
+
+    enum Color { Red, Green }
+
+    fn main() -> u64 {
+        val c = Color::Red
+        match c {
+            Color::Red => 1u64,
+        }
+    }
+
+Add the missing arms (or a `_` wildcard) to make the match
+exhaustive.",
+    },
+];
+
+/// Look up the registry entry for `code`. Matching is case-insensitive
+/// so `--explain e0101` works as well as `--explain E0101`.
+pub fn lookup(code: &str) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES
+        .iter()
+        .find(|info| info.code.eq_ignore_ascii_case(code))
+}
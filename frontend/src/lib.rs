@@ -1,19 +1,26 @@
 pub mod ast;
 pub mod type_decl;
 pub mod token;
+pub mod token_stream;
 pub mod type_checker;
 pub mod parser;
 pub mod visitor;
 pub mod module_resolver;
 pub mod alias_resolution;
+pub mod cfg;
+pub mod purity;
+pub mod lints;
+pub mod error_code;
 
 #[cfg(test)]
 mod tuple_tests;
 
 pub use alias_resolution::resolve_type_aliases;
+pub use cfg::{prune_cfg, CfgEnv};
 pub use parser::{Parser, ParserWithInterner};
 pub use parser::error::{MultipleParserResult, ParserError};
 pub use type_checker::error::{MultipleTypeCheckResult, TypeCheckError};
 pub use module_resolver::{ModuleResolver, ResolvedModule};
+pub use token_stream::lex_with_spans;
 
 
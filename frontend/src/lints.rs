@@ -0,0 +1,661 @@
+//! Lints: style and maintainability checks over a program that already
+//! type-checks.
+//!
+//! Each rule has a level — `allow` (not run), `warn` or `deny` — taken
+//! from the `[lints]` table of the project manifest, `toylang.toml`,
+//! found in the source file's directory or the nearest ancestor:
+//!
+//! ```toml
+//! [lints]
+//! magic-number = "warn"
+//! deep-nesting = "deny"
+//! max-nesting-depth = 3
+//! ```
+//!
+//! Rules that aren't mentioned keep their default level. Drivers run
+//! [`run_lints`] on the user's own program, before any module is
+//! integrated, and fail when a `deny` lint fired.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+
+use crate::ast::{Expr, ExprRef, Pattern, Program, Stmt, StmtRef, Visibility};
+use crate::type_checker::SourceLocation;
+use crate::type_decl::TypeDecl;
+
+/// File name of the project manifest.
+pub const MANIFEST_FILE: &str = "toylang.toml";
+
+/// Control-flow nesting allowed before `deep-nesting` fires, unless the
+/// manifest sets `max-nesting-depth`.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LintRule {
+    /// `snake_case` functions, parameters, locals and fields;
+    /// `UpperCamelCase` types, traits and variants; `SCREAMING_SNAKE_CASE`
+    /// consts.
+    NamingConvention,
+    /// `if` / `match` / loops / closures nested deeper than
+    /// `max-nesting-depth` inside one function.
+    DeepNesting,
+    /// Numeric literals other than 0, 1 and 2 outside `const`
+    /// initializers.
+    MagicNumber,
+    /// `pub` items nothing refers to, in a program with a `main` and no
+    /// `package` line (so nothing can import them).
+    UnusedPub,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] =
+        [LintRule::NamingConvention, LintRule::DeepNesting, LintRule::MagicNumber, LintRule::UnusedPub];
+
+    /// Name used in the manifest and in diagnostics.
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::NamingConvention => "naming-convention",
+            LintRule::DeepNesting => "deep-nesting",
+            LintRule::MagicNumber => "magic-number",
+            LintRule::UnusedPub => "unused-pub",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+
+    /// `magic-number` is noisy on small programs, so it is opt-in.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            LintRule::MagicNumber => LintLevel::Allow,
+            _ => LintLevel::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Per-rule levels plus rule options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintConfig {
+    levels: HashMap<LintRule, LintLevel>,
+    pub max_nesting_depth: usize,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            levels: HashMap::new(),
+            max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels.get(&rule).copied().unwrap_or_else(|| rule.default_level())
+    }
+
+    pub fn set_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+
+    /// Read the `[lints]` table of a manifest. Other tables are ignored;
+    /// only `key = "string"` and `key = integer` entries are understood.
+    pub fn from_manifest(text: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut in_lints = false;
+        for (index, raw) in text.lines().enumerate() {
+            let line_no = index + 1;
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_lints = table.trim() == "lints";
+                continue;
+            }
+            if !in_lints {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `key = value`", line_no));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if key == "max-nesting-depth" {
+                config.max_nesting_depth = value
+                    .parse()
+                    .map_err(|_| format!("line {}: max-nesting-depth must be a number, got `{}`", line_no, value))?;
+                continue;
+            }
+            let rule = LintRule::from_name(key).ok_or_else(|| format!("line {}: unknown lint `{}`", line_no, key))?;
+            let level = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .and_then(LintLevel::from_name)
+                .ok_or_else(|| {
+                    format!("line {}: level of `{}` must be \"allow\", \"warn\" or \"deny\", got {}", line_no, key, value)
+                })?;
+            config.set_level(rule, level);
+        }
+        Ok(config)
+    }
+
+    /// The manifest in `dir` or its nearest ancestor, if any.
+    pub fn find_manifest(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors().map(|d| d.join(MANIFEST_FILE)).find(|path| path.is_file())
+    }
+
+    /// Configuration for sources in `dir`: its manifest's, or the
+    /// defaults when there is none.
+    pub fn for_dir(dir: &Path) -> Result<Self, String> {
+        match Self::find_manifest(dir) {
+            Some(path) => {
+                let text = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+                Self::from_manifest(&text).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// One lint finding. `level` is never `Allow`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub rule: LintRule,
+    pub level: LintLevel,
+    pub message: String,
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.rule.name())
+    }
+}
+
+/// Run every rule that isn't `allow` over `program`. `source` is the text
+/// it was parsed from. Findings come back in source order.
+pub fn run_lints(program: &Program, interner: &DefaultStringInterner, source: &str, config: &LintConfig) -> Vec<Lint> {
+    let mut cx = LintContext {
+        program,
+        interner,
+        source,
+        config,
+        lints: Vec::new(),
+    };
+    cx.naming();
+    cx.nesting_and_numbers();
+    cx.unused_pub();
+    cx.lints.sort_by_key(|lint| lint.location.map(|l| l.offset));
+    cx.lints
+}
+
+struct LintContext<'a> {
+    program: &'a Program,
+    interner: &'a DefaultStringInterner,
+    source: &'a str,
+    config: &'a LintConfig,
+    lints: Vec<Lint>,
+}
+
+impl LintContext<'_> {
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.config.level(rule) != LintLevel::Allow
+    }
+
+    fn report(&mut self, rule: LintRule, message: String, location: Option<SourceLocation>) {
+        let level = self.config.level(rule);
+        if level != LintLevel::Allow {
+            self.lints.push(Lint { rule, level, message, location });
+        }
+    }
+
+    fn name(&self, symbol: DefaultSymbol) -> &str {
+        self.interner.resolve(symbol).unwrap_or("<unknown>")
+    }
+
+    fn at_offset(&self, offset: usize) -> Option<SourceLocation> {
+        let before = self.source.get(..offset)?;
+        let line = before.matches('\n').count() as u32 + 1;
+        let column = (offset - before.rfind('\n').map_or(0, |nl| nl + 1)) as u32 + 1;
+        Some(SourceLocation { line, column, offset: offset as u32 })
+    }
+
+    fn expr_location(&self, expr: &ExprRef) -> Option<SourceLocation> {
+        self.program.location_pool.get_expr_location(expr).copied()
+    }
+
+    fn stmt_location(&self, stmt: &StmtRef) -> Option<SourceLocation> {
+        self.program.location_pool.get_stmt_location(stmt).copied()
+    }
+
+    fn declarations(&self) -> Vec<(StmtRef, Stmt)> {
+        (0..self.program.statement.len())
+            .map(|i| StmtRef(i as u32))
+            .filter_map(|r| self.program.statement.get(&r).map(|s| (r, s)))
+            .collect()
+    }
+
+    // ---- naming-convention ----
+
+    fn naming(&mut self) {
+        if !self.enabled(LintRule::NamingConvention) {
+            return;
+        }
+        for func in &self.program.function {
+            let location = self.at_offset(func.node.start);
+            if !func.is_extern {
+                self.check_name(func.name, Case::Snake, "function", location);
+            }
+            for (param, _) in &func.parameter {
+                self.check_name(*param, Case::Snake, "parameter", location);
+            }
+            for generic in &func.generic_params {
+                self.check_name(*generic, Case::Camel, "type parameter", location);
+            }
+        }
+        for c in &self.program.consts {
+            let location = self.at_offset(c.node.start);
+            self.check_name(c.name, Case::Screaming, "const", location);
+        }
+        for (stmt_ref, stmt) in self.declarations() {
+            let location = self.stmt_location(&stmt_ref);
+            match stmt {
+                Stmt::StructDecl { name, fields, .. } => {
+                    self.check_name(name, Case::Camel, "struct", location);
+                    for field in &fields {
+                        if !Case::Snake.matches(&field.name) {
+                            self.report_name(&field.name, Case::Snake, "field", location);
+                        }
+                    }
+                }
+                Stmt::EnumDecl { name, variants, .. } => {
+                    self.check_name(name, Case::Camel, "enum", location);
+                    for variant in &variants {
+                        self.check_name(variant.name, Case::Camel, "variant", location);
+                    }
+                }
+                Stmt::TraitDecl { name, .. } => self.check_name(name, Case::Camel, "trait", location),
+                Stmt::ImplBlock { methods, .. } => {
+                    for method in &methods {
+                        let location = self.at_offset(method.node.start);
+                        self.check_name(method.name, Case::Snake, "method", location);
+                        for (param, _) in &method.parameter {
+                            self.check_name(*param, Case::Snake, "parameter", location);
+                        }
+                    }
+                }
+                Stmt::Val(name, _, init) => {
+                    let location = self.expr_location(&init);
+                    self.check_name(name, Case::Snake, "local", location);
+                }
+                Stmt::Var(name, _, init) => {
+                    let location = init.as_ref().and_then(|e| self.expr_location(e)).or(location);
+                    self.check_name(name, Case::Snake, "local", location);
+                }
+                Stmt::For(_, var, start, ..) => {
+                    let location = self.expr_location(&start);
+                    self.check_name(var, Case::Snake, "loop variable", location);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_name(&mut self, symbol: DefaultSymbol, case: Case, what: &str, location: Option<SourceLocation>) {
+        let name = self.name(symbol).to_string();
+        if !case.matches(&name) {
+            self.report_name(&name, case, what, location);
+        }
+    }
+
+    fn report_name(&mut self, name: &str, case: Case, what: &str, location: Option<SourceLocation>) {
+        self.report(
+            LintRule::NamingConvention,
+            format!("{} `{}` should be {}", what, name, case.describe()),
+            location,
+        );
+    }
+
+    // ---- deep-nesting and magic-number ----
+
+    fn nesting_and_numbers(&mut self) {
+        let nesting = self.enabled(LintRule::DeepNesting);
+        let numbers = self.enabled(LintRule::MagicNumber);
+        if !nesting && !numbers {
+            return;
+        }
+        let mut bodies: Vec<(DefaultSymbol, usize, StmtRef)> =
+            self.program.function.iter().filter(|f| !f.is_extern).map(|f| (f.name, f.node.start, f.code)).collect();
+        for (_, stmt) in self.declarations() {
+            if let Stmt::ImplBlock { methods, .. } = stmt {
+                bodies.extend(methods.iter().map(|m| (m.name, m.node.start, m.code)));
+            }
+        }
+        for (name, start, code) in bodies {
+            let mut walk = BodyWalk { cx: self, depth: 0, deepest: 0, magic: Vec::new() };
+            walk.stmt(&code);
+            let (deepest, magic) = (walk.deepest, walk.magic);
+            if nesting && deepest > self.config.max_nesting_depth {
+                let message = format!(
+                    "`{}` nests control flow {} levels deep (limit {})",
+                    self.name(name),
+                    deepest,
+                    self.config.max_nesting_depth
+                );
+                let location = self.at_offset(start);
+                self.report(LintRule::DeepNesting, message, location);
+            }
+            if numbers {
+                for (literal, location) in magic {
+                    self.report(
+                        LintRule::MagicNumber,
+                        format!("magic number `{}`; give it a name with `const`", literal),
+                        location,
+                    );
+                }
+            }
+        }
+    }
+
+    // ---- unused-pub ----
+
+    fn unused_pub(&mut self) {
+        if !self.enabled(LintRule::UnusedPub) || self.program.package_decl.is_some() {
+            return;
+        }
+        let has_main = self.program.function.iter().any(|f| self.name(f.name) == "main");
+        if !has_main {
+            return;
+        }
+        let used = self.referenced_names();
+        let mut unused = Vec::new();
+        for func in &self.program.function {
+            if func.visibility == Visibility::Public && self.name(func.name) != "main" && !used.contains(&func.name) {
+                unused.push(("function", func.name, self.at_offset(func.node.start)));
+            }
+        }
+        for c in &self.program.consts {
+            if c.visibility == Visibility::Public && !used.contains(&c.name) {
+                unused.push(("const", c.name, self.at_offset(c.node.start)));
+            }
+        }
+        for (stmt_ref, stmt) in self.declarations() {
+            let (what, name, visibility) = match stmt {
+                Stmt::StructDecl { name, visibility, .. } => ("struct", name, visibility),
+                Stmt::EnumDecl { name, visibility, .. } => ("enum", name, visibility),
+                Stmt::TraitDecl { name, visibility, .. } => ("trait", name, visibility),
+                _ => continue,
+            };
+            if visibility == Visibility::Public && !used.contains(&name) {
+                unused.push((what, name, self.stmt_location(&stmt_ref)));
+            }
+        }
+        for (what, name, location) in unused {
+            let message = format!("{} `{}` is `pub` but nothing uses it", what, self.name(name));
+            self.report(LintRule::UnusedPub, message, location);
+        }
+    }
+
+    /// Every item name the program mentions outside its own declaration.
+    fn referenced_names(&self) -> HashSet<DefaultSymbol> {
+        let mut used = HashSet::new();
+        for i in 0..self.program.expression.len() {
+            let Some(expr) = self.program.expression.get(&ExprRef(i as u32)) else {
+                continue;
+            };
+            match expr {
+                Expr::Identifier(name) | Expr::Call(name, ..) => {
+                    used.insert(name);
+                }
+                Expr::StructLiteral(name, _, args) => {
+                    used.insert(name);
+                    args.iter().for_each(|t| mentioned_types(t, &mut used));
+                }
+                Expr::AssociatedFunctionCall(owner, ..) => {
+                    used.insert(owner);
+                }
+                Expr::QualifiedIdentifier(path) => used.extend(path),
+                Expr::Cast(_, ty) => mentioned_types(&ty, &mut used),
+                Expr::Closure { params, return_type, .. } => {
+                    params.iter().for_each(|(_, t)| mentioned_types(t, &mut used));
+                    return_type.iter().for_each(|t| mentioned_types(t, &mut used));
+                }
+                Expr::Match(_, arms) => arms.iter().for_each(|arm| mentioned_in_pattern(&arm.pattern, &mut used)),
+                _ => {}
+            }
+        }
+        let signature = |params: &[(DefaultSymbol, TypeDecl)], ret: &Option<TypeDecl>, used: &mut HashSet<DefaultSymbol>| {
+            params.iter().for_each(|(_, t)| mentioned_types(t, used));
+            ret.iter().for_each(|t| mentioned_types(t, used));
+        };
+        for func in &self.program.function {
+            signature(&func.parameter, &func.return_type, &mut used);
+        }
+        for c in &self.program.consts {
+            mentioned_types(&c.type_decl, &mut used);
+        }
+        for (_, stmt) in self.declarations() {
+            match stmt {
+                Stmt::Val(_, Some(ty), _) | Stmt::Var(_, Some(ty), _) => mentioned_types(&ty, &mut used),
+                Stmt::StructDecl { fields, .. } => fields.iter().for_each(|f| mentioned_types(&f.type_decl, &mut used)),
+                Stmt::EnumDecl { variants, .. } => {
+                    variants.iter().flat_map(|v| &v.payload_types).for_each(|t| mentioned_types(t, &mut used))
+                }
+                Stmt::ImplBlock { methods, trait_name, .. } => {
+                    used.extend(trait_name);
+                    for method in &methods {
+                        signature(&method.parameter, &method.return_type, &mut used);
+                    }
+                }
+                Stmt::TraitDecl { methods, .. } => {
+                    for method in &methods {
+                        signature(&method.parameter, &method.return_type, &mut used);
+                    }
+                }
+                _ => {}
+            }
+        }
+        used
+    }
+}
+
+fn mentioned_types(ty: &TypeDecl, used: &mut HashSet<DefaultSymbol>) {
+    match ty {
+        TypeDecl::Identifier(name) => {
+            used.insert(*name);
+        }
+        TypeDecl::Struct(name, args) | TypeDecl::Enum(name, args) => {
+            used.insert(*name);
+            args.iter().for_each(|t| mentioned_types(t, used));
+        }
+        TypeDecl::Array(elements, _) | TypeDecl::Tuple(elements) => elements.iter().for_each(|t| mentioned_types(t, used)),
+        TypeDecl::Dict(k, v) => {
+            mentioned_types(k, used);
+            mentioned_types(v, used);
+        }
+        TypeDecl::Range(inner) | TypeDecl::Ref { inner, .. } => mentioned_types(inner, used),
+        TypeDecl::Function(params, ret) => {
+            params.iter().for_each(|t| mentioned_types(t, used));
+            mentioned_types(ret, used);
+        }
+        _ => {}
+    }
+}
+
+fn mentioned_in_pattern(pattern: &Pattern, used: &mut HashSet<DefaultSymbol>) {
+    match pattern {
+        Pattern::EnumVariant(owner, _, subs) => {
+            used.insert(*owner);
+            subs.iter().for_each(|p| mentioned_in_pattern(p, used));
+        }
+        Pattern::Tuple(subs) => subs.iter().for_each(|p| mentioned_in_pattern(p, used)),
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Case {
+    Snake,
+    Camel,
+    Screaming,
+}
+
+impl Case {
+    fn matches(self, name: &str) -> bool {
+        let body = name.trim_start_matches('_');
+        match self {
+            Case::Snake => body.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            Case::Screaming => body.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+            Case::Camel => body.starts_with(|c: char| c.is_ascii_uppercase()) && !body.contains('_'),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Case::Snake => "snake_case",
+            Case::Camel => "UpperCamelCase",
+            Case::Screaming => "SCREAMING_SNAKE_CASE",
+        }
+    }
+}
+
+/// One function body: how deep control flow nests, and the magic
+/// numbers in it.
+struct BodyWalk<'c, 'a> {
+    cx: &'c LintContext<'a>,
+    depth: usize,
+    deepest: usize,
+    magic: Vec<(String, Option<SourceLocation>)>,
+}
+
+impl BodyWalk<'_, '_> {
+    fn nested(&mut self, exprs: &[&ExprRef]) {
+        self.depth += 1;
+        self.deepest = self.deepest.max(self.depth);
+        for e in exprs {
+            self.expr(e);
+        }
+        self.depth -= 1;
+    }
+
+    fn exprs<'e>(&mut self, exprs: impl IntoIterator<Item = &'e ExprRef>) {
+        for e in exprs {
+            self.expr(e);
+        }
+    }
+
+    fn stmt(&mut self, stmt_ref: &StmtRef) {
+        let Some(stmt) = self.cx.program.statement.get(stmt_ref) else {
+            return;
+        };
+        match stmt {
+            Stmt::Expression(e) | Stmt::Val(_, _, e) | Stmt::Return(Some(e)) | Stmt::Break(_, Some(e)) => self.expr(&e),
+            Stmt::Var(_, _, Some(e)) => self.expr(&e),
+            Stmt::For(_, _, start, end, body) => {
+                self.exprs([&start, &end]);
+                self.nested(&[&body]);
+            }
+            Stmt::While(_, cond, body) => {
+                self.expr(&cond);
+                self.nested(&[&body]);
+            }
+            _ => {}
+        }
+    }
+
+    fn expr(&mut self, expr_ref: &ExprRef) {
+        let Some(expr) = self.cx.program.expression.get(expr_ref) else {
+            return;
+        };
+        let literal = match expr {
+            Expr::Int64(v) => Some(v.to_string()),
+            Expr::Int8(v) => Some(v.to_string()),
+            Expr::Int16(v) => Some(v.to_string()),
+            Expr::Int32(v) => Some(v.to_string()),
+            Expr::UInt64(v) => Some(v.to_string()),
+            Expr::UInt8(v) => Some(v.to_string()),
+            Expr::UInt16(v) => Some(v.to_string()),
+            Expr::UInt32(v) => Some(v.to_string()),
+            Expr::Float64(v) => Some(format!("{:?}", v)),
+            Expr::Number(text) => Some(self.cx.name(text).to_string()),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            if !literal.parse::<f64>().is_ok_and(|v| v == 0.0 || v == 1.0 || v == 2.0) {
+                let location = self.cx.expr_location(expr_ref);
+                self.magic.push((literal, location));
+            }
+            return;
+        }
+        match expr {
+            Expr::IfElifElse(cond, then, elifs, els) => {
+                self.expr(&cond);
+                self.nested(&[&then]);
+                for (c, b) in &elifs {
+                    self.expr(c);
+                    self.nested(&[b]);
+                }
+                self.nested(&[&els]);
+            }
+            Expr::Match(scrutinee, arms) => {
+                self.expr(&scrutinee);
+                let bodies: Vec<&ExprRef> = arms.iter().flat_map(|arm| arm.guard.iter().chain([&arm.body])).collect();
+                self.nested(&bodies);
+            }
+            Expr::Closure { body, .. } => self.nested(&[&body]),
+            Expr::WithResource { resource, body, .. } => self.exprs([&resource, &body]),
+            Expr::TryCatch { body, handler, .. } => self.exprs([&body, &handler]),
+            Expr::Comprehension { element, value, source, filter, .. } => {
+                self.expr(&source);
+                let parts: Vec<&ExprRef> = filter.iter().chain([&element]).chain(&value).collect();
+                self.nested(&parts);
+            }
+            Expr::Block(stmts) => {
+                for s in &stmts {
+                    self.stmt(s);
+                }
+            }
+            Expr::Assign(l, r) | Expr::Binary(_, l, r) | Expr::Range(l, r) | Expr::With(l, r) => self.exprs([&l, &r]),
+            Expr::Unary(_, e) | Expr::FieldAccess(e, _) | Expr::TupleAccess(e, _) | Expr::Cast(e, _) => self.expr(&e),
+            Expr::ExprList(es) | Expr::ArrayLiteral(es) | Expr::TupleLiteral(es) => self.exprs(&es),
+            Expr::StructLiteral(_, fields, _) => self.exprs(fields.iter().map(|(_, e)| e)),
+            Expr::DictLiteral(entries) => {
+                for (k, v) in &entries {
+                    self.exprs([k, v]);
+                }
+            }
+            Expr::SliceAccess(e, info) => self.exprs([&e].into_iter().chain(&info.start).chain(&info.end)),
+            Expr::SliceAssign(target, info, value) => {
+                self.exprs([&target].into_iter().chain(&info.start).chain(&info.end).chain([&value]))
+            }
+            Expr::Call(_, args, _) => self.expr(&args),
+            Expr::AssociatedFunctionCall(_, _, args) | Expr::BuiltinCall(_, args) => self.exprs(&args),
+            Expr::MethodCall(receiver, _, args) | Expr::BuiltinMethodCall(receiver, _, args) => {
+                self.exprs([&receiver].into_iter().chain(&args))
+            }
+            _ => {}
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use string_interner::{DefaultSymbol, DefaultStringInterner};
-use crate::ast::{Program, ImportDecl};
+use crate::ast::{Program, ImportDecl, Visibility};
 use crate::type_checker::TypeCheckError;
 use crate::Parser;
 
@@ -287,6 +287,70 @@ impl ModuleResolver {
         self.dependency_graph.clear();
         self.resolving_stack.clear();
     }
+
+    /// Dotted paths of the modules under the search paths that `import`
+    /// would bind to `alias` and that export a `pub fn member`, sorted.
+    /// Used to suggest the import behind an unresolved `alias::member`.
+    pub fn modules_exporting(&self, alias: &str, member: &str) -> Vec<String> {
+        let mut found = Vec::new();
+        for search_path in &self.search_paths {
+            let mut files = Vec::new();
+            collect_module_files(search_path, &mut Vec::new(), &mut files);
+            for (components, file_path) in files {
+                if components.last().map(String::as_str) != Some(alias) {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&file_path) else {
+                    continue;
+                };
+                let mut interner = DefaultStringInterner::new();
+                let Ok(program) = Parser::new(&content, &mut interner).parse_program() else {
+                    continue;
+                };
+                let exported = program.function.iter().any(|f| {
+                    f.visibility == Visibility::Public && interner.resolve(f.name) == Some(member)
+                });
+                if exported {
+                    found.push(components.join("."));
+                }
+            }
+        }
+        found.sort();
+        found.dedup();
+        found
+    }
+}
+
+/// Every module file below `dir` in the layouts `find_module_file`
+/// accepts, with its module path: `a/b.t` and `a/b/mod.t` are `a.b`.
+/// Hidden directories and `target` are skipped.
+fn collect_module_files(dir: &Path, prefix: &mut Vec<String>, out: &mut Vec<(Vec<String>, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if path.is_dir() {
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            prefix.push(name);
+            collect_module_files(&path, prefix, out);
+            prefix.pop();
+        } else if let Some(stem) = name.strip_suffix(".t") {
+            let mut components = prefix.clone();
+            if stem != "mod" {
+                components.push(stem.to_string());
+            }
+            if !components.is_empty() {
+                out.push((components, path));
+            }
+        }
+    }
 }
 
 impl Default for ModuleResolver {
@@ -14,6 +14,12 @@ pub mod lexer {
     include!(concat!(env!("OUT_DIR"), "/lexer.rs"));
 }
 
+/// Largest `N` accepted in an array type `[T; N]` unless the entry
+/// point raises it with `Parser::set_max_array_len`. The checker keeps
+/// one element type per slot, so a huge `N` would exhaust memory
+/// before any diagnostic could be printed.
+pub const DEFAULT_MAX_ARRAY_LEN: usize = 1 << 16;
+
 /// Parser wrapper that owns its string interner (for backward compatibility)
 pub struct ParserWithInterner {
     input: String,
@@ -71,6 +77,11 @@ impl ParserWithInterner {
         self.get_parser().set_source_file(path);
     }
 
+    /// Forward to the inner parser's `set_max_array_len`.
+    pub fn set_max_array_len(&mut self, max: usize) {
+        self.get_parser().set_max_array_len(max);
+    }
+
     pub fn get_string_interner(&mut self) -> &mut DefaultStringInterner {
         &mut self.string_interner
     }
@@ -158,7 +169,7 @@ pub struct Parser<'a> {
     pub string_interner: &'a mut DefaultStringInterner,
     pub builtin_symbols: BuiltinFunctionSymbols,
     pub errors: Vec<ParserError>,
-    input: &'a str,
+    pub(super) input: &'a str,
     recursion_depth: u32,
     max_recursion_depth: u32,
     /// Context for format-independent token processing
@@ -192,6 +203,14 @@ pub struct Parser<'a> {
     /// module loader); test / bench / inline-string parser sites
     /// typically leave it unset.
     pub source_file: Option<String>,
+    /// Target type of the `impl` block whose methods are being parsed.
+    /// `Self` in expression position (`Self { .. }`, `Self::new(..)`)
+    /// is rewritten to this name, so every later stage sees the same
+    /// AST as for the spelled-out type.
+    pub current_impl_target: Option<DefaultSymbol>,
+    /// Largest `N` accepted in an array type `[T; N]`; see
+    /// `DEFAULT_MAX_ARRAY_LEN`.
+    pub max_array_len: usize,
 }
 
 impl<'a> Parser<'a> {
@@ -216,6 +235,8 @@ impl<'a> Parser<'a> {
             synthetic_counter: 0,
             type_aliases: HashMap::new(),
             source_file: None,
+            current_impl_target: None,
+            max_array_len: DEFAULT_MAX_ARRAY_LEN,
         }
     }
 
@@ -227,6 +248,11 @@ impl<'a> Parser<'a> {
         self.source_file = Some(path.into());
     }
 
+    /// Change the largest array length `[T; N]` may declare.
+    pub fn set_max_array_len(&mut self, max: usize) {
+        self.max_array_len = max;
+    }
+
     /// Borrow a substring of the original source. Used by
     /// `__builtin_dbg(expr)` to recover the textual form of `expr`
     /// from the byte range of its tokens.
@@ -370,6 +396,25 @@ impl<'a> Parser<'a> {
         self.errors.push(ParserError::unexpected_token(location, error_msg.to_string()));
     }
 
+    /// Range-check the text of a `Kind::Integer` token and collect an
+    /// `InvalidLiteral` error when it can't be represented. A
+    /// suffixed literal only reaches the parser as `Integer` when the
+    /// lexer found it overflowing (`256u8`); an unsuffixed one must
+    /// fit `i64::MIN ..= u64::MAX`, the widest types Number
+    /// inference can settle on.
+    pub fn check_int_literal(&mut self, text: &str) {
+        let (body, suffix) = crate::token::split_int_suffix(text);
+        let message = match (crate::token::parse_int_literal(body), suffix) {
+            (Some(value), Some(sfx)) if crate::token::typed_int_kind(value, sfx).is_some() => return,
+            (Some(_), Some(sfx)) => format!("integer literal `{text}` is out of range for {sfx}"),
+            (Some(value), None) if (i64::MIN as i128..=u64::MAX as i128).contains(&value) => return,
+            (Some(_), None) => format!("integer literal `{text}` does not fit in 64 bits"),
+            (None, _) => format!("invalid integer literal `{text}`"),
+        };
+        let location = self.current_source_location();
+        self.errors.push(ParserError::invalid_literal(location, message));
+    }
+
     /// Check condition and collect error if failed, continue parsing
     pub fn expect_or_collect(&mut self, condition: bool, error_msg: &str) -> bool {
         if !condition {
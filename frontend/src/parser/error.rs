@@ -1,3 +1,4 @@
+use crate::error_code;
 use crate::type_checker::SourceLocation;
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,7 @@ pub enum ParserErrorKind {
     RecursionLimitExceeded,
     GenericError { message: String },
     IoError { message: String },
+    InvalidLiteral { message: String },
 }
 
 #[derive(Debug)]
@@ -74,7 +76,32 @@ impl ParserError {
             location,
         }
     }
+
+    pub fn invalid_literal(location: SourceLocation, message: String) -> Self {
+        Self {
+            kind: ParserErrorKind::InvalidLiteral { message },
+            location,
+        }
+    }
+
+    /// Stable diagnostic code (see `crate::error_code`).
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
 }
+
+impl ParserErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserErrorKind::UnexpectedToken { .. } => error_code::E_UNEXPECTED_TOKEN,
+            ParserErrorKind::RecursionLimitExceeded => error_code::E_RECURSION_LIMIT,
+            ParserErrorKind::GenericError { .. } => error_code::E_PARSE_GENERIC,
+            ParserErrorKind::IoError { .. } => error_code::E_IO,
+            ParserErrorKind::InvalidLiteral { .. } => error_code::E_INVALID_LITERAL,
+        }
+    }
+}
+
 impl std::fmt::Display for ParserError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let base_message = match &self.kind {
@@ -90,6 +117,9 @@ impl std::fmt::Display for ParserError {
             ParserErrorKind::IoError { message } => {
                 format!("IO error: {}", message)
             }
+            ParserErrorKind::InvalidLiteral { message } => {
+                message.clone()
+            }
         };
 
         let mut result = base_message;
@@ -3,6 +3,7 @@ use crate::token::Kind;
 use crate::parser::core::Parser;
 use crate::parser::error::{ParserResult, ParserError};
 use crate::type_decl::TypeDecl;
+use string_interner::DefaultSymbol;
 use super::{parse_logical_expr, parse_block, parse_match_pattern};
 
 /// Parse `dict{key: value, ...}` literal.
@@ -16,10 +17,47 @@ pub fn parse_dict_literal(parser: &mut Parser) -> ParserResult<ExprRef> {
     }
     let entries = parse_dict_entries(parser, vec![])?;
     parser.skip_newlines();
+    if entries.len() == 1 && parser.peek() == Some(&Kind::For) {
+        let (key, value) = entries[0];
+        let (var, source, filter) = parse_comprehension_tail(parser)?;
+        parser.expect_err(&Kind::BraceClose)?;
+        return Ok(parser.ast_builder.comprehension_expr(key, Some(value), var, source, filter, Some(location)));
+    }
     parser.expect_err(&Kind::BraceClose)?;
     Ok(parser.ast_builder.dict_literal_expr(entries, Some(location)))
 }
 
+/// Parse the `for x in source [if cond]` tail shared by array and dict
+/// comprehensions. The closing bracket is left to the caller.
+pub fn parse_comprehension_tail(parser: &mut Parser) -> ParserResult<(DefaultSymbol, ExprRef, Option<ExprRef>)> {
+    parser.expect_err(&Kind::For)?;
+    let var = match parser.peek().cloned() {
+        Some(Kind::Identifier(name)) => {
+            parser.next();
+            parser.string_interner.get_or_intern(name)
+        }
+        other => {
+            let location = parser.current_source_location();
+            return Err(ParserError::generic_error(
+                location,
+                format!("expected identifier after `for` in comprehension, got {:?}", other),
+            ));
+        }
+    };
+    parser.expect_err(&Kind::In)?;
+    let source = super::parse_range_expr(parser)?;
+    parser.skip_newlines();
+    let filter = if parser.peek() == Some(&Kind::If) {
+        parser.next();
+        let cond = parse_logical_expr(parser)?;
+        parser.skip_newlines();
+        Some(cond)
+    } else {
+        None
+    };
+    Ok((var, source, filter))
+}
+
 fn parse_dict_entries(parser: &mut Parser, mut entries: Vec<(ExprRef, ExprRef)>) -> ParserResult<Vec<(ExprRef, ExprRef)>> {
     loop {
         parser.skip_newlines();
@@ -40,6 +78,8 @@ fn parse_dict_entries(parser: &mut Parser, mut entries: Vec<(ExprRef, ExprRef)>)
                 continue;
             }
             Some(Kind::BraceClose) => break,
+            // `dict{k: v for x in src}`: the caller parses the comprehension tail.
+            Some(Kind::For) if entries.len() == 1 => break,
             _ => {
                 parser.collect_error("Expected ',' or '}' in dict literal");
                 break;
@@ -132,26 +172,60 @@ fn parse_if_val(parser: &mut Parser) -> ParserResult<ExprRef> {
     Ok(match_expr)
 }
 
-/// Parse `with allocator = expr { body }`.
+/// Parse `with allocator = expr { body }` or the resource form
+/// `with name = expr { body }`.
 pub fn parse_with(parser: &mut Parser) -> ParserResult<ExprRef> {
     let location = parser.current_source_location();
-    match parser.peek() {
+    let resource_name = match parser.peek() {
         Some(Kind::Identifier(name)) if name.as_str() == "allocator" => {
             parser.next();
+            None
+        }
+        Some(Kind::Identifier(name)) => {
+            let name = name.to_string();
+            parser.next();
+            Some(parser.string_interner.get_or_intern(name))
         }
         other => {
             let other_cloned = other.cloned();
             return Err(ParserError::generic_error(
                 location,
-                format!("expected `allocator` after `with`, found {:?}", other_cloned),
+                format!("expected `allocator` or a binding name after `with`, found {:?}", other_cloned),
             ));
         }
-    }
+    };
     parser.expect_err(&Kind::Equal)?;
     parser.push_context(crate::parser::core::ParseContext::Condition);
-    let allocator_expr = parse_logical_expr(parser)?;
+    let value_expr = parse_logical_expr(parser)?;
     parser.pop_context();
     let body = parse_block(parser)?;
     let location = parser.current_source_location();
-    Ok(parser.ast_builder.with_expr(allocator_expr, body, Some(location)))
+    match resource_name {
+        Some(name) => Ok(parser.ast_builder.with_resource_expr(name, value_expr, body, Some(location))),
+        None => Ok(parser.ast_builder.with_expr(value_expr, body, Some(location))),
+    }
+}
+
+/// Parse `try { body } catch name { handler }`; the `try` keyword has
+/// already been consumed.
+pub fn parse_try(parser: &mut Parser) -> ParserResult<ExprRef> {
+    let location = parser.current_source_location();
+    let body = parse_block(parser)?;
+    parser.expect_err(&Kind::Catch)?;
+    let error = match parser.peek() {
+        Some(Kind::Identifier(name)) => {
+            let name = name.to_string();
+            parser.next();
+            parser.string_interner.get_or_intern(name)
+        }
+        other => {
+            let other_cloned = other.cloned();
+            return Err(ParserError::generic_error(
+                parser.current_source_location(),
+                format!("expected a binding name after `catch`, found {:?}", other_cloned),
+            ));
+        }
+    };
+    let handler = parse_block(parser)?;
+    Ok(parser.ast_builder.try_catch_expr(body, error, handler, Some(location)))
 }
@@ -124,6 +124,7 @@ fn parse_pattern_literal(parser: &mut Parser) -> ParserResult<Option<crate::ast:
         Some(Kind::Integer(s)) => {
             let s_copy = s.to_string();
             let location = parser.current_source_location();
+            parser.check_int_literal(&s_copy);
             parser.next();
             let sym = parser.string_interner.get_or_intern(s_copy);
             parser.ast_builder.number_expr(sym, Some(location))
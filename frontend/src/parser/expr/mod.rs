@@ -1,7 +1,7 @@
 use crate::ast::*;
 use crate::token::Kind;
 use super::core::Parser;
-use crate::parser::error::{ParserResult, ParserError};
+use crate::parser::error::{ParserResult, ParserError, ParserErrorKind};
 
 
 mod match_;
@@ -50,6 +50,12 @@ impl<'a> Parser<'a> {
         }
         
         let lhs = parse_range_expr(self);
+        // Past the recursion limit there is nothing sensible to recover
+        // into, so that error stops the parse instead of becoming a
+        // dummy expression.
+        if matches!(&lhs, Err(ParserError { kind: ParserErrorKind::RecursionLimitExceeded, .. })) {
+            return lhs;
+        }
         if lhs.is_ok() {
             return match self.peek() {
                 Some(Kind::Equal)
@@ -117,12 +123,9 @@ pub fn parse_assign(parser: &mut Parser, mut lhs: ExprRef) -> ParserResult<ExprR
             if let Some(Expr::SliceAccess(object, slice_info)) =
                 parser.ast_builder.expr_pool.get(&lhs)
             {
-                let start = slice_info.start;
-                let end = slice_info.end;
                 lhs = parser.ast_builder.slice_assign_expr(
                     object,
-                    start,
-                    end,
+                    slice_info,
                     combined,
                     Some(location),
                 );
@@ -141,10 +144,7 @@ pub fn parse_assign(parser: &mut Parser, mut lhs: ExprRef) -> ParserResult<ExprR
 
                 // Check if lhs is a SliceAccess expression and convert to SliceAssign
                 if let Some(Expr::SliceAccess(object, slice_info)) = parser.ast_builder.expr_pool.get(&lhs) {
-                    let object = object;
-                    let start = slice_info.start;
-                    let end = slice_info.end;
-                    lhs = parser.ast_builder.slice_assign_expr(object, start, end, new_rhs, Some(location));
+                    lhs = parser.ast_builder.slice_assign_expr(object, slice_info, new_rhs, Some(location));
                 } else {
                     lhs = parser.ast_builder.assign_expr(lhs, new_rhs, Some(location));
                 }
@@ -214,6 +214,7 @@ pub fn parse_block_impl(parser: &mut Parser, mut statements: Vec<StmtRef>) -> Pa
                 }
                 statements.push(stmt);
             }
+            Err(err) if matches!(err.kind, ParserErrorKind::RecursionLimitExceeded) => return Err(err),
             Err(err) => {
                 let error_token = parser.peek().cloned();
                 parser.collect_error(&format!("expected statement in block: {:?} at token {:?}", err, error_token));
@@ -260,41 +261,23 @@ pub fn parse_range_expr(parser: &mut Parser) -> ParserResult<ExprRef> {
     }
 }
 
+/// Entry point for a non-assignment, non-range expression. Levels
+/// from here down follow the operator table in `docs/language.md`:
+/// `||` < `&&` < equality < relational < `|` < `^` < `&` < shift.
 pub fn parse_logical_expr(parser: &mut Parser) -> ParserResult<ExprRef> {
     let group = OperatorGroup {
         tokens: vec![
-            (Kind::DoubleAnd, Operator::LogicalAnd),
             (Kind::DoubleOr, Operator::LogicalOr),
         ],
-        next_precedence: parse_bitwise_or
-    };
-    parse_binary(parser, &group)
-}
-
-pub fn parse_bitwise_or(parser: &mut Parser) -> ParserResult<ExprRef> {
-    let group = OperatorGroup {
-        tokens: vec![
-            (Kind::Or, Operator::BitwiseOr),
-        ],
-        next_precedence: parse_bitwise_xor
+        next_precedence: parse_logical_and
     };
     parse_binary(parser, &group)
 }
 
-pub fn parse_bitwise_xor(parser: &mut Parser) -> ParserResult<ExprRef> {
+pub fn parse_logical_and(parser: &mut Parser) -> ParserResult<ExprRef> {
     let group = OperatorGroup {
         tokens: vec![
-            (Kind::Xor, Operator::BitwiseXor),
-        ],
-        next_precedence: parse_bitwise_and
-    };
-    parse_binary(parser, &group)
-}
-
-pub fn parse_bitwise_and(parser: &mut Parser) -> ParserResult<ExprRef> {
-    let group = OperatorGroup {
-        tokens: vec![
-            (Kind::And, Operator::BitwiseAnd),
+            (Kind::DoubleAnd, Operator::LogicalAnd),
         ],
         next_precedence: parse_equality
     };
@@ -313,7 +296,7 @@ pub fn parse_equality(parser: &mut Parser) -> ParserResult<ExprRef> {
 }
 
 pub fn parse_relational(parser: &mut Parser) -> ParserResult<ExprRef> {
-    let lhs = parse_shift(parser)?;
+    let lhs = parse_bitwise_or(parser)?;
     let op1 = match parser.peek() {
         Some(Kind::LT) => Operator::LT,
         Some(Kind::LE) => Operator::LE,
@@ -324,7 +307,7 @@ pub fn parse_relational(parser: &mut Parser) -> ParserResult<ExprRef> {
 
     let location = parser.current_source_location();
     parser.next();
-    let rhs1 = parse_shift(parser)?;
+    let rhs1 = parse_bitwise_or(parser)?;
 
     // Single comparison: no chain → plain binary expr.
     if !matches!(parser.peek(), Some(Kind::LT) | Some(Kind::LE) | Some(Kind::GT) | Some(Kind::GE)) {
@@ -366,7 +349,7 @@ pub fn parse_relational(parser: &mut Parser) -> ParserResult<ExprRef> {
             _ => break,
         };
         parser.next();
-        let rhs = parse_shift(parser)?;
+        let rhs = parse_bitwise_or(parser)?;
 
         let counter = parser.synthetic_counter;
         parser.synthetic_counter += 1;
@@ -416,6 +399,36 @@ pub fn parse_relational(parser: &mut Parser) -> ParserResult<ExprRef> {
     Ok(parser.ast_builder.block_expr(stmts, Some(location)))
 }
 
+pub fn parse_bitwise_or(parser: &mut Parser) -> ParserResult<ExprRef> {
+    let group = OperatorGroup {
+        tokens: vec![
+            (Kind::Or, Operator::BitwiseOr),
+        ],
+        next_precedence: parse_bitwise_xor
+    };
+    parse_binary(parser, &group)
+}
+
+pub fn parse_bitwise_xor(parser: &mut Parser) -> ParserResult<ExprRef> {
+    let group = OperatorGroup {
+        tokens: vec![
+            (Kind::Xor, Operator::BitwiseXor),
+        ],
+        next_precedence: parse_bitwise_and
+    };
+    parse_binary(parser, &group)
+}
+
+pub fn parse_bitwise_and(parser: &mut Parser) -> ParserResult<ExprRef> {
+    let group = OperatorGroup {
+        tokens: vec![
+            (Kind::And, Operator::BitwiseAnd),
+        ],
+        next_precedence: parse_shift
+    };
+    parse_binary(parser, &group)
+}
+
 pub fn parse_shift(parser: &mut Parser) -> ParserResult<ExprRef> {
     let group = OperatorGroup {
         tokens: vec![
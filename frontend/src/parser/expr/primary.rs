@@ -2,11 +2,12 @@ use crate::ast::*;
 use crate::token::{Kind, StringPart};
 use crate::parser::core::Parser;
 use crate::parser::error::{ParserResult, ParserError};
+use crate::type_checker::SourceLocation;
 use string_interner::DefaultSymbol;
 
 use super::{
     parse_logical_expr, parse_block, parse_expr_list,
-    parse_if, parse_with, parse_dict_literal, parse_match,
+    parse_if, parse_with, parse_try, parse_dict_literal, parse_match,
     parse_postfix, try_intercept_parser_macro,
 };
 
@@ -184,22 +185,40 @@ fn parse_primary_impl(parser: &mut Parser) -> ParserResult<ExprRef> {
     }
     match parser.peek() {
         Some(Kind::ParenOpen) => parse_tuple_or_grouped_expr(parser),
-        Some(ref kind) if kind.is_keyword() && !matches!(kind, Kind::True | Kind::False | Kind::Null | Kind::If | Kind::Dict | Kind::Self_ | Kind::With | Kind::Ambient | Kind::Match) => {
+        Some(ref kind) if kind.is_keyword() && !matches!(kind, Kind::True | Kind::False | Kind::Null | Kind::If | Kind::Dict | Kind::Self_ | Kind::With | Kind::Try | Kind::Ambient | Kind::Match | Kind::Loop) => {
             let location = parser.current_source_location();
             Err(ParserError::generic_error(location, "parse_primary_impl: reserved keyword cannot be used as identifier".to_string()))
         }
         Some(Kind::Identifier(s)) => {
             let s = s.to_string();
             let s = parser.string_interner.get_or_intern(s);
+            let start = parser.current_source_location();
             parser.next();
-            parse_primary_after_identifier(parser, s)
+            parse_primary_after_identifier(parser, s, start)
+        }
+        Some(Kind::Self_) => {
+            // `Self { .. }` / `Self::f(..)` name the impl target.
+            let Some(target) = parser.current_impl_target else {
+                let location = parser.current_source_location();
+                return Err(ParserError::generic_error(location, "`Self` is only valid inside an impl block".to_string()));
+            };
+            let start = parser.current_source_location();
+            parser.next();
+            parse_primary_after_identifier(parser, target, start)
         }
         _ => parse_primary_atom_or_form(parser),
     }
 }
 
-/// Parse what follows an identifier head in primary position.
-fn parse_primary_after_identifier(parser: &mut Parser, name: DefaultSymbol) -> ParserResult<ExprRef> {
+/// Parse what follows an identifier head in primary position. `start`
+/// is where the identifier itself begins; a bare identifier is located
+/// there rather than at the token after it.
+fn parse_primary_after_identifier(parser: &mut Parser, name: DefaultSymbol, start: SourceLocation) -> ParserResult<ExprRef> {
+    // Turbofish: `f::<T>(..)` / `Name::<T> { .. }`.
+    if parser.peek() == Some(&Kind::DoubleColon) && parser.peek_n(1) == Some(&Kind::LT) {
+        parser.next();
+        return parse_explicit_type_args(parser, name);
+    }
     if parser.peek() == Some(&Kind::DoubleColon) {
         let mut qualified_path = vec![name];
         while parser.peek() == Some(&Kind::DoubleColon) {
@@ -236,6 +255,53 @@ fn parse_primary_after_identifier(parser: &mut Parser, name: DefaultSymbol) -> P
         };
     }
 
+    // `is<T>(x)` / `downcast<T>(x)` / `from_dict<T>(d)`: the only call
+    // forms that take an explicit type argument, so these names
+    // directly followed by `<` always start one.
+    if parser.peek() == Some(&Kind::LT)
+        && (name == parser.builtin_symbols.is
+            || name == parser.builtin_symbols.downcast
+            || name == parser.builtin_symbols.from_dict)
+    {
+        let location = parser.current_source_location();
+        parser.next();
+        let target = parser.parse_type_declaration()?;
+        parser.expect_err(&Kind::GT)?;
+        parser.expect_err(&Kind::ParenOpen)?;
+        let args = parse_expr_list(parser, vec![])?;
+        parser.expect_err(&Kind::ParenClose)?;
+        let func = if name == parser.builtin_symbols.is {
+            BuiltinFunction::Is(target)
+        } else if name == parser.builtin_symbols.downcast {
+            BuiltinFunction::Downcast(target)
+        } else {
+            BuiltinFunction::FromDict(target)
+        };
+        return Ok(parser.ast_builder.builtin_call_expr(func, args, Some(location)));
+    }
+
+    // `lua!("raw code")`: inline Lua for the Lua backend. The type
+    // checker insists on a single plain string literal so a code
+    // generator can paste it verbatim.
+    if name == parser.builtin_symbols.lua
+        && parser.peek() == Some(&Kind::Exclamation)
+        && parser.peek_n(1) == Some(&Kind::ParenOpen)
+    {
+        let location = parser.current_source_location();
+        parser.next();
+        parser.next();
+        let args = parse_expr_list(parser, vec![])?;
+        parser.expect_err(&Kind::ParenClose)?;
+        return Ok(parser.ast_builder.builtin_call_expr(BuiltinFunction::InlineLua, args, Some(location)));
+    }
+
+    // Plain form: `f<T>(..)` / `Name<T> { .. }`. Only taken when the
+    // tokens up to the matching `>` can form a type list and a call or
+    // struct literal follows, so `a < b` stays a comparison.
+    if parser.peek() == Some(&Kind::LT) && looks_like_type_args(parser) {
+        return parse_explicit_type_args(parser, name);
+    }
+
     let struct_literal_allowed = parser.is_struct_literal_allowed();
     match parser.peek() {
         Some(Kind::ParenOpen) => {
@@ -255,7 +321,7 @@ fn parse_primary_after_identifier(parser: &mut Parser, name: DefaultSymbol) -> P
         Some(Kind::BracketOpen) => {
             let location = parser.current_source_location();
             parser.next();
-            let object_ref = parser.ast_builder.identifier_expr(name, None);
+            let object_ref = parser.ast_builder.identifier_expr(name, Some(start));
             parse_bracket_access(parser, object_ref, location)
         }
         Some(Kind::BraceOpen) if struct_literal_allowed => {
@@ -265,13 +331,83 @@ fn parse_primary_after_identifier(parser: &mut Parser, name: DefaultSymbol) -> P
             parser.expect_err(&Kind::BraceClose)?;
             Ok(parser.ast_builder.struct_literal_expr(name, fields, Some(location)))
         }
-        _ => {
-            let location = parser.current_source_location();
-            Ok(parser.ast_builder.identifier_expr(name, Some(location)))
+        _ => Ok(parser.ast_builder.identifier_expr(name, Some(start))),
+    }
+}
+
+/// Lookahead for the plain `name<T, ..>(` / `name<T, ..> {` form,
+/// starting at the `<`. Nothing is consumed.
+fn looks_like_type_args(parser: &mut Parser) -> bool {
+    let mut depth = 0usize;
+    let mut pos = 0;
+    loop {
+        let closes = match parser.peek_n(pos) {
+            Some(Kind::LT) => {
+                depth += 1;
+                0
+            }
+            Some(Kind::GT) => 1,
+            Some(Kind::RightShift) => 2,
+            Some(
+                Kind::Identifier(_) | Kind::Comma | Kind::DoubleColon | Kind::BracketOpen | Kind::BracketClose
+                | Kind::Semicolon | Kind::UInt64(_) | Kind::Integer(_) | Kind::And | Kind::Mut
+                | Kind::Bool | Kind::U64 | Kind::I64 | Kind::U32 | Kind::I32 | Kind::U16 | Kind::I16
+                | Kind::U8 | Kind::I8 | Kind::F64 | Kind::Str | Kind::Ptr | Kind::Dict,
+            ) => 0,
+            _ => return false,
+        };
+        if closes > depth {
+            return false;
+        }
+        depth -= closes;
+        pos += 1;
+        if depth == 0 {
+            return match parser.peek_n(pos) {
+                Some(Kind::ParenOpen) => true,
+                Some(Kind::BraceOpen) => parser.is_struct_literal_allowed(),
+                _ => false,
+            };
         }
     }
 }
 
+/// Parse `<T, ..>` and the call or struct literal it belongs to,
+/// keeping the type list on the resulting expression.
+fn parse_explicit_type_args(parser: &mut Parser, name: DefaultSymbol) -> ParserResult<ExprRef> {
+    parser.expect_err(&Kind::LT)?;
+    let mut type_args = vec![parser.parse_type_declaration()?];
+    while parser.peek() == Some(&Kind::Comma) {
+        parser.next();
+        type_args.push(parser.parse_type_declaration()?);
+    }
+    parser.expect_err(&Kind::GT)?;
+    let location = parser.current_source_location();
+    match parser.peek() {
+        Some(Kind::ParenOpen) => {
+            if parser.builtin_symbols.symbol_to_builtin(name).is_some() {
+                return Err(ParserError::generic_error(
+                    location,
+                    format!("builtin function `{}` takes no type arguments", parser.string_interner.resolve(name).unwrap_or("?")),
+                ));
+            }
+            parser.next();
+            let args = parse_expr_list(parser, vec![])?;
+            parser.expect_err(&Kind::ParenClose)?;
+            Ok(parser.ast_builder.generic_call_expr(name, type_args, args, Some(location)))
+        }
+        Some(Kind::BraceOpen) => {
+            parser.next();
+            let fields = parse_struct_literal_fields(parser, vec![])?;
+            parser.expect_err(&Kind::BraceClose)?;
+            Ok(parser.ast_builder.generic_struct_literal_expr(name, type_args, fields, Some(location)))
+        }
+        _ => Err(ParserError::generic_error(
+            location,
+            "expected `(` or `{` after explicit type arguments".to_string(),
+        )),
+    }
+}
+
 /// Parse atomic literal or structured form.
 fn parse_primary_atom_or_form(parser: &mut Parser) -> ParserResult<ExprRef> {
     let x = parser.peek();
@@ -330,9 +466,18 @@ fn parse_primary_atom_or_form(parser: &mut Parser) -> ParserResult<ExprRef> {
             let s = parser.string_interner.get_or_intern(s_copy);
             parser.ast_builder.string_expr(s, Some(location))
         }
+        Some(Kind::Bytes(b)) => {
+            // One Latin-1 char per byte so the payload round-trips
+            // through the string interner (see `Expr::Bytes`).
+            let text: String = b.iter().map(|&byte| byte as char).collect();
+            let location = parser.current_source_location();
+            let s = parser.string_interner.get_or_intern(text);
+            parser.ast_builder.bytes_expr(s, Some(location))
+        }
         Some(Kind::Integer(s)) => {
             let s_copy = s.to_string();
             let location = parser.current_source_location();
+            parser.check_int_literal(&s_copy);
             let s = parser.string_interner.get_or_intern(s_copy);
             parser.ast_builder.number_expr(s, Some(location))
         }
@@ -357,6 +502,11 @@ fn parse_primary_keyword_form(parser: &mut Parser) -> ParserResult<ExprRef> {
             let location = parser.current_source_location();
             parser.next();
             let elements = parse_array_elements(parser, vec![])?;
+            if elements.len() == 1 && parser.peek() == Some(&Kind::For) {
+                let (var, source, filter) = super::control::parse_comprehension_tail(parser)?;
+                parser.expect_err(&Kind::BracketClose)?;
+                return Ok(parser.ast_builder.comprehension_expr(elements[0], None, var, source, filter, Some(location)));
+            }
             parser.expect_err(&Kind::BracketClose)?;
             Ok(parser.ast_builder.array_literal_expr(elements, Some(location)))
         }
@@ -368,6 +518,10 @@ fn parse_primary_keyword_form(parser: &mut Parser) -> ParserResult<ExprRef> {
             parser.next();
             parse_with(parser)
         }
+        Some(Kind::Try) => {
+            parser.next();
+            parse_try(parser)
+        }
         Some(Kind::Ambient) => {
             let location = parser.current_source_location();
             parser.next();
@@ -385,6 +539,18 @@ fn parse_primary_keyword_form(parser: &mut Parser) -> ParserResult<ExprRef> {
             parser.next();
             parse_match(parser)
         }
+        // `loop` / `@label: loop` as an expression: the loop's value
+        // is whatever its `break value` carries.
+        Some(Kind::Loop) => {
+            let location = parser.current_source_location();
+            let loop_stmt = crate::parser::stmt::parse_loop_with_label(parser, None)?;
+            Ok(parser.ast_builder.block_expr(vec![loop_stmt], Some(location)))
+        }
+        Some(Kind::At) => {
+            let location = parser.current_source_location();
+            let loop_stmt = crate::parser::stmt::parse_labelled_loop(parser)?;
+            Ok(parser.ast_builder.block_expr(vec![loop_stmt], Some(location)))
+        }
         _ => {
             let x_cloned = x.cloned();
             parser.collect_error(&format!("unexpected token in primary expression: {:?}", x_cloned));
@@ -436,6 +602,11 @@ pub fn parse_array_elements(parser: &mut Parser, mut elements: Vec<ExprRef>) ->
                 parser.exit_nested_structure(false);
                 return Ok(elements);
             }
+            // `[elem for x in src]`: the caller parses the comprehension tail.
+            Some(Kind::For) if elements.len() == 1 => {
+                parser.exit_nested_structure(false);
+                return Ok(elements);
+            }
             Some(Kind::NewLine) => {
                 parser.skip_newlines();
                 match parser.peek() {
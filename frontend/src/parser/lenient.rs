@@ -0,0 +1,424 @@
+//! Error-tolerant parsing for editor completion.
+//!
+//! `Parser::parse_program_lenient` never fails. When the ordinary parse
+//! gives up, part of the source is blanked out and the program is
+//! parsed again. Blanking replaces every byte but `\n` with a space, so
+//! offsets and line numbers of everything else stay put. The first
+//! thing blanked is the line holding the last token before the error.
+//! When that does not help, the whole top-level item goes. What
+//! survives is returned with the errors met on the way.
+//!
+//! `LenientProgram::context_at` then tells a completion provider what
+//! belongs at a cursor. It reads the raw token stream, so it also works
+//! inside the code that had to be blanked. It types a `receiver.` from
+//! the bindings and struct fields of the recovered AST.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use string_interner::DefaultSymbol;
+
+use crate::ast::*;
+use crate::token::Kind;
+use crate::token_stream::lex_with_spans;
+use crate::type_decl::TypeDecl;
+use super::core::Parser;
+use super::error::ParserError;
+
+/// Re-parses allowed before the rest of the source is dropped at once.
+const MAX_REPAIRS: usize = 256;
+
+/// Result of [`Parser::parse_program_lenient`].
+#[derive(Debug)]
+pub struct LenientProgram {
+    /// Whatever parsed once the broken parts were blanked out.
+    pub program: Program,
+    /// The error that made each re-parse necessary, in the order met.
+    pub errors: Vec<ParserError>,
+    /// Byte ranges of the original source left out of `program`.
+    pub skipped: Vec<Range<usize>>,
+    tokens: Vec<(Kind, Range<usize>)>,
+    bindings: Vec<Binding>,
+    struct_fields: StructFields,
+}
+
+/// What a completion at some offset should offer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprContext {
+    /// After `receiver.`: fields and methods of the receiver's type.
+    /// `TypeDecl::Unknown` when declarations alone do not tell.
+    FieldAccessOf(TypeDecl),
+    /// Inside the argument list of a call to `function` (a function,
+    /// method or associated function), at argument `arg_index`.
+    CallArgsOf { function: String, arg_index: usize },
+    /// After `Name::`: members of a module, enum or type.
+    PathOf(String),
+    /// Where a type is written: after `->`, or after the `:` of a
+    /// binding, parameter or struct field.
+    TypeAnnotation,
+    /// Anywhere else in code: names in scope and keywords.
+    Expression,
+    /// Inside a comment or string literal.
+    Nothing,
+}
+
+/// Field names and types of each declared struct.
+type StructFields = HashMap<DefaultSymbol, Vec<(String, TypeDecl)>>;
+
+/// A parameter or `val` / `var` the recovered AST declares.
+#[derive(Debug)]
+struct Binding {
+    name: String,
+    ty: TypeDecl,
+    /// Offset the name is visible from.
+    from: usize,
+    /// Span of the function or method that declares it.
+    scope: Range<usize>,
+}
+
+impl<'a> Parser<'a> {
+    /// Parse the whole input, skipping what cannot be parsed instead of
+    /// failing. See the module docs for how the skipping works.
+    pub fn parse_program_lenient(&mut self) -> LenientProgram {
+        let source = self.input;
+        let items = item_starts(source);
+        let mut text = source.to_string();
+        let mut errors = Vec::new();
+        let mut skipped: Vec<Range<usize>> = Vec::new();
+        let program = loop {
+            let mut attempt = Parser::new(&text, &mut *self.string_interner);
+            attempt.source_file = self.source_file.clone();
+            attempt.max_array_len = self.max_array_len;
+            let error = match attempt.parse_program() {
+                // Errors the parser recovered from itself can still
+                // leave a mangled tree (a body that swallowed the next
+                // function), so they get the same treatment.
+                Ok(program) if attempt.errors.is_empty() => break program,
+                Ok(_) => attempt.errors.swap_remove(0),
+                Err(error) => error,
+            };
+            let at = (error.location.offset as usize).min(text.len());
+            errors.push(error);
+            let mut range = if skipped.len() < MAX_REPAIRS {
+                region_to_skip(&text, &items, at)
+            } else {
+                0..text.len()
+            };
+            if is_blank(&text[range.clone()]) {
+                if is_blank(&text) {
+                    drop(attempt);
+                    break Parser::new("", &mut *self.string_interner)
+                        .parse_program()
+                        .expect("an empty program parses");
+                }
+                range = 0..text.len();
+            }
+            blank(&mut text, range.clone());
+            skipped.push(range);
+        };
+        let (bindings, struct_fields) = collect_declarations(&program, self.string_interner);
+        LenientProgram {
+            program,
+            errors,
+            skipped,
+            tokens: lex_with_spans(source),
+            bindings,
+            struct_fields,
+        }
+    }
+}
+
+impl LenientProgram {
+    /// What kind of completion fits at byte `offset`. An identifier
+    /// ending at `offset` is taken to be the word being typed.
+    pub fn context_at(&self, offset: usize) -> ExprContext {
+        let inside_text = self.tokens.iter().any(|(kind, range)| match kind {
+            Kind::Comment(_) => range.start < offset && offset <= range.end,
+            Kind::String(_) | Kind::InterpolatedString(_) | Kind::Bytes(_) => {
+                range.start < offset && offset < range.end
+            }
+            _ => false,
+        });
+        if inside_text {
+            return ExprContext::Nothing;
+        }
+
+        let mut before: Vec<&(Kind, Range<usize>)> = self
+            .tokens
+            .iter()
+            .take_while(|(_, range)| range.end <= offset)
+            .filter(|(kind, _)| !matches!(kind, Kind::NewLine | Kind::Comment(_)))
+            .collect();
+        if let Some((Kind::Identifier(_), range)) = before.last()
+            && range.end == offset
+        {
+            before.pop();
+        }
+        let kind_at = |back: usize| before.len().checked_sub(back + 1).map(|i| &before[i].0);
+
+        match kind_at(0) {
+            Some(Kind::Dot) => {
+                return ExprContext::FieldAccessOf(self.receiver_type(&before[..before.len() - 1], offset));
+            }
+            Some(Kind::DoubleColon) => {
+                if let Some(Kind::Identifier(name)) = kind_at(1) {
+                    return ExprContext::PathOf(name.clone());
+                }
+            }
+            Some(Kind::Arrow) => return ExprContext::TypeAnnotation,
+            Some(Kind::Colon) => {
+                if let (Some(Kind::Identifier(_)), Some(Kind::Val | Kind::Var)) = (kind_at(1), kind_at(2)) {
+                    return ExprContext::TypeAnnotation;
+                }
+            }
+            _ => {}
+        }
+
+        let Some(open) = innermost_open(&before) else {
+            return ExprContext::Expression;
+        };
+        let opener = |back: usize| open.checked_sub(back).map(|i| &before[i].0);
+        match (opener(0), opener(1), opener(2)) {
+            (Some(Kind::ParenOpen), _, _) if kind_at(0) == Some(&Kind::Colon) => ExprContext::TypeAnnotation,
+            (Some(Kind::BraceOpen), Some(Kind::Identifier(_)), Some(Kind::Struct))
+                if kind_at(0) == Some(&Kind::Colon) =>
+            {
+                ExprContext::TypeAnnotation
+            }
+            (Some(Kind::ParenOpen), Some(Kind::Identifier(name)), before_name)
+                if before_name != Some(&Kind::Function) =>
+            {
+                let arg_index = before[open + 1..]
+                    .iter()
+                    .scan(0i32, |depth, (kind, _)| {
+                        match kind {
+                            Kind::ParenOpen | Kind::BracketOpen | Kind::BraceOpen => *depth += 1,
+                            Kind::ParenClose | Kind::BracketClose | Kind::BraceClose => *depth -= 1,
+                            _ => {}
+                        }
+                        Some(*depth == 0 && *kind == Kind::Comma)
+                    })
+                    .filter(|top_level_comma| *top_level_comma)
+                    .count();
+                ExprContext::CallArgsOf { function: name.clone(), arg_index }
+            }
+            _ => ExprContext::Expression,
+        }
+    }
+
+    /// Type of the `a.b.c` chain ending `tokens`, as far as the
+    /// declarations go.
+    fn receiver_type(&self, tokens: &[&(Kind, Range<usize>)], offset: usize) -> TypeDecl {
+        let mut path = Vec::new();
+        let mut rest = tokens;
+        while let [init @ .., (Kind::Identifier(name), _)] = rest {
+            path.push(name.as_str());
+            match init {
+                [init @ .., (Kind::Dot, _)] => rest = init,
+                _ => break,
+            }
+        }
+        let Some((root, fields)) = path.split_last() else {
+            return TypeDecl::Unknown;
+        };
+        let mut ty = self
+            .bindings
+            .iter()
+            .filter(|b| b.name == *root && b.from <= offset && b.scope.contains(&offset))
+            .max_by_key(|b| b.from)
+            .map_or(TypeDecl::Unknown, |b| b.ty.clone());
+        for field in fields.iter().rev() {
+            ty = self.field_type(&ty, field).unwrap_or(TypeDecl::Unknown);
+        }
+        match ty {
+            // Annotations name a struct the same way they name an enum
+            // or alias; settle it now that the declarations are known.
+            TypeDecl::Identifier(name) if self.struct_fields.contains_key(&name) => {
+                TypeDecl::Struct(name, vec![])
+            }
+            ty => ty,
+        }
+    }
+
+    fn field_type(&self, ty: &TypeDecl, field: &str) -> Option<TypeDecl> {
+        let name = match ty {
+            TypeDecl::Struct(name, _) | TypeDecl::Identifier(name) => name,
+            TypeDecl::Ref { inner, .. } => return self.field_type(inner, field),
+            _ => return None,
+        };
+        let fields = self.struct_fields.get(name)?;
+        fields.iter().find(|(f, _)| f == field).map(|(_, ty)| ty.clone())
+    }
+}
+
+/// Offsets of the keywords that start a top-level item: item keywords
+/// at the very beginning of a line.
+fn item_starts(source: &str) -> Vec<usize> {
+    lex_with_spans(source)
+        .into_iter()
+        .filter(|(kind, range)| {
+            matches!(
+                kind,
+                Kind::Function | Kind::Extern | Kind::Public | Kind::Struct | Kind::Enum | Kind::Impl
+                    | Kind::Trait | Kind::Const | Kind::Type | Kind::Import | Kind::Package | Kind::At
+            ) && (range.start == 0 || source.as_bytes()[range.start - 1] == b'\n')
+        })
+        .map(|(_, range)| range.start)
+        .collect()
+}
+
+/// What to blank after a parse error at `at`: the line of the last
+/// token before it within the same item, or the error's own line.
+/// Brackets left open by an earlier item are blamed on that item.
+fn region_to_skip(text: &str, items: &[usize], at: usize) -> Range<usize> {
+    let tokens = lex_with_spans(text);
+    let item_of = |offset: usize| {
+        let i = items.partition_point(|&start| start <= offset);
+        let start = if i == 0 { 0 } else { items[i - 1] };
+        start..items.get(i).copied().unwrap_or(text.len())
+    };
+    let unbalanced = items
+        .iter()
+        .take_while(|&&start| start <= at)
+        .map(|&start| item_of(start))
+        .find(|item| bracket_depth(&tokens, item) != 0);
+    let item = unbalanced.unwrap_or_else(|| item_of(at));
+    let limit = at.min(item.end);
+    let culprit = tokens
+        .iter()
+        .rev()
+        .filter(|(kind, _)| !matches!(kind, Kind::NewLine | Kind::Comment(_)))
+        .find(|(_, range)| range.start < limit)
+        .filter(|(_, range)| range.start >= item.start)
+        .map_or(at.min(text.len().saturating_sub(1)), |(_, range)| range.start);
+    let line = line_around(text, culprit);
+    if is_blank(&text[line.clone()]) { item } else { line }
+}
+
+fn bracket_depth(tokens: &[(Kind, Range<usize>)], item: &Range<usize>) -> i32 {
+    tokens
+        .iter()
+        .filter(|(_, range)| item.contains(&range.start))
+        .map(|(kind, _)| match kind {
+            Kind::ParenOpen | Kind::BracketOpen | Kind::BraceOpen => 1,
+            Kind::ParenClose | Kind::BracketClose | Kind::BraceClose => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn line_around(text: &str, offset: usize) -> Range<usize> {
+    let start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+    start..end
+}
+
+fn is_blank(text: &str) -> bool {
+    text.bytes().all(|b| b.is_ascii_whitespace())
+}
+
+fn blank(text: &mut String, range: Range<usize>) {
+    let blanked: String = text[range.clone()]
+        .bytes()
+        .map(|b| if b == b'\n' { '\n' } else { ' ' })
+        .collect();
+    text.replace_range(range, &blanked);
+}
+
+/// Index of the innermost bracket still open at the end of `tokens`.
+fn innermost_open(tokens: &[&(Kind, Range<usize>)]) -> Option<usize> {
+    let mut depth = 0;
+    for (i, (kind, _)) in tokens.iter().enumerate().rev() {
+        match kind {
+            Kind::ParenClose | Kind::BracketClose | Kind::BraceClose => depth += 1,
+            Kind::ParenOpen | Kind::BracketOpen | Kind::BraceOpen if depth == 0 => return Some(i),
+            Kind::ParenOpen | Kind::BracketOpen | Kind::BraceOpen => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parameters, `self`, `val` / `var` bindings and struct fields of
+/// `program`, with names resolved so lookups work on token text.
+fn collect_declarations(
+    program: &Program,
+    interner: &string_interner::DefaultStringInterner,
+) -> (Vec<Binding>, StructFields) {
+    let name_of = |sym: DefaultSymbol| interner.resolve(sym).unwrap_or_default().to_string();
+    let mut bindings = Vec::new();
+    let mut scopes: Vec<Range<usize>> = Vec::new();
+    let mut returns: HashMap<(Option<DefaultSymbol>, DefaultSymbol), TypeDecl> = HashMap::new();
+    let mut struct_fields = HashMap::new();
+
+    let add_params = |bindings: &mut Vec<Binding>, node: &Node, params: &ParameterList| {
+        let scope = node.start..node.end;
+        for (name, ty) in params {
+            bindings.push(Binding { name: name_of(*name), ty: ty.clone(), from: node.start, scope: scope.clone() });
+        }
+        scope
+    };
+    for function in &program.function {
+        scopes.push(add_params(&mut bindings, &function.node, &function.parameter));
+        if let Some(ty) = &function.return_type {
+            returns.insert((None, function.name), ty.clone());
+        }
+    }
+    let stmts = || (0..program.statement.len()).filter_map(|i| program.statement.get(&StmtRef(i as u32)).map(|s| (i, s)));
+    for (_, stmt) in stmts() {
+        match stmt {
+            Stmt::StructDecl { name, fields, .. } => {
+                let fields = fields.into_iter().map(|f| (f.name, f.type_decl)).collect();
+                struct_fields.insert(name, fields);
+            }
+            Stmt::ImplBlock { target_type, target_type_args, methods, .. } => {
+                let self_ty = TypeDecl::Struct(target_type, target_type_args);
+                for method in methods {
+                    let scope = add_params(&mut bindings, &method.node, &method.parameter);
+                    if method.has_self_param {
+                        bindings.push(Binding {
+                            name: "self".to_string(),
+                            ty: self_ty.clone(),
+                            from: method.node.start,
+                            scope: scope.clone(),
+                        });
+                    }
+                    scopes.push(scope);
+                    if let Some(ty) = &method.return_type {
+                        let ty = if *ty == TypeDecl::Self_ { self_ty.clone() } else { ty.clone() };
+                        returns.insert((Some(target_type), method.name), ty);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let value_type = |value: &ExprRef| match program.expression.get(value)? {
+        Expr::StructLiteral(name, _, args) => Some(TypeDecl::Struct(name, args)),
+        Expr::AssociatedFunctionCall(target, function, _) => returns.get(&(Some(target), function)).cloned(),
+        Expr::Call(function, _, _) => returns.get(&(None, function)).cloned(),
+        Expr::String(_) => Some(TypeDecl::String),
+        _ => None,
+    };
+    for (i, stmt) in stmts() {
+        let (name, declared, value) = match stmt {
+            Stmt::Val(name, declared, value) => (name, declared, Some(value)),
+            Stmt::Var(name, declared, value) => (name, declared, value),
+            _ => continue,
+        };
+        let Some(location) = program.location_pool.get_stmt_location(&StmtRef(i as u32)) else {
+            continue;
+        };
+        let from = location.offset as usize;
+        let Some(scope) = scopes.iter().find(|scope| scope.contains(&from)) else {
+            continue;
+        };
+        // An unannotated binding is recorded with `Some(Unknown)`.
+        let ty = declared
+            .filter(|ty| *ty != TypeDecl::Unknown)
+            .or_else(|| value.as_ref().and_then(value_type))
+            .unwrap_or(TypeDecl::Unknown);
+        bindings.push(Binding { name: name_of(name), ty, from, scope: scope.clone() });
+    }
+    (bindings, struct_fields)
+}
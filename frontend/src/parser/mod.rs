@@ -6,10 +6,12 @@ pub mod token_source;
 pub mod types;
 pub mod declarations;
 pub mod program_parser;
+pub mod lenient;
 
 #[cfg(test)]
 pub mod tests;
 pub mod error;
 
-pub use core::{Parser, ParserWithInterner, ParseContext};
-pub use error::{ParserError, ParserResult, MultipleParserResult};
\ No newline at end of file
+pub use core::{Parser, ParserWithInterner, ParseContext, DEFAULT_MAX_ARRAY_LEN};
+pub use error::{ParserError, ParserResult, MultipleParserResult};
+pub use lenient::{ExprContext, LenientProgram};
\ No newline at end of file
@@ -4,7 +4,7 @@ use string_interner::DefaultSymbol;
 use crate::ast::*;
 use crate::type_decl::TypeDecl;
 use crate::token::Kind;
-use crate::parser::error::{ParserErrorKind, ParserResult, MultipleParserResult};
+use crate::parser::error::{ParserError, ParserErrorKind, ParserResult, MultipleParserResult};
 use super::core::Parser;
 
 /// Map a primitive-type token to the canonical string it should be
@@ -49,6 +49,13 @@ impl<'a> Parser<'a> {
         };
         let mut def_func = vec![];
         let mut consts: Vec<ConstDecl> = vec![];
+        let mut cfg_items: Vec<CfgItem> = vec![];
+        // `@cfg(...)` seen but its item not parsed yet, together with
+        // the function / const / statement counts at that point so the
+        // next item to land can be identified.
+        let mut pending_cfg: Option<(CfgPredicate, usize, usize, usize)> = None;
+        // `@inline` / `@noinline` waiting for its `fn`.
+        let mut pending_inline: Option<InlineHint> = None;
 
         // Parse package declaration (optional, at beginning of file)
         let package_decl = if matches!(self.peek(), Some(Kind::Package)) {
@@ -64,6 +71,39 @@ impl<'a> Parser<'a> {
         }
 
         loop {
+            if let Some((predicate, funcs, const_count, stmts)) = pending_cfg.take() {
+                let stmt_len = self.ast_builder.get_stmt_pool().len();
+                let target = if def_func.len() > funcs {
+                    Some(CfgTarget::Function(funcs))
+                } else if consts.len() > const_count {
+                    Some(CfgTarget::Const(const_count))
+                } else if stmt_len > stmts {
+                    // Declarations are added after their children, so
+                    // the newest statement is the item itself.
+                    Some(CfgTarget::Decl(StmtRef((stmt_len - 1) as u32)))
+                } else {
+                    None
+                };
+                match target {
+                    Some(CfgTarget::Decl(stmt_ref))
+                        if matches!(
+                            self.ast_builder.get_stmt_pool().get(&stmt_ref),
+                            Some(Stmt::TypeAlias { .. })
+                        ) =>
+                    {
+                        // Aliases are substituted while parsing, so
+                        // they are gone before pruning could run.
+                        let location = self.current_source_location();
+                        return Err(ParserError::generic_error(
+                            location,
+                            "`@cfg` cannot be applied to a type alias".to_string(),
+                        ));
+                    }
+                    Some(target) => cfg_items.push(CfgItem { predicate, target }),
+                    None => pending_cfg = Some((predicate, funcs, const_count, stmts)),
+                }
+            }
+
             // Check for visibility modifier first
             let visibility = if matches!(self.peek(), Some(Kind::Public)) {
                 self.next(); // consume 'pub'
@@ -72,6 +112,16 @@ impl<'a> Parser<'a> {
                 Visibility::Private
             };
 
+            if pending_inline.is_some()
+                && !matches!(self.peek(), Some(Kind::Function) | Some(Kind::At) | Some(Kind::NewLine))
+            {
+                let location = self.current_source_location();
+                return Err(ParserError::generic_error(
+                    location,
+                    "`@inline` / `@noinline` must be followed by a function".to_string(),
+                ));
+            }
+
             match self.peek() {
                 Some(Kind::Extern) => {
                     // `extern fn name(params) -> ret` — declares a
@@ -149,6 +199,7 @@ impl<'a> Parser<'a> {
                         code: placeholder_body,
                         is_extern: true,
                         visibility,
+                        inline: None,
                     }));
                 }
                 Some(Kind::Function) => {
@@ -201,6 +252,7 @@ impl<'a> Parser<'a> {
                                 code: self.ast_builder.expression_stmt(block, Some(location)),
                                 is_extern: false,
                                 visibility,
+                                inline: pending_inline.take(),
                             }));
                         }
                         _ => {
@@ -324,9 +376,16 @@ impl<'a> Parser<'a> {
                                 (vec![], std::collections::HashMap::new())
                             };
 
-                            self.expect_err(&Kind::BraceOpen)?;
-                            let fields = super::stmt::parse_struct_fields_with_generic_context(self, vec![], &generic_params)?;
-                            self.expect_err(&Kind::BraceClose)?;
+                            // `struct Meters(u64)` declares a tuple struct
+                            // with positional fields.
+                            let fields = if matches!(self.peek(), Some(Kind::ParenOpen)) {
+                                super::stmt::parse_tuple_struct_fields_with_generic_context(self, &generic_params)?
+                            } else {
+                                self.expect_err(&Kind::BraceOpen)?;
+                                let fields = super::stmt::parse_struct_fields_with_generic_context(self, vec![], &generic_params)?;
+                                self.expect_err(&Kind::BraceClose)?;
+                                fields
+                            };
                             let struct_end_pos = self.peek_position_n(0).unwrap_or(&(0..0)).end;
                             update_end_pos(struct_end_pos);
 
@@ -517,7 +576,10 @@ impl<'a> Parser<'a> {
                                 };
 
                             self.expect_err(&Kind::BraceOpen)?;
-                            let methods = super::stmt::parse_impl_methods_with_generic_context(self, vec![], &generic_params, &generic_bounds)?;
+                            self.current_impl_target = Some(target_type_symbol);
+                            let methods = super::stmt::parse_impl_methods_with_generic_context(self, vec![], &generic_params, &generic_bounds);
+                            self.current_impl_target = None;
+                            let methods = methods?;
                             self.expect_err(&Kind::BraceClose)?;
                             let impl_end_pos = self.peek_position_n(0).unwrap_or(&(0..0)).end;
                             update_end_pos(impl_end_pos);
@@ -582,6 +644,34 @@ impl<'a> Parser<'a> {
                         }
                     }
                 }
+                Some(Kind::At) => {
+                    if matches!(visibility, Visibility::Public) {
+                        self.collect_error("'pub' must follow `@` attributes, not precede them");
+                    }
+                    if matches!(self.peek_n(1), Some(Kind::Identifier(name)) if name == "inline" || name == "noinline") {
+                        let hint = self.parse_inline_attribute()?;
+                        if pending_inline.replace(hint).is_some() {
+                            self.collect_error("a function takes at most one `@inline` / `@noinline`");
+                        }
+                    } else {
+                        let predicate = self.parse_cfg_attribute()?;
+                        // Stacked attributes must all hold.
+                        pending_cfg = Some(match pending_cfg.take() {
+                            Some((previous, funcs, const_count, stmts)) => (
+                                CfgPredicate::All(vec![previous, predicate]),
+                                funcs,
+                                const_count,
+                                stmts,
+                            ),
+                            None => (
+                                predicate,
+                                def_func.len(),
+                                consts.len(),
+                                self.ast_builder.get_stmt_pool().len(),
+                            ),
+                        });
+                    }
+                }
                 Some(Kind::NewLine) => {
                     self.next()
                 }
@@ -611,6 +701,21 @@ impl<'a> Parser<'a> {
             }
         }
 
+        if pending_inline.is_some() {
+            let location = self.current_source_location();
+            return Err(ParserError::generic_error(
+                location,
+                "`@inline` / `@noinline` must be followed by a function".to_string(),
+            ));
+        }
+        if pending_cfg.is_some() {
+            let location = self.current_source_location();
+            return Err(ParserError::generic_error(
+                location,
+                "`@cfg(...)` must be followed by a top-level item".to_string(),
+            ));
+        }
+
         // Check if there were critical errors during parsing (like keyword usage)
         for error in &self.errors {
             // Check both direct GenericError and nested errors in UnexpectedToken
@@ -623,6 +728,17 @@ impl<'a> Parser<'a> {
                     if expected.contains("reserved keyword") => {
                         return Err(error.clone());
                     }
+                // An out-of-range literal (`256u8`, `0x1_0000_0000_0000_0000`)
+                // has no value to carry forward.
+                ParserErrorKind::InvalidLiteral { .. } => {
+                    return Err(error.clone());
+                }
+                // An array type over `max_array_len` has no type to
+                // carry forward either.
+                ParserErrorKind::GenericError { message }
+                    if message.contains("exceeds the array length limit") => {
+                        return Err(error.clone());
+                    }
                 _ => {}
             }
         }
@@ -639,12 +755,114 @@ impl<'a> Parser<'a> {
             imported_function_names: std::collections::HashSet::new(),
             function_module_paths,
             consts,
+            cfg_items,
             statement: stmt,
             expression: expr,
             location_pool,
         })
     }
 
+    /// Parse `@cfg(<predicate>)`. The leading `@` is at `self.peek()`.
+    fn parse_cfg_attribute(&mut self) -> ParserResult<CfgPredicate> {
+        self.next(); // consume `@`
+        match self.peek().cloned() {
+            Some(Kind::Identifier(name)) if name == "cfg" => {
+                self.next();
+            }
+            other => {
+                let location = self.current_source_location();
+                return Err(ParserError::generic_error(
+                    location,
+                    format!("expected `cfg`, `inline` or `noinline` after top-level `@`, got {:?}", other),
+                ));
+            }
+        }
+        self.expect_err(&Kind::ParenOpen)?;
+        let predicate = self.parse_cfg_predicate()?;
+        self.expect_err(&Kind::ParenClose)?;
+        Ok(predicate)
+    }
+
+    /// Parse `@inline` or `@noinline` (top-level functions and impl
+    /// methods). The leading `@` is at `self.peek()`.
+    pub(super) fn parse_inline_attribute(&mut self) -> ParserResult<InlineHint> {
+        self.next(); // consume `@`
+        let hint = match self.peek().cloned() {
+            Some(Kind::Identifier(name)) if name == "inline" => InlineHint::Always,
+            Some(Kind::Identifier(name)) if name == "noinline" => InlineHint::Never,
+            other => {
+                let location = self.current_source_location();
+                return Err(ParserError::generic_error(
+                    location,
+                    format!("expected `inline` or `noinline` after `@`, got {:?}", other),
+                ));
+            }
+        };
+        self.next();
+        Ok(hint)
+    }
+
+    /// `name`, `name = "value"`, `not(p)`, `all(p, ...)` or `any(p, ...)`.
+    fn parse_cfg_predicate(&mut self) -> ParserResult<CfgPredicate> {
+        let name = match self.peek().cloned() {
+            Some(Kind::Identifier(name)) => {
+                self.next();
+                name
+            }
+            other => {
+                let location = self.current_source_location();
+                return Err(ParserError::generic_error(
+                    location,
+                    format!("expected a cfg name, got {:?}", other),
+                ));
+            }
+        };
+        match (name.as_str(), self.peek()) {
+            ("not" | "all" | "any", Some(Kind::ParenOpen)) => {
+                self.next();
+                let mut args = vec![];
+                while !matches!(self.peek(), Some(Kind::ParenClose) | None | Some(Kind::EOF)) {
+                    args.push(self.parse_cfg_predicate()?);
+                    if matches!(self.peek(), Some(Kind::Comma)) {
+                        self.next();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_err(&Kind::ParenClose)?;
+                match name.as_str() {
+                    "not" if args.len() == 1 => Ok(CfgPredicate::Not(Box::new(args.remove(0)))),
+                    "not" => {
+                        let location = self.current_source_location();
+                        Err(ParserError::generic_error(
+                            location,
+                            format!("`not(...)` takes exactly one predicate, got {}", args.len()),
+                        ))
+                    }
+                    "all" => Ok(CfgPredicate::All(args)),
+                    _ => Ok(CfgPredicate::Any(args)),
+                }
+            }
+            (_, Some(Kind::Equal)) => {
+                self.next();
+                match self.peek().cloned() {
+                    Some(Kind::String(value)) => {
+                        self.next();
+                        Ok(CfgPredicate::Equals(name, value))
+                    }
+                    other => {
+                        let location = self.current_source_location();
+                        Err(ParserError::generic_error(
+                            location,
+                            format!("expected a string value after `{} =`, got {:?}", name, other),
+                        ))
+                    }
+                }
+            }
+            _ => Ok(CfgPredicate::Defined(name)),
+        }
+    }
+
     /// Parse program with multiple error collection
     pub fn parse_program_multiple_errors(&mut self) -> MultipleParserResult<Program> {
         self.errors.clear();
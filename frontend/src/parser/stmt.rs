@@ -49,7 +49,18 @@ pub fn parse_stmt(parser: &mut Parser) -> ParserResult<StmtRef> {
             let location = parser.current_source_location();
             parser.next();
             let label = parse_optional_loop_label(parser)?;
-            Ok(parser.ast_builder.break_stmt_with_label(label, Some(location)))
+            // `break value`: anything other than a statement terminator
+            // after `break [@label]` is the loop's result value.
+            let value = match parser.peek() {
+                None
+                | Some(Kind::NewLine)
+                | Some(Kind::BraceClose)
+                | Some(Kind::Semicolon)
+                | Some(Kind::Comma)
+                | Some(Kind::EOF) => None,
+                Some(_) => Some(parser.parse_expr_impl()?),
+            };
+            Ok(parser.ast_builder.break_stmt_with_value(label, value, Some(location)))
         }
         Some(Kind::Continue) => {
             let location = parser.current_source_location();
@@ -60,12 +71,14 @@ pub fn parse_stmt(parser: &mut Parser) -> ParserResult<StmtRef> {
         Some(Kind::Return) => {
             parser.next();
             match parser.peek() {
-                Some(&Kind::NewLine) | Some(&Kind::BracketClose) | Some(Kind::EOF) => {
+                Some(&Kind::NewLine) | Some(Kind::EOF) => {
                     let location = parser.current_source_location();
                     parser.next();
                     Ok(parser.ast_builder.return_stmt(None, Some(location)))
                 }
-                None => {
+                // `{ return }`: the brace closes the enclosing block, so
+                // leave it for the block parser.
+                Some(Kind::BraceClose) | None => {
                     let location = parser.current_source_location();
                     Ok(parser.ast_builder.return_stmt(None, Some(location)))
                 },
@@ -76,6 +89,12 @@ pub fn parse_stmt(parser: &mut Parser) -> ParserResult<StmtRef> {
                 }
             }
         }
+        Some(Kind::Yield) => {
+            let location = parser.current_source_location();
+            parser.next();
+            let value = parser.parse_expr_impl()?;
+            Ok(parser.ast_builder.yield_stmt(value, Some(location)))
+        }
         Some(Kind::For) => parse_for_with_label(parser, None),
         Some(Kind::While) => parse_while_with_label(parser, None),
         Some(Kind::Loop) => parse_loop_with_label(parser, None),
@@ -86,7 +105,7 @@ pub fn parse_stmt(parser: &mut Parser) -> ParserResult<StmtRef> {
 /// LABEL: parse `@label: while/for ...`. The leading `@` is at
 /// `parser.peek()` on entry. Errors if the token after the label is
 /// not a loop keyword.
-fn parse_labelled_loop(parser: &mut Parser) -> ParserResult<StmtRef> {
+pub(super) fn parse_labelled_loop(parser: &mut Parser) -> ParserResult<StmtRef> {
     parser.expect_err(&Kind::At)?;
     let label_sym = match parser.peek().cloned() {
         Some(Kind::Identifier(s)) => {
@@ -160,7 +179,10 @@ fn parse_while_with_label(parser: &mut Parser, label: Option<DefaultSymbol>) ->
 }
 
 /// `loop { BODY }` desugars to `while true { BODY }` at parse time.
-fn parse_loop_with_label(parser: &mut Parser, label: Option<DefaultSymbol>) -> ParserResult<StmtRef> {
+/// In expression position (`val x = loop { ... break v }`) the
+/// statement is wrapped in a one-statement block by the primary
+/// parser; the loop's value is the value its `break` carries.
+pub(super) fn parse_loop_with_label(parser: &mut Parser, label: Option<DefaultSymbol>) -> ParserResult<StmtRef> {
     parser.expect_err(&Kind::Loop)?;
     let block = super::expr::parse_block(parser)?;
     let location = parser.current_source_location();
@@ -251,16 +273,45 @@ fn parse_for_with_label(parser: &mut Parser, label: Option<DefaultSymbol>) -> Pa
             let start = super::expr::parse_logical_expr(parser)?;
             parser.pop_context();
             // Three-way fork on the next token:
-            //   `to` / `..`  → integer range fast path (Stmt::For)
+            //   `to` / `..`  → integer range fast path (Stmt::For),
+            //                  or a `while` desugar with `step N`
             //   `{`          → iterator-protocol form (desugar)
             //   else         → error
             match parser.peek() {
                 Some(Kind::To) | Some(Kind::DotDot) => {
                     parser.next();
-                    let end = super::expr::parse_logical_expr(parser)?;
+                    // Same guard for the range end: `0 to n { ... }`
+                    // must not read `n { ... }` as a struct literal.
+                    parser.push_context(crate::parser::core::ParseContext::Condition);
+                    let end = super::expr::parse_logical_expr(parser);
+                    parser.pop_context();
+                    let end = end?;
+                    // `step` is contextual: it is only a keyword right
+                    // after the range end, so `step` stays usable as an
+                    // ordinary identifier everywhere else.
+                    let step = match parser.peek() {
+                        Some(Kind::Identifier(name)) if name.as_str() == "step" => {
+                            parser.next();
+                            let step_location = parser.current_source_location();
+                            let step = super::expr::parse_logical_expr(parser)?;
+                            let ascending = step_direction(parser, step).ok_or_else(|| {
+                                ParserError::generic_error(
+                                    step_location,
+                                    "for-range `step` must be a non-zero integer literal".to_string(),
+                                )
+                            })?;
+                            Some((step, ascending))
+                        }
+                        _ => None,
+                    };
                     let block = super::expr::parse_block(parser)?;
                     let location = parser.current_source_location();
-                    Ok(parser.ast_builder.for_stmt_with_label(label, ident, start, end, block, Some(location)))
+                    match step {
+                        Some((step, ascending)) => Ok(desugar_for_range_step(
+                            parser, label, ident, start, end, step, ascending, block, location,
+                        )),
+                        None => Ok(parser.ast_builder.for_stmt_with_label(label, ident, start, end, block, Some(location))),
+                    }
                 }
                 Some(Kind::BraceOpen) => {
                     let body = super::expr::parse_block(parser)?;
@@ -284,6 +335,142 @@ fn parse_for_with_label(parser: &mut Parser, label: Option<DefaultSymbol>) -> Pa
     }
 }
 
+/// Classify a `step` expression: `Some(true)` for a positive integer
+/// literal, `Some(false)` for a negative one (`-2i64` / `-(2i64)`),
+/// `None` for zero or anything that is not an integer literal. The
+/// sign picks the loop comparison at parse time, which is why the
+/// step has to be a constant.
+fn step_direction(parser: &Parser, step: ExprRef) -> Option<bool> {
+    let sign = |n: i128| if n == 0 { None } else { Some(n > 0) };
+    match parser.ast_builder.get_expr_pool().get(&step)? {
+        Expr::Int64(n) => sign(n as i128),
+        Expr::UInt64(n) => sign(n as i128),
+        Expr::Int8(n) => sign(n as i128),
+        Expr::Int16(n) => sign(n as i128),
+        Expr::Int32(n) => sign(n as i128),
+        Expr::UInt8(n) => sign(n as i128),
+        Expr::UInt16(n) => sign(n as i128),
+        Expr::UInt32(n) => sign(n as i128),
+        Expr::Number(sym) => {
+            let text = parser.string_interner.resolve(sym)?;
+            sign(crate::token::parse_int_literal(text)?)
+        }
+        Expr::Unary(UnaryOp::Negate, inner) => step_direction(parser, inner).map(|up| !up),
+        _ => None,
+    }
+}
+
+/// Desugar `for i in START..END step STEP { body }` into:
+///
+/// ```text
+/// {
+///     var __for_i_<n> = START
+///     val __for_end_<n> = END
+///     val __for_zero_<n> = __for_end_<n> - __for_end_<n>
+///     val __for_step_<n> = __for_zero_<n> + STEP
+///     while __for_i_<n> < __for_end_<n> {      // `>` for a negative step
+///         val i = __for_i_<n>
+///         val __for_more_<n> = if __for_i_<n> < __for_zero_<n> {
+///             __for_i_<n> + __for_step_<n> < __for_end_<n>
+///         } else {
+///             __for_end_<n> - __for_i_<n> > __for_step_<n>
+///         }
+///         __for_i_<n> = if __for_more_<n> { __for_i_<n> + __for_step_<n> } else { __for_end_<n> }
+///         body
+///     }
+/// }
+/// ```
+///
+/// The counter advances before the body runs so `continue` needs no
+/// special handling, and `END` is evaluated once, like the plain
+/// `Stmt::For` range. Every backend already handles `while`, so no
+/// `Stmt::For` consumer needs to learn about steps.
+///
+/// `__for_more` asks whether another step still lands before `END`
+/// without computing a value outside the counter's type, so a range
+/// ending near the type's maximum (`250u8..255u8 step 10u8`) stops
+/// instead of wrapping around. Which check is safe depends on the
+/// counter's sign; a negative step swaps the two branches and flips
+/// the comparisons. The zero and the step are derived from `END` so
+/// they take the counter's type.
+#[allow(clippy::too_many_arguments)]
+fn desugar_for_range_step(
+    parser: &mut Parser,
+    label: Option<DefaultSymbol>,
+    loop_var: DefaultSymbol,
+    start: ExprRef,
+    end: ExprRef,
+    step: ExprRef,
+    ascending: bool,
+    body: ExprRef,
+    location: crate::type_checker::SourceLocation,
+) -> StmtRef {
+    let counter = parser.synthetic_counter;
+    parser.synthetic_counter += 1;
+    let counter_sym = parser.string_interner.get_or_intern(format!("__for_i_{counter}"));
+    let end_sym = parser.string_interner.get_or_intern(format!("__for_end_{counter}"));
+    let zero_sym = parser.string_interner.get_or_intern(format!("__for_zero_{counter}"));
+    let step_sym = parser.string_interner.get_or_intern(format!("__for_step_{counter}"));
+    let more_sym = parser.string_interner.get_or_intern(format!("__for_more_{counter}"));
+    let b = &mut parser.ast_builder;
+    let loc = Some(location);
+    let id = |b: &mut AstBuilder, sym| b.identifier_expr(sym, loc);
+    let bin = |b: &mut AstBuilder, op, lhs, rhs| {
+        let (lhs, rhs) = (id(b, lhs), id(b, rhs));
+        b.binary_expr(op, lhs, rhs, loc)
+    };
+    let block_of = |b: &mut AstBuilder, expr| {
+        let stmt = b.add_stmt_with_location(Stmt::Expression(expr), loc);
+        b.block_expr(vec![stmt], loc)
+    };
+
+    let counter_decl = b.var_stmt(counter_sym, None, Some(start), loc);
+    let end_decl = b.val_stmt(end_sym, None, end, loc);
+    let zero = bin(b, Operator::ISub, end_sym, end_sym);
+    let zero_decl = b.val_stmt(zero_sym, None, zero, loc);
+    let zero_ref = id(b, zero_sym);
+    let typed_step = b.binary_expr(Operator::IAdd, zero_ref, step, loc);
+    let step_decl = b.val_stmt(step_sym, None, typed_step, loc);
+
+    let (cmp, mirrored) = if ascending { (Operator::LT, Operator::GT) } else { (Operator::GT, Operator::LT) };
+    let cond = bin(b, cmp.clone(), counter_sym, end_sym);
+
+    let current = id(b, counter_sym);
+    let bind = b.val_stmt(loop_var, None, current, loc);
+
+    // `__for_i + step` cannot overflow while it moves the counter
+    // towards zero, and `__for_end - __for_i` cannot while both sit on
+    // the same side of it.
+    let stepped = bin(b, Operator::IAdd, counter_sym, step_sym);
+    let end_ref = id(b, end_sym);
+    let by_sum = b.binary_expr(cmp, stepped, end_ref, loc);
+    let gap = bin(b, Operator::ISub, end_sym, counter_sym);
+    let step_ref = id(b, step_sym);
+    let by_gap = b.binary_expr(mirrored, gap, step_ref, loc);
+    let (negative_check, other_check) = if ascending { (by_sum, by_gap) } else { (by_gap, by_sum) };
+    let negative = bin(b, Operator::LT, counter_sym, zero_sym);
+    let negative_block = block_of(b, negative_check);
+    let other_block = block_of(b, other_check);
+    let more = b.if_elif_else_expr(negative, negative_block, vec![], other_block, loc);
+    let more_decl = b.val_stmt(more_sym, None, more, loc);
+
+    let next = bin(b, Operator::IAdd, counter_sym, step_sym);
+    let next_block = block_of(b, next);
+    let end_ref = id(b, end_sym);
+    let end_block = block_of(b, end_ref);
+    let more_ref = id(b, more_sym);
+    let next_value = b.if_elif_else_expr(more_ref, next_block, vec![], end_block, loc);
+    let assign_target = id(b, counter_sym);
+    let assign = b.assign_expr(assign_target, next_value, loc);
+    let advance = b.add_stmt_with_location(Stmt::Expression(assign), loc);
+    let user_body = b.add_stmt_with_location(Stmt::Expression(body), loc);
+    let while_body = b.block_expr(vec![bind, more_decl, advance, user_body], loc);
+    let while_stmt = b.while_stmt_with_label(label, cond, while_body, loc);
+
+    let outer_block = b.block_expr(vec![counter_decl, end_decl, zero_decl, step_decl, while_stmt], loc);
+    b.add_stmt_with_location(Stmt::Expression(outer_block), loc)
+}
+
 /// Desugar `for x in EXPR { body }` into the iterator-protocol shape:
 ///
 /// ```text
@@ -718,6 +905,50 @@ pub fn parse_struct_fields_with_generic_context(parser: &mut Parser, mut fields:
     }
 }
 
+/// Parse the `(T, ..)` field list of a tuple struct (`struct Meters(u64)`).
+/// Fields are named by position (`"0"`, `"1"`, ..), which is what `.0`
+/// resolves to once the type checker sees the receiver is a tuple struct.
+/// The names are interned here so the type checker, which only holds a
+/// shared interner, can look them up.
+pub fn parse_tuple_struct_fields_with_generic_context(parser: &mut Parser, generic_params: &[DefaultSymbol]) -> ParserResult<Vec<StructField>> {
+    parser.expect_err(&Kind::ParenOpen)?;
+    let generic_context: std::collections::HashSet<DefaultSymbol> = generic_params.iter().cloned().collect();
+    let mut fields = Vec::new();
+    loop {
+        parser.skip_newlines();
+        if parser.peek() == Some(&Kind::ParenClose) {
+            break;
+        }
+        let visibility = if parser.peek() == Some(&Kind::Public) {
+            parser.next();
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+        let type_decl = parser.parse_type_declaration_with_generic_context(&generic_context)?;
+        let name = fields.len().to_string();
+        parser.string_interner.get_or_intern(&name);
+        fields.push(StructField { name, type_decl, visibility });
+        parser.skip_newlines();
+        match parser.peek().cloned() {
+            Some(Kind::Comma) => {
+                parser.next();
+            }
+            Some(Kind::ParenClose) => break,
+            other => {
+                let location = parser.current_source_location();
+                return Err(ParserError::generic_error(location, format!("expected ',' or ')' after tuple struct field, found {:?}", other)));
+            }
+        }
+    }
+    parser.expect_err(&Kind::ParenClose)?;
+    if fields.is_empty() {
+        let location = parser.current_source_location();
+        return Err(ParserError::generic_error(location, "tuple struct needs at least one field".to_string()));
+    }
+    Ok(fields)
+}
+
 pub fn parse_impl_methods(parser: &mut Parser, methods: Vec<Rc<MethodFunction>>) -> ParserResult<Vec<Rc<MethodFunction>>> {
     parse_impl_methods_with_generic_context(parser, methods, &[], &std::collections::HashMap::new())
 }
@@ -833,6 +1064,15 @@ pub fn parse_impl_methods_with_generic_context(
             return Ok(methods);
         }
 
+        // `@inline` / `@noinline` goes before `pub` and `fn`.
+        let inline = if matches!(parser.peek(), Some(Kind::At)) {
+            let hint = parser.parse_inline_attribute()?;
+            parser.skip_newlines();
+            Some(hint)
+        } else {
+            None
+        };
+
         // Check for visibility modifier first
         let visibility = if matches!(parser.peek(), Some(Kind::Public)) {
             parser.next(); // consume 'pub'
@@ -914,6 +1154,7 @@ pub fn parse_impl_methods_with_generic_context(
                             has_self_param: has_self,
                             self_is_mut,
                             visibility,
+                            inline,
                         }));
                         
                         parser.skip_newlines();
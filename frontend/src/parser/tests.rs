@@ -46,6 +46,8 @@ mod lexer_tests{
             (" val ", Kind::Val),
             (" var ", Kind::Var),
             (" with ", Kind::With),
+            (" try ", Kind::Try),
+            (" catch ", Kind::Catch),
             (" ambient ", Kind::Ambient),
             (" bool ", Kind::Bool),
         ];
@@ -144,6 +146,47 @@ mod lexer_tests{
         assert_token(" \"string\" ", Kind::String("string".to_string()));
     }
 
+    #[test]
+    fn lexer_octal_binary_literals() {
+        assert_token(" 0o755 ", Kind::Integer("0o755".to_string()));
+        assert_token(" 0b1010 ", Kind::Integer("0b1010".to_string()));
+        assert_token(" 0o7_7u8 ", Kind::UInt8(0o77));
+        assert_token(" 0b1111_0000u8 ", Kind::UInt8(0xF0));
+        assert_token(" -0b1000_0000i8 ", Kind::Int8(-128));
+        assert_token(" 0B11i64 ", Kind::Int64(3));
+        // Overflow keeps the text for the parser to report.
+        assert_token(" 0b1_0000_0000u8 ", Kind::Integer("0b1_0000_0000u8".to_string()));
+    }
+
+    #[test]
+    fn lexer_octal_binary_bad_digit_is_error() {
+        for src in ["0o78", "0b102", "0b12u8"] {
+            let mut l = lexer::Lexer::new(src, 1u64);
+            assert!(l.yylex().is_err(), "Input: '{}'", src);
+        }
+    }
+
+    #[test]
+    fn int_literal_text_parsing() {
+        use crate::token::{parse_int_literal, split_int_suffix};
+        assert_eq!(parse_int_literal("1_000_000"), Some(1_000_000));
+        assert_eq!(parse_int_literal("0xFF"), Some(255));
+        assert_eq!(parse_int_literal("0o77"), Some(63));
+        assert_eq!(parse_int_literal("0b1010"), Some(10));
+        assert_eq!(parse_int_literal("-0x8000_0000_0000_0000"), Some(i64::MIN as i128));
+        assert_eq!(parse_int_literal("0x"), None);
+        assert_eq!(parse_int_literal("0x_1"), None);
+        assert_eq!(split_int_suffix("0xFFu8"), ("0xFF", Some("u8")));
+        assert_eq!(split_int_suffix("12"), ("12", None));
+    }
+
+    #[test]
+    fn lexer_bytes_literal() {
+        assert_token(" b\"A\\x00\\xff\\n{}\" ", Kind::Bytes(vec![b'A', 0x00, 0xff, b'\n', b'{', b'}']));
+        // A bare `b` is still an identifier.
+        assert_token(" b ", Kind::Identifier("b".to_string()));
+    }
+
     #[test]
     fn lexer_simple_symbol1() {
         let s = " ( ) { } [ ] , . :: : = !";
@@ -367,7 +410,7 @@ mod parser_tests {
         assert_expr_pool_size(&p, 2);
         let expected_symbol = p.get_string_interner().get_or_intern("abc");
         assert_expr_at(&p, 0, Expr::ExprList(vec![]));
-        assert_expr_at(&p, 1, Expr::Call(expected_symbol, ExprRef(0)));
+        assert_expr_at(&p, 1, Expr::Call(expected_symbol, ExprRef(0), vec![]));
     }
 
     #[test]
@@ -429,7 +472,7 @@ mod parser_tests {
         assert_expr_at(&p, 1, Expr::UInt64(2));
         assert_expr_at(&p, 2, Expr::ExprList(vec![ExprRef(0), ExprRef(1)]));
         let expected_symbol = p.get_string_interner().get_or_intern("abc");
-        assert_expr_at(&p, 3, Expr::Call(expected_symbol, ExprRef(2)));
+        assert_expr_at(&p, 3, Expr::Call(expected_symbol, ExprRef(2), vec![]));
     }
 
     #[test]
@@ -977,4 +1020,43 @@ mod parser_tests {
         let result = parser.parse_program();
         assert!(result.is_ok(), "`with allocator = <identifier>` should parse: {:?}", result);
     }
+
+    #[test]
+    fn parser_with_resource_binding() {
+        let input = "fn main() -> u64 {\nval a = 0u64\nwith f = a {\nf\n}\n}";
+        let mut parser = ParserWithInterner::new(input);
+        let result = parser.parse_program();
+        assert!(result.is_ok(), "`with <name> = expr {{ ... }}` should parse: {:?}", result);
+        let program = result.unwrap();
+        let has_with_resource = (0..program.expression.len()).any(|i| {
+            matches!(
+                program.expression.get(&ExprRef(i as u32)),
+                Some(Expr::WithResource { .. })
+            )
+        });
+        assert!(has_with_resource, "expected an Expr::WithResource node");
+    }
+
+    #[test]
+    fn parser_try_catch_expr() {
+        let input = "fn main() -> u64 {\nval v = try {\n1u64\n} catch e {\n2u64\n}\nv\n}";
+        let mut parser = ParserWithInterner::new(input);
+        let result = parser.parse_program();
+        assert!(result.is_ok(), "`try {{ ... }} catch e {{ ... }}` should parse: {:?}", result);
+        let program = result.unwrap();
+        let has_try_catch = (0..program.expression.len()).any(|i| {
+            matches!(
+                program.expression.get(&ExprRef(i as u32)),
+                Some(Expr::TryCatch { .. })
+            )
+        });
+        assert!(has_try_catch, "expected an Expr::TryCatch node");
+    }
+
+    #[test]
+    fn parser_try_without_catch_errors() {
+        let input = "fn main() -> u64 {\ntry {\n1u64\n}\n}";
+        let mut parser = ParserWithInterner::new(input);
+        assert!(parser.parse_program().is_err() || !parser.errors.is_empty());
+    }
 }
\ No newline at end of file
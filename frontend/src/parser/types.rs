@@ -68,6 +68,7 @@ impl<'a> Parser<'a> {
                 if self.peek() == Some(&Kind::Semicolon) {
                     self.next(); // consume semicolon
 
+                    let size_location = self.current_source_location();
                     let size = match self.peek().cloned() {
                         Some(Kind::UInt64(n)) => {
                             self.next();
@@ -75,10 +76,12 @@ impl<'a> Parser<'a> {
                         }
                         Some(Kind::Integer(s)) => {
                             self.next();
-                            s.parse::<usize>().map_err(|_| {
-                                let location = self.current_source_location();
-                                ParserError::generic_error(location, format!("Invalid array size: {}", s))
-                            })?
+                            crate::token::parse_int_literal(&s)
+                                .and_then(|n| usize::try_from(n).ok())
+                                .ok_or_else(|| {
+                                    let location = self.current_source_location();
+                                    ParserError::generic_error(location, format!("Invalid array size: {}", s))
+                                })?
                         }
                         _ => {
                             let location = self.current_source_location();
@@ -86,6 +89,18 @@ impl<'a> Parser<'a> {
                         }
                     };
 
+                    if size > self.max_array_len {
+                        // Also recorded directly: statement-level recovery
+                        // would otherwise swallow it, and `parse_program`
+                        // fails on it (see the critical-error check there).
+                        let error = ParserError::generic_error(
+                            size_location,
+                            format!("[T; {}] exceeds the array length limit of {}", size, self.max_array_len),
+                        );
+                        self.errors.push(error.clone());
+                        return Err(error);
+                    }
+
                     self.expect_err(&Kind::BracketClose)?;
                     Ok(TypeDecl::Array(vec![element_type; size], size))
                 } else {
@@ -154,6 +169,24 @@ impl<'a> Parser<'a> {
                 if s_owned == "Allocator" {
                     return Ok(TypeDecl::Allocator);
                 }
+                // Same for `bytes`, so existing code that uses `bytes`
+                // as a variable or field name keeps parsing.
+                if s_owned == "bytes" {
+                    return Ok(TypeDecl::Bytes);
+                }
+                // `any` is the dynamic type. It maps onto `Unknown`, which
+                // the checker already treats as compatible with every
+                // type in both directions; `is<T>` / `downcast<T>` add
+                // the run-time checks.
+                if s_owned == "any" {
+                    return Ok(TypeDecl::Unknown);
+                }
+                // `Task` is a cooperative task: a generator that yields
+                // nothing, only hands control back to the scheduler at
+                // `yield_now()`.
+                if s_owned == "Task" {
+                    return Ok(TypeDecl::Generator(Box::new(TypeDecl::Unit)));
+                }
 
                 // Check if this is a generic struct with type arguments: Container<T>
                 if matches!(self.peek(), Some(Kind::LT)) {
@@ -191,6 +224,11 @@ impl<'a> Parser<'a> {
                     }
 
                     self.expect_err(&Kind::GT)?;
+                    // `Generator<T>` is the built-in lazy sequence a
+                    // function with `yield` statements returns.
+                    if s_owned == "Generator" && type_args.len() == 1 {
+                        return Ok(TypeDecl::Generator(Box::new(type_args.remove(0))));
+                    }
                     // If `ident` names a generic alias (`type Pair<T>
                     // = ...`), substitute the parsed type args into
                     // the alias target. Arity mismatches surface as
@@ -0,0 +1,444 @@
+//! Purity inference for functions and methods.
+//!
+//! A function is pure when calling it has no effect beyond computing
+//! its result: it performs no I/O, reads no clock or random state,
+//! touches no raw memory or allocator, does not mutate anything its
+//! caller can see, and only calls other pure functions. `panic` /
+//! `assert` are allowed — they abort the same way for the same
+//! arguments. Pure calls can share one evaluation (CSE), be folded
+//! when their arguments are constant, or have their results cached.
+//!
+//! The analysis runs on the checked program (interpreter and compiler
+//! drivers store the result in `TypeCheckResults::purity`) and is
+//! conservative: anything it can't resolve counts as impure. In
+//! particular:
+//!
+//! - values are shared references at runtime, so writing a field or
+//!   an element is only allowed on a local initialised from a fresh
+//!   literal, one level deep (`s.x = v`, `a[i] = v`);
+//! - a call through a local (a closure value) is impure;
+//! - method calls are resolved through the receiver's checked type
+//!   (`TypeCheckResults::expr_types`); bodies the checker didn't visit
+//!   (imported module code) can't resolve theirs;
+//! - `extern fn`s are pure only under the stdlib's `__extern_` prefix
+//!   (the runtime-provided math and bit operations);
+//! - functions sharing a name are pure only if all of them are.
+//!
+//! Recursion is resolved as a greatest fixed point: functions start out
+//! pure and lose it when a body or a callee turns out impure, so a pure
+//! recursive function stays pure.
+
+use std::collections::{HashMap, HashSet};
+
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+
+use crate::ast::{Expr, ExprRef, Pattern, Program, Stmt, StmtRef};
+use crate::type_decl::TypeDecl;
+
+/// Name prefix of the runtime-provided stdlib externs, all of which
+/// are pure.
+pub const PURE_EXTERN_PREFIX: &str = "__extern_";
+
+/// Pure functions (by name) and methods (by impl target and name).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Purity {
+    functions: HashSet<DefaultSymbol>,
+    methods: HashSet<(DefaultSymbol, DefaultSymbol)>,
+}
+
+impl Purity {
+    pub fn is_pure_function(&self, name: DefaultSymbol) -> bool {
+        self.functions.contains(&name)
+    }
+
+    /// `target` is the impl's target type (`Point`, or `i64` for a
+    /// primitive extension impl).
+    pub fn is_pure_method(&self, target: DefaultSymbol, name: DefaultSymbol) -> bool {
+        self.methods.contains(&(target, name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Callee {
+    Function(DefaultSymbol),
+    Method(DefaultSymbol, DefaultSymbol),
+}
+
+/// Infer purity for every function and impl method of `program`.
+/// `expr_types` are the checker's per-expression types, used to
+/// resolve method receivers.
+pub fn infer_purity(
+    program: &Program,
+    expr_types: &HashMap<ExprRef, TypeDecl>,
+    interner: &DefaultStringInterner,
+) -> Purity {
+    let mut methods = HashSet::new();
+    for i in 0..program.statement.len() {
+        if let Some(Stmt::ImplBlock { target_type, methods: ms, .. }) = program.statement.get(&StmtRef(i as u32)) {
+            methods.extend(ms.iter().map(|m| (target_type, m.name)));
+        }
+    }
+
+    // Callees of every body that is impure on its own are dropped;
+    // `None` marks such a body.
+    let mut bodies: Vec<(Callee, Option<HashSet<Callee>>)> = Vec::new();
+    for func in &program.function {
+        let scan = if func.is_extern {
+            let pure = interner.resolve(func.name).is_some_and(|n| n.starts_with(PURE_EXTERN_PREFIX));
+            pure.then(HashSet::new)
+        } else {
+            let mut scan = BodyScan::new(program, expr_types, interner, &methods);
+            scan.bind_params(func.parameter.iter().map(|(name, _)| *name));
+            scan.exprs(func.requires.iter().chain(&func.ensures));
+            scan.stmt(&func.code);
+            scan.finish()
+        };
+        bodies.push((Callee::Function(func.name), scan));
+    }
+    for i in 0..program.statement.len() {
+        let Some(Stmt::ImplBlock { target_type, methods: ms, .. }) = program.statement.get(&StmtRef(i as u32)) else {
+            continue;
+        };
+        for method in &ms {
+            let mut scan = BodyScan::new(program, expr_types, interner, &methods);
+            if method.has_self_param {
+                scan.bind_params(interner.get("self"));
+            }
+            scan.bind_params(method.parameter.iter().map(|(name, _)| *name));
+            scan.exprs(method.requires.iter().chain(&method.ensures));
+            scan.stmt(&method.code);
+            bodies.push((Callee::Method(target_type, method.name), scan.finish()));
+        }
+    }
+
+    // Start from everything whose own body is clean, then drop callers
+    // of anything not (or no longer) in the set until nothing changes.
+    let mut pure: HashSet<Callee> = bodies.iter().map(|(c, _)| *c).collect();
+    for (callee, scan) in &bodies {
+        if scan.is_none() {
+            pure.remove(callee);
+        }
+    }
+    loop {
+        let before = pure.len();
+        for (callee, scan) in &bodies {
+            if let Some(calls) = scan
+                && !calls.iter().all(|c| pure.contains(c))
+            {
+                pure.remove(callee);
+            }
+        }
+        if pure.len() == before {
+            break;
+        }
+    }
+
+    let mut purity = Purity::default();
+    for callee in pure {
+        match callee {
+            Callee::Function(name) => purity.functions.insert(name),
+            Callee::Method(target, name) => purity.methods.insert((target, name)),
+        };
+    }
+    purity
+}
+
+/// Walks one body, collecting its callees and whether it does anything
+/// impure by itself.
+struct BodyScan<'a> {
+    program: &'a Program,
+    expr_types: &'a HashMap<ExprRef, TypeDecl>,
+    interner: &'a DefaultStringInterner,
+    methods: &'a HashSet<(DefaultSymbol, DefaultSymbol)>,
+    params: HashSet<DefaultSymbol>,
+    /// Every local name bound in the body.
+    locals: HashSet<DefaultSymbol>,
+    /// Locals bound at least once to something other than a fresh
+    /// literal.
+    shared: HashSet<DefaultSymbol>,
+    calls: HashSet<Callee>,
+    impure: bool,
+}
+
+impl<'a> BodyScan<'a> {
+    fn new(
+        program: &'a Program,
+        expr_types: &'a HashMap<ExprRef, TypeDecl>,
+        interner: &'a DefaultStringInterner,
+        methods: &'a HashSet<(DefaultSymbol, DefaultSymbol)>,
+    ) -> Self {
+        Self {
+            program,
+            expr_types,
+            interner,
+            methods,
+            params: HashSet::new(),
+            locals: HashSet::new(),
+            shared: HashSet::new(),
+            calls: HashSet::new(),
+            impure: false,
+        }
+    }
+
+    fn finish(self) -> Option<HashSet<Callee>> {
+        (!self.impure).then_some(self.calls)
+    }
+
+    fn bind_params(&mut self, names: impl IntoIterator<Item = DefaultSymbol>) {
+        self.params.extend(names);
+    }
+
+    fn bind_local(&mut self, name: DefaultSymbol, init: Option<&ExprRef>) {
+        self.locals.insert(name);
+        let fresh = init.and_then(|e| self.program.expression.get(e)).is_some_and(|e| {
+            matches!(
+                e,
+                Expr::StructLiteral(..)
+                    | Expr::ArrayLiteral(_)
+                    | Expr::TupleLiteral(_)
+                    | Expr::DictLiteral(_)
+                    | Expr::Comprehension { .. }
+            )
+        });
+        if !fresh {
+            self.shared.insert(name);
+        }
+    }
+
+    fn is_bound(&self, name: DefaultSymbol) -> bool {
+        self.params.contains(&name) || self.locals.contains(&name)
+    }
+
+    fn exprs<'e>(&mut self, exprs: impl IntoIterator<Item = &'e ExprRef>) {
+        for e in exprs {
+            self.expr(e);
+        }
+    }
+
+    fn stmt(&mut self, stmt_ref: &StmtRef) {
+        if self.impure {
+            return;
+        }
+        let Some(stmt) = self.program.statement.get(stmt_ref) else {
+            return;
+        };
+        match stmt {
+            Stmt::Expression(e) | Stmt::Return(Some(e)) | Stmt::Break(_, Some(e)) => self.expr(&e),
+            Stmt::Val(name, _, e) => {
+                self.expr(&e);
+                self.bind_local(name, Some(&e));
+            }
+            Stmt::Var(name, _, e) => {
+                if let Some(e) = &e {
+                    self.expr(e);
+                }
+                self.bind_local(name, e.as_ref());
+            }
+            Stmt::For(_, var, start, end, body) => {
+                self.bind_local(var, None);
+                self.exprs([&start, &end, &body]);
+            }
+            Stmt::While(_, cond, body) => self.exprs([&cond, &body]),
+            // A call hands back a generator whose `next()` advances
+            // hidden state, so it can be neither shared nor folded.
+            Stmt::Yield(_) => self.impure = true,
+            Stmt::Return(None) | Stmt::Break(_, None) | Stmt::Continue(_) => {}
+            // Nested declarations carry no code of this body.
+            Stmt::StructDecl { .. }
+            | Stmt::ImplBlock { .. }
+            | Stmt::TraitDecl { .. }
+            | Stmt::EnumDecl { .. }
+            | Stmt::TypeAlias { .. } => {}
+        }
+    }
+
+    fn expr(&mut self, expr_ref: &ExprRef) {
+        if self.impure {
+            return;
+        }
+        let Some(expr) = self.program.expression.get(expr_ref) else {
+            return;
+        };
+        match expr {
+            Expr::True
+            | Expr::False
+            | Expr::Int64(_)
+            | Expr::UInt64(_)
+            | Expr::Int8(_)
+            | Expr::Int16(_)
+            | Expr::Int32(_)
+            | Expr::UInt8(_)
+            | Expr::UInt16(_)
+            | Expr::UInt32(_)
+            | Expr::Float64(_)
+            | Expr::Number(_)
+            | Expr::Identifier(_)
+            | Expr::Null
+            | Expr::String(_)
+            | Expr::Bytes(_)
+            | Expr::QualifiedIdentifier(_) => {}
+            Expr::Assign(lhs, rhs) => {
+                self.assign_target(&lhs);
+                self.expr(&rhs);
+            }
+            Expr::SliceAssign(target, info, value) => {
+                self.assign_target(&target);
+                self.exprs(info.start.iter().chain(&info.end).chain([&value]));
+            }
+            Expr::IfElifElse(cond, then, elifs, els) => {
+                self.exprs([&cond, &then]);
+                for (c, b) in &elifs {
+                    self.exprs([c, b]);
+                }
+                self.expr(&els);
+            }
+            Expr::Binary(_, l, r) | Expr::Range(l, r) => self.exprs([&l, &r]),
+            Expr::Unary(_, e) | Expr::FieldAccess(e, _) | Expr::TupleAccess(e, _) | Expr::Cast(e, _) => {
+                self.expr(&e)
+            }
+            Expr::Block(stmts) => {
+                for s in &stmts {
+                    self.stmt(s);
+                }
+            }
+            Expr::ExprList(es) | Expr::ArrayLiteral(es) | Expr::TupleLiteral(es) => self.exprs(&es),
+            Expr::StructLiteral(_, fields, _) => self.exprs(fields.iter().map(|(_, e)| e)),
+            Expr::DictLiteral(entries) => {
+                for (k, v) in &entries {
+                    self.exprs([k, v]);
+                }
+            }
+            Expr::SliceAccess(e, info) => self.exprs([&e].into_iter().chain(&info.start).chain(&info.end)),
+            Expr::Call(name, args, _) => {
+                if self.is_bound(name) {
+                    self.impure = true;
+                }
+                self.calls.insert(Callee::Function(name));
+                self.expr(&args);
+            }
+            Expr::AssociatedFunctionCall(owner, name, args) => {
+                // `Type::f(..)` when the impl has it, else a
+                // module-qualified `module::f(..)`.
+                let callee = if self.methods.contains(&(owner, name)) {
+                    Callee::Method(owner, name)
+                } else {
+                    Callee::Function(name)
+                };
+                self.calls.insert(callee);
+                self.exprs(&args);
+            }
+            Expr::MethodCall(receiver, name, args) => {
+                match self.receiver_target(&receiver) {
+                    Some(target) => {
+                        self.calls.insert(Callee::Method(target, name));
+                    }
+                    None => self.impure = true,
+                }
+                self.expr(&receiver);
+                self.exprs(&args);
+            }
+            // Every builtin method reads its receiver and returns a
+            // fresh value.
+            Expr::BuiltinMethodCall(receiver, _, args) => {
+                self.expr(&receiver);
+                self.exprs(&args);
+            }
+            Expr::BuiltinCall(builtin, args) => {
+                if !builtin.is_pure() {
+                    self.impure = true;
+                }
+                self.exprs(&args);
+            }
+            Expr::With(..) | Expr::WithResource { .. } => self.impure = true,
+            Expr::Match(scrutinee, arms) => {
+                self.expr(&scrutinee);
+                for arm in &arms {
+                    self.pattern(&arm.pattern);
+                    self.exprs(arm.guard.iter().chain([&arm.body]));
+                }
+            }
+            Expr::Closure { params, body, .. } => {
+                self.bind_params(params.iter().map(|(name, _)| *name));
+                self.expr(&body);
+            }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                self.expr(&source);
+                self.bind_local(var, None);
+                self.exprs(filter.iter().chain([&element]).chain(&value));
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.expr(&body);
+                self.bind_local(error, None);
+                self.expr(&handler);
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Name(name) => self.bind_local(*name, None),
+            Pattern::Literal(e) => self.expr(e),
+            Pattern::EnumVariant(_, _, subs) | Pattern::Tuple(subs) => {
+                for p in subs {
+                    self.pattern(p);
+                }
+            }
+            Pattern::Wildcard => {}
+        }
+    }
+
+    /// Left-hand side of `=`: rebinding a local or parameter is fine,
+    /// writing into a value is only allowed on a fresh local.
+    fn assign_target(&mut self, target: &ExprRef) {
+        match self.program.expression.get(target) {
+            Some(Expr::Identifier(name)) => {
+                if !self.is_bound(name) {
+                    self.impure = true;
+                }
+            }
+            Some(Expr::FieldAccess(base, _)) | Some(Expr::TupleAccess(base, _)) => self.write_into(&base),
+            Some(Expr::SliceAccess(base, info)) => {
+                self.write_into(&base);
+                self.exprs(info.start.iter().chain(&info.end));
+            }
+            _ => self.impure = true,
+        }
+    }
+
+    fn write_into(&mut self, base: &ExprRef) {
+        let fresh_local = match self.program.expression.get(base) {
+            Some(Expr::Identifier(name)) => {
+                self.locals.contains(&name) && !self.shared.contains(&name) && !self.params.contains(&name)
+            }
+            _ => false,
+        };
+        if !fresh_local {
+            self.impure = true;
+        }
+    }
+
+    /// Impl target symbol for a method receiver's checked type.
+    fn receiver_target(&self, receiver: &ExprRef) -> Option<DefaultSymbol> {
+        let mut ty = self.expr_types.get(receiver)?;
+        while let TypeDecl::Ref { inner, .. } = ty {
+            ty = inner;
+        }
+        let primitive = match ty {
+            TypeDecl::Struct(name, _) | TypeDecl::Enum(name, _) | TypeDecl::Identifier(name) => return Some(*name),
+            TypeDecl::Bool => "bool",
+            TypeDecl::Int64 => "i64",
+            TypeDecl::UInt64 => "u64",
+            TypeDecl::Int8 => "i8",
+            TypeDecl::Int16 => "i16",
+            TypeDecl::Int32 => "i32",
+            TypeDecl::UInt8 => "u8",
+            TypeDecl::UInt16 => "u16",
+            TypeDecl::UInt32 => "u32",
+            TypeDecl::Float64 => "f64",
+            TypeDecl::String => "str",
+            TypeDecl::Ptr => "ptr",
+            _ => return None,
+        };
+        self.interner.get(primitive)
+    }
+}
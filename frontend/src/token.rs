@@ -32,6 +32,12 @@ pub enum Kind {
     Impl,
     Function,
     Return,
+    /// `yield expr` — hands one item out of a generator function.
+    Yield,
+    /// `try { ... } catch e { ... }` — runs the handler when the
+    /// body fails at runtime.
+    Try,
+    Catch,
     Extern,
     Public,
     Package,
@@ -152,6 +158,15 @@ pub enum Kind {
     /// `}` (Rust convention). At least one Expr part is present
     /// (otherwise the lexer emits a plain `String`).
     InterpolatedString(Vec<StringPart>),
+    /// Byte-string literal — `b"GET \x00\xff"`. The payload is the
+    /// raw bytes after escape processing; no interpolation and no
+    /// non-ASCII source characters (use `\xHH`).
+    Bytes(Vec<u8>),
+    /// Integer literal the lexer could not pin to a width: either
+    /// unsuffixed (`42`, `0xFF`, `0b1010`, `1_000`) and resolved later
+    /// through `Expr::Number` inference, or a suffixed literal that
+    /// overflowed its suffix (`256u8`), which the parser reports.
+    /// See `parse_int_literal` for the accepted text.
     Integer(String),
 
     Identifier(String),
@@ -162,6 +177,10 @@ pub enum Kind {
 
     NewLine,
     EOF,
+    /// Text the lexer could not turn into a token. Only produced by
+    /// [`crate::lex_with_spans`]; the parser stops at the first
+    /// lexing error instead.
+    Error,
 }
 
 impl Kind {
@@ -170,10 +189,72 @@ impl Kind {
         matches!(self, 
             Kind::If | Kind::Elif | Kind::Else | Kind::For | Kind::In | Kind::To | 
             Kind::While | Kind::Loop | Kind::Break | Kind::Continue | Kind::Class | Kind::Struct |
-            Kind::Trait | Kind::Impl | Kind::Function | Kind::Return | Kind::Extern | Kind::Public |
+            Kind::Trait | Kind::Impl | Kind::Function | Kind::Return | Kind::Yield | Kind::Try | Kind::Catch | Kind::Extern | Kind::Public |
             Kind::Val | Kind::Var | Kind::Mut | Kind::Const | Kind::With | Kind::Ambient | Kind::Enum | Kind::Match | Kind::Requires | Kind::Ensures | Kind::Type | Kind::Bool | Kind::U64 | Kind::I64 | Kind::F64 | Kind::USize |
             Kind::U8 | Kind::U16 | Kind::U32 | Kind::I8 | Kind::I16 | Kind::I32 |
             Kind::Str | Kind::Ptr | Kind::Null | Kind::Dict | Kind::Self_ | Kind::True | Kind::False
         )
     }
 }
+
+/// Width suffixes an integer literal may carry.
+const INT_SUFFIXES: [&str; 8] = ["i64", "u64", "i32", "u32", "i16", "u16", "i8", "u8"];
+
+/// Split an integer literal's source text into its numeric body and
+/// an optional width suffix: `"0xFFu8"` -> `("0xFF", Some("u8"))`,
+/// `"1_000"` -> `("1_000", None)`. No radix digit is an `i` / `u`,
+/// so the suffix is never ambiguous.
+pub fn split_int_suffix(text: &str) -> (&str, Option<&str>) {
+    for suffix in INT_SUFFIXES {
+        if let Some(body) = text.strip_suffix(suffix) {
+            return (body, Some(suffix));
+        }
+    }
+    (text, None)
+}
+
+/// Value of an integer literal body (no width suffix): an optional
+/// leading `-`, an optional `0x` / `0o` / `0b` radix prefix (either
+/// case), then digits with `_` separators anywhere after the first
+/// digit. Returns `None` for malformed text or values outside `i128`,
+/// which comfortably covers every literal width (`i64::MIN ..=
+/// u64::MAX`) so callers can range-check with `try_from`.
+///
+/// Every consumer of `Kind::Integer` / `Expr::Number` text goes
+/// through here so hex / octal / binary and separators behave the
+/// same in the parser, the type checker, and the Number inference
+/// path.
+pub fn parse_int_literal(text: &str) -> Option<i128> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned[2..]),
+        Some("0o" | "0O") => (8, &unsigned[2..]),
+        Some("0b" | "0B") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+    if !digits.chars().next().is_some_and(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let cleaned = digits.replace('_', "");
+    let magnitude = i128::from_str_radix(&cleaned, radix).ok()?;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Typed literal token for `value` under width `suffix`, or `None`
+/// when the value does not fit that width.
+pub fn typed_int_kind(value: i128, suffix: &str) -> Option<Kind> {
+    Some(match suffix {
+        "i64" => Kind::Int64(i64::try_from(value).ok()?),
+        "u64" => Kind::UInt64(u64::try_from(value).ok()?),
+        "i32" => Kind::Int32(i32::try_from(value).ok()?),
+        "u32" => Kind::UInt32(u32::try_from(value).ok()?),
+        "i16" => Kind::Int16(i16::try_from(value).ok()?),
+        "u16" => Kind::UInt16(u16::try_from(value).ok()?),
+        "i8" => Kind::Int8(i8::try_from(value).ok()?),
+        "u8" => Kind::UInt8(u8::try_from(value).ok()?),
+        _ => return None,
+    })
+}
@@ -0,0 +1,58 @@
+//! Tokens with their byte ranges for editor integrations (syntax
+//! highlighting, LSP semantic tokens). Unlike the parser's token
+//! source, this never stops early: text the lexer rejects becomes a
+//! `Kind::Error` token and lexing resumes right after it.
+
+use std::ops::Range;
+
+use crate::parser::core::lexer::{Error, Lexer};
+use crate::token::Kind;
+
+/// Lex all of `source`. Comments and newlines are kept, whitespace is
+/// not. Ranges are byte offsets into `source`, in order and
+/// non-overlapping. Adjacent rejected characters are merged into one
+/// `Kind::Error` token.
+pub fn lex_with_spans(source: &str) -> Vec<(Kind, Range<usize>)> {
+    let mut tokens: Vec<(Kind, Range<usize>)> = Vec::new();
+    let mut offset = 0;
+    'restart: while offset < source.len() {
+        let mut lexer = Lexer::new(&source[offset..], 1);
+        loop {
+            match lexer.yylex() {
+                Ok(token) => {
+                    let range = token.position.start + offset..token.position.end + offset;
+                    tokens.push((token.kind, range));
+                }
+                Err(Error::EOF) => break 'restart,
+                Err(Error::Unmatch) => {
+                    let rejected = lexer.yybytepos();
+                    if !rejected.is_empty() {
+                        // A rule matched but refused the text (a bad
+                        // escape, `1abc`); the lexer is already past it.
+                        push_error(&mut tokens, rejected.start + offset..rejected.end + offset);
+                        continue;
+                    }
+                    // No rule matches here at all. The lexer cannot
+                    // move past the character itself, so skip it and
+                    // start a fresh lexer after it.
+                    let at = rejected.start + offset;
+                    let width = source[at..].chars().next().map_or(1, char::len_utf8);
+                    push_error(&mut tokens, at..at + width);
+                    offset = at + width;
+                    continue 'restart;
+                }
+            }
+        }
+    }
+    tokens
+}
+
+fn push_error(tokens: &mut Vec<(Kind, Range<usize>)>, range: Range<usize>) {
+    if let Some((Kind::Error, last)) = tokens.last_mut()
+        && last.end == range.start
+    {
+        last.end = range.end;
+        return;
+    }
+    tokens.push((Kind::Error, range));
+}
@@ -15,14 +15,16 @@ pub mod core;
 pub mod context;
 pub mod error;
 pub mod function;
+pub mod check_cache;
 pub mod generics;
 pub mod inference;
 pub mod optimization;
 
 pub use core::CoreReferences;
 pub use context::{TypeCheckContext, VarState};
-pub use error::{SourceLocation, TypeCheckError, TypeCheckErrorKind};
+pub use error::{FixIt, SourceLocation, TypeCheckError, TypeCheckErrorKind};
 pub use function::FunctionCheckingState;
+pub use check_cache::{FunctionCheckCache, FunctionFingerprints};
 pub use generics::GenericTypeChecking;
 pub use inference::TypeInferenceState;
 pub use optimization::PerformanceOptimization;
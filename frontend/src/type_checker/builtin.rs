@@ -7,21 +7,11 @@ use crate::type_checker::TypeCheckerVisitor;
 impl<'a> TypeCheckerVisitor<'a> {
     /// Create builtin method registry
     pub fn create_builtin_method_registry() -> HashMap<(TypeDecl, String), BuiltinMethod> {
-        let mut registry = HashMap::new();
-        
-        // Universal methods (available for all types - we'll handle these specially)
-        // is_null is handled separately in visit_method_call
-        
-        // String methods
-        registry.insert((TypeDecl::String, "len".to_string()), BuiltinMethod::StrLen);
-        registry.insert((TypeDecl::String, "concat".to_string()), BuiltinMethod::StrConcat);
-        registry.insert((TypeDecl::String, "substring".to_string()), BuiltinMethod::StrSubstring);
-        registry.insert((TypeDecl::String, "contains".to_string()), BuiltinMethod::StrContains);
-        registry.insert((TypeDecl::String, "split".to_string()), BuiltinMethod::StrSplit);
-        registry.insert((TypeDecl::String, "trim".to_string()), BuiltinMethod::StrTrim);
-        registry.insert((TypeDecl::String, "to_upper".to_string()), BuiltinMethod::StrToUpper);
-        registry.insert((TypeDecl::String, "to_lower".to_string()), BuiltinMethod::StrToLower);
-
+        // Built from the one table shared with the interpreter; the
+        // key's receiver is `BuiltinMethod::receiver_key` of the
+        // receiver type (arrays erase element type and length).
+        // is_null is universal and handled separately in visit_method_call.
+        //
         // NOTE: numeric value-method registrations (`i64.abs()` /
         // `f64.abs()` / `f64.sqrt()`) lived here as
         // `BuiltinMethod::{I64Abs, F64Abs, F64Sqrt}` entries. Step E
@@ -31,11 +21,10 @@ impl<'a> TypeCheckerVisitor<'a> {
         // resolve through `context.struct_methods` keyed by the
         // canonical primitive name (`"i64"` / `"f64"`) instead of
         // through this builtin-method registry.
-        
-        // Future: Array methods (when ArrayLen etc. are added)
-        // registry.insert((TypeDecl::Array(vec![], 0), "len".to_string()), BuiltinMethod::ArrayLen);
-        // Note: For arrays, we'll need special handling since TypeDecl::Array contains element types
-        
+        let registry: HashMap<(TypeDecl, String), BuiltinMethod> = BuiltinMethod::registry_entries()
+            .into_iter()
+            .map(|(receiver, name, method)| ((receiver, name.to_string()), method))
+            .collect();
         registry
     }
 
@@ -0,0 +1,411 @@
+//! Session-wide cache of function type-check results.
+//!
+//! `FunctionCheckingState::is_checked_fn` only lives as long as one
+//! `TypeCheckerVisitor`, so every `check` / `run` cycle re-walks every
+//! body. `FunctionCheckCache` is owned by the caller (normally
+//! `CompilerSession`) and survives between cycles: a function whose
+//! fingerprint matches a previous successful check is seeded into
+//! `is_checked_fn` instead of being walked again, and the side effects
+//! the walk would have produced (finalized literals, tuple-struct
+//! rewrites, per-expression types) are replayed from the cache.
+//!
+//! A fingerprint covers the function's signature, every expression and
+//! statement reachable from its body and contracts, and an environment
+//! hash over everything a body check can observe: all function
+//! signatures, top-level declarations, consts and module bookkeeping.
+//! Bodies whose return type is inferred (no `-> T`) feed the
+//! environment too, since callers see the inferred type. Pool indices
+//! are part of the fingerprint, so an edit that shifts the pool
+//! re-checks the functions after it. Symbols are hashed by id, which
+//! is only meaningful for programs parsed with the same interner.
+//!
+//! Generic and `extern` functions are never cached, and neither is a
+//! body whose check appended new expressions to the pool.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use string_interner::DefaultSymbol;
+
+use crate::ast::*;
+use crate::type_decl::TypeDecl;
+use super::TypeCheckerVisitor;
+
+/// What a successful check of one function left behind.
+#[derive(Debug, Clone)]
+struct CachedCheck {
+    return_type: TypeDecl,
+    /// Final state of every expression in the function's subtree.
+    exprs: Vec<(ExprRef, Expr)>,
+    expr_types: Vec<(ExprRef, TypeDecl)>,
+}
+
+/// Fingerprints for one batch of functions, computed against the
+/// unchecked pools before any body is walked.
+#[derive(Debug, Clone)]
+pub struct FunctionFingerprints {
+    /// Parallel to the function slice; `None` marks an uncacheable entry.
+    keys: Vec<Option<u64>>,
+    /// Expression pool length before checking. Refs at or beyond it
+    /// were appended by the checker and cannot be replayed.
+    pool_len: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct FunctionCheckCache {
+    entries: HashMap<u64, CachedCheck>,
+    hits: usize,
+    misses: usize,
+}
+
+impl FunctionCheckCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Functions served from the cache since creation.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Cacheable functions that had to be checked from scratch.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Fingerprint `functions` against `program`. Call after module
+    /// integration and alias resolution, before the type checker
+    /// rewrites anything.
+    pub fn fingerprint(&self, program: &Program, functions: &[Rc<Function>]) -> FunctionFingerprints {
+        let env = environment_hash(program);
+        let keys = functions
+            .iter()
+            .map(|func| {
+                if func.is_extern || !func.generic_params.is_empty() {
+                    return None;
+                }
+                let mut hasher = DefaultHasher::new();
+                env.hash(&mut hasher);
+                hash_signature(&mut hasher, &func.name, &func.generic_params, &func.generic_bounds, &func.parameter, &func.return_type);
+                hash_subtree(&mut hasher, program, &function_roots(func));
+                Some(hasher.finish())
+            })
+            .collect();
+        FunctionFingerprints { keys, pool_len: program.expression.len() }
+    }
+
+    /// Seed `tc` with every cached function so `type_check` returns
+    /// early for them, replaying the pool rewrites and expression types
+    /// their check produced.
+    pub fn replay(&mut self, tc: &mut TypeCheckerVisitor, functions: &[Rc<Function>], prints: &FunctionFingerprints) {
+        for (func, key) in functions.iter().zip(&prints.keys) {
+            let Some(key) = key else { continue };
+            let Some(cached) = self.entries.get(key) else {
+                self.misses += 1;
+                continue;
+            };
+            self.hits += 1;
+            for (expr_ref, expr) in &cached.exprs {
+                tc.core.expr_pool.update(expr_ref, expr.clone());
+            }
+            tc.type_inference.expr_types.extend(cached.expr_types.iter().cloned());
+            tc.function_checking.is_checked_fn.insert(func.name, Some(cached.return_type.clone()));
+        }
+    }
+
+    /// Record every function `tc` finished checking successfully.
+    /// Entries that were replayed are left as they are.
+    pub fn record(&mut self, tc: &TypeCheckerVisitor, functions: &[Rc<Function>], prints: &FunctionFingerprints) {
+        for (func, key) in functions.iter().zip(&prints.keys) {
+            let Some(key) = key else { continue };
+            if self.entries.contains_key(key) {
+                continue;
+            }
+            let Some(Some(return_type)) = tc.function_checking.is_checked_fn.get(&func.name) else {
+                continue;
+            };
+            let (expr_refs, _) = collect_subtree(tc.core.expr_pool, tc.core.stmt_pool, &function_roots(func));
+            if expr_refs.iter().any(|e| e.to_index() >= prints.pool_len) {
+                continue;
+            }
+            let exprs = expr_refs
+                .iter()
+                .filter_map(|e| tc.core.expr_pool.get(e).map(|expr| (*e, expr)))
+                .collect();
+            let expr_types = expr_refs
+                .iter()
+                .filter_map(|e| tc.type_inference.expr_types.get(e).map(|ty| (*e, ty.clone())))
+                .collect();
+            self.entries.insert(*key, CachedCheck { return_type: return_type.clone(), exprs, expr_types });
+        }
+    }
+}
+
+fn function_roots(func: &Function) -> (Vec<ExprRef>, Vec<StmtRef>) {
+    let exprs = func.requires.iter().chain(&func.ensures).copied().collect();
+    (exprs, vec![func.code])
+}
+
+/// Every expression and statement reachable from `roots`, in visit order.
+pub(super) fn collect_subtree(
+    expr_pool: &ExprPool,
+    stmt_pool: &StmtPool,
+    roots: &(Vec<ExprRef>, Vec<StmtRef>),
+) -> (Vec<ExprRef>, Vec<StmtRef>) {
+    let mut exprs = Vec::new();
+    let mut stmts = Vec::new();
+    let mut seen_exprs = HashSet::new();
+    let mut seen_stmts = HashSet::new();
+    let mut expr_stack: Vec<ExprRef> = roots.0.iter().rev().copied().collect();
+    let mut stmt_stack: Vec<StmtRef> = roots.1.iter().rev().copied().collect();
+    while !expr_stack.is_empty() || !stmt_stack.is_empty() {
+        if let Some(s) = stmt_stack.pop() {
+            if !seen_stmts.insert(s) {
+                continue;
+            }
+            stmts.push(s);
+            if let Some(stmt) = stmt_pool.get(&s) {
+                let mut children = Vec::new();
+                stmt_children(&stmt, &mut children);
+                expr_stack.extend(children.into_iter().rev());
+            }
+            continue;
+        }
+        let Some(e) = expr_stack.pop() else { break };
+        if !seen_exprs.insert(e) {
+            continue;
+        }
+        exprs.push(e);
+        if let Some(expr) = expr_pool.get(&e) {
+            let mut children = Vec::new();
+            let mut blocks = Vec::new();
+            expr_children(&expr, &mut children, &mut blocks);
+            expr_stack.extend(children.into_iter().rev());
+            stmt_stack.extend(blocks.into_iter().rev());
+        }
+    }
+    (exprs, stmts)
+}
+
+fn stmt_children(stmt: &Stmt, out: &mut Vec<ExprRef>) {
+    match stmt {
+        Stmt::Expression(e) | Stmt::Val(_, _, e) | Stmt::Yield(e) => out.push(*e),
+        Stmt::Var(_, _, e) | Stmt::Return(e) | Stmt::Break(_, e) => out.extend(e.iter().copied()),
+        Stmt::For(_, _, start, end, block) => out.extend([*start, *end, *block]),
+        Stmt::While(_, cond, block) => out.extend([*cond, *block]),
+        Stmt::Continue(_)
+        | Stmt::StructDecl { .. }
+        | Stmt::ImplBlock { .. }
+        | Stmt::TraitDecl { .. }
+        | Stmt::EnumDecl { .. }
+        | Stmt::TypeAlias { .. } => {}
+    }
+}
+
+fn expr_children(expr: &Expr, out: &mut Vec<ExprRef>, blocks: &mut Vec<StmtRef>) {
+    match expr {
+        Expr::Assign(a, b) | Expr::Binary(_, a, b) | Expr::With(a, b) | Expr::Range(a, b) => out.extend([*a, *b]),
+        Expr::IfElifElse(cond, then, elifs, els) => {
+            out.extend([*cond, *then]);
+            for (c, b) in elifs {
+                out.extend([*c, *b]);
+            }
+            out.push(*els);
+        }
+        Expr::Unary(_, e)
+        | Expr::Call(_, e, _)
+        | Expr::FieldAccess(e, _)
+        | Expr::TupleAccess(e, _)
+        | Expr::Cast(e, _)
+        | Expr::Closure { body: e, .. } => out.push(*e),
+        Expr::Block(stmts) => blocks.extend(stmts.iter().copied()),
+        Expr::ExprList(es) | Expr::ArrayLiteral(es) | Expr::TupleLiteral(es) | Expr::BuiltinCall(_, es) | Expr::AssociatedFunctionCall(_, _, es) => {
+            out.extend(es.iter().copied())
+        }
+        Expr::MethodCall(recv, _, args) | Expr::BuiltinMethodCall(recv, _, args) => {
+            out.push(*recv);
+            out.extend(args.iter().copied());
+        }
+        Expr::StructLiteral(_, fields, _) => out.extend(fields.iter().map(|(_, e)| *e)),
+        Expr::SliceAccess(e, info) => {
+            out.push(*e);
+            out.extend(info.start.iter().chain(&info.end).copied());
+        }
+        Expr::SliceAssign(target, info, value) => {
+            out.push(*target);
+            out.extend(info.start.iter().chain(&info.end).copied());
+            out.push(*value);
+        }
+        Expr::DictLiteral(entries) => {
+            for (k, v) in entries {
+                out.extend([*k, *v]);
+            }
+        }
+        Expr::WithResource { resource, body, .. } => out.extend([*resource, *body]),
+        Expr::TryCatch { body, handler, .. } => out.extend([*body, *handler]),
+        Expr::Comprehension { element, value, source, filter, .. } => {
+            out.extend([*source, *element]);
+            out.extend(value.iter().chain(filter).copied());
+        }
+        Expr::Match(scrutinee, arms) => {
+            out.push(*scrutinee);
+            for arm in arms {
+                pattern_children(&arm.pattern, out);
+                out.extend(arm.guard.iter().copied());
+                out.push(arm.body);
+            }
+        }
+        Expr::True
+        | Expr::False
+        | Expr::Int64(_)
+        | Expr::UInt64(_)
+        | Expr::Int8(_)
+        | Expr::Int16(_)
+        | Expr::Int32(_)
+        | Expr::UInt8(_)
+        | Expr::UInt16(_)
+        | Expr::UInt32(_)
+        | Expr::Float64(_)
+        | Expr::Number(_)
+        | Expr::Identifier(_)
+        | Expr::Null
+        | Expr::String(_)
+        | Expr::Bytes(_)
+        | Expr::QualifiedIdentifier(_) => {}
+    }
+}
+
+fn pattern_children(pattern: &Pattern, out: &mut Vec<ExprRef>) {
+    match pattern {
+        Pattern::Literal(e) => out.push(*e),
+        Pattern::EnumVariant(_, _, subs) | Pattern::Tuple(subs) => {
+            for sub in subs {
+                pattern_children(sub, out);
+            }
+        }
+        Pattern::Name(_) | Pattern::Wildcard => {}
+    }
+}
+
+/// Hash the nodes reachable from `roots` together with their pool
+/// positions. Node `Debug` output carries child refs, so structure and
+/// placement both feed the hash.
+fn hash_subtree<H: Hasher>(hasher: &mut H, program: &Program, roots: &(Vec<ExprRef>, Vec<StmtRef>)) {
+    let (exprs, stmts) = collect_subtree(&program.expression, &program.statement, roots);
+    roots.0.hash(hasher);
+    roots.1.hash(hasher);
+    for e in &exprs {
+        e.hash(hasher);
+        format!("{:?}", program.expression.get(e)).hash(hasher);
+    }
+    for s in &stmts {
+        s.hash(hasher);
+        format!("{:?}", program.statement.get(s)).hash(hasher);
+    }
+}
+
+/// `HashMap`'s `Debug` order varies between instances, so bounds are
+/// hashed in symbol order.
+fn hash_bounds<H: Hasher>(hasher: &mut H, bounds: &HashMap<DefaultSymbol, TypeDecl>) {
+    let mut sorted: Vec<_> = bounds.iter().collect();
+    sorted.sort_by_key(|(sym, _)| **sym);
+    format!("{:?}", sorted).hash(hasher);
+}
+
+fn hash_signature<H: Hasher>(
+    hasher: &mut H,
+    name: &DefaultSymbol,
+    generic_params: &[DefaultSymbol],
+    generic_bounds: &HashMap<DefaultSymbol, TypeDecl>,
+    parameter: &ParameterList,
+    return_type: &Option<TypeDecl>,
+) {
+    name.hash(hasher);
+    generic_params.hash(hasher);
+    hash_bounds(hasher, generic_bounds);
+    format!("{:?}{:?}", parameter, return_type).hash(hasher);
+}
+
+fn hash_method<H: Hasher>(hasher: &mut H, program: &Program, method: &MethodFunction) {
+    hash_signature(hasher, &method.name, &method.generic_params, &method.generic_bounds, &method.parameter, &method.return_type);
+    (method.has_self_param, method.self_is_mut).hash(hasher);
+    format!("{:?}", method.visibility).hash(hasher);
+    let mut contracts = method.requires.clone();
+    contracts.extend(&method.ensures);
+    if method.return_type.is_none() {
+        hash_subtree(hasher, program, &(contracts, vec![method.code]));
+    } else {
+        hash_subtree(hasher, program, &(contracts, Vec::new()));
+    }
+}
+
+/// Everything outside a function body that checking the body can see.
+fn environment_hash(program: &Program) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for func in &program.function {
+        hash_signature(&mut hasher, &func.name, &func.generic_params, &func.generic_bounds, &func.parameter, &func.return_type);
+        func.is_extern.hash(&mut hasher);
+        if func.return_type.is_none() {
+            hash_subtree(&mut hasher, program, &function_roots(func));
+        }
+    }
+    for c in &program.consts {
+        format!("{:?}{:?}{:?}", c.name, c.type_decl, c.visibility).hash(&mut hasher);
+        hash_subtree(&mut hasher, program, &(vec![c.value], Vec::new()));
+    }
+    let mut imported: Vec<_> = program.imported_function_names.iter().collect();
+    imported.sort();
+    imported.hash(&mut hasher);
+    format!("{:?}", program.function_module_paths).hash(&mut hasher);
+    for i in 0..program.statement.len() {
+        let stmt_ref = StmtRef(i as u32);
+        let Some(stmt) = program.statement.get(&stmt_ref) else { continue };
+        match &stmt {
+            Stmt::StructDecl { name, generic_params, generic_bounds, fields, visibility } => {
+                (name, generic_params).hash(&mut hasher);
+                hash_bounds(&mut hasher, generic_bounds);
+                format!("{:?}{:?}", fields, visibility).hash(&mut hasher);
+            }
+            Stmt::ImplBlock { target_type, target_type_args, methods, trait_name, trait_type_args } => {
+                (target_type, trait_name).hash(&mut hasher);
+                format!("{:?}{:?}", target_type_args, trait_type_args).hash(&mut hasher);
+                for method in methods {
+                    hash_method(&mut hasher, program, method);
+                }
+            }
+            Stmt::TraitDecl { name, generic_params, methods, visibility } => {
+                (name, generic_params).hash(&mut hasher);
+                format!("{:?}", visibility).hash(&mut hasher);
+                for m in methods {
+                    hash_signature(&mut hasher, &m.name, &m.generic_params, &m.generic_bounds, &m.parameter, &m.return_type);
+                    (m.has_self_param, m.self_is_mut).hash(&mut hasher);
+                    let mut contracts = m.requires.clone();
+                    contracts.extend(&m.ensures);
+                    hash_subtree(&mut hasher, program, &(contracts, Vec::new()));
+                }
+            }
+            Stmt::EnumDecl { .. } | Stmt::TypeAlias { .. } => {
+                stmt_ref.hash(&mut hasher);
+                format!("{:?}", stmt).hash(&mut hasher);
+            }
+            _ => {}
+        }
+    }
+    hasher.finish()
+}
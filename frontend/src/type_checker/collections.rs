@@ -1,5 +1,6 @@
 use crate::ast::*;
 use crate::type_decl::*;
+use string_interner::DefaultSymbol;
 use crate::type_checker::{
     TypeCheckerVisitor, TypeCheckError
 };
@@ -98,6 +99,33 @@ impl<'a> TypeCheckerVisitor<'a> {
                     }
                 }
             }
+            TypeDecl::Bytes => {
+                // Byte buffers index like arrays (negative indices
+                // count from the end) but always read back as `u64`
+                // so callers never juggle narrow-int arithmetic.
+                let original_hint = self.type_inference.type_hint.clone();
+                self.type_inference.type_hint = Some(TypeDecl::Int64);
+                for index_expr in [&slice_info.start, &slice_info.end].into_iter().flatten() {
+                    let index_type = self.visit_expr(index_expr)?;
+                    match index_type {
+                        TypeDecl::UInt64 | TypeDecl::Int64 | TypeDecl::Unknown => {}
+                        TypeDecl::Number => {
+                            self.transform_numeric_expr(index_expr, &TypeDecl::Int64)?;
+                        }
+                        _ => {
+                            self.type_inference.type_hint = original_hint;
+                            return Err(TypeCheckError::array_error(&format!(
+                                "bytes index must be an integer type, but got {:?}", index_type
+                            )));
+                        }
+                    }
+                }
+                self.type_inference.type_hint = original_hint;
+                match slice_info.slice_type {
+                    SliceType::SingleElement => Ok(TypeDecl::UInt64),
+                    SliceType::RangeSlice => Ok(TypeDecl::Bytes),
+                }
+            }
             TypeDecl::Dict(ref key_type, ref value_type) => {
                 // Dictionary access: dict[key] (only single element access, not slicing)
                 if slice_info.is_valid_for_dict() {
@@ -106,7 +134,7 @@ impl<'a> TypeCheckerVisitor<'a> {
                         let index_type = self.visit_expr(index_expr)?;
 
                         // Verify the index type matches the key type
-                        if index_type != **key_type {
+                        if !key_type.is_equivalent(&index_type) {
                             return Err(TypeCheckError::type_mismatch(
                                 *key_type.clone(), index_type
                             ));
@@ -135,6 +163,23 @@ impl<'a> TypeCheckerVisitor<'a> {
                     self.check_struct_getslice_method(struct_name, slice_info, &object_type)
                 }
             }
+            TypeDecl::Unknown => {
+                // Dynamically typed value (e.g. a `json_parse` result):
+                // `v[key]` / `v[i]` is checked at run time and yields
+                // another dynamic value. Untyped integer indices are
+                // pinned to u64, the array index type.
+                if slice_info.is_valid_for_dict() {
+                    if let Some(index_expr) = &slice_info.start {
+                        let index_type = self.visit_expr(index_expr)?;
+                        if index_type == TypeDecl::Number {
+                            self.transform_numeric_expr(index_expr, &TypeDecl::UInt64)?;
+                        }
+                    }
+                    Ok(TypeDecl::Unknown)
+                } else {
+                    Err(TypeCheckError::generic_error("Range slicing of a dynamically typed value is not supported"))
+                }
+            }
             _ => {
                 Err(TypeCheckError::generic_error(&format!(
                     "Cannot access type {:?} - only arrays, dictionaries, and structs with __getitem__ are supported", object_type
@@ -144,23 +189,25 @@ impl<'a> TypeCheckerVisitor<'a> {
     }
 
     /// Type check slice assignment - implementation
-    pub fn visit_slice_assign_impl(&mut self, object: &ExprRef, start: &Option<ExprRef>, end: &Option<ExprRef>, value: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+    pub fn visit_slice_assign_impl(&mut self, object: &ExprRef, slice_info: &SliceInfo, value: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
         let object_type = self.visit_expr(object)?;
+        self.check_assignment_root(object, true)?;
         let value_type = self.visit_expr(value)?;
+        let (start, end) = (&slice_info.start, &slice_info.end);
 
         match object_type {
             TypeDecl::Array(ref element_types, _size) => {
-                self.handle_array_slice_assign(element_types, start, end, &value_type)
+                self.handle_array_slice_assign(element_types, slice_info, &value_type)
             }
             TypeDecl::Dict(ref key_type, ref dict_value_type) => {
                 // Dictionary assignment: dict[key] = value (only single element assignment)
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: dict[key] = value
                     if let Some(key_expr) = start {
                         let key_type_result = self.visit_expr(key_expr)?;
 
                         // Verify the key type matches the dictionary key type
-                        if key_type_result != **key_type {
+                        if !key_type.is_equivalent(&key_type_result) {
                             return Err(TypeCheckError::type_mismatch(
                                 *key_type.clone(), key_type_result
                             ));
@@ -197,7 +244,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             }
             TypeDecl::Identifier(struct_name) => {
                 // Struct assignment: check for __setitem__ method (only single element assignment)
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: struct[key] = value
                     if let Some(key_expr) = start {
                         let struct_name_str = self.core.string_interner.resolve(struct_name)
@@ -250,7 +297,7 @@ impl<'a> TypeCheckerVisitor<'a> {
                 let struct_name_str = self.core.string_interner.resolve(struct_name)
                     .ok_or_else(|| TypeCheckError::generic_error("Unknown struct name"))?;
 
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: struct[key] = value - use __setitem__
                     if let Some(key_expr) = start {
                         let key_type_result = self.visit_expr(key_expr)?;
@@ -289,6 +336,9 @@ impl<'a> TypeCheckerVisitor<'a> {
                     self.check_struct_setslice_method(struct_name, start, end, &value_type, &object_type)
                 }
             }
+            TypeDecl::Bytes => {
+                Err(TypeCheckError::generic_error("bytes values are immutable - build a new buffer instead of assigning through an index"))
+            }
             _ => {
                 Err(TypeCheckError::generic_error(&format!(
                     "Cannot assign to type {:?} - only arrays, dictionaries, and structs with __setitem__ are supported", object_type
@@ -302,6 +352,7 @@ impl<'a> TypeCheckerVisitor<'a> {
         if entries.is_empty() {
             // Empty dict - type will be inferred from usage or type hint
             if let Some(TypeDecl::Dict(key_type, value_type)) = &self.type_inference.type_hint {
+                self.check_dict_key_hashable(key_type)?;
                 return Ok(TypeDecl::Dict(key_type.clone(), value_type.clone()));
             }
             return Ok(TypeDecl::Dict(Box::new(TypeDecl::Unknown), Box::new(TypeDecl::Unknown)));
@@ -410,9 +461,147 @@ impl<'a> TypeCheckerVisitor<'a> {
             }
         }
 
+        self.check_dict_key_hashable(&final_key_type)?;
         Ok(TypeDecl::Dict(Box::new(final_key_type), Box::new(final_value_type)))
     }
 
+    /// Type check `[elem for x in src if cond]` and the dict form
+    /// `dict{key: value for x in src if cond}`. `src` is a range or an
+    /// array; the result type comes from the body. An unfiltered
+    /// comprehension over a constant range or a sized array keeps the
+    /// static length, so it can initialize a `[T; N]` binding.
+    pub fn visit_comprehension_impl(&mut self, element: &ExprRef, value: &Option<ExprRef>, var: DefaultSymbol, source: &ExprRef, filter: &Option<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
+        let original_hint = self.type_inference.type_hint.clone();
+        let (element_hint, value_hint) = match (&original_hint, value) {
+            (Some(TypeDecl::Array(items, _)), None) => (items.first().cloned(), None),
+            (Some(TypeDecl::Dict(k, v)), Some(_)) => (Some(k.as_ref().clone()), Some(v.as_ref().clone())),
+            _ => (None, None),
+        };
+
+        self.type_inference.type_hint = None;
+        let source_ty = self.visit_expr(source)?;
+        let (item_ty, static_len) = match (&source_ty, self.core.expr_pool.get(source)) {
+            (TypeDecl::Range(item), Some(Expr::Range(start, end))) => {
+                for bound in [&start, &end] {
+                    self.transform_numeric_expr(bound, item)?;
+                }
+                let len = match (self.extract_constant_value(&start), self.extract_constant_value(&end)) {
+                    (Some(s), Some(e)) => Some(e.saturating_sub(s).max(0) as usize),
+                    _ => None,
+                };
+                (item.as_ref().clone(), len)
+            }
+            (TypeDecl::Range(item), _) => (item.as_ref().clone(), None),
+            (TypeDecl::Array(items, size), _) if !items.is_empty() => {
+                (items[0].clone(), if *size > 0 { Some(*size) } else { None })
+            }
+            _ => {
+                self.type_inference.type_hint = original_hint;
+                return Err(TypeCheckError::generic_error(&format!(
+                    "comprehension source must be a range or an array, got {:?}",
+                    source_ty
+                )));
+            }
+        };
+
+        self.push_context();
+        self.context.set_var(var, item_ty);
+        let result = self.check_comprehension_body(element, value, filter, element_hint, value_hint);
+        self.pop_context();
+        self.type_inference.type_hint = original_hint;
+        let (element_ty, value_ty) = result?;
+
+        match value_ty {
+            Some(value_ty) => {
+                self.check_dict_key_hashable(&element_ty)?;
+                Ok(TypeDecl::Dict(Box::new(element_ty), Box::new(value_ty)))
+            }
+            None => match static_len {
+                Some(n) if n > 0 && filter.is_none() => Ok(TypeDecl::Array(vec![element_ty; n], n)),
+                _ => Ok(TypeDecl::Array(vec![element_ty], 0)),
+            },
+        }
+    }
+
+    /// Check the filter and body of a comprehension with its loop
+    /// variable already in scope. Untyped numeric literals settle on the
+    /// hinted integer type, or `u64` like any other literal.
+    fn check_comprehension_body(&mut self, element: &ExprRef, value: &Option<ExprRef>, filter: &Option<ExprRef>, element_hint: Option<TypeDecl>, value_hint: Option<TypeDecl>) -> Result<(TypeDecl, Option<TypeDecl>), TypeCheckError> {
+        if let Some(filter) = filter {
+            self.type_inference.type_hint = None;
+            let filter_ty = self.visit_expr(filter)?;
+            if filter_ty != TypeDecl::Bool {
+                return Err(TypeCheckError::type_mismatch(TypeDecl::Bool, filter_ty)
+                    .with_context("comprehension filter"));
+            }
+        }
+        let element_ty = self.check_comprehension_part(element, element_hint)?;
+        let value_ty = match value {
+            Some(value) => Some(self.check_comprehension_part(value, value_hint)?),
+            None => None,
+        };
+        Ok((element_ty, value_ty))
+    }
+
+    fn check_comprehension_part(&mut self, expr: &ExprRef, hint: Option<TypeDecl>) -> Result<TypeDecl, TypeCheckError> {
+        self.type_inference.type_hint = hint.clone();
+        let ty = self.visit_expr(expr)?;
+        match ty {
+            TypeDecl::Number => {
+                let target = match hint {
+                    Some(TypeDecl::Int64) => TypeDecl::Int64,
+                    _ => TypeDecl::UInt64,
+                };
+                self.transform_numeric_expr(expr, &target)?;
+                Ok(target)
+            }
+            TypeDecl::Unknown => Ok(hint.unwrap_or(TypeDecl::Unknown)),
+            ty => Ok(ty),
+        }
+    }
+
+    /// Rejects dict key types that have no stable equality / hash:
+    /// closures, allocators, references and dicts themselves, also
+    /// when nested inside an array, tuple or struct field. A struct's
+    /// own `eq` does not lift this, since the dict still stores its
+    /// keys by their structural hash.
+    pub fn check_dict_key_hashable(&self, key_type: &TypeDecl) -> Result<(), TypeCheckError> {
+        match self.unhashable_part(key_type, &mut Vec::new()) {
+            Some(part) if part == *key_type => Err(TypeCheckError::generic_error(&format!(
+                "dict key type '{}' is not hashable",
+                self.type_name_for_error(key_type)
+            ))),
+            Some(part) => Err(TypeCheckError::generic_error(&format!(
+                "dict key type '{}' is not hashable: it contains '{}'",
+                self.type_name_for_error(key_type),
+                self.type_name_for_error(&part)
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn unhashable_part(&self, ty: &TypeDecl, visiting: &mut Vec<DefaultSymbol>) -> Option<TypeDecl> {
+        match ty {
+            TypeDecl::Function(_, _) | TypeDecl::Allocator | TypeDecl::Dict(_, _) | TypeDecl::Ref { .. } => {
+                Some(ty.clone())
+            }
+            TypeDecl::Array(elems, _) | TypeDecl::Tuple(elems) => {
+                elems.iter().find_map(|elem| self.unhashable_part(elem, visiting))
+            }
+            TypeDecl::Struct(name, _) | TypeDecl::Identifier(name) => {
+                if visiting.contains(name) {
+                    return None;
+                }
+                let fields = self.context.get_struct_fields(*name)?;
+                visiting.push(*name);
+                let found = fields.iter().find_map(|field| self.unhashable_part(&field.type_decl, visiting));
+                visiting.pop();
+                found
+            }
+            _ => None,
+        }
+    }
+
     /// Type check tuple literals - implementation
     pub fn visit_tuple_literal_impl(&mut self, elements: &Vec<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
         if elements.is_empty() {
@@ -471,6 +660,11 @@ impl<'a> TypeCheckerVisitor<'a> {
                 }
                 Ok(types[index].clone())
             }
+            TypeDecl::Struct(name, _) | TypeDecl::Identifier(name)
+                if self.context.struct_definitions.get(&name).is_some_and(|def| def.is_tuple_struct()) =>
+            {
+                self.visit_tuple_struct_field(tuple, index, name)
+            }
             _ => {
                 Err(TypeCheckError::generic_error(&format!(
                     "Cannot access index {} on non-tuple type {:?}",
@@ -694,7 +888,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             Expr::Int64(val) => Some(val),
             Expr::Number(symbol) => {
                 let num_str = self.core.string_interner.resolve(symbol)?;
-                num_str.parse::<i64>().ok()
+                crate::token::parse_int_literal(num_str).and_then(|n| i64::try_from(n).ok())
             }
             _ => None,
         }
@@ -23,6 +23,14 @@ pub struct StructDefinition {
     pub visibility: Visibility,
 }
 
+impl StructDefinition {
+    /// `struct Meters(u64)`: the fields are positional and named
+    /// `"0"`, `"1"`, .. by the parser, a name no `{ .. }` field can have.
+    pub fn is_tuple_struct(&self) -> bool {
+        self.fields.first().is_some_and(|f| f.name == "0")
+    }
+}
+
 #[derive(Debug)]
 pub struct TypeCheckContext {
     pub vars: Vec<HashMap<DefaultSymbol, VarState>>,
@@ -88,12 +96,57 @@ pub struct TypeCheckContext {
     /// collision-free. The `ExprPool` is append-only so indices stay
     /// stable across the type-check pass.
     pub closure_captures: HashMap<crate::ast::ExprRef, Vec<(DefaultSymbol, TypeDecl)>>,
-    /// LABEL: stack of currently-active loop labels (innermost on top).
-    /// `Some(sym)` for `@label: while`, `None` for an unlabelled loop.
+    /// LABEL: stack of currently-active loops (innermost on top).
     /// `visit_break_impl` / `visit_continue_impl` walk this stack
     /// (rev-iter for labelled targets) to validate that a label exists
     /// in scope and that bare `break` / `continue` is inside *some* loop.
-    pub loop_label_stack: Vec<Option<DefaultSymbol>>,
+    pub loop_stack: Vec<LoopFrame>,
+    /// Item type of the generator function being checked (`T` for a
+    /// function returning `Generator<T>`); `None` everywhere else, so
+    /// `yield` outside a generator body is rejected.
+    pub generator_item: Option<TypeDecl>,
+    /// Source offset of the first registration of each top-level
+    /// function (under its module qualifier) and struct. Registering
+    /// the same declaration again is a no-op; a different one under
+    /// the same key lands in `duplicate_definitions`.
+    pub definition_sites: HashMap<(DefinitionKind, Option<DefaultSymbol>, DefaultSymbol), usize>,
+    pub duplicate_definitions: Vec<DuplicateDefinition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefinitionKind {
+    Function,
+    Struct,
+}
+
+/// Two declarations of the same name. `first` and `second` are source
+/// offsets, in registration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateDefinition {
+    pub kind: DefinitionKind,
+    pub name: DefaultSymbol,
+    pub first: usize,
+    pub second: usize,
+}
+
+/// One active loop, as seen by `break` / `continue`.
+#[derive(Debug, Clone)]
+pub struct LoopFrame {
+    /// `Some(sym)` for `@label: while`, `None` for an unlabelled loop.
+    pub label: Option<DefaultSymbol>,
+    /// `loop { ... }` / `while true { ... }`: the only form that can
+    /// exit solely through `break`, so the only one allowed to take
+    /// `break value`.
+    pub is_infinite: bool,
+    /// Type of the `break value` statements seen so far; all of them
+    /// must agree, and it becomes the type of the loop.
+    pub break_type: Option<TypeDecl>,
+}
+
+impl LoopFrame {
+    pub fn new(label: Option<DefaultSymbol>, is_infinite: bool) -> Self {
+        Self { label, is_infinite, break_type: None }
+    }
 }
 
 impl Default for TypeCheckContext {
@@ -122,7 +175,10 @@ impl TypeCheckContext {
             pending_trait_type_args: Vec::new(),
             struct_trait_impls: HashMap::new(),
             closure_captures: HashMap::new(),
-            loop_label_stack: Vec::new(),
+            loop_stack: Vec::new(),
+            generator_item: None,
+            definition_sites: HashMap::new(),
+            duplicate_definitions: Vec::new(),
         }
     }
 
@@ -168,7 +224,7 @@ impl TypeCheckContext {
     /// user-authored slot). Use `set_fn_with_module` for integrated
     /// `pub fn`s that came in through module integration.
     pub fn set_fn(&mut self, name: DefaultSymbol, f: Rc<Function>) {
-        self.functions.insert((None, name), f);
+        self.set_fn_with_module(None, name, f);
     }
 
     /// Module-aware registration. `qualifier` is `Some(last_segment)`
@@ -180,9 +236,30 @@ impl TypeCheckContext {
         name: DefaultSymbol,
         f: Rc<Function>,
     ) {
+        // Repeated `extern fn` declarations all bind the same runtime
+        // symbol (the prelude and `math` both declare `__extern_abs_f64`).
+        if !f.is_extern {
+            self.record_definition(DefinitionKind::Function, qualifier, name, f.node.start);
+        }
         self.functions.insert((qualifier, name), f);
     }
 
+    /// Note that a `kind` declaration named `name` starts at `offset`,
+    /// recording a duplicate when another one was registered first.
+    pub fn record_definition(
+        &mut self,
+        kind: DefinitionKind,
+        qualifier: Option<DefaultSymbol>,
+        name: DefaultSymbol,
+        offset: usize,
+    ) {
+        let first = *self.definition_sites.entry((kind, qualifier, name)).or_insert(offset);
+        let duplicate = DuplicateDefinition { kind, name, first, second: offset };
+        if first != offset && !self.duplicate_definitions.contains(&duplicate) {
+            self.duplicate_definitions.push(duplicate);
+        }
+    }
+
     pub fn get_var(&self, name: DefaultSymbol) -> Option<TypeDecl> {
         for v in self.vars.iter().rev() {
             let v_val = v.get(&name);
@@ -324,8 +401,11 @@ impl TypeCheckContext {
         if let Some(definition) = self.get_struct_fields(struct_name) {
             // Check if all required fields are provided
             for required_field in definition {
-                let field_name_symbol = string_interner.string_interner.get(&required_field.name).unwrap_or_else(|| panic!("Field name not found in string interner"));
-                let field_provided = provided_fields.iter().any(|(name, _)| *name == field_name_symbol);
+                // Compare by resolved name: a field the source never
+                // mentions may not be interned at all.
+                let interner = &string_interner.string_interner;
+                let field_provided = provided_fields.iter()
+                    .any(|(name, _)| interner.resolve(*name) == Some(required_field.name.as_str()));
                 if !field_provided {
                     return Err(TypeCheckError::generic_error(&format!(
                         "Missing required field '{}' in struct '{:?}'", 
@@ -337,8 +417,7 @@ impl TypeCheckContext {
             // Check if any extra fields are provided
             for (provided_field_name, _) in provided_fields {
                 let field_valid = definition.iter().any(|def| {
-                    let def_field_symbol = string_interner.string_interner.get(&def.name).unwrap_or_else(|| panic!("Field name not found in string interner"));
-                    def_field_symbol == *provided_field_name
+                    string_interner.string_interner.resolve(*provided_field_name) == Some(def.name.as_str())
                 });
                 if !field_valid {
                     return Err(TypeCheckError::generic_error(&format!(
@@ -1,3 +1,4 @@
+use crate::error_code;
 use crate::type_decl::TypeDecl;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -44,7 +45,8 @@ impl<T> MultipleTypeCheckResult<T> {
 pub enum TypeCheckErrorKind {
     TypeMismatch { expected: TypeDecl, actual: TypeDecl },
     TypeMismatchOperation(Box<TypeMismatchOperationError>),
-    NotFound { item_type: String, name: String },
+    /// `fix` is a suggested edit that resolves the error, if one is known.
+    NotFound { item_type: String, name: String, fix: Option<Box<FixIt>> },
     UnsupportedOperation { operation: String, type_name: TypeDecl },
     ConversionError { from: String, to: String },
     ArrayError { message: String },
@@ -54,6 +56,24 @@ pub enum TypeCheckErrorKind {
     GenericError { message: String },
 }
 
+impl TypeCheckErrorKind {
+    /// Stable diagnostic code (see `crate::error_code`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckErrorKind::TypeMismatch { .. } => error_code::E_TYPE_MISMATCH,
+            TypeCheckErrorKind::TypeMismatchOperation(_) => error_code::E_OPERAND_MISMATCH,
+            TypeCheckErrorKind::NotFound { .. } => error_code::E_NOT_FOUND,
+            TypeCheckErrorKind::UnsupportedOperation { .. } => error_code::E_UNSUPPORTED_OPERATION,
+            TypeCheckErrorKind::ConversionError { .. } => error_code::E_CONVERSION,
+            TypeCheckErrorKind::ArrayError { .. } => error_code::E_ARRAY,
+            TypeCheckErrorKind::MethodError(_) => error_code::E_METHOD,
+            TypeCheckErrorKind::InvalidLiteral { .. } => error_code::E_INVALID_LITERAL,
+            TypeCheckErrorKind::AccessDenied { .. } => error_code::E_ACCESS_DENIED,
+            TypeCheckErrorKind::GenericError { .. } => error_code::E_TYPE_CHECK_GENERIC,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeMismatchOperationError {
     pub operation: String,
@@ -68,6 +88,51 @@ pub struct MethodErrorData {
     pub reason: String,
 }
 
+/// A machine-applicable suggestion attached to a diagnostic: insert
+/// `text` at byte `offset` of the checked source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixIt {
+    pub message: String,
+    pub offset: usize,
+    pub text: String,
+}
+
+impl FixIt {
+    /// Add `import <module>` below the last `import` line of `source`,
+    /// else below its `package` line, else at the top.
+    pub fn add_import(source: &str, module: &str) -> Self {
+        let mut last_import = None;
+        let mut package = None;
+        let mut start = 0;
+        for line in source.split_inclusive('\n') {
+            let end = start + line.len();
+            if line.starts_with("import ") {
+                last_import = Some(end);
+            } else if line.starts_with("package ") {
+                package = Some(end);
+            }
+            start = end;
+        }
+        let import = format!("import {}", module);
+        let (offset, text) = match (last_import, package) {
+            (Some(end), _) => (end, format!("{}\n", import)),
+            (None, Some(end)) => (end, format!("\n{}\n", import)),
+            (None, None) => (0, format!("{}\n\n", import)),
+        };
+        // A last line without its newline needs one before the import.
+        let text = if offset == source.len() && !source.is_empty() && !source.ends_with('\n') {
+            format!("\n{}", text.trim_end_matches('\n'))
+        } else {
+            text
+        };
+        Self {
+            message: format!("add `{}`", import),
+            offset,
+            text,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeCheckError {
     pub kind: TypeCheckErrorKind,
@@ -101,6 +166,7 @@ impl TypeCheckError {
             kind: TypeCheckErrorKind::NotFound {
                 item_type: item_type.to_string(),
                 name: name.to_string(),
+                fix: None,
             },
             context: None,
             location: None,
@@ -192,9 +258,29 @@ impl TypeCheckError {
         self
     }
 
+    /// Attach a suggested edit. Only not-found errors carry one; on any
+    /// other kind this is a no-op.
+    pub fn with_fix(mut self, fix: FixIt) -> Self {
+        if let TypeCheckErrorKind::NotFound { fix: slot, .. } = &mut self.kind {
+            *slot = Some(Box::new(fix));
+        }
+        self
+    }
+
+    pub fn fix(&self) -> Option<&FixIt> {
+        match &self.kind {
+            TypeCheckErrorKind::NotFound { fix, .. } => fix.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn new(msg: String) -> Self {
         Self::generic_error(&msg)
     }
+
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
 }
 
 impl std::fmt::Display for TypeCheckError {
@@ -206,7 +292,7 @@ impl std::fmt::Display for TypeCheckError {
             TypeCheckErrorKind::TypeMismatchOperation(data) => {
                 format!("Type mismatch in {} operation: incompatible types {:?} and {:?}", data.operation, data.left, data.right)
             }
-            TypeCheckErrorKind::NotFound { item_type, name } => {
+            TypeCheckErrorKind::NotFound { item_type, name, .. } => {
                 format!("{} '{}' not found", item_type, name)
             }
             TypeCheckErrorKind::UnsupportedOperation { operation, type_name } => {
@@ -192,6 +192,7 @@ impl<'a> ErrorHandling for TypeCheckerVisitor<'a> {
             TypeDecl::Float64 => "f64".to_string(),
             TypeDecl::Bool => "bool".to_string(),
             TypeDecl::String => "str".to_string(),
+            TypeDecl::Bytes => "bytes".to_string(),
             TypeDecl::Unit => "()".to_string(),
             TypeDecl::Array(element_types, size) => {
                 if element_types.len() == 1 {
@@ -227,6 +228,7 @@ impl<'a> ErrorHandling for TypeCheckerVisitor<'a> {
                 format!("Generic({})", param_str)
             },
             TypeDecl::Self_ => "Self".to_string(),
+            TypeDecl::Generator(elem) => format!("Generator<{}>", self.format_type_for_error(elem)),
             TypeDecl::Identifier(name) => {
                 let name_str = self.resolve_symbol_name(*name);
                 format!("Identifier({})", name_str)
@@ -51,6 +51,40 @@ impl<'a> TypeCheckerVisitor<'a> {
         }
     }
 
+    /// Assignment through a place path (`a.b.c = x`, `arr[i].f = x`,
+    /// `t.0 = x`) mutates the binding at its root, so that binding must
+    /// be a `var`. Bare `name = x` is left to the runtime check, and
+    /// `self` is exempt: value-`self` methods mutate their own copy.
+    /// `indexed` is set for the object of `x[i] = v` / `x[a..b] = v`,
+    /// which writes into `x` even when it is a bare name.
+    pub(super) fn check_assignment_root(&self, lhs: &ExprRef, indexed: bool) -> Result<(), TypeCheckError> {
+        let mut cur = *lhs;
+        let mut is_path = indexed;
+        let root = loop {
+            match self.core.expr_pool.get(&cur) {
+                Some(Expr::Identifier(sym)) => break sym,
+                Some(Expr::FieldAccess(obj, _)) | Some(Expr::TupleAccess(obj, _)) => cur = obj,
+                Some(Expr::SliceAccess(obj, info)) if matches!(info.slice_type, SliceType::SingleElement) => cur = obj,
+                _ => return Ok(()),
+            }
+            is_path = true;
+        };
+        if !is_path || self.context.is_var_mutable(root) != Some(false) {
+            return Ok(());
+        }
+        let name = self.core.string_interner.resolve(root).unwrap_or("?").to_string();
+        if name == "self" {
+            return Ok(());
+        }
+        Err(self.error_with_location(
+            TypeCheckError::generic_error(&format!(
+                "cannot assign through `{}`: binding is not declared `var`",
+                name
+            )),
+            &cur,
+        ))
+    }
+
     /// Main entry point for expression type checking
     pub fn visit_expr(&mut self, expr: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
         // Check cache first
@@ -60,10 +94,10 @@ impl<'a> TypeCheckerVisitor<'a> {
         
         // Set up context hint for nested expressions
         let original_hint = self.type_inference.type_hint.clone();
-        let expr_obj = self.core.expr_pool.get(expr)
+        let mut expr_obj = self.core.expr_pool.get(expr)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference"))?;
         
-        let result = expr_obj.clone().accept(self);
+        let result = expr_obj.accept(self);
         
         // Add location information to errors if not already present
         let result = match result {
@@ -94,10 +128,11 @@ impl<'a> TypeCheckerVisitor<'a> {
         let op = op.clone();
         let operand = *operand;
         let operand_ty = {
-            let operand_obj = self.core.expr_pool.get(&operand)
+            let mut operand_obj = self.core.expr_pool.get(&operand)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid operand expression reference"))?;
-            operand_obj.clone().accept(self)?
+            operand_obj.accept(self)?
         };
+        self.type_inference.set_expr_type(operand, operand_ty.clone());
 
         // REF-Stage-2: explicit `&expr` / `&mut expr` short-circuit
         // before the Number/coercion logic runs, since wrapping an
@@ -265,16 +300,18 @@ impl<'a> TypeCheckerVisitor<'a> {
         let rhs = *rhs;
 
         let lhs_ty = {
-            let lhs_obj = self.core.expr_pool.get(&lhs)
+            let mut lhs_obj = self.core.expr_pool.get(&lhs)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid left-hand expression reference"))?;
-            lhs_obj.clone().accept(self)?
+            lhs_obj.accept(self)?
         };
+        self.type_inference.set_expr_type(lhs, lhs_ty.clone());
 
         let rhs_ty = {
-            let rhs_obj = self.core.expr_pool.get(&rhs)
+            let mut rhs_obj = self.core.expr_pool.get(&rhs)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid right-hand expression reference"))?;
-            rhs_obj.clone().accept(self)?
+            rhs_obj.accept(self)?
         };
+        self.type_inference.set_expr_type(rhs, rhs_ty.clone());
 
         // Operator overload (Phase B continuation): arithmetic ops
         // between matching struct values dispatch to `add` / `sub` /
@@ -585,8 +622,18 @@ impl<'a> TypeCheckerVisitor<'a> {
         }
     }
 
-    /// Type check block expressions
+    /// Type check block expressions. A block is a scope: `val` /
+    /// `var` declared inside it are dropped when it ends, whether it
+    /// is a bare `{ }` or the body of an `if` / `elif` / `else`,
+    /// a loop or a match arm.
     pub fn visit_block(&mut self, statements: &Vec<StmtRef>) -> Result<TypeDecl, TypeCheckError> {
+        self.push_context();
+        let res = self.visit_block_statements(statements);
+        self.pop_context();
+        res
+    }
+
+    fn visit_block_statements(&mut self, statements: &[StmtRef]) -> Result<TypeDecl, TypeCheckError> {
         let mut last_empty = true;
         let mut last: Option<TypeDecl> = None;
         
@@ -615,9 +662,9 @@ impl<'a> TypeCheckerVisitor<'a> {
                 Stmt::Return(ret_ty) => {
                     if let Some(e) = ret_ty {
                         let e = e;
-                        let expr_obj = self.core.expr_pool.get(&e)
+                        let mut expr_obj = self.core.expr_pool.get(&e)
                             .ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference in return"))?;
-                        let ty = expr_obj.clone().accept(self)?;
+                        let ty = expr_obj.accept(self)?;
                         if last_empty {
                             last_empty = false;
                             Ok(ty)
@@ -634,11 +681,7 @@ impl<'a> TypeCheckerVisitor<'a> {
                         Ok(TypeDecl::Unit)
                     }
                 }
-                _ => {
-                    let stmt_obj = self.core.stmt_pool.get(s)
-                        .ok_or_else(|| TypeCheckError::generic_error("Invalid statement reference"))?;
-                    stmt_obj.clone().accept(self)
-                }
+                mut other => other.accept(self),
             };
 
             match stmt_type {
@@ -663,45 +706,33 @@ impl<'a> TypeCheckerVisitor<'a> {
 
         // Check if-block
         let if_block = *then_block;
-        let is_if_empty = match self.core.expr_pool.get(&if_block)
-            .ok_or_else(|| TypeCheckError::generic_error("Invalid if block expression reference"))? {
-            Expr::Block(expressions) => expressions.is_empty(),
-            _ => false,
-        };
+        let is_if_empty = self.core.expr_pool.stmt_list_of(&if_block).is_some_and(|stmts| stmts.is_empty());
         if !is_if_empty {
-            let if_expr = self.core.expr_pool.get(&if_block)
+            let mut if_expr = self.core.expr_pool.get(&if_block)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid if block expression reference"))?;
-            let if_ty = if_expr.clone().accept(self)?;
+            let if_ty = if_expr.accept(self)?;
             block_types.push(if_ty);
         }
 
         // Check elif-blocks
         for (_, elif_block) in elif_pairs {
             let elif_block = *elif_block;
-            let is_elif_empty = match self.core.expr_pool.get(&elif_block)
-                .ok_or_else(|| TypeCheckError::generic_error("Invalid elif block expression reference"))? {
-                Expr::Block(expressions) => expressions.is_empty(),
-                _ => false,
-            };
+            let is_elif_empty = self.core.expr_pool.stmt_list_of(&elif_block).is_some_and(|stmts| stmts.is_empty());
             if !is_elif_empty {
-                let elif_expr = self.core.expr_pool.get(&elif_block)
+                let mut elif_expr = self.core.expr_pool.get(&elif_block)
                     .ok_or_else(|| TypeCheckError::generic_error("Invalid elif block expression reference"))?;
-                let elif_ty = elif_expr.clone().accept(self)?;
+                let elif_ty = elif_expr.accept(self)?;
                 block_types.push(elif_ty);
             }
         }
 
         // Check else-block
         let else_block = *else_block;
-        let is_else_empty = match self.core.expr_pool.get(&else_block)
-            .ok_or_else(|| TypeCheckError::generic_error("Invalid else block expression reference"))? {
-            Expr::Block(expressions) => expressions.is_empty(),
-            _ => false,
-        };
+        let is_else_empty = self.core.expr_pool.stmt_list_of(&else_block).is_some_and(|stmts| stmts.is_empty());
         if !is_else_empty {
-            let else_expr = self.core.expr_pool.get(&else_block)
+            let mut else_expr = self.core.expr_pool.get(&else_block)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid else block expression reference"))?;
-            let else_ty = else_expr.clone().accept(self)?;
+            let else_ty = else_expr.accept(self)?;
             block_types.push(else_ty);
         }
 
@@ -733,16 +764,19 @@ impl<'a> TypeCheckerVisitor<'a> {
         let rhs = *rhs;
         
         let lhs_ty = {
-            let lhs_obj = self.core.expr_pool.get(&lhs)
+            let mut lhs_obj = self.core.expr_pool.get(&lhs)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid left-hand expression reference"))?;
-            lhs_obj.clone().accept(self)?
+            lhs_obj.accept(self)?
         };
+        self.type_inference.set_expr_type(lhs, lhs_ty.clone());
+        self.check_assignment_root(&lhs, false)?;
         
         let rhs_ty = {
-            let rhs_obj = self.core.expr_pool.get(&rhs)
+            let mut rhs_obj = self.core.expr_pool.get(&rhs)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid right-hand expression reference"))?;
-            rhs_obj.clone().accept(self)?
+            rhs_obj.accept(self)?
         };
+        self.type_inference.set_expr_type(rhs, rhs_ty.clone());
         
         // Allow assignment compatibility. `is_equivalent` covers the
         // user-named-type cases the parser emits ambiguously
@@ -966,7 +1000,7 @@ impl<'a> TypeCheckerVisitor<'a> {
     }
 
     /// Type check function calls
-    pub fn visit_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+    pub fn visit_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
         let _fn_name_str = self.resolve_symbol_name(fn_name);
 
         // Namespace-only enforcement: functions that came in through
@@ -994,7 +1028,7 @@ impl<'a> TypeCheckerVisitor<'a> {
 
         if let Some(fun) = self.context.get_fn(fn_name) {
             // Check visibility access control
-            if let Err(err) = self.check_function_access(&fun) {
+            if let Err(err) = self.check_function_access(&fun, false) {
                 self.pop_context();
                 return Err(err);
             }
@@ -1007,9 +1041,24 @@ impl<'a> TypeCheckerVisitor<'a> {
 
             // Handle generic function calls
             if !fun.generic_params.is_empty() {
-                return self.visit_generic_call(fn_name, args_ref, &fun);
+                return self.visit_generic_call(fn_name, args_ref, &fun, type_args);
+            }
+            if !type_args.is_empty() {
+                self.pop_context();
+                return Err(TypeCheckError::generic_error(&format!(
+                    "function '{}' is not generic and takes no type arguments",
+                    self.resolve_symbol_name(fn_name)
+                )));
             }
             
+            // `Some(None)`: the callee's body is being checked further
+            // up, so without an annotation its return type isn't known
+            // yet. `type_check` reports the cycle if that matters.
+            let in_progress = matches!(self.function_checking.is_checked_fn.get(&fn_name), Some(None));
+            if in_progress && fun.return_type.is_none() {
+                self.function_checking.record_recursive_call(fn_name);
+            }
+
             // Check if function has been type checked
             let status = self.function_checking.is_checked_fn.get(&fn_name);
             if status.is_none() || status.as_ref().and_then(|s| s.as_ref()).is_none() {
@@ -1038,6 +1087,15 @@ impl<'a> TypeCheckerVisitor<'a> {
             // emits `Expr::Call(name, args)`; this branch handles
             // the value case.
             self.pop_context();
+            if self.context.struct_definitions.get(&fn_name).is_some_and(|def| def.is_tuple_struct()) {
+                return self.visit_tuple_struct_constructor(fn_name, args_ref, type_args);
+            }
+            if !type_args.is_empty() {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "'{}' is not a generic function and takes no type arguments",
+                    self.resolve_symbol_name(fn_name)
+                )));
+            }
             if let Some(callee_ty) = self.context.get_var(fn_name)
                 && let TypeDecl::Function(param_tys, ret_ty) = callee_ty {
                     return self.visit_indirect_call(fn_name, args_ref, &param_tys, &ret_ty);
@@ -1063,7 +1121,7 @@ impl<'a> TypeCheckerVisitor<'a> {
         // (parser only produces ExprList here); a missing slot means
         // the args ref dangles.
         let args = match self.core.expr_pool.get(args_ref) {
-            Some(Expr::ExprList(args)) => args.clone(),
+            Some(Expr::ExprList(args)) => args,
             Some(_) => return Ok(()),
             None => return Err(TypeCheckError::generic_error("Invalid arguments reference")),
         };
@@ -1142,7 +1200,7 @@ impl<'a> TypeCheckerVisitor<'a> {
         ret_ty: &TypeDecl,
     ) -> Result<TypeDecl, TypeCheckError> {
         let args_data = match self.core.expr_pool.get(args_ref) {
-            Some(Expr::ExprList(args)) => args.clone(),
+            Some(Expr::ExprList(args)) => args,
             _ => return Err(TypeCheckError::generic_error("Invalid arguments reference")),
         };
         if args_data.len() != param_tys.len() {
@@ -1200,12 +1258,18 @@ impl<'a> TypeCheckerVisitor<'a> {
         // type-checks).
         Self::reject_generic_in_closure_signature(params, return_type)?;
 
-        // Push a fresh scope and bind each parameter.
+        // Push a fresh scope and bind each parameter. The closure body
+        // is its own function: loops of the enclosing function are not
+        // `break` / `continue` targets from inside it.
         self.push_context();
         for (name, ty) in params {
             self.context.set_var(*name, ty.clone());
         }
+        let outer_loops = std::mem::take(&mut self.context.loop_stack);
+        let outer_generator = self.context.generator_item.take();
         let body_result = self.visit_expr(body);
+        self.context.loop_stack = outer_loops;
+        self.context.generator_item = outer_generator;
         self.pop_context();
         let body_ty = body_result?;
 
@@ -1358,7 +1422,7 @@ impl<'a> TypeCheckerVisitor<'a> {
         };
         match expr {
             Expr::Identifier(s) => record(s, out, seen),
-            Expr::Call(name, args_ref) => {
+            Expr::Call(name, args_ref, _) => {
                 record(name, out, seen);
                 self.collect_closure_free_vars(args_ref, bound, out, seen);
             }
@@ -1418,7 +1482,7 @@ impl<'a> TypeCheckerVisitor<'a> {
                     self.collect_closure_free_vars(a, bound, out, seen);
                 }
             }
-            Expr::StructLiteral(_, fields) => {
+            Expr::StructLiteral(_, fields, _) => {
                 for (_, e) in fields {
                     self.collect_closure_free_vars(e, bound, out, seen);
                 }
@@ -1437,12 +1501,12 @@ impl<'a> TypeCheckerVisitor<'a> {
                     self.collect_closure_free_vars(e, bound, out, seen);
                 }
             }
-            Expr::SliceAssign(obj, start, end, value) => {
+            Expr::SliceAssign(obj, info, value) => {
                 self.collect_closure_free_vars(obj, bound, out, seen);
-                if let Some(s) = start {
+                if let Some(s) = info.start {
                     self.collect_closure_free_vars(s, bound, out, seen);
                 }
-                if let Some(e) = end {
+                if let Some(e) = info.end {
                     self.collect_closure_free_vars(e, bound, out, seen);
                 }
                 self.collect_closure_free_vars(value, bound, out, seen);
@@ -1472,11 +1536,31 @@ impl<'a> TypeCheckerVisitor<'a> {
                 }
                 self.collect_closure_free_vars(body, &nested_bound, out, seen);
             }
+            Expr::WithResource { name, resource, body } => {
+                self.collect_closure_free_vars(resource, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(name);
+                self.collect_closure_free_vars(body, &nested_bound, out, seen);
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.collect_closure_free_vars(body, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(error);
+                self.collect_closure_free_vars(handler, &nested_bound, out, seen);
+            }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                self.collect_closure_free_vars(source, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(var);
+                for e in filter.iter().chain([&element]).chain(&value) {
+                    self.collect_closure_free_vars(*e, &nested_bound, out, seen);
+                }
+            }
             Expr::QualifiedIdentifier(_)
             | Expr::Int64(_) | Expr::UInt64(_) | Expr::Float64(_)
             | Expr::Int8(_) | Expr::Int16(_) | Expr::Int32(_)
             | Expr::UInt8(_) | Expr::UInt16(_) | Expr::UInt32(_)
-            | Expr::Number(_) | Expr::String(_)
+            | Expr::Number(_) | Expr::String(_) | Expr::Bytes(_)
             | Expr::True | Expr::False | Expr::Null => {}
         }
     }
@@ -1489,7 +1573,7 @@ impl<'a> TypeCheckerVisitor<'a> {
         seen: &mut std::collections::HashSet<DefaultSymbol>,
     ) {
         match stmt {
-            Stmt::Expression(e) => self.collect_closure_free_vars(*e, bound, out, seen),
+            Stmt::Expression(e) | Stmt::Yield(e) => self.collect_closure_free_vars(*e, bound, out, seen),
             Stmt::Val(name, _, e) => {
                 self.collect_closure_free_vars(*e, bound, out, seen);
                 bound.insert(*name);
@@ -1516,7 +1600,12 @@ impl<'a> TypeCheckerVisitor<'a> {
                 self.collect_closure_free_vars(*cond, bound, out, seen);
                 self.collect_closure_free_vars(*body, bound, out, seen);
             }
-            Stmt::Break(_) | Stmt::Continue(_) => {}
+            Stmt::Break(_, value) => {
+                if let Some(e) = value {
+                    self.collect_closure_free_vars(*e, bound, out, seen);
+                }
+            }
+            Stmt::Continue(_) => {}
             Stmt::StructDecl { .. }
             | Stmt::ImplBlock { .. }
             | Stmt::EnumDecl { .. }
@@ -6,6 +6,12 @@ use crate::type_decl::TypeDecl;
 pub struct FunctionCheckingState {
     pub call_depth: usize,
     pub is_checked_fn: HashMap<DefaultSymbol, Option<TypeDecl>>,
+    /// Functions whose bodies are being checked, outermost first.
+    pub checking_stack: Vec<DefaultSymbol>,
+    /// Functions without a return type annotation that a call reached
+    /// while their body was still being checked, with the call chain
+    /// that led back to them (`[f, g, f]`).
+    pub recursive_cycles: HashMap<DefaultSymbol, Vec<DefaultSymbol>>,
 }
 
 impl Default for FunctionCheckingState {
@@ -19,6 +25,8 @@ impl FunctionCheckingState {
         Self {
             call_depth: 0,
             is_checked_fn: HashMap::new(),
+            checking_stack: Vec::new(),
+            recursive_cycles: HashMap::new(),
         }
     }
 
@@ -44,6 +52,16 @@ impl FunctionCheckingState {
         self.is_checked_fn.get(&name).and_then(|t| t.clone())
     }
 
+    /// Record a call back into `name`, whose body is still being
+    /// checked further up `checking_stack`.
+    pub fn record_recursive_call(&mut self, name: DefaultSymbol) {
+        if let Some(start) = self.checking_stack.iter().position(|n| *n == name) {
+            let mut cycle = self.checking_stack[start..].to_vec();
+            cycle.push(name);
+            self.recursive_cycles.entry(name).or_insert(cycle);
+        }
+    }
+
     pub fn get_call_depth(&self) -> usize {
         self.call_depth
     }
@@ -51,5 +69,7 @@ impl FunctionCheckingState {
     pub fn clear(&mut self) {
         self.call_depth = 0;
         self.is_checked_fn.clear();
+        self.checking_stack.clear();
+        self.recursive_cycles.clear();
     }
 }
\ No newline at end of file
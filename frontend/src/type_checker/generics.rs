@@ -9,7 +9,9 @@ use std::collections::HashMap;
 /// Extension trait for generic type checking functionality
 pub trait GenericTypeChecking {
     /// Handle generic function calls with type inference and instantiation recording
-    fn visit_generic_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, fun: &Function) -> Result<TypeDecl, TypeCheckError>;
+    /// `type_args` are the `<..>` written at the call site, empty if none.
+    fn visit_generic_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, fun: &Function,
+                          type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError>;
     
     /// Handle generic struct literal type inference  
     fn visit_generic_struct_literal(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>, 
@@ -37,7 +39,8 @@ pub trait GenericTypeChecking {
 }
 
 impl GenericTypeChecking for TypeCheckerVisitor<'_> {
-    fn visit_generic_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, fun: &Function) -> Result<TypeDecl, TypeCheckError> {
+    fn visit_generic_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, fun: &Function,
+                          type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
         use crate::ast::Expr;
         
         // Extract argument expressions from the reference
@@ -67,18 +70,46 @@ impl GenericTypeChecking for TypeCheckerVisitor<'_> {
             )));
         }
         
+        // Explicit `<..>` arguments pre-seed the substitution: parameter
+        // types are specialised before they meet the arguments, so
+        // `identity<u64>(5)` checks `5` as a u64.
+        let explicit = if type_args.is_empty() {
+            None
+        } else {
+            match self.explicit_type_substitutions(fn_name, &fun.generic_params, type_args) {
+                Ok(subst) => Some(subst),
+                Err(e) => {
+                    self.pop_context();
+                    return Err(e);
+                }
+            }
+        };
+
         // Clear previous constraints for this inference
         self.type_inference.clear_constraints();
         
         // Collect argument types and add constraints
         let mut arg_types = Vec::new();
         for (i, (arg_expr, (_, param_type))) in args.iter().zip(&fun.parameter).enumerate() {
-            let arg_type = self.visit_expr(arg_expr)?;
+            let param_type = match &explicit {
+                Some(subst) => param_type.substitute_generics(subst),
+                None => param_type.clone(),
+            };
+            let arg_type = if explicit.is_some() {
+                // Fully specialised, so it can steer literals like a
+                // non-generic parameter does.
+                let original_hint = self.type_inference.type_hint.replace(param_type.clone());
+                let arg_type = self.visit_expr(arg_expr);
+                self.type_inference.type_hint = original_hint;
+                arg_type?
+            } else {
+                self.visit_expr(arg_expr)?
+            };
             arg_types.push(arg_type.clone());
             
             // Add constraint for parameter-argument type unification
             self.type_inference.add_constraint(
-                param_type.clone(),
+                param_type,
                 arg_type,
                 crate::type_checker::inference::ConstraintContext::FunctionCall {
                     function_name: fn_name,
@@ -88,7 +119,7 @@ impl GenericTypeChecking for TypeCheckerVisitor<'_> {
         }
         
         // Solve constraints to get type substitutions
-        let substitutions = match self.type_inference.solve_constraints() {
+        let mut substitutions = match self.type_inference.solve_constraints() {
             Ok(solution) => solution,
             Err(e) => {
                 self.pop_context();
@@ -99,6 +130,9 @@ impl GenericTypeChecking for TypeCheckerVisitor<'_> {
                 )));
             }
         };
+        if let Some(explicit) = explicit {
+            substitutions.extend(explicit);
+        }
         
         // Ensure all generic parameters have been inferred
         for generic_param in &fun.generic_params {
@@ -553,6 +587,27 @@ impl GenericTypeChecking for TypeCheckerVisitor<'_> {
 
 // Helper trait methods that need to be accessible
 impl<'a> TypeCheckerVisitor<'a> {
+    /// Map `owner`'s generic parameters to the explicit `<..>` type
+    /// arguments written at a call or struct literal.
+    pub(super) fn explicit_type_substitutions(&self, owner: DefaultSymbol, generic_params: &[DefaultSymbol],
+                                   type_args: &[TypeDecl]) -> Result<HashMap<DefaultSymbol, TypeDecl>, TypeCheckError> {
+        if type_args.len() != generic_params.len() {
+            return Err(TypeCheckError::generic_error(&format!(
+                "'{}' expects {} type argument(s), found {}",
+                self.resolve_symbol_name(owner), generic_params.len(), type_args.len()
+            )));
+        }
+        Ok(generic_params.iter().zip(type_args).map(|(param, arg)| {
+            let arg = match arg {
+                TypeDecl::Identifier(name) if self.context.struct_definitions.contains_key(name) => {
+                    TypeDecl::Struct(*name, vec![])
+                }
+                _ => self.normalize_generic_identifier(arg),
+            };
+            (*param, arg)
+        }).collect())
+    }
+
     /// Helper method to convert TypeDecl to string representation
     fn type_to_string(&self, typ: &TypeDecl) -> String {
         match typ {
@@ -29,6 +29,22 @@ impl<'a> TypeCheckerVisitor<'a> {
         // for concrete-args impls).
         let _ = target_type_args;
 
+        // Methods cannot be overloaded: one definition per name and block.
+        let mut seen: std::collections::HashMap<DefaultSymbol, &Rc<MethodFunction>> =
+            std::collections::HashMap::new();
+        for method in methods {
+            if let Some(first) = seen.insert(method.name, method) {
+                let (line, column) = self.calculate_line_col_from_offset(first.node.start);
+                return Err(TypeCheckError::generic_error(&format!(
+                    "method '{}::{}' is already defined at line {line}, column {column} \
+                     (methods cannot be overloaded)",
+                    self.resolve_symbol_name(struct_symbol),
+                    self.resolve_symbol_name(method.name),
+                ))
+                .with_location(self.node_to_source_location(&method.node)));
+            }
+        }
+
         // For `impl <Trait> for <Struct>`, validate that the trait exists
         // and that this block satisfies every required method signature.
         // Conformance is recorded in the context for use at call sites.
@@ -176,9 +192,9 @@ impl<'a> TypeCheckerVisitor<'a> {
         cond: &ExprRef,
         kind: &str,
     ) -> Result<(), TypeCheckError> {
-        let expr = self.core.expr_pool.get(cond)
+        let mut expr = self.core.expr_pool.get(cond)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid contract expression reference"))?;
-        let ty = expr.clone().accept(self)?;
+        let ty = expr.accept(self)?;
         if ty != TypeDecl::Bool {
             return Err(TypeCheckError::generic_error(
                 &format!("`{kind}` clause must be of type bool, got {ty:?}")
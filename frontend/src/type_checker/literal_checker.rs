@@ -19,19 +19,20 @@ impl<'a> LiteralTypeChecker for TypeCheckerVisitor<'a> {
     fn check_number_literal(&mut self, value: DefaultSymbol) -> Result<TypeDecl, TypeCheckError> {
         let num_str = self.core.string_interner.resolve(value)
             .ok_or_else(|| TypeCheckError::generic_error("Failed to resolve number literal"))?;
+        let parsed = crate::token::parse_int_literal(num_str);
         
         // If we have a type hint from val/var declaration, validate and return the hint type
         if let Some(hint) = self.type_inference.type_hint.clone() {
             match hint {
                 TypeDecl::Int64 => {
-                    if let Ok(_val) = num_str.parse::<i64>() {
+                    if parsed.is_some_and(|n| i64::try_from(n).is_ok()) {
                         return Ok(hint);
                     } else {
                         return Err(TypeCheckError::conversion_error(num_str, "Int64"));
                     }
                 },
                 TypeDecl::UInt64 => {
-                    if let Ok(_val) = num_str.parse::<u64>() {
+                    if parsed.is_some_and(|n| u64::try_from(n).is_ok()) {
                         return Ok(hint);
                     } else {
                         return Err(TypeCheckError::conversion_error(num_str, "UInt64"));
@@ -42,7 +43,7 @@ impl<'a> LiteralTypeChecker for TypeCheckerVisitor<'a> {
         }
         
         // Parse the number and determine appropriate type
-        if let Ok(val) = num_str.parse::<i64>() {
+        if let Some(val) = parsed.and_then(|n| i64::try_from(n).ok()) {
             if val >= 0 {
                 // Positive number that fits in both i64 and u64 - use Number for inference
                 Ok(TypeDecl::Number)
@@ -50,7 +51,7 @@ impl<'a> LiteralTypeChecker for TypeCheckerVisitor<'a> {
                 // Negative number or very large positive - must be i64
                 Ok(TypeDecl::Int64)
             }
-        } else if let Ok(_val) = num_str.parse::<u64>() {
+        } else if parsed.is_some_and(|n| u64::try_from(n).is_ok()) {
             // Very large positive number that doesn't fit in i64 - must be u64
             Ok(TypeDecl::UInt64)
         } else {
@@ -143,10 +143,10 @@ impl<'a> MethodProcessing for TypeCheckerVisitor<'a> {
         Ok(())
     }
 
-    /// Process builtin method calls. Each variant returns the
-    /// declared type of the method per `BuiltinMethod`'s docstring;
-    /// the receiver and args are still visited so any nested
-    /// type-check error inside them surfaces here.
+    /// Process builtin method calls. The argument count and return
+    /// type come from `BuiltinMethod::signature`; the receiver and
+    /// args are still visited so any nested type-check error inside
+    /// them surfaces here.
     fn visit_builtin_method_call(&mut self, receiver: &ExprRef, method: &BuiltinMethod, args: &Vec<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
         // Visit receiver and args for side effects — earlier type
         // errors deeper in the expression tree must propagate even
@@ -155,31 +155,51 @@ impl<'a> MethodProcessing for TypeCheckerVisitor<'a> {
         for arg in args {
             let _ = self.visit_expr(arg)?;
         }
-        match method {
-            BuiltinMethod::IsNull => Ok(TypeDecl::Bool),
-            BuiltinMethod::StrLen => Ok(TypeDecl::UInt64),
-            // String methods that return a fresh string. Pre-fix
-            // these all fell through the `_ => Ok(Unit)` arm, so
-            // `val s = "a".concat("b")` infers Unit and any
-            // downstream str-typed use (return type, str field,
-            // typed val) reports a Type mismatch. The catch-all
-            // remained from when only `len` and `is_null` were
-            // wired up.
-            BuiltinMethod::StrConcat
-            | BuiltinMethod::StrSubstring
-            | BuiltinMethod::StrTrim
-            | BuiltinMethod::StrToUpper
-            | BuiltinMethod::StrToLower => Ok(TypeDecl::String),
-            BuiltinMethod::StrContains => Ok(TypeDecl::Bool),
-            BuiltinMethod::StrSplit => Ok(TypeDecl::Array(
-                vec![TypeDecl::String],
-                0,
-            )),
+        let (params, return_type) = method.signature();
+        if args.len() != params.len() {
+            return Err(TypeCheckError::generic_error(&format!(
+                "builtin method {:?} expects {} argument(s), found {}",
+                method, params.len(), args.len()
+            )));
         }
+        Ok(return_type)
     }
 
     /// Process impl block method validation
     fn process_impl_method_validation(&mut self, target_type: DefaultSymbol, method: &Rc<MethodFunction>, has_generics: bool) -> Result<(), TypeCheckError> {
+        // `__str__` is called implicitly by `print` / `println` and
+        // string interpolation, so its shape is fixed: a receiver
+        // (`self: Self` or `&self`), nothing else, returning `str`.
+        if self.resolve_symbol_name(method.name) == "__str__" {
+            let receivers = usize::from(method.has_self_param) + method.parameter.len();
+            let self_only = receivers == 1
+                && method.parameter.first().is_none_or(|(name, _)| self.resolve_symbol_name(*name) == "self");
+            if !self_only || method.return_type != Some(TypeDecl::String) {
+                if has_generics {
+                    self.type_inference.pop_generic_scope();
+                }
+                return Err(TypeCheckError::generic_error(
+                    "__str__ must be declared as `fn __str__(self: Self) -> str`",
+                ));
+            }
+        }
+
+        // `__close__` is called implicitly when a `with name = ...`
+        // block exits: a receiver and nothing else, no result.
+        if self.resolve_symbol_name(method.name) == "__close__" {
+            let receivers = usize::from(method.has_self_param) + method.parameter.len();
+            let self_only = receivers == 1
+                && method.parameter.first().is_none_or(|(name, _)| self.resolve_symbol_name(*name) == "self");
+            if !self_only || method.return_type.as_ref().is_some_and(|t| *t != TypeDecl::Unit) {
+                if has_generics {
+                    self.type_inference.pop_generic_scope();
+                }
+                return Err(TypeCheckError::generic_error(
+                    "__close__ must be declared as `fn __close__(&mut self)`",
+                ));
+            }
+        }
+
         // Check method parameter types
         for (_, param_type) in &method.parameter {
             // Resolve Self type to the actual struct type
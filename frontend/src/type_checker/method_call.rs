@@ -137,9 +137,30 @@ impl<'a> TypeCheckerVisitor<'a> {
             arg_types.push(self.visit_expr(arg)?);
         }
         
+        // `gen.next()` resumes a generator and hands back its next item,
+        // or `None` once the body has run to completion.
+        if let TypeDecl::Generator(item) = &resolved_obj_type {
+            if method_name != "next" || !args.is_empty() {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "Generator has no method `{method_name}` taking {} argument(s); only `next()` is available",
+                    args.len()
+                )));
+            }
+            let option_sym = self
+                .core
+                .string_interner
+                .get("Option")
+                .filter(|sym| self.context.enum_definitions.contains_key(sym))
+                .ok_or_else(|| TypeCheckError::generic_error(
+                    "Generator::next returns Option<T>, which requires the core library",
+                ))?;
+            return Ok(TypeDecl::Enum(option_sym, vec![(**item).clone()]));
+        }
+
         // Check for builtin methods
         let method_str = self.resolve_symbol_name(*method);
-        let builtin_method = self.builtin_methods.get(&(resolved_obj_type.clone(), method_str.to_string())).cloned();
+        let builtin_method = BuiltinMethod::receiver_key(&resolved_obj_type)
+            .and_then(|key| self.builtin_methods.get(&(key, method_str.to_string())).cloned());
         if let Some(builtin_method) = builtin_method {
             // visit_builtin_method_call expects ExprRef, not TypeDecl
             return self.visit_builtin_method_call(obj, &builtin_method, args);
@@ -165,7 +186,7 @@ impl<'a> TypeCheckerVisitor<'a> {
     }
 
     /// Helper method to handle method calls on a specific type
-    pub fn visit_method_call_on_type(&mut self, obj_type: &TypeDecl, method: &DefaultSymbol, args: &Vec<ExprRef>, _arg_types: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
+    pub fn visit_method_call_on_type(&mut self, obj_type: &TypeDecl, method: &DefaultSymbol, args: &Vec<ExprRef>, arg_types: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
         let method_name = self.resolve_symbol_name(*method);
 
         // Step B of extension-trait support: dispatch primitive
@@ -288,7 +309,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             
             if !type_params.is_empty() {
                 // Handle generic struct method call
-                let method_func_opt = self.context.get_struct_method(*struct_name, *method);
+                let method_func_opt = self.context.get_struct_method(*struct_name, *method).cloned();
                 
                 
                 if let Some(method_func) = method_func_opt {
@@ -305,6 +326,33 @@ impl<'a> TypeCheckerVisitor<'a> {
                     }
                     
                     
+                    // Arguments whose parameter mentions the struct's
+                    // type params are checked against the receiver's
+                    // substitution, so `Pair<u64>` rejects
+                    // `p.with_a(-1i64)` for `fn with_a(self: Self, v: T)`.
+                    let receiver_slot = method_func.parameter.first().is_some_and(|(name, _)| {
+                        self.core.string_interner.resolve(*name) == Some("self")
+                    });
+                    let declared_args = method_func.parameter.iter().skip(usize::from(receiver_slot));
+                    for ((_, declared), (arg_ref, arg_ty)) in declared_args.zip(args.iter().zip(arg_types)) {
+                        let expected = declared.substitute_generics(&substitutions);
+                        if expected == *declared
+                            || matches!(arg_ty, TypeDecl::Unknown)
+                            || TypeDecl::is_arg_compatible(arg_ty, &expected)
+                        {
+                            continue;
+                        }
+                        if *arg_ty == TypeDecl::Number && matches!(expected, TypeDecl::UInt64 | TypeDecl::Int64) {
+                            self.transform_numeric_expr(arg_ref, &expected)?;
+                            continue;
+                        }
+                        return Err(TypeCheckError::method_error(
+                            &method_name,
+                            obj_type.clone(),
+                            &format!("argument type mismatch: expected {:?}, found {:?}", expected, arg_ty),
+                        ));
+                    }
+
                     // Apply substitutions to method return type
                     let method_return_type = method_func.return_type.as_ref().unwrap_or(&TypeDecl::Unit);
                     
@@ -381,15 +429,10 @@ impl<'a> TypeCheckerVisitor<'a> {
             }
         }
 
-        // Check array methods
-        if let TypeDecl::Array(_, _) = obj_type
-            && method_name == "len" {
-                // Array len() returns u64
-                return Ok(TypeDecl::UInt64);
-            }
-
-        // Check builtin methods
-        if let Some(builtin_method) = self.builtin_methods.get(&(obj_type.clone(), method_name.to_string())).cloned() {
+        // Check builtin methods (str / array / bytes)
+        if let Some(builtin_method) = BuiltinMethod::receiver_key(obj_type)
+            .and_then(|key| self.builtin_methods.get(&(key, method_name.to_string())).cloned())
+        {
             // For builtin methods, we need to create a temporary expression ref for the object
             // This is a bit of a hack but necessary for the current API
             let dummy_obj_ref = ExprRef(0); // Use dummy ref for now
@@ -482,8 +525,9 @@ impl<'a> TypeCheckerVisitor<'a> {
             .ok_or_else(|| {
                 TypeCheckError::not_found("Function", &self.resolve_symbol_name(function_name))
             })?;
-        // Honour visibility (matches the bare-call path).
-        self.check_function_access(&fun)?;
+        // A qualified call reaches into another module, so only its
+        // `pub fn`s are visible.
+        self.check_function_access(&fun, qualifier.is_some())?;
         // Generic module functions: synthesize an `ExprList` for the
         // args and reuse the regular generic-call path so the
         // existing inference / monomorphisation logic runs.
@@ -492,7 +536,7 @@ impl<'a> TypeCheckerVisitor<'a> {
                 .core
                 .expr_pool
                 .add(Expr::ExprList(args.clone()));
-            return self.visit_generic_call(function_name, &args_ref, &fun);
+            return self.visit_generic_call(function_name, &args_ref, &fun, &[]);
         }
         let params: Vec<_> = fun
             .parameter
@@ -641,7 +685,11 @@ impl<'a> TypeCheckerVisitor<'a> {
 
         // Verify the struct exists — generic and non-generic both count.
         if !self.context.struct_definitions.contains_key(&struct_name) {
-            return Err(TypeCheckError::not_found("Struct", &format!("{:?}", struct_name)));
+            let error = TypeCheckError::not_found("Struct", &self.resolve_symbol_name(struct_name));
+            return Err(match self.missing_import_fix(struct_name, function_name) {
+                Some(fix) => error.with_fix(fix),
+                None => error,
+            });
         }
 
         let function_name_str = self.resolve_symbol_name(function_name);
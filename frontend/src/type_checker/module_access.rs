@@ -1,7 +1,8 @@
 use string_interner::DefaultSymbol;
 use crate::ast::*;
 use crate::type_decl::*;
-use crate::type_checker::{TypeCheckerVisitor, TypeCheckError, error};
+use crate::type_checker::{FixIt, TypeCheckerVisitor, TypeCheckError, error};
+use crate::type_checker::visitor::declaration_statements;
 
 impl<'a> TypeCheckerVisitor<'a> {
     /// Add error to collection without returning immediately
@@ -13,16 +14,28 @@ impl<'a> TypeCheckerVisitor<'a> {
     pub fn check_program_multiple_errors(&mut self, program: &Program) -> error::MultipleTypeCheckResult<()> {
         self.errors.clear();
 
-        // Collect errors during type checking instead of returning immediately
-        for func in &program.function {
-            if let Err(e) = self.type_check(func.clone()) {
+        // Same order as `visit_program`, collecting errors instead of
+        // returning at the first one.
+        self.register_declarations(program);
+        self.errors.extend(self.duplicate_definition_errors());
+        let (type_decls, impl_blocks) = declaration_statements(program);
+        for stmt_ref in &type_decls {
+            if let Err(e) = self.visit_stmt(stmt_ref) {
                 self.errors.push(e);
             }
         }
-
-        for index in 0..program.statement.len() {
-            let stmt_ref = StmtRef(index as u32);
-            if let Err(e) = self.visit_stmt(&stmt_ref) {
+        for c in &program.consts {
+            if let Err(e) = self.check_const(c) {
+                self.errors.push(e);
+            }
+        }
+        for stmt_ref in &impl_blocks {
+            if let Err(e) = self.visit_stmt(stmt_ref) {
+                self.errors.push(e);
+            }
+        }
+        for func in &program.function {
+            if let Err(e) = self.type_check(func.clone()) {
                 self.errors.push(e);
             }
         }
@@ -107,6 +120,19 @@ impl<'a> TypeCheckerVisitor<'a> {
         }
     }
 
+    /// Fix-it for `alias::member(...)` when `alias` names no struct or
+    /// imported module, but exactly one module under the module
+    /// resolver's search paths would: it adds the missing `import`.
+    pub(crate) fn missing_import_fix(&self, alias: DefaultSymbol, member: DefaultSymbol) -> Option<FixIt> {
+        let resolver = self.core.module_resolver.as_deref()?;
+        let alias = self.resolve_symbol_name(alias);
+        let member = self.resolve_symbol_name(member);
+        match resolver.modules_exporting(&alias, &member).as_slice() {
+            [module] => Some(FixIt::add_import(self.source_code.unwrap_or(""), module)),
+            _ => None,
+        }
+    }
+
     /// Helper to check if a name looks like a function (simple heuristic)
     fn is_likely_function_name(&self, name: &str) -> bool {
         name.chars().all(|c| c.is_alphanumeric() || c == '_') &&
@@ -124,36 +150,18 @@ impl<'a> TypeCheckerVisitor<'a> {
     // Phase 3: Access Control and Visibility Enforcement
     // =========================================================================
 
-    /// Check if a function can be accessed based on visibility and module context
-    pub(super) fn check_function_access(&self, function: &Function) -> Result<(), TypeCheckError> {
-        // If function is public, it's accessible from anywhere
-        if function.visibility == Visibility::Public {
-            return Ok(());
-        }
-
-        // If function is private, check if we're in the same module
-        if function.visibility == Visibility::Private {
-            // For now, assume same-module access is allowed
-            if self.is_same_module_access() {
-                return Ok(());
-            } else {
-                let fn_name = self.resolve_symbol_name(function.name);
-                return Err(TypeCheckError::access_denied(
-                    &format!("Private function '{}' cannot be accessed from different module", fn_name)
-                ));
-            }
+    /// Check if a function can be accessed based on visibility and module
+    /// context. `from_other_module` is true for a `module::func(...)` call
+    /// into an imported module; bare calls stay inside their own module.
+    pub(super) fn check_function_access(&self, function: &Function, from_other_module: bool) -> Result<(), TypeCheckError> {
+        if function.visibility == Visibility::Private && from_other_module {
+            let fn_name = self.resolve_symbol_name(function.name);
+            return Err(TypeCheckError::access_denied(
+                &format!("Private function '{}' cannot be accessed from different module", fn_name)
+            ));
         }
-
         Ok(())
     }
-
-    /// Check if current access is within the same module
-    #[allow(dead_code)]
-    fn is_same_module_access(&self) -> bool {
-        // For Phase 3 initial implementation, assume same module access
-        // TODO: Implement proper module context tracking
-        true
-    }
 }
 
 /// Check if a string is a reserved keyword
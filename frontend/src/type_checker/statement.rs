@@ -6,12 +6,13 @@ use crate::type_checker::{
     TypeCheckerVisitor, TypeCheckError,
     Acceptable,
 };
+use crate::type_checker::context::LoopFrame;
 
 /// Statement type checking implementation
 impl<'a> TypeCheckerVisitor<'a> {
     /// Main entry point for statement type checking
     pub fn visit_stmt(&mut self, stmt: &StmtRef) -> Result<TypeDecl, TypeCheckError> {
-        let mut stmt_val = self.core.stmt_pool.get(stmt).unwrap_or(Stmt::Break(None)).clone();
+        let mut stmt_val = self.core.stmt_pool.get(stmt).unwrap_or(Stmt::Break(None, None));
         
         let result = stmt_val.accept(self);
         
@@ -27,9 +28,11 @@ impl<'a> TypeCheckerVisitor<'a> {
 
     /// Type check expression statements
     pub fn visit_expression_stmt(&mut self, expr: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
-        let expr_obj = self.core.expr_pool.get(expr)
+        let mut expr_obj = self.core.expr_pool.get(expr)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference in statement"))?;
-        expr_obj.clone().accept(self)
+        let ty = expr_obj.accept(self)?;
+        self.type_inference.set_expr_type(*expr, ty.clone());
+        Ok(ty)
     }
 
     /// Type check variable declarations (var) - internal implementation
@@ -135,33 +138,156 @@ impl<'a> TypeCheckerVisitor<'a> {
     pub fn visit_return(&mut self, expr: &Option<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
         if expr.is_none() {
             Ok(TypeDecl::Unit)
+        } else if self.context.generator_item.is_some() {
+            Err(TypeCheckError::generic_error(
+                "a generator function ends with a bare `return`; use `yield` to produce values",
+            ))
         } else {
             let e = expr.as_ref()
                 .ok_or_else(|| TypeCheckError::generic_error("Expected expression in return"))?;
-            let expr_obj = self.core.expr_pool.get(e)
+            let mut expr_obj = self.core.expr_pool.get(e)
                 .ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference in return"))?;
-            let return_type = expr_obj.clone().accept(self)?;
+            let return_type = expr_obj.accept(self)?;
+            self.type_inference.set_expr_type(*e, return_type.clone());
             Ok(return_type)
         }
     }
 
+    /// Type check `yield value`. Only valid inside a function declared
+    /// to return `Generator<T>`; the value must have the item type `T`.
+    pub fn visit_yield_impl(&mut self, expr: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+        let Some(item) = self.context.generator_item.clone() else {
+            return Err(TypeCheckError::generic_error(
+                "`yield` is only allowed inside a function returning Generator<T>",
+            ));
+        };
+        let original_hint = self.type_inference.type_hint.replace(item.clone());
+        let result = self.visit_expr(expr);
+        self.type_inference.type_hint = original_hint;
+        let value_ty = result?;
+        if value_ty != TypeDecl::Unknown && !value_ty.is_equivalent(&item) {
+            return Err(TypeCheckError::type_mismatch(item, value_ty)
+                .with_context("yield value"));
+        }
+        self.type_inference.set_expr_type(*expr, item);
+        Ok(TypeDecl::Unit)
+    }
+
+    /// Reject `yield` anywhere a suspended generator could not resume.
+    /// The interpreter saves a generator's position as a path of block
+    /// statements, loop bodies, if/elif/else branches and match arms, so
+    /// `yield` must sit in statement position reachable only through
+    /// those; a `yield` nested inside an expression (a `val` initialiser,
+    /// a call argument, a loop condition) cannot be resumed. In a `Task`
+    /// body the same goes for `yield_now()`, its switch point.
+    pub(super) fn check_yield_placement(&self, statements: &[StmtRef]) -> Result<(), TypeCheckError> {
+        for stmt in statements {
+            let Some(stmt) = self.core.stmt_pool.get(stmt) else { continue };
+            match stmt {
+                Stmt::Yield(value) => self.reject_nested_yield(&[value])?,
+                Stmt::While(_, cond, body) => {
+                    self.reject_nested_yield(&[cond])?;
+                    self.check_yield_placement_in_block(&body)?;
+                }
+                Stmt::For(_, _, start, end, body) => {
+                    self.reject_nested_yield(&[start, end])?;
+                    self.check_yield_placement_in_block(&body)?;
+                }
+                Stmt::Expression(e) if self.is_task_switch_point(&e) => {}
+                Stmt::Expression(e) => self.check_yield_placement_in_expr(&e)?,
+                Stmt::Val(_, _, e) => self.reject_nested_yield(&[e])?,
+                Stmt::Var(_, _, e) | Stmt::Return(e) | Stmt::Break(_, e) => {
+                    let exprs: Vec<ExprRef> = e.into_iter().collect();
+                    self.reject_nested_yield(&exprs)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn check_yield_placement_in_block(&self, block: &ExprRef) -> Result<(), TypeCheckError> {
+        match self.core.expr_pool.get(block) {
+            Some(Expr::Block(statements)) => self.check_yield_placement(&statements),
+            _ => self.reject_nested_yield(&[*block]),
+        }
+    }
+
+    fn check_yield_placement_in_expr(&self, expr: &ExprRef) -> Result<(), TypeCheckError> {
+        match self.core.expr_pool.get(expr) {
+            Some(Expr::Block(statements)) => self.check_yield_placement(&statements),
+            Some(Expr::IfElifElse(cond, then, elifs, els)) => {
+                self.reject_nested_yield(&[cond])?;
+                self.check_yield_placement_in_block(&then)?;
+                for (elif_cond, elif_block) in &elifs {
+                    self.reject_nested_yield(&[*elif_cond])?;
+                    self.check_yield_placement_in_block(elif_block)?;
+                }
+                self.check_yield_placement_in_block(&els)
+            }
+            Some(Expr::Match(scrutinee, arms)) => {
+                self.reject_nested_yield(&[scrutinee])?;
+                for arm in &arms {
+                    if let Some(guard) = arm.guard {
+                        self.reject_nested_yield(&[guard])?;
+                    }
+                    self.check_yield_placement_in_block(&arm.body)?;
+                }
+                Ok(())
+            }
+            _ => self.reject_nested_yield(&[*expr]),
+        }
+    }
+
+    /// Whether `expr` is a `yield_now()` call inside a `Task` body, which
+    /// suspends the task rather than running the scheduler.
+    fn is_task_switch_point(&self, expr: &ExprRef) -> bool {
+        self.context.generator_item == Some(TypeDecl::Unit)
+            && matches!(
+                self.core.expr_pool.get(expr),
+                Some(Expr::BuiltinCall(BuiltinFunction::YieldNow, _))
+            )
+    }
+
+    fn reject_nested_yield(&self, exprs: &[ExprRef]) -> Result<(), TypeCheckError> {
+        let roots = (exprs.to_vec(), Vec::new());
+        let (reachable_exprs, reachable) =
+            super::check_cache::collect_subtree(self.core.expr_pool, self.core.stmt_pool, &roots);
+        if reachable_exprs.iter().any(|e| self.is_task_switch_point(e)) {
+            return Err(TypeCheckError::generic_error(
+                "inside a Task, `yield_now()` must appear as a statement of the task body, \
+                 a loop body, an if/elif/else branch or a match arm",
+            ));
+        }
+        if reachable
+            .iter()
+            .any(|s| matches!(self.core.stmt_pool.get(s), Some(Stmt::Yield(_))))
+        {
+            return Err(TypeCheckError::generic_error(
+                "`yield` must appear as a statement of the generator body, a loop body, \
+                 an if/elif/else branch or a match arm; it cannot be nested inside an expression",
+            ));
+        }
+        Ok(())
+    }
+
     /// Type check for loops - internal implementation
     pub fn visit_for_impl(&mut self, label: Option<DefaultSymbol>, init: DefaultSymbol, _cond: &ExprRef, range: &ExprRef, body: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
         self.push_context();
-        self.context.loop_label_stack.push(label);
+        self.context.loop_stack.push(LoopFrame::new(label, false));
 
-        let range_obj = self.core.expr_pool.get(range)
+        let mut range_obj = self.core.expr_pool.get(range)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid range expression reference"))?;
-        let range_ty = range_obj.clone().accept(self)?;
+        let range_ty = range_obj.accept(self)?;
         let ty = Some(range_ty);
 
         self.process_val_type(init, &ty, &Some(*range))?;
 
-        let body_obj = self.core.expr_pool.get(body)
+        let mut body_obj = self.core.expr_pool.get(body)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid body expression reference"))?;
-        let res = body_obj.clone().accept(self);
+        let res = body_obj.accept(self);
 
-        self.context.loop_label_stack.pop();
+        self.context.loop_stack.pop();
         self.pop_context();
         res
     }
@@ -169,30 +295,75 @@ impl<'a> TypeCheckerVisitor<'a> {
     /// Type check while loops - internal implementation
     pub fn visit_while_impl(&mut self, label: Option<DefaultSymbol>, cond: &ExprRef, body: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
         // Evaluate condition type first
-        let cond_obj = self.core.expr_pool.get(cond)
+        let mut cond_obj = self.core.expr_pool.get(cond)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid condition expression reference in while"))?;
-        let cond_type = cond_obj.clone().accept(self)?;
+        let cond_type = cond_obj.accept(self)?;
 
         // Verify condition is boolean
         if cond_type != TypeDecl::Bool {
             return Err(TypeCheckError::type_mismatch(TypeDecl::Bool, cond_type));
         }
 
+        // `loop { ... }` is parsed as `while true { ... }`.
+        let is_infinite = matches!(cond_obj, Expr::True);
+
         // Create new scope for while body
         self.push_context();
-        self.context.loop_label_stack.push(label);
-        let body_obj = self.core.expr_pool.get(body)
+        self.context.loop_stack.push(LoopFrame::new(label, is_infinite));
+        let mut body_obj = self.core.expr_pool.get(body)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid body expression reference in while"))?;
-        let res = body_obj.clone().accept(self);
-        self.context.loop_label_stack.pop();
+        let res = body_obj.accept(self);
+        let frame = self.context.loop_stack.pop();
         self.pop_context();
-        res
+        // A loop left through `break value` has the value's type.
+        match frame.and_then(|f| f.break_type) {
+            Some(break_type) => res.map(|_| break_type),
+            None => res,
+        }
     }
 
     /// Type check break statements. LABEL: validates that bare `break` is
     /// inside a loop, and that `break @label` references an active label.
-    pub fn visit_break_impl(&mut self, label: Option<DefaultSymbol>) -> Result<TypeDecl, TypeCheckError> {
-        self.validate_loop_label("break", label)?;
+    ///
+    /// `break value` is only valid for a `loop`, and every value break
+    /// targeting the same loop must agree on one type.
+    pub fn visit_break_impl(&mut self, label: Option<DefaultSymbol>, value: &Option<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
+        let index = self.validate_loop_label("break", label)?;
+        let Some(value) = value else {
+            return Ok(TypeDecl::Unit);
+        };
+        if !self.context.loop_stack[index].is_infinite {
+            return Err(TypeCheckError::generic_error(
+                "`break` with a value is only allowed inside `loop`",
+            ));
+        }
+        let mut value_obj = self.core.expr_pool.get(value)
+            .ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference in break"))?;
+        let value_ty = value_obj.accept(self)?;
+        self.type_inference.set_expr_type(*value, value_ty.clone());
+
+        let frame = &mut self.context.loop_stack[index];
+        match &frame.break_type {
+            None => frame.break_type = Some(value_ty),
+            // `Unknown` (e.g. a diverging `panic`) and an unsuffixed
+            // `Number` literal yield to the other side's type.
+            Some(prev) if *prev == TypeDecl::Unknown || *prev == TypeDecl::Number => {
+                if value_ty != TypeDecl::Unknown {
+                    frame.break_type = Some(value_ty);
+                }
+            }
+            Some(prev) => {
+                let compatible = prev.is_equivalent(&value_ty)
+                    || value_ty == TypeDecl::Unknown
+                    || (value_ty == TypeDecl::Number && matches!(prev,
+                        TypeDecl::Int8 | TypeDecl::Int16 | TypeDecl::Int32 | TypeDecl::Int64 |
+                        TypeDecl::UInt8 | TypeDecl::UInt16 | TypeDecl::UInt32 | TypeDecl::UInt64));
+                if !compatible {
+                    return Err(TypeCheckError::type_mismatch(prev.clone(), value_ty)
+                        .with_context("break value"));
+                }
+            }
+        }
         Ok(TypeDecl::Unit)
     }
 
@@ -202,18 +373,20 @@ impl<'a> TypeCheckerVisitor<'a> {
         Ok(TypeDecl::Unit)
     }
 
-    fn validate_loop_label(&self, kw: &str, label: Option<DefaultSymbol>) -> Result<(), TypeCheckError> {
+    /// Resolve the loop `kw` targets; returns its index in `loop_stack`.
+    fn validate_loop_label(&self, kw: &str, label: Option<DefaultSymbol>) -> Result<usize, TypeCheckError> {
+        let stack = &self.context.loop_stack;
         match label {
             None => {
-                if self.context.loop_label_stack.is_empty() {
+                if stack.is_empty() {
                     Err(TypeCheckError::generic_error(&format!("`{kw}` outside of a loop")))
                 } else {
-                    Ok(())
+                    Ok(stack.len() - 1)
                 }
             }
             Some(sym) => {
-                if self.context.loop_label_stack.iter().rev().any(|l| *l == Some(sym)) {
-                    Ok(())
+                if let Some(index) = stack.iter().rposition(|f| f.label == Some(sym)) {
+                    Ok(index)
                 } else {
                     let name = self.core.string_interner.resolve(sym).unwrap_or("?");
                     Err(TypeCheckError::generic_error(&format!(
@@ -269,7 +269,7 @@ impl<'a> TypeCheckerVisitor<'a> {
     }
 
     /// Type check struct literal - wrapper with recursion guard
-    pub fn visit_struct_literal_impl(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>) -> Result<TypeDecl, TypeCheckError> {
+    pub fn visit_struct_literal_impl(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
         // Check recursion depth to prevent stack overflow
         if self.type_inference.recursion_depth >= self.type_inference.max_recursion_depth {
             return Err(TypeCheckError::generic_error(
@@ -278,14 +278,14 @@ impl<'a> TypeCheckerVisitor<'a> {
         }
 
         self.type_inference.recursion_depth += 1;
-        let result = self.visit_struct_literal_core(struct_name, fields);
+        let result = self.visit_struct_literal_core(struct_name, fields, type_args);
         self.type_inference.recursion_depth -= 1;
 
         result
     }
 
     /// Core struct literal type checking logic
-    fn visit_struct_literal_core(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>) -> Result<TypeDecl, TypeCheckError> {
+    fn visit_struct_literal_core(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
         // 1. Check if struct definition exists and clone it
         let struct_definition = self.context.get_struct_definition(*struct_name)
             .ok_or_else(|| TypeCheckError::not_found("Struct", &format!("{:?}", struct_name)))?
@@ -296,7 +296,13 @@ impl<'a> TypeCheckerVisitor<'a> {
         let is_generic = generic_params.is_some() && !generic_params.as_ref().unwrap().is_empty();
 
         if is_generic {
-            return self.visit_generic_struct_literal(struct_name, fields, &struct_definition, &generic_params.unwrap());
+            return self.visit_generic_struct_literal(struct_name, fields, &struct_definition, &generic_params.unwrap(), type_args);
+        }
+        if !type_args.is_empty() {
+            return Err(TypeCheckError::generic_error(&format!(
+                "struct '{}' is not generic and takes no type arguments",
+                self.resolve_symbol_name(*struct_name)
+            )));
         }
 
         // 3. Handle non-generic struct (existing logic)
@@ -335,7 +341,16 @@ impl<'a> TypeCheckerVisitor<'a> {
     /// Handle generic struct literal type inference
     pub fn visit_generic_struct_literal(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>,
                                    struct_definition: &crate::type_checker::context::StructDefinition,
-                                   generic_params: &Vec<DefaultSymbol>) -> Result<TypeDecl, TypeCheckError> {
+                                   generic_params: &Vec<DefaultSymbol>,
+                                   type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
+        // Explicit `<..>` arguments (`Box<u64> { .. }`) pre-seed the
+        // substitution, so the field types are concrete before the
+        // field values are checked against them.
+        let explicit = if type_args.is_empty() {
+            None
+        } else {
+            Some(self.explicit_type_substitutions(*struct_name, generic_params, type_args)?)
+        };
         self.type_inference.clear_constraints();
 
         self.context.validate_struct_fields(*struct_name, fields, &self.core)?;
@@ -355,10 +370,19 @@ impl<'a> TypeCheckerVisitor<'a> {
                 .map(|def| &def.type_decl);
 
             if let Some(expected_type) = expected_field_type {
-                let field_type = self.visit_expr(field_expr)?;
+                let (expected_type, field_type) = match &explicit {
+                    Some(subst) => {
+                        let expected_type = expected_type.substitute_generics(subst);
+                        let original_hint = self.type_inference.type_hint.replace(expected_type.clone());
+                        let field_type = self.visit_expr(field_expr);
+                        self.type_inference.type_hint = original_hint;
+                        (expected_type, field_type?)
+                    }
+                    None => (expected_type.clone(), self.visit_expr(field_expr)?),
+                };
 
                 self.type_inference.add_constraint(
-                    expected_type.clone(),
+                    expected_type,
                     field_type.clone(),
                     crate::type_checker::inference::ConstraintContext::FieldAccess {
                         struct_name: *struct_name,
@@ -381,6 +405,9 @@ impl<'a> TypeCheckerVisitor<'a> {
                 )));
             }
         };
+        if let Some(explicit) = explicit {
+            substitutions.extend(explicit);
+        }
 
         for (field_name, field_expr) in fields {
             let field_name_str = self.resolve_symbol_name(*field_name);
@@ -473,8 +500,10 @@ impl<'a> TypeCheckerVisitor<'a> {
 
         self.type_inference.pop_generic_scope();
 
-        let _instantiated_name_str = self.generate_instantiated_struct_name(*struct_name, &substitutions);
-
+        // The substitution travels with the instance as the type args
+        // of the returned `Struct(name, args)`: method calls on it
+        // substitute them into parameter and return types, and the
+        // AOT lowering monomorphises on the same (name, args) pair.
         let mut type_params = Vec::new();
         for generic_param in generic_params {
             if let Some(concrete_type) = substitutions.get(generic_param) {
@@ -591,4 +620,62 @@ impl<'a> TypeCheckerVisitor<'a> {
             )))
         }
     }
-}
\ No newline at end of file
+
+    /// `Meters(5)`: a tuple struct constructor parses as a call. The call
+    /// node is rewritten in place into the struct literal it stands for
+    /// (`Meters { 0: 5 }`) so the backends never see tuple structs, then
+    /// checked as one.
+    pub fn visit_tuple_struct_constructor(&mut self, struct_name: DefaultSymbol, args_ref: &ExprRef, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
+        let args = match self.core.expr_pool.get(args_ref) {
+            Some(Expr::ExprList(args)) => args,
+            _ => return Err(TypeCheckError::generic_error("Invalid arguments reference")),
+        };
+        let field_count = self.context.struct_definitions.get(&struct_name).map_or(0, |def| def.fields.len());
+        if args.len() != field_count {
+            return Err(TypeCheckError::generic_error(&format!(
+                "tuple struct '{}' takes {} field(s), found {}",
+                self.resolve_symbol_name(struct_name), field_count, args.len()
+            )));
+        }
+        let mut fields = Vec::with_capacity(args.len());
+        for (index, arg) in args.into_iter().enumerate() {
+            fields.push((self.tuple_field_symbol(index)?, arg));
+        }
+        if let Some(call_ref) = self.find_expr_ref(|e| matches!(e, Expr::Call(_, a, _) if a == args_ref)) {
+            self.core.expr_pool.update(&call_ref, Expr::StructLiteral(struct_name, fields.clone(), type_args.to_vec()));
+        }
+        self.visit_struct_literal_impl(&struct_name, &fields, type_args)
+    }
+
+    /// `m.0` on a tuple struct: parsed as tuple access, rewritten in place
+    /// into the field access on the positional field.
+    pub fn visit_tuple_struct_field(&mut self, obj: &ExprRef, index: usize, struct_name: DefaultSymbol) -> Result<TypeDecl, TypeCheckError> {
+        let field_count = self.context.struct_definitions.get(&struct_name).map_or(0, |def| def.fields.len());
+        if index >= field_count {
+            return Err(TypeCheckError::generic_error(&format!(
+                "Tuple index {} out of bounds for tuple struct '{}' with {} field(s)",
+                index, self.resolve_symbol_name(struct_name), field_count
+            )));
+        }
+        let field = self.tuple_field_symbol(index)?;
+        if let Some(access_ref) = self.find_expr_ref(|e| matches!(e, Expr::TupleAccess(o, i) if o == obj && *i == index)) {
+            self.core.expr_pool.update(&access_ref, Expr::FieldAccess(*obj, field));
+        }
+        self.visit_field_access_impl(obj, &field)
+    }
+
+    /// Symbol of the positional field `index`. The parser interns these
+    /// when it reads a tuple struct declaration.
+    fn tuple_field_symbol(&self, index: usize) -> Result<DefaultSymbol, TypeCheckError> {
+        self.core.string_interner.get(index.to_string())
+            .ok_or_else(|| TypeCheckError::generic_error(&format!("tuple struct field {} is not declared", index)))
+    }
+
+    /// First expression in the pool matching `pred`. Used to locate the
+    /// parent node of a sub-expression, which is unique in the tree.
+    fn find_expr_ref(&self, pred: impl Fn(&Expr) -> bool) -> Option<ExprRef> {
+        (0..self.core.expr_pool.len())
+            .map(|i| ExprRef(i as u32))
+            .find(|r| self.core.expr_pool.get(r).is_some_and(|e| pred(&e)))
+    }
+}
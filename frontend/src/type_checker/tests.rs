@@ -29,6 +29,7 @@ mod tests {
             imported_function_names: std::collections::HashSet::new(),
             transformed_exprs: HashMap::new(),
             builtin_methods: TypeCheckerVisitor::create_builtin_method_registry(),
+            inline_lua_allowed: false,
         }
     }
 
@@ -176,38 +176,22 @@ impl<'a> TypeCheckerVisitor<'a> {
                 let num_str_owned = self.core.string_interner.resolve(value)
                     .ok_or_else(|| TypeCheckError::generic_error("Failed to resolve number literal"))?
                     .to_string();
-                // Numeric literal separators: `_` between digits is
-                // legal in source (`1_000_000`) but `str::parse` and
-                // `from_str_radix` don't accept it. Strip every `_`
-                // before invoking either.
-                let cleaned = num_str_owned.replace("_", "");
-                let num_str = cleaned.as_str();
+                // `parse_int_literal` handles `_` separators and the
+                // `0x` / `0o` / `0b` radix prefixes, so every literal
+                // spelling lands on the same value here.
                 let num_orig = num_str_owned.as_str();
+                let value = crate::token::parse_int_literal(num_orig);
 
                 // Create the new expression based on target type
                 let new_expr = match target_type {
                     TypeDecl::UInt64 => {
-                        let val = if num_str.starts_with("0x") || num_str.starts_with("0X") {
-                            // Parse hexadecimal literal
-                            u64::from_str_radix(&num_str[2..], 16)
-                                .map_err(|_| TypeCheckError::conversion_error(num_orig, "UInt64"))?
-                        } else {
-                            // Parse decimal literal
-                            num_str.parse::<u64>()
-                                .map_err(|_| TypeCheckError::conversion_error(num_orig, "UInt64"))?
-                        };
+                        let val = value.and_then(|n| u64::try_from(n).ok())
+                            .ok_or_else(|| TypeCheckError::conversion_error(num_orig, "UInt64"))?;
                         Expr::UInt64(val)
                     },
                     TypeDecl::Int64 => {
-                        let val = if num_str.starts_with("0x") || num_str.starts_with("0X") {
-                            // Parse hexadecimal literal
-                            i64::from_str_radix(&num_str[2..], 16)
-                                .map_err(|_| TypeCheckError::conversion_error(num_orig, "Int64"))?
-                        } else {
-                            // Parse decimal literal
-                            num_str.parse::<i64>()
-                                .map_err(|_| TypeCheckError::conversion_error(num_orig, "Int64"))?
-                        };
+                        let val = value.and_then(|n| i64::try_from(n).ok())
+                            .ok_or_else(|| TypeCheckError::conversion_error(num_orig, "Int64"))?;
                         Expr::Int64(val)
                     },
                     _ => {
@@ -231,6 +215,16 @@ impl<'a> TypeCheckerVisitor<'a> {
         self.transformed_exprs.clear();
     }
 
+    /// Apply the pending transformation of one expression right away.
+    /// Used where a builtin pins a literal to another operand's type: the
+    /// end-of-function pass would otherwise default a still-`Number`
+    /// literal to u64 over the recorded transformation.
+    pub fn commit_expr_transformation(&mut self, expr_ref: &ExprRef) {
+        if let Some(new_expr) = self.transformed_exprs.remove(expr_ref) {
+            self.core.expr_pool.update(expr_ref, new_expr);
+        }
+    }
+
     /// Update variable type in context if identifier was type-converted
     pub fn update_identifier_types(&mut self, expr_ref: &ExprRef, original_ty: &TypeDecl, resolved_ty: &TypeDecl) -> Result<(), TypeCheckError> {
         if original_ty == &TypeDecl::Number && resolved_ty != &TypeDecl::Number
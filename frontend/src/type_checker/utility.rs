@@ -214,38 +214,62 @@ impl<'a> TypeCheckerVisitor<'a> {
                 s1 == s2
             }
 
+            // Dicts are compatible when both key and value types are,
+            // so `dict[P, u64]` accepts a literal keyed by `P { .. }`
+            (TypeDecl::Dict(expected_key, expected_value), TypeDecl::Dict(actual_key, actual_value)) => {
+                self.are_types_compatible(expected_key, actual_key)
+                    && self.are_types_compatible(expected_value, actual_value)
+            }
+
             // No other implicit conversions allowed (including bool -> numeric)
             _ => false,
         }
     }
     
     /// Handle array slice assignment (both single element and range)
-    pub fn handle_array_slice_assign(&mut self, element_types: &Vec<TypeDecl>, start: &Option<ExprRef>, end: &Option<ExprRef>, value_type: &TypeDecl) -> Result<TypeDecl, TypeCheckError> {
-        if start.is_some() && end.is_none() {
-            // Single element assignment: arr[index] = value
-            if element_types.len() == 1 {
-                let element_type = &element_types[0];
-                if element_type != value_type && !self.are_types_compatible(element_type, value_type) {
-                    return Err(TypeCheckError::type_mismatch(
-                        element_type.clone(),
-                        value_type.clone()
-                    ));
-                }
+    pub fn handle_array_slice_assign(&mut self, element_types: &Vec<TypeDecl>, slice_info: &SliceInfo, value_type: &TypeDecl) -> Result<TypeDecl, TypeCheckError> {
+        if slice_info.slice_type == SliceType::SingleElement {
+            // Single element assignment: arr[index] = value. A sized
+            // array repeats its element type once per slot, so the
+            // first entry stands for all of them.
+            if let Some(element_type) = element_types.first()
+                && element_type != value_type && !self.are_types_compatible(element_type, value_type) {
+                return Err(TypeCheckError::type_mismatch(
+                    element_type.clone(),
+                    value_type.clone()
+                ));
             }
         } else {
             // Range assignment: arr[start..end] = value or arr[start..] = value
+            // Bounds may be negative (counted from the end), same as range reads
+            let original_hint = self.type_inference.type_hint.clone();
+            self.type_inference.type_hint = Some(TypeDecl::Int64);
+            for bound in [&slice_info.start, &slice_info.end].into_iter().flatten() {
+                let bound_type = self.visit_expr(bound)?;
+                match bound_type {
+                    TypeDecl::UInt64 | TypeDecl::Int64 | TypeDecl::Unknown => {}
+                    TypeDecl::Number => {
+                        self.transform_numeric_expr(bound, &TypeDecl::Int64)?;
+                    }
+                    _ => {
+                        self.type_inference.type_hint = original_hint;
+                        return Err(TypeCheckError::array_error(&format!(
+                            "Slice bound must be an integer type, but got {:?}", bound_type
+                        )));
+                    }
+                }
+            }
+            self.type_inference.type_hint = original_hint;
+
             // Value must be an array with compatible element types
             match value_type {
                 TypeDecl::Array(value_elements, _) => {
-                    if element_types.len() == 1 && value_elements.len() == 1 {
-                        let element_type = &element_types[0];
-                        let value_element = &value_elements[0];
-                        if element_type != value_element && !self.are_types_compatible(element_type, value_element) {
-                            return Err(TypeCheckError::type_mismatch(
-                                element_type.clone(),
-                                value_element.clone()
-                            ));
-                        }
+                    if let (Some(element_type), Some(value_element)) = (element_types.first(), value_elements.first())
+                        && element_type != value_element && !self.are_types_compatible(element_type, value_element) {
+                        return Err(TypeCheckError::type_mismatch(
+                            element_type.clone(),
+                            value_element.clone()
+                        ));
                     }
                 }
                 _ => {
@@ -305,8 +329,29 @@ impl<'a> TypeCheckerVisitor<'a> {
     
     /// Extract expression type mappings after type checking
     pub fn get_expr_types(&self) -> HashMap<crate::ast::ExprRef, crate::type_decl::TypeDecl> {
-        // Return a clone of the comprehensive expr_types mapping
-        self.type_inference.expr_types.clone()
+        // Number literals are rewritten in place by `finalize_number_types`
+        // after their (still `Number`) type was recorded, so report the
+        // finalized literal's type instead of the placeholder.
+        let mut types = self.type_inference.expr_types.clone();
+        for (expr_ref, ty) in types.iter_mut() {
+            if *ty != TypeDecl::Number {
+                continue;
+            }
+            let finalized = match self.core.expr_pool.get(expr_ref) {
+                Some(Expr::Int64(_)) => TypeDecl::Int64,
+                Some(Expr::UInt64(_)) => TypeDecl::UInt64,
+                Some(Expr::Int8(_)) => TypeDecl::Int8,
+                Some(Expr::Int16(_)) => TypeDecl::Int16,
+                Some(Expr::Int32(_)) => TypeDecl::Int32,
+                Some(Expr::UInt8(_)) => TypeDecl::UInt8,
+                Some(Expr::UInt16(_)) => TypeDecl::UInt16,
+                Some(Expr::UInt32(_)) => TypeDecl::UInt32,
+                Some(Expr::Float64(_)) => TypeDecl::Float64,
+                _ => continue,
+            };
+            *ty = finalized;
+        }
+        types
     }
     
     /// Get human-readable type name for error messages
@@ -316,6 +361,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             TypeDecl::UInt64 => "u64".to_string(),
             TypeDecl::Int64 => "i64".to_string(),
             TypeDecl::String => "string".to_string(),
+            TypeDecl::Bytes => "bytes".to_string(),
             TypeDecl::Number => "number".to_string(),
             TypeDecl::Unit => "unit".to_string(),
             TypeDecl::Unknown => "unknown".to_string(),
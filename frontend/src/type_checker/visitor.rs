@@ -5,8 +5,9 @@ use crate::ast::*;
 use crate::type_decl::*;
 use crate::module_resolver::ModuleResolver;
 use crate::visitor::ProgramVisitor;
+use crate::type_checker::context::DefinitionKind;
 use crate::type_checker::{
-    Acceptable, BuiltinFunctionSignature, CoreReferences, TypeCheckContext, TypeCheckError,
+    Acceptable, BuiltinFunctionSignature, CoreReferences, TypeCheckContext, TypeCheckError, TypeCheckErrorKind,
     TypeInferenceState, FunctionCheckingState, PerformanceOptimization,
 };
 
@@ -32,6 +33,10 @@ pub struct TypeCheckerVisitor<'a> {
     pub builtin_methods: HashMap<(TypeDecl, String), BuiltinMethod>,
     // Builtin function signatures table
     pub builtin_function_signatures: Vec<BuiltinFunctionSignature>,
+    /// Whether `lua!(...)` is accepted. Only a driver checking for the
+    /// Lua target sets this; every other backend gets the
+    /// "backend-only construct" error.
+    pub inline_lua_allowed: bool,
 }
 
 impl<'a> TypeCheckerVisitor<'a> {
@@ -70,6 +75,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             builtin_methods: Self::create_builtin_method_registry(),
             builtin_function_signatures: TypeCheckerVisitor::create_builtin_function_signatures(),
             transformed_exprs: HashMap::new(),
+            inline_lua_allowed: false,
         };
 
         // Process package and imports immediately
@@ -100,6 +106,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             let stmt_ref = StmtRef(i as u32);
             if let Some(stmt) = visitor.core.stmt_pool.get(&stmt_ref)
                 && let Stmt::StructDecl { name, generic_params: _, generic_bounds: _, fields, visibility } = stmt {
+                    visitor.record_struct_definition(&stmt_ref, name);
                     visitor.context.register_struct(
                         name,
                         fields.clone(),
@@ -111,6 +118,72 @@ impl<'a> TypeCheckerVisitor<'a> {
         visitor
     }
 
+    /// Register every function signature (under its module qualifier)
+    /// and every struct of `program`, ahead of checking any of them.
+    /// `with_program` does the same while it builds the checker.
+    pub fn register_declarations(&mut self, program: &Program) {
+        for (idx, func) in program.function.iter().enumerate() {
+            let qualifier = program
+                .function_module_paths
+                .get(idx)
+                .and_then(|opt| opt.as_ref())
+                .and_then(|path| path.last().copied());
+            self.add_function_with_module(qualifier, func.clone());
+        }
+        for i in 0..program.statement.len() {
+            let stmt_ref = StmtRef(i as u32);
+            if let Some(Stmt::StructDecl { name, generic_params, fields, visibility, .. }) =
+                program.statement.get(&stmt_ref)
+            {
+                self.record_struct_definition(&stmt_ref, name);
+                self.context.register_struct(name, fields, visibility);
+                if !generic_params.is_empty() {
+                    self.context.set_struct_generic_params(name, generic_params);
+                }
+            }
+        }
+    }
+
+    fn record_struct_definition(&mut self, stmt_ref: &StmtRef, name: DefaultSymbol) {
+        if let Some(location) = self.get_stmt_location(stmt_ref) {
+            self.context.record_definition(DefinitionKind::Struct, None, name, location.offset as usize);
+        }
+    }
+
+    /// One error per function or struct name declared more than once,
+    /// located at the later declaration and naming the earlier one.
+    pub fn duplicate_definition_errors(&self) -> Vec<TypeCheckError> {
+        self.context
+            .duplicate_definitions
+            .iter()
+            .map(|dup| {
+                let (kind, note) = match dup.kind {
+                    DefinitionKind::Function => ("function", " (functions cannot be overloaded)"),
+                    DefinitionKind::Struct => ("struct", ""),
+                };
+                let (line, column) = self.calculate_line_col_from_offset(dup.first);
+                let location = self.node_to_source_location(&Node::new(dup.second, dup.second));
+                TypeCheckError::generic_error(&format!(
+                    "{kind} '{}' is already defined at line {line}, column {column}{note}",
+                    self.resolve_symbol_name(dup.name),
+                ))
+                .with_location(location)
+            })
+            .collect()
+    }
+
+    /// Check a top-level `const` initialiser against its declared type
+    /// and bind the name. Consts see only the consts checked before
+    /// them, so callers go in declaration order.
+    pub fn check_const(&mut self, c: &ConstDecl) -> Result<(), TypeCheckError> {
+        let value_ty = self.visit_expr(&c.value)?;
+        if !value_ty.is_equivalent(&c.type_decl) && value_ty != TypeDecl::Number {
+            return Err(TypeCheckError::type_mismatch(c.type_decl.clone(), value_ty));
+        }
+        self.context.set_var(c.name, c.type_decl.clone());
+        Ok(())
+    }
+
     // Keep the old API for backward compatibility
     pub fn new(stmt_pool: &'a StmtPool, expr_pool: &'a mut ExprPool, string_interner: &'a DefaultStringInterner, location_pool: &'a LocationPool) -> Self {
         Self {
@@ -125,6 +198,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             imported_modules: HashMap::new(),
             imported_function_names: std::collections::HashSet::new(),
             transformed_exprs: HashMap::new(),
+            inline_lua_allowed: false,
             builtin_methods: Self::create_builtin_method_registry(),
             builtin_function_signatures: TypeCheckerVisitor::create_builtin_function_signatures(),
         }
@@ -249,6 +323,323 @@ impl<'a> TypeCheckerVisitor<'a> {
                 arg_types: vec![TypeDecl::Unknown],
                 return_type: TypeDecl::Unit,
             },
+            // Leveled logging.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::LogDebug,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::LogInfo,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::LogWarn,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::LogError,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unit,
+            },
+            // Binary file I/O. Arg types are enforced in
+            // visit_builtin_call (the table alone is informational).
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ReadBytes,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Bytes,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::WriteBytes,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::String, TypeDecl::Bytes],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::BytesFromPtr,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Ptr, TypeDecl::UInt64],
+                return_type: TypeDecl::Bytes,
+            },
+            // Standard input.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ReadLine,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::String,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ReadAll,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::String,
+            },
+            // Process spawning. The argument list is `[str; N]` for any
+            // N, checked in visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Exec,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::String, TypeDecl::Array(vec![TypeDecl::String], 0)],
+                return_type: TypeDecl::Tuple(vec![TypeDecl::Int64, TypeDecl::String, TypeDecl::String]),
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::HttpGet,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Tuple(vec![TypeDecl::UInt64, TypeDecl::String]),
+            },
+            // TCP sockets; handles are u64.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpListen,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::UInt64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpAccept,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::UInt64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpRead,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::String,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpWrite,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::UInt64, TypeDecl::String],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpClose,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TcpLocalPort,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::UInt64,
+            },
+            // JSON. The parse result is dynamically typed; stringify
+            // accepts any value, checked in visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::JsonParse,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::JsonStringify,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Unknown],
+                return_type: TypeDecl::String,
+            },
+            // Runtime type inspection. `is<T>` / `downcast<T>` carry
+            // their target type and are checked in visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::TypeOf,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Unknown],
+                return_type: TypeDecl::String,
+            },
+            // Struct <-> dict conversion. The struct argument and the
+            // `from_dict<T>` target are checked in visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ToDict,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Unknown],
+                return_type: TypeDecl::Dict(Box::new(TypeDecl::String), Box::new(TypeDecl::Unknown)),
+            },
+            // Deep copy; the result takes the argument's type in
+            // visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Clone,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Unknown],
+                return_type: TypeDecl::Unknown,
+            },
+            // Seedable PRNG. `random_range` is `(T, T) -> T` over i64 /
+            // u64, resolved in visit_builtin_call like min / max.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Seed,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Random,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::Float64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::RandomRange,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Unknown, TypeDecl::Unknown],
+                return_type: TypeDecl::Unknown,
+            },
+            // Time.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::NowMillis,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::UInt64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Clock,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::Float64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Sleep,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::FormatTimestamp,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Int64, TypeDecl::String],
+                return_type: TypeDecl::String,
+            },
+            // Cooperative tasks.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Spawn,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Generator(Box::new(TypeDecl::Unit))],
+                return_type: TypeDecl::Unit,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::YieldNow,
+                arg_count: 0,
+                arg_types: vec![],
+                return_type: TypeDecl::Bool,
+            },
+            // Integer <-> text. The parse results are Option<T>, built in
+            // visit_builtin_call once the core `Option` enum is known.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ParseU64,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ParseI64,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::String],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ToHex,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::UInt64],
+                return_type: TypeDecl::String,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::FormatRadix,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Unknown, TypeDecl::UInt64],
+                return_type: TypeDecl::String,
+            },
+            // Number formatting.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Format,
+                arg_count: 3,
+                arg_types: vec![TypeDecl::Unknown, TypeDecl::UInt64, TypeDecl::UInt32],
+                return_type: TypeDecl::String,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::FormatFixed,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Float64, TypeDecl::UInt64],
+                return_type: TypeDecl::String,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::FormatExp,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Float64, TypeDecl::UInt64],
+                return_type: TypeDecl::String,
+            },
+            // Sorting. The result has the array argument's type, worked
+            // out in visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Sort,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Unknown, TypeDecl::Unknown],
+                return_type: TypeDecl::Unknown,
+            },
+            // Array algorithms. `Unknown` stands for the element type
+            // `T`, taken from the array argument in visit_builtin_call;
+            // binary_search's Option<u64> is built there too.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::BinarySearch,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0), TypeDecl::Unknown],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::LowerBound,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0), TypeDecl::Unknown],
+                return_type: TypeDecl::UInt64,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Reverse,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0)],
+                return_type: TypeDecl::Array(vec![TypeDecl::Unknown], 0),
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::MinOf,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0)],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::MaxOf,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0)],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::SumOf,
+                arg_count: 1,
+                arg_types: vec![TypeDecl::Array(vec![TypeDecl::Unknown], 0)],
+                return_type: TypeDecl::Unknown,
+            },
+            // Lua-style patterns. find's Option<str> is built in
+            // visit_builtin_call.
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Matches,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::String, TypeDecl::String],
+                return_type: TypeDecl::Bool,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::Find,
+                arg_count: 2,
+                arg_types: vec![TypeDecl::String, TypeDecl::String],
+                return_type: TypeDecl::Unknown,
+            },
+            BuiltinFunctionSignature {
+                func: BuiltinFunction::ReplacePattern,
+                arg_count: 3,
+                arg_types: vec![TypeDecl::String, TypeDecl::String, TypeDecl::String],
+                return_type: TypeDecl::String,
+            },
             // `panic(msg: str)` aborts the run. The "return type" is Unknown
             // so the call expression unifies with any surrounding context
             // (e.g. `if c { panic("...") } else { 5i64 }`); the value is
@@ -343,6 +734,7 @@ impl<'a> TypeCheckerVisitor<'a> {
             builtin_methods: Self::create_builtin_method_registry(),
             builtin_function_signatures: TypeCheckerVisitor::create_builtin_function_signatures(),
             transformed_exprs: HashMap::new(),
+            inline_lua_allowed: false,
         }
     }
 
@@ -440,6 +832,47 @@ impl<'a> TypeCheckerVisitor<'a> {
     }
 
     pub fn type_check(&mut self, func: Rc<Function>) -> Result<TypeDecl, TypeCheckError> {
+        self.function_checking.checking_stack.push(func.name);
+        let result = self.type_check_body(func.clone());
+        self.function_checking.checking_stack.pop();
+
+        // A call into a function whose body is still being checked has
+        // no type to go on unless the function is annotated; it types
+        // as `Unknown`. That only matters if the function returns a
+        // value, so an unannotated recursive function is rejected when
+        // its body yields one, or when an `Unknown` from such a call
+        // broke the body's check.
+        let cycle_of = |name: DefaultSymbol| self.function_checking.recursive_cycles.get(&name);
+        match result {
+            Ok(ty) if !matches!(ty, TypeDecl::Unit | TypeDecl::Unknown) => match cycle_of(func.name) {
+                Some(cycle) if func.return_type.is_none() => {
+                    let location = self.node_to_source_location(&func.node);
+                    Err(self.recursive_return_type_error(func.name, cycle).with_location(location))
+                }
+                _ => Ok(ty),
+            },
+            Err(err) if involves_unknown(&err) => match cycle_of(func.name) {
+                Some(cycle) => {
+                    let located = self.recursive_return_type_error(func.name, cycle);
+                    Err(TypeCheckError { location: err.location, ..located })
+                }
+                None => Err(err),
+            },
+            other => other,
+        }
+    }
+
+    fn recursive_return_type_error(&self, name: DefaultSymbol, cycle: &[DefaultSymbol]) -> TypeCheckError {
+        let path: Vec<String> = cycle.iter().map(|n| self.resolve_symbol_name(*n)).collect();
+        TypeCheckError::generic_error(&format!(
+            "function '{}' is recursive ({}) and needs an explicit return type: \
+             calls made while its body is checked cannot know what it returns",
+            self.resolve_symbol_name(name),
+            path.join(" -> "),
+        ))
+    }
+
+    fn type_check_body(&mut self, func: Rc<Function>) -> Result<TypeDecl, TypeCheckError> {
         let mut last = TypeDecl::Unit;
         let s = func.code;
 
@@ -488,9 +921,9 @@ impl<'a> TypeCheckerVisitor<'a> {
         let statements = match self.core.stmt_pool.get(&s).ok_or_else(|| TypeCheckError::generic_error("Invalid statement reference"))? {
             Stmt::Expression(e) => {
                 match self.core.expr_pool.get(&e).ok_or_else(|| TypeCheckError::generic_error("Invalid expression reference"))? {
-                    Expr::Block(statements) => {
-                        statements.clone()  // Clone required: statements is used in multiple loops and we need mutable access to self
-                    }
+                    // `get` already hands back an owned list, so the body
+                    // can be walked while `self` is borrowed mutably.
+                    Expr::Block(statements) => statements,
                     _ => {
                         return Err(TypeCheckError::generic_error("type_check: expected block expression"));
                     }
@@ -526,6 +959,23 @@ impl<'a> TypeCheckerVisitor<'a> {
             }
         });
 
+        // A `Generator<T>` function is checked against its item type:
+        // `yield` takes `T` and the body itself produces no value.
+        let generator_item = match &func.return_type {
+            Some(TypeDecl::Generator(item)) => Some((**item).clone()),
+            _ => None,
+        };
+        let outer_generator = std::mem::replace(&mut self.context.generator_item, generator_item.clone());
+        let placement = match generator_item {
+            Some(_) => self.check_yield_placement(&statements),
+            None => Ok(()),
+        };
+        if let Err(e) = placement {
+            self.context.current_fn_generic_bounds = prev_bounds;
+            self.context.generator_item = outer_generator;
+            return Err(e);
+        }
+
         // `requires` clauses see only the parameters, not `result`. Each must
         // be a bool expression — anything else is rejected here so the
         // diagnostic points at the contract, not the call site.
@@ -537,17 +987,20 @@ impl<'a> TypeCheckerVisitor<'a> {
         let original_hint = self.type_inference.type_hint.clone();
         if let Some(numeric_type) = self.scan_numeric_type_hint(&statements) {
             self.type_inference.type_hint = Some(numeric_type);
+        } else if let Some(ref item) = generator_item {
+            self.type_inference.type_hint = Some(item.clone());
         } else if let Some(ref return_type) = func.return_type {
             // Use function return type as type hint for Number literals
             self.type_inference.type_hint = Some(return_type.clone());
         }
 
         for stmt in statements.iter() {
-            let stmt_obj = self.core.stmt_pool.get(stmt).ok_or_else(|| TypeCheckError::generic_error("Invalid statement reference"))?;
-            let res = stmt_obj.clone().accept(self);
+            let mut stmt_obj = self.core.stmt_pool.get(stmt).ok_or_else(|| TypeCheckError::generic_error("Invalid statement reference"))?;
+            let res = stmt_obj.accept(self);
             if res.is_err() {
                 // Restore bounds so a following type-check doesn't inherit them.
                 self.context.current_fn_generic_bounds = prev_bounds;
+                self.context.generator_item = outer_generator;
                 return res;
             } else {
                 last = res?;
@@ -555,8 +1008,15 @@ impl<'a> TypeCheckerVisitor<'a> {
         }
         self.pop_context();
         self.context.current_fn_generic_bounds = prev_bounds;
+        self.context.generator_item = outer_generator;
         self.function_checking.call_depth -= 1;
 
+        // Calling a generator function hands back the generator itself;
+        // whatever the body's last statement evaluated to is discarded.
+        if generator_item.is_some() {
+            last = func.return_type.clone().unwrap_or(TypeDecl::Unit);
+        }
+
         // Restore original type hint
         self.type_inference.type_hint = original_hint;
 
@@ -701,9 +1161,9 @@ impl<'a> TypeCheckerVisitor<'a> {
         cond: &ExprRef,
         kind: &str,
     ) -> Result<(), TypeCheckError> {
-        let expr = self.core.expr_pool.get(cond)
+        let mut expr = self.core.expr_pool.get(cond)
             .ok_or_else(|| TypeCheckError::generic_error("Invalid contract expression reference"))?;
-        let ty = expr.clone().accept(self)?;
+        let ty = expr.accept(self)?;
         if ty != TypeDecl::Bool {
             return Err(TypeCheckError::generic_error(
                 &format!("`{kind}` clause must be of type bool, got {ty:?}")
@@ -712,3 +1172,34 @@ impl<'a> TypeCheckerVisitor<'a> {
         Ok(())
     }
 }
+
+/// Whether `err` is a type mismatch with `Unknown` on one side.
+fn involves_unknown(err: &TypeCheckError) -> bool {
+    match &err.kind {
+        TypeCheckErrorKind::TypeMismatch { expected, actual } => {
+            *expected == TypeDecl::Unknown || *actual == TypeDecl::Unknown
+        }
+        TypeCheckErrorKind::TypeMismatchOperation(data) => {
+            data.left == TypeDecl::Unknown || data.right == TypeDecl::Unknown
+        }
+        _ => false,
+    }
+}
+
+/// The top-level declaration statements of `program`: struct, enum and
+/// trait declarations, then impl blocks. The statement pool also holds
+/// every statement of every body; those are checked through their
+/// function.
+pub(crate) fn declaration_statements(program: &Program) -> (Vec<StmtRef>, Vec<StmtRef>) {
+    let mut type_decls = Vec::new();
+    let mut impl_blocks = Vec::new();
+    for index in 0..program.statement.len() {
+        let stmt_ref = StmtRef(index as u32);
+        match program.statement.get(&stmt_ref) {
+            Some(Stmt::StructDecl { .. } | Stmt::EnumDecl { .. } | Stmt::TraitDecl { .. }) => type_decls.push(stmt_ref),
+            Some(Stmt::ImplBlock { .. }) => impl_blocks.push(stmt_ref),
+            _ => {}
+        }
+    }
+    (type_decls, impl_blocks)
+}
@@ -8,6 +8,7 @@ use crate::type_checker::{
     CoreReferences, method,
 };
 use crate::type_checker::{Acceptable, TypeCheckerCore, TypeInferenceManager};
+use crate::type_checker::visitor::declaration_statements;
 
 impl Acceptable for Expr {
     fn accept(&mut self, visitor: &mut dyn AstVisitor) -> Result<TypeDecl, TypeCheckError> {
@@ -18,7 +19,7 @@ impl Acceptable for Expr {
             Expr::IfElifElse(cond, then_block, elif_pairs, else_block) => visitor.visit_if_elif_else(cond, then_block, elif_pairs, else_block),
             Expr::Assign(lhs, rhs) => visitor.visit_assign(lhs, rhs),
             Expr::Identifier(name) => visitor.visit_identifier(*name),
-            Expr::Call(fn_name, args) => visitor.visit_call(*fn_name, args),
+            Expr::Call(fn_name, args, type_args) => visitor.visit_call(*fn_name, args, type_args),
             Expr::Int64(val) => visitor.visit_int64_literal(val),
             Expr::UInt64(val) => visitor.visit_uint64_literal(val),
             Expr::Int8(val) => visitor.visit_int8_literal(val),
@@ -30,17 +31,18 @@ impl Acceptable for Expr {
             Expr::Float64(val) => visitor.visit_float64_literal(val),
             Expr::Number(val) => visitor.visit_number_literal(*val),
             Expr::String(val) => visitor.visit_string_literal(*val),
+            Expr::Bytes(val) => visitor.visit_bytes_literal(*val),
             Expr::True | Expr::False => visitor.visit_boolean_literal(self),
             Expr::Null => visitor.visit_null_literal(),
             Expr::ExprList(items) => visitor.visit_expr_list(items),
             Expr::ArrayLiteral(elements) => visitor.visit_array_literal(elements),
             Expr::FieldAccess(obj, field) => visitor.visit_field_access(obj, field),
             Expr::MethodCall(obj, method, args) => visitor.visit_method_call(obj, method, args),
-            Expr::StructLiteral(struct_name, fields) => visitor.visit_struct_literal(struct_name, fields),
+            Expr::StructLiteral(struct_name, fields, type_args) => visitor.visit_struct_literal(struct_name, fields, type_args),
             Expr::QualifiedIdentifier(path) => visitor.visit_qualified_identifier(path),
             Expr::BuiltinMethodCall(receiver, method, args) => visitor.visit_builtin_method_call(receiver, method, args),
-            Expr::SliceAssign(object, start, end, value) => {
-                visitor.visit_slice_assign(object, start, end, value)
+            Expr::SliceAssign(object, slice_info, value) => {
+                visitor.visit_slice_assign(object, slice_info, value)
             },
             Expr::AssociatedFunctionCall(struct_name, function_name, args) => {
                 visitor.visit_associated_function_call(*struct_name, *function_name, args)
@@ -54,11 +56,16 @@ impl Acceptable for Expr {
             Expr::TupleAccess(tuple, index) => visitor.visit_tuple_access(tuple, *index),
             Expr::Cast(expr, target_type) => visitor.visit_cast(expr, target_type),
             Expr::With(allocator, body) => visitor.visit_with(allocator, body),
+            Expr::WithResource { name, resource, body } => visitor.visit_with_resource(*name, resource, body),
+            Expr::TryCatch { body, error, handler } => visitor.visit_try_catch(body, *error, handler),
             Expr::Match(scrutinee, arms) => visitor.visit_match(scrutinee, arms),
             Expr::Range(start, end) => visitor.visit_range(start, end),
             Expr::Closure { params, return_type, body } => {
                 visitor.visit_closure(params, return_type, body)
             }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                visitor.visit_comprehension(element, value, *var, source, filter)
+            }
         }
     }
 }
@@ -70,9 +77,10 @@ impl Acceptable for Stmt {
             Stmt::Var(name, type_decl, expr) => visitor.visit_var(*name, type_decl, expr),
             Stmt::Val(name, type_decl, expr) => visitor.visit_val(*name, type_decl, expr),
             Stmt::Return(expr) => visitor.visit_return(expr),
+            Stmt::Yield(expr) => visitor.visit_yield(expr),
             Stmt::For(label, init, cond, step, body) => visitor.visit_for(*label, *init, cond, step, body),
             Stmt::While(label, cond, body) => visitor.visit_while(*label, cond, body),
-            Stmt::Break(label) => visitor.visit_break(*label),
+            Stmt::Break(label, value) => visitor.visit_break(*label, value),
             Stmt::Continue(label) => visitor.visit_continue(*label),
             Stmt::StructDecl { name, generic_params, generic_bounds, fields, visibility } => visitor.visit_struct_decl(*name, generic_params, generic_bounds, fields, visibility),
             Stmt::ImplBlock { target_type, target_type_args, methods, trait_name, trait_type_args } => visitor.visit_impl_block_with_trait_args(*target_type, target_type_args, methods, *trait_name, trait_type_args),
@@ -97,13 +105,25 @@ impl<'a> ProgramVisitor for TypeCheckerVisitor<'a> {
             self.visit_import(import_decl)?;
         }
 
-        // Process all statements in the program (this includes StructDecl and ImplBlock)
-        for index in 0..program.statement.len() {
-            let stmt_ref = StmtRef(index as u32);
-            self.visit_stmt(&stmt_ref)?;
+        // Every function signature and struct is known before anything
+        // is checked, so no check depends on where an item sits in the
+        // file. Type declarations go first, then consts, then impl
+        // blocks (which need their target and trait), then bodies.
+        self.register_declarations(program);
+        if let Some(err) = self.duplicate_definition_errors().into_iter().next() {
+            return Err(err);
+        }
+        let (type_decls, impl_blocks) = declaration_statements(program);
+        for stmt_ref in &type_decls {
+            self.visit_stmt(stmt_ref)?;
+        }
+        for c in &program.consts {
+            self.check_const(c)?;
+        }
+        for stmt_ref in &impl_blocks {
+            self.visit_stmt(stmt_ref)?;
         }
 
-        // Process all functions in the program
         for function in &program.function {
             self.type_check(function.clone())?;
         }
@@ -210,8 +230,8 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
     // Function and Method Type Checking
     // =========================================================================
 
-    fn visit_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
-        self.visit_call(fn_name, args_ref)
+    fn visit_call(&mut self, fn_name: DefaultSymbol, args_ref: &ExprRef, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_call(fn_name, args_ref, type_args)
     }
 
     // =========================================================================
@@ -258,8 +278,8 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         self.visit_slice_access_impl(object, slice_info)
     }
 
-    fn visit_slice_assign(&mut self, object: &ExprRef, start: &Option<ExprRef>, end: &Option<ExprRef>, value: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
-        self.visit_slice_assign_impl(object, start, end, value)
+    fn visit_slice_assign(&mut self, object: &ExprRef, slice_info: &SliceInfo, value: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_slice_assign_impl(object, slice_info, value)
     }
 
     fn visit_associated_function_call(&mut self, struct_name: DefaultSymbol, function_name: DefaultSymbol, args: &Vec<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
@@ -308,6 +328,10 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         Ok(TypeDecl::Range(Box::new(element_ty)))
     }
 
+    fn visit_comprehension(&mut self, element: &ExprRef, value: &Option<ExprRef>, var: DefaultSymbol, source: &ExprRef, filter: &Option<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_comprehension_impl(element, value, var, source, filter)
+    }
+
     fn visit_with(&mut self, allocator: &ExprRef, body: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
         // The RHS of `with allocator = ...` must evaluate to an Allocator handle.
         // Three accept paths:
@@ -379,6 +403,53 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         self.visit_expr(body)
     }
 
+    fn visit_with_resource(&mut self, name: DefaultSymbol, resource: &ExprRef, body: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+        // `with name = resource { ... }` calls `resource.__close__()` on
+        // the way out, so the resource must be a struct that declares
+        // one. Its shape (receiver only, no result) is checked where
+        // the impl is validated.
+        let resource_ty = self.visit_expr(resource)?;
+        let struct_name = match &resource_ty {
+            TypeDecl::Struct(name, _) => Some(*name),
+            TypeDecl::Identifier(name) if self.context.struct_definitions.contains_key(name) => Some(*name),
+            _ => None,
+        };
+        let has_close = struct_name.is_some_and(|s| {
+            let close = self.core.string_interner.get("__close__");
+            close.is_some_and(|c| self.context.struct_methods.get(&s).is_some_and(|m| m.contains_key(&c)))
+        });
+        if !has_close {
+            let binding = self.core.string_interner.resolve(name).unwrap_or("?").to_string();
+            return Err(TypeCheckError::generic_error(&format!(
+                "`with {binding} = ...` requires a value whose type declares `fn __close__(&mut self)`, but got {:?}",
+                resource_ty
+            )));
+        }
+        self.push_context();
+        self.context.set_var(name, resource_ty);
+        let result = self.visit_expr(body);
+        self.pop_context();
+        result
+    }
+
+    fn visit_try_catch(&mut self, body: &ExprRef, error: DefaultSymbol, handler: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+        // The handler sees the failure's message as a `str`. Both
+        // blocks produce the expression's value, so they unify like
+        // if/else branches: a diverging (`Unknown`) side takes the
+        // other's type, and mismatched types make the whole thing Unit.
+        let body_ty = self.visit_expr(body)?;
+        self.push_context();
+        self.context.set_var(error, TypeDecl::String);
+        let handler_ty = self.visit_expr(handler);
+        self.pop_context();
+        let handler_ty = handler_ty?;
+        Ok(match (body_ty, handler_ty) {
+            (TypeDecl::Unknown, other) | (other, TypeDecl::Unknown) => other,
+            (body_ty, handler_ty) if body_ty.is_equivalent(&handler_ty) => body_ty,
+            _ => TypeDecl::Unit,
+        })
+    }
+
     // =========================================================================
     // Statement Type Checking
     // =========================================================================
@@ -399,6 +470,10 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         self.visit_return(expr)
     }
 
+    fn visit_yield(&mut self, expr: &ExprRef) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_yield_impl(expr)
+    }
+
     // =========================================================================
     // Control Flow Type Checking
     // =========================================================================
@@ -411,8 +486,8 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         self.visit_while_impl(label, cond, body)
     }
 
-    fn visit_break(&mut self, label: Option<DefaultSymbol>) -> Result<TypeDecl, TypeCheckError> {
-        self.visit_break_impl(label)
+    fn visit_break(&mut self, label: Option<DefaultSymbol>, value: &Option<ExprRef>) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_break_impl(label, value)
     }
 
     fn visit_continue(&mut self, label: Option<DefaultSymbol>) -> Result<TypeDecl, TypeCheckError> {
@@ -508,8 +583,8 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
         self.visit_method_call_impl(obj, method, args)
     }
 
-    fn visit_struct_literal(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>) -> Result<TypeDecl, TypeCheckError> {
-        self.visit_struct_literal_impl(struct_name, fields)
+    fn visit_struct_literal(&mut self, struct_name: &DefaultSymbol, fields: &Vec<(DefaultSymbol, ExprRef)>, type_args: &[TypeDecl]) -> Result<TypeDecl, TypeCheckError> {
+        self.visit_struct_literal_impl(struct_name, fields, type_args)
     }
 
     fn visit_qualified_identifier(&mut self, path: &Vec<DefaultSymbol>) -> Result<TypeDecl, TypeCheckError> {
@@ -603,6 +678,24 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
             return Ok(TypeDecl::UInt64);
         }
 
+        // `lua!("...")` takes the annotated type (`val n: u64 =
+        // lua!("...")`) and is `unit` in statement position.
+        if matches!(func, BuiltinFunction::InlineLua) {
+            let is_literal = args.len() == 1
+                && matches!(self.core.expr_pool.get(&args[0]), Some(Expr::String(_)));
+            if !is_literal {
+                return Err(TypeCheckError::generic_error(
+                    "lua!(...) expects a single plain string literal",
+                ));
+            }
+            if !self.inline_lua_allowed {
+                return Err(TypeCheckError::generic_error(
+                    "lua!(...) is a backend-only construct: only the Lua backend can compile it; guard the item with @cfg(target = \"lua\")",
+                ));
+            }
+            return Ok(self.type_inference.type_hint.clone().unwrap_or(TypeDecl::Unit));
+        }
+
         // Integer math builtins: dispatch on the actual argument type
         // so the same `min(a, b)` symbol works for both `i64` and `u64`.
         // `abs(x)` accepts `i64` only; the result type matches the
@@ -673,6 +766,596 @@ impl<'a> AstVisitor for TypeCheckerVisitor<'a> {
             return Ok(arg_types[0].clone());
         }
 
+        // File, stdin, HTTP and socket I/O, timestamp formatting and
+        // logging: fixed `(str) -> bytes` / `(str, bytes) -> unit` /
+        // `() -> str` / `(str) -> (u64, str)` / `(u64, ..)` /
+        // `(i64, str) -> str` / `(str) -> unit` shapes, checked
+        // argument by argument so a `str` payload passed to
+        // `write_bytes` is caught here rather than at run time.
+        if matches!(
+            func,
+            BuiltinFunction::ReadBytes | BuiltinFunction::WriteBytes
+                | BuiltinFunction::ReadLine | BuiltinFunction::ReadAll
+                | BuiltinFunction::JsonParse | BuiltinFunction::HttpGet
+                | BuiltinFunction::TcpListen | BuiltinFunction::TcpAccept
+                | BuiltinFunction::TcpRead | BuiltinFunction::TcpWrite
+                | BuiltinFunction::TcpClose | BuiltinFunction::TcpLocalPort
+                | BuiltinFunction::FormatTimestamp
+                | BuiltinFunction::LogDebug | BuiltinFunction::LogInfo
+                | BuiltinFunction::LogWarn | BuiltinFunction::LogError
+        ) {
+            let (name, expected, ret): (&str, &[TypeDecl], TypeDecl) = match func {
+                BuiltinFunction::ReadBytes => ("read_bytes", &[TypeDecl::String], TypeDecl::Bytes),
+                BuiltinFunction::WriteBytes => ("write_bytes", &[TypeDecl::String, TypeDecl::Bytes], TypeDecl::Unit),
+                BuiltinFunction::ReadLine => ("read_line", &[], TypeDecl::String),
+                BuiltinFunction::JsonParse => ("json_parse", &[TypeDecl::String], TypeDecl::Unknown),
+                BuiltinFunction::HttpGet => (
+                    "http_get",
+                    &[TypeDecl::String],
+                    TypeDecl::Tuple(vec![TypeDecl::UInt64, TypeDecl::String]),
+                ),
+                BuiltinFunction::TcpListen => ("tcp_listen", &[TypeDecl::UInt64], TypeDecl::UInt64),
+                BuiltinFunction::TcpAccept => ("tcp_accept", &[TypeDecl::UInt64], TypeDecl::UInt64),
+                BuiltinFunction::TcpRead => ("tcp_read", &[TypeDecl::UInt64], TypeDecl::String),
+                BuiltinFunction::TcpWrite => ("tcp_write", &[TypeDecl::UInt64, TypeDecl::String], TypeDecl::Unit),
+                BuiltinFunction::TcpClose => ("tcp_close", &[TypeDecl::UInt64], TypeDecl::Unit),
+                BuiltinFunction::TcpLocalPort => ("tcp_local_port", &[TypeDecl::UInt64], TypeDecl::UInt64),
+                BuiltinFunction::FormatTimestamp => (
+                    "format_timestamp",
+                    &[TypeDecl::Int64, TypeDecl::String],
+                    TypeDecl::String,
+                ),
+                BuiltinFunction::LogDebug => ("log_debug", &[TypeDecl::String], TypeDecl::Unit),
+                BuiltinFunction::LogInfo => ("log_info", &[TypeDecl::String], TypeDecl::Unit),
+                BuiltinFunction::LogWarn => ("log_warn", &[TypeDecl::String], TypeDecl::Unit),
+                BuiltinFunction::LogError => ("log_error", &[TypeDecl::String], TypeDecl::Unit),
+                _ => ("read_all", &[], TypeDecl::String),
+            };
+            if args.len() != expected.len() {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {} argument(s), got {}",
+                    expected.len(),
+                    args.len()
+                )));
+            }
+            for (arg, expected_ty) in args.iter().zip(expected) {
+                let arg_ty = self.visit_expr(arg)?;
+                // An unsuffixed literal port / handle / timestamp
+                // settles as the expected integer type.
+                if arg_ty == TypeDecl::Number && matches!(expected_ty, TypeDecl::UInt64 | TypeDecl::Int64) {
+                    self.transform_numeric_expr(arg, expected_ty)?;
+                    continue;
+                }
+                if arg_ty != *expected_ty && arg_ty != TypeDecl::Unknown {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects {expected_ty:?} argument, got {arg_ty:?}"
+                    )));
+                }
+            }
+            return Ok(ret);
+        }
+
+        // Process spawning: `exec(cmd: str, args: [str; N])`. Any array
+        // length is accepted, including an empty `[]` literal, which
+        // has no element type of its own and so is not visited.
+        if matches!(func, BuiltinFunction::Exec) {
+            if args.len() != 2 {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "exec expects 2 argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let cmd_ty = self.visit_expr(&args[0])?;
+            if cmd_ty != TypeDecl::String && cmd_ty != TypeDecl::Unknown {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "exec expects a str command, got {cmd_ty:?}"
+                )));
+            }
+            let empty_literal = matches!(
+                self.core.expr_pool.get(&args[1]),
+                Some(Expr::ArrayLiteral(ref elems)) if elems.is_empty()
+            );
+            if !empty_literal {
+                let list_ty = self.visit_expr(&args[1])?;
+                let ok = match &list_ty {
+                    TypeDecl::Array(elems, _) => elems.iter().all(|t| *t == TypeDecl::String),
+                    TypeDecl::Unknown => true,
+                    _ => false,
+                };
+                if !ok {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "exec expects a [str] argument list, got {list_ty:?}"
+                    )));
+                }
+            }
+            return Ok(TypeDecl::Tuple(vec![TypeDecl::Int64, TypeDecl::String, TypeDecl::String]));
+        }
+
+        // `to_dict(s)` takes a struct; `from_dict<T>(d)` takes a dict
+        // (or a dynamic value) and names a non-generic struct `T`. The
+        // result is `Option<T>`, so the core library's `Option` has to
+        // be loaded.
+        if matches!(func, BuiltinFunction::ToDict | BuiltinFunction::FromDict(_)) {
+            let name = if matches!(func, BuiltinFunction::ToDict) { "to_dict" } else { "from_dict" };
+            if args.len() != 1 {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects 1 argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let arg_ty = self.visit_expr(&args[0])?;
+            let is_struct = |ty: &TypeDecl| match ty {
+                TypeDecl::Struct(sym, _) | TypeDecl::Identifier(sym) => {
+                    self.context.struct_definitions.contains_key(sym)
+                }
+                _ => false,
+            };
+            let BuiltinFunction::FromDict(target) = func else {
+                if !is_struct(&arg_ty) && arg_ty != TypeDecl::Unknown {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "to_dict expects a struct argument, got {arg_ty:?}"
+                    )));
+                }
+                return Ok(TypeDecl::Dict(Box::new(TypeDecl::String), Box::new(TypeDecl::Unknown)));
+            };
+            let target_sym = match target {
+                TypeDecl::Struct(sym, _) | TypeDecl::Identifier(sym) if is_struct(target) => *sym,
+                _ => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "from_dict target must be a struct type, got {target:?}"
+                    )));
+                }
+            };
+            if self.context.struct_generic_params.get(&target_sym).is_some_and(|p| !p.is_empty()) {
+                return Err(TypeCheckError::generic_error(
+                    "from_dict does not support generic structs",
+                ));
+            }
+            if !matches!(arg_ty, TypeDecl::Dict(..) | TypeDecl::Unknown) {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "from_dict expects a dict argument, got {arg_ty:?}"
+                )));
+            }
+            let option_sym = self
+                .core
+                .string_interner
+                .get("Option")
+                .filter(|sym| self.context.enum_definitions.contains_key(sym))
+                .ok_or_else(|| TypeCheckError::generic_error(
+                    "from_dict returns Option<T>, which requires the core library",
+                ))?;
+            return Ok(TypeDecl::Enum(option_sym, vec![TypeDecl::Struct(target_sym, vec![])]));
+        }
+
+        // `json_stringify(value)` and the type-inspection builtins take
+        // a single value of any type. Encodability and the `is<T>` /
+        // `downcast<T>` tests happen at run time.
+        if matches!(
+            func,
+            BuiltinFunction::JsonStringify | BuiltinFunction::TypeOf
+                | BuiltinFunction::Is(_) | BuiltinFunction::Downcast(_)
+        ) {
+            let (name, ret) = match func {
+                BuiltinFunction::JsonStringify => ("json_stringify", TypeDecl::String),
+                BuiltinFunction::TypeOf => ("type_of", TypeDecl::String),
+                BuiltinFunction::Is(_) => ("is", TypeDecl::Bool),
+                BuiltinFunction::Downcast(target) => ("downcast", target.clone()),
+                _ => unreachable!(),
+            };
+            if args.len() != 1 {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects 1 argument(s), got {}",
+                    args.len()
+                )));
+            }
+            // An untyped literal argument settles on the default
+            // integer type (u64) in the final numeric pass.
+            self.visit_expr(&args[0])?;
+            return Ok(ret);
+        }
+
+        // `clone(x)` has the type of `x`.
+        if matches!(func, BuiltinFunction::Clone) {
+            if args.len() != 1 {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "clone expects 1 argument(s), got {}",
+                    args.len()
+                )));
+            }
+            return self.visit_expr(&args[0]);
+        }
+
+        // Seedable PRNG and time builtins. Untyped literal operands
+        // are pinned here (`seed(42)` / `sleep(10)` -> u64,
+        // `random_range(1, n)` -> n's type) since builtin calls don't
+        // carry a parameter list for the usual call-site inference.
+        if matches!(
+            func,
+            BuiltinFunction::Seed | BuiltinFunction::Random | BuiltinFunction::RandomRange
+                | BuiltinFunction::NowMillis | BuiltinFunction::Clock | BuiltinFunction::Sleep
+        ) {
+            let (name, expected) = match func {
+                BuiltinFunction::Seed => ("seed", 1usize),
+                BuiltinFunction::Sleep => ("sleep", 1),
+                BuiltinFunction::Random => ("random", 0),
+                BuiltinFunction::NowMillis => ("now_millis", 0),
+                BuiltinFunction::Clock => ("clock", 0),
+                _ => ("random_range", 2),
+            };
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            match func {
+                BuiltinFunction::Random | BuiltinFunction::Clock => return Ok(TypeDecl::Float64),
+                BuiltinFunction::NowMillis => return Ok(TypeDecl::UInt64),
+                BuiltinFunction::Seed | BuiltinFunction::Sleep => {
+                    let ty = self.visit_expr(&args[0])?;
+                    match ty {
+                        TypeDecl::UInt64 => {}
+                        TypeDecl::Number => self.transform_numeric_expr(&args[0], &TypeDecl::UInt64)?,
+                        other => {
+                            return Err(TypeCheckError::generic_error(&format!(
+                                "{name} expects a u64 argument, got {other:?}"
+                            )));
+                        }
+                    }
+                    return Ok(TypeDecl::Unit);
+                }
+                _ => {
+                    let lo_ty = self.visit_expr(&args[0])?;
+                    let hi_ty = self.visit_expr(&args[1])?;
+                    let ty = match (&lo_ty, &hi_ty) {
+                        (TypeDecl::Number, TypeDecl::Number) => TypeDecl::UInt64,
+                        (TypeDecl::Number, other) | (other, TypeDecl::Number) => other.clone(),
+                        _ if lo_ty == hi_ty => lo_ty.clone(),
+                        _ => {
+                            return Err(TypeCheckError::generic_error(&format!(
+                                "random_range arguments must agree on type: got {lo_ty:?} and {hi_ty:?}"
+                            )));
+                        }
+                    };
+                    if !matches!(ty, TypeDecl::Int64 | TypeDecl::UInt64) {
+                        return Err(TypeCheckError::generic_error(&format!(
+                            "random_range expects integer arguments, got {ty:?}"
+                        )));
+                    }
+                    for (arg, arg_ty) in args.iter().zip([&lo_ty, &hi_ty]) {
+                        if *arg_ty == TypeDecl::Number {
+                            self.transform_numeric_expr(arg, &ty)?;
+                        }
+                    }
+                    return Ok(ty);
+                }
+            }
+        }
+
+        // Cooperative tasks. `spawn` only takes the `Task` a
+        // `fn f(..) -> Task` call hands back; `yield_now` reports
+        // whether any other task ran.
+        if matches!(func, BuiltinFunction::Spawn | BuiltinFunction::YieldNow) {
+            let (name, expected) = match func {
+                BuiltinFunction::Spawn => ("spawn", 1usize),
+                _ => ("yield_now", 0),
+            };
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let Some(task) = args.first() else {
+                return Ok(TypeDecl::Bool);
+            };
+            let ty = self.visit_expr(task)?;
+            if ty != TypeDecl::Generator(Box::new(TypeDecl::Unit)) {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "spawn expects a Task (call a `fn ... -> Task`), got {ty:?}"
+                )));
+            }
+            return Ok(TypeDecl::Unit);
+        }
+
+        // Integer <-> text. `parse_*` hand back Option<T> (so they need
+        // the core library); untyped literal operands of `to_hex` /
+        // `format_radix` are pinned to u64.
+        if matches!(
+            func,
+            BuiltinFunction::ParseU64 | BuiltinFunction::ParseI64
+                | BuiltinFunction::ToHex | BuiltinFunction::FormatRadix
+        ) {
+            let (name, expected) = match func {
+                BuiltinFunction::ParseU64 => ("parse_u64", 1usize),
+                BuiltinFunction::ParseI64 => ("parse_i64", 1),
+                BuiltinFunction::ToHex => ("to_hex", 1),
+                _ => ("format_radix", 2),
+            };
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let arg_ty = self.visit_expr(&args[0])?;
+            if matches!(func, BuiltinFunction::ParseU64 | BuiltinFunction::ParseI64) {
+                if !matches!(arg_ty, TypeDecl::String | TypeDecl::Unknown) {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects a str argument, got {arg_ty:?}"
+                    )));
+                }
+                let option_sym = self
+                    .core
+                    .string_interner
+                    .get("Option")
+                    .filter(|sym| self.context.enum_definitions.contains_key(sym))
+                    .ok_or_else(|| TypeCheckError::generic_error(&format!(
+                        "{name} returns Option<T>, which requires the core library"
+                    )))?;
+                let inner = if matches!(func, BuiltinFunction::ParseU64) {
+                    TypeDecl::UInt64
+                } else {
+                    TypeDecl::Int64
+                };
+                return Ok(TypeDecl::Enum(option_sym, vec![inner]));
+            }
+            match arg_ty {
+                TypeDecl::UInt64 => {}
+                TypeDecl::Int64 if matches!(func, BuiltinFunction::FormatRadix) => {}
+                TypeDecl::Number => self.transform_numeric_expr(&args[0], &TypeDecl::UInt64)?,
+                other => {
+                    let want = if matches!(func, BuiltinFunction::ToHex) { "a u64" } else { "an i64 or u64" };
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects {want} argument, got {other:?}"
+                    )));
+                }
+            }
+            if let Some(base) = args.get(1) {
+                match self.visit_expr(base)? {
+                    TypeDecl::UInt64 => {}
+                    TypeDecl::Number => self.transform_numeric_expr(base, &TypeDecl::UInt64)?,
+                    other => {
+                        return Err(TypeCheckError::generic_error(&format!(
+                            "format_radix expects a u64 base, got {other:?}"
+                        )));
+                    }
+                }
+            }
+            return Ok(TypeDecl::String);
+        }
+
+        // Number formatting. The width / digit counts pin untyped
+        // literals to u64, as does `format`'s value operand.
+        if matches!(
+            func,
+            BuiltinFunction::Format | BuiltinFunction::FormatFixed | BuiltinFunction::FormatExp
+        ) {
+            let (name, expected) = match func {
+                BuiltinFunction::Format => ("format", 3usize),
+                BuiltinFunction::FormatFixed => ("format_fixed", 2),
+                _ => ("format_exp", 2),
+            };
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            match (func, self.visit_expr(&args[0])?) {
+                (BuiltinFunction::Format, TypeDecl::Int64 | TypeDecl::UInt64 | TypeDecl::Float64 | TypeDecl::String)
+                | (_, TypeDecl::Float64) => {}
+                (BuiltinFunction::Format, TypeDecl::Number) => {
+                    self.transform_numeric_expr(&args[0], &TypeDecl::UInt64)?
+                }
+                (BuiltinFunction::Format, other) => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "format expects an i64, u64, f64 or str value, got {other:?}"
+                    )));
+                }
+                (_, other) => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects an f64 value, got {other:?}"
+                    )));
+                }
+            }
+            match self.visit_expr(&args[1])? {
+                TypeDecl::UInt64 => {}
+                TypeDecl::Number => self.transform_numeric_expr(&args[1], &TypeDecl::UInt64)?,
+                other => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects a u64 count, got {other:?}"
+                    )));
+                }
+            }
+            if let Some(pad) = args.get(2) {
+                let pad_ty = self.visit_expr(pad)?;
+                if pad_ty != TypeDecl::UInt32 {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "format expects a char pad (e.g. ' ' or '0'), got {pad_ty:?}"
+                    )));
+                }
+            }
+            return Ok(TypeDecl::String);
+        }
+
+        // `sort(arr, less)`. The comparator is visited first so its
+        // parameter type can pin an untyped array literal (`[3, 1, 2]`).
+        if matches!(func, BuiltinFunction::Sort) {
+            if args.len() != 2 {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "sort expects 2 argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let (params, ret) = match self.visit_expr(&args[1])? {
+                TypeDecl::Function(params, ret) if params.len() == 2 => (params, ret),
+                other => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "sort expects a comparator `fn (T, T) -> bool`, got {other:?}"
+                    )));
+                }
+            };
+            if *ret != TypeDecl::Bool {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "sort comparator must return bool, got {ret:?}"
+                )));
+            }
+            let saved_hint = self.type_inference.type_hint.clone();
+            self.type_inference.type_hint = Some(TypeDecl::Array(vec![params[0].clone()], 0));
+            let arr_ty = self.visit_expr(&args[0]);
+            self.type_inference.type_hint = saved_hint;
+            let arr_ty = arr_ty?;
+            if let Some(Expr::ArrayLiteral(elements)) = self.core.expr_pool.get(&args[0]) {
+                for element in &elements {
+                    self.commit_expr_transformation(element);
+                }
+            }
+            let elem = match &arr_ty {
+                TypeDecl::Array(elems, _) => elems.first().cloned(),
+                other => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "sort expects an array, got {other:?}"
+                    )));
+                }
+            };
+            if let Some(elem) = elem
+                && !params.iter().all(|p| p.is_equivalent(&elem))
+            {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "sort comparator takes ({:?}, {:?}) but the array holds {elem:?}",
+                    params[0], params[1]
+                )));
+            }
+            return Ok(arr_ty);
+        }
+
+        // Array algorithms. The signature table writes the element type
+        // `T` as `Unknown`; it is taken from the array argument here, and
+        // an untyped needle literal is pinned to it.
+        if matches!(
+            func,
+            BuiltinFunction::BinarySearch | BuiltinFunction::LowerBound
+                | BuiltinFunction::Reverse | BuiltinFunction::MinOf
+                | BuiltinFunction::MaxOf | BuiltinFunction::SumOf
+        ) {
+            let name = match func {
+                BuiltinFunction::BinarySearch => "binary_search",
+                BuiltinFunction::LowerBound => "lower_bound",
+                BuiltinFunction::Reverse => "reverse",
+                BuiltinFunction::MinOf => "min_of",
+                BuiltinFunction::MaxOf => "max_of",
+                _ => "sum_of",
+            };
+            let expected = self
+                .builtin_function_signatures
+                .iter()
+                .find(|sig| sig.func == *func)
+                .map_or(1, |sig| sig.arg_count);
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            let arr_ty = self.visit_expr(&args[0])?;
+            let elem = match &arr_ty {
+                TypeDecl::Array(elems, _) => elems.first().cloned().unwrap_or(TypeDecl::Unknown),
+                other => {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects an array, got {other:?}"
+                    )));
+                }
+            };
+            let ordered = matches!(
+                elem,
+                TypeDecl::Int8 | TypeDecl::Int16 | TypeDecl::Int32 | TypeDecl::Int64
+                    | TypeDecl::UInt8 | TypeDecl::UInt16 | TypeDecl::UInt32 | TypeDecl::UInt64
+                    | TypeDecl::Float64 | TypeDecl::Number | TypeDecl::Unknown
+            );
+            if !matches!(func, BuiltinFunction::Reverse) && !ordered {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects an array of integers or f64, got {arr_ty:?}"
+                )));
+            }
+            if let Some(needle) = args.get(1) {
+                let saved_hint = self.type_inference.type_hint.clone();
+                self.type_inference.type_hint = Some(elem.clone());
+                let needle_ty = self.visit_expr(needle);
+                self.type_inference.type_hint = saved_hint;
+                match needle_ty? {
+                    TypeDecl::Number if matches!(elem, TypeDecl::Int64 | TypeDecl::UInt64) => {
+                        self.transform_numeric_expr(needle, &elem)?;
+                        self.commit_expr_transformation(needle);
+                    }
+                    ty if ty == elem || elem == TypeDecl::Unknown => {}
+                    other => {
+                        return Err(TypeCheckError::generic_error(&format!(
+                            "{name} looks for a {elem:?} but got {other:?}"
+                        )));
+                    }
+                }
+            }
+            return match func {
+                BuiltinFunction::BinarySearch => {
+                    let option_sym = self
+                        .core
+                        .string_interner
+                        .get("Option")
+                        .filter(|sym| self.context.enum_definitions.contains_key(sym))
+                        .ok_or_else(|| TypeCheckError::generic_error(
+                            "binary_search returns Option<u64>, which requires the core library",
+                        ))?;
+                    Ok(TypeDecl::Enum(option_sym, vec![TypeDecl::UInt64]))
+                }
+                BuiltinFunction::LowerBound => Ok(TypeDecl::UInt64),
+                BuiltinFunction::Reverse => Ok(arr_ty),
+                _ => Ok(elem),
+            };
+        }
+
+        // Lua-style patterns: every operand is a `str`.
+        if matches!(
+            func,
+            BuiltinFunction::Matches | BuiltinFunction::Find | BuiltinFunction::ReplacePattern
+        ) {
+            let name = match func {
+                BuiltinFunction::Matches => "matches",
+                BuiltinFunction::Find => "find",
+                _ => "replace_pattern",
+            };
+            let expected = if matches!(func, BuiltinFunction::ReplacePattern) { 3 } else { 2 };
+            if args.len() != expected {
+                return Err(TypeCheckError::generic_error(&format!(
+                    "{name} expects {expected} argument(s), got {}",
+                    args.len()
+                )));
+            }
+            for arg in args {
+                let arg_ty = self.visit_expr(arg)?;
+                if !matches!(arg_ty, TypeDecl::String | TypeDecl::Unknown) {
+                    return Err(TypeCheckError::generic_error(&format!(
+                        "{name} expects str arguments, got {arg_ty:?}"
+                    )));
+                }
+            }
+            return match func {
+                BuiltinFunction::Matches => Ok(TypeDecl::Bool),
+                BuiltinFunction::Find => {
+                    let option_sym = self
+                        .core
+                        .string_interner
+                        .get("Option")
+                        .filter(|sym| self.context.enum_definitions.contains_key(sym))
+                        .ok_or_else(|| TypeCheckError::generic_error(
+                            "find returns Option<str>, which requires the core library",
+                        ))?;
+                    Ok(TypeDecl::Enum(option_sym, vec![TypeDecl::String]))
+                }
+                _ => Ok(TypeDecl::String),
+            };
+        }
+
         // Find matching function signature from pre-built table
         let signature = self.builtin_function_signatures.iter().find(|sig| sig.func == *func).cloned();
 
@@ -20,6 +20,10 @@ pub enum TypeDecl {
     UInt32,
     Identifier(DefaultSymbol),
     String,
+    /// Immutable byte buffer (`bytes`). Written as a contextual
+    /// identifier in type position, produced by `b"..."` literals,
+    /// slicing and `read_bytes`. Indexing yields `u64`.
+    Bytes,
     Number,  // Type-unspecified numeric literal for type inference
     Array(Vec<TypeDecl>, usize),  // element types and fixed size
     Struct(DefaultSymbol, Vec<TypeDecl>),  // struct type with type parameters
@@ -46,6 +50,11 @@ pub enum TypeDecl {
     /// landing — interpreter / JIT / AOT execution paths come in
     /// follow-up phases.
     Function(Vec<TypeDecl>, Box<TypeDecl>),
+    /// `Generator<T>`: the lazy sequence returned by a function whose
+    /// body contains `yield` statements. `next()` resumes the body
+    /// and returns `Option<T>`, so it drives `for x in gen { ... }`.
+    /// Interpreter only.
+    Generator(Box<TypeDecl>),
 }
 
 impl TypeDecl {
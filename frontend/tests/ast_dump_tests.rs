@@ -0,0 +1,67 @@
+//! AST dump serializer tests (`frontend::ast::dump`).
+//!
+//! Covers the three renderers behind `--emit-ast`, `--emit-ast=json`,
+//! and `--emit-ast-dot`.
+
+use frontend::ast::dump::{to_dot, to_json, to_pretty, AstDumper, DumpNode};
+use frontend::Parser;
+use string_interner::DefaultStringInterner;
+
+fn dump(source: &str) -> DumpNode {
+    let mut interner = DefaultStringInterner::new();
+    let program = Parser::new(source, &mut interner)
+        .parse_program()
+        .expect("parse failed");
+    AstDumper::new(&program, &interner).build()
+}
+
+const SOURCE: &str = r#"
+struct Point { x: i64, y: i64 }
+
+fn main() -> i64 {
+    val p = Point { x: 1i64, y: 2i64 }
+    if p.x < p.y { p.x + p.y } else { 0i64 }
+}
+"#;
+
+#[test]
+fn test_pretty_dump_shows_structure() {
+    let text = to_pretty(&dump(SOURCE));
+    assert!(text.starts_with("Program\n"));
+    assert!(text.contains("  StructDecl Point\n"));
+    assert!(text.contains("      Field x: i64\n"));
+    assert!(text.contains("  Function main() -> i64\n"));
+    assert!(text.contains("      Val p\n"));
+    assert!(text.contains("Binary IAdd\n"));
+    assert!(text.contains("FieldAccess y\n"));
+}
+
+#[test]
+fn test_json_dump_is_balanced() {
+    let json = to_json(&dump(SOURCE));
+    assert!(json.starts_with("{\"kind\": \"Program\""));
+    assert!(json.contains("{\"kind\": \"Int64\", \"detail\": \"2\", \"children\": []}"));
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+    assert_eq!(json.matches('[').count(), json.matches(']').count());
+}
+
+#[test]
+fn test_json_dump_escapes_strings() {
+    let json = to_json(&dump("fn main() -> u64 {\n    val s = \"a\\tb\"\n    0u64\n}\n"));
+    // Detail is the Rust-debug-quoted literal `"a\tb"`, escaped again for JSON.
+    assert!(json.contains(r#""detail": "\"a\\tb\"""#), "{json}");
+}
+
+#[test]
+fn test_dot_dump_has_one_edge_per_child() {
+    let tree = dump(SOURCE);
+    let dot = to_dot(&tree);
+    fn count(node: &DumpNode) -> usize {
+        1 + node.children.iter().map(count).sum::<usize>()
+    }
+    let nodes = count(&tree);
+    assert!(dot.starts_with("digraph ast {"));
+    assert!(dot.trim_end().ends_with('}'));
+    assert_eq!(dot.matches(" [label=").count(), nodes);
+    assert_eq!(dot.matches(" -> n").count(), nodes - 1);
+}
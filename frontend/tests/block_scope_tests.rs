@@ -0,0 +1,97 @@
+// Block scoping in the type checker: a `val` / `var` declared inside
+// a block is not visible after the block, for every construct that
+// has one (bare blocks, if / elif / else, while, loop, for, match
+// arms), and a for-loop variable does not outlive its loop.
+
+use frontend::ParserWithInterner;
+use frontend::type_checker::TypeCheckerVisitor;
+
+fn parse_and_type_check(source: &str) -> Result<(), String> {
+    let mut parser = ParserWithInterner::new(source);
+    let mut program = parser.parse_program().map_err(|e| format!("parse error: {:?}", e))?;
+    let functions = program.function.clone();
+    let string_interner = parser.get_string_interner();
+    let mut tc = TypeCheckerVisitor::with_program(&mut program, string_interner);
+    let mut errors = Vec::new();
+    for f in functions.iter() {
+        if let Err(e) = tc.type_check(f.clone()) {
+            errors.push(format!("{:?}", e));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// `statement` declares `x` in an inner block; reading `x` afterwards
+/// must fail to type-check.
+fn assert_x_out_of_scope(statement: &str) {
+    let src = format!("fn main() -> u64 {{\n    {statement}\n    x\n}}\n");
+    let err = parse_and_type_check(&src).expect_err(&format!("`x` leaked out of: {statement}"));
+    assert!(err.contains("NotFound"), "unexpected error for {statement}: {err}");
+}
+
+#[test]
+fn bare_block_drops_its_bindings() {
+    assert_x_out_of_scope("{ val x = 1u64 }");
+    assert_x_out_of_scope("{ var x = 1u64 }");
+}
+
+#[test]
+fn if_branches_drop_their_bindings() {
+    assert_x_out_of_scope("if true { val x = 1u64 }");
+    assert_x_out_of_scope("if false { 0u64 } else { val x = 1u64 }");
+    assert_x_out_of_scope("if false { 0u64 } elif true { var x = 1u64 } else { 0u64 }");
+}
+
+#[test]
+fn loop_bodies_drop_their_bindings() {
+    assert_x_out_of_scope("while false { val x = 1u64 }");
+    assert_x_out_of_scope("loop { val x = 1u64\n break }");
+    assert_x_out_of_scope("for i in 0u64..3u64 { var x = i }");
+}
+
+#[test]
+fn for_loop_variable_is_loop_local() {
+    assert_x_out_of_scope("for x in 0u64..3u64 { val y = x }");
+}
+
+#[test]
+fn match_arms_drop_their_bindings() {
+    assert_x_out_of_scope("match 1u64 { 1u64 => { val x = 2u64 } _ => { } }");
+}
+
+#[test]
+fn block_value_and_outer_bindings_still_work() {
+    parse_and_type_check(
+        "fn main() -> u64 {
+            var total = 0u64
+            val x = {
+                val inner = 3u64
+                inner
+            }
+            if x > 1u64 {
+                val step = 2u64
+                total = total + step
+            }
+            total + x
+        }",
+    )
+    .expect("outer bindings stay visible and block values flow out");
+}
+
+#[test]
+fn inner_block_may_reuse_an_outer_name() {
+    parse_and_type_check(
+        "fn main() -> u64 {
+            val x = 1u64
+            if true {
+                val x = true
+            }
+            x
+        }",
+    )
+    .expect("a shadowing binding ends with its block");
+}
@@ -0,0 +1,93 @@
+//! Conditional Compilation Tests
+//!
+//! `@cfg(...)` parsing into `Program::cfg_items` and pruning through
+//! `frontend::prune_cfg`.
+
+use frontend::ast::{CfgPredicate, CfgTarget, Stmt};
+use frontend::{prune_cfg, CfgEnv, ParserWithInterner};
+
+const SOURCE: &str = r#"
+@cfg(target = "lua")
+pub fn now() -> u64 {
+    1u64
+}
+
+@cfg(not(target = "lua"))
+pub fn now() -> u64 {
+    2u64
+}
+
+@cfg(fast)
+const LIMIT: u64 = 10u64
+
+@cfg(target = "lua")
+@cfg(debug)
+struct LuaOnly {
+    x: u64
+}
+
+fn main() -> u64 {
+    now()
+}
+"#;
+
+#[test]
+fn parser_keeps_guarded_items_and_records_them() {
+    let mut parser = ParserWithInterner::new(SOURCE);
+    let program = parser.parse_program().expect("parse");
+    assert_eq!(program.function.len(), 3);
+    assert_eq!(program.consts.len(), 1);
+
+    let targets: Vec<CfgTarget> = program.cfg_items.iter().map(|i| i.target).collect();
+    assert_eq!(targets[..3], [CfgTarget::Function(0), CfgTarget::Function(1), CfgTarget::Const(0)]);
+    let CfgTarget::Decl(stmt_ref) = targets[3] else {
+        panic!("struct should be recorded as a declaration, got {:?}", targets[3]);
+    };
+    assert!(matches!(program.statement.get(&stmt_ref), Some(Stmt::StructDecl { .. })));
+
+    // Stacked attributes are AND-composed.
+    assert_eq!(
+        program.cfg_items[3].predicate,
+        CfgPredicate::All(vec![
+            CfgPredicate::Equals("target".into(), "lua".into()),
+            CfgPredicate::Defined("debug".into()),
+        ])
+    );
+}
+
+#[test]
+fn prune_drops_items_whose_predicate_fails() {
+    let mut parser = ParserWithInterner::new(SOURCE);
+    let mut program = parser.parse_program().expect("parse");
+    let struct_ref = match program.cfg_items[3].target {
+        CfgTarget::Decl(r) => r,
+        other => panic!("unexpected target {other:?}"),
+    };
+
+    let removed = prune_cfg(&mut program, &CfgEnv::for_target("interpreter"));
+    assert_eq!(removed, 3);
+    assert!(program.cfg_items.is_empty());
+    assert_eq!(program.function.len(), 2);
+    assert_eq!(program.function_module_paths.len(), 2);
+    assert!(program.consts.is_empty());
+    assert!(matches!(program.statement.get(&struct_ref), Some(Stmt::Break(None, None))));
+}
+
+#[test]
+fn define_arg_accepts_flags_and_values() {
+    let mut env = CfgEnv::new();
+    env.define_arg("fast").unwrap();
+    env.define_arg("mode=debug").unwrap();
+    assert_eq!(env.get("fast"), Some(""));
+    assert!(env.matches(&CfgPredicate::Defined("fast".into())));
+    assert!(env.matches(&CfgPredicate::Equals("mode".into(), "debug".into())));
+    assert!(!env.matches(&CfgPredicate::Equals("fast".into(), "1".into())));
+    assert!(env.define_arg("=x").is_err());
+    assert!(env.define_arg("1abc=x").is_err());
+}
+
+#[test]
+fn dangling_cfg_is_a_parse_error() {
+    let mut parser = ParserWithInterner::new("fn main() -> u64 { 1u64 }\n@cfg(x)\n");
+    assert!(parser.parse_program().is_err());
+}
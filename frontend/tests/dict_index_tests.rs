@@ -218,7 +218,7 @@ fn test_index_with_expression() {
 
 #[test]
 fn test_consistent_dict_operations() {
-    let source = "fn main() -> u64 {\nval numbers = dict{\"one\": 1, \"two\": 2}\nnumbers[\"three\"] = 3\n1u64\n}";
+    let source = "fn main() -> u64 {\nvar numbers = dict{\"one\": 1, \"two\": 2}\nnumbers[\"three\"] = 3\n1u64\n}";
     let result = parse_and_check(source);
     
     // Should succeed - all operations maintain type consistency
@@ -230,7 +230,7 @@ fn test_consistent_dict_operations() {
 
 #[test]
 fn test_inconsistent_dict_assignment_should_fail() {
-    let source = "fn main() -> u64 {\nval numbers = dict{\"one\": 1, \"two\": 2}\nnumbers[\"three\"] = \"three\"\n1u64\n}";
+    let source = "fn main() -> u64 {\nvar numbers = dict{\"one\": 1, \"two\": 2}\nnumbers[\"three\"] = \"three\"\n1u64\n}";
     let result = parse_and_check(source);
     
     // Should fail - trying to assign string to number dict
@@ -244,7 +244,7 @@ fn test_inconsistent_dict_assignment_should_fail() {
 
 #[test]
 fn test_array_index_operations() {
-    let source = "fn main() -> u64 {\nval arr = [1, 2, 3]\narr[0] = 42\n1u64\n}";
+    let source = "fn main() -> u64 {\nvar arr = [1, 2, 3]\narr[0] = 42\n1u64\n}";
     let result = parse_and_check(source);
     
     // Should succeed - consistent array operations
@@ -257,7 +257,7 @@ fn test_array_index_operations() {
 #[test]
 fn test_array_type_mismatch_assignment() {
     let source = r#"
-val arr = [1, 2, 3]
+var arr = [1, 2, 3]
 arr[0] = "text"
 "#;
     let result = parse_and_check(source);
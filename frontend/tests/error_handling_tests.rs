@@ -664,4 +664,118 @@ fn test_func(param: u64) {
             println!("Error collection mechanism test completed: {} errors found", result.errors.len());
         }
     }
+
+    // ========================================================================
+    // Diagnostic Code Tests
+    // ========================================================================
+    mod error_codes {
+        use frontend::error_code::{lookup, ERROR_CODES};
+        use frontend::type_checker::TypeCheckError;
+        use frontend::type_decl::TypeDecl;
+        use frontend::ParserError;
+        use frontend::type_checker::SourceLocation;
+
+        #[test]
+        fn test_error_codes_are_unique() {
+            let mut codes: Vec<&str> = ERROR_CODES.iter().map(|info| info.code).collect();
+            codes.sort();
+            codes.dedup();
+            assert_eq!(codes.len(), ERROR_CODES.len());
+        }
+
+        #[test]
+        fn test_every_emitted_code_has_explanation() {
+            let loc = SourceLocation { line: 1, column: 1, offset: 0 };
+            let emitted = [
+                ParserError::unexpected_token(loc, "x".to_string()).code(),
+                ParserError::recursion_limit_exceeded(loc).code(),
+                ParserError::generic_error(loc, "x".to_string()).code(),
+                ParserError::io_error(loc, "x".to_string()).code(),
+                TypeCheckError::type_mismatch(TypeDecl::Int64, TypeDecl::Bool).code(),
+                TypeCheckError::type_mismatch_operation("+", TypeDecl::Int64, TypeDecl::Bool).code(),
+                TypeCheckError::not_found("Variable", "x").code(),
+                TypeCheckError::unsupported_operation("-", TypeDecl::Bool).code(),
+                TypeCheckError::conversion_error("x", "u8").code(),
+                TypeCheckError::array_error("x").code(),
+                TypeCheckError::method_error("m", TypeDecl::Int64, "x").code(),
+                TypeCheckError::invalid_literal("x", "u8").code(),
+                TypeCheckError::access_denied("x").code(),
+                TypeCheckError::generic_error("x").code(),
+            ];
+            for code in emitted {
+                let info = lookup(code).unwrap_or_else(|| panic!("{code} has no registry entry"));
+                assert!(!info.explanation.is_empty());
+            }
+        }
+
+        /// The indented program under "Erroneous code example:".
+        fn erroneous_example(code: &str) -> String {
+            let explanation = lookup(code).unwrap().explanation;
+            let start = explanation.find("Erroneous code example:\n\n").unwrap() + "Erroneous code example:\n\n".len();
+            explanation[start..]
+                .lines()
+                .take_while(|line| line.is_empty() || line.starts_with("    "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        /// Codes the example reports: the parse error's, or else the
+        /// type checker's. A listing split into `# <file>` sections
+        /// is joined the way module integration would: the other files'
+        /// functions go after `main.t`'s, registered under their package.
+        fn example_codes(source: &str) -> Vec<&'static str> {
+            let mut main_source = String::new();
+            let mut modules: Vec<(String, String)> = Vec::new();
+            let mut file = "main.t".to_string();
+            for line in source.lines() {
+                let line = line.strip_prefix("    ").unwrap_or(line);
+                if let Some(name) = line.strip_prefix("# ") {
+                    file = name.to_string();
+                } else if let Some(package) = line.strip_prefix("package ") {
+                    modules.push((package.to_string(), String::new()));
+                } else if file == "main.t" {
+                    main_source.push_str(line);
+                    main_source.push('\n');
+                } else if let Some((_, body)) = modules.last_mut() {
+                    body.push_str(line);
+                    body.push('\n');
+                }
+            }
+            let joined = modules.iter().fold(main_source, |acc, (_, body)| acc + body);
+
+            let mut parser = frontend::ParserWithInterner::new(&joined);
+            let mut program = match parser.parse_program() {
+                Ok(program) => program,
+                Err(err) => return vec![err.code()],
+            };
+            for (package, body) in &modules {
+                let module_functions = frontend::ParserWithInterner::new(body).parse_program().expect("module parses").function.len();
+                let package = parser.get_string_interner().get_or_intern(package);
+                let total = program.function.len();
+                for path in &mut program.function_module_paths[total - module_functions..] {
+                    *path = Some(vec![package]);
+                }
+            }
+            let mut expr_pool = program.expression.clone();
+            let mut tc = frontend::type_checker::TypeCheckerVisitor::new(&program.statement, &mut expr_pool, parser.get_string_interner(), &program.location_pool);
+            for import in &program.imports {
+                tc.register_import(import.module_path.clone());
+            }
+            tc.check_program_multiple_errors(&program).errors.iter().map(|e| e.code()).collect()
+        }
+
+        #[test]
+        fn test_every_example_reports_its_code() {
+            for info in ERROR_CODES.iter().filter(|info| info.explanation.contains("Erroneous code example:")) {
+                let source = erroneous_example(info.code);
+                assert_eq!(example_codes(&source), vec![info.code], "example was:\n{source}");
+            }
+        }
+
+        #[test]
+        fn test_lookup_is_case_insensitive() {
+            assert_eq!(lookup("e0001").map(|i| i.code), Some("E0001"));
+            assert!(lookup("E9999").is_none());
+        }
+    }
 }
\ No newline at end of file
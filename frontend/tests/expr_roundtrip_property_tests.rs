@@ -0,0 +1,144 @@
+//! Print / re-parse round-trip for expressions.
+//!
+//! There is no source formatter yet, so the printer lives here: it
+//! renders a randomly generated expression tree twice — once fully
+//! parenthesized, once with only the parentheses the documented
+//! precedence table (`docs/language.md`, "Operators") requires — and
+//! both renderings must parse to the same AST. A mismatch means the
+//! parser's precedence / associativity disagrees with the table, or
+//! the minimal printer dropped a parenthesis it needed.
+//!
+//! ASTs are compared through `frontend::ast::dump::to_pretty`, which
+//! carries no source locations.
+
+use frontend::ast::dump::{to_pretty, AstDumper};
+use frontend::Parser;
+use proptest::prelude::*;
+use string_interner::DefaultStringInterner;
+
+#[derive(Debug, Clone)]
+enum GenExpr {
+    Ident(&'static str),
+    Int(u64),
+    Bool(bool),
+    Unary(&'static str, Box<GenExpr>),
+    Binary(&'static str, Box<GenExpr>, Box<GenExpr>),
+    Cast(Box<GenExpr>),
+    Index(Box<GenExpr>, Box<GenExpr>),
+}
+
+/// Binding power per the documented table, higher binds tighter.
+fn precedence(op: &str) -> u8 {
+    match op {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" => 3,
+        "<" | "<=" | ">" | ">=" => 4,
+        "|" => 5,
+        "^" => 6,
+        "&" => 7,
+        "<<" | ">>" => 8,
+        "+" | "-" => 9,
+        "*" | "/" | "%" => 10,
+        _ => unreachable!("unknown operator {op}"),
+    }
+}
+
+const UNARY_PREC: u8 = 11;
+const POSTFIX_PREC: u8 = 12;
+
+fn expr_precedence(e: &GenExpr) -> u8 {
+    match e {
+        GenExpr::Binary(op, _, _) => precedence(op),
+        GenExpr::Unary(_, _) => UNARY_PREC,
+        _ => POSTFIX_PREC,
+    }
+}
+
+fn print(e: &GenExpr, minimal: bool) -> String {
+    let wrap = |child: &GenExpr, needs: bool| {
+        let text = print(child, minimal);
+        if needs || !minimal { format!("({text})") } else { text }
+    };
+    match e {
+        GenExpr::Ident(name) => name.to_string(),
+        GenExpr::Int(n) => format!("{n}u64"),
+        GenExpr::Bool(b) => b.to_string(),
+        // An operand that starts with a digit is always parenthesized so
+        // the lexer cannot fold a `-` into the literal (`-1u64`).
+        GenExpr::Unary(op, operand) => {
+            let starts_with_digit = print(operand, true).starts_with(|c: char| c.is_ascii_digit());
+            let needs = expr_precedence(operand) < UNARY_PREC || starts_with_digit;
+            format!("{op}{}", wrap(operand, needs))
+        }
+        GenExpr::Binary(op, lhs, rhs) => {
+            let p = precedence(op);
+            // Left-associative, except that relational operators chain
+            // (`a < b < c` desugars to a conjunction), so a relational
+            // lhs of a relational operator keeps its parentheses.
+            let lhs_needs = expr_precedence(lhs) < p || (p == 4 && expr_precedence(lhs) == 4);
+            let rhs_needs = expr_precedence(rhs) <= p;
+            format!("{} {op} {}", wrap(lhs, lhs_needs), wrap(rhs, rhs_needs))
+        }
+        GenExpr::Cast(inner) => format!("{} as u64", wrap(inner, expr_precedence(inner) < POSTFIX_PREC)),
+        GenExpr::Index(base, index) => {
+            format!("{}[{}]", wrap(base, expr_precedence(base) < POSTFIX_PREC), print(index, minimal))
+        }
+    }
+}
+
+fn gen_expr() -> impl Strategy<Value = GenExpr> {
+    let leaf = prop_oneof![
+        prop::sample::select(vec!["a", "b", "c", "xs"]).prop_map(GenExpr::Ident),
+        (0u64..100).prop_map(GenExpr::Int),
+        any::<bool>().prop_map(GenExpr::Bool),
+    ];
+    leaf.prop_recursive(4, 24, 2, |inner| {
+        prop_oneof![
+            (prop::sample::select(vec!["-", "!", "~"]), inner.clone())
+                .prop_map(|(op, e)| GenExpr::Unary(op, Box::new(e))),
+            (
+                prop::sample::select(vec![
+                    "||", "&&", "==", "!=", "<", "<=", ">", ">=", "|", "^", "&", "<<", ">>", "+",
+                    "-", "*", "/", "%",
+                ]),
+                inner.clone(),
+                inner.clone(),
+            )
+                .prop_map(|(op, l, r)| GenExpr::Binary(op, Box::new(l), Box::new(r))),
+            inner.clone().prop_map(|e| GenExpr::Cast(Box::new(e))),
+            (inner.clone(), inner).prop_map(|(b, i)| GenExpr::Index(Box::new(b), Box::new(i))),
+        ]
+    })
+}
+
+fn parse_dump(expr_source: &str) -> Result<String, String> {
+    let source = format!("fn main() -> u64 {{\n    {expr_source}\n}}\n");
+    let mut interner = DefaultStringInterner::new();
+    let program = Parser::new(&source, &mut interner)
+        .parse_program()
+        .map_err(|e| format!("{e:?}"))?;
+    Ok(to_pretty(&AstDumper::new(&program, &interner).build()))
+}
+
+proptest! {
+    #[test]
+    fn minimal_and_full_parenthesization_parse_identically(e in gen_expr()) {
+        let full = print(&e, false);
+        let minimal = print(&e, true);
+        let full_ast = parse_dump(&full).map_err(|err| TestCaseError::fail(format!("{full}: {err}")))?;
+        let minimal_ast = parse_dump(&minimal).map_err(|err| TestCaseError::fail(format!("{minimal}: {err}")))?;
+        prop_assert_eq!(full_ast, minimal_ast, "full: {}\nminimal: {}", full, minimal);
+    }
+}
+
+#[test]
+fn logical_and_binds_tighter_than_or() {
+    assert_eq!(parse_dump("a || b && c").unwrap(), parse_dump("a || (b && c)").unwrap());
+}
+
+#[test]
+fn bitwise_binds_tighter_than_comparison() {
+    assert_eq!(parse_dump("a & b == c").unwrap(), parse_dump("(a & b) == c").unwrap());
+    assert_eq!(parse_dump("a | b < c").unwrap(), parse_dump("(a | b) < c").unwrap());
+}
@@ -0,0 +1,95 @@
+//! Inline Attribute Tests
+//!
+//! `@inline` / `@noinline` on top-level functions and impl methods.
+
+use frontend::ast::{InlineHint, Stmt, StmtRef};
+use frontend::ParserWithInterner;
+
+const SOURCE: &str = r#"
+struct Point {
+    x: u64
+}
+
+impl Point {
+    @inline
+    fn get_x(&self) -> u64 {
+        self.x
+    }
+
+    @noinline
+    pub fn twice(&self) -> u64 {
+        self.x * 2u64
+    }
+
+    fn plain(&self) -> u64 {
+        self.x
+    }
+}
+
+@inline
+fn sq(n: u64) -> u64 {
+    n * n
+}
+
+@noinline
+pub fn cube(n: u64) -> u64 {
+    n * n * n
+}
+
+@cfg(fast)
+@inline
+fn fast_path() -> u64 {
+    1u64
+}
+
+fn main() -> u64 {
+    sq(3u64)
+}
+"#;
+
+#[test]
+fn functions_record_their_hint() {
+    let mut parser = ParserWithInterner::new(SOURCE);
+    let program = parser.parse_program().expect("parse");
+    let hints: Vec<Option<InlineHint>> = program.function.iter().map(|f| f.inline).collect();
+    assert_eq!(
+        hints,
+        [Some(InlineHint::Always), Some(InlineHint::Never), Some(InlineHint::Always), None]
+    );
+    // `@inline` stacks with `@cfg`.
+    assert_eq!(program.cfg_items.len(), 1);
+}
+
+#[test]
+fn methods_record_their_hint() {
+    let mut parser = ParserWithInterner::new(SOURCE);
+    let program = parser.parse_program().expect("parse");
+    let methods = (0..program.statement.len())
+        .find_map(|i| match program.statement.get(&StmtRef(i as u32)) {
+            Some(Stmt::ImplBlock { methods, .. }) => Some(methods),
+            _ => None,
+        })
+        .expect("impl block");
+    let hints: Vec<Option<InlineHint>> = methods.iter().map(|m| m.inline).collect();
+    assert_eq!(hints, [Some(InlineHint::Always), Some(InlineHint::Never), None]);
+}
+
+#[test]
+fn inline_on_a_non_function_is_a_parse_error() {
+    let mut parser = ParserWithInterner::new("@inline\nstruct S {\n    x: u64\n}\nfn main() -> u64 { 1u64 }\n");
+    assert!(parser.parse_program().is_err());
+}
+
+#[test]
+fn conflicting_hints_are_a_parse_error() {
+    let mut parser = ParserWithInterner::new("@inline\n@noinline\nfn f() -> u64 { 1u64 }\nfn main() -> u64 { f() }\n");
+    // Reported through the collected errors; parsing carries on.
+    parser.parse_program().expect("parse");
+    assert_eq!(parser.errors.len(), 1, "{:?}", parser.errors);
+}
+
+#[test]
+fn dangling_inline_is_a_parse_error() {
+    let mut parser = ParserWithInterner::new("fn main() -> u64 { 1u64 }\n@inline\n");
+    assert!(parser.parse_program().is_err());
+}
@@ -0,0 +1,58 @@
+//! Inline Lua Tests
+//!
+//! `lua!("raw code")` parses to `BuiltinFunction::InlineLua`; the type
+//! checker only accepts it when checking for the Lua target.
+
+use frontend::ast::{BuiltinFunction, Expr, ExprRef};
+use frontend::type_checker::TypeCheckerVisitor;
+use frontend::ParserWithInterner;
+
+fn check(source: &str, inline_lua_allowed: bool) -> Result<(), String> {
+    let mut parser = ParserWithInterner::new(source);
+    let mut program = parser.parse_program().map_err(|e| format!("Parse error: {e:?}"))?;
+    let functions = program.function.clone();
+    let string_interner = parser.get_string_interner();
+    let mut type_checker = TypeCheckerVisitor::with_program(&mut program, string_interner);
+    type_checker.inline_lua_allowed = inline_lua_allowed;
+    for func in functions {
+        type_checker.type_check(func).map_err(|e| format!("{e:?}"))?;
+    }
+    Ok(())
+}
+
+const SOURCE: &str = r#"
+fn now() -> u64 {
+    val t: u64 = lua!("os.time()")
+    t
+}
+"#;
+
+#[test]
+fn lua_bang_parses_to_inline_lua_builtin() {
+    let mut parser = ParserWithInterner::new(SOURCE);
+    let program = parser.parse_program().expect("parse");
+    let found = (0..program.expression.len()).any(|i| {
+        matches!(
+            program.expression.get(&ExprRef(i as u32)),
+            Some(Expr::BuiltinCall(BuiltinFunction::InlineLua, _))
+        )
+    });
+    assert!(found);
+}
+
+#[test]
+fn inline_lua_is_rejected_outside_the_lua_target() {
+    let err = check(SOURCE, false).unwrap_err();
+    assert!(err.contains("backend-only construct"), "{err}");
+}
+
+#[test]
+fn inline_lua_takes_the_annotated_type_for_the_lua_target() {
+    check(SOURCE, true).expect("lua target accepts lua!(...)");
+}
+
+#[test]
+fn inline_lua_needs_a_plain_string_literal() {
+    let err = check("fn f() -> u64 {\n    val t: u64 = lua!(1u64)\n    t\n}\n", true).unwrap_err();
+    assert!(err.contains("single plain string literal"), "{err}");
+}
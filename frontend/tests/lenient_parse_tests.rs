@@ -0,0 +1,128 @@
+//! Lenient Parse Tests
+//!
+//! `Parser::parse_program_lenient` and the completion context it
+//! answers with `LenientProgram::context_at`.
+
+use frontend::parser::{ExprContext, LenientProgram};
+use frontend::type_decl::TypeDecl;
+use frontend::Parser;
+use string_interner::DefaultStringInterner;
+
+const SOURCE: &str = r#"
+struct Line {
+    start: Point,
+    end: Point
+}
+
+struct Point {
+    x: u64,
+    y: u64
+}
+
+impl Point {
+    fn origin() -> Self {
+        Point { x: 0u64, y: 0u64 }
+    }
+
+    fn norm(&self) -> u64 {
+        self.
+    }
+}
+
+fn scale(p: Point, k: u64) -> Point {
+    Point { x: p.x * k, y: p.y * k }
+}
+
+fn main() -> u64 {
+    val line = Line { start: Point::origin(), end: Point::origin() }
+    val q = Point::origin()
+    # cursor targets follow
+    line.end.
+    scale(q, 
+}
+
+fn after() -> u64 {
+    1u64
+}
+"#;
+
+fn lenient(source: &str, interner: &mut DefaultStringInterner) -> LenientProgram {
+    Parser::new(source, interner).parse_program_lenient()
+}
+
+/// Byte offset just past the `nth` occurrence of `needle`.
+fn after_nth(needle: &str, nth: usize) -> usize {
+    SOURCE.match_indices(needle).nth(nth).expect("needle in source").0 + needle.len()
+}
+
+#[test]
+fn broken_lines_are_skipped_and_the_rest_parses() {
+    let mut interner = DefaultStringInterner::new();
+    let parsed = lenient(SOURCE, &mut interner);
+    assert!(!parsed.errors.is_empty());
+    assert!(!parsed.skipped.is_empty());
+    let names: Vec<&str> = parsed
+        .program
+        .function
+        .iter()
+        .map(|f| interner.resolve(f.name).unwrap())
+        .collect();
+    assert_eq!(names, vec!["scale", "main", "after"]);
+}
+
+#[test]
+fn valid_source_is_parsed_unchanged() {
+    let mut interner = DefaultStringInterner::new();
+    let parsed = lenient("fn main() -> u64 {\n    1u64\n}\n", &mut interner);
+    assert!(parsed.errors.is_empty());
+    assert!(parsed.skipped.is_empty());
+    assert_eq!(parsed.program.function.len(), 1);
+}
+
+#[test]
+fn field_access_is_typed_through_bindings_and_fields() {
+    let mut interner = DefaultStringInterner::new();
+    let parsed = lenient(SOURCE, &mut interner);
+    let point = TypeDecl::Struct(interner.get("Point").unwrap(), vec![]);
+    assert_eq!(parsed.context_at(after_nth("self.", 0)), ExprContext::FieldAccessOf(point.clone()));
+    assert_eq!(parsed.context_at(after_nth("line.end.", 0)), ExprContext::FieldAccessOf(point.clone()));
+    // A partly typed member name does not change the answer.
+    assert_eq!(parsed.context_at(after_nth("p.x", 0)), ExprContext::FieldAccessOf(point));
+    // The value of a struct literal field is an expression, not a type.
+    assert_eq!(parsed.context_at(after_nth("Point { x: ", 1)), ExprContext::Expression);
+}
+
+#[test]
+fn call_arguments_paths_and_annotations() {
+    let mut interner = DefaultStringInterner::new();
+    let parsed = lenient(SOURCE, &mut interner);
+    assert_eq!(
+        parsed.context_at(after_nth("scale(q, ", 0)),
+        ExprContext::CallArgsOf { function: "scale".to_string(), arg_index: 1 }
+    );
+    assert_eq!(parsed.context_at(after_nth("Point::", 0)), ExprContext::PathOf("Point".to_string()));
+    assert_eq!(parsed.context_at(after_nth("fn scale(p: ", 0)), ExprContext::TypeAnnotation);
+    assert_eq!(parsed.context_at(after_nth("start: ", 0)), ExprContext::TypeAnnotation);
+    assert_eq!(parsed.context_at(after_nth("-> ", 0)), ExprContext::TypeAnnotation);
+    assert_eq!(parsed.context_at(after_nth("# cursor", 0)), ExprContext::Nothing);
+    // A parameter list is not a call.
+    assert_eq!(parsed.context_at(after_nth("fn scale(", 0)), ExprContext::Expression);
+}
+
+#[test]
+fn unknown_receivers_and_unbalanced_items() {
+    let mut interner = DefaultStringInterner::new();
+    let source = "fn main() -> u64 {\n    val n = foo(\n\nfn ok() -> u64 {\n    2u64\n}\n";
+    let parsed = lenient(source, &mut interner);
+    assert!(!parsed.errors.is_empty());
+    assert_eq!(parsed.program.function.len(), 1);
+    assert_eq!(interner.resolve(parsed.program.function[0].name), Some("ok"));
+    assert_eq!(
+        parsed.context_at(source.find("foo(").unwrap() + 4),
+        ExprContext::CallArgsOf { function: "foo".to_string(), arg_index: 0 }
+    );
+
+    let source = "fn main() -> u64 {\n    n.\n}\n";
+    let parsed = lenient(source, &mut interner);
+    assert_eq!(parsed.context_at(source.find("n.").unwrap() + 2), ExprContext::FieldAccessOf(TypeDecl::Unknown));
+}
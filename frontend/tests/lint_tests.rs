@@ -0,0 +1,87 @@
+//! `frontend::lints`: the rules, their levels and the manifest table
+//! that configures them.
+
+use frontend::lints::{run_lints, Lint, LintConfig, LintLevel, LintRule};
+use frontend::ParserWithInterner;
+
+fn lint(source: &str, config: &LintConfig) -> Vec<Lint> {
+    let mut parser = ParserWithInterner::new(source);
+    let program = parser.parse_program().expect("test program parses");
+    run_lints(&program, parser.get_string_interner(), source, config)
+}
+
+fn messages(lints: &[Lint], rule: LintRule) -> Vec<String> {
+    lints.iter().filter(|l| l.rule == rule).map(|l| l.message.clone()).collect()
+}
+
+#[test]
+fn naming_convention_covers_items_and_locals() {
+    let source = "const limit: u64 = 10u64\n\nstruct Point_t {\n    Xs: u64\n}\n\nfn Compute(aValue: u64) -> u64 {\n    val Doubled = aValue * 2u64\n    Doubled\n}\n\nfn main() -> u64 {\n    Compute(1u64)\n}\n";
+    let lints = lint(source, &LintConfig::default());
+    assert_eq!(
+        messages(&lints, LintRule::NamingConvention),
+        vec![
+            "const `limit` should be SCREAMING_SNAKE_CASE",
+            "struct `Point_t` should be UpperCamelCase",
+            "field `Xs` should be snake_case",
+            "function `Compute` should be snake_case",
+            "parameter `aValue` should be snake_case",
+            "local `Doubled` should be snake_case",
+        ]
+    );
+    assert!(lints.iter().all(|l| l.level == LintLevel::Warn));
+    assert_eq!(lints[0].location.map(|l| l.line), Some(1));
+}
+
+#[test]
+fn deep_nesting_counts_control_flow_against_the_limit() {
+    let source = "fn main() -> u64 {\n    var n = 0u64\n    while n < 9u64 {\n        if n > 1u64 {\n            if n > 2u64 {\n                n = n + 1u64\n            }\n        }\n        n = n + 1u64\n    }\n    n\n}\n";
+    assert!(messages(&lint(source, &LintConfig::default()), LintRule::DeepNesting).is_empty());
+
+    let mut config = LintConfig::default();
+    config.max_nesting_depth = 2;
+    assert_eq!(
+        messages(&lint(source, &config), LintRule::DeepNesting),
+        vec!["`main` nests control flow 3 levels deep (limit 2)"]
+    );
+}
+
+#[test]
+fn magic_numbers_are_opt_in_and_skip_consts_and_small_values() {
+    let source = "const LIMIT: u64 = 100u64\n\nfn main() -> u64 {\n    val x = 2u64 * LIMIT\n    x + 42u64 + 0u64\n}\n";
+    assert!(messages(&lint(source, &LintConfig::default()), LintRule::MagicNumber).is_empty());
+
+    let mut config = LintConfig::default();
+    config.set_level(LintRule::MagicNumber, LintLevel::Deny);
+    let lints = lint(source, &config);
+    assert_eq!(messages(&lints, LintRule::MagicNumber), vec!["magic number `42`; give it a name with `const`"]);
+    assert_eq!(lints[0].level, LintLevel::Deny);
+    assert_eq!(lints[0].location.map(|l| (l.line, l.column)), Some((5, 9)));
+}
+
+#[test]
+fn unused_pub_only_applies_to_programs_nothing_can_import() {
+    let program = "pub struct Used {\n    v: u64\n}\n\npub fn helper() -> u64 {\n    1u64\n}\n\npub fn make() -> Used {\n    Used { v: 1u64 }\n}\n\nfn main() -> u64 {\n    make().v\n}\n";
+    assert_eq!(
+        messages(&lint(program, &LintConfig::default()), LintRule::UnusedPub),
+        vec!["function `helper` is `pub` but nothing uses it"]
+    );
+
+    let module = format!("package util\n\n{}", program.replace("fn main() -> u64 {\n    make().v\n}\n", ""));
+    assert!(messages(&lint(&module, &LintConfig::default()), LintRule::UnusedPub).is_empty());
+}
+
+#[test]
+fn manifest_lints_table_sets_levels_and_options() {
+    let manifest = "[package]\nname = \"demo\"\n\n[lints]\nmagic-number = \"warn\"  # opt in\nnaming-convention = \"allow\"\nmax-nesting-depth = 2\n";
+    let config = LintConfig::from_manifest(manifest).unwrap();
+    assert_eq!(config.level(LintRule::MagicNumber), LintLevel::Warn);
+    assert_eq!(config.level(LintRule::NamingConvention), LintLevel::Allow);
+    assert_eq!(config.level(LintRule::UnusedPub), LintLevel::Warn);
+    assert_eq!(config.max_nesting_depth, 2);
+
+    let err = LintConfig::from_manifest("[lints]\nshadowing = \"deny\"\n").unwrap_err();
+    assert_eq!(err, "line 2: unknown lint `shadowing`");
+    let err = LintConfig::from_manifest("[lints]\ndeep-nesting = \"error\"\n").unwrap_err();
+    assert_eq!(err, "line 2: level of `deep-nesting` must be \"allow\", \"warn\" or \"deny\", got \"error\"");
+}
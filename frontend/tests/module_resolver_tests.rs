@@ -277,4 +277,31 @@ fn main() -> u64 {
         // Should resolve to first directory
         assert!(resolved.file_path.starts_with(temp_dir1.path()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_modules_exporting_finds_public_functions_by_alias() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_module(&temp_dir, "math/utils.t", "package math.utils\n\npub fn add(a: u64, b: u64) -> u64 {\n    a + b\n}\n\nfn hidden() -> u64 {\n    1u64\n}\n");
+        create_test_module(&temp_dir, "text/utils/mod.t", "package text.utils\n\npub fn add(a: u64, b: u64) -> u64 {\n    a\n}\n");
+        create_test_module(&temp_dir, "geo.t", "package geo\n\npub fn add(a: u64, b: u64) -> u64 {\n    b\n}\n");
+
+        let resolver = ModuleResolver::with_search_paths(vec![temp_dir.path().to_path_buf()]);
+        assert_eq!(resolver.modules_exporting("utils", "add"), vec!["math.utils", "text.utils"]);
+        assert_eq!(resolver.modules_exporting("geo", "add"), vec!["geo"]);
+        assert!(resolver.modules_exporting("utils", "hidden").is_empty());
+        assert!(resolver.modules_exporting("math", "add").is_empty());
+    }
+
+    #[test]
+    fn test_add_import_fix_goes_after_existing_imports() {
+        use frontend::type_checker::FixIt;
+        let apply = |source: &str| {
+            let fix = FixIt::add_import(source, "math.utils");
+            assert_eq!(fix.message, "add `import math.utils`");
+            format!("{}{}{}", &source[..fix.offset], fix.text, &source[fix.offset..])
+        };
+        assert_eq!(apply("fn main() -> u64 {\n    1u64\n}\n"), "import math.utils\n\nfn main() -> u64 {\n    1u64\n}\n");
+        assert_eq!(apply("import geo\n\nfn main() -> u64 {\n    1u64\n}\n"), "import geo\nimport math.utils\n\nfn main() -> u64 {\n    1u64\n}\n");
+        assert_eq!(apply("package app\n\nfn main() -> u64 {\n    1u64\n}\n"), "package app\n\nimport math.utils\n\nfn main() -> u64 {\n    1u64\n}\n");
+    }
+}
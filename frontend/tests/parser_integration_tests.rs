@@ -113,6 +113,8 @@ mod lexer_tests {
             "100_000u32",
             "0xFF_FFu64",
             "0xDEAD_BEEFu64",
+            "0o7_777",
+            "0b1111_0000u8",
             "3_141.592_653f64",
             "1_000_000",       // suffix-less Integer token
         ];
@@ -129,6 +131,20 @@ mod lexer_tests {
         }
     }
 
+    #[test]
+    fn test_integer_literal_overflow_is_invalid_literal() {
+        for input in ["256u8", "-0b1u8", "99999999999999999999", "-0x8000_0000_0000_0001"] {
+            let mut parser = ParserWithInterner::new(input);
+            let _ = parser.parse_stmt();
+            assert!(
+                parser.errors.iter().any(|e| e.code() == frontend::error_code::E_INVALID_LITERAL),
+                "`{}` should report E_INVALID_LITERAL, got {:?}",
+                input,
+                parser.errors,
+            );
+        }
+    }
+
     #[test]
     fn test_string_literals() {
         let input = "\"hello world\"";
@@ -731,6 +747,19 @@ mod statement_parsing {
         assert!(!parser.get_stmt_pool().is_empty());
     }
 
+    #[test]
+    fn test_for_loop_with_step() {
+        let input = "for i in 0u64..10u64 step 2 { i }";
+        let parser = helpers::parse_stmt_success(input);
+        assert!(!parser.get_stmt_pool().is_empty());
+    }
+
+    #[test]
+    fn test_for_loop_with_zero_step_is_error() {
+        let mut parser = ParserWithInterner::new("for i in 0u64..10u64 step 0u64 { i }");
+        assert!(parser.parse_stmt().is_err());
+    }
+
     #[test]
     fn test_while_loop() {
         let input = "while true { break }";
@@ -1113,6 +1142,18 @@ mod type_annotation_parsing {
         assert!(result.is_ok() || result.is_err(), "Should parse array type annotation");
     }
 
+    #[test]
+    fn test_array_type_length_limit() {
+        let input = "fn main() -> u64 {\n    val arr: [u64; 100000000] = [1u64]\n    0u64\n}";
+        let err = ParserWithInterner::new(input).parse_program().unwrap_err();
+        assert!(err.to_string().contains("[T; 100000000] exceeds the array length limit of 65536"), "{err}");
+        assert_eq!((err.location.line, err.location.column), (2, 20));
+
+        let mut parser = ParserWithInterner::new("fn main() -> u64 { val arr: [u64; 3] = [1u64, 2u64, 3u64] 0u64 }");
+        parser.set_max_array_len(2);
+        assert!(parser.parse_program().is_err(), "the limit should be configurable");
+    }
+
     #[test]
     fn test_function_return_type() {
         let input = "fn func(x: u64) -> u64 { x }";
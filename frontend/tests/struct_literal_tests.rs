@@ -320,6 +320,25 @@ mod struct_literal_creation {
         "#;
         assert!(parse_and_check(source).is_ok());
     }
+
+    #[test]
+    fn test_missing_field_is_error_not_panic() {
+        // Found by the `type_check` fuzz target: the checker looked the
+        // declared field names up in the interner and panicked when a
+        // literal omitted every field.
+        let source = r#"
+            struct Line {
+                d: u64
+            }
+
+            fn main() -> u64 {
+                val l = Line { }
+                0u64
+            }
+        "#;
+        let err = parse_and_check(source).expect_err("missing field must be rejected");
+        assert!(err.contains("Missing required field"), "{err}");
+    }
 }
 
 mod generic_struct_literal {
@@ -403,3 +422,44 @@ mod generic_struct_literal {
         assert!(parse_and_check(source).is_ok());
     }
 }
+
+mod tuple_struct {
+    use super::*;
+    use frontend::ast::{Expr, ExprRef};
+
+    /// Tuple struct construction and `.0` access leave plain struct
+    /// literals and field accesses behind for the backends.
+    #[test]
+    fn test_tuple_struct_rewritten_to_named_fields() {
+        let source = r#"
+            struct Meters(u64)
+
+            fn main() -> u64 {
+                val m = Meters(5u64)
+                m.0
+            }
+        "#;
+        let mut parser = ParserWithInterner::new(source);
+        let mut program = parser.parse_program().expect("tuple struct should parse");
+        let functions = program.function.clone();
+        let string_interner = parser.get_string_interner();
+        {
+            let mut type_checker = TypeCheckerVisitor::with_program(&mut program, string_interner);
+            for func in functions.iter() {
+                type_checker.type_check(func.clone()).expect("tuple struct should type-check");
+            }
+        }
+        let exprs: Vec<Expr> = (0..program.expression.len())
+            .filter_map(|i| program.expression.get(&ExprRef(i as u32)))
+            .collect();
+        assert!(exprs.iter().any(|e| matches!(e, Expr::StructLiteral(..))));
+        assert!(exprs.iter().any(|e| matches!(e, Expr::FieldAccess(..))));
+        assert!(!exprs.iter().any(|e| matches!(e, Expr::Call(..) | Expr::TupleAccess(..))));
+    }
+
+    #[test]
+    fn test_tuple_struct_parse_errors() {
+        let mut parser = ParserWithInterner::new("struct Empty()\nfn main() -> u64 { 0u64 }\n");
+        assert!(parser.parse_program().is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! `frontend::lex_with_spans`, the never-failing token stream used by
+//! editor highlighting.
+
+use frontend::lex_with_spans;
+use frontend::token::Kind;
+
+fn kinds_and_text(source: &str) -> Vec<(Kind, &str)> {
+    lex_with_spans(source)
+        .into_iter()
+        .map(|(kind, range)| (kind, &source[range]))
+        .collect()
+}
+
+#[test]
+fn spans_cover_the_token_text() {
+    let source = "val x = 1u64 # note\nx";
+    let tokens = kinds_and_text(source);
+    assert_eq!(tokens[0], (Kind::Val, "val"));
+    assert_eq!(tokens[1], (Kind::Identifier("x".to_string()), "x"));
+    assert_eq!(tokens[3], (Kind::UInt64(1), "1u64"));
+    assert!(tokens.iter().all(|(kind, _)| *kind != Kind::Error));
+    assert_eq!(tokens.last().unwrap(), &(Kind::Identifier("x".to_string()), "x"));
+}
+
+#[test]
+fn unlexable_text_becomes_error_tokens_and_lexing_resumes() {
+    let tokens = kinds_and_text("a $$ b");
+    assert_eq!(
+        tokens,
+        vec![
+            (Kind::Identifier("a".to_string()), "a"),
+            (Kind::Error, "$$"),
+            (Kind::Identifier("b".to_string()), "b"),
+        ]
+    );
+
+    let tokens = kinds_and_text("x é y");
+    assert_eq!(tokens[1], (Kind::Error, "é"));
+    assert_eq!(tokens[2], (Kind::Identifier("y".to_string()), "y"));
+
+    // A rule that matched but rejected its text.
+    let tokens = kinds_and_text("1abc + 2u64");
+    assert_eq!(tokens[0].0, Kind::Error);
+    assert_eq!(tokens.last().unwrap(), &(Kind::UInt64(2), "2u64"));
+}
+
+#[test]
+fn ranges_are_ordered_and_never_overlap() {
+    let source = "fn f(é: u64) -> \"open";
+    let tokens = lex_with_spans(source);
+    assert!(tokens.windows(2).all(|w| w[0].1.end <= w[1].1.start));
+    assert!(tokens.iter().all(|(_, range)| range.end <= source.len()));
+    assert!(tokens.iter().any(|(kind, _)| *kind == Kind::Error));
+}
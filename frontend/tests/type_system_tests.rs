@@ -195,6 +195,55 @@ mod advanced_scenarios {
         assert!(parse_and_check(source).is_ok());
     }
 
+    #[test]
+    fn test_unannotated_recursion_names_the_cycle() {
+        let source = r#"
+            fn count(n: u64) {
+                if n == 0u64 { 0u64 } else { 1u64 + count(n - 1u64) }
+            }
+        "#;
+
+        let err = parse_and_check(source).unwrap_err();
+        assert!(err.contains("function 'count' is recursive (count -> count)"), "{err}");
+    }
+
+    #[test]
+    fn test_unannotated_mutual_recursion_names_the_cycle() {
+        let source = r#"
+            fn is_even(n: u64) {
+                if n == 0u64 { true } else { is_odd(n - 1u64) }
+            }
+
+            fn is_odd(n: u64) -> bool {
+                if n == 0u64 { false } else { is_even(n - 1u64) }
+            }
+        "#;
+
+        let err = parse_and_check(source).unwrap_err();
+        assert!(err.contains("function 'is_even' is recursive (is_even -> is_odd -> is_even)"), "{err}");
+    }
+
+    #[test]
+    fn test_recursion_without_a_value_needs_no_annotation() {
+        let source = r#"
+            fn countdown(n: u64) {
+                if n > 0u64 {
+                    countdown(n - 1u64)
+                }
+            }
+
+            fn is_even(n: u64) -> bool {
+                if n == 0u64 { true } else { is_odd(n - 1u64) }
+            }
+
+            fn is_odd(n: u64) -> bool {
+                if n == 0u64 { false } else { is_even(n - 1u64) }
+            }
+        "#;
+
+        assert!(parse_and_check(source).is_ok());
+    }
+
     #[test]
     fn test_complex_expression_inference() {
         let source = r#"
@@ -433,3 +482,93 @@ mod error_cases {
 //
 //     assert!(parse_and_check(source).is_ok());
 // }
+
+mod declaration_order {
+    //! Whole-program checks (`visit_program`,
+    //! `check_program_multiple_errors`) must not depend on the order
+    //! of top-level items.
+
+    use frontend::ParserWithInterner;
+    use frontend::type_checker::TypeCheckerVisitor;
+    use frontend::visitor::ProgramVisitor;
+
+    const USES_BEFORE_DECLARATIONS: &str = r#"
+        impl Outer {
+            fn total(&self) -> u64 {
+                self.inner.v + weight(Kind::Heavy) + LIMIT
+            }
+        }
+
+        fn main() -> u64 {
+            val o = Outer { inner: Inner { v: 5u64 } }
+            o.total() + later(1u64)
+        }
+
+        const LIMIT: u64 = 40u64
+
+        fn later(x: u64) -> u64 {
+            x + LIMIT
+        }
+
+        fn weight(k: Kind) -> u64 {
+            match k {
+                Kind::Light => 1u64,
+                Kind::Heavy => 2u64,
+            }
+        }
+
+        struct Outer {
+            inner: Inner
+        }
+
+        struct Inner {
+            v: u64
+        }
+
+        enum Kind {
+            Light,
+            Heavy,
+        }
+    "#;
+
+    fn with_checker<R>(source: &str, check: impl FnOnce(&mut TypeCheckerVisitor, &frontend::ast::Program) -> R) -> R {
+        let mut parser = ParserWithInterner::new(source);
+        let program = parser.parse_program().expect("parse");
+        let mut expr_pool = program.expression.clone();
+        let mut checker = TypeCheckerVisitor::new(
+            &program.statement,
+            &mut expr_pool,
+            parser.get_string_interner(),
+            &program.location_pool,
+        )
+        .with_source_code(source);
+        check(&mut checker, &program)
+    }
+
+    #[test]
+    fn test_items_may_be_used_before_they_are_declared() {
+        let result = with_checker(USES_BEFORE_DECLARATIONS, |tc, program| tc.visit_program(program));
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn test_multiple_error_check_reports_only_real_errors() {
+        let source = USES_BEFORE_DECLARATIONS.replace("x + LIMIT", "x + true");
+        let errors = with_checker(&source, |tc, program| tc.check_program_multiple_errors(program).errors);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+    }
+
+    #[test]
+    fn test_duplicate_definitions_name_both_locations() {
+        let source = "fn f() -> u64 {\n    1u64\n}\nstruct P {\n    x: u64\n}\nfn f() -> u64 {\n    2u64\n}\nstruct P {\n    y: u64\n}\n";
+        let errors = with_checker(source, |tc, program| tc.check_program_multiple_errors(program).errors);
+        let find = |message: &str| errors.iter().find(|e| e.to_string().contains(message));
+        let f = find("function 'f' is already defined at line 1, column 1").expect("duplicate fn");
+        assert_eq!(f.location.as_ref().map(|l| (l.line, l.column)), Some((7, 1)));
+        let p = find("struct 'P' is already defined at line 4, column 1").expect("duplicate struct");
+        assert_eq!(p.location.as_ref().map(|l| (l.line, l.column)), Some((10, 1)));
+
+        let result = with_checker(source, |tc, program| tc.visit_program(program));
+        assert!(result.unwrap_err().to_string().contains("is already defined"));
+    }
+}
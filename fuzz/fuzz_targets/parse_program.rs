@@ -0,0 +1,16 @@
+//! Feeds arbitrary bytes to the parser. Any input is allowed to be
+//! rejected with a `ParserError`; the target only fails on a panic.
+//!
+//! Run: `cargo +nightly fuzz run parse_program`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+    let mut parser = frontend::ParserWithInterner::new(source);
+    let _ = parser.parse_program();
+});
@@ -0,0 +1,39 @@
+//! Builds a source file out of a fixed token vocabulary (one input
+//! byte per token) so most inputs get past the lexer and a useful
+//! fraction past the parser, then runs the type checker over every
+//! function the parser produced. Type errors are fine; panics are not.
+//!
+//! Run: `cargo +nightly fuzz run type_check`
+
+#![no_main]
+
+use frontend::type_checker::TypeCheckerVisitor;
+use libfuzzer_sys::fuzz_target;
+
+const TOKENS: &[&str] = &[
+    "fn", "main", "()", "(", ")", "{", "}", "[", "]", "<", ">", ",", ":", "::", ".", "..",
+    "=", "==", "+", "-", "*", "/", "%", "&&", "||", "!", "&", "|", "^", "<<", ">>", "->",
+    "=>", "val", "var", "struct", "impl", "trait", "enum", "match", "if", "elif", "else",
+    "for", "in", "to", "while", "break", "continue", "return", "as", "self", "Self", "pub",
+    "const", "type", "with", "_", "x", "y", "p", "T", "Point", "Color", "Red", "u64", "i64",
+    "bool", "str", "f64", "u8", "0u64", "1u64", "2i64", "1.5f64", "true", "false", "\"s\"",
+    "'a'", "[u64; 2]", "Option", "Some", "None", "\n",
+];
+
+fuzz_target!(|data: &[u8]| {
+    let mut source = String::new();
+    for &byte in data {
+        source.push_str(TOKENS[byte as usize % TOKENS.len()]);
+        source.push(' ');
+    }
+    let mut parser = frontend::ParserWithInterner::new(&source);
+    let Ok(mut program) = parser.parse_program() else {
+        return;
+    };
+    let functions = program.function.clone();
+    let interner = parser.get_string_interner();
+    let mut checker = TypeCheckerVisitor::with_program(&mut program, interner);
+    for function in functions.iter() {
+        let _ = checker.type_check(function.clone());
+    }
+});
@@ -0,0 +1,19 @@
+# Smoke-test for the `BitOps` extension trait (`core/std/bits.t`).
+# Each method forwards to a `__extern_*_u64` helper that the JIT
+# lowers to a native cranelift instruction (popcnt / clz / ctz /
+# rotl / rotr) when INTERPRETER_JIT=1.
+
+fn main() -> u64 {
+    val x: u64 = 0b1011u64
+    val m: i64 = -2i64
+    val ones: u64 = x.count_ones()                  # 3
+    val lz: u64 = x.leading_zeros()                 # 60
+    val tz: u64 = m.trailing_zeros()                # 1
+    val zero_lz: u64 = 0u64.leading_zeros()         # 64
+    val rl: u64 = x.rotate_left(66u64)              # 44 (amount mod 64)
+    val rr: u64 = 1u64.rotate_right(1u64) >> 60u64  # 8
+    val neg: i64 = m.rotate_right(1u64)             # i64::MAX
+    val neg_ones: u64 = neg.count_ones()            # 63
+    # 3 + 60 + 1 + 64 + 44 + 8 + 63 = 243
+    ones + lz + tz + zero_lz + rl + rr + neg_ones
+}
@@ -0,0 +1,149 @@
+//! Compile-time evaluation of pure calls.
+//!
+//! A call to a pure function (`TypeCheckResults::purity`) whose
+//! arguments are constant computes the same value on every run, so a
+//! backend can run it once while compiling and put the result in its
+//! place. `fold_pure_calls` does that on the checked program: it runs
+//! each such call in a sandboxed interpreter and rewrites the call
+//! expression into the literal it returned.
+//!
+//! An argument is constant when it is a bool or number literal, an
+//! arithmetic / comparison / logical expression over constants, or
+//! itself a foldable call. Only calls whose checked type is `bool`, an
+//! integer or `f64` are replaced. The sandbox holds no capabilities,
+//! checks every contract clause and gives up after
+//! [`STEP_BUDGET`] expression evaluations or past [`CALL_DEPTH_LIMIT`]
+//! nested calls; a call that panics, breaks a contract, runs out of
+//! steps or recurses too deep is left alone, so it fails (or not) at
+//! run time exactly as it would have.
+
+use std::collections::HashMap;
+
+use frontend::ast::{Expr, ExprRef, Program};
+use frontend::purity::Purity;
+use frontend::type_decl::TypeDecl;
+use string_interner::DefaultStringInterner;
+
+use crate::evaluation::{ContractMode, EvaluationResult};
+use crate::object::Object;
+use crate::Capabilities;
+
+/// Expression evaluations one folded call may take.
+pub const STEP_BUDGET: u64 = 1_000_000;
+
+/// Calls one folded call may nest. Kept small: the sandbox runs on the
+/// compiler's own stack, and a tree-walked call frame is large.
+pub const CALL_DEPTH_LIMIT: u32 = 16;
+
+/// Replace every pure call with constant arguments in `program` by the
+/// literal it evaluates to. `expr_types` and `purity` are the checked
+/// program's `TypeCheckResults`. Returns how many calls were folded.
+pub fn fold_pure_calls(
+    program: &mut Program,
+    expr_types: &HashMap<ExprRef, TypeDecl>,
+    purity: &Purity,
+    string_interner: &DefaultStringInterner,
+) -> usize {
+    let candidates: Vec<ExprRef> = (0..program.expression.len())
+        .map(|i| ExprRef(i as u32))
+        .filter(|e| is_foldable_call(program, purity, e) && expr_types.get(e).is_some_and(is_scalar))
+        .collect();
+    if candidates.is_empty() {
+        return 0;
+    }
+
+    let mut interner = string_interner.clone();
+    let Ok(mut eval) = crate::prepare_evaluation(program, string_interner, &mut interner, Capabilities::none())
+    else {
+        return 0;
+    };
+    eval.set_contract_mode(ContractMode::default());
+    eval.call_depth_limit = Some(CALL_DEPTH_LIMIT);
+    let mut folded: Vec<(ExprRef, Expr)> = Vec::new();
+    for call in candidates {
+        eval.step_budget = Some(STEP_BUDGET);
+        let Ok(EvaluationResult::Value(value)) = eval.evaluate(&call) else {
+            continue;
+        };
+        let literal = match &*value.into_rc().borrow() {
+            Object::Bool(true) => Expr::True,
+            Object::Bool(false) => Expr::False,
+            Object::Int64(v) => Expr::Int64(*v),
+            Object::UInt64(v) => Expr::UInt64(*v),
+            Object::Int8(v) => Expr::Int8(*v),
+            Object::Int16(v) => Expr::Int16(*v),
+            Object::Int32(v) => Expr::Int32(*v),
+            Object::UInt8(v) => Expr::UInt8(*v),
+            Object::UInt16(v) => Expr::UInt16(*v),
+            Object::UInt32(v) => Expr::UInt32(*v),
+            Object::Float64(v) => Expr::Float64(*v),
+            _ => continue,
+        };
+        if literal_type(&literal) == expr_types[&call] {
+            folded.push((call, literal));
+        }
+    }
+    drop(eval);
+
+    let count = folded.len();
+    for (call, literal) in folded {
+        program.expression.update(&call, literal);
+    }
+    count
+}
+
+fn is_foldable_call(program: &Program, purity: &Purity, e: &ExprRef) -> bool {
+    let Some(Expr::Call(name, args, _)) = program.expression.get(e) else {
+        return false;
+    };
+    if !purity.is_pure_function(name) {
+        return false;
+    }
+    match program.expression.get(&args) {
+        Some(Expr::ExprList(items)) => items.iter().all(|a| is_constant(program, purity, a)),
+        _ => false,
+    }
+}
+
+fn is_constant(program: &Program, purity: &Purity, e: &ExprRef) -> bool {
+    match program.expression.get(e) {
+        Some(Expr::Binary(_, lhs, rhs)) => is_constant(program, purity, &lhs) && is_constant(program, purity, &rhs),
+        Some(Expr::Unary(_, operand)) => is_constant(program, purity, &operand),
+        Some(Expr::Call(..)) => is_foldable_call(program, purity, e),
+        Some(literal) => is_scalar(&literal_type(&literal)),
+        None => false,
+    }
+}
+
+fn is_scalar(ty: &TypeDecl) -> bool {
+    matches!(
+        ty,
+        TypeDecl::Bool
+            | TypeDecl::Int64
+            | TypeDecl::UInt64
+            | TypeDecl::Int8
+            | TypeDecl::Int16
+            | TypeDecl::Int32
+            | TypeDecl::UInt8
+            | TypeDecl::UInt16
+            | TypeDecl::UInt32
+            | TypeDecl::Float64
+    )
+}
+
+/// Type of a bool or number literal; `Unknown` for anything else.
+fn literal_type(literal: &Expr) -> TypeDecl {
+    match literal {
+        Expr::True | Expr::False => TypeDecl::Bool,
+        Expr::Int64(_) => TypeDecl::Int64,
+        Expr::UInt64(_) => TypeDecl::UInt64,
+        Expr::Int8(_) => TypeDecl::Int8,
+        Expr::Int16(_) => TypeDecl::Int16,
+        Expr::Int32(_) => TypeDecl::Int32,
+        Expr::UInt8(_) => TypeDecl::UInt8,
+        Expr::UInt16(_) => TypeDecl::UInt16,
+        Expr::UInt32(_) => TypeDecl::UInt32,
+        Expr::Float64(_) => TypeDecl::Float64,
+        _ => TypeDecl::Unknown,
+    }
+}
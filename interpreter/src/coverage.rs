@@ -0,0 +1,134 @@
+//! Line coverage behind `interpreter --coverage`.
+//!
+//! While a [`Coverage`] is installed on the evaluator, every evaluated
+//! expression and every statement a block runs bumps a counter indexed by
+//! its `ExprRef` / `StmtRef`. The counters are sized from the program as
+//! parsed, before the prelude and core modules are integrated, so only the
+//! user's own nodes are counted. After the run [`Coverage::lcov`] maps the
+//! counts onto source lines through the program's `LocationPool` and renders
+//! an lcov tracefile that `genhtml` and editor plugins understand.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use frontend::ast::{Expr, ExprRef, Program, Stmt, StmtRef};
+use string_interner::DefaultStringInterner;
+
+/// Where `--coverage` writes the report when no file is given.
+pub const DEFAULT_REPORT_FILE: &str = "lcov.info";
+
+/// Execution counts of the user's expressions and statements.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    exprs: Vec<u64>,
+    stmts: Vec<u64>,
+}
+
+impl Coverage {
+    /// Counters for every node `program` holds right now. Call this on
+    /// the freshly parsed program; nodes added later are not counted.
+    pub fn new(program: &Program) -> Self {
+        Coverage { exprs: vec![0; program.expression.len()], stmts: vec![0; program.statement.len()] }
+    }
+
+    pub fn hit_expr(&mut self, expr: &ExprRef) {
+        if let Some(count) = self.exprs.get_mut(expr.0 as usize) {
+            *count += 1;
+        }
+    }
+
+    pub fn hit_stmt(&mut self, stmt: &StmtRef) {
+        if let Some(count) = self.stmts.get_mut(stmt.0 as usize) {
+            *count += 1;
+        }
+    }
+
+    pub fn expr_count(&self, expr: &ExprRef) -> Option<u64> {
+        self.exprs.get(expr.0 as usize).copied()
+    }
+
+    pub fn stmt_count(&self, stmt: &StmtRef) -> Option<u64> {
+        self.stmts.get(stmt.0 as usize).copied()
+    }
+
+    /// Per-line hit counts: a line is instrumented when one of the user's
+    /// expressions is located on it, and its count is the highest count
+    /// among those expressions. Blocks and the forms built around them
+    /// are skipped — the parser locates those at their closing brace, and
+    /// their contents mark the lines that matter.
+    pub fn line_hits(&self, program: &Program) -> BTreeMap<u32, u64> {
+        let mut lines = BTreeMap::new();
+        for (i, count) in self.exprs.iter().enumerate() {
+            let expr_ref = ExprRef(i as u32);
+            let wraps_block = matches!(
+                program.expression.get(&expr_ref),
+                Some(Expr::Block(_) | Expr::IfElifElse(..) | Expr::Match(..) | Expr::Closure { .. } | Expr::Assign(..))
+            );
+            if wraps_block {
+                continue;
+            }
+            if let Some(location) = program.location_pool.get_expr_location(&expr_ref) {
+                let line = lines.entry(location.line).or_insert(0);
+                *line = (*line).max(*count);
+            }
+        }
+        lines
+    }
+
+    /// lcov tracefile for `source`, reported as `source_name`. Functions
+    /// and methods are reported at the line of their `fn` with the number
+    /// of times their first statement ran; a function with an empty body
+    /// reports zero.
+    pub fn lcov(&self, program: &Program, interner: &DefaultStringInterner, source: &str, source_name: &str) -> String {
+        let name = |sym| interner.resolve(sym).unwrap_or("<unknown>").to_string();
+        let mut bodies: Vec<(String, usize, StmtRef)> = program
+            .function
+            .iter()
+            .filter(|f| !f.is_extern)
+            .map(|f| (name(f.name), f.node.start, f.code))
+            .collect();
+        for i in 0..self.stmts.len() {
+            if let Some(Stmt::ImplBlock { target_type, methods, .. }) = program.statement.get(&StmtRef(i as u32)) {
+                let target = name(target_type);
+                bodies.extend(methods.iter().map(|m| (format!("{}::{}", target, name(m.name)), m.node.start, m.code)));
+            }
+        }
+
+        let mut functions = Vec::new();
+        for (fn_name, start, code) in bodies {
+            if self.stmt_count(&code).is_none() {
+                continue;
+            }
+            let Some(Stmt::Expression(block)) = program.statement.get(&code) else {
+                continue;
+            };
+            let line = source[..start.min(source.len())].matches('\n').count() + 1;
+            let hits = program
+                .expression
+                .stmt_list_of(&block)
+                .and_then(|stmts| stmts.first().copied())
+                .and_then(|first| self.stmt_count(&first))
+                .unwrap_or(0);
+            functions.push((line, fn_name, hits));
+        }
+        functions.sort();
+
+        let mut out = format!("TN:\nSF:{source_name}\n");
+        for (line, fn_name, _) in &functions {
+            let _ = writeln!(out, "FN:{line},{fn_name}");
+        }
+        for (_, fn_name, hits) in &functions {
+            let _ = writeln!(out, "FNDA:{hits},{fn_name}");
+        }
+        let _ = writeln!(out, "FNF:{}", functions.len());
+        let _ = writeln!(out, "FNH:{}", functions.iter().filter(|(_, _, hits)| *hits > 0).count());
+        let lines = self.line_hits(program);
+        for (line, hits) in &lines {
+            let _ = writeln!(out, "DA:{line},{hits}");
+        }
+        let _ = writeln!(out, "LF:{}", lines.len());
+        let _ = writeln!(out, "LH:{}", lines.values().filter(|hits| **hits > 0).count());
+        out.push_str("end_of_record\n");
+        out
+    }
+}
@@ -31,6 +31,9 @@ pub struct Environment {
     var: Vec<HashMap<DefaultSymbol, VariableValue>>,
     pub modules: HashMap<Vec<DefaultSymbol>, ModuleEnvironment>,  // Module registry
     pub current_module: Option<Vec<DefaultSymbol>>,               // Current module path
+    // Saved scopes of a resuming generator, innermost first; `enter_block`
+    // reinstates them in place of fresh scopes (see `evaluation::generator`).
+    resume_scopes: Vec<HashMap<DefaultSymbol, VariableValue>>,
 }
 
 #[derive(Eq, PartialEq)]
@@ -51,17 +54,45 @@ impl Environment {
             var: vec![HashMap::new()],
             modules: HashMap::new(),
             current_module: None,
+            resume_scopes: Vec::new(),
         }
     }
 
     pub fn enter_block(&mut self) {
-        self.var.push(HashMap::new());
+        let scope = self.resume_scopes.pop().unwrap_or_default();
+        self.var.push(scope);
     }
 
     pub fn exit_block(&mut self) {
         self.var.pop();
     }
 
+    /// Close every scope opened after the first `depth`, e.g. the ones
+    /// an error unwound through without reaching their `exit_block`.
+    pub fn truncate_scopes(&mut self, depth: usize) {
+        self.var.truncate(depth);
+    }
+
+    /// Number of open block scopes.
+    pub fn depth(&self) -> usize {
+        self.var.len()
+    }
+
+    /// Copies of the scopes from `from` inward, outermost first.
+    pub(crate) fn snapshot_scopes(&self, from: usize) -> Vec<HashMap<DefaultSymbol, VariableValue>> {
+        self.var[from..].to_vec()
+    }
+
+    /// Have the next `enter_block` calls reopen `scopes` (outermost
+    /// first) instead of starting empty.
+    pub(crate) fn queue_resume_scopes(&mut self, scopes: Vec<HashMap<DefaultSymbol, VariableValue>>) {
+        self.resume_scopes = scopes.into_iter().rev().collect();
+    }
+
+    pub(crate) fn resume_finished(&self) -> bool {
+        self.resume_scopes.is_empty()
+    }
+
     pub fn set_val(&mut self, name: DefaultSymbol, value: Value) {
         if let Some(last) = self.var.last_mut() {
             last.insert(name,
@@ -116,6 +147,15 @@ impl Environment {
         Ok(())
     }
 
+    /// Every bound value: all block scopes, then module variables.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+        self.var
+            .iter_mut()
+            .flat_map(|scope| scope.values_mut())
+            .chain(self.modules.values_mut().flat_map(|module| module.variables.values_mut()))
+            .map(|v| &mut v.value)
+    }
+
     pub fn get_val(&self, name: DefaultSymbol) -> Option<Value> {
         for v in self.var.iter().rev() {
             if let Some(val) = v.get(&name) {
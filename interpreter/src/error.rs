@@ -23,8 +23,56 @@ pub enum InterpreterError {
         clause_index: usize,
     },
     /// Explicit user-triggered abort via the `panic("msg")` builtin.
-    /// The message is exactly what the user passed.
-    Panic { message: String },
+    /// The message is exactly what the user passed. `backtrace` holds
+    /// the function / method names the panic unwound through,
+    /// innermost first; it is filled in by `with_frame` at each call
+    /// boundary on the way out, so it starts empty.
+    Panic { message: String, backtrace: Vec<String> },
+}
+
+impl InterpreterError {
+    /// A `Panic` with an empty backtrace, for raising at the point of
+    /// failure.
+    pub fn panic(message: impl Into<String>) -> Self {
+        InterpreterError::Panic { message: message.into(), backtrace: Vec::new() }
+    }
+
+    /// Whether a `try` body's failure is handed to its `catch` block.
+    /// Only failures the program itself can cause are: panics, failed
+    /// contracts and bad indexing. Everything else means the
+    /// interpreter or the type checker got something wrong, and has to
+    /// reach the top level unchanged.
+    pub fn is_catchable(&self) -> bool {
+        match self {
+            InterpreterError::Panic { .. }
+            | InterpreterError::ContractViolation { .. }
+            | InterpreterError::IndexOutOfBounds { .. } => true,
+            InterpreterError::ObjectError(err) => {
+                matches!(err, ObjectError::IndexOutOfBounds { .. } | ObjectError::NullDereference)
+            }
+            _ => false,
+        }
+    }
+
+    /// The text a `catch` binding receives: a panic's own message
+    /// (without the `panic:` prefix or backtrace), else the error's
+    /// display form.
+    pub fn caught_message(&self) -> String {
+        match self {
+            InterpreterError::Panic { message, .. } => message.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Record that this error is propagating out of the call frame
+    /// `name`. Only panics carry a backtrace; other errors pass
+    /// through unchanged.
+    pub fn with_frame(mut self, name: &str) -> Self {
+        if let InterpreterError::Panic { backtrace, .. } = &mut self {
+            backtrace.push(name.to_string());
+        }
+        self
+    }
 }
 
 impl fmt::Display for InterpreterError {
@@ -58,8 +106,15 @@ impl fmt::Display for InterpreterError {
                 write!(f, "Contract violation: `{kind}` clause #{idx} of function `{function}` evaluated to false",
                        idx = clause_index + 1)
             }
-            InterpreterError::Panic { message } => {
-                write!(f, "panic: {message}")
+            InterpreterError::Panic { message, backtrace } => {
+                write!(f, "panic: {message}")?;
+                if !backtrace.is_empty() {
+                    write!(f, "\nstack backtrace:")?;
+                    for (i, frame) in backtrace.iter().enumerate() {
+                        write!(f, "\n  {i}: {frame}")?;
+                    }
+                }
+                Ok(())
             }
         }
     }
@@ -1,4 +1,5 @@
 use frontend::parser::error::ParserError;
+use frontend::lints::{Lint, LintLevel};
 use frontend::type_checker::{SourceLocation, TypeCheckError};
 
 /// Enum for different types of errors that can occur
@@ -43,26 +44,53 @@ impl<'a> ErrorFormatter<'a> {
     }
 
     pub fn format_parse_error(&self, error: &ParserError) -> String {
-        self.format_error_with_location(&error.to_string(), &error.location)
+        self.format_error_with_location(&error.to_string(), &error.location, Some(error.code()))
     }
 
     pub fn format_type_check_error(&self, error: &TypeCheckError) -> String {
-        if let Some(location) = &error.location {
-            self.format_error_with_location(&error.to_string(), location)
+        let formatted = if let Some(location) = &error.location {
+            self.format_error_with_location(&error.to_string(), location, Some(error.code()))
         } else {
-            format!("Error: {error}")
+            format!("Error[{}]: {error}", error.code())
+        };
+        match error.fix() {
+            Some(fix) => format!("{formatted}\n   = help: {}", fix.message),
+            None => formatted,
         }
     }
 
     pub fn format_runtime_error(&self, error_msg: &str, location: Option<&SourceLocation>) -> String {
         if let Some(loc) = location {
-            self.format_error_with_location(error_msg, loc)
+            self.format_error_with_location(error_msg, loc, None)
         } else {
             error_msg.to_string()  // Don't add "Runtime Error:" prefix here since it's handled by display method
         }
     }
 
-    fn format_error_with_location(&self, error_msg: &str, location: &SourceLocation) -> String {
+    /// `Warning[rule]` / `Error[rule]` for a lint, with the source line
+    /// when it has a location.
+    pub fn format_lint(&self, lint: &Lint) -> String {
+        let header = match lint.level {
+            LintLevel::Deny => format!("Error[{}]", lint.rule.name()),
+            _ => format!("Warning[{}]", lint.rule.name()),
+        };
+        match &lint.location {
+            Some(location) => self.format_with_location(&header, &lint.message, location),
+            None => format!("{header}: {}", lint.message),
+        }
+    }
+
+    /// `code` is the stable diagnostic code (`E0101` etc.) for parse /
+    /// type-check errors; runtime errors have none.
+    fn format_error_with_location(&self, error_msg: &str, location: &SourceLocation, code: Option<&str>) -> String {
+        let header = match code {
+            Some(code) => format!("Error[{code}]"),
+            None => "Error".to_string(),
+        };
+        self.format_with_location(&header, error_msg, location)
+    }
+
+    fn format_with_location(&self, header: &str, error_msg: &str, location: &SourceLocation) -> String {
         let line_number = location.line;
         let column = location.column;
         
@@ -95,7 +123,8 @@ impl<'a> ErrorFormatter<'a> {
         };
         
         format!(
-            "Error at {}:{}:{}:\n   |\n{} | {}\n   | {} {}\n   |",
+            "{} at {}:{}:{}:\n   |\n{} | {}\n   | {} {}\n   |",
+            header,
             self.filename,
             line_number,
             column,
@@ -158,11 +187,14 @@ impl<'a> ErrorFormatter<'a> {
     }
 
     fn find_error_position_in_line(&self, error_msg: &str, source_line: &str) -> Option<usize> {
-        // Extract identifier from error messages like "Identifier 'undefined_variable' not found"
-        if let Some(start) = error_msg.find("'") {
-            if let Some(end) = error_msg[start + 1..].find("'") {
-                let identifier = &error_msg[start + 1..start + 1 + end];
-                return source_line.find(identifier);
+        // Extract identifier from error messages like "Identifier 'undefined_variable' not found",
+        // or lint messages like "function `Compute` should be snake_case"
+        for quote in ['\'', '`'] {
+            if let Some(start) = error_msg.find(quote) {
+                if let Some(end) = error_msg[start + 1..].find(quote) {
+                    let identifier = &error_msg[start + 1..start + 1 + end];
+                    return source_line.find(identifier);
+                }
             }
         }
         None
@@ -190,7 +222,7 @@ mod tests {
         });
         
         let formatted = formatter.format_type_check_error(&error);
-        assert!(formatted.contains("Error at test.t:2:18:"));
+        assert!(formatted.contains("Error[E0101] at test.t:2:18:"));
         assert!(formatted.contains("val x: i64 = \"string\""));
         assert!(formatted.contains("^^"));
     }
@@ -202,7 +234,18 @@ mod tests {
         
         let error = TypeCheckError::generic_error("Generic error message");
         let formatted = formatter.format_type_check_error(&error);
-        assert_eq!(formatted, "Error: Generic error message");
+        assert_eq!(formatted, "Error[E0110]: Generic error message");
+    }
+
+    #[test]
+    fn test_error_formatter_with_fix() {
+        let source = "fn main() -> u64 {\n    utils::add(1u64, 2u64)\n}\n";
+        let formatter = ErrorFormatter::new(source, "test.t");
+
+        let error = TypeCheckError::not_found("Struct", "utils")
+            .with_fix(frontend::type_checker::FixIt::add_import(source, "math.utils"));
+        let formatted = formatter.format_type_check_error(&error);
+        assert_eq!(formatted, "Error[E0103]: Struct 'utils' not found\n   = help: add `import math.utils`");
     }
 
     #[test]
@@ -2,10 +2,14 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use frontend::ast::*;
 use frontend::type_decl::TypeDecl;
-use crate::object::{Object, RcObject};
+use crate::object::{format_type_decl_for_display, Object, RcObject};
 use crate::error::InterpreterError;
 use crate::try_value;
 use super::{EvaluationContext, EvaluationResult};
+use super::capabilities::Capability;
+use super::lua_pattern;
+use super::script_log::LogLevel;
+use crate::value::Value;
 
 /// Compute the byte size of a runtime value by walking its Object tree.
 /// Primitives have fixed widths; composite values sum their components.
@@ -23,6 +27,7 @@ fn object_byte_size(value: &Object) -> Option<u64> {
         Object::Int32(_) | Object::UInt32(_) => Some(4),
         Object::Bool(_) => Some(1),
         Object::Unit => Some(0),
+        Object::Bytes(v) => Some(v.len() as u64),
         Object::Struct { fields, .. } => {
             let mut total: u64 = 0;
             for v in fields.values() {
@@ -55,13 +60,13 @@ fn object_byte_size(value: &Object) -> Option<u64> {
         // Opaque / non-serialisable values have no canonical byte size.
         Object::ConstString(_) | Object::String(_) | Object::Dict(_)
         | Object::Null(_) | Object::Allocator(_) | Object::Range { .. }
-        | Object::Closure { .. } => None,
+        | Object::Closure { .. } | Object::Generator(_) => None,
     }
 }
 
 impl EvaluationContext<'_> {
     /// Evaluate builtin method calls
-    pub(super) fn evaluate_builtin_method_call(&mut self, receiver: &ExprRef, method: &BuiltinMethod, args: &Vec<ExprRef>) -> Result<EvaluationResult, InterpreterError> {
+    pub(super) fn evaluate_builtin_method_call(&mut self, receiver: &ExprRef, method: &BuiltinMethod, args: &[ExprRef]) -> Result<EvaluationResult, InterpreterError> {
         let receiver_value = self.evaluate(receiver)?;
         let receiver_obj = try_value!(Ok(receiver_value));
 
@@ -69,7 +74,7 @@ impl EvaluationContext<'_> {
     }
 
     /// Execute builtin method with table-driven approach
-    fn execute_builtin_method(&mut self, receiver: &RcObject, method: &BuiltinMethod, args: &Vec<ExprRef>) -> Result<EvaluationResult, InterpreterError> {
+    pub(super) fn execute_builtin_method(&mut self, receiver: &RcObject, method: &BuiltinMethod, args: &[ExprRef]) -> Result<EvaluationResult, InterpreterError> {
         match method {
             BuiltinMethod::IsNull => {
                 if !args.is_empty() {
@@ -126,10 +131,7 @@ impl EvaluationContext<'_> {
                     });
                 }
 
-                let string_symbol = receiver.borrow().try_unwrap_string().map_err(InterpreterError::ObjectError)?;
-                let string_value = self.string_interner.resolve(string_symbol)
-                    .ok_or_else(|| InterpreterError::InternalError("String symbol not found in interner".to_string()))?
-                    .to_string();
+                let string_value = receiver.borrow().to_string_value(self.string_interner);
 
                 let start_value = self.evaluate(&args[0])?;
                 let start_obj = try_value!(Ok(start_value));
@@ -157,17 +159,11 @@ impl EvaluationContext<'_> {
                     });
                 }
 
-                let string_symbol = receiver.borrow().try_unwrap_string().map_err(InterpreterError::ObjectError)?;
-                let string_value = self.string_interner.resolve(string_symbol)
-                    .ok_or_else(|| InterpreterError::InternalError("String symbol not found in interner".to_string()))?
-                    .to_string();
+                let string_value = receiver.borrow().to_string_value(self.string_interner);
 
                 let arg_value = self.evaluate(&args[0])?;
                 let arg_obj = try_value!(Ok(arg_value));
-                let arg_symbol = arg_obj.borrow().try_unwrap_string().map_err(InterpreterError::ObjectError)?;
-                let arg_string = self.string_interner.resolve(arg_symbol)
-                    .ok_or_else(|| InterpreterError::InternalError("Argument string symbol not found in interner".to_string()))?
-                    .to_string();
+                let arg_string = arg_obj.borrow().to_string_value(self.string_interner);
 
                 let contains = string_value.contains(&arg_string);
                 Ok(EvaluationResult::Value((Object::Bool(contains)).into()))
@@ -243,6 +239,25 @@ impl EvaluationContext<'_> {
                 Ok(EvaluationResult::Value(Object::Array(Box::new(parts)).into()))
             }
 
+            BuiltinMethod::ArrayLen | BuiltinMethod::BytesLen => {
+                if !args.is_empty() {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "len() takes no arguments".to_string(),
+                        expected: 0,
+                        found: args.len()
+                    });
+                }
+
+                let length = match &*receiver.borrow() {
+                    Object::Array(elements) => elements.len() as u64,
+                    Object::Bytes(data) => data.len() as u64,
+                    other => return Err(InterpreterError::InternalError(format!(
+                        "len() called on a non-container value: {other:?}"
+                    ))),
+                };
+                Ok(EvaluationResult::Value((Object::UInt64(length)).into()))
+            }
+
             // NOTE: numeric value-method arms (`I64Abs` / `F64Abs` /
             // `F64Sqrt`) lived here before Step F. The prelude's
             // extension-trait impls now cover the same surface; the
@@ -703,7 +718,7 @@ impl EvaluationContext<'_> {
                 // __builtin_to_string(x))`).
                 let value = self.evaluate(&args[0])?;
                 let value = try_value!(Ok(value));
-                let rendered = value.borrow().to_display_string(self.string_interner);
+                let rendered = self.display_string(&value)?;
                 Ok(EvaluationResult::Value(Object::String(rendered).into()))
             }
 
@@ -722,7 +737,7 @@ impl EvaluationContext<'_> {
                 let value = self.evaluate(&args[0])?;
                 let value = try_value!(Ok(value));
                 let message = value.borrow().to_display_string(self.string_interner);
-                Err(InterpreterError::Panic { message })
+                Err(InterpreterError::panic(message))
             }
 
             BuiltinFunction::Assert => {
@@ -748,7 +763,7 @@ impl EvaluationContext<'_> {
                 let msg_val = self.evaluate(&args[1])?;
                 let msg_val = try_value!(Ok(msg_val));
                 let message = msg_val.borrow().to_display_string(self.string_interner);
-                Err(InterpreterError::Panic { message })
+                Err(InterpreterError::panic(message))
             }
 
             BuiltinFunction::Print | BuiltinFunction::Println => {
@@ -764,7 +779,7 @@ impl EvaluationContext<'_> {
                 }
                 let value = self.evaluate(&args[0])?;
                 let value = try_value!(Ok(value));
-                let rendered = value.borrow().to_display_string(self.string_interner);
+                let rendered = self.display_string(&value)?;
                 if matches!(func, BuiltinFunction::Println) {
                     crate::output::println_text(&rendered);
                 } else {
@@ -773,6 +788,904 @@ impl EvaluationContext<'_> {
                 Ok(EvaluationResult::Value((Object::Unit).into()))
             }
 
+            BuiltinFunction::LogDebug | BuiltinFunction::LogInfo
+            | BuiltinFunction::LogWarn | BuiltinFunction::LogError => {
+                let (name, level) = match func {
+                    BuiltinFunction::LogDebug => ("log_debug", LogLevel::Debug),
+                    BuiltinFunction::LogInfo => ("log_info", LogLevel::Info),
+                    BuiltinFunction::LogWarn => ("log_warn", LogLevel::Warn),
+                    _ => ("log_error", LogLevel::Error),
+                };
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 1 argument"),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let value = self.evaluate(&args[0])?;
+                let value = try_value!(Ok(value));
+                // Skip rendering a message nobody will see.
+                if self.log.enabled(level) {
+                    let message = value.borrow().to_string_value(self.string_interner);
+                    self.log
+                        .write(level, &message)
+                        .map_err(|e| InterpreterError::panic(format!("{name}: cannot write log: {e}")))?;
+                }
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            BuiltinFunction::ReadBytes => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "read_bytes takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::FsRead, "read_bytes")?;
+                let path_val = self.evaluate(&args[0])?;
+                let path_val = try_value!(Ok(path_val));
+                let path = path_val.borrow().to_string_value(self.string_interner);
+                // I/O failures surface as a panic: the language has no
+                // error channel a builtin could return through yet.
+                let data = std::fs::read(&path).map_err(|e| {
+                    InterpreterError::panic(format!("read_bytes(\"{path}\"): {e}"))
+                })?;
+                Ok(EvaluationResult::Value((Object::Bytes(Box::new(data))).into()))
+            }
+
+            BuiltinFunction::WriteBytes => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "write_bytes takes 2 arguments (path, data)".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::FsWrite, "write_bytes")?;
+                let path_val = self.evaluate(&args[0])?;
+                let path_val = try_value!(Ok(path_val));
+                let path = path_val.borrow().to_string_value(self.string_interner);
+                let data_val = self.evaluate(&args[1])?;
+                let data_val = try_value!(Ok(data_val));
+                let data_borrowed = data_val.borrow();
+                let Object::Bytes(data) = &*data_borrowed else {
+                    return Err(InterpreterError::InternalError(
+                        "write_bytes expects bytes as second argument".to_string(),
+                    ));
+                };
+                std::fs::write(&path, data.as_slice()).map_err(|e| {
+                    InterpreterError::panic(format!("write_bytes(\"{path}\"): {e}"))
+                })?;
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            BuiltinFunction::BytesFromPtr => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "bytes_from_ptr takes 2 arguments (ptr, len)".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let ptr_val = self.evaluate(&args[0])?;
+                let ptr_val = try_value!(Ok(ptr_val));
+                let addr = ptr_val.borrow().try_unwrap_pointer()
+                    .map_err(|_| InterpreterError::InternalError("bytes_from_ptr expects pointer as first argument".to_string()))?;
+                let len_val = self.evaluate(&args[1])?;
+                let len_val = try_value!(Ok(len_val));
+                let len = len_val.borrow().try_unwrap_uint64()
+                    .map_err(|_| InterpreterError::InternalError("bytes_from_ptr expects u64 length as second argument".to_string()))?;
+                match self.heap_manager.borrow().read_byte_range(addr, len as usize) {
+                    Some(data) => Ok(EvaluationResult::Value((Object::Bytes(Box::new(data))).into())),
+                    None => Err(InterpreterError::InternalError("Invalid memory access in bytes_from_ptr".to_string())),
+                }
+            }
+
+            BuiltinFunction::ReadLine | BuiltinFunction::ReadAll => {
+                let name = if matches!(func, BuiltinFunction::ReadLine) { "read_line" } else { "read_all" };
+                if !args.is_empty() {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes no arguments"),
+                        expected: 0,
+                        found: args.len(),
+                    });
+                }
+                let text = if matches!(func, BuiltinFunction::ReadLine) {
+                    crate::output::read_line_text()
+                } else {
+                    crate::output::read_all_text()
+                }
+                .map_err(|e| InterpreterError::panic(format!("{name}: {e}")))?;
+                Ok(EvaluationResult::Value((Object::String(text)).into()))
+            }
+
+            BuiltinFunction::Exec => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "exec takes 2 arguments (cmd, args)".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::Exec, "exec")?;
+                let cmd_val = self.evaluate(&args[0])?;
+                let cmd_val = try_value!(Ok(cmd_val));
+                let cmd = cmd_val.borrow().to_string_value(self.string_interner);
+                let list_val = self.evaluate(&args[1])?;
+                let list_val = try_value!(Ok(list_val));
+                let argv: Vec<String> = match &*list_val.borrow() {
+                    Object::Array(elems) => elems
+                        .iter()
+                        .map(|e| e.borrow().to_string_value(self.string_interner))
+                        .collect(),
+                    _ => {
+                        return Err(InterpreterError::InternalError(
+                            "exec expects a [str] array as second argument".to_string(),
+                        ))
+                    }
+                };
+                // Children never inherit stdin (`output()` wires it to
+                // /dev/null), so a command can't block on the
+                // interpreter's own input. Spawn failures (missing
+                // binary, permissions) panic like the file builtins.
+                let out = std::process::Command::new(&cmd)
+                    .args(&argv)
+                    .output()
+                    .map_err(|e| InterpreterError::panic(format!("exec(\"{cmd}\"): {e}")))?;
+                // Killed by a signal: no exit code to report.
+                let status = out.status.code().map(i64::from).unwrap_or(-1);
+                let tuple: Vec<RcObject> = vec![
+                    Rc::new(RefCell::new(Object::Int64(status))),
+                    Rc::new(RefCell::new(Object::String(String::from_utf8_lossy(&out.stdout).into_owned()))),
+                    Rc::new(RefCell::new(Object::String(String::from_utf8_lossy(&out.stderr).into_owned()))),
+                ];
+                Ok(EvaluationResult::Value((Object::Tuple(Box::new(tuple))).into()))
+            }
+
+            BuiltinFunction::HttpGet => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "http_get takes 1 argument (url)".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::Net, "http_get")?;
+                let url_val = self.evaluate(&args[0])?;
+                let url_val = try_value!(Ok(url_val));
+                let url = url_val.borrow().to_string_value(self.string_interner);
+                let (status, body) = super::http::get(&url)
+                    .map_err(|e| InterpreterError::panic(format!("http_get(\"{url}\"): {e}")))?;
+                let tuple: Vec<RcObject> = vec![
+                    Rc::new(RefCell::new(Object::UInt64(status))),
+                    Rc::new(RefCell::new(Object::String(body))),
+                ];
+                Ok(EvaluationResult::Value((Object::Tuple(Box::new(tuple))).into()))
+            }
+
+            BuiltinFunction::TcpListen | BuiltinFunction::TcpAccept | BuiltinFunction::TcpRead
+            | BuiltinFunction::TcpClose | BuiltinFunction::TcpLocalPort => {
+                let name = match func {
+                    BuiltinFunction::TcpListen => "tcp_listen",
+                    BuiltinFunction::TcpAccept => "tcp_accept",
+                    BuiltinFunction::TcpRead => "tcp_read",
+                    BuiltinFunction::TcpClose => "tcp_close",
+                    _ => "tcp_local_port",
+                };
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 1 argument"),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::Net, name)?;
+                let arg_val = self.evaluate(&args[0])?;
+                let arg_val = try_value!(Ok(arg_val));
+                let arg = arg_val.borrow().try_unwrap_uint64().map_err(InterpreterError::ObjectError)?;
+                let result = match func {
+                    BuiltinFunction::TcpListen => self.sockets.listen(arg).map(Object::UInt64),
+                    BuiltinFunction::TcpAccept => self.sockets.accept(arg).map(Object::UInt64),
+                    BuiltinFunction::TcpRead => self
+                        .sockets
+                        .read(arg)
+                        .map(|data| Object::String(String::from_utf8_lossy(&data).into_owned())),
+                    BuiltinFunction::TcpClose => self.sockets.close(arg).map(|()| Object::Unit),
+                    _ => self.sockets.local_port(arg).map(Object::UInt64),
+                };
+                let obj = result.map_err(|e| InterpreterError::panic(format!("{name}({arg}): {e}")))?;
+                Ok(EvaluationResult::Value(obj.into()))
+            }
+
+            BuiltinFunction::TcpWrite => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "tcp_write takes 2 arguments (conn, data)".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::Net, "tcp_write")?;
+                let conn_val = self.evaluate(&args[0])?;
+                let conn_val = try_value!(Ok(conn_val));
+                let conn = conn_val.borrow().try_unwrap_uint64().map_err(InterpreterError::ObjectError)?;
+                let data_val = self.evaluate(&args[1])?;
+                let data_val = try_value!(Ok(data_val));
+                let data = data_val.borrow().to_string_value(self.string_interner);
+                self.sockets
+                    .write(conn, data.as_bytes())
+                    .map_err(|e| InterpreterError::panic(format!("tcp_write({conn}): {e}")))?;
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            BuiltinFunction::JsonParse | BuiltinFunction::JsonStringify => {
+                let name = if matches!(func, BuiltinFunction::JsonParse) { "json_parse" } else { "json_stringify" };
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 1 argument"),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let result = if matches!(func, BuiltinFunction::JsonParse) {
+                    let text = v.borrow().to_string_value(self.string_interner);
+                    super::json::parse(&text, self.string_interner)
+                } else {
+                    super::json::stringify(&v.borrow(), self.string_interner, &self.struct_definitions)
+                        .map(Object::String)
+                };
+                let obj = result.map_err(|e| InterpreterError::panic(format!("{name}: {e}")))?;
+                Ok(EvaluationResult::Value(obj.into()))
+            }
+
+            BuiltinFunction::TypeOf | BuiltinFunction::Is(_) | BuiltinFunction::Downcast(_) => {
+                let name = match func {
+                    BuiltinFunction::TypeOf => "type_of",
+                    BuiltinFunction::Is(_) => "is",
+                    _ => "downcast",
+                };
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 1 argument"),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                match func {
+                    BuiltinFunction::TypeOf => {
+                        let type_name = v.borrow().type_name(self.string_interner);
+                        Ok(EvaluationResult::Value(Object::String(type_name).into()))
+                    }
+                    BuiltinFunction::Is(target) => {
+                        let matches = v.borrow().matches_type(target);
+                        Ok(EvaluationResult::Value(Object::Bool(matches).into()))
+                    }
+                    BuiltinFunction::Downcast(target) => {
+                        if !v.borrow().matches_type(target) {
+                            return Err(InterpreterError::panic(format!(
+                                "downcast: expected {}, found {}",
+                                format_type_decl_for_display(target, self.string_interner),
+                                v.borrow().type_name(self.string_interner)
+                            )));
+                        }
+                        Ok(EvaluationResult::Value(v.into()))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            BuiltinFunction::ToDict | BuiltinFunction::FromDict(_) => {
+                let name = if matches!(func, BuiltinFunction::ToDict) { "to_dict" } else { "from_dict" };
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 1 argument"),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let target = match func {
+                    BuiltinFunction::FromDict(TypeDecl::Struct(target, _) | TypeDecl::Identifier(target)) => target,
+                    BuiltinFunction::FromDict(other) => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "from_dict target is not a struct: {other:?}"
+                        )));
+                    }
+                    _ => {
+                        let dict = super::struct_dict::to_dict(&v.borrow()).ok_or_else(|| {
+                            InterpreterError::panic(format!(
+                                "to_dict: expected a struct, found {}",
+                                v.borrow().type_name(self.string_interner)
+                            ))
+                        })?;
+                        return Ok(EvaluationResult::Value(dict.into()));
+                    }
+                };
+                let converted = super::struct_dict::from_dict(
+                    &v.borrow(),
+                    *target,
+                    self.string_interner,
+                    &self.struct_definitions,
+                );
+                let option_sym = self.string_interner.get_or_intern("Option");
+                let (variant, values) = match converted {
+                    Some(obj) => ("Some", vec![Rc::new(RefCell::new(obj))]),
+                    None => ("None", Vec::new()),
+                };
+                let variant_name = self.string_interner.get_or_intern(variant);
+                let option = Object::EnumVariant {
+                    enum_name: option_sym,
+                    variant_name,
+                    values,
+                    type_args: vec![TypeDecl::Struct(*target, vec![])],
+                };
+                Ok(EvaluationResult::Value(option.into()))
+            }
+
+            BuiltinFunction::Clone => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "clone takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                Ok(EvaluationResult::Value(super::deep_clone::deep_clone(&v).into()))
+            }
+
+            BuiltinFunction::Seed => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "seed takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let n = v.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError("seed expects a u64 argument".to_string())
+                })?;
+                self.rng.seed(n);
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            BuiltinFunction::Random => {
+                if !args.is_empty() {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "random takes no arguments".to_string(),
+                        expected: 0,
+                        found: args.len(),
+                    });
+                }
+                Ok(EvaluationResult::Value(Object::Float64(self.rng.next_f64()).into()))
+            }
+
+            BuiltinFunction::RandomRange => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "random_range takes 2 arguments".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let lo = self.evaluate(&args[0])?;
+                let lo = try_value!(Ok(lo));
+                let hi = self.evaluate(&args[1])?;
+                let hi = try_value!(Ok(hi));
+                let empty_range = || {
+                    InterpreterError::panic("random_range: empty range (lo must be less than hi)")
+                };
+                // Both operands share a type (type-checker enforced);
+                // the draw is an offset into `[0, hi - lo)` computed
+                // in u64 so the full i64 span doesn't overflow.
+                let lo_borrow = lo.borrow();
+                if let Ok(l) = lo_borrow.try_unwrap_int64() {
+                    let h = hi.borrow().try_unwrap_int64().map_err(|_| {
+                        InterpreterError::InternalError(
+                            "random_range operands must agree on i64 / u64".to_string(),
+                        )
+                    })?;
+                    if l >= h {
+                        return Err(empty_range());
+                    }
+                    let offset = self.rng.next_below(h.wrapping_sub(l) as u64);
+                    return Ok(EvaluationResult::Value(
+                        Object::Int64(l.wrapping_add(offset as i64)).into(),
+                    ));
+                }
+                if let Ok(l) = lo_borrow.try_unwrap_uint64() {
+                    let h = hi.borrow().try_unwrap_uint64().map_err(|_| {
+                        InterpreterError::InternalError(
+                            "random_range operands must agree on i64 / u64".to_string(),
+                        )
+                    })?;
+                    if l >= h {
+                        return Err(empty_range());
+                    }
+                    let offset = self.rng.next_below(h - l);
+                    return Ok(EvaluationResult::Value(Object::UInt64(l + offset).into()));
+                }
+                Err(InterpreterError::InternalError(
+                    "random_range expects i64 or u64 operands".to_string(),
+                ))
+            }
+
+            BuiltinFunction::NowMillis | BuiltinFunction::Clock => {
+                if !args.is_empty() {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!(
+                            "{} takes no arguments",
+                            if matches!(func, BuiltinFunction::NowMillis) { "now_millis" } else { "clock" }
+                        ),
+                        expected: 0,
+                        found: args.len(),
+                    });
+                }
+                let name = if matches!(func, BuiltinFunction::Clock) { "clock" } else { "now_millis" };
+                self.require_capability(Capability::Clock, name)?;
+                if let Some(millis) = self.virtual_millis {
+                    let value = if matches!(func, BuiltinFunction::Clock) {
+                        Object::Float64(millis as f64 / 1000.0)
+                    } else {
+                        Object::UInt64(millis)
+                    };
+                    return Ok(EvaluationResult::Value(value.into()));
+                }
+                if matches!(func, BuiltinFunction::Clock) {
+                    let secs = self.start_instant.elapsed().as_secs_f64();
+                    return Ok(EvaluationResult::Value(Object::Float64(secs).into()));
+                }
+                // A clock set before the epoch reads as 0 rather than
+                // failing the run.
+                let millis = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                Ok(EvaluationResult::Value(Object::UInt64(millis).into()))
+            }
+
+            BuiltinFunction::Sleep => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "sleep takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                self.require_capability(Capability::Clock, "sleep")?;
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let ms = v.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError("sleep expects a u64 argument".to_string())
+                })?;
+                match &mut self.virtual_millis {
+                    Some(millis) => *millis = millis.saturating_add(ms),
+                    None => std::thread::sleep(std::time::Duration::from_millis(ms)),
+                }
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            BuiltinFunction::FormatTimestamp => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "format_timestamp takes 2 arguments (epoch, fmt)".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let epoch_val = self.evaluate(&args[0])?;
+                let epoch_val = try_value!(Ok(epoch_val));
+                let epoch = epoch_val.borrow().try_unwrap_int64().map_err(InterpreterError::ObjectError)?;
+                let fmt_val = self.evaluate(&args[1])?;
+                let fmt_val = try_value!(Ok(fmt_val));
+                let fmt = fmt_val.borrow().to_string_value(self.string_interner);
+                let text = super::timestamp::format(epoch, &fmt)
+                    .map_err(|e| InterpreterError::panic(format!("format_timestamp({epoch}, \"{fmt}\"): {e}")))?;
+                Ok(EvaluationResult::Value((Object::String(text)).into()))
+            }
+
+            BuiltinFunction::Spawn => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "spawn takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let task = match &*v.borrow() {
+                    Object::Generator(task) => task.clone(),
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "spawn expects a Task, got {}", other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                self.tasks.push_back(task);
+                Ok(EvaluationResult::Value((Object::Unit).into()))
+            }
+
+            // `yield_now()` as a statement of a task body suspends the
+            // task (see `evaluate_statements`); everywhere else it runs
+            // the other queued tasks once.
+            BuiltinFunction::YieldNow => {
+                if !args.is_empty() {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "yield_now takes no arguments".to_string(),
+                        expected: 0,
+                        found: args.len(),
+                    });
+                }
+                let ran = self.run_tasks_once()?;
+                Ok(EvaluationResult::Value((Object::Bool(ran)).into()))
+            }
+
+            BuiltinFunction::ParseU64 | BuiltinFunction::ParseI64 => {
+                let is_u64 = matches!(func, BuiltinFunction::ParseU64);
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!(
+                            "{} takes 1 argument",
+                            if is_u64 { "parse_u64" } else { "parse_i64" }
+                        ),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let text = v.borrow().to_string_value(self.string_interner);
+                // Rust's integer parse already matches the contract: an
+                // optional sign, then ASCII digits, and no overflow.
+                let (parsed, inner) = if is_u64 {
+                    (text.parse::<u64>().ok().map(Object::UInt64), TypeDecl::UInt64)
+                } else {
+                    (text.parse::<i64>().ok().map(Object::Int64), TypeDecl::Int64)
+                };
+                let option_sym = self.string_interner.get_or_intern("Option");
+                let (variant, values) = match parsed {
+                    Some(obj) => ("Some", vec![Rc::new(RefCell::new(obj))]),
+                    None => ("None", Vec::new()),
+                };
+                let variant_name = self.string_interner.get_or_intern(variant);
+                let option = Object::EnumVariant {
+                    enum_name: option_sym,
+                    variant_name,
+                    values,
+                    type_args: vec![inner],
+                };
+                Ok(EvaluationResult::Value(option.into()))
+            }
+
+            BuiltinFunction::ToHex => {
+                if args.len() != 1 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "to_hex takes 1 argument".to_string(),
+                        expected: 1,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let n = v.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError("to_hex expects a u64 argument".to_string())
+                })?;
+                Ok(EvaluationResult::Value(Object::String(format!("{n:x}")).into()))
+            }
+
+            BuiltinFunction::FormatRadix => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "format_radix takes 2 arguments".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let base = self.evaluate(&args[1])?;
+                let base = try_value!(Ok(base));
+                let base = base.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError("format_radix expects a u64 base".to_string())
+                })?;
+                if !(2..=36).contains(&base) {
+                    return Err(InterpreterError::panic(format!(
+                        "format_radix: base must be between 2 and 36, got {base}"
+                    )));
+                }
+                let (negative, magnitude) = match &*v.borrow() {
+                    Object::UInt64(n) => (false, *n),
+                    Object::Int64(n) => (*n < 0, n.unsigned_abs()),
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "format_radix expects an i64 or u64, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                Ok(EvaluationResult::Value(
+                    Object::String(format_radix(negative, magnitude, base)).into(),
+                ))
+            }
+
+            BuiltinFunction::Format => {
+                if args.len() != 3 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "format takes 3 arguments".to_string(),
+                        expected: 3,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let width = self.evaluate(&args[1])?;
+                let width = try_value!(Ok(width));
+                let width = width.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError("format expects a u64 width".to_string())
+                })?;
+                let pad = self.evaluate(&args[2])?;
+                let pad = try_value!(Ok(pad));
+                let pad = match &*pad.borrow() {
+                    Object::UInt32(c) => char::from_u32(*c).ok_or_else(|| {
+                        InterpreterError::panic(format!("format: {c:#x} is not a valid char"))
+                    })?,
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "format expects a char pad, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                // Render through Rust's own formatting rather than the
+                // `print` path so the text never depends on a locale.
+                let text = match &*v.borrow() {
+                    Object::Int64(n) => n.to_string(),
+                    Object::UInt64(n) => n.to_string(),
+                    Object::Float64(x) => x.to_string(),
+                    other @ (Object::String(_) | Object::ConstString(_)) => {
+                        other.to_string_value(self.string_interner)
+                    }
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "format expects an i64, u64, f64 or str value, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                Ok(EvaluationResult::Value(Object::String(pad_to_width(&text, width, pad)).into()))
+            }
+
+            BuiltinFunction::FormatFixed | BuiltinFunction::FormatExp => {
+                let name = if matches!(func, BuiltinFunction::FormatFixed) { "format_fixed" } else { "format_exp" };
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes 2 arguments"),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let v = self.evaluate(&args[0])?;
+                let v = try_value!(Ok(v));
+                let x = match &*v.borrow() {
+                    Object::Float64(x) => *x,
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "{name} expects an f64 value, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                let digits = self.evaluate(&args[1])?;
+                let digits = try_value!(Ok(digits));
+                let digits = digits.borrow().try_unwrap_uint64().map_err(|_| {
+                    InterpreterError::InternalError(format!("{name} expects a u64 digit count"))
+                })?;
+                // Rust's formatter takes any `usize` precision and fills
+                // the extra digits exactly; a cap keeps a stray huge count
+                // from allocating gigabytes.
+                if digits > MAX_FORMAT_DIGITS {
+                    return Err(InterpreterError::panic(format!(
+                        "{name}: at most {MAX_FORMAT_DIGITS} digits, got {digits}"
+                    )));
+                }
+                let digits = digits as usize;
+                let text = if matches!(func, BuiltinFunction::FormatFixed) {
+                    format!("{x:.digits$}")
+                } else {
+                    format!("{x:.digits$e}")
+                };
+                Ok(EvaluationResult::Value(Object::String(text).into()))
+            }
+
+            BuiltinFunction::Sort => {
+                if args.len() != 2 {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: "sort takes 2 arguments".to_string(),
+                        expected: 2,
+                        found: args.len(),
+                    });
+                }
+                let arr = self.evaluate(&args[0])?;
+                let arr = try_value!(Ok(arr));
+                let less = self.evaluate(&args[1])?;
+                let less = try_value!(Ok(less));
+                let items = match &*arr.borrow() {
+                    Object::Array(items) => items.to_vec(),
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "sort expects an array, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                let sorted = self.merge_sort(items, &less)?;
+                Ok(EvaluationResult::Value(Object::Array(Box::new(sorted)).into()))
+            }
+
+            BuiltinFunction::BinarySearch | BuiltinFunction::LowerBound
+            | BuiltinFunction::Reverse | BuiltinFunction::MinOf
+            | BuiltinFunction::MaxOf | BuiltinFunction::SumOf => {
+                let (name, expected) = match func {
+                    BuiltinFunction::BinarySearch => ("binary_search", 2usize),
+                    BuiltinFunction::LowerBound => ("lower_bound", 2),
+                    BuiltinFunction::Reverse => ("reverse", 1),
+                    BuiltinFunction::MinOf => ("min_of", 1),
+                    BuiltinFunction::MaxOf => ("max_of", 1),
+                    _ => ("sum_of", 1),
+                };
+                if args.len() != expected {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes {expected} argument(s)"),
+                        expected,
+                        found: args.len(),
+                    });
+                }
+                let arr = self.evaluate(&args[0])?;
+                let arr = try_value!(Ok(arr));
+                let items = match &*arr.borrow() {
+                    Object::Array(items) => items.to_vec(),
+                    other => {
+                        return Err(InterpreterError::InternalError(format!(
+                            "{name} expects an array, got {}",
+                            other.type_name(self.string_interner)
+                        )))
+                    }
+                };
+                match func {
+                    BuiltinFunction::BinarySearch | BuiltinFunction::LowerBound => {
+                        let needle = self.evaluate(&args[1])?;
+                        let needle = try_value!(Ok(needle));
+                        let needle = needle.borrow().clone();
+                        let index = self.lower_bound(&items, &needle)?;
+                        if matches!(func, BuiltinFunction::LowerBound) {
+                            return Ok(EvaluationResult::Value(Object::UInt64(index as u64).into()));
+                        }
+                        let found = match items.get(index) {
+                            Some(item) => !self.less_than(&needle, &item.borrow())?,
+                            None => false,
+                        };
+                        let option_sym = self.string_interner.get_or_intern("Option");
+                        let (variant, values) = if found {
+                            ("Some", vec![Rc::new(RefCell::new(Object::UInt64(index as u64)))])
+                        } else {
+                            ("None", Vec::new())
+                        };
+                        let variant_name = self.string_interner.get_or_intern(variant);
+                        let option = Object::EnumVariant {
+                            enum_name: option_sym,
+                            variant_name,
+                            values,
+                            type_args: vec![TypeDecl::UInt64],
+                        };
+                        Ok(EvaluationResult::Value(option.into()))
+                    }
+                    BuiltinFunction::Reverse => {
+                        let mut reversed = items;
+                        reversed.reverse();
+                        Ok(EvaluationResult::Value(Object::Array(Box::new(reversed)).into()))
+                    }
+                    BuiltinFunction::MinOf | BuiltinFunction::MaxOf => {
+                        let mut items = items.into_iter();
+                        let Some(first) = items.next() else {
+                            return Err(InterpreterError::panic(format!("{name} of an empty array")));
+                        };
+                        let mut best = first.borrow().clone();
+                        for item in items {
+                            let item = item.borrow().clone();
+                            let better = if matches!(func, BuiltinFunction::MinOf) {
+                                self.less_than(&item, &best)?
+                            } else {
+                                self.less_than(&best, &item)?
+                            };
+                            if better {
+                                best = item;
+                            }
+                        }
+                        Ok(EvaluationResult::Value(best.into()))
+                    }
+                    _ => {
+                        let mut items = items.into_iter();
+                        // An empty slice has no element to take the type
+                        // from; its sum is the unsigned zero.
+                        let Some(first) = items.next() else {
+                            return Ok(EvaluationResult::Value(Object::UInt64(0).into()));
+                        };
+                        let mut total = first.borrow().clone();
+                        for item in items {
+                            total = self.evaluate_add(&total, &item.borrow())?;
+                        }
+                        Ok(EvaluationResult::Value(total.into()))
+                    }
+                }
+            }
+
+            BuiltinFunction::Matches | BuiltinFunction::Find | BuiltinFunction::ReplacePattern => {
+                let (name, expected) = match func {
+                    BuiltinFunction::Matches => ("matches", 2usize),
+                    BuiltinFunction::Find => ("find", 2),
+                    _ => ("replace_pattern", 3),
+                };
+                if args.len() != expected {
+                    return Err(InterpreterError::FunctionParameterMismatch {
+                        message: format!("{name} takes {expected} arguments"),
+                        expected,
+                        found: args.len(),
+                    });
+                }
+                let mut texts = Vec::with_capacity(expected);
+                for arg in args {
+                    let v = self.evaluate(arg)?;
+                    let v = try_value!(Ok(v));
+                    texts.push(v.borrow().to_string_value(self.string_interner));
+                }
+                let (src, pattern) = (texts[0].as_bytes(), texts[1].as_bytes());
+                let pattern_error = |e: String| InterpreterError::panic(format!("{name}: {e}"));
+                if matches!(func, BuiltinFunction::ReplacePattern) {
+                    let out = lua_pattern::replace(src, pattern, texts[2].as_bytes()).map_err(pattern_error)?;
+                    let out = String::from_utf8_lossy(&out).into_owned();
+                    return Ok(EvaluationResult::Value(Object::String(out).into()));
+                }
+                let found = lua_pattern::find(src, pattern).map_err(pattern_error)?;
+                if matches!(func, BuiltinFunction::Matches) {
+                    return Ok(EvaluationResult::Value(Object::Bool(found.is_some()).into()));
+                }
+                let option_sym = self.string_interner.get_or_intern("Option");
+                let (variant, values) = match found {
+                    Some(m) => {
+                        let (start, end) = m.first_capture();
+                        let text = String::from_utf8_lossy(&src[start..end]).into_owned();
+                        ("Some", vec![Rc::new(RefCell::new(Object::String(text)))])
+                    }
+                    None => ("None", Vec::new()),
+                };
+                let variant_name = self.string_interner.get_or_intern(variant);
+                let option = Object::EnumVariant {
+                    enum_name: option_sym,
+                    variant_name,
+                    values,
+                    type_args: vec![TypeDecl::String],
+                };
+                Ok(EvaluationResult::Value(option.into()))
+            }
+
+            // Normally rejected by the type checker; module bodies are
+            // not re-checked, so an unguarded use in a core module
+            // still lands here.
+            BuiltinFunction::InlineLua => Err(InterpreterError::InternalError(
+                "lua!(...) is a backend-only construct: the interpreter cannot run inline Lua (guard it with @cfg(target = \"lua\"))".to_string(),
+            )),
+
             BuiltinFunction::Abs => {
                 if args.len() != 1 {
                     return Err(InterpreterError::FunctionParameterMismatch {
@@ -850,3 +1763,102 @@ impl EvaluationContext<'_> {
         }
     }
 }
+
+impl EvaluationContext<'_> {
+    /// Stable bottom-up merge sort driven by the user's `less(a, b)`.
+    /// Written out rather than using `slice::sort_by`, which needs a
+    /// total order (and may panic without one) and cannot stop on the
+    /// first error the comparator raises.
+    fn merge_sort(&mut self, mut items: Vec<RcObject>, less: &RcObject) -> Result<Vec<RcObject>, InterpreterError> {
+        let n = items.len();
+        let mut width = 1;
+        while width < n {
+            let mut merged = Vec::with_capacity(n);
+            let mut start = 0;
+            while start < n {
+                let mid = (start + width).min(n);
+                let end = (start + 2 * width).min(n);
+                let (mut i, mut j) = (start, mid);
+                while i < mid && j < end {
+                    // Take from the right run only when it is strictly
+                    // less, so equal elements keep their order.
+                    let args = vec![Value::from_rc(&items[j]), Value::from_rc(&items[i])];
+                    let right_first = self.call_closure_with_values(less, args)?;
+                    let right_first = right_first.borrow().try_unwrap_bool().map_err(|_| {
+                        InterpreterError::InternalError("sort comparator must return bool".to_string())
+                    })?;
+                    if right_first {
+                        merged.push(items[j].clone());
+                        j += 1;
+                    } else {
+                        merged.push(items[i].clone());
+                        i += 1;
+                    }
+                }
+                merged.extend_from_slice(&items[i..mid]);
+                merged.extend_from_slice(&items[j..end]);
+                start = end;
+            }
+            items = merged;
+            width *= 2;
+        }
+        Ok(items)
+    }
+
+    /// `a < b` with the language's own comparison rules.
+    fn less_than(&self, a: &Object, b: &Object) -> Result<bool, InterpreterError> {
+        self.evaluate_lt(a, b)?
+            .try_unwrap_bool()
+            .map_err(|_| InterpreterError::InternalError("`<` did not produce a bool".to_string()))
+    }
+
+    /// First index in the ascending `items` whose element is not less
+    /// than `needle` (`items.len()` when there is none).
+    fn lower_bound(&self, items: &[RcObject], needle: &Object) -> Result<usize, InterpreterError> {
+        let (mut lo, mut hi) = (0, items.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.less_than(&items[mid].borrow(), needle)? {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(lo)
+    }
+}
+
+/// Digits of `magnitude` in `base` (2..=36, lowercase), with a leading
+/// `-` when `negative`.
+fn format_radix(negative: bool, mut magnitude: u64, base: u64) -> String {
+    let mut digits = Vec::new();
+    loop {
+        let d = (magnitude % base) as u32;
+        digits.push(char::from_digit(d, base as u32).unwrap_or('?'));
+        magnitude /= base;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if negative {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+/// Largest digit count `format_fixed` / `format_exp` accept.
+const MAX_FORMAT_DIGITS: u64 = 1000;
+
+/// Right-align `text` in `width` characters, filling with `pad`. A
+/// `'0'` fill goes after a leading `-` so `-42` pads to `-0042`.
+fn pad_to_width(text: &str, width: u64, pad: char) -> String {
+    let len = text.chars().count() as u64;
+    if len >= width {
+        return text.to_string();
+    }
+    let fill: String = std::iter::repeat_n(pad, (width - len) as usize).collect();
+    match text.strip_prefix('-') {
+        Some(digits) if pad == '0' => format!("-{fill}{digits}"),
+        _ => format!("{fill}{text}"),
+    }
+}
@@ -7,6 +7,7 @@ use string_interner::{DefaultStringInterner, DefaultSymbol};
 use crate::object::{Object, RcObject};
 use crate::error::InterpreterError;
 use crate::try_value;
+use crate::try_value_v;
 use super::{EnumRegistryEntry, EnumRegistryVariant, EvaluationContext, EvaluationResult, StructRegistryEntry};
 use std::collections::HashMap as HashMapStd;
 
@@ -43,6 +44,18 @@ fn primitive_target_symbol(
     interner.get(name)
 }
 
+/// Runtime counterpart of `BuiltinMethod::receiver_key`: the
+/// registry key for a receiver value, or `None` when the value's type
+/// has no builtin methods.
+fn builtin_receiver_key(obj: &Object) -> Option<TypeDecl> {
+    match obj {
+        Object::ConstString(_) | Object::String(_) => Some(TypeDecl::String),
+        Object::Array(_) => Some(TypeDecl::Array(vec![], 0)),
+        Object::Bytes(_) => Some(TypeDecl::Bytes),
+        _ => None,
+    }
+}
+
 /// Walk the struct field type-decls looking for `Generic(P)`
 /// occurrences and bind each generic parameter to the runtime
 /// type of the matching field. Returns `type_args` in the order
@@ -234,8 +247,18 @@ impl EvaluationContext<'_> {
                     EvaluationResult::Value(v) => v,
                     _ => return Ok(WritebackTarget::None),
                 };
+                let obj_rc = obj_value.clone_to_rc();
                 let index = match idx_value {
                     crate::value::Value::UInt64(n) => n as usize,
+                    // Negative indices count from the end, as for a
+                    // plain `arr[i]` read.
+                    crate::value::Value::Int64(n) if n < 0 => {
+                        let len = match &*obj_rc.borrow() {
+                            Object::Array(elements) => elements.len(),
+                            _ => return Ok(WritebackTarget::None),
+                        };
+                        self.resolve_array_index(&idx_value, len)?
+                    }
                     crate::value::Value::Int64(n) => n as usize,
                     crate::value::Value::UInt8(n) => n as usize,
                     crate::value::Value::UInt16(n) => n as usize,
@@ -245,10 +268,7 @@ impl EvaluationContext<'_> {
                     crate::value::Value::Int32(n) => n as usize,
                     _ => return Ok(WritebackTarget::None),
                 };
-                Ok(WritebackTarget::ArrayElement {
-                    obj: obj_value.clone_to_rc(),
-                    index,
-                })
+                Ok(WritebackTarget::ArrayElement { obj: obj_rc, index })
             }
             _ => Ok(WritebackTarget::None),
         }
@@ -338,7 +358,7 @@ impl EvaluationContext<'_> {
         }
     }
 
-    pub(super) fn call_method(&mut self, method: Rc<MethodFunction>, self_obj: RcObject, args: Vec<RcObject>) -> Result<EvaluationResult, InterpreterError> {
+    pub(super) fn call_method(&mut self, method: Rc<MethodFunction>, self_obj: RcObject, args: Vec<crate::value::Value>) -> Result<EvaluationResult, InterpreterError> {
         // Create new scope for method execution
         self.environment.enter_block();
 
@@ -383,7 +403,7 @@ impl EvaluationContext<'_> {
                     param_index
                 };
                 if arg_idx < args.len() {
-                    self.environment.set_val(*param_symbol, args[arg_idx].clone().into());
+                    self.environment.set_val(*param_symbol, args[arg_idx].clone());
                 }
             }
             param_index += 1;
@@ -404,14 +424,14 @@ impl EvaluationContext<'_> {
         // anyway, but we don't want to mask the original error).
         let result = match result {
             Ok(EvaluationResult::Value(v)) => {
-                if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, v.clone_to_rc()) {
+                if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, v.clone()) {
                     self.environment.exit_block();
                     return Err(e);
                 }
                 Ok(EvaluationResult::Value(v))
             }
             Ok(EvaluationResult::Return(v)) => {
-                let ret = v.clone().map(|val| val.into_rc()).unwrap_or_else(|| Rc::new(RefCell::new(Object::Unit)));
+                let ret = v.clone().unwrap_or(crate::value::Value::Unit);
                 if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, ret) {
                     self.environment.exit_block();
                     return Err(e);
@@ -477,12 +497,12 @@ impl EvaluationContext<'_> {
         &mut self,
         fn_name: DefaultSymbol,
         clauses: &[ExprRef],
-        return_value: RcObject,
+        return_value: crate::value::Value,
     ) -> Result<(), InterpreterError> {
         if !self.contract_mode.check_post || clauses.is_empty() {
             return Ok(());
         }
-        self.environment.set_val(self.result_symbol, (return_value).into());
+        self.environment.set_val(self.result_symbol, return_value);
         for (idx, cond) in clauses.iter().enumerate() {
             let cond_res = self.evaluate(cond)?;
             let cond_obj = self.unwrap_value(cond_res)?;
@@ -499,7 +519,7 @@ impl EvaluationContext<'_> {
     }
 
     /// Call an associated method (without self parameter)
-    pub(super) fn call_associated_method(&mut self, method: Rc<MethodFunction>, args: Vec<RcObject>) -> Result<EvaluationResult, InterpreterError> {
+    pub(super) fn call_associated_method(&mut self, method: Rc<MethodFunction>, args: Vec<crate::value::Value>) -> Result<EvaluationResult, InterpreterError> {
         // Create new scope for method execution
         self.environment.enter_block();
 
@@ -526,7 +546,7 @@ impl EvaluationContext<'_> {
 
             let arg_index = if skip_self { param_index - 1 } else { param_index };
             if arg_index < args.len() {
-                self.environment.set_val(*param_symbol, args[arg_index].clone().into());
+                self.environment.set_val(*param_symbol, args[arg_index].clone());
             }
             param_index += 1;
         }
@@ -543,14 +563,14 @@ impl EvaluationContext<'_> {
 
         let result = match result {
             Ok(EvaluationResult::Value(v)) => {
-                if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, v.clone_to_rc()) {
+                if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, v.clone()) {
                     self.environment.exit_block();
                     return Err(e);
                 }
                 Ok(EvaluationResult::Value(v))
             }
             Ok(EvaluationResult::Return(v)) => {
-                let ret = v.clone().map(|val| val.into_rc()).unwrap_or_else(|| Rc::new(RefCell::new(Object::Unit)));
+                let ret = v.clone().unwrap_or(crate::value::Value::Unit);
                 if let Err(e) = self.evaluate_ensures_clauses(method.name, &method.ensures, ret) {
                     self.environment.exit_block();
                     return Err(e);
@@ -575,16 +595,47 @@ impl EvaluationContext<'_> {
             .ok_or_else(|| InterpreterError::InternalError("Invalid method code reference".to_string()))?;
 
         // Execute the method body
-        match stmt {
+        let result = match stmt {
             frontend::ast::Stmt::Expression(expr_ref) => {
-                if let Some(Expr::Block(statements)) = self.expr_pool.get(&expr_ref) {
-                    self.evaluate_block(&statements)
+                let pool = self.expr_pool;
+                if let Some(statements) = pool.stmt_list_of(&expr_ref) {
+                    self.in_call_frame(|this| this.evaluate_block(statements))
                 } else {
                     // Single expression method body
-                    self.evaluate(&expr_ref)
+                    self.in_call_frame(|this| this.evaluate(&expr_ref))
                 }
             }
             _ => Err(InterpreterError::InternalError(format!("evaluate_method: unexpected method body type: {stmt:?}")))
+        };
+        result.map_err(|e| self.panic_frame(e, method.name))
+    }
+
+    /// Run a function or method body one call level deeper. Past
+    /// `call_depth_limit` the call fails instead, so a sandboxed run
+    /// gives up on deep recursion before it exhausts the host stack.
+    pub(super) fn in_call_frame<T>(
+        &mut self,
+        body: impl FnOnce(&mut Self) -> Result<T, InterpreterError>,
+    ) -> Result<T, InterpreterError> {
+        if self.call_depth_limit.is_some_and(|limit| self.call_depth >= limit) {
+            return Err(InterpreterError::InternalError("Call depth limit reached".to_string()));
+        }
+        self.call_depth += 1;
+        let result = body(self);
+        self.call_depth -= 1;
+        result
+    }
+
+    /// Append the frame `name` to a propagating panic's backtrace.
+    /// Called at each function / method body boundary; the symbol
+    /// is only resolved when an error is actually unwinding.
+    pub(super) fn panic_frame(&self, err: InterpreterError, name: DefaultSymbol) -> InterpreterError {
+        match err {
+            InterpreterError::Panic { .. } => {
+                let name = self.string_interner.resolve(name).unwrap_or("<unknown>");
+                err.with_frame(name)
+            }
+            other => other,
         }
     }
 
@@ -600,10 +651,11 @@ impl EvaluationContext<'_> {
             .lookup_function_qualified(None, *name)
             .or_else(|| self.function.get::<DefaultSymbol>(name).cloned());
         if let Some(func) = resolved {
-            let args = self.expr_pool.get(args)
+            let pool = self.expr_pool;
+            let args_type = pool.expr_type(args)
                 .ok_or_else(|| InterpreterError::InternalError("Invalid arguments reference".to_string()))?;
-            match args {
-                Expr::ExprList(args) => {
+            match (args_type, pool.expr_list_of(args)) {
+                (ExprType::ExprList, Some(args)) => {
                     if args.len() != func.parameter.len() {
                         return Err(
                             InterpreterError::FunctionParameterMismatch {
@@ -793,14 +845,54 @@ impl EvaluationContext<'_> {
         }
     }
 
+    /// Call a closure value with already-evaluated arguments, for
+    /// builtins that take a function argument (`sort`). The type
+    /// checker has matched the arity, so only the body can fail.
+    pub(super) fn call_closure_with_values(
+        &mut self,
+        callee: &RcObject,
+        args: Vec<crate::value::Value>,
+    ) -> Result<RcObject, InterpreterError> {
+        let (params, body, captures) = match &*callee.borrow() {
+            Object::Closure { params, body, captures, .. } => (params.clone(), *body, captures.clone()),
+            _ => return Err(InterpreterError::InternalError(
+                "call_closure_with_values: callee was not a closure".to_string(),
+            )),
+        };
+        self.environment.enter_block();
+        for (name, val) in &captures {
+            self.environment
+                .set_val(*name, crate::value::Value::from_rc(val));
+        }
+        for ((param_sym, _), arg_val) in params.iter().zip(args) {
+            self.environment.set_val(*param_sym, arg_val);
+        }
+        let body_expr = self.expr_pool.get(&body).ok_or_else(|| {
+            InterpreterError::InternalError("closure body ExprRef not in pool".to_string())
+        })?;
+        let result = match body_expr {
+            Expr::Block(stmts) => self.evaluate_block(&stmts),
+            _ => self.evaluate(&body),
+        };
+        self.environment.exit_block();
+        match result? {
+            EvaluationResult::Value(v) | EvaluationResult::Return(Some(v)) => Ok(v.into_rc()),
+            EvaluationResult::Return(None) => Ok(crate::value::Value::Unit.into_rc()),
+            _ => Err(InterpreterError::InternalError(
+                "closure body produced a control-flow value".to_string(),
+            )),
+        }
+    }
+
     fn evaluate_indirect_call(
         &mut self,
         callee: RcObject,
         callee_name: &DefaultSymbol,
         args: &ExprRef,
     ) -> Result<EvaluationResult, InterpreterError> {
-        let args_list = match self.expr_pool.get(args) {
-            Some(Expr::ExprList(args)) => args,
+        let pool = self.expr_pool;
+        let args_list = match (pool.expr_type(args), pool.expr_list_of(args)) {
+            (Some(ExprType::ExprList), Some(args)) => args,
             _ => return Err(InterpreterError::InternalError(
                 "evaluate_indirect_call: expected ExprList".to_string(),
             )),
@@ -839,12 +931,12 @@ impl EvaluationContext<'_> {
         // Evaluate args in caller scope FIRST — they may reference
         // bindings that aren't in the closure's capture set.
         let mut evaluated: Vec<crate::value::Value> = Vec::with_capacity(args_list.len());
-        for arg in &args_list {
+        for arg in args_list {
             let v = self.evaluate(arg)?;
             let v = match v {
                 EvaluationResult::Value(v) => v,
                 EvaluationResult::Return(_)
-                | EvaluationResult::Break(_)
+                | EvaluationResult::Break(..)
                 | EvaluationResult::Continue(_)
                 | EvaluationResult::None => {
                     return Err(InterpreterError::InternalError(
@@ -939,6 +1031,13 @@ impl EvaluationContext<'_> {
             return Ok(EvaluationResult::Value((Object::Bool(is_null)).into()));
         }
 
+        // `gen.next()` runs a generator body up to its next `yield`.
+        if let Object::Generator(generator) = &*obj_borrowed {
+            let generator = generator.clone();
+            drop(obj_borrowed);
+            return Ok(EvaluationResult::Value(self.resume_generator(&generator)?));
+        }
+
         // Step B of extension-trait support: dispatch through the
         // user-registered method registry first when the receiver is
         // a primitive. Mirrors what `Object::Struct { type_name, .. }`
@@ -962,136 +1061,35 @@ impl EvaluationContext<'_> {
                 let mut arg_values = Vec::new();
                 for arg in args {
                     let arg_val = self.evaluate(arg)?;
-                    let arg_val = try_value!(Ok(arg_val));
+                    let arg_val = try_value_v!(Ok(arg_val));
                     arg_values.push(arg_val);
                 }
                 return self.call_method(method_func, obj_val, arg_values);
             }
         }
 
-        match &*obj_borrowed {
-            Object::ConstString(_) | Object::String(_) => {
-                // Handle built-in String methods
-                match method_name {
-                    "len" => {
-                        // String.len() method - no arguments required, returns u64
-                        if !args.is_empty() {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.len() method takes no arguments, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        // Get the actual string value regardless of internal representation
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-                        let len = string_value.len() as u64;
-
-                        Ok(EvaluationResult::Value((Object::UInt64(len)).into()))
-                    }
-                    "contains" => {
-                        if args.len() != 1 {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.contains() method takes 1 argument, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-
-                        let arg_value = self.evaluate(&args[0])?;
-                        let arg_obj = try_value!(Ok(arg_value));
-                        let arg_borrowed = arg_obj.borrow();
-                        let arg_string = arg_borrowed.to_string_value(self.string_interner);
-
-                        let contains = string_value.contains(&arg_string);
-                        Ok(EvaluationResult::Value((Object::Bool(contains)).into()))
-                    }
-                    "concat" => {
-                        if args.len() != 1 {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.concat() method takes 1 argument, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-
-                        let arg_value = self.evaluate(&args[0])?;
-                        let arg_obj = try_value!(Ok(arg_value));
-                        let arg_borrowed = arg_obj.borrow();
-                        let arg_string = arg_borrowed.to_string_value(self.string_interner);
-
-                        let concatenated = format!("{}{}", string_value, arg_string);
-                        // Return as dynamic String, not interned - this is the key improvement
-                        Ok(EvaluationResult::Value((Object::String(concatenated)).into()))
-                    }
-                    "trim" => {
-                        if !args.is_empty() {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.trim() method takes no arguments, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-                        let trimmed = string_value.trim().to_string();
-                        // Return as dynamic String, not interned
-                        Ok(EvaluationResult::Value((Object::String(trimmed)).into()))
-                    }
-                    "to_upper" => {
-                        if !args.is_empty() {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.to_upper() method takes no arguments, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-                        let upper = string_value.to_uppercase();
-                        // Return as dynamic String, not interned
-                        Ok(EvaluationResult::Value((Object::String(upper)).into()))
-                    }
-                    "to_lower" => {
-                        if !args.is_empty() {
-                            return Err(InterpreterError::InternalError(format!(
-                                "String.to_lower() method takes no arguments, but {} provided",
-                                args.len()
-                            )));
-                        }
-
-                        let string_value = obj_borrowed.to_string_value(self.string_interner);
-                        let lower = string_value.to_lowercase();
-                        // Return as dynamic String, not interned
-                        Ok(EvaluationResult::Value((Object::String(lower)).into()))
-                    }
-                    _ => {
-                        Err(InterpreterError::InternalError(format!(
-                            "Method '{method_name}' not found for String type"
-                        )))
-                    }
-                }
-            }
-            Object::Array(elements) => {
-                // Handle built-in Array methods
-                match method_name {
-                    "len" => {
-                        // Array.len() method - no arguments required, returns u64
-                        if !args.is_empty() {
-                            return Err(InterpreterError::InternalError(format!(
-                                "Array.len() method takes no arguments, but {} provided",
-                                args.len()
-                            )));
-                        }
-                        let len = elements.len() as u64;
-                        Ok(EvaluationResult::Value((Object::UInt64(len)).into()))
-                    }
-                    _ => {
-                        Err(InterpreterError::InternalError(format!(
-                            "Method '{method_name}' not found for Array type"
-                        )))
-                    }
-                }
+        // Builtin receiver methods, from the table the type checker's
+        // `builtin_methods` registry is built from.
+        if let Some(key) = builtin_receiver_key(&obj_borrowed) {
+            if let Some(builtin) = self.builtin_methods.get(&(key, *method)).cloned() {
+                drop(obj_borrowed);
+                return self.execute_builtin_method(&obj_val, &builtin, args);
             }
+        }
+
+        match &*obj_borrowed {
+            // Builtin str / array / bytes methods were dispatched
+            // above through `builtin_methods`; reaching here means
+            // the name isn't one of them.
+            Object::ConstString(_) | Object::String(_) => Err(InterpreterError::InternalError(format!(
+                "Method '{method_name}' not found for String type"
+            ))),
+            Object::Array(_) => Err(InterpreterError::InternalError(format!(
+                "Method '{method_name}' not found for Array type"
+            ))),
+            Object::Bytes(_) => Err(InterpreterError::InternalError(format!(
+                "Method '{method_name}' not found for bytes type"
+            ))),
             // NOTE: hardcoded `Object::Int64.abs()` /
             // `Object::Float64.{abs,sqrt}` arms lived here before
             // Step F. The Step B primitive-receiver dispatch path
@@ -1125,7 +1123,7 @@ impl EvaluationContext<'_> {
                     let mut arg_values = Vec::new();
                     for arg in args {
                         let arg_val = self.evaluate(arg)?;
-                        let arg_val = try_value!(Ok(arg_val));
+                        let arg_val = try_value_v!(Ok(arg_val));
                         arg_values.push(arg_val);
                     }
 
@@ -1174,7 +1172,7 @@ impl EvaluationContext<'_> {
                     let mut arg_values = Vec::new();
                     for arg in args {
                         let arg_val = self.evaluate(arg)?;
-                        let arg_val = try_value!(Ok(arg_val));
+                        let arg_val = try_value_v!(Ok(arg_val));
                         arg_values.push(arg_val);
                     }
                     self.call_method(method_func, obj_val, arg_values)
@@ -1199,11 +1197,11 @@ impl EvaluationContext<'_> {
 
         for (field_name, field_expr) in fields {
             // Handle null expressions specially in struct literals
-            let expr = self.expr_pool.get(field_expr)
+            let expr_type = self.expr_pool.expr_type(field_expr)
                 .ok_or_else(|| InterpreterError::InternalError(format!("Unbound error: {:?}", field_expr)))?;
 
-            let field_value = match expr {
-                Expr::Null => {
+            let field_value = match expr_type {
+                ExprType::Null => {
                     // Use pre-created null object for struct fields
                     self.null_object.clone()
                 }
@@ -1271,8 +1269,7 @@ impl EvaluationContext<'_> {
         let mut arg_values = Vec::new();
         for arg_expr in args {
             let arg_value = self.evaluate(arg_expr)?;
-            let arg_obj = try_value!(Ok(arg_value));
-            arg_values.push(arg_obj);
+            arg_values.push(try_value_v!(Ok(arg_value)));
         }
 
         // Call the associated function as if it's a static method
@@ -1315,7 +1312,7 @@ impl EvaluationContext<'_> {
                 let v = match result {
                     EvaluationResult::Value(v) => v,
                     EvaluationResult::Return(_)
-                    | EvaluationResult::Break(_)
+                    | EvaluationResult::Break(..)
                     | EvaluationResult::Continue(_)
                     | EvaluationResult::None => {
                         return Err(InterpreterError::InternalError(
@@ -1327,10 +1324,27 @@ impl EvaluationContext<'_> {
             }
             return self.dispatch_extern_fn(&function, &arg_values).map(|v| v.into_rc());
         }
+        if Self::is_generator_function(&function) {
+            // Calling a generator function only captures its arguments;
+            // the body runs piecemeal under `next()`.
+            let mut arg_values: Vec<crate::value::Value> = Vec::with_capacity(args.len());
+            for arg_expr in args {
+                match self.evaluate(arg_expr)? {
+                    EvaluationResult::Value(v) => arg_values.push(v),
+                    _ => {
+                        return Err(InterpreterError::InternalError(
+                            "generator argument produced control-flow value".to_string(),
+                        ));
+                    }
+                }
+            }
+            return Ok(Self::make_generator(function, arg_values).into_rc());
+        }
+        let pool = self.expr_pool;
         let block = match self.stmt_pool.get(&function.code) {
             Some(Stmt::Expression(e)) => {
-                match self.expr_pool.get(&e) {
-                    Some(Expr::Block(statements)) => statements,
+                match pool.stmt_list_of(&e) {
+                    Some(statements) => statements,
                     _ => return Err(InterpreterError::FunctionNotFound(format!("evaluate_function: Not handled yet {:?}", function.code))),
                 }
             }
@@ -1347,7 +1361,7 @@ impl EvaluationContext<'_> {
                     self.environment.exit_block();
                     return Ok(v.map(|x| x.into_rc()).unwrap_or_else(|| Rc::new(RefCell::new(Object::null_unknown()))));
                 },
-                Ok(EvaluationResult::Break(_)) | Ok(EvaluationResult::Continue(_)) => {
+                Ok(EvaluationResult::Break(..)) | Ok(EvaluationResult::Continue(_)) => {
                     self.environment.exit_block();
                     return Ok(Rc::new(RefCell::new(Object::Unit)));
                 },
@@ -1360,7 +1374,9 @@ impl EvaluationContext<'_> {
             self.environment.set_val(name, (value).into());
         }
 
-        let res = self.evaluate_block(&block)?;
+        let res = self
+            .in_call_frame(|this| this.evaluate_block(block))
+            .map_err(|e| self.panic_frame(e, function.name))?;
         self.environment.exit_block();
 
         if function.return_type.as_ref().is_none_or(|t| *t == TypeDecl::Unit) {
@@ -1370,7 +1386,7 @@ impl EvaluationContext<'_> {
                 EvaluationResult::Value(v) => v.into_rc(),
                 EvaluationResult::Return(None) => Rc::new(RefCell::new(Object::Unit)),
                 EvaluationResult::Return(v) => v.map(|x| x.into_rc()).unwrap_or_else(|| Rc::new(RefCell::new(Object::null_unknown()))),
-                EvaluationResult::Break(_) | EvaluationResult::Continue(_) | EvaluationResult::None => Rc::new(RefCell::new(Object::Unit)),
+                EvaluationResult::Break(..) | EvaluationResult::Continue(_) | EvaluationResult::None => Rc::new(RefCell::new(Object::Unit)),
             })
         }
     }
@@ -1408,10 +1424,15 @@ impl EvaluationContext<'_> {
             let v = self.dispatch_extern_fn(&function, args)?;
             return Ok((v, vec![None; function.parameter.len()]));
         }
+        if Self::is_generator_function(&function) {
+            let n = function.parameter.len();
+            return Ok((Self::make_generator(function, args.to_vec()), vec![None; n]));
+        }
+        let pool = self.expr_pool;
         let block = match self.stmt_pool.get(&function.code) {
             Some(Stmt::Expression(e)) => {
-                match self.expr_pool.get(&e) {
-                    Some(Expr::Block(statements)) => statements,
+                match pool.stmt_list_of(&e) {
+                    Some(statements) => statements,
                     _ => return Err(InterpreterError::FunctionNotFound(format!("evaluate_function_with_values: Not handled yet {:?}", function.code))),
                 }
             }
@@ -1447,7 +1468,9 @@ impl EvaluationContext<'_> {
             return Err(e);
         }
 
-        let res = self.evaluate_block(&block)?;
+        let res = self
+            .in_call_frame(|this| this.evaluate_block(block))
+            .map_err(|e| self.panic_frame(e, function.name))?;
 
         let return_value: crate::value::Value = if function.return_type.as_ref().is_none_or(|t| *t == TypeDecl::Unit) {
             crate::value::Value::Unit
@@ -1456,13 +1479,12 @@ impl EvaluationContext<'_> {
                 EvaluationResult::Value(v) => v,
                 EvaluationResult::Return(None) => crate::value::Value::Unit,
                 EvaluationResult::Return(v) => v.unwrap_or_else(crate::value::Value::null_unknown),
-                EvaluationResult::Break(_) | EvaluationResult::Continue(_) | EvaluationResult::None => crate::value::Value::Unit,
+                EvaluationResult::Break(..) | EvaluationResult::Continue(_) | EvaluationResult::None => crate::value::Value::Unit,
             }
         };
 
         // Post-body `ensures` checks with `result` bound to the return value.
-        // The contract helper still takes `RcObject`; bridge the value once.
-        if let Err(e) = self.evaluate_ensures_clauses(function.name, &function.ensures, return_value.clone_to_rc()) {
+        if let Err(e) = self.evaluate_ensures_clauses(function.name, &function.ensures, return_value.clone()) {
             self.environment.exit_block();
             return Err(e);
         }
@@ -1485,15 +1507,15 @@ impl EvaluationContext<'_> {
         &mut self,
         object: RcObject,
         method_name: DefaultSymbol,
-        args: &[RcObject],
+        args: &[crate::value::Value],
         struct_name: &str
     ) -> Result<EvaluationResult, InterpreterError> {
         // Look for the method in the function map first
         if let Some(method_func) = self.function.get(&method_name).cloned() {
-            // This is a regular function, call it directly. Convert
-            // legacy `RcObject` arguments to `Value` at the boundary.
+            // This is a regular function, call it directly with the
+            // receiver prepended to the argument list.
             let mut method_args: Vec<crate::value::Value> = vec![object.into()];
-            method_args.extend(args.iter().cloned().map(Into::into));
+            method_args.extend(args.iter().cloned());
             let result = self.evaluate_function_with_values(method_func, &method_args)?;
             return Ok(EvaluationResult::Value(result));
         }
@@ -1528,7 +1550,7 @@ impl EvaluationContext<'_> {
         &mut self,
         struct_name: DefaultSymbol,
         function_name: DefaultSymbol,
-        args: &[RcObject],
+        args: &[crate::value::Value],
         struct_name_str: &str,
         function_name_str: &str
     ) -> Result<EvaluationResult, InterpreterError> {
@@ -1539,15 +1561,21 @@ impl EvaluationContext<'_> {
         // even when a user `fn add(Point, Point)` exists. Falls
         // back to the bare-name lookup, then to the legacy flat
         // map for back-compat.
-        let resolved = self
-            .lookup_function_qualified(Some(struct_name), function_name)
+        // A struct's own associated function comes before the
+        // bare-name fallback so a same-named top-level `fn` never
+        // answers `Type::helper(...)`.
+        let qualified = self.lookup_function_qualified(Some(struct_name), function_name);
+        if qualified.is_none() {
+            if let Some(method) = self.associated_functions.get(&(struct_name, function_name)).cloned() {
+                return self.call_associated_method(method, args.to_vec());
+            }
+        }
+        let resolved = qualified
             .or_else(|| self.lookup_function_qualified(None, function_name))
             .or_else(|| self.function.get(&function_name).cloned());
         if let Some(func) = resolved {
             // This is a regular function, call it directly without self.
-            // Bridge `RcObject` args to `Value` at the boundary.
-            let value_args: Vec<crate::value::Value> = args.iter().cloned().map(Into::into).collect();
-            let result = self.evaluate_function_with_values(func, &value_args)?;
+            let result = self.evaluate_function_with_values(func, args)?;
             return Ok(EvaluationResult::Value(result));
         }
 
@@ -0,0 +1,121 @@
+// Host access granted to a running program.
+//
+// Every builtin that reaches outside the interpreter (files,
+// processes, the network, the clock) asks the evaluation context for
+// its capability before doing anything, and a denied call aborts the
+// run like `panic`. Embedders running untrusted scripts start from
+// `Capabilities::none()` and switch on only what the script needs.
+
+/// One kind of host access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// `read_bytes`
+    FsRead,
+    /// `write_bytes`
+    FsWrite,
+    /// `exec`
+    Exec,
+    /// `http_get`, `tcp_*`
+    Net,
+    /// `now_millis`, `clock`, `sleep`
+    Clock,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 5] = [
+        Capability::FsRead,
+        Capability::FsWrite,
+        Capability::Exec,
+        Capability::Net,
+        Capability::Clock,
+    ];
+
+    /// Name used by the interpreter's `--allow-<name>` flag.
+    pub fn flag_name(self) -> &'static str {
+        match self {
+            Capability::FsRead => "fs-read",
+            Capability::FsWrite => "fs-write",
+            Capability::Exec => "exec",
+            Capability::Net => "net",
+            Capability::Clock => "clock",
+        }
+    }
+
+    pub fn from_flag_name(name: &str) -> Option<Capability> {
+        Capability::ALL.into_iter().find(|c| c.flag_name() == name)
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Capability::FsRead => "file reading",
+            Capability::FsWrite => "file writing",
+            Capability::Exec => "process spawning",
+            Capability::Net => "network access",
+            Capability::Clock => "clock access",
+        }
+    }
+
+    /// Panic message for a builtin that was refused this capability.
+    pub fn denied_message(self, builtin: &str) -> String {
+        format!(
+            "{builtin}: {} is disabled (run with --allow-{})",
+            self.description(),
+            self.flag_name()
+        )
+    }
+}
+
+/// The set of capabilities a run holds. `Default` keeps what the
+/// language always allowed (files and the clock) and leaves process
+/// spawning and networking off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub fs_read: bool,
+    pub fs_write: bool,
+    pub exec: bool,
+    pub net: bool,
+    pub clock: bool,
+}
+
+impl Capabilities {
+    pub const fn none() -> Self {
+        Self { fs_read: false, fs_write: false, exec: false, net: false, clock: false }
+    }
+
+    pub const fn all() -> Self {
+        Self { fs_read: true, fs_write: true, exec: true, net: true, clock: true }
+    }
+
+    pub fn allows(&self, cap: Capability) -> bool {
+        match cap {
+            Capability::FsRead => self.fs_read,
+            Capability::FsWrite => self.fs_write,
+            Capability::Exec => self.exec,
+            Capability::Net => self.net,
+            Capability::Clock => self.clock,
+        }
+    }
+
+    pub fn set(&mut self, cap: Capability, allowed: bool) {
+        let slot = match cap {
+            Capability::FsRead => &mut self.fs_read,
+            Capability::FsWrite => &mut self.fs_write,
+            Capability::Exec => &mut self.exec,
+            Capability::Net => &mut self.net,
+            Capability::Clock => &mut self.clock,
+        };
+        *slot = allowed;
+    }
+
+    /// Builder-style [`set`](Self::set) for `Capabilities::none().with(..)`.
+    pub fn with(mut self, cap: Capability) -> Self {
+        self.set(cap, true);
+        self
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self { fs_read: true, fs_write: true, exec: false, net: false, clock: true }
+    }
+}
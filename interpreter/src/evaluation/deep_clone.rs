@@ -0,0 +1,128 @@
+// Deep copy backing the `clone(x)` builtin.
+//
+// Arrays, tuples, dicts (keys and values), structs, enum payloads and
+// ranges are copied all the way down, so mutating the copy never shows
+// through the original. Every `RcObject` visited is remembered, which
+// does two things:
+//   - a value reachable twice is copied once, so the copy shares
+//     exactly where the original did
+//   - a value reachable from itself (a cycle) is copied once and the
+//     back edge points at the copy, instead of recursing forever
+// Closures keep their captures (a function value is not data), and a
+// pointer keeps its address: the memory behind it belongs to the
+// `HeapManager` and its allocator, not to the value being cloned.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::object::{Object, ObjectKey, RcObject};
+use crate::value::Value;
+
+pub fn deep_clone(value: &RcObject) -> RcObject {
+    DeepCloner::default().clone_rc(value)
+}
+
+/// Several deep copies that share one memo, so a value reachable from
+/// more than one root (two variables bound to the same array) is still
+/// shared between the copies. Used by `EvaluationContext::snapshot`.
+#[derive(Default)]
+pub(super) struct DeepCloner {
+    seen: HashMap<*const RefCell<Object>, RcObject>,
+}
+
+impl DeepCloner {
+    pub(super) fn clone_rc(&mut self, value: &RcObject) -> RcObject {
+        deep_clone_rc(value, &mut self.seen)
+    }
+
+    pub(super) fn clone_value(&mut self, value: &Value) -> Value {
+        match value {
+            Value::Heap(rc) => Value::Heap(self.clone_rc(rc)),
+            other => other.clone(),
+        }
+    }
+}
+
+fn deep_clone_rc(value: &RcObject, seen: &mut HashMap<*const RefCell<Object>, RcObject>) -> RcObject {
+    if let Some(copy) = seen.get(&Rc::as_ptr(value)) {
+        return copy.clone();
+    }
+    // Registered before the children are visited so a cycle back to
+    // `value` finds it; the real contents are filled in below.
+    let copy = Rc::new(RefCell::new(Object::Unit));
+    seen.insert(Rc::as_ptr(value), copy.clone());
+    let contents = deep_clone_object(&value.borrow(), seen);
+    *copy.borrow_mut() = contents;
+    copy
+}
+
+// Keys are cloned like any other value; the lint's concern is keys
+// mutated after insertion, and a fresh copy is never shared.
+#[allow(clippy::mutable_key_type)]
+fn deep_clone_object(value: &Object, seen: &mut HashMap<*const RefCell<Object>, RcObject>) -> Object {
+    match value {
+        Object::Array(items) => {
+            Object::Array(Box::new(items.iter().map(|v| deep_clone_rc(v, seen)).collect()))
+        }
+        Object::Tuple(items) => {
+            Object::Tuple(Box::new(items.iter().map(|v| deep_clone_rc(v, seen)).collect()))
+        }
+        Object::Dict(entries) => {
+            let entries: HashMap<ObjectKey, RcObject> = entries
+                .iter()
+                .map(|(k, v)| {
+                    (ObjectKey::new(deep_clone_object(k.as_object(), seen)), deep_clone_rc(v, seen))
+                })
+                .collect();
+            Object::Dict(Box::new(entries))
+        }
+        Object::Struct { type_name, fields, type_args } => Object::Struct {
+            type_name: *type_name,
+            fields: Box::new(fields.iter().map(|(name, v)| (*name, deep_clone_rc(v, seen))).collect()),
+            type_args: type_args.clone(),
+        },
+        Object::EnumVariant { enum_name, variant_name, values, type_args } => Object::EnumVariant {
+            enum_name: *enum_name,
+            variant_name: *variant_name,
+            values: values.iter().map(|v| deep_clone_rc(v, seen)).collect(),
+            type_args: type_args.clone(),
+        },
+        Object::Range { start, end } => Object::Range {
+            start: deep_clone_rc(start, seen),
+            end: deep_clone_rc(end, seen),
+        },
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_is_copied_once() {
+        // The language cannot build this directly, but an array that
+        // contains itself is the smallest cycle the walk must survive.
+        let array = Rc::new(RefCell::new(Object::Array(Box::default())));
+        if let Object::Array(items) = &mut *array.borrow_mut() {
+            items.push(array.clone());
+            items.push(Rc::new(RefCell::new(Object::UInt64(7))));
+        }
+
+        let copy = deep_clone(&array);
+        assert!(!Rc::ptr_eq(&copy, &array));
+        if let Object::Array(items) = &*copy.borrow() {
+            assert!(Rc::ptr_eq(&items[0], &copy));
+            assert!(matches!(*items[1].borrow(), Object::UInt64(7)));
+        } else {
+            panic!("expected an array");
+        }
+
+        // Break both cycles so the test doesn't leak.
+        for rc in [&array, &copy] {
+            if let Object::Array(items) = &mut *rc.borrow_mut() {
+                items.clear();
+            }
+        }
+    }
+}
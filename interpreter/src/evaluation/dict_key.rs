@@ -0,0 +1,94 @@
+// Dict key resolution for struct keys.
+//
+// Structs compare structurally by default, which is what `ObjectKey`
+// already does. A struct that defines `eq` (the same method `==`
+// dispatches to) gets its own notion of equality, so before a struct
+// key is used for a lookup or an insertion it is matched against the
+// keys of the same type already in the dict: the first one `eq` says
+// is equal is used instead. When the struct also implements
+// `hash(self: Self) -> u64`, only keys with the same hash are asked,
+// mirroring the `eq` / `hash` contract of the `Hash` trait.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use frontend::ast::MethodFunction;
+use frontend::type_decl::TypeDecl;
+use string_interner::DefaultSymbol;
+use crate::error::InterpreterError;
+use crate::object::{Object, ObjectKey, RcObject};
+use super::{EvaluationContext, EvaluationResult};
+
+impl EvaluationContext<'_> {
+    #[allow(clippy::mutable_key_type)]
+    pub(super) fn resolve_dict_key(
+        &mut self,
+        key: &RcObject,
+        dict: &HashMap<ObjectKey, RcObject>,
+    ) -> Result<ObjectKey, InterpreterError> {
+        let (type_name, type_args) = match &*key.borrow() {
+            Object::Struct { type_name, type_args, .. } => (*type_name, type_args.clone()),
+            _ => return Ok(ObjectKey::from_rc(key)),
+        };
+        let Some(eq) = self.key_method(type_name, "eq", &type_args) else {
+            return Ok(ObjectKey::from_rc(key));
+        };
+        let hash = self.key_method(type_name, "hash", &type_args);
+
+        let candidates: Vec<ObjectKey> = dict
+            .keys()
+            .filter(|existing| matches!(
+                existing.as_object(),
+                Object::Struct { type_name: n, type_args: a, .. } if *n == type_name && *a == type_args
+            ))
+            .cloned()
+            .collect();
+        if candidates.is_empty() {
+            return Ok(ObjectKey::from_rc(key));
+        }
+
+        let key_hash = match &hash {
+            Some(method) => Some(self.call_key_method(method.clone(), key.clone(), Vec::new())?),
+            None => None,
+        };
+        for candidate in candidates {
+            let candidate_rc = Rc::new(RefCell::new(candidate.as_object().clone()));
+            if let (Some(method), Some(key_hash)) = (&hash, &key_hash) {
+                if self.call_key_method(method.clone(), candidate_rc.clone(), Vec::new())? != *key_hash {
+                    continue;
+                }
+            }
+            let equal = self.call_key_method(eq.clone(), key.clone(), vec![candidate_rc.into()])?;
+            if equal == Object::Bool(true) {
+                return Ok(candidate);
+            }
+        }
+        Ok(ObjectKey::from_rc(key))
+    }
+
+    fn key_method(
+        &self,
+        type_name: DefaultSymbol,
+        method: &str,
+        type_args: &[TypeDecl],
+    ) -> Option<Rc<MethodFunction>> {
+        let method_sym = self.string_interner.get(method)?;
+        self.get_method(type_name, method_sym, type_args)
+    }
+
+    fn call_key_method(
+        &mut self,
+        method: Rc<MethodFunction>,
+        receiver: RcObject,
+        args: Vec<crate::value::Value>,
+    ) -> Result<Object, InterpreterError> {
+        match self.call_method(method, receiver, args)? {
+            EvaluationResult::Value(v) | EvaluationResult::Return(Some(v)) => {
+                Ok(v.into_rc().borrow().clone())
+            }
+            _ => Err(InterpreterError::InternalError(
+                "dict key method produced no value".to_string(),
+            )),
+        }
+    }
+}
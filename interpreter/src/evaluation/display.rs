@@ -0,0 +1,72 @@
+// Display rendering for `print` / `println` and string interpolation,
+// including the `__str__` protocol: a struct or enum whose impl
+// defines `fn __str__(self: Self) -> str` is shown as that string
+// wherever it appears — top level, array element, dict value or a
+// field of another struct.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use frontend::ast::MethodFunction;
+use string_interner::DefaultSymbol;
+use crate::error::InterpreterError;
+use crate::object::{Object, RcObject};
+use super::{EvaluationContext, EvaluationResult};
+
+impl EvaluationContext<'_> {
+    pub(super) fn display_string(&mut self, value: &RcObject) -> Result<String, InterpreterError> {
+        let str_sym = self.string_interner.get_or_intern("__str__");
+
+        // First pass: the built-in rendering, collecting every value
+        // that has a `__str__` (its subtree is skipped). Most values
+        // have none, and then this rendering is the answer.
+        let mut custom: Vec<(Rc<MethodFunction>, Object)> = Vec::new();
+        let plain = {
+            let this = &*self;
+            value.borrow().to_display_string_with(this.string_interner, &mut |obj| {
+                let method = this.str_method(obj, str_sym)?;
+                custom.push((method, obj.clone()));
+                Some(String::new())
+            })
+        };
+        if custom.is_empty() {
+            return Ok(plain);
+        }
+
+        // The receiver is a shallow copy, so `__str__` sees the same
+        // field values without holding a borrow of the original.
+        let mut rendered = Vec::with_capacity(custom.len());
+        for (method, receiver) in custom {
+            let result = self.call_method(method, Rc::new(RefCell::new(receiver)), Vec::new())?;
+            let text = match result {
+                EvaluationResult::Value(v) | EvaluationResult::Return(Some(v)) => {
+                    v.into_rc().borrow().to_string_value(self.string_interner)
+                }
+                _ => {
+                    return Err(InterpreterError::InternalError(
+                        "__str__ produced no value".to_string(),
+                    ));
+                }
+            };
+            rendered.push(text);
+        }
+
+        // Second pass walks the same values in the same order and
+        // splices the results in.
+        let mut rendered = rendered.into_iter();
+        let this = &*self;
+        Ok(value.borrow().to_display_string_with(this.string_interner, &mut |obj| {
+            this.str_method(obj, str_sym)?;
+            rendered.next()
+        }))
+    }
+
+    fn str_method(&self, obj: &Object, str_sym: DefaultSymbol) -> Option<Rc<MethodFunction>> {
+        match obj {
+            Object::Struct { type_name: name, type_args, .. }
+            | Object::EnumVariant { enum_name: name, type_args, .. } => {
+                self.get_method(*name, str_sym, type_args)
+            }
+            _ => None,
+        }
+    }
+}
@@ -3,11 +3,13 @@ use std::rc::Rc;
 use frontend::ast::*;
 use frontend::type_decl::TypeDecl;
 use string_interner::DefaultSymbol;
-use crate::object::{Object, ObjectKey, RcObject};
+use crate::object::{Object, RcObject};
 use crate::value::Value;
+use crate::environment::VariableSetType;
 use crate::error::InterpreterError;
 use crate::try_value;
 use super::{convert_object, EvaluationContext, EvaluationResult};
+use super::generator::ResumePoint;
 
 impl EvaluationContext<'_> {
     pub fn evaluate(&mut self, e: &ExprRef) -> Result<EvaluationResult, InterpreterError> {
@@ -17,6 +19,15 @@ impl EvaluationContext<'_> {
                 "Maximum recursion depth reached in expression evaluation - possible circular reference".to_string()
             ));
         }
+        if let Some(steps) = &mut self.step_budget {
+            if *steps == 0 {
+                return Err(InterpreterError::InternalError("Step budget exhausted".to_string()));
+            }
+            *steps -= 1;
+        }
+        if let Some(coverage) = &mut self.coverage {
+            coverage.hit_expr(e);
+        }
 
         self.recursion_depth += 1;
         let result = self.evaluate_impl(e);
@@ -25,7 +36,83 @@ impl EvaluationContext<'_> {
     }
 
     fn evaluate_impl(&mut self, e: &ExprRef) -> Result<EvaluationResult, InterpreterError> {
-        let expr = self.expr_pool.get(e)
+        // Variants carrying a list payload are dispatched straight from
+        // the pool columns: `ExprPool::get` would clone the list on every
+        // evaluation, and these are the nodes loops revisit most. The
+        // pool outlives `self`, so the borrowed slices stay valid across
+        // the `&mut self` calls below.
+        let pool = self.expr_pool;
+        let malformed = || InterpreterError::InternalError(format!("Malformed expression: {:?}", e));
+        match pool.expr_type(e) {
+            // Block as a value-position expression: needed for match arm
+            // bodies (`Some(x) => { ... }`) and the iterator-protocol
+            // `for x in iter { body }` desugaring whose match arms wrap
+            // the user body in a fresh block. Mirrors the
+            // statement-position handling in `handle_expression_statement`.
+            Some(ExprType::Block) => {
+                let stmts = pool.stmt_list_of(e).ok_or_else(malformed)?;
+                self.environment.enter_block();
+                let res = self.evaluate_block(stmts);
+                self.environment.exit_block();
+                return res;
+            }
+            Some(ExprType::IfElifElse) => {
+                let cond = pool.lhs_of(e).ok_or_else(malformed)?;
+                let then = pool.rhs_of(e).ok_or_else(malformed)?;
+                let elif_pairs = pool.entry_list_of(e).ok_or_else(malformed)?;
+                let else_block = pool.third_operand_of(e).ok_or_else(malformed)?;
+                return self.evaluate_if_elif_else(&cond, &then, elif_pairs, &else_block);
+            }
+            Some(ExprType::MethodCall) => {
+                let obj = pool.lhs_of(e).ok_or_else(malformed)?;
+                let method = pool.symbol_of(e).ok_or_else(malformed)?;
+                let args = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_method_call(&obj, &method, args);
+            }
+            Some(ExprType::BuiltinMethodCall) => {
+                let receiver = pool.lhs_of(e).ok_or_else(malformed)?;
+                let method = pool.builtin_method_of(e).ok_or_else(malformed)?;
+                let args = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_builtin_method_call(&receiver, method, args);
+            }
+            Some(ExprType::BuiltinCall) => {
+                let func = pool.builtin_function_of(e).ok_or_else(malformed)?;
+                let args = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_builtin_call(func, args);
+            }
+            Some(ExprType::AssociatedFunctionCall) => {
+                let (struct_name, function_name) = match pool.symbol_list_of(e) {
+                    Some([s, f, ..]) => (*s, *f),
+                    _ => return Err(malformed()),
+                };
+                let args = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_associated_function_call(&struct_name, &function_name, args);
+            }
+            Some(ExprType::ArrayLiteral) => {
+                let elements = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_array_literal(elements);
+            }
+            Some(ExprType::TupleLiteral) => {
+                let elements = pool.expr_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_tuple_literal(elements);
+            }
+            Some(ExprType::StructLiteral) => {
+                let struct_name = pool.symbol_of(e).ok_or_else(malformed)?;
+                let fields = pool.field_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_struct_literal(&struct_name, fields);
+            }
+            Some(ExprType::DictLiteral) => {
+                let entries = pool.entry_list_of(e).ok_or_else(malformed)?;
+                return self.evaluate_dict_literal(entries);
+            }
+            Some(ExprType::Match) => {
+                let scrutinee = pool.lhs_of(e).ok_or_else(malformed)?;
+                let arms = pool.match_arms_of(e).ok_or_else(malformed)?;
+                return self.evaluate_match(&scrutinee, arms);
+            }
+            _ => {}
+        }
+        let expr = pool.get(e)
             .ok_or_else(|| InterpreterError::InternalError(format!("Unbound error: {:?}", e)))?;
         match expr {
             Expr::Binary(op, lhs, rhs) => {
@@ -39,6 +126,14 @@ impl EvaluationContext<'_> {
             | Expr::UInt8(_) | Expr::UInt16(_) | Expr::UInt32(_) => {
                 self.evaluate_literal(&expr)
             }
+            Expr::Bytes(sym) => {
+                // Decode the Latin-1 interned payload (one char per
+                // byte, see `Expr::Bytes`) into a fresh buffer.
+                let text = self.string_interner.resolve(sym)
+                    .ok_or_else(|| InterpreterError::InternalError("Unresolved bytes literal".to_string()))?;
+                let bytes: Vec<u8> = text.chars().map(|c| c as u8).collect();
+                Ok(EvaluationResult::Value(Object::Bytes(Box::new(bytes)).into()))
+            }
             Expr::Number(_v) => {
                 // Type-unspecified numbers should be resolved during type checking
                 Err(InterpreterError::InternalError("Expr::Number should be transformed to concrete type during type checking".to_string()))
@@ -48,60 +143,30 @@ impl EvaluationContext<'_> {
                     .ok_or_else(|| InterpreterError::UndefinedVariable(format!("Variable not found: {s:?}")))?;
                 Ok(EvaluationResult::Value(val))
             }
-            Expr::IfElifElse(cond, then, elif_pairs, _else) => {
-                self.evaluate_if_elif_else(&cond, &then, &elif_pairs, &_else)
-            }
-            Expr::Call(name, args) => {
+            Expr::Call(name, args, _) => {
                 self.evaluate_function_call(&name, &args)
             }
-            Expr::ArrayLiteral(elements) => {
-                self.evaluate_array_literal(&elements)
-            }
             Expr::FieldAccess(obj, field) => {
                 self.evaluate_field_access(&obj, &field)
             }
-            Expr::MethodCall(obj, method, args) => {
-                self.evaluate_method_call(&obj, &method, &args)
-            }
-            Expr::BuiltinMethodCall(receiver, method, args) => {
-                self.evaluate_builtin_method_call(&receiver, &method, &args)
-            }
-            Expr::BuiltinCall(func, args) => {
-                self.evaluate_builtin_call(&func, &args)
-            }
-            Expr::StructLiteral(struct_name, fields) => {
-                self.evaluate_struct_literal(&struct_name, &fields)
-            }
             Expr::QualifiedIdentifier(path) => {
                 self.evaluate_qualified_identifier(&path)
             }
             Expr::Null => {
                 Err(InterpreterError::InternalError("Null reference error".to_string()))
             }
-            Expr::SliceAssign(object, start, end, value) => {
-                self.evaluate_slice_assign(&object, &start, &end, &value)
+            Expr::SliceAssign(object, slice_info, value) => {
+                self.evaluate_slice_assign(&object, &slice_info, &value)
             }
             Expr::SliceAccess(object, slice_info) => {
                 self.evaluate_slice_access_with_info(&object, &slice_info)
             }
-            Expr::DictLiteral(entries) => {
-                self.evaluate_dict_literal(&entries)
-            }
-            Expr::TupleLiteral(elements) => {
-                self.evaluate_tuple_literal(&elements)
-            }
             Expr::TupleAccess(tuple, index) => {
                 self.evaluate_tuple_access(&tuple, index)
             }
-            Expr::AssociatedFunctionCall(struct_name, function_name, args) => {
-                self.evaluate_associated_function_call(&struct_name, &function_name, &args)
-            }
             Expr::Cast(expr, target_type) => {
                 self.evaluate_cast(&expr, &target_type)
             }
-            Expr::Match(scrutinee, arms) => {
-                self.evaluate_match(&scrutinee, &arms)
-            }
             Expr::Range(start, end) => {
                 let start_val = self.evaluate(&start);
                 let start_val = try_value!(start_val);
@@ -221,16 +286,14 @@ impl EvaluationContext<'_> {
             Expr::Closure { params, return_type, body } => {
                 self.evaluate_closure_literal(&params, &return_type, &body)
             }
-            // Block as a value-position expression: needed for match arm
-            // bodies (`Some(x) => { ... }`) and the iterator-protocol
-            // `for x in iter { body }` desugaring whose match arms wrap
-            // the user body in a fresh block. Mirrors the
-            // statement-position handling in `handle_expression_statement`.
-            Expr::Block(stmts) => {
-                self.environment.enter_block();
-                let res = self.evaluate_block(&stmts);
-                self.environment.exit_block();
-                res
+            Expr::Comprehension { element, value, var, source, filter } => {
+                self.evaluate_comprehension(&element, &value, var, &source, &filter)
+            }
+            Expr::WithResource { name, resource, body } => {
+                self.evaluate_with_resource(name, &resource, &body)
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.evaluate_try_catch(&body, error, &handler)
             }
             _ => Err(InterpreterError::InternalError(format!("evaluate: unexpected expr: {expr:?}"))),
         }
@@ -299,7 +362,7 @@ impl EvaluationContext<'_> {
         };
         match expr {
             Expr::Identifier(s) => record(s, out, seen),
-            Expr::Call(name, args_ref) => {
+            Expr::Call(name, args_ref, _) => {
                 record(name, out, seen);
                 self.collect_closure_captures(args_ref, bound, out, seen);
             }
@@ -356,7 +419,7 @@ impl EvaluationContext<'_> {
                     self.collect_closure_captures(a, bound, out, seen);
                 }
             }
-            Expr::StructLiteral(_, fields) => {
+            Expr::StructLiteral(_, fields, _) => {
                 for (_, e) in fields {
                     self.collect_closure_captures(e, bound, out, seen);
                 }
@@ -370,12 +433,12 @@ impl EvaluationContext<'_> {
                     self.collect_closure_captures(e, bound, out, seen);
                 }
             }
-            Expr::SliceAssign(obj, start, end, value) => {
+            Expr::SliceAssign(obj, info, value) => {
                 self.collect_closure_captures(obj, bound, out, seen);
-                if let Some(s) = start {
+                if let Some(s) = info.start {
                     self.collect_closure_captures(s, bound, out, seen);
                 }
-                if let Some(e) = end {
+                if let Some(e) = info.end {
                     self.collect_closure_captures(e, bound, out, seen);
                 }
                 self.collect_closure_captures(value, bound, out, seen);
@@ -405,11 +468,31 @@ impl EvaluationContext<'_> {
                 }
                 self.collect_closure_captures(body, &nested_bound, out, seen);
             }
+            Expr::WithResource { name, resource, body } => {
+                self.collect_closure_captures(resource, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(name);
+                self.collect_closure_captures(body, &nested_bound, out, seen);
+            }
+            Expr::TryCatch { body, error, handler } => {
+                self.collect_closure_captures(body, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(error);
+                self.collect_closure_captures(handler, &nested_bound, out, seen);
+            }
+            Expr::Comprehension { element, value, var, source, filter } => {
+                self.collect_closure_captures(source, bound, out, seen);
+                let mut nested_bound = bound.clone();
+                nested_bound.insert(var);
+                for e in filter.iter().chain([&element]).chain(&value) {
+                    self.collect_closure_captures(*e, &nested_bound, out, seen);
+                }
+            }
             Expr::QualifiedIdentifier(_)
             | Expr::Int64(_) | Expr::UInt64(_) | Expr::Float64(_)
             | Expr::Int8(_) | Expr::Int16(_) | Expr::Int32(_)
             | Expr::UInt8(_) | Expr::UInt16(_) | Expr::UInt32(_)
-            | Expr::Number(_) | Expr::String(_)
+            | Expr::Number(_) | Expr::String(_) | Expr::Bytes(_)
             | Expr::True | Expr::False | Expr::Null => {}
         }
     }
@@ -422,7 +505,7 @@ impl EvaluationContext<'_> {
         seen: &mut std::collections::HashSet<DefaultSymbol>,
     ) {
         match stmt {
-            Stmt::Expression(e) => self.collect_closure_captures(*e, bound, out, seen),
+            Stmt::Expression(e) | Stmt::Yield(e) => self.collect_closure_captures(*e, bound, out, seen),
             Stmt::Val(name, _, e) => {
                 self.collect_closure_captures(*e, bound, out, seen);
                 bound.insert(*name);
@@ -449,7 +532,12 @@ impl EvaluationContext<'_> {
                 self.collect_closure_captures(*cond, bound, out, seen);
                 self.collect_closure_captures(*body, bound, out, seen);
             }
-            Stmt::Break(_) | Stmt::Continue(_) => {}
+            Stmt::Break(_, value) => {
+                if let Some(e) = value {
+                    self.collect_closure_captures(*e, bound, out, seen);
+                }
+            }
+            Stmt::Continue(_) => {}
             Stmt::StructDecl { .. }
             | Stmt::ImplBlock { .. }
             | Stmt::EnumDecl { .. }
@@ -487,6 +575,20 @@ impl EvaluationContext<'_> {
         // checker has already enforced this — so we work directly on the
         // inline `Value` and skip the `Rc::clone` + `borrow()` round-trip.
         use crate::try_value_v;
+        // A generator resuming inside a branch goes straight back into
+        // it; the conditions were settled before it suspended.
+        match self.take_resume_point() {
+            None => {}
+            Some(ResumePoint::Branch(k)) => {
+                let block = match k {
+                    0 => *then,
+                    k if k <= elif_pairs.len() => elif_pairs[k - 1].1,
+                    _ => *_else,
+                };
+                return self.evaluate_if_branch(&block, k);
+            }
+            Some(point) => return Err(Self::unexpected_resume_point(point, "an if expression")),
+        }
         let cond = self.evaluate(cond);
         let cond_v = try_value_v!(cond);
         let cond_bool = match &cond_v {
@@ -502,15 +604,13 @@ impl EvaluationContext<'_> {
 
         // Check if condition
         if cond_bool {
-            let then_expr = self.expr_pool.get(then)
-                .ok_or_else(|| InterpreterError::InternalError("Invalid then block reference".to_string()))?;
-            if !then_expr.is_block() {
+            if self.expr_pool.expr_type(then) != Some(ExprType::Block) {
                 return Err(InterpreterError::InternalError("if-then is not block".to_string()));
             }
-            selected_block = Some(then);
+            selected_block = Some((then, 0));
         } else {
             // Check elif conditions
-            for (elif_cond, elif_block) in elif_pairs {
+            for (i, (elif_cond, elif_block)) in elif_pairs.iter().enumerate() {
                 let elif_cond = self.evaluate(elif_cond);
                 let elif_cond_v = try_value_v!(elif_cond);
                 let elif_bool = match &elif_cond_v {
@@ -523,44 +623,49 @@ impl EvaluationContext<'_> {
                 };
 
                 if elif_bool {
-                    let elif_expr = self.expr_pool.get(elif_block)
-                        .ok_or_else(|| InterpreterError::InternalError("Invalid elif block reference".to_string()))?;
-                    if !elif_expr.is_block() {
+                    if self.expr_pool.expr_type(elif_block) != Some(ExprType::Block) {
                         return Err(InterpreterError::InternalError("elif block is not block".to_string()));
                     }
-                    selected_block = Some(elif_block);
+                    selected_block = Some((elif_block, i + 1));
                     break;
                 }
             }
 
             // If no elif condition matched, use else block
             if selected_block.is_none() {
-                let else_expr = self.expr_pool.get(_else)
-                    .ok_or_else(|| InterpreterError::InternalError("Invalid else block reference".to_string()))?;
-                if !else_expr.is_block() {
+                if self.expr_pool.expr_type(_else) != Some(ExprType::Block) {
                     return Err(InterpreterError::InternalError("else block is not block".to_string()));
                 }
-                selected_block = Some(_else);
+                selected_block = Some((_else, elif_pairs.len() + 1));
             }
         }
 
         // Execute selected block
-        if let Some(block_expr) = selected_block {
-            self.environment.enter_block();
-            let res = {
-                if let Some(Expr::Block(statements)) = self.expr_pool.get(block_expr) {
-                    self.evaluate_block(&statements)
-                } else {
-                    return Err(InterpreterError::InternalError("evaluate: selected block is not block".to_string()))
-                }
-            };
-            self.environment.exit_block();
-            res
+        if let Some((block_expr, branch)) = selected_block {
+            self.evaluate_if_branch(block_expr, branch)
         } else {
             Err(InterpreterError::InternalError("evaluate: no block selected in if-elif-else".to_string()))
         }
     }
 
+    /// Runs the selected if/elif/else block; `branch` is its position,
+    /// recorded when a generator suspends inside it.
+    fn evaluate_if_branch(&mut self, block_expr: &ExprRef, branch: usize) -> Result<EvaluationResult, InterpreterError> {
+        self.environment.enter_block();
+        let res = {
+            if let Some(statements) = self.expr_pool.stmt_list_of(block_expr) {
+                self.evaluate_block(statements)
+            } else {
+                return Err(InterpreterError::InternalError("evaluate: selected block is not block".to_string()))
+            }
+        };
+        self.environment.exit_block();
+        if self.is_suspending() {
+            self.record_resume_point(ResumePoint::Branch(branch));
+        }
+        res
+    }
+
     /// Evaluates array literal expressions
     pub(super) fn evaluate_array_literal(&mut self, elements: &[ExprRef]) -> Result<EvaluationResult, InterpreterError> {
         let mut array_objects = Vec::new();
@@ -580,9 +685,9 @@ impl EvaluationContext<'_> {
             let key_val = self.evaluate(key_ref)?;
             let key_obj_rc = try_value!(Ok(key_val));
 
-            // Convert to ObjectKey - clone the object for use as a key
-            let key_object = key_obj_rc.borrow().clone();
-            let object_key = ObjectKey::new(key_object);
+            // Convert to ObjectKey - clone the object for use as a key,
+            // or reuse an existing key a struct's `eq` considers equal
+            let object_key = self.resolve_dict_key(&key_obj_rc, &dict)?;
 
             // Evaluate value
             let value_val = self.evaluate(value_ref)?;
@@ -595,6 +700,162 @@ impl EvaluationContext<'_> {
         Ok(EvaluationResult::Value((dict_obj).into()))
     }
 
+    /// Evaluate `try { body } catch error { handler }`. Any
+    /// `InterpreterError` out of the body is caught: the scopes it
+    /// unwound through are discarded (their pending auto-drops do not
+    /// run, as on any error path) and the handler runs with `error`
+    /// bound to the failure's message.
+    fn evaluate_try_catch(
+        &mut self,
+        body: &ExprRef,
+        error: DefaultSymbol,
+        handler: &ExprRef,
+    ) -> Result<EvaluationResult, InterpreterError> {
+        let env_depth = self.environment.depth();
+        let drop_depth = self.drop_scopes.len();
+        let allocator_depth = self.allocator_stack.len();
+        let generator_depth = self.generator_frames.len();
+        let err = match self.evaluate(body) {
+            Err(err) if err.is_catchable() => err,
+            other => return other,
+        };
+        self.environment.truncate_scopes(env_depth);
+        self.drop_scopes.truncate(drop_depth);
+        self.allocator_stack.truncate(allocator_depth);
+        self.generator_frames.truncate(generator_depth);
+        let statements = match self.expr_pool.get(handler) {
+            Some(Expr::Block(statements)) => statements,
+            _ => return Err(InterpreterError::InternalError("catch handler is not a block".to_string())),
+        };
+        self.environment.enter_block();
+        self.environment.set_val(error, Object::String(err.caught_message()).into());
+        let result = self.evaluate_block(&statements);
+        self.environment.exit_block();
+        result
+    }
+
+    /// Evaluate `with name = resource { body }`: bind `name` for the
+    /// body, then call the resource's `__close__` however the body
+    /// exits — normally, through `return` / `break` / `continue`, or
+    /// with an error. An error from the body wins over one from
+    /// `__close__`.
+    fn evaluate_with_resource(
+        &mut self,
+        name: DefaultSymbol,
+        resource: &ExprRef,
+        body: &ExprRef,
+    ) -> Result<EvaluationResult, InterpreterError> {
+        use crate::try_value_v;
+        let value = self.evaluate(resource);
+        let value = try_value_v!(value);
+        let receiver = value.clone().into_rc();
+        let statements = match self.expr_pool.get(body) {
+            Some(Expr::Block(statements)) => statements,
+            _ => return Err(InterpreterError::InternalError("with body is not a block".to_string())),
+        };
+        self.environment.enter_block();
+        self.environment.set_val(name, value);
+        let result = self.evaluate_block(&statements);
+        self.environment.exit_block();
+        let closed = self.invoke_close(receiver);
+        let result = result?;
+        closed?;
+        Ok(result)
+    }
+
+    /// Evaluate `[elem for x in src if cond]` (and the dict form when
+    /// `value` is set). Each item gets a fresh block scope holding `x`,
+    /// so the loop variable never leaks into the enclosing scope.
+    // Dict keys go through `resolve_dict_key`, so they are the same
+    // hashable `ObjectKey`s every `Object::Dict` uses.
+    #[allow(clippy::mutable_key_type)]
+    fn evaluate_comprehension(
+        &mut self,
+        element: &ExprRef,
+        value: &Option<ExprRef>,
+        var: DefaultSymbol,
+        source: &ExprRef,
+        filter: &Option<ExprRef>,
+    ) -> Result<EvaluationResult, InterpreterError> {
+        let source_val = self.evaluate(source);
+        let source_obj = try_value!(source_val);
+        let items: Vec<Value> = match &*source_obj.borrow() {
+            Object::Range { start, end } => match (&*start.borrow(), &*end.borrow()) {
+                (Object::Int64(s), Object::Int64(e)) => (*s..*e).map(Value::Int64).collect(),
+                (Object::UInt64(s), Object::UInt64(e)) => (*s..*e).map(Value::UInt64).collect(),
+                (s, e) => {
+                    return Err(InterpreterError::InternalError(format!(
+                        "comprehension: unsupported range bounds {s:?}..{e:?}"
+                    )))
+                }
+            },
+            Object::Array(elements) => elements.iter().map(Value::from_rc).collect(),
+            other => {
+                return Err(InterpreterError::InternalError(format!(
+                    "comprehension: source is not a range or an array: {other:?}"
+                )))
+            }
+        };
+
+        let mut elements = Vec::new();
+        let mut dict = HashMap::new();
+        for item in items {
+            self.environment.enter_block();
+            let step = self.evaluate_comprehension_item(element, value, var, item, filter);
+            self.environment.exit_block();
+            match step? {
+                None => {}
+                Some((elem, None)) => elements.push(elem),
+                Some((key, Some(val))) => {
+                    let object_key = self.resolve_dict_key(&key, &dict)?;
+                    dict.insert(object_key, val);
+                }
+            }
+        }
+
+        let obj = if value.is_some() {
+            Object::Dict(Box::new(dict))
+        } else {
+            Object::Array(Box::new(elements))
+        };
+        Ok(EvaluationResult::Value(obj.into()))
+    }
+
+    /// One comprehension item: bind `var`, apply the filter, then
+    /// evaluate the element (and value). `None` means filtered out.
+    #[allow(clippy::type_complexity)]
+    fn evaluate_comprehension_item(
+        &mut self,
+        element: &ExprRef,
+        value: &Option<ExprRef>,
+        var: DefaultSymbol,
+        item: Value,
+        filter: &Option<ExprRef>,
+    ) -> Result<Option<(RcObject, Option<RcObject>)>, InterpreterError> {
+        self.environment.set_var(var, item, VariableSetType::Insert, self.string_interner)?;
+        if let Some(filter) = filter {
+            let keep = self.comprehension_value(filter)?;
+            if !keep.try_unwrap_bool().map_err(InterpreterError::ObjectError)? {
+                return Ok(None);
+            }
+        }
+        let elem = self.comprehension_value(element)?.into_rc();
+        let val = match value {
+            Some(value) => Some(self.comprehension_value(value)?.into_rc()),
+            None => None,
+        };
+        Ok(Some((elem, val)))
+    }
+
+    fn comprehension_value(&mut self, expr: &ExprRef) -> Result<Value, InterpreterError> {
+        match self.evaluate(expr)? {
+            EvaluationResult::Value(v) => Ok(v),
+            other => Err(InterpreterError::InternalError(format!(
+                "comprehension: control flow cannot leave a comprehension body: {other:?}"
+            ))),
+        }
+    }
+
     pub(super) fn evaluate_tuple_literal(&mut self, elements: &[ExprRef]) -> Result<EvaluationResult, InterpreterError> {
         let mut tuple_elements = Vec::new();
 
@@ -765,11 +1026,29 @@ impl EvaluationContext<'_> {
     pub(super) fn evaluate_match(
         &mut self,
         scrutinee: &ExprRef,
-        arms: &Vec<MatchArm>,
+        arms: &[MatchArm],
     ) -> Result<EvaluationResult, InterpreterError> {
+        // A generator resuming inside an arm re-enters its body; the
+        // arm's pattern bindings come back with the saved scopes.
+        match self.take_resume_point() {
+            None => {}
+            Some(ResumePoint::Arm(k)) => {
+                let arm = arms.get(k).ok_or_else(|| {
+                    InterpreterError::InternalError(format!("generator resumed at missing match arm {k}"))
+                })?;
+                self.environment.enter_block();
+                let result = self.evaluate(&arm.body);
+                self.environment.exit_block();
+                if self.is_suspending() {
+                    self.record_resume_point(ResumePoint::Arm(k));
+                }
+                return result;
+            }
+            Some(point) => return Err(Self::unexpected_resume_point(point, "a match expression")),
+        }
         let scrutinee_val = self.evaluate(scrutinee);
         let scrutinee_val = try_value!(scrutinee_val);
-        for arm in arms {
+        for (k, arm) in arms.iter().enumerate() {
             // Probe each arm in a fresh scope so bindings that were set
             // during a partial match don't leak across arms when the
             // match ultimately fails or the guard is false.
@@ -789,6 +1068,9 @@ impl EvaluationContext<'_> {
                 if guard_passed {
                     let result = self.evaluate(&arm.body);
                     self.environment.exit_block();
+                    if self.is_suspending() {
+                        self.record_resume_point(ResumePoint::Arm(k));
+                    }
                     return result;
                 }
             }
@@ -44,6 +44,13 @@ pub fn build_default_registry() -> HashMap<&'static str, ExternFn> {
     // `i64::MIN` stays at `i64::MIN` (matches the legacy
     // `BuiltinMethod::I64Abs` semantics that the prelude replaces).
     m.insert("__extern_abs_i64", extern_abs_i64);
+    // u64 bit manipulation — used by `core/std/bits.t`'s `BitOps`
+    // impls (the i64 impl reinterprets through `as u64`).
+    m.insert("__extern_count_ones_u64", extern_count_ones_u64);
+    m.insert("__extern_leading_zeros_u64", extern_leading_zeros_u64);
+    m.insert("__extern_trailing_zeros_u64", extern_trailing_zeros_u64);
+    m.insert("__extern_rotate_left_u64", extern_rotate_left_u64);
+    m.insert("__extern_rotate_right_u64", extern_rotate_right_u64);
 
     // Test-only aliases used by Phase 1/2 regression tests so we can
     // exercise the extern dispatch without rewriting math.t yet.
@@ -120,6 +127,53 @@ fn extern_abs_i64(args: &[Value]) -> Result<Value, InterpreterError> {
     }
 }
 
+fn u64_args<const N: usize>(name: &str, args: &[Value]) -> Result<[u64; N], InterpreterError> {
+    if args.len() != N {
+        return Err(InterpreterError::FunctionParameterMismatch {
+            message: format!("extern fn `{name}` takes {N} argument(s)"),
+            expected: N,
+            found: args.len(),
+        });
+    }
+    let mut out = [0u64; N];
+    for (slot, arg) in out.iter_mut().zip(args) {
+        *slot = match arg {
+            Value::UInt64(v) => *v,
+            other => return Err(InterpreterError::InternalError(format!(
+                "extern fn `{name}` expects u64 arguments, got {other:?}"
+            ))),
+        };
+    }
+    Ok(out)
+}
+
+fn extern_count_ones_u64(args: &[Value]) -> Result<Value, InterpreterError> {
+    let [x] = u64_args("count_ones", args)?;
+    Ok(Value::UInt64(u64::from(x.count_ones())))
+}
+
+fn extern_leading_zeros_u64(args: &[Value]) -> Result<Value, InterpreterError> {
+    let [x] = u64_args("leading_zeros", args)?;
+    Ok(Value::UInt64(u64::from(x.leading_zeros())))
+}
+
+fn extern_trailing_zeros_u64(args: &[Value]) -> Result<Value, InterpreterError> {
+    let [x] = u64_args("trailing_zeros", args)?;
+    Ok(Value::UInt64(u64::from(x.trailing_zeros())))
+}
+
+// Rotate amounts are reduced modulo 64 (same as cranelift's
+// `rotl` / `rotr`), so any `u64` amount is well-defined.
+fn extern_rotate_left_u64(args: &[Value]) -> Result<Value, InterpreterError> {
+    let [x, n] = u64_args("rotate_left", args)?;
+    Ok(Value::UInt64(x.rotate_left((n % 64) as u32)))
+}
+
+fn extern_rotate_right_u64(args: &[Value]) -> Result<Value, InterpreterError> {
+    let [x, n] = u64_args("rotate_right", args)?;
+    Ok(Value::UInt64(x.rotate_right((n % 64) as u32)))
+}
+
 fn extern_pow_f64(args: &[Value]) -> Result<Value, InterpreterError> {
     if args.len() != 2 {
         return Err(InterpreterError::FunctionParameterMismatch {
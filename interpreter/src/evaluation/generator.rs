@@ -0,0 +1,357 @@
+//! Generator functions: calling a function declared to return
+//! `Generator<T>` builds a suspended generator instead of running the
+//! body, and each `next()` runs the body up to its next `yield`.
+//!
+//! The tree-walker has no native stack to park, so a suspended
+//! generator is a saved continuation: the block scopes and auto-drop
+//! scopes that were live at the `yield`, plus the path of resume
+//! points (statement index in each block, which loop / branch / match
+//! arm was executing) leading down to it. `yield` unwinds to the
+//! driver as `Return(None)` with `generator_suspend` set, and every
+//! construct on the way out records its resume point. On the next
+//! `next()` the driver re-enters the body, each construct on the path
+//! consumes its point instead of re-evaluating conditions or earlier
+//! statements, and `Environment::enter_block` / `enter_drop_scope`
+//! hand back the saved scopes in place of fresh ones. The type checker
+//! only admits `yield` where this replay is possible
+//! (`check_yield_placement`).
+//!
+//! A `Task` is a `Generator<()>` whose switch points are the
+//! `yield_now()` statements of its body: each one suspends the task
+//! exactly like `yield ()`. `spawn` queues a task on the context's run
+//! queue, and a `yield_now()` anywhere else resumes every queued task
+//! once (`run_tasks_once`).
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use frontend::ast::{BuiltinFunction, Expr, ExprRef, Function, Stmt, StmtRef};
+use frontend::type_decl::TypeDecl;
+use string_interner::DefaultSymbol;
+use crate::environment::VariableValue;
+use crate::error::InterpreterError;
+use crate::object::Object;
+use crate::value::Value;
+use super::{DropEntry, EvaluationContext, EvaluationResult};
+
+/// One step of the path from a generator's body down to the `yield` it
+/// is suspended at. Recorded innermost first while the `yield` unwinds,
+/// consumed outermost first on resume.
+#[derive(Debug, Clone)]
+pub(crate) enum ResumePoint {
+    /// A block was executing its `index`-th statement.
+    Stmt(usize),
+    /// The suspending `yield` itself; its block continues after it.
+    Yield,
+    /// A `while` body; the condition is not re-evaluated on re-entry.
+    Loop,
+    /// A range `for` body at counter `current` of the range ending at `end`.
+    Range { current: Value, end: Value },
+    /// Branch `k` of an if/elif/else (`0` is `then`, the last is `else`).
+    Branch(usize),
+    /// The body of match arm `k`; its pattern bindings live in the saved scopes.
+    Arm(usize),
+}
+
+/// Everything needed to continue a generator body after a `yield`.
+#[derive(Debug)]
+pub(crate) struct SavedFrame {
+    /// Block scopes from the body's parameter scope inward.
+    scopes: Vec<HashMap<DefaultSymbol, VariableValue>>,
+    /// Auto-drop scopes from the body block inward. Their drops only
+    /// run once the generator resumes and leaves those blocks.
+    drop_scopes: Vec<Vec<DropEntry>>,
+    /// Innermost first.
+    path: Vec<ResumePoint>,
+}
+
+/// Set by `yield` and taken by the driver once the body has unwound.
+#[derive(Debug)]
+pub(crate) struct Suspension {
+    value: Value,
+    frame: SavedFrame,
+}
+
+#[derive(Debug)]
+enum GeneratorState {
+    /// Not started yet; holds the call's argument values.
+    Fresh(Vec<Value>),
+    Suspended(SavedFrame),
+    /// Inside `next()`. A body that reaches its own generator again
+    /// would otherwise resume a frame that is still on the Rust stack.
+    Running,
+    Done,
+}
+
+/// Runtime value of a `Generator<T>`.
+#[derive(Debug)]
+pub struct Generator {
+    function: Rc<Function>,
+    state: GeneratorState,
+    /// The `yield_now()` statements that suspend a `Task` body,
+    /// collected on its first resume. Always empty for other generators.
+    switch_points: Option<Rc<HashSet<ExprRef>>>,
+}
+
+/// A generator body currently running.
+#[derive(Debug)]
+pub(crate) struct GeneratorFrame {
+    /// Environment depth at the body's entry; a `yield` saves the scopes above it.
+    env_base: usize,
+    /// Drop-scope depth at the body's entry.
+    drop_base: usize,
+    switch_points: Rc<HashSet<ExprRef>>,
+}
+
+impl Generator {
+    /// Item type `T` of the function's `Generator<T>` return type.
+    pub fn item_type(&self) -> TypeDecl {
+        match &self.function.return_type {
+            Some(TypeDecl::Generator(item)) => (**item).clone(),
+            _ => TypeDecl::Unknown,
+        }
+    }
+}
+
+impl EvaluationContext<'_> {
+    pub(super) fn is_generator_function(function: &Function) -> bool {
+        matches!(function.return_type, Some(TypeDecl::Generator(_)))
+    }
+
+    /// The value a call to a generator function evaluates to.
+    pub(super) fn make_generator(function: Rc<Function>, args: Vec<Value>) -> Value {
+        let generator = Generator { function, state: GeneratorState::Fresh(args), switch_points: None };
+        Object::Generator(Rc::new(RefCell::new(generator))).into()
+    }
+
+    /// `gen.next()`: run the body up to its next `yield` and return
+    /// `Some(value)`, or `None` once the body has finished.
+    pub(super) fn resume_generator(&mut self, generator: &Rc<RefCell<Generator>>) -> Result<Value, InterpreterError> {
+        let item = generator.borrow().item_type();
+        let value = self.step_generator(generator)?;
+        Ok(self.make_option(value, item))
+    }
+
+    /// Run a generator body up to its next `yield` (`Some(value)`) or
+    /// to its end (`None`).
+    fn step_generator(&mut self, generator: &Rc<RefCell<Generator>>) -> Result<Option<Value>, InterpreterError> {
+        let (function, state) = {
+            let mut g = generator.borrow_mut();
+            let state = std::mem::replace(&mut g.state, GeneratorState::Running);
+            (g.function.clone(), state)
+        };
+        let statements = match self.stmt_pool.get(&function.code) {
+            Some(Stmt::Expression(e)) => self.expr_pool.stmt_list_of(&e),
+            _ => None,
+        }
+        .ok_or_else(|| InterpreterError::InternalError(format!(
+            "resume_generator: function body is not a block {:?}", function.code
+        )))?;
+        let switch_points = self.switch_points_of(generator, statements);
+
+        let env_base = self.environment.depth();
+        let drop_base = self.drop_scopes.len();
+        match state {
+            GeneratorState::Fresh(args) => {
+                self.environment.enter_block();
+                for ((name, _), value) in function.parameter.iter().zip(args) {
+                    self.environment.set_val(*name, value);
+                }
+            }
+            GeneratorState::Suspended(frame) => {
+                self.environment.queue_resume_scopes(frame.scopes);
+                self.resume_drop_scopes = frame.drop_scopes.into_iter().rev().collect();
+                self.generator_resume = frame.path;
+                self.environment.enter_block();
+            }
+            GeneratorState::Running => {
+                generator.borrow_mut().state = GeneratorState::Running;
+                return Err(InterpreterError::panic("generator resumed while it is already running"));
+            }
+            GeneratorState::Done => {
+                generator.borrow_mut().state = GeneratorState::Done;
+                return Ok(None);
+            }
+        }
+
+        self.generator_frames.push(GeneratorFrame { env_base, drop_base, switch_points });
+        let result = self.evaluate_block(statements);
+        self.generator_frames.pop();
+        self.environment.exit_block();
+        let suspension = self.generator_suspend.take();
+
+        match result {
+            Err(e) => {
+                self.generator_resume.clear();
+                self.resume_drop_scopes.clear();
+                self.environment.queue_resume_scopes(Vec::new());
+                generator.borrow_mut().state = GeneratorState::Done;
+                Err(self.panic_frame(e, function.name))
+            }
+            Ok(_) => match suspension {
+                Some(Suspension { value, frame }) => {
+                    generator.borrow_mut().state = GeneratorState::Suspended(frame);
+                    Ok(Some(value))
+                }
+                None => {
+                    generator.borrow_mut().state = GeneratorState::Done;
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    /// The switch points of `generator`'s body, collected once. Only a
+    /// `Task` (`Generator<()>`) has any.
+    fn switch_points_of(&self, generator: &Rc<RefCell<Generator>>, body: &[StmtRef]) -> Rc<HashSet<ExprRef>> {
+        let mut g = generator.borrow_mut();
+        if g.switch_points.is_none() {
+            let mut points = HashSet::new();
+            if g.item_type() == TypeDecl::Unit {
+                self.collect_switch_points(body, &mut points);
+            }
+            g.switch_points = Some(Rc::new(points));
+        }
+        g.switch_points.clone().unwrap_or_default()
+    }
+
+    /// Walk the same positions `check_yield_placement` admits a `yield`
+    /// in, collecting the `yield_now()` expression statements.
+    fn collect_switch_points(&self, statements: &[StmtRef], points: &mut HashSet<ExprRef>) {
+        for stmt in statements {
+            match self.stmt_pool.get(stmt) {
+                Some(Stmt::Expression(e)) => self.collect_switch_points_in_expr(&e, points),
+                Some(Stmt::While(_, _, body)) | Some(Stmt::For(_, _, _, _, body)) => {
+                    self.collect_switch_points_in_expr(&body, points)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_switch_points_in_expr(&self, expr: &ExprRef, points: &mut HashSet<ExprRef>) {
+        match self.expr_pool.get(expr) {
+            Some(Expr::BuiltinCall(BuiltinFunction::YieldNow, _)) => {
+                points.insert(*expr);
+            }
+            Some(Expr::Block(statements)) => self.collect_switch_points(&statements, points),
+            Some(Expr::IfElifElse(_, then, elifs, els)) => {
+                self.collect_switch_points_in_expr(&then, points);
+                for (_, block) in &elifs {
+                    self.collect_switch_points_in_expr(block, points);
+                }
+                self.collect_switch_points_in_expr(&els, points);
+            }
+            Some(Expr::Match(_, arms)) => {
+                for arm in &arms {
+                    self.collect_switch_points_in_expr(&arm.body, points);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `expr`, an expression statement, is a switch point of the
+    /// task body currently running.
+    pub(super) fn is_task_switch_point(&self, expr: &ExprRef) -> bool {
+        self.generator_frames
+            .last()
+            .is_some_and(|frame| frame.switch_points.contains(expr))
+    }
+
+    /// `yield_now()` outside a task body: resume every task queued
+    /// before the call once, requeueing those that suspend again.
+    /// Tasks that are running further up the stack are skipped. Returns
+    /// whether any task ran.
+    pub(super) fn run_tasks_once(&mut self) -> Result<bool, InterpreterError> {
+        let mut ran = false;
+        for _ in 0..self.tasks.len() {
+            let Some(task) = self.tasks.pop_front() else { break };
+            if matches!(task.borrow().state, GeneratorState::Running) {
+                self.tasks.push_back(task);
+                continue;
+            }
+            ran = true;
+            if self.step_generator(&task)?.is_some() {
+                self.tasks.push_back(task);
+            }
+        }
+        Ok(ran)
+    }
+
+    /// `Option<T>` carrying a generator item of type `item`.
+    fn make_option(&mut self, value: Option<Value>, item: TypeDecl) -> Value {
+        let option_sym = self.string_interner.get_or_intern("Option");
+        let (variant, values) = match value {
+            Some(v) => ("Some", vec![v.into_rc()]),
+            None => ("None", Vec::new()),
+        };
+        let variant_name = self.string_interner.get_or_intern(variant);
+        Object::EnumVariant {
+            enum_name: option_sym,
+            variant_name,
+            values,
+            type_args: vec![item],
+        }
+        .into()
+    }
+
+    /// `yield value`: evaluate the value, save the live scopes and
+    /// unwind to the driver.
+    pub(super) fn handle_yield(&mut self, expr: &ExprRef) -> Result<EvaluationResult, InterpreterError> {
+        use crate::try_value_v;
+        let res = self.evaluate(expr);
+        let value = try_value_v!(res);
+        self.suspend(value)
+    }
+
+    /// Save the live scopes of the innermost generator body and unwind
+    /// to its driver, which hands `value` to the consumer.
+    pub(super) fn suspend(&mut self, value: Value) -> Result<EvaluationResult, InterpreterError> {
+        let frame = self.generator_frames.last().ok_or_else(|| {
+            InterpreterError::InternalError("yield outside a generator body".to_string())
+        })?;
+        let frame = SavedFrame {
+            scopes: self.environment.snapshot_scopes(frame.env_base),
+            drop_scopes: self.drop_scopes[frame.drop_base..].to_vec(),
+            path: vec![ResumePoint::Yield],
+        };
+        self.generator_suspend = Some(Suspension { value, frame });
+        Ok(EvaluationResult::Return(None))
+    }
+
+    /// Whether a `yield` is unwinding to its generator's driver.
+    pub(super) fn is_suspending(&self) -> bool {
+        self.generator_suspend.is_some()
+    }
+
+    /// Record where the unwinding `yield` passed through.
+    pub(super) fn record_resume_point(&mut self, point: ResumePoint) {
+        if let Some(suspension) = &mut self.generator_suspend {
+            suspension.frame.path.push(point);
+        }
+    }
+
+    /// The next resume point while a generator is replaying its way
+    /// back to the `yield` it suspended at; `None` otherwise.
+    pub(super) fn take_resume_point(&mut self) -> Option<ResumePoint> {
+        self.generator_resume.pop()
+    }
+
+    /// Where a block resuming at `Stmt(index)` continues: after the
+    /// `yield` when it is that statement, otherwise at the statement
+    /// that still holds the rest of the path.
+    pub(super) fn resume_block_start(&mut self, index: usize) -> usize {
+        if matches!(self.generator_resume.last(), Some(ResumePoint::Yield)) {
+            self.generator_resume.pop();
+            debug_assert!(self.resume_drop_scopes.is_empty() && self.environment.resume_finished());
+            index + 1
+        } else {
+            index
+        }
+    }
+
+    pub(super) fn unexpected_resume_point(point: ResumePoint, site: &str) -> InterpreterError {
+        InterpreterError::InternalError(format!("generator resumed at {point:?} inside {site}"))
+    }
+}
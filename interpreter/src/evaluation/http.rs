@@ -0,0 +1,164 @@
+// Minimal blocking HTTP/1.1 client backing the `http_get` builtin.
+//
+// One request per call over a fresh `TcpStream`, sent with
+// `Connection: close` so the body simply runs to end of stream unless
+// the response gives a `Content-Length` or uses chunked encoding.
+// Plain `http://` only: there is no TLS stack in the tree, so an
+// `https://` URL is an error rather than a silent downgrade.
+// Redirects are not followed; the 3xx status comes back to the
+// script like any other.
+//
+// Built only with the `http` feature (on by default). Without it the
+// builtin still type-checks and fails at run time with a message
+// naming the feature.
+
+/// Connect / read / write timeout. A hung server ends the call with
+/// an error instead of stalling the run forever.
+#[cfg(feature = "http")]
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// `GET url`, returning the status code and the body (lossy UTF-8).
+#[cfg(feature = "http")]
+pub fn get(url: &str) -> Result<(u64, String), String> {
+    use std::io::{Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let target = Url::parse(url)?;
+    let addr = (target.host.as_str(), target.port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("cannot resolve host `{}`", target.host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: toylang\r\nAccept: */*\r\nConnection: close\r\n\r\n",
+        target.path,
+        target.host_header()
+    );
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+    let (status, body) = parse_response(&raw)?;
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+#[cfg(not(feature = "http"))]
+pub fn get(_url: &str) -> Result<(u64, String), String> {
+    Err("this interpreter was built without the `http` feature".to_string())
+}
+
+#[cfg(feature = "http")]
+struct Url {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+#[cfg(feature = "http")]
+impl Url {
+    fn parse(url: &str) -> Result<Url, String> {
+        let rest = match url.split_once("://") {
+            Some(("http", rest)) => rest,
+            Some(("https", _)) => return Err("https is not supported (no TLS in the built-in client)".to_string()),
+            Some((scheme, _)) => return Err(format!("unsupported URL scheme `{scheme}`")),
+            None => return Err("URL must start with `http://`".to_string()),
+        };
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) if rest.as_bytes()[i] == b'?' => (&rest[..i], format!("/{}", &rest[i..])),
+            Some(i) => (&rest[..i], rest[i..].to_string()),
+            None => (rest, "/".to_string()),
+        };
+        // Drop any `#fragment`: it never goes on the wire.
+        let path = match path.split_once('#') {
+            Some((p, _)) => p.to_string(),
+            None => path,
+        };
+        if authority.contains('@') {
+            return Err("credentials in the URL are not supported".to_string());
+        }
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((h, p)) if !h.is_empty() => {
+                let port = p.parse::<u16>().map_err(|_| format!("invalid port `{p}`"))?;
+                (h, port)
+            }
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err("URL has no host".to_string());
+        }
+        Ok(Url { host: host.to_string(), port, path })
+    }
+
+    fn host_header(&self) -> String {
+        if self.port == 80 {
+            self.host.clone()
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Split a raw response into its status code and decoded body.
+#[cfg(feature = "http")]
+fn parse_response(raw: &[u8]) -> Result<(u64, Vec<u8>), String> {
+    let head_end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| "malformed response: no end of headers".to_string())?;
+    let head = String::from_utf8_lossy(&raw[..head_end]);
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or("");
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .filter(|_| status_line.starts_with("HTTP/"))
+        .and_then(|code| code.parse::<u64>().ok())
+        .ok_or_else(|| format!("malformed status line `{status_line}`"))?;
+    let mut chunked = false;
+    let mut content_length = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse::<usize>().ok();
+        }
+    }
+    let body = &raw[head_end + 4..];
+    let body = if chunked {
+        decode_chunked(body)?
+    } else if let Some(len) = content_length {
+        body[..len.min(body.len())].to_vec()
+    } else {
+        body.to_vec()
+    };
+    Ok((status, body))
+}
+
+#[cfg(feature = "http")]
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| "malformed chunked body".to_string())?;
+        let size_field = String::from_utf8_lossy(&body[..line_end]);
+        // Chunk extensions (`;name=value`) carry nothing we need.
+        let size_hex = size_field.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| format!("malformed chunk size `{size_hex}`"))?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Ok(out);
+        }
+        if body.len() < size {
+            return Err("chunked body ends early".to_string());
+        }
+        out.extend_from_slice(&body[..size]);
+        body = body[size..].strip_prefix(b"\r\n").unwrap_or(&body[size..]);
+    }
+}
@@ -0,0 +1,401 @@
+// JSON text <-> `Object` conversion backing the `json_parse` /
+// `json_stringify` builtins.
+//
+// Mapping:
+//   null            <-> Null / Unit
+//   true / false    <-> Bool
+//   integer literal  -> Int64 (Float64 when it doesn't fit in i64)
+//   other number     -> Float64
+//   string          <-> String (ConstString is accepted on encode)
+//   array           <-> Array (Tuple is accepted on encode)
+//   object          <-> Dict keyed by interned strings, so
+//                       `v["name"]` finds the key the same way it
+//                       would in a `dict{"name": ...}` literal
+//   struct           -> object in field declaration order
+//
+// Encoding sorts dict keys so the output doesn't depend on HashMap
+// iteration order, and writes integral floats with a trailing `.0`
+// so a round trip keeps them `f64`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+use crate::object::{Object, ObjectKey, RcObject};
+use super::StructRegistryEntry;
+
+/// Deeper nesting than this is rejected instead of risking the host
+/// stack on hostile input.
+const MAX_DEPTH: usize = 512;
+
+pub fn parse(text: &str, interner: &mut DefaultStringInterner) -> Result<Object, String> {
+    let mut p = Parser { src: text.as_bytes(), pos: 0, interner };
+    p.skip_ws();
+    let value = p.parse_value(0)?;
+    p.skip_ws();
+    if p.pos != p.src.len() {
+        return Err(p.error("trailing characters after JSON value"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+    interner: &'a mut DefaultStringInterner,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> String {
+        format!("{msg} at byte {}", self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_keyword(&mut self, word: &str, value: Object) -> Result<Object, String> {
+        if self.src[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<Object, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.expect_keyword("null", Object::null_unknown()),
+            Some(b't') => self.expect_keyword("true", Object::Bool(true)),
+            Some(b'f') => self.expect_keyword("false", Object::Bool(false)),
+            Some(b'"') => Ok(Object::String(self.parse_string()?)),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'{') => self.parse_object(depth),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<Object, String> {
+        self.pos += 1;
+        let mut items: Vec<RcObject> = Vec::new();
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Object::Array(Box::new(items)));
+        }
+        loop {
+            self.skip_ws();
+            let item = self.parse_value(depth + 1)?;
+            items.push(Rc::new(RefCell::new(item)));
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Object::Array(Box::new(items)));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    // Keys are always interned strings, never the interior-mutable
+    // compound objects the lint worries about.
+    #[allow(clippy::mutable_key_type)]
+    fn parse_object(&mut self, depth: usize) -> Result<Object, String> {
+        self.pos += 1;
+        let mut entries: HashMap<ObjectKey, RcObject> = HashMap::new();
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Object::Dict(Box::new(entries)));
+        }
+        loop {
+            self.skip_ws();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected string key"));
+            }
+            let key = self.parse_string()?;
+            self.skip_ws();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+            self.skip_ws();
+            let value = self.parse_value(depth + 1)?;
+            let sym = self.interner.get_or_intern(&key);
+            // Duplicate keys: the last one wins, as in most parsers.
+            entries.insert(ObjectKey::new(Object::ConstString(sym)), Rc::new(RefCell::new(value)));
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Object::Dict(Box::new(entries)));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .src
+            .get(self.pos..self.pos + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out: Vec<u8> = Vec::new();
+        loop {
+            let Some(b) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match b {
+                b'"' => break,
+                b'\\' => {
+                    let esc = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    let ch = match esc {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let hi = self.parse_hex4()?;
+                            let code = if (0xD800..0xDC00).contains(&hi) {
+                                // High surrogate: must pair with a low one.
+                                if !self.src[self.pos..].starts_with(b"\\u") {
+                                    return Err(self.error("unpaired surrogate"));
+                                }
+                                self.pos += 2;
+                                let lo = self.parse_hex4()?;
+                                if !(0xDC00..0xE000).contains(&lo) {
+                                    return Err(self.error("unpaired surrogate"));
+                                }
+                                0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00)
+                            } else {
+                                hi
+                            };
+                            char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buf = [0u8; 4];
+                    out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                }
+                0x00..=0x1F => return Err(self.error("control character in string")),
+                _ => out.push(b),
+            }
+        }
+        // The input is a `&str`, so unescaped bytes are already valid
+        // UTF-8 and escapes only ever append whole characters.
+        String::from_utf8(out).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn parse_number(&mut self) -> Result<Object, String> {
+        let start = self.pos;
+        let mut integral = true;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+        if self.peek() == Some(b'.') {
+            integral = false;
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            integral = false;
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        // Only ASCII digits, signs, '.' and exponent markers were consumed.
+        let text = std::str::from_utf8(&self.src[start..self.pos]).unwrap_or_default();
+        if integral {
+            if let Ok(v) = text.parse::<i64>() {
+                return Ok(Object::Int64(v));
+            }
+        }
+        text.parse::<f64>()
+            .map(Object::Float64)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+}
+
+pub fn stringify(
+    value: &Object,
+    interner: &DefaultStringInterner,
+    structs: &HashMap<DefaultSymbol, StructRegistryEntry>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    write_value(&mut out, value, interner, structs, 0)?;
+    Ok(out)
+}
+
+fn write_value(
+    out: &mut String,
+    value: &Object,
+    interner: &DefaultStringInterner,
+    structs: &HashMap<DefaultSymbol, StructRegistryEntry>,
+    depth: usize,
+) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("value nested too deeply (or cyclic)".to_string());
+    }
+    match value {
+        Object::Null(_) | Object::Unit => out.push_str("null"),
+        Object::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Object::Int64(v) => out.push_str(&v.to_string()),
+        Object::UInt64(v) => out.push_str(&v.to_string()),
+        Object::Int8(v) => out.push_str(&v.to_string()),
+        Object::Int16(v) => out.push_str(&v.to_string()),
+        Object::Int32(v) => out.push_str(&v.to_string()),
+        Object::UInt8(v) => out.push_str(&v.to_string()),
+        Object::UInt16(v) => out.push_str(&v.to_string()),
+        Object::UInt32(v) => out.push_str(&v.to_string()),
+        Object::Float64(v) => {
+            if !v.is_finite() {
+                // JSON has no NaN / infinity; follow JavaScript.
+                out.push_str("null");
+            } else if v.fract() == 0.0 && v.abs() < 1e16 {
+                out.push_str(&format!("{v:.1}"));
+            } else {
+                out.push_str(&v.to_string());
+            }
+        }
+        Object::ConstString(sym) => write_string(out, interner.resolve(*sym).unwrap_or("")),
+        Object::String(s) => write_string(out, s),
+        Object::Array(items) | Object::Tuple(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, &item.borrow(), interner, structs, depth + 1)?;
+            }
+            out.push(']');
+        }
+        Object::Dict(entries) => {
+            let mut keyed: Vec<(String, &RcObject)> = Vec::with_capacity(entries.len());
+            for (key, v) in entries.iter() {
+                let name = match key.as_object() {
+                    Object::ConstString(sym) => interner.resolve(*sym).unwrap_or("").to_string(),
+                    Object::String(s) => s.clone(),
+                    // JSON keys are strings; other key types are written
+                    // in their display form, like Python's `json`.
+                    other => other.to_display_string(interner),
+                };
+                keyed.push((name, v));
+            }
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            out.push('{');
+            for (i, (name, v)) in keyed.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, &name);
+                out.push(':');
+                write_value(out, &v.borrow(), interner, structs, depth + 1)?;
+            }
+            out.push('}');
+        }
+        Object::Struct { type_name, fields, .. } => {
+            // Declaration order when the struct is registered, name
+            // order otherwise.
+            let order: Vec<DefaultSymbol> = match structs.get(type_name) {
+                Some(entry) => entry.fields.iter().map(|(sym, _)| *sym).collect(),
+                None => {
+                    let mut syms: Vec<DefaultSymbol> = fields.keys().copied().collect();
+                    syms.sort_by_key(|s| interner.resolve(*s).unwrap_or(""));
+                    syms
+                }
+            };
+            out.push('{');
+            let mut first = true;
+            for sym in order {
+                let Some(v) = fields.get(&sym) else { continue };
+                if !first {
+                    out.push(',');
+                }
+                first = false;
+                write_string(out, interner.resolve(sym).unwrap_or(""));
+                out.push(':');
+                write_value(out, &v.borrow(), interner, structs, depth + 1)?;
+            }
+            out.push('}');
+        }
+        other => {
+            return Err(format!("cannot encode {} as JSON", kind_name(other)));
+        }
+    }
+    Ok(())
+}
+
+fn kind_name(value: &Object) -> &'static str {
+    match value {
+        Object::Bytes(_) => "bytes",
+        Object::Pointer(_) => "a pointer",
+        Object::Allocator(_) => "an allocator",
+        Object::EnumVariant { .. } => "an enum value",
+        Object::Range { .. } => "a range",
+        Object::Closure { .. } => "a closure",
+        _ => "this value",
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
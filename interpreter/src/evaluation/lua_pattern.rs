@@ -0,0 +1,426 @@
+// Lua pattern matching backing the `matches` / `find` /
+// `replace_pattern` builtins.
+//
+// A port of the matcher in Lua 5.4's `lstrlib.c`, so a Lua backend can
+// hand the same pattern to `string.find` / `string.match` /
+// `string.gsub` unchanged:
+//   .  %a %c %d %g %l %p %s %u %w %x   classes (upper case negates)
+//   [set] [^set]                       sets, with ranges and classes
+//   * + - ?                            repetition (`-` is lazy)
+//   ^ $                                anchors
+//   ( )  %1-%9                         captures and back-references
+//   %bxy  %f[set]                      balanced match, frontier
+// Matching works on bytes and the classes are ASCII-only, as in Lua.
+// Position captures `()` are rejected: Lua numbers them from 1, every
+// other index in the language counts from 0.
+
+/// Same limit as Lua's `MAXCCALLS`: deeper backtracking is reported
+/// as an error instead of risking the host stack.
+const MAX_DEPTH: usize = 200;
+const MAX_CAPTURES: usize = 32;
+
+/// A successful match: the byte range of the whole match and of each
+/// capture.
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub captures: Vec<(usize, usize)>,
+}
+
+impl Match {
+    /// What Lua's `string.match` returns first: the first capture, or
+    /// the whole match when the pattern has none.
+    pub fn first_capture(&self) -> (usize, usize) {
+        self.captures.first().copied().unwrap_or((self.start, self.end))
+    }
+}
+
+/// First match of `pattern` in `src`, like `string.find(src, pattern)`.
+pub fn find(src: &[u8], pattern: &[u8]) -> Result<Option<Match>, String> {
+    let (anchor, p) = split_anchor(pattern);
+    let mut ms = MatchState::new(src, pattern);
+    let mut start = 0;
+    loop {
+        ms.reset();
+        if let Some(end) = ms.do_match(start, p)? {
+            return Ok(Some(Match { start, end, captures: ms.captures()? }));
+        }
+        start += 1;
+        if anchor || start > src.len() {
+            return Ok(None);
+        }
+    }
+}
+
+/// Every match of `pattern` in `src` replaced by `repl`, like
+/// `string.gsub(src, pattern, repl)`. In `repl`, `%0` is the whole
+/// match, `%1`-`%9` a capture and `%%` a literal `%`.
+pub fn replace(src: &[u8], pattern: &[u8], repl: &[u8]) -> Result<Vec<u8>, String> {
+    let (anchor, p) = split_anchor(pattern);
+    let mut ms = MatchState::new(src, pattern);
+    let mut out = Vec::with_capacity(src.len());
+    let mut pos = 0;
+    // An empty match right where the previous match ended is skipped,
+    // as in Lua 5.4, so `%w*` replaces a word once rather than twice.
+    let mut last_end = None;
+    loop {
+        ms.reset();
+        match ms.do_match(pos, p)? {
+            Some(end) if Some(end) != last_end => {
+                let m = Match { start: pos, end, captures: ms.captures()? };
+                expand_replacement(src, &m, repl, &mut out)?;
+                pos = end;
+                last_end = Some(end);
+            }
+            _ if pos < src.len() => {
+                out.push(src[pos]);
+                pos += 1;
+            }
+            _ => break,
+        }
+        if anchor {
+            break;
+        }
+    }
+    out.extend_from_slice(&src[pos..]);
+    Ok(out)
+}
+
+fn split_anchor(pattern: &[u8]) -> (bool, usize) {
+    if pattern.first() == Some(&b'^') { (true, 1) } else { (false, 0) }
+}
+
+fn expand_replacement(src: &[u8], m: &Match, repl: &[u8], out: &mut Vec<u8>) -> Result<(), String> {
+    let mut i = 0;
+    while i < repl.len() {
+        let c = repl[i];
+        i += 1;
+        if c != b'%' {
+            out.push(c);
+            continue;
+        }
+        match repl.get(i) {
+            Some(b'%') => out.push(b'%'),
+            Some(b'0') => out.extend_from_slice(&src[m.start..m.end]),
+            Some(d @ b'1'..=b'9') => {
+                let index = usize::from(d - b'1');
+                // `%1` with no captures is the whole match, as in Lua.
+                let (a, b) = match m.captures.get(index) {
+                    Some(range) => *range,
+                    None if index == 0 && m.captures.is_empty() => (m.start, m.end),
+                    None => return Err(format!("invalid capture index %{} in replacement string", index + 1)),
+                };
+                out.extend_from_slice(&src[a..b]);
+            }
+            _ => return Err("invalid use of '%' in replacement string".to_string()),
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// A capture's length while its `)` has not been reached yet.
+const UNFINISHED: isize = -1;
+
+struct MatchState<'a> {
+    src: &'a [u8],
+    pat: &'a [u8],
+    depth: usize,
+    level: usize,
+    capture: [(usize, isize); MAX_CAPTURES],
+}
+
+impl<'a> MatchState<'a> {
+    fn new(src: &'a [u8], pat: &'a [u8]) -> Self {
+        Self { src, pat, depth: 0, level: 0, capture: [(0, 0); MAX_CAPTURES] }
+    }
+
+    fn reset(&mut self) {
+        self.depth = 0;
+        self.level = 0;
+    }
+
+    fn captures(&self) -> Result<Vec<(usize, usize)>, String> {
+        self.capture[..self.level]
+            .iter()
+            .map(|&(start, len)| match usize::try_from(len) {
+                Ok(len) => Ok((start, start + len)),
+                Err(_) => Err("unfinished capture".to_string()),
+            })
+            .collect()
+    }
+
+    fn do_match(&mut self, s: usize, p: usize) -> Result<Option<usize>, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("pattern too complex".to_string());
+        }
+        let result = self.match_here(s, p);
+        self.depth -= 1;
+        result
+    }
+
+    fn match_here(&mut self, mut s: usize, mut p: usize) -> Result<Option<usize>, String> {
+        let pat = self.pat;
+        loop {
+            let Some(&pc) = pat.get(p) else {
+                return Ok(Some(s));
+            };
+            match pc {
+                b'(' => {
+                    if pat.get(p + 1) == Some(&b')') {
+                        return Err("position captures `()` are not supported".to_string());
+                    }
+                    return self.start_capture(s, p + 1);
+                }
+                b')' => return self.end_capture(s, p + 1),
+                b'$' if p + 1 == pat.len() => {
+                    return Ok((s == self.src.len()).then_some(s));
+                }
+                b'%' if pat.get(p + 1) == Some(&b'b') => {
+                    match self.match_balance(s, p + 2)? {
+                        Some(next) => {
+                            s = next;
+                            p += 4;
+                            continue;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                b'%' if pat.get(p + 1) == Some(&b'f') => {
+                    p += 2;
+                    if pat.get(p) != Some(&b'[') {
+                        return Err("missing '[' after '%f' in pattern".to_string());
+                    }
+                    let ep = self.class_end(p)?;
+                    let prev = if s == 0 { 0 } else { self.src[s - 1] };
+                    let cur = self.src.get(s).copied().unwrap_or(0);
+                    if !self.match_bracket_class(prev, p, ep - 1)
+                        && self.match_bracket_class(cur, p, ep - 1)
+                    {
+                        p = ep;
+                        continue;
+                    }
+                    return Ok(None);
+                }
+                b'%' if pat.get(p + 1).is_some_and(u8::is_ascii_digit) => {
+                    match self.match_capture(s, pat[p + 1])? {
+                        Some(next) => {
+                            s = next;
+                            p += 2;
+                            continue;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                _ => {}
+            }
+            let ep = self.class_end(p)?;
+            let next = pat.get(ep).copied();
+            if !self.single_match(s, p, ep) {
+                // `*`, `?` and `-` also accept zero repetitions.
+                if matches!(next, Some(b'*' | b'?' | b'-')) {
+                    p = ep + 1;
+                    continue;
+                }
+                return Ok(None);
+            }
+            match next {
+                Some(b'?') => {
+                    if let Some(end) = self.do_match(s + 1, ep + 1)? {
+                        return Ok(Some(end));
+                    }
+                    p = ep + 1;
+                }
+                Some(b'+') => return self.max_expand(s + 1, p, ep),
+                Some(b'*') => return self.max_expand(s, p, ep),
+                Some(b'-') => return self.min_expand(s, p, ep),
+                _ => {
+                    s += 1;
+                    p = ep;
+                }
+            }
+        }
+    }
+
+    /// End of the single-character class starting at `p`.
+    fn class_end(&self, mut p: usize) -> Result<usize, String> {
+        let pat = self.pat;
+        let c = pat[p];
+        p += 1;
+        match c {
+            b'%' => {
+                if p >= pat.len() {
+                    return Err("malformed pattern (ends with '%')".to_string());
+                }
+                Ok(p + 1)
+            }
+            b'[' => {
+                if pat.get(p) == Some(&b'^') {
+                    p += 1;
+                }
+                // The first character is part of the set even when it
+                // is `]`.
+                loop {
+                    let Some(&c) = pat.get(p) else {
+                        return Err("malformed pattern (missing ']')".to_string());
+                    };
+                    p += 1;
+                    if c == b'%' && p < pat.len() {
+                        p += 1;
+                    }
+                    if pat.get(p) == Some(&b']') {
+                        return Ok(p + 1);
+                    }
+                }
+            }
+            _ => Ok(p),
+        }
+    }
+
+    fn single_match(&self, s: usize, p: usize, ep: usize) -> bool {
+        let Some(&c) = self.src.get(s) else {
+            return false;
+        };
+        match self.pat[p] {
+            b'.' => true,
+            b'%' => match_class(c, self.pat[p + 1]),
+            b'[' => self.match_bracket_class(c, p, ep - 1),
+            pc => pc == c,
+        }
+    }
+
+    /// Whether `c` is in the set running from the `[` at `p` to the
+    /// `]` at `ec`.
+    fn match_bracket_class(&self, c: u8, mut p: usize, ec: usize) -> bool {
+        let pat = self.pat;
+        let mut found = true;
+        if pat[p + 1] == b'^' {
+            found = false;
+            p += 1;
+        }
+        p += 1;
+        while p < ec {
+            if pat[p] == b'%' {
+                p += 1;
+                if match_class(c, pat[p]) {
+                    return found;
+                }
+            } else if pat[p + 1] == b'-' && p + 2 < ec {
+                if pat[p] <= c && c <= pat[p + 2] {
+                    return found;
+                }
+                p += 2;
+            } else if pat[p] == c {
+                return found;
+            }
+            p += 1;
+        }
+        !found
+    }
+
+    fn max_expand(&mut self, s: usize, p: usize, ep: usize) -> Result<Option<usize>, String> {
+        let mut count = 0;
+        while self.single_match(s + count, p, ep) {
+            count += 1;
+        }
+        loop {
+            if let Some(end) = self.do_match(s + count, ep + 1)? {
+                return Ok(Some(end));
+            }
+            if count == 0 {
+                return Ok(None);
+            }
+            count -= 1;
+        }
+    }
+
+    fn min_expand(&mut self, mut s: usize, p: usize, ep: usize) -> Result<Option<usize>, String> {
+        loop {
+            if let Some(end) = self.do_match(s, ep + 1)? {
+                return Ok(Some(end));
+            }
+            if !self.single_match(s, p, ep) {
+                return Ok(None);
+            }
+            s += 1;
+        }
+    }
+
+    fn start_capture(&mut self, s: usize, p: usize) -> Result<Option<usize>, String> {
+        if self.level >= MAX_CAPTURES {
+            return Err("too many captures".to_string());
+        }
+        self.capture[self.level] = (s, UNFINISHED);
+        self.level += 1;
+        let result = self.do_match(s, p)?;
+        if result.is_none() {
+            self.level -= 1;
+        }
+        Ok(result)
+    }
+
+    fn end_capture(&mut self, s: usize, p: usize) -> Result<Option<usize>, String> {
+        let Some(l) = (0..self.level).rev().find(|&l| self.capture[l].1 == UNFINISHED) else {
+            return Err("invalid pattern capture".to_string());
+        };
+        self.capture[l].1 = (s - self.capture[l].0) as isize;
+        let result = self.do_match(s, p)?;
+        if result.is_none() {
+            self.capture[l].1 = UNFINISHED;
+        }
+        Ok(result)
+    }
+
+    fn match_balance(&self, s: usize, p: usize) -> Result<Option<usize>, String> {
+        let (Some(&open), Some(&close)) = (self.pat.get(p), self.pat.get(p + 1)) else {
+            return Err("malformed pattern (missing arguments to '%b')".to_string());
+        };
+        if self.src.get(s) != Some(&open) {
+            return Ok(None);
+        }
+        let mut depth = 1;
+        for (i, &c) in self.src.iter().enumerate().skip(s + 1) {
+            if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(Some(i + 1));
+                }
+            } else if c == open {
+                depth += 1;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Back-reference `%1`-`%9`: the text of an earlier capture again.
+    fn match_capture(&self, s: usize, digit: u8) -> Result<Option<usize>, String> {
+        let index = usize::from(digit).wrapping_sub(usize::from(b'1'));
+        let Some(&(start, len)) = self.capture[..self.level].get(index) else {
+            return Err(format!("invalid capture index %{}", char::from(digit)));
+        };
+        let Ok(len) = usize::try_from(len) else {
+            return Err(format!("invalid capture index %{}", char::from(digit)));
+        };
+        let text = &self.src[start..start + len];
+        Ok(self.src[s..].starts_with(text).then_some(s + len))
+    }
+}
+
+/// `%x` class test; an upper-case class letter is the complement.
+fn match_class(c: u8, class: u8) -> bool {
+    let result = match class.to_ascii_lowercase() {
+        b'a' => c.is_ascii_alphabetic(),
+        b'c' => c.is_ascii_control(),
+        b'd' => c.is_ascii_digit(),
+        b'g' => c.is_ascii_graphic(),
+        b'l' => c.is_ascii_lowercase(),
+        b'p' => c.is_ascii_punctuation(),
+        b's' => c.is_ascii_whitespace() || c == 0x0b,
+        b'u' => c.is_ascii_uppercase(),
+        b'w' => c.is_ascii_alphanumeric(),
+        b'x' => c.is_ascii_hexdigit(),
+        _ => return class == c,
+    };
+    if class.is_ascii_uppercase() { !result } else { result }
+}
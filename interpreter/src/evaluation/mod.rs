@@ -12,6 +12,20 @@ use crate::heap::{Allocator, GlobalAllocator, HeapManager};
 
 pub mod extern_math;
 use extern_math::ExternFn;
+pub mod rng;
+pub mod capabilities;
+pub mod script_log;
+mod deep_clone;
+mod dict_key;
+mod display;
+mod http;
+mod json;
+mod lua_pattern;
+mod sockets;
+mod snapshot;
+mod struct_dict;
+mod timestamp;
+pub use snapshot::EvaluationSnapshot;
 
 /// Per-enum entry registered with the evaluation context. Carries
 /// enough info both for variant lookup at construction sites and for
@@ -57,6 +71,7 @@ mod statement;
 mod call;
 mod slice;
 mod builtin;
+pub(crate) mod generator;
 
 /// Whether `requires` and `ensures` clauses are evaluated at runtime. The
 /// fields default to "both on" so the interpreter has the same semantics
@@ -97,10 +112,10 @@ impl ContractMode {
             "post" => Self { check_pre: false, check_post: true },
             "off" | "0" | "false" => Self { check_pre: false, check_post: false },
             other => {
-                eprintln!(
+                crate::logger::warn(&format!(
                     "warning: INTERPRETER_CONTRACTS={other:?} not recognised; using `all`. \
                      Valid: all|pre|post|off"
-                );
+                ));
                 Self::default()
             }
         }
@@ -121,8 +136,9 @@ pub enum EvaluationResult {
     /// LABEL: optional target label for `break @label`. Loops match by
     /// `Some(sym) == loop_label_sym`; bare `break` (`None`) exits the
     /// innermost loop. The signal is forwarded up the block stack
-    /// until the matching loop consumes it.
-    Break(Option<DefaultSymbol>),
+    /// until the matching loop consumes it. The second field carries
+    /// the value of `break value`, which the loop yields as its result.
+    Break(Option<DefaultSymbol>, Option<Value>),
     /// LABEL: same dispatch convention as `Break`.
     Continue(Option<DefaultSymbol>),
 }
@@ -148,6 +164,17 @@ pub struct EvaluationContext<'a> {
     pub(super) function_qualified: HashMap<(Option<DefaultSymbol>, DefaultSymbol), Rc<Function>>,
     pub environment: Environment,
     pub(super) method_registry: HashMap<DefaultSymbol, HashMap<DefaultSymbol, Vec<MethodSpec>>>, // struct_name -> method_name -> [specs by target_type_args]
+    /// Impl-block functions without a `self` parameter, keyed by
+    /// `(struct_name, fn_name)`. Filled by `register_method` alongside
+    /// `method_registry` so `Type::helper(args)` dispatches straight
+    /// to the impl's function: no receiver is built, and a top-level
+    /// `fn helper` of the same name cannot shadow it.
+    pub(super) associated_functions: HashMap<(DefaultSymbol, DefaultSymbol), Rc<MethodFunction>>,
+    /// Builtin receiver methods (`s.len()`, `a.len()`, `s.split(..)`)
+    /// keyed by `BuiltinMethod::receiver_key` and the interned method
+    /// name. Built from `BuiltinMethod::registry_entries`, the same
+    /// table the type checker's `builtin_methods` registry comes from.
+    pub(super) builtin_methods: HashMap<(TypeDecl, DefaultSymbol), BuiltinMethod>,
     pub(super) null_object: RcObject, // Pre-created null object for reuse
     pub(super) recursion_depth: u32,
     pub(super) max_recursion_depth: u32,
@@ -205,6 +232,52 @@ pub struct EvaluationContext<'a> {
     /// scope. The depth mirrors `Environment::var` so block enter
     /// / exit and function call boundaries stay in lock-step.
     pub(super) drop_scopes: Vec<Vec<DropEntry>>,
+    /// State of the `seed` / `random` / `random_range` builtins. One
+    /// stream per evaluation context, so in-process runs (tests, the
+    /// REPL-less `run_source` entry point) never leak state into
+    /// each other.
+    pub(super) rng: rng::SplitMix64,
+    /// Reference point for the `clock()` builtin, captured when the
+    /// context is constructed (i.e. at the start of the run).
+    pub(super) start_instant: std::time::Instant,
+    /// Milliseconds on the simulated clock while the run is
+    /// deterministic (`set_deterministic`); `None` reads the host
+    /// clock. `now_millis` counts from the Unix epoch, `clock` from
+    /// zero, and only `sleep` moves it forward.
+    pub(super) virtual_millis: Option<u64>,
+    /// Host access the running program holds. Checked by every
+    /// side-effecting builtin via `require_capability`.
+    pub capabilities: capabilities::Capabilities,
+    /// Threshold and destination for the `log_*` builtins.
+    pub log: script_log::LogConfig,
+    /// Expression evaluations left before the run is cut off; `None`
+    /// (the default) never runs out. Compile-time evaluation sets it
+    /// so a pure call that loops forever cannot hang the compiler.
+    pub step_budget: Option<u64>,
+    /// Function and method bodies allowed to be running at once;
+    /// `None` (the default) leaves only `max_recursion_depth` and the
+    /// host stack. Compile-time evaluation sets it so deep recursion
+    /// cannot overflow the compiler's stack.
+    pub call_depth_limit: Option<u32>,
+    pub(super) call_depth: u32,
+    /// Execution counts for `--coverage`; `None` (the default) counts
+    /// nothing.
+    pub coverage: Option<crate::coverage::Coverage>,
+    /// Every generator body currently running, innermost last. A
+    /// `yield` saves the scopes the innermost one opened.
+    pub(super) generator_frames: Vec<generator::GeneratorFrame>,
+    /// Set by `yield` while it unwinds to its generator's `next()`.
+    pub(super) generator_suspend: Option<generator::Suspension>,
+    /// Resume points still to be consumed while a generator replays
+    /// its way back to the `yield` it suspended at, outermost last.
+    pub(super) generator_resume: Vec<generator::ResumePoint>,
+    /// Saved auto-drop scopes `enter_drop_scope` reinstates during
+    /// that replay, innermost first.
+    pub(super) resume_drop_scopes: Vec<Vec<DropEntry>>,
+    /// Run queue of the tasks `spawn` queued, in resume order.
+    pub(super) tasks: std::collections::VecDeque<Rc<RefCell<generator::Generator>>>,
+    /// Sockets opened by `tcp_listen` / `tcp_accept`, by handle.
+    pub(super) sockets: sockets::SocketTable,
 }
 
 /// Phase 5 (汎用 RAII): one auto-drop record. `name` is just for
@@ -240,6 +313,10 @@ impl<'a> EvaluationContext<'a> {
         let global_allocator: Rc<dyn Allocator> = Rc::new(GlobalAllocator::new(heap_manager.clone()));
         let allocator_stack: Vec<Rc<dyn Allocator>> = vec![global_allocator.clone()];
         let result_symbol = string_interner.get_or_intern("result");
+        let builtin_methods = BuiltinMethod::registry_entries()
+            .into_iter()
+            .map(|(receiver, name, method)| ((receiver, string_interner.get_or_intern(name)), method))
+            .collect();
         Self {
             stmt_pool,
             expr_pool,
@@ -248,6 +325,8 @@ impl<'a> EvaluationContext<'a> {
             function_qualified,
             environment: Environment::new(),
             method_registry: HashMap::new(),
+            associated_functions: HashMap::new(),
+            builtin_methods,
             null_object: Rc::new(RefCell::new(Object::null_unknown())),
             recursion_depth: 0,
             max_recursion_depth: 1000, // Increased to support deeper recursion like fib(20)
@@ -261,6 +340,34 @@ impl<'a> EvaluationContext<'a> {
             extern_registry: extern_math::build_default_registry(),
             drop_trait_structs: std::collections::HashSet::new(),
             drop_scopes: vec![Vec::new()],
+            rng: rng::SplitMix64::default(),
+            start_instant: std::time::Instant::now(),
+            virtual_millis: None,
+            capabilities: capabilities::Capabilities::default(),
+            log: script_log::LogConfig::default(),
+            step_budget: None,
+            call_depth_limit: None,
+            call_depth: 0,
+            coverage: None,
+            generator_frames: Vec::new(),
+            generator_suspend: None,
+            generator_resume: Vec::new(),
+            resume_drop_scopes: Vec::new(),
+            tasks: std::collections::VecDeque::new(),
+            sockets: sockets::SocketTable::default(),
+        }
+    }
+
+    /// Refuse `builtin` with a panic unless the run holds `cap`.
+    pub(super) fn require_capability(
+        &self,
+        cap: capabilities::Capability,
+        builtin: &str,
+    ) -> Result<(), InterpreterError> {
+        if self.capabilities.allows(cap) {
+            Ok(())
+        } else {
+            Err(InterpreterError::panic(cap.denied_message(builtin)))
         }
     }
 
@@ -306,6 +413,24 @@ impl<'a> EvaluationContext<'a> {
         self.contract_mode = mode;
     }
 
+    /// Count typed heap stores by type as well (see `HeapStats`).
+    pub fn set_trace_alloc(&mut self, on: bool) {
+        self.heap_manager.borrow_mut().trace_types = on;
+    }
+
+    /// Make the run reproducible from `seed`: the random-number
+    /// builtins start from it and the time builtins read a simulated
+    /// clock instead of the host's.
+    pub fn set_deterministic(&mut self, seed: u64) {
+        self.rng.seed(seed);
+        self.virtual_millis = Some(0);
+    }
+
+    /// Allocation counters of this context's heap so far.
+    pub fn heap_stats(&self) -> crate::heap::HeapStats {
+        self.heap_manager.borrow().stats().clone()
+    }
+
     pub fn register_enum(&mut self, name: DefaultSymbol, entry: EnumRegistryEntry) {
         self.enum_definitions.insert(name, entry);
     }
@@ -329,6 +454,17 @@ impl<'a> EvaluationContext<'a> {
         target_type_args: Vec<TypeDecl>,
         method: Rc<MethodFunction>,
     ) {
+        if !method.has_self_param {
+            // An associated call has no receiver type args to pick a
+            // spec with, so the generic-parameterised impl wins and
+            // otherwise the first registration stays.
+            let key = (struct_name, method_name);
+            if target_type_args.is_empty() {
+                self.associated_functions.insert(key, method.clone());
+            } else {
+                self.associated_functions.entry(key).or_insert_with(|| method.clone());
+            }
+        }
         let specs = self
             .method_registry
             .entry(struct_name)
@@ -392,7 +528,8 @@ impl<'a> EvaluationContext<'a> {
     /// introduces bindings gets a paired drop scope so its
     /// `Drop`-impling values can be cleaned up at exit.
     pub(super) fn enter_drop_scope(&mut self) {
-        self.drop_scopes.push(Vec::new());
+        let scope = self.resume_drop_scopes.pop().unwrap_or_default();
+        self.drop_scopes.push(scope);
     }
 
     /// Pop the current auto-drop scope and run each `Drop::drop`
@@ -480,6 +617,26 @@ impl<'a> EvaluationContext<'a> {
         Ok(())
     }
 
+    /// Call `resource.__close__()` when a `with name = resource { ... }`
+    /// block exits. The type checker guarantees the method exists.
+    pub(super) fn invoke_close(&mut self, resource: RcObject) -> Result<(), InterpreterError> {
+        let (struct_sym, type_args) = match &*resource.borrow() {
+            Object::Struct { type_name, type_args, .. } => (*type_name, type_args.clone()),
+            other => {
+                return Err(InterpreterError::InternalError(format!(
+                    "with: resource is not a struct: {other:?}"
+                )))
+            }
+        };
+        let close_sym = self.string_interner.get_or_intern("__close__");
+        let method = self.get_method(struct_sym, close_sym, &type_args).ok_or_else(|| {
+            let s = self.string_interner.resolve(struct_sym).unwrap_or("?");
+            InterpreterError::InternalError(format!("with: no `__close__` method registered for struct `{s}`"))
+        })?;
+        self.call_method(method, resource, Vec::new())?;
+        Ok(())
+    }
+
     /// Drop the `EvaluationResult` envelope of a successful evaluation,
     /// returning the produced value. **Pre-condition**: the caller has
     /// already separated control-flow signals (Return / Break / Continue)
@@ -493,7 +650,7 @@ impl<'a> EvaluationContext<'a> {
         match result {
             EvaluationResult::Value(v) => Ok(v.into_rc()),
             EvaluationResult::Return(_)
-            | EvaluationResult::Break(_)
+            | EvaluationResult::Break(..)
             | EvaluationResult::Continue(_)
             | EvaluationResult::None => Err(InterpreterError::InternalError(
                 "control-flow signal reached unwrap_value (use try_value! to extract values from positions where flow may occur)".to_string(),
@@ -531,7 +688,7 @@ macro_rules! try_value {
             Ok($crate::evaluation::EvaluationResult::Return(opt)) => {
                 return Ok($crate::evaluation::EvaluationResult::Return(opt));
             }
-            Ok(flow @ $crate::evaluation::EvaluationResult::Break(_)) => return Ok(flow),
+            Ok(flow @ $crate::evaluation::EvaluationResult::Break(..)) => return Ok(flow),
             Ok(flow @ $crate::evaluation::EvaluationResult::Continue(_)) => return Ok(flow),
             Ok($crate::evaluation::EvaluationResult::None) => {
                 return Err($crate::error::InterpreterError::InternalError(
@@ -554,7 +711,7 @@ macro_rules! try_value_v {
             Ok($crate::evaluation::EvaluationResult::Return(opt)) => {
                 return Ok($crate::evaluation::EvaluationResult::Return(opt));
             }
-            Ok(flow @ $crate::evaluation::EvaluationResult::Break(_)) => return Ok(flow),
+            Ok(flow @ $crate::evaluation::EvaluationResult::Break(..)) => return Ok(flow),
             Ok(flow @ $crate::evaluation::EvaluationResult::Continue(_)) => return Ok(flow),
             Ok($crate::evaluation::EvaluationResult::None) => {
                 return Err($crate::error::InterpreterError::InternalError(
@@ -400,6 +400,13 @@ impl EvaluationContext<'_> {
                             "{}: String comparison only supports == and !=", op.name()
                         ))),
                     },
+                    (Object::Bytes(l), Object::Bytes(r)) => match op {
+                        ComparisonOp::Eq => Value::Bool(l == r),
+                        ComparisonOp::Ne => Value::Bool(l != r),
+                        _ => return Err(mismatch(lhs, rhs, format!(
+                            "{}: Bytes comparison only supports == and !=", op.name()
+                        ))),
+                    },
                     (Object::Allocator(l), Object::Allocator(r)) => {
                         let same = Rc::ptr_eq(l, r);
                         match op {
@@ -636,7 +643,7 @@ impl EvaluationContext<'_> {
                             let result = self.call_method(
                                 method,
                                 lhs_rc.clone(),
-                                vec![rhs_rc.clone()],
+                                vec![rhs_v.clone()],
                             )?;
                             let result_v = match result {
                                 EvaluationResult::Value(v) => v,
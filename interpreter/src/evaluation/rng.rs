@@ -0,0 +1,61 @@
+// Deterministic PRNG backing the `seed` / `random` / `random_range`
+// builtins.
+//
+// The generator is SplitMix64 rather than anything from the host
+// (`rand`, `/dev/urandom`, ...) so a program's output depends only
+// on the seed it passes. The algorithm is a handful of 64-bit
+// wrapping adds, shifts and multiplies, which keeps it portable to
+// other runtimes (C, Lua with 64-bit integers) bit-for-bit.
+
+/// Seed used until the program calls `seed(n)`. Fixed so that an
+/// unseeded run is still reproducible.
+pub const DEFAULT_SEED: u64 = 0;
+
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn seed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform `f64` in `[0, 1)` built from the top 53 bits, so every
+    /// result is exactly representable.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Uniform value in `[0, span)`. Draws that fall into the biased
+    /// remainder at the bottom of the `u64` range are rejected, so
+    /// every residue is equally likely. `span` must be non-zero.
+    pub fn next_below(&mut self, span: u64) -> u64 {
+        debug_assert!(span != 0);
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let x = self.next_u64();
+            if x >= threshold {
+                return x % span;
+            }
+        }
+    }
+}
+
+impl Default for SplitMix64 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
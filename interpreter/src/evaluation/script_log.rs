@@ -0,0 +1,123 @@
+// Threshold and destination for the `log_*` builtins.
+//
+// Scripts log through `log_debug` / `log_info` / `log_warn` /
+// `log_error`; the host decides what comes out and where. Embedders
+// set `EvaluationContext::log` (or `RunOptions::log`) and can silence
+// a script entirely with `LogLevel::Off`. The `interpreter` binary
+// reads the same settings from `TOYLANG_LOG` and `TOYLANG_LOG_FILE`.
+//
+// These are the program's own messages. The interpreter's internal
+// diagnostics are separate and never go through here.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Severity of a log line. A config's `min_level` drops everything
+/// below it; `Off` drops everything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LogLevel {
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+    Off,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Debug, LogLevel::Info, LogLevel::Warn, LogLevel::Error, LogLevel::Off];
+
+    /// Name used by `TOYLANG_LOG`.
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Off => "off",
+        }
+    }
+
+    /// Case-insensitive inverse of [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<LogLevel> {
+        LogLevel::ALL.into_iter().find(|l| l.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Tag written in front of each message, e.g. `[WARN]`.
+    fn tag(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "[DEBUG]",
+            LogLevel::Info => "[INFO]",
+            LogLevel::Warn => "[WARN]",
+            LogLevel::Error => "[ERROR]",
+            LogLevel::Off => "",
+        }
+    }
+}
+
+/// Where enabled log lines go.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LogTarget {
+    #[default]
+    Stderr,
+    Stdout,
+    /// Appended to, created if missing.
+    File(PathBuf),
+}
+
+/// What the `log_*` builtins print and where. `Default` writes
+/// `info` and above to stderr.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogConfig {
+    pub min_level: LogLevel,
+    pub target: LogTarget,
+}
+
+impl LogConfig {
+    /// Drop every log line.
+    pub fn off() -> Self {
+        Self { min_level: LogLevel::Off, target: LogTarget::Stderr }
+    }
+
+    /// Settings from `TOYLANG_LOG` (a level name) and
+    /// `TOYLANG_LOG_FILE` (a path to append to), each falling back to
+    /// the default when unset or empty.
+    pub fn from_env() -> Result<Self, String> {
+        let mut config = LogConfig::default();
+        match std::env::var("TOYLANG_LOG") {
+            Ok(level) if !level.is_empty() => {
+                config.min_level = LogLevel::from_name(&level).ok_or_else(|| {
+                    format!("invalid TOYLANG_LOG level `{level}` (expected debug, info, warn, error or off)")
+                })?;
+            }
+            _ => {}
+        }
+        match std::env::var_os("TOYLANG_LOG_FILE") {
+            Some(path) if !path.is_empty() => config.target = LogTarget::File(PathBuf::from(path)),
+            _ => {}
+        }
+        Ok(config)
+    }
+
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        level != LogLevel::Off && level >= self.min_level
+    }
+
+    /// Write `message` at `level` if the threshold lets it through.
+    /// Only a log file can fail.
+    pub fn write(&self, level: LogLevel, message: &str) -> std::io::Result<()> {
+        if !self.enabled(level) {
+            return Ok(());
+        }
+        let line = format!("{} {message}", level.tag());
+        match &self.target {
+            LogTarget::Stderr => crate::output::eprintln_text(&line),
+            LogTarget::Stdout => crate::output::println_text(&line),
+            LogTarget::File(path) => {
+                let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,16 @@
-use std::cell::RefCell;
-use std::rc::Rc;
 use frontend::ast::*;
-use crate::object::{Object, ObjectKey, RcObject};
+use crate::object::Object;
 use crate::error::InterpreterError;
 use crate::try_value;
+use crate::try_value_v;
+use crate::value::Value;
 use super::{EvaluationContext, EvaluationResult};
 
 impl EvaluationContext<'_> {
     /// Convert index (positive or negative) to array index
-    fn resolve_array_index(&self, index_obj: &RcObject, array_len: usize) -> Result<usize, InterpreterError> {
-        let borrowed = index_obj.borrow();
-        match &*borrowed {
-            Object::UInt64(idx) => {
+    pub(super) fn resolve_array_index(&self, index: &Value, array_len: usize) -> Result<usize, InterpreterError> {
+        match index {
+            Value::UInt64(idx) => {
                 let idx = *idx as usize;
                 if idx >= array_len {
                     return Err(InterpreterError::IndexOutOfBounds {
@@ -21,7 +20,7 @@ impl EvaluationContext<'_> {
                 }
                 Ok(idx)
             }
-            Object::Int64(idx) => {
+            Value::Int64(idx) => {
                 if *idx >= 0 {
                     // Positive i64, treat as u64
                     let idx = *idx as usize;
@@ -48,6 +47,82 @@ impl EvaluationContext<'_> {
         }
     }
 
+    /// Resolve already-evaluated `[start..end]` / `[i]` bounds against
+    /// a sequence of `array_len` elements: missing bounds default to the full
+    /// range, negative indices count from the end, and out-of-range
+    /// or inverted bounds are reported before any element is touched.
+    fn resolve_slice_range(&self, start: Option<Value>, end: Option<Value>, array_len: usize) -> Result<(usize, usize), InterpreterError> {
+        // Start index (default to 0)
+        let start_idx = if let Some(start_v) = start {
+            self.resolve_array_index(&start_v, array_len)?
+        } else {
+            0
+        };
+
+        // End index (default to array length)
+        let end_idx = if let Some(end_v) = end {
+            // Use same logic as in original function for end index
+            match &end_v {
+                Value::UInt64(idx) => {
+                    let idx = *idx as usize;
+                    if idx > array_len {
+                        return Err(InterpreterError::IndexOutOfBounds {
+                            index: idx as isize,
+                            size: array_len
+                        });
+                    }
+                    idx
+                }
+                Value::Int64(idx) => {
+                    if *idx >= 0 {
+                        let idx = *idx as usize;
+                        if idx > array_len {
+                            return Err(InterpreterError::IndexOutOfBounds {
+                                index: idx as isize,
+                                size: array_len
+                            });
+                        }
+                        idx
+                    } else {
+                        // Negative end index: convert to positive
+                        let abs_idx = (-*idx) as usize;
+                        if abs_idx > array_len {
+                            return Err(InterpreterError::IndexOutOfBounds {
+                                index: *idx as isize,
+                                size: array_len
+                            });
+                        }
+                        array_len - abs_idx
+                    }
+                }
+                _ => return Err(InterpreterError::InternalError("Array index must be an integer".to_string()))
+            }
+        } else {
+            array_len
+        };
+
+        // Validate indices
+        if start_idx > array_len {
+            return Err(InterpreterError::IndexOutOfBounds {
+                index: start_idx as isize,
+                size: array_len
+            });
+        }
+        if end_idx > array_len {
+            return Err(InterpreterError::IndexOutOfBounds {
+                index: end_idx as isize,
+                size: array_len
+            });
+        }
+        if start_idx > end_idx {
+            return Err(InterpreterError::InternalError(
+                format!("Invalid slice range: start ({}) > end ({})", start_idx, end_idx)
+            ));
+        }
+
+        Ok((start_idx, end_idx))
+    }
+
     pub(super) fn evaluate_slice_access_with_info(&mut self, object: &ExprRef, slice_info: &SliceInfo) -> Result<EvaluationResult, InterpreterError> {
         let object_val = self.evaluate(object)?;
         let object_obj = try_value!(Ok(object_val));
@@ -57,78 +132,21 @@ impl EvaluationContext<'_> {
             Object::Array(elements) => {
                 let array_len = elements.len();
 
-                // Evaluate start index (default to 0)
-                let start_idx = if let Some(start_expr) = &slice_info.start {
-                    let start_val = self.evaluate(start_expr)?;
-                    let start_obj = try_value!(Ok(start_val));
-                    self.resolve_array_index(&start_obj, array_len)?
-                } else {
-                    0
+                let start = match &slice_info.start {
+                    Some(start_expr) => {
+                        let start_val = self.evaluate(start_expr)?;
+                        Some(try_value_v!(Ok(start_val)))
+                    }
+                    None => None,
                 };
-
-                // Evaluate end index (default to array length)
-                let end_idx = if let Some(end_expr) = &slice_info.end {
-                    let end_val = self.evaluate(end_expr)?;
-                    let end_obj = try_value!(Ok(end_val));
-                    // Use same logic as in original function for end index
-                    let borrowed = end_obj.borrow();
-                    match &*borrowed {
-                        Object::UInt64(idx) => {
-                            let idx = *idx as usize;
-                            if idx > array_len {
-                                return Err(InterpreterError::IndexOutOfBounds {
-                                    index: idx as isize,
-                                    size: array_len
-                                });
-                            }
-                            idx
-                        }
-                        Object::Int64(idx) => {
-                            if *idx >= 0 {
-                                let idx = *idx as usize;
-                                if idx > array_len {
-                                    return Err(InterpreterError::IndexOutOfBounds {
-                                        index: idx as isize,
-                                        size: array_len
-                                    });
-                                }
-                                idx
-                            } else {
-                                // Negative end index: convert to positive
-                                let abs_idx = (-*idx) as usize;
-                                if abs_idx > array_len {
-                                    return Err(InterpreterError::IndexOutOfBounds {
-                                        index: *idx as isize,
-                                        size: array_len
-                                    });
-                                }
-                                array_len - abs_idx
-                            }
-                        }
-                        _ => return Err(InterpreterError::InternalError("Array index must be an integer".to_string()))
+                let end = match &slice_info.end {
+                    Some(end_expr) => {
+                        let end_val = self.evaluate(end_expr)?;
+                        Some(try_value_v!(Ok(end_val)))
                     }
-                } else {
-                    array_len
+                    None => None,
                 };
-
-                // Validate indices
-                if start_idx > array_len {
-                    return Err(InterpreterError::IndexOutOfBounds {
-                        index: start_idx as isize,
-                        size: array_len
-                    });
-                }
-                if end_idx > array_len {
-                    return Err(InterpreterError::IndexOutOfBounds {
-                        index: end_idx as isize,
-                        size: array_len
-                    });
-                }
-                if start_idx > end_idx {
-                    return Err(InterpreterError::InternalError(
-                        format!("Invalid slice range: start ({}) > end ({})", start_idx, end_idx)
-                    ));
-                }
+                let (start_idx, end_idx) = self.resolve_slice_range(start, end, array_len)?;
 
                 // Use SliceInfo to distinguish single element vs range slice
                 match slice_info.slice_type {
@@ -149,6 +167,39 @@ impl EvaluationContext<'_> {
                     }
                 }
             }
+            Object::Bytes(data) => {
+                let start = match &slice_info.start {
+                    Some(start_expr) => {
+                        let start_val = self.evaluate(start_expr)?;
+                        Some(try_value_v!(Ok(start_val)))
+                    }
+                    None => None,
+                };
+                let end = match &slice_info.end {
+                    Some(end_expr) => {
+                        let end_val = self.evaluate(end_expr)?;
+                        Some(try_value_v!(Ok(end_val)))
+                    }
+                    None => None,
+                };
+                let (start_idx, end_idx) = self.resolve_slice_range(start, end, data.len())?;
+                match slice_info.slice_type {
+                    SliceType::SingleElement => {
+                        // `b[i]` widens the byte to u64.
+                        if start_idx >= data.len() {
+                            return Err(InterpreterError::IndexOutOfBounds {
+                                index: start_idx as isize,
+                                size: data.len()
+                            });
+                        }
+                        Ok(EvaluationResult::Value(Object::UInt64(data[start_idx] as u64).into()))
+                    }
+                    SliceType::RangeSlice => {
+                        let slice = data[start_idx..end_idx].to_vec();
+                        Ok(EvaluationResult::Value(Object::Bytes(Box::new(slice)).into()))
+                    }
+                }
+            }
             Object::Dict(_dict) => {
                 // Dictionary access uses the original method
                 self.evaluate_slice_access(object, &slice_info.start, &slice_info.end)
@@ -163,7 +214,7 @@ impl EvaluationContext<'_> {
                             drop(obj_borrowed); // Release borrow before method call
 
                             let start_val = self.evaluate(start_expr)?;
-                            let start_obj = try_value!(Ok(start_val));
+                            let start_v = try_value_v!(Ok(start_val));
 
                             // Resolve names first before method call
                             let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -172,7 +223,7 @@ impl EvaluationContext<'_> {
                             let getitem_method = self.string_interner.get_or_intern("__getitem__");
 
                             // Call __getitem__(self, index)
-                            let args = vec![start_obj];
+                            let args = vec![start_v];
                             self.call_struct_method(object_obj, getitem_method, &args, &struct_name_str)
                         } else {
                             Err(InterpreterError::InternalError("Struct access requires index".to_string()))
@@ -183,18 +234,18 @@ impl EvaluationContext<'_> {
                         let struct_name_val = *type_name;
                         drop(obj_borrowed);
 
-                        let start_obj = if let Some(start_expr) = &slice_info.start {
+                        let start_v = if let Some(start_expr) = &slice_info.start {
                             let start_val = self.evaluate(start_expr)?;
-                            try_value!(Ok(start_val))
+                            try_value_v!(Ok(start_val))
                         } else {
-                            Rc::new(RefCell::new(Object::Int64(0)))
+                            Value::Int64(0)
                         };
 
-                        let end_obj = if let Some(end_expr) = &slice_info.end {
+                        let end_v = if let Some(end_expr) = &slice_info.end {
                             let end_val = self.evaluate(end_expr)?;
-                            try_value!(Ok(end_val))
+                            try_value_v!(Ok(end_val))
                         } else {
-                            Rc::new(RefCell::new(Object::Int64(i64::MAX)))
+                            Value::Int64(i64::MAX)
                         };
 
                         let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -202,7 +253,7 @@ impl EvaluationContext<'_> {
                             .to_string();
                         let getslice_method = self.string_interner.get_or_intern("__getslice__");
 
-                        let args = vec![start_obj, end_obj];
+                        let args = vec![start_v, end_v];
                         self.call_struct_method(object_obj, getslice_method, &args, &struct_name_str)
                     }
                 }
@@ -225,8 +276,8 @@ impl EvaluationContext<'_> {
                 // Evaluate start index (default to 0)
                 let start_idx = if let Some(start_expr) = start {
                     let start_val = self.evaluate(start_expr)?;
-                    let start_obj = try_value!(Ok(start_val));
-                    self.resolve_array_index(&start_obj, array_len)?
+                    let start_v = try_value_v!(Ok(start_val));
+                    self.resolve_array_index(&start_v, array_len)?
                 } else {
                     0
                 };
@@ -234,11 +285,10 @@ impl EvaluationContext<'_> {
                 // Evaluate end index (default to array length)
                 let end_idx = if let Some(end_expr) = end {
                     let end_val = self.evaluate(end_expr)?;
-                    let end_obj = try_value!(Ok(end_val));
+                    let end_v = try_value_v!(Ok(end_val));
                     // For end index, we need to allow array_len as valid (exclusive end)
-                    let borrowed = end_obj.borrow();
-                    match &*borrowed {
-                        Object::UInt64(idx) => {
+                    match &end_v {
+                        Value::UInt64(idx) => {
                             let idx = *idx as usize;
                             if idx > array_len {
                                 return Err(InterpreterError::IndexOutOfBounds {
@@ -248,7 +298,7 @@ impl EvaluationContext<'_> {
                             }
                             idx
                         }
-                        Object::Int64(idx) => {
+                        Value::Int64(idx) => {
                             if *idx >= 0 {
                                 let idx = *idx as usize;
                                 if idx > array_len {
@@ -319,9 +369,7 @@ impl EvaluationContext<'_> {
                         let start_obj = try_value!(Ok(start_val));
 
                         // Create ObjectKey for dictionary lookup
-                        let key_borrowed = start_obj.borrow();
-                        let key_object = key_borrowed.clone();
-                        let object_key = ObjectKey::new(key_object);
+                        let object_key = self.resolve_dict_key(&start_obj, dict)?;
 
                         dict.get(&object_key)
                             .cloned()
@@ -344,7 +392,7 @@ impl EvaluationContext<'_> {
                         drop(obj_borrowed); // Release borrow before method call
 
                         let start_val = self.evaluate(start_expr)?;
-                        let start_obj = try_value!(Ok(start_val));
+                        let start_v = try_value_v!(Ok(start_val));
 
                         // Resolve names first before method call
                         let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -353,7 +401,7 @@ impl EvaluationContext<'_> {
                         let getitem_method = self.string_interner.get_or_intern("__getitem__");
 
                         // Call __getitem__(self, index)
-                        let args = vec![start_obj];
+                        let args = vec![start_v];
                         self.call_struct_method(object_obj, getitem_method, &args, &struct_name_str)
                     } else {
                         Err(InterpreterError::InternalError("Struct access requires index".to_string()))
@@ -363,18 +411,18 @@ impl EvaluationContext<'_> {
                     let struct_name_val = *type_name;
                     drop(obj_borrowed);
 
-                    let start_obj = if let Some(start_expr) = start {
+                    let start_v = if let Some(start_expr) = start {
                         let start_val = self.evaluate(start_expr)?;
-                        try_value!(Ok(start_val))
+                        try_value_v!(Ok(start_val))
                     } else {
-                        Rc::new(RefCell::new(Object::Int64(0)))
+                        Value::Int64(0)
                     };
 
-                    let end_obj = if let Some(end_expr) = end {
+                    let end_v = if let Some(end_expr) = end {
                         let end_val = self.evaluate(end_expr)?;
-                        try_value!(Ok(end_val))
+                        try_value_v!(Ok(end_val))
                     } else {
-                        Rc::new(RefCell::new(Object::Int64(-1)))
+                        Value::Int64(-1)
                     };
 
                     let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -382,7 +430,7 @@ impl EvaluationContext<'_> {
                         .to_string();
                     let getslice_method = self.string_interner.get_or_intern("__getslice__");
 
-                    let args = vec![start_obj, end_obj];
+                    let args = vec![start_v, end_v];
                     self.call_struct_method(object_obj, getslice_method, &args, &struct_name_str)
                 }
             }
@@ -392,7 +440,8 @@ impl EvaluationContext<'_> {
         }
     }
 
-    pub(super) fn evaluate_slice_assign(&mut self, object: &ExprRef, start: &Option<ExprRef>, end: &Option<ExprRef>, value: &ExprRef) -> Result<EvaluationResult, InterpreterError> {
+    pub(super) fn evaluate_slice_assign(&mut self, object: &ExprRef, slice_info: &SliceInfo, value: &ExprRef) -> Result<EvaluationResult, InterpreterError> {
+        let (start, end) = (&slice_info.start, &slice_info.end);
         // Get the object being indexed
         let object_val = self.evaluate(object)?;
         let object_obj = try_value!(Ok(object_val));
@@ -408,12 +457,12 @@ impl EvaluationContext<'_> {
                 drop(obj_borrowed);
 
                 // Check if this is single element assignment (start provided, end is None)
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: arr[i] = value
                     if let Some(start_expr) = start {
                         let start_val = self.evaluate(start_expr)?;
-                        let start_obj = try_value!(Ok(start_val));
-                        let resolved_idx = self.resolve_array_index(&start_obj, array_len)?;
+                        let start_v = try_value_v!(Ok(start_val));
+                        let resolved_idx = self.resolve_array_index(&start_v, array_len)?;
 
                         let mut obj_borrowed = object_obj.borrow_mut();
                         if let Object::Array(elements) = &mut *obj_borrowed {
@@ -426,23 +475,58 @@ impl EvaluationContext<'_> {
                         Err(InterpreterError::InternalError("Single element assignment requires start index".to_string()))
                     }
                 } else {
-                    // Range slice assignment: arr[start..end] = value (not implemented yet)
-                    Err(InterpreterError::InternalError("Range slice assignment not yet implemented".to_string()))
+                    // Range slice assignment: arr[start..end] = value writes the
+                    // value's elements one by one; the lengths must agree.
+                    let start_v = match start {
+                        Some(start_expr) => Some(try_value_v!(Ok(self.evaluate(start_expr)?))),
+                        None => None,
+                    };
+                    let end_v = match end {
+                        Some(end_expr) => Some(try_value_v!(Ok(self.evaluate(end_expr)?))),
+                        None => None,
+                    };
+                    let (start_idx, end_idx) = self.resolve_slice_range(start_v, end_v, array_len)?;
+
+                    // Copy the source elements out first so `arr[a..b] = arr`
+                    // doesn't hold a borrow of the target while writing.
+                    let source = match &*value_obj.borrow() {
+                        Object::Array(values) => values.to_vec(),
+                        other => return Err(InterpreterError::InternalError(format!(
+                            "Slice assignment requires an array value, but got {:?}", other.get_type()
+                        ))),
+                    };
+                    if source.len() != end_idx - start_idx {
+                        return Err(InterpreterError::panic(format!(
+                            "Slice assignment length mismatch: range {}..{} has {} elements, but the value has {}",
+                            start_idx, end_idx, end_idx - start_idx, source.len()
+                        )));
+                    }
+
+                    let mut obj_borrowed = object_obj.borrow_mut();
+                    if let Object::Array(elements) = &mut *obj_borrowed {
+                        for (slot, element) in elements[start_idx..end_idx].iter_mut().zip(source) {
+                            *slot = element;
+                        }
+                        Ok(EvaluationResult::Value(value_obj.into()))
+                    } else {
+                        Err(InterpreterError::InternalError("Expected array for slice assignment".to_string()))
+                    }
                 }
             }
             Object::Dict(_) => {
                 drop(obj_borrowed);
                 // Dictionary assignment: dict[key] = value (only single element assignment)
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: dict[key] = value
                     if let Some(start_expr) = start {
                         let start_val = self.evaluate(start_expr)?;
                         let start_obj = try_value!(Ok(start_val));
 
                         // Create ObjectKey for dictionary assignment
-                        let key_borrowed = start_obj.borrow();
-                        let key_object = key_borrowed.clone();
-                        let object_key = ObjectKey::new(key_object);
+                        let object_key = match &*object_obj.borrow() {
+                            Object::Dict(dict) => self.resolve_dict_key(&start_obj, dict)?,
+                            _ => return Err(InterpreterError::InternalError("Expected dict for assignment".to_string())),
+                        };
 
                         let mut obj_borrowed = object_obj.borrow_mut();
                         if let Object::Dict(dict) = &mut *obj_borrowed {
@@ -464,11 +548,11 @@ impl EvaluationContext<'_> {
                 let struct_name_val = *type_name;
                 drop(obj_borrowed);
 
-                if start.is_some() && end.is_none() {
+                if slice_info.slice_type == SliceType::SingleElement {
                     // Single element assignment: struct[key] = value
                     if let Some(start_expr) = start {
                         let start_val = self.evaluate(start_expr)?;
-                        let start_obj = try_value!(Ok(start_val));
+                        let start_v = try_value_v!(Ok(start_val));
 
                         // Resolve names first before method call
                         let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -477,7 +561,7 @@ impl EvaluationContext<'_> {
                         let setitem_method = self.string_interner.get_or_intern("__setitem__");
 
                         // Call __setitem__(self, index, value)
-                        let args = vec![start_obj, value_obj.clone()];
+                        let args = vec![start_v, value_obj.clone().into()];
                         self.call_struct_method(object_obj, setitem_method, &args, &struct_name_str)?;
 
                         // Return the assigned value
@@ -487,18 +571,18 @@ impl EvaluationContext<'_> {
                     }
                 } else {
                     // Range slice assignment: struct[start..end] = value calls __setslice__(self, start, end, value)
-                    let start_obj = if let Some(start_expr) = start {
+                    let start_v = if let Some(start_expr) = start {
                         let start_val = self.evaluate(start_expr)?;
-                        try_value!(Ok(start_val))
+                        try_value_v!(Ok(start_val))
                     } else {
-                        Rc::new(RefCell::new(Object::Int64(0)))
+                        Value::Int64(0)
                     };
 
-                    let end_obj = if let Some(end_expr) = end {
+                    let end_v = if let Some(end_expr) = end {
                         let end_val = self.evaluate(end_expr)?;
-                        try_value!(Ok(end_val))
+                        try_value_v!(Ok(end_val))
                     } else {
-                        Rc::new(RefCell::new(Object::Int64(i64::MAX)))
+                        Value::Int64(i64::MAX)
                     };
 
                     let struct_name_str = self.string_interner.resolve(struct_name_val)
@@ -507,7 +591,7 @@ impl EvaluationContext<'_> {
                     let setslice_method = self.string_interner.get_or_intern("__setslice__");
 
                     // Call __setslice__(self, start, end, value)
-                    let args = vec![start_obj, end_obj, value_obj.clone()];
+                    let args = vec![start_v, end_v, value_obj.clone().into()];
                     self.call_struct_method(object_obj, setslice_method, &args, &struct_name_str)?;
 
                     Ok(EvaluationResult::Value(value_obj.into()))
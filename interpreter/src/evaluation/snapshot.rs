@@ -0,0 +1,111 @@
+// Save / restore of an `EvaluationContext`'s mutable state, for a REPL
+// `:undo` or a notebook that forks a session.
+//
+// A snapshot owns a deep copy of every value the program can reach:
+// the variables in scope, module variables, the values parked in the
+// heap's typed slots and the pending auto-drops. One memo is shared
+// across all of them, so two bindings that alias the same array still
+// alias it after a restore. Each restore copies again, which lets the
+// same snapshot be restored (or forked into another context) any
+// number of times. The registries (functions, methods, enum and
+// struct declarations) hold immutable `Rc`s and are copied shallowly.
+//
+// Three things are not deep: closures keep their captures, like
+// `clone(x)`, the allocators on the `with` stack are kept as-is, and
+// the run queue of spawned tasks is put back with the same tasks in
+// it, each keeping whatever progress it has made since: a suspended
+// body cannot be copied.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use frontend::ast::{Function, MethodFunction};
+use string_interner::DefaultSymbol;
+use crate::environment::Environment;
+use crate::heap::{Allocator, HeapManager};
+use super::deep_clone::DeepCloner;
+use super::generator::Generator;
+use super::{
+    rng, DropEntry, EnumRegistryEntry, EvaluationContext, MethodSpec, StructRegistryEntry,
+};
+
+/// Saved state of an [`EvaluationContext`], made by
+/// [`EvaluationContext::snapshot`] and put back with
+/// [`EvaluationContext::restore`].
+#[derive(Debug, Clone)]
+pub struct EvaluationSnapshot {
+    environment: Environment,
+    heap: HeapManager,
+    drop_scopes: Vec<Vec<DropEntry>>,
+    allocator_stack: Vec<Rc<dyn Allocator>>,
+    tasks: VecDeque<Rc<RefCell<Generator>>>,
+    function: HashMap<DefaultSymbol, Rc<Function>>,
+    function_qualified: HashMap<(Option<DefaultSymbol>, DefaultSymbol), Rc<Function>>,
+    method_registry: HashMap<DefaultSymbol, HashMap<DefaultSymbol, Vec<MethodSpec>>>,
+    associated_functions: HashMap<(DefaultSymbol, DefaultSymbol), Rc<MethodFunction>>,
+    enum_definitions: HashMap<DefaultSymbol, EnumRegistryEntry>,
+    struct_definitions: HashMap<DefaultSymbol, StructRegistryEntry>,
+    rng: rng::SplitMix64,
+    virtual_millis: Option<u64>,
+}
+
+impl EvaluationSnapshot {
+    /// A copy whose values share nothing with `self`.
+    fn deep_copy(&self) -> Self {
+        let mut cloner = DeepCloner::default();
+        let mut copy = self.clone();
+        for value in copy.environment.values_mut() {
+            *value = cloner.clone_value(value);
+        }
+        for slot in copy.heap.typed_values_mut() {
+            *slot = cloner.clone_rc(slot);
+        }
+        for entry in copy.drop_scopes.iter_mut().flatten() {
+            entry.value = cloner.clone_rc(&entry.value);
+        }
+        copy
+    }
+}
+
+impl EvaluationContext<'_> {
+    /// Capture everything a later statement could change, so
+    /// [`restore`](Self::restore) can rewind to this point.
+    pub fn snapshot(&self) -> EvaluationSnapshot {
+        EvaluationSnapshot {
+            environment: self.environment.clone(),
+            heap: self.heap_manager.borrow().clone(),
+            drop_scopes: self.drop_scopes.clone(),
+            allocator_stack: self.allocator_stack.clone(),
+            tasks: self.tasks.clone(),
+            function: self.function.clone(),
+            function_qualified: self.function_qualified.clone(),
+            method_registry: self.method_registry.clone(),
+            associated_functions: self.associated_functions.clone(),
+            enum_definitions: self.enum_definitions.clone(),
+            struct_definitions: self.struct_definitions.clone(),
+            rng: self.rng.clone(),
+            virtual_millis: self.virtual_millis,
+        }
+        .deep_copy()
+    }
+
+    /// Rewind to `snapshot`. The snapshot itself is left untouched,
+    /// so it can be restored again.
+    pub fn restore(&mut self, snapshot: &EvaluationSnapshot) {
+        let copy = snapshot.deep_copy();
+        self.environment = copy.environment;
+        // In place: the global allocator holds the same cell.
+        *self.heap_manager.borrow_mut() = copy.heap;
+        self.drop_scopes = copy.drop_scopes;
+        self.allocator_stack = copy.allocator_stack;
+        self.tasks = copy.tasks;
+        self.function = copy.function;
+        self.function_qualified = copy.function_qualified;
+        self.method_registry = copy.method_registry;
+        self.associated_functions = copy.associated_functions;
+        self.enum_definitions = copy.enum_definitions;
+        self.struct_definitions = copy.struct_definitions;
+        self.rng = copy.rng;
+        self.virtual_millis = copy.virtual_millis;
+    }
+}
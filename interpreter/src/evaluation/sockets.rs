@@ -0,0 +1,99 @@
+// Open TCP sockets behind the `tcp_*` builtins.
+//
+// Scripts never see a socket object: `tcp_listen` / `tcp_accept`
+// hand out `u64` handles, like file descriptors, and the other
+// builtins look the handle up here. Handles are never reused within
+// a run, so a stale handle after `tcp_close` is reported instead of
+// silently reaching a newer socket. Everything still open is closed
+// when the evaluation context is dropped.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+/// Largest chunk a single `tcp_read` returns.
+const READ_CHUNK: usize = 64 * 1024;
+
+#[derive(Debug)]
+enum Socket {
+    Listener(TcpListener),
+    Stream(TcpStream),
+}
+
+#[derive(Debug)]
+pub struct SocketTable {
+    next_handle: u64,
+    open: HashMap<u64, Socket>,
+}
+
+impl Default for SocketTable {
+    fn default() -> Self {
+        Self { next_handle: 1, open: HashMap::new() }
+    }
+}
+
+impl SocketTable {
+    /// Listen on every interface at `port`; port 0 picks a free one.
+    pub fn listen(&mut self, port: u64) -> Result<u64, String> {
+        let port = u16::try_from(port).map_err(|_| format!("port {port} is out of range"))?;
+        let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| e.to_string())?;
+        Ok(self.insert(Socket::Listener(listener)))
+    }
+
+    /// Block until a client connects to the listener `handle`.
+    pub fn accept(&mut self, handle: u64) -> Result<u64, String> {
+        let stream = match self.open.get(&handle) {
+            Some(Socket::Listener(listener)) => listener.accept().map_err(|e| e.to_string())?.0,
+            Some(Socket::Stream(_)) => return Err(format!("handle {handle} is a connection, not a listener")),
+            None => return Err(unknown(handle)),
+        };
+        Ok(self.insert(Socket::Stream(stream)))
+    }
+
+    /// Whatever the peer has sent, waiting for at least one byte.
+    /// Empty once the peer has closed its end.
+    pub fn read(&mut self, handle: u64) -> Result<Vec<u8>, String> {
+        let stream = self.stream(handle)?;
+        let mut buf = vec![0u8; READ_CHUNK];
+        let n = stream.read(&mut buf).map_err(|e| e.to_string())?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    pub fn write(&mut self, handle: u64, data: &[u8]) -> Result<(), String> {
+        self.stream(handle)?.write_all(data).map_err(|e| e.to_string())
+    }
+
+    pub fn close(&mut self, handle: u64) -> Result<(), String> {
+        self.open.remove(&handle).map(drop).ok_or_else(|| unknown(handle))
+    }
+
+    /// The port a listener is bound to, for listeners opened on port 0.
+    pub fn local_port(&self, handle: u64) -> Result<u64, String> {
+        let addr = match self.open.get(&handle) {
+            Some(Socket::Listener(listener)) => listener.local_addr(),
+            Some(Socket::Stream(stream)) => stream.local_addr(),
+            None => return Err(unknown(handle)),
+        };
+        addr.map(|a| u64::from(a.port())).map_err(|e| e.to_string())
+    }
+
+    fn stream(&mut self, handle: u64) -> Result<&mut TcpStream, String> {
+        match self.open.get_mut(&handle) {
+            Some(Socket::Stream(stream)) => Ok(stream),
+            Some(Socket::Listener(_)) => Err(format!("handle {handle} is a listener, not a connection")),
+            None => Err(unknown(handle)),
+        }
+    }
+
+    fn insert(&mut self, socket: Socket) -> u64 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.open.insert(handle, socket);
+        handle
+    }
+}
+
+fn unknown(handle: u64) -> String {
+    format!("no open socket with handle {handle}")
+}
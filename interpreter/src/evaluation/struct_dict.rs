@@ -0,0 +1,107 @@
+// Struct <-> dict conversion backing the `to_dict` / `from_dict<T>`
+// builtins.
+//
+// `to_dict` is shallow: each field becomes one `"name": value` entry
+// sharing the field's value. `from_dict` is the validating direction
+// and recurses so a dict produced by `json_parse` can fill nested
+// struct fields:
+//   - the dict must have exactly the struct's fields (by name)
+//   - a value already matching the declared field type is taken as is
+//   - integers convert between widths when the value fits
+//   - a dict value fills a struct-typed field recursively
+// Anything else makes the whole conversion fail (`None` at the
+// language level).
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use frontend::type_decl::TypeDecl;
+use string_interner::{DefaultStringInterner, DefaultSymbol};
+use crate::object::{Object, ObjectKey, RcObject};
+use super::StructRegistryEntry;
+
+// Keys are field-name symbols, never the interior-mutable compound
+// objects the lint worries about.
+#[allow(clippy::mutable_key_type)]
+pub fn to_dict(value: &Object) -> Option<Object> {
+    let Object::Struct { fields, .. } = value else {
+        return None;
+    };
+    let entries: HashMap<ObjectKey, RcObject> = fields
+        .iter()
+        .map(|(name, v)| (ObjectKey::new(Object::ConstString(*name)), v.clone()))
+        .collect();
+    Some(Object::Dict(Box::new(entries)))
+}
+
+pub fn from_dict(
+    value: &Object,
+    struct_name: DefaultSymbol,
+    interner: &DefaultStringInterner,
+    structs: &HashMap<DefaultSymbol, StructRegistryEntry>,
+) -> Option<Object> {
+    let Object::Dict(map) = value else {
+        return None;
+    };
+    let entry = structs.get(&struct_name)?;
+    if map.len() != entry.fields.len() {
+        return None;
+    }
+    let mut fields = HashMap::with_capacity(entry.fields.len());
+    for (field_name, field_ty) in &entry.fields {
+        // Keys from dict literals and json_parse are interned, but a
+        // key built at run time is a heap string.
+        let key_text = interner.resolve(*field_name)?;
+        let found = map
+            .get(&ObjectKey::new(Object::ConstString(*field_name)))
+            .or_else(|| map.get(&ObjectKey::new(Object::String(key_text.to_string()))))?;
+        let converted = convert_field(&found.borrow(), field_ty, interner, structs)?;
+        let converted = converted.map_or_else(|| found.clone(), |obj| Rc::new(RefCell::new(obj)));
+        fields.insert(*field_name, converted);
+    }
+    Some(Object::Struct { type_name: struct_name, fields: Box::new(fields), type_args: Vec::new() })
+}
+
+/// `Some(None)` keeps the value unchanged, `Some(Some(obj))` replaces
+/// it with a converted one, and `None` rejects it.
+fn convert_field(
+    value: &Object,
+    ty: &TypeDecl,
+    interner: &DefaultStringInterner,
+    structs: &HashMap<DefaultSymbol, StructRegistryEntry>,
+) -> Option<Option<Object>> {
+    if value.matches_type(ty) {
+        return Some(None);
+    }
+    match (value, ty) {
+        (Object::Dict(_), TypeDecl::Struct(name, _) | TypeDecl::Identifier(name)) => {
+            from_dict(value, *name, interner, structs).map(Some)
+        }
+        _ => convert_integer(value, ty).map(Some),
+    }
+}
+
+fn convert_integer(value: &Object, ty: &TypeDecl) -> Option<Object> {
+    let n: i128 = match value {
+        Object::Int8(v) => (*v).into(),
+        Object::Int16(v) => (*v).into(),
+        Object::Int32(v) => (*v).into(),
+        Object::Int64(v) => (*v).into(),
+        Object::UInt8(v) => (*v).into(),
+        Object::UInt16(v) => (*v).into(),
+        Object::UInt32(v) => (*v).into(),
+        Object::UInt64(v) => (*v).into(),
+        _ => return None,
+    };
+    Some(match ty {
+        TypeDecl::Int8 => Object::Int8(n.try_into().ok()?),
+        TypeDecl::Int16 => Object::Int16(n.try_into().ok()?),
+        TypeDecl::Int32 => Object::Int32(n.try_into().ok()?),
+        TypeDecl::Int64 => Object::Int64(n.try_into().ok()?),
+        TypeDecl::UInt8 => Object::UInt8(n.try_into().ok()?),
+        TypeDecl::UInt16 => Object::UInt16(n.try_into().ok()?),
+        TypeDecl::UInt32 => Object::UInt32(n.try_into().ok()?),
+        TypeDecl::UInt64 => Object::UInt64(n.try_into().ok()?),
+        _ => return None,
+    })
+}
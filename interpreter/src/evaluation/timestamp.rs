@@ -0,0 +1,124 @@
+// UTC calendar arithmetic behind `__builtin_format_timestamp`, the
+// native half of `core/std/time.t`.
+//
+// Timestamps are `i64` seconds since 1970-01-01T00:00:00Z; negative
+// values are before the epoch. There is no time-zone database, so
+// everything is UTC and leap seconds don't exist. Dates use the
+// proleptic Gregorian calendar (Howard Hinnant's `civil_from_days`),
+// which stays in range for every `i64` input: the day count is at
+// most about 1e14.
+
+const WEEKDAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// A timestamp broken into calendar fields.
+struct Parts {
+    year: i64,
+    /// 1..=12
+    month: u32,
+    /// 1..=31
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    /// 0 = Sunday
+    weekday: u32,
+    /// 1..=366
+    day_of_year: u32,
+}
+
+impl Parts {
+    fn from_epoch(epoch: i64) -> Parts {
+        let days = epoch.div_euclid(86_400);
+        let secs = epoch.rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+        let day_of_year = (days - days_from_civil(year, 1, 1) + 1) as u32;
+        Parts {
+            year,
+            month,
+            day,
+            hour: secs / 3600,
+            minute: secs / 60 % 60,
+            second: secs % 60,
+            // 1970-01-01 was a Thursday.
+            weekday: (days + 4).rem_euclid(7) as u32,
+            day_of_year,
+        }
+    }
+}
+
+/// `(year, month, day)` of the day `days` after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of `civil_from_days`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = year - i64::from(month <= 2);
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `strftime`-style formatting in UTC. Supported conversions:
+/// `%Y %y %m %d %e %H %M %S %j %a %A %b %B %u %w %s %F %T %D %R %Z %z
+/// %n %t %%`. Anything else is an error rather than passed through,
+/// so a typo in `fmt` shows up the first time it runs.
+pub fn format(epoch: i64, fmt: &str) -> Result<String, String> {
+    let p = Parts::from_epoch(epoch);
+    let mut out = String::with_capacity(fmt.len() + 16);
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let Some(spec) = chars.next() else {
+            return Err("format ends with a lone `%`".to_string());
+        };
+        match spec {
+            'Y' => out.push_str(&p.year.to_string()),
+            'y' => out.push_str(&format!("{:02}", p.year.rem_euclid(100))),
+            'm' => out.push_str(&format!("{:02}", p.month)),
+            'd' => out.push_str(&format!("{:02}", p.day)),
+            'e' => out.push_str(&format!("{:2}", p.day)),
+            'H' => out.push_str(&format!("{:02}", p.hour)),
+            'M' => out.push_str(&format!("{:02}", p.minute)),
+            'S' => out.push_str(&format!("{:02}", p.second)),
+            'j' => out.push_str(&format!("{:03}", p.day_of_year)),
+            'a' => out.push_str(&WEEKDAYS[p.weekday as usize][..3]),
+            'A' => out.push_str(WEEKDAYS[p.weekday as usize]),
+            'b' => out.push_str(&MONTHS[p.month as usize - 1][..3]),
+            'B' => out.push_str(MONTHS[p.month as usize - 1]),
+            'u' => out.push_str(&(if p.weekday == 0 { 7 } else { p.weekday }).to_string()),
+            'w' => out.push_str(&p.weekday.to_string()),
+            's' => out.push_str(&epoch.to_string()),
+            'F' => out.push_str(&format!("{}-{:02}-{:02}", p.year, p.month, p.day)),
+            'T' => out.push_str(&format!("{:02}:{:02}:{:02}", p.hour, p.minute, p.second)),
+            'D' => out.push_str(&format!("{:02}/{:02}/{:02}", p.month, p.day, p.year.rem_euclid(100))),
+            'R' => out.push_str(&format!("{:02}:{:02}", p.hour, p.minute)),
+            'Z' => out.push_str("UTC"),
+            'z' => out.push_str("+0000"),
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            '%' => out.push('%'),
+            other => return Err(format!("unknown conversion `%{other}`")),
+        }
+    }
+    Ok(out)
+}
@@ -54,12 +54,53 @@ impl Allocator for GlobalAllocator {
 // `__builtin_fixed_buffer_allocator` / `__builtin_fixed_buffer_drop`)
 // were retired together.
 
+/// Allocation counters kept by `HeapManager`. The allocation and free
+/// counts include the ones a `realloc` makes when it moves a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    pub allocations: u64,
+    pub frees: u64,
+    pub reallocations: u64,
+    pub bytes_allocated: u64,
+    pub live_bytes: u64,
+    pub peak_bytes: u64,
+    /// Typed stores (`ptr_write` of a value the byte buffer can't hold
+    /// as-is) by the stored value's type. Only filled while
+    /// `HeapManager::trace_types` is on.
+    pub typed_stores: HashMap<frontend::type_decl::TypeDecl, u64>,
+}
+
+impl HeapStats {
+    /// Human-readable summary, one fact per line, for `--trace-alloc`.
+    pub fn report(&self, string_interner: &string_interner::DefaultStringInterner) -> String {
+        let mut out = format!(
+            "heap: {} allocations ({} reallocations), {} frees\n\
+             heap: {} bytes allocated, {} live, {} peak\n",
+            self.allocations, self.reallocations, self.frees, self.bytes_allocated, self.live_bytes, self.peak_bytes,
+        );
+        let mut by_type: Vec<(String, u64)> = self
+            .typed_stores
+            .iter()
+            .map(|(ty, n)| (crate::object::format_type_decl_for_display(ty, string_interner), *n))
+            .collect();
+        by_type.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        for (ty, n) in by_type {
+            out.push_str(&format!("heap: {n} typed stores of {ty}\n"));
+        }
+        out
+    }
+}
+
 /// Simple heap memory manager for pointer operations
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HeapManager {
     memory: Vec<u8>,
     allocations: HashMap<usize, usize>, // address -> size
     next_addr: usize,
+    stats: HeapStats,
+    /// Also count typed stores by type (`HeapStats::typed_stores`).
+    /// Off by default: it computes the type of every stored value.
+    pub trace_types: bool,
     // Typed-slot storage keyed by (base address, byte offset). When a write
     // stores a non-u64 value (bool, i64, user struct, enum variant, ...)
     // the evaluator records the `RcObject` here so a matching `ptr_read`
@@ -76,13 +117,27 @@ impl HeapManager {
             memory: Vec::new(),
             allocations: HashMap::new(),
             next_addr: 1, // 0 is reserved for null pointer
+            stats: HeapStats::default(),
+            trace_types: false,
             typed_slots: HashMap::new(),
         }
     }
 
+    pub fn stats(&self) -> &HeapStats {
+        &self.stats
+    }
+
+    /// The values held in typed slots, for callers that copy the heap.
+    pub(crate) fn typed_values_mut(&mut self) -> impl Iterator<Item = &mut crate::object::RcObject> {
+        self.typed_slots.values_mut()
+    }
+
     /// Record a typed slot so a later `typed_read` can return the exact Rc.
     pub fn typed_write(&mut self, addr: usize, offset: usize, value: crate::object::RcObject) {
         if addr != 0 {
+            if self.trace_types {
+                *self.stats.typed_stores.entry(value.borrow().get_type()).or_default() += 1;
+            }
             self.typed_slots.insert((addr, offset), value);
         }
     }
@@ -102,6 +157,10 @@ impl HeapManager {
         self.memory.resize(self.memory.len() + size, 0);
         self.allocations.insert(addr, size);
         self.next_addr += size;
+        self.stats.allocations += 1;
+        self.stats.bytes_allocated += size as u64;
+        self.stats.live_bytes += size as u64;
+        self.stats.peak_bytes = self.stats.peak_bytes.max(self.stats.live_bytes);
         addr
     }
     
@@ -111,7 +170,14 @@ impl HeapManager {
             return true; // freeing null pointer is a no-op
         }
         
-        self.allocations.remove(&addr).is_some()
+        match self.allocations.remove(&addr) {
+            Some(size) => {
+                self.stats.frees += 1;
+                self.stats.live_bytes -= size as u64;
+                true
+            }
+            None => false,
+        }
     }
     
     /// Reallocate memory
@@ -128,6 +194,7 @@ impl HeapManager {
         }
         
         if let Some(old_size) = self.allocations.get(&addr).copied() {
+            self.stats.reallocations += 1;
             // Allocate new memory
             let new_addr = self.alloc(new_size);
 
@@ -184,6 +251,27 @@ impl HeapManager {
         Some(u64::from_le_bytes(slice.try_into().ok()?))
     }
     
+    /// Read `len` bytes starting at `addr`, for
+    /// `__builtin_bytes_from_ptr`. A byte stored as a typed `u8` slot
+    /// (`String::push`, `__builtin_str_to_ptr`) wins over the raw
+    /// buffer, which only holds bytes written through the untyped
+    /// path. `None` when the range runs past the allocation.
+    pub fn read_byte_range(&self, addr: usize, len: usize) -> Option<Vec<u8>> {
+        if len == 0 {
+            return Some(Vec::new());
+        }
+        let raw = self.get_memory_slice(addr, len)?;
+        let mut out = raw.to_vec();
+        for (i, byte) in out.iter_mut().enumerate() {
+            if let Some(value) = self.typed_slots.get(&(addr, i)) {
+                if let crate::object::Object::UInt8(b) = &*value.borrow() {
+                    *byte = *b;
+                }
+            }
+        }
+        Some(out)
+    }
+
     /// Write u64 to memory at address + offset
     pub fn write_u64(&mut self, addr: usize, offset: usize, value: u64) -> bool {
         if addr == 0 {
@@ -432,6 +520,26 @@ mod tests {
         assert!(!heap.borrow().is_valid_address(addr));
     }
 
+    #[test]
+    fn test_heap_stats_track_live_and_peak_bytes() {
+        let mut heap = HeapManager::new();
+        heap.trace_types = true;
+
+        let a = heap.alloc(16);
+        let b = heap.alloc(32);
+        heap.typed_write(b, 0, Rc::new(RefCell::new(crate::object::Object::Int64(-1))));
+        assert!(heap.free(a));
+        let b = heap.realloc(b, 64);
+        assert!(!heap.free(a), "a second free of the same block is not counted");
+
+        let stats = heap.stats();
+        assert_eq!((stats.allocations, stats.frees, stats.reallocations), (3, 2, 1));
+        assert_eq!((stats.bytes_allocated, stats.live_bytes, stats.peak_bytes), (112, 64, 96));
+        assert_eq!(stats.typed_stores.get(&frontend::type_decl::TypeDecl::Int64), Some(&1));
+        assert!(heap.free(b));
+        assert_eq!(heap.stats().live_bytes, 0);
+    }
+
     // Arena / FixedBuffer runtime tests removed when the runtime
     // arena/fixed_buffer types were retired. Equivalent contracts
     // are now covered end-to-end by the consistency suite against
@@ -596,7 +596,7 @@ fn gather_struct_values(
             }
             Ok(values)
         }
-        Expr::StructLiteral(_, lit_fields) => {
+        Expr::StructLiteral(_, lit_fields, _) => {
             let mut values = Vec::with_capacity(layout.fields.len());
             for (field_sym, _) in &layout.fields {
                 let (_, field_expr_ref) = lit_fields
@@ -1135,6 +1135,71 @@ impl<'a, 'b> State<'a, 'b> {
                     BuiltinFunction::StrLen => {
                         Err("__builtin_str_len unreachable in JIT codegen (eligibility should reject)".into())
                     }
+                    BuiltinFunction::ReadBytes | BuiltinFunction::WriteBytes | BuiltinFunction::BytesFromPtr => {
+                        Err("read_bytes / write_bytes / __builtin_bytes_from_ptr unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::ReadLine | BuiltinFunction::ReadAll => {
+                        Err("read_line / read_all unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::FormatTimestamp => {
+                        Err("format_timestamp unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::LogDebug | BuiltinFunction::LogInfo
+                    | BuiltinFunction::LogWarn | BuiltinFunction::LogError => {
+                        Err("log_* unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Exec => {
+                        Err("exec unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::HttpGet => {
+                        Err("http_get unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::TcpListen | BuiltinFunction::TcpAccept | BuiltinFunction::TcpRead
+                    | BuiltinFunction::TcpWrite | BuiltinFunction::TcpClose | BuiltinFunction::TcpLocalPort => {
+                        Err("tcp_* unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::JsonParse | BuiltinFunction::JsonStringify => {
+                        Err("json_parse / json_stringify unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::TypeOf | BuiltinFunction::Is(_) | BuiltinFunction::Downcast(_) => {
+                        Err("type_of / is / downcast unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::ToDict | BuiltinFunction::FromDict(_) => {
+                        Err("to_dict / from_dict unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Clone => {
+                        Err("clone unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Seed | BuiltinFunction::Random | BuiltinFunction::RandomRange => {
+                        Err("seed / random / random_range unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::NowMillis | BuiltinFunction::Clock | BuiltinFunction::Sleep => {
+                        Err("now_millis / clock / sleep unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::InlineLua => {
+                        Err("lua!(...) unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Spawn | BuiltinFunction::YieldNow => {
+                        Err("spawn / yield_now unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::ParseU64 | BuiltinFunction::ParseI64
+                    | BuiltinFunction::ToHex | BuiltinFunction::FormatRadix => {
+                        Err("parse_u64 / parse_i64 / to_hex / format_radix unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Format | BuiltinFunction::FormatFixed | BuiltinFunction::FormatExp => {
+                        Err("format / format_fixed / format_exp unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Sort => {
+                        Err("sort unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::BinarySearch | BuiltinFunction::LowerBound
+                    | BuiltinFunction::Reverse | BuiltinFunction::MinOf
+                    | BuiltinFunction::MaxOf | BuiltinFunction::SumOf => {
+                        Err("array algorithm builtins unreachable in JIT codegen (eligibility should reject)".into())
+                    }
+                    BuiltinFunction::Matches | BuiltinFunction::Find | BuiltinFunction::ReplacePattern => {
+                        Err("matches / find / replace_pattern unreachable in JIT codegen (eligibility should reject)".into())
+                    }
                     BuiltinFunction::HeapAlloc => {
                         let size = self
                             .gen_expr(&args[0])?
@@ -1381,7 +1446,7 @@ impl<'a, 'b> State<'a, 'b> {
                 };
                 Ok(Some(result))
             }
-            Expr::Call(_, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
+            Expr::Call(_, _, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
                 // STR-INTERP-INTERP-JIT: str.concat(t) doesn't go
                 // through the user-method registry; it's a builtin
                 // that lowers directly to the `jit_str_concat`
@@ -1747,7 +1812,7 @@ impl<'a, 'b> State<'a, 'b> {
                     }
                     return Ok(None);
                 }
-                Stmt::Break(label) => {
+                Stmt::Break(label, _) => {
                     let frame = self.resolve_loop_frame(label, "break")?;
                     let target = frame.break_block;
                     let depth_at_entry = frame.with_depth_at_entry;
@@ -1780,6 +1845,9 @@ impl<'a, 'b> State<'a, 'b> {
                 Stmt::StructDecl { .. } | Stmt::ImplBlock { .. } | Stmt::EnumDecl { .. } | Stmt::TraitDecl { .. } => {
                     return Err("decl inside JIT body".into());
                 }
+                Stmt::Yield(_) => {
+                    return Err("yield inside JIT body".into());
+                }
                 Stmt::TypeAlias { .. } => {
                     // Type aliases are resolved at parse time and have
                     // no runtime effect — safe to skip in the JIT body.
@@ -2056,7 +2124,7 @@ impl<'a, 'b> State<'a, 'b> {
             None => return Ok(false),
         };
         match value {
-            Expr::StructLiteral(struct_name, lit_fields) => {
+            Expr::StructLiteral(struct_name, lit_fields, _) => {
                 let layout = match self.struct_layouts.get(&struct_name) {
                     Some(l) => l.clone(),
                     None => return Ok(false),
@@ -2079,7 +2147,7 @@ impl<'a, 'b> State<'a, 'b> {
                 self.struct_local_types.insert(name, struct_name);
                 Ok(true)
             }
-            Expr::Call(_, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
+            Expr::Call(_, _, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
                 // Look up the call's resolved monomorph to see if it
                 // returns a struct. If yes, handle the multi-return.
                 let target_key = match self.call_targets.get(value_ref) {
@@ -2184,7 +2252,7 @@ impl<'a, 'b> State<'a, 'b> {
                 self.tuple_local_types.insert(name, element_tys);
                 Ok(true)
             }
-            Expr::Call(_, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
+            Expr::Call(_, _, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
                 let target_key = match self.call_targets.get(value_ref) {
                     Some(k) => k.clone(),
                     None => return Ok(false),
@@ -2243,7 +2311,7 @@ impl<'a, 'b> State<'a, 'b> {
         // [receiver, ...args] so the rest of this function stays
         // independent of which form we're dispatching.
         let arg_list: Vec<ExprRef> = match call_expr {
-            Expr::Call(_, args_ref) => {
+            Expr::Call(_, args_ref, _) => {
                 let args_expr = self
                     .program
                     .expression
@@ -2447,7 +2515,7 @@ impl<'a, 'b> State<'a, 'b> {
             .get(expr_ref)
             .ok_or_else(|| "missing extern call expression".to_string())?;
         let (name_sym, arg_exprs) = match call_expr {
-            Expr::Call(name, args_ref) => {
+            Expr::Call(name, args_ref, _) => {
                 let args_expr = self
                     .program
                     .expression
@@ -2508,6 +2576,11 @@ impl<'a, 'b> State<'a, 'b> {
                 let cmp = self.builder.ins().icmp(IntCC::SignedLessThan, x, zero);
                 self.builder.ins().select(cmp, neg, x)
             }
+            ExternDispatch::NativeCountOnesU64 => self.builder.ins().popcnt(args[0]),
+            ExternDispatch::NativeLeadingZerosU64 => self.builder.ins().clz(args[0]),
+            ExternDispatch::NativeTrailingZerosU64 => self.builder.ins().ctz(args[0]),
+            ExternDispatch::NativeRotateLeftU64 => self.builder.ins().rotl(args[0], args[1]),
+            ExternDispatch::NativeRotateRightU64 => self.builder.ins().rotr(args[0], args[1]),
         };
         Ok(Some(result))
     }
@@ -2662,7 +2735,7 @@ impl<'a, 'b> State<'a, 'b> {
             // register the enum local. Routed through call_targets +
             // gather_call_args so the arg expansion shares the
             // boundary helper above.
-            Expr::Call(_, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
+            Expr::Call(_, _, _) | Expr::MethodCall(_, _, _) | Expr::AssociatedFunctionCall(_, _, _) => {
                 let target_key = match self.call_targets.get(value_ref) {
                     Some(k) => k.clone(),
                     None => return Ok(false),
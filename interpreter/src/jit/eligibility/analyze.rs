@@ -286,19 +286,22 @@ pub(super) fn expr_kind_name(e: &Expr) -> &'static str {
         Expr::Null => "null",
         Expr::ExprList(_) => "expression list",
         Expr::String(_) => "string literal",
+        Expr::Bytes(_) => "bytes literal",
         Expr::ArrayLiteral(_) => "array literal",
         Expr::FieldAccess(_, _) => "field access",
         Expr::MethodCall(_, _, _) => "method call",
-        Expr::StructLiteral(_, _) => "struct literal",
+        Expr::StructLiteral(_, _, _) => "struct literal",
         Expr::QualifiedIdentifier(_) => "qualified identifier",
         Expr::BuiltinMethodCall(_, _, _) => "builtin method call",
         Expr::SliceAccess(_, _) => "slice access",
-        Expr::SliceAssign(_, _, _, _) => "slice assign",
+        Expr::SliceAssign(_, _, _) => "slice assign",
         Expr::AssociatedFunctionCall(_, _, _) => "associated function call",
         Expr::DictLiteral(_) => "dict literal",
         Expr::TupleLiteral(_) => "tuple literal",
         Expr::TupleAccess(_, _) => "tuple access",
         Expr::With(_, _) => "`with allocator` block",
+        Expr::WithResource { .. } => "`with` resource block",
+        Expr::TryCatch { .. } => "`try` / `catch` expression",
         Expr::Match(_, _) => "match expression",
         Expr::Range(_, _) => "range value",
         Expr::Closure { .. } => "closure literal",
@@ -244,7 +244,7 @@ fn check_struct_returning_body(
                 }
             }
         }
-        Expr::StructLiteral(lit_name, _) => {
+        Expr::StructLiteral(lit_name, _, _) => {
             if lit_name != struct_name {
                 note(reject_reason, || {
                     "returned struct literal does not match declared return type"
@@ -748,7 +748,7 @@ fn check_tuple_returning_call(
 ) -> Option<Vec<ScalarTy>> {
     let expr = program.expression.get(value_ref)?;
     let callee_name = match expr {
-        Expr::Call(n, _) => n,
+        Expr::Call(n, _, _) => n,
         _ => return None,
     };
     let callee = program
@@ -796,7 +796,7 @@ fn struct_literal_target(
 ) -> Option<DefaultSymbol> {
     let expr = program.expression.get(value_ref)?;
     let lit_name = match expr {
-        Expr::StructLiteral(name, _) => name,
+        Expr::StructLiteral(name, _, _) => name,
         _ => return None,
     };
     if !struct_layouts.contains_key(&lit_name) {
@@ -863,7 +863,7 @@ fn check_struct_returning_call(
 ) -> Option<DefaultSymbol> {
     let expr = program.expression.get(value_ref)?;
     let (callee_name, args_ref) = match expr {
-        Expr::Call(n, a) => (n, a),
+        Expr::Call(n, a, _) => (n, a),
         _ => return None,
     };
     let callee = program
@@ -937,7 +937,7 @@ fn check_enum_returning_call(
 ) -> Option<EnumLocalInfo> {
     let expr = program.expression.get(value_ref)?;
     let callee_name = match expr {
-        Expr::Call(n, _) => n,
+        Expr::Call(n, _, _) => n,
         _ => return None,
     };
     let callee = program
@@ -1184,7 +1184,7 @@ fn check_struct_literal_fields(
         None => return false,
     };
     let lit_fields = match expr {
-        Expr::StructLiteral(_, fields) => fields,
+        Expr::StructLiteral(_, fields, _) => fields,
         _ => return false,
     };
     if lit_fields.len() != layout.fields.len() {
@@ -1444,7 +1444,7 @@ fn walk_expr_for_ptr_read(program: &Program, expr_ref: &ExprRef, found: &mut boo
             }
             walk_expr_for_ptr_read(program, &el, found);
         }
-        Expr::Call(_, args) => walk_expr_for_ptr_read(program, &args, found),
+        Expr::Call(_, args, _) => walk_expr_for_ptr_read(program, &args, found),
         Expr::ExprList(es) | Expr::ArrayLiteral(es) | Expr::TupleLiteral(es) => {
             for e in &es {
                 walk_expr_for_ptr_read(program, e, found);
@@ -1753,7 +1753,13 @@ fn check_stmt(
                 true
             }
         }
-        Stmt::Break(_) | Stmt::Continue(_) => true,
+        Stmt::Break(_, None) | Stmt::Continue(_) => true,
+        // `loop` as a value-producing expression stays on the
+        // tree-walking path for now.
+        Stmt::Break(_, Some(_)) => {
+            *reject_reason = Some("`break value` (loop expression)".to_string());
+            false
+        }
         Stmt::For(_label, var, start, end, block) => {
             let start_ty = match check_expr(program, &start, locals, compound_locals, substitutions, struct_layouts, callees, ptr_read_hints, reject_reason) {
                 Some(t) => t,
@@ -1800,6 +1806,12 @@ fn check_stmt(
         // a function body (which the parser doesn't actually allow) is
         // a no-op and would not disqualify the body either way.
         Stmt::TypeAlias { .. } => true,
+        // Generators suspend mid-body, which needs the interpreter's
+        // saved-continuation machinery.
+        Stmt::Yield(_) => {
+            *reject_reason = Some("yield (generators are interpreter-only)".to_string());
+            false
+        }
     }
 }
 
@@ -2239,7 +2251,7 @@ pub(crate) fn check_expr(
                 ptr_read_hints, reject_reason,
             )
         }
-        Expr::Call(name, args_ref) => {
+        Expr::Call(name, args_ref, _) => {
             let args_expr = program.expression.get(&args_ref)?;
             let arg_list = match args_expr {
                 Expr::ExprList(v) => v,
@@ -2434,6 +2446,110 @@ pub(crate) fn check_expr(
                     );
                     None
                 }
+                BuiltinFunction::ReadBytes | BuiltinFunction::WriteBytes | BuiltinFunction::BytesFromPtr => {
+                    *reject_reason = Some(
+                        "read_bytes / write_bytes / __builtin_bytes_from_ptr (JIT does not model bytes values)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::ReadLine | BuiltinFunction::ReadAll => {
+                    *reject_reason = Some(
+                        "read_line / read_all (JIT does not yet model str scalar values)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::FormatTimestamp => {
+                    *reject_reason = Some(
+                        "format_timestamp (JIT does not yet model str scalar values)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::LogDebug | BuiltinFunction::LogInfo
+                | BuiltinFunction::LogWarn | BuiltinFunction::LogError => {
+                    *reject_reason = Some("log_* (log configuration lives in the interpreter)".to_string());
+                    None
+                }
+                BuiltinFunction::Exec => {
+                    *reject_reason = Some("exec (process spawning is interpreter-only)".to_string());
+                    None
+                }
+                BuiltinFunction::HttpGet => {
+                    *reject_reason = Some("http_get (network access is interpreter-only)".to_string());
+                    None
+                }
+                BuiltinFunction::TcpListen | BuiltinFunction::TcpAccept | BuiltinFunction::TcpRead
+                | BuiltinFunction::TcpWrite | BuiltinFunction::TcpClose | BuiltinFunction::TcpLocalPort => {
+                    *reject_reason = Some("tcp_* (network access is interpreter-only)".to_string());
+                    None
+                }
+                BuiltinFunction::JsonParse | BuiltinFunction::JsonStringify => {
+                    *reject_reason = Some("json_parse / json_stringify (JIT does not model dynamic values)".to_string());
+                    None
+                }
+                BuiltinFunction::TypeOf | BuiltinFunction::Is(_) | BuiltinFunction::Downcast(_) => {
+                    *reject_reason = Some("type_of / is / downcast (JIT does not model dynamic values)".to_string());
+                    None
+                }
+                BuiltinFunction::ToDict | BuiltinFunction::FromDict(_) => {
+                    *reject_reason = Some("to_dict / from_dict (JIT does not model dicts)".to_string());
+                    None
+                }
+                BuiltinFunction::Clone => {
+                    *reject_reason = Some("clone (JIT does not model heap values)".to_string());
+                    None
+                }
+                BuiltinFunction::Seed | BuiltinFunction::Random | BuiltinFunction::RandomRange => {
+                    // The generator state lives on the interpreter's
+                    // evaluation context; falling back keeps a single
+                    // stream per run.
+                    *reject_reason = Some(
+                        "seed / random / random_range (PRNG state lives in the interpreter)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::NowMillis | BuiltinFunction::Clock | BuiltinFunction::Sleep => {
+                    *reject_reason = Some(
+                        "now_millis / clock / sleep (no JIT time helpers)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::InlineLua => {
+                    *reject_reason = Some("lua!(...) (backend-only construct)".to_string());
+                    None
+                }
+                BuiltinFunction::Spawn | BuiltinFunction::YieldNow => {
+                    *reject_reason = Some("spawn / yield_now (task scheduler is interpreter-only)".to_string());
+                    None
+                }
+                BuiltinFunction::ParseU64 | BuiltinFunction::ParseI64
+                | BuiltinFunction::ToHex | BuiltinFunction::FormatRadix => {
+                    *reject_reason = Some(
+                        "parse_u64 / parse_i64 / to_hex / format_radix (string results are interpreter-only)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::Format | BuiltinFunction::FormatFixed | BuiltinFunction::FormatExp => {
+                    *reject_reason = Some(
+                        "format / format_fixed / format_exp (string results are interpreter-only)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::Sort => {
+                    *reject_reason = Some("sort (calls a closure comparator)".to_string());
+                    None
+                }
+                BuiltinFunction::BinarySearch | BuiltinFunction::LowerBound
+                | BuiltinFunction::Reverse | BuiltinFunction::MinOf
+                | BuiltinFunction::MaxOf | BuiltinFunction::SumOf => {
+                    *reject_reason = Some(
+                        "binary_search / lower_bound / reverse / min_of / max_of / sum_of (array argument)".to_string(),
+                    );
+                    None
+                }
+                BuiltinFunction::Matches | BuiltinFunction::Find | BuiltinFunction::ReplacePattern => {
+                    *reject_reason = Some("matches / find / replace_pattern (pattern matching is interpreter-only)".to_string());
+                    None
+                }
                 BuiltinFunction::MemCopy | BuiltinFunction::MemMove => {
                     if !check_args(
                         &[ScalarTy::Ptr, ScalarTy::Ptr, ScalarTy::U64],
@@ -27,6 +27,15 @@ pub(crate) enum ExternDispatch {
     /// result stays at `i64::MIN`, matching the legacy
     /// `BuiltinMethod::I64Abs` semantics.
     NativeAbsI64,
+    /// `__extern_*_u64` bit manipulation, used by `core/std/bits.t`.
+    /// Lowered to cranelift's `popcnt` / `clz` / `ctz` / `rotl` /
+    /// `rotr`; `clz(0)` / `ctz(0)` are 64 and rotate amounts are
+    /// taken modulo 64, matching the interpreter helpers.
+    NativeCountOnesU64,
+    NativeLeadingZerosU64,
+    NativeTrailingZerosU64,
+    NativeRotateLeftU64,
+    NativeRotateRightU64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -42,7 +51,7 @@ pub(crate) struct ExternDispatchEntry {
 /// codegen-side recipe.
 const JIT_EXTERN_DISPATCH: &[(&str, ExternDispatchEntry)] = {
     use ExternDispatch::*;
-    use ScalarTy::{F64, I64};
+    use ScalarTy::{F64, I64, U64};
     &[
         ("__extern_sin_f64", ExternDispatchEntry { dispatch: Helper(HelperKind::SinF64),  params: &[F64], ret: F64 }),
         ("__extern_cos_f64", ExternDispatchEntry { dispatch: Helper(HelperKind::CosF64),  params: &[F64], ret: F64 }),
@@ -56,6 +65,11 @@ const JIT_EXTERN_DISPATCH: &[(&str, ExternDispatchEntry)] = {
         ("__extern_ceil_f64", ExternDispatchEntry { dispatch: NativeCeilF64,  params: &[F64], ret: F64 }),
         ("__extern_abs_f64", ExternDispatchEntry { dispatch: NativeAbsF64,    params: &[F64], ret: F64 }),
         ("__extern_abs_i64", ExternDispatchEntry { dispatch: NativeAbsI64,    params: &[I64], ret: I64 }),
+        ("__extern_count_ones_u64", ExternDispatchEntry { dispatch: NativeCountOnesU64, params: &[U64], ret: U64 }),
+        ("__extern_leading_zeros_u64", ExternDispatchEntry { dispatch: NativeLeadingZerosU64, params: &[U64], ret: U64 }),
+        ("__extern_trailing_zeros_u64", ExternDispatchEntry { dispatch: NativeTrailingZerosU64, params: &[U64], ret: U64 }),
+        ("__extern_rotate_left_u64", ExternDispatchEntry { dispatch: NativeRotateLeftU64, params: &[U64, U64], ret: U64 }),
+        ("__extern_rotate_right_u64", ExternDispatchEntry { dispatch: NativeRotateRightU64, params: &[U64, U64], ret: U64 }),
         // Phase 1/2 test aliases.
         ("extern_sin", ExternDispatchEntry { dispatch: Helper(HelperKind::SinF64), params: &[F64], ret: F64 }),
         ("extern_cos", ExternDispatchEntry { dispatch: Helper(HelperKind::CosF64), params: &[F64], ret: F64 }),
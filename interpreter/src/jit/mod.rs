@@ -9,6 +9,7 @@ mod eligibility;
 mod codegen;
 mod runtime;
 
+pub use runtime::take_codegen_time;
 pub use runtime::try_execute_main;
 pub use runtime::with_jit_override;
 pub use runtime::with_jit_verbose_override;
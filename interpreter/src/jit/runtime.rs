@@ -896,9 +896,20 @@ thread_local! {
     /// Per-thread override for the JIT verbose flag. Mirrors
     /// `JIT_ENABLED_OVERRIDE`: `Some(true)` enables `JIT compiled:`
     /// / `JIT: skipped (...)` log lines on the stderr sink, `Some(false)`
-    /// suppresses them, `None` falls back to the `-v` argv probe used
-    /// by the binary entry point.
+    /// suppresses them, `None` falls back to the `info` threshold of
+    /// [`crate::logger`] (raised by the binary's `-v`).
     static JIT_VERBOSE_OVERRIDE: std::cell::Cell<Option<bool>> = const { std::cell::Cell::new(None) };
+    /// Time the last `try_execute_main` on this thread spent on
+    /// eligibility analysis and codegen. `None` when it didn't compile
+    /// (JIT off, cache hit, or no `main`).
+    static LAST_CODEGEN_TIME: std::cell::Cell<Option<std::time::Duration>> = const { std::cell::Cell::new(None) };
+}
+
+/// How long the last [`try_execute_main`] on this thread spent
+/// compiling, if it compiled at all. Lets the caller split the JIT's
+/// share out of the execution time.
+pub fn take_codegen_time() -> Option<std::time::Duration> {
+    LAST_CODEGEN_TIME.with(|c| c.take())
 }
 
 /// Run `f` with the JIT enable flag forced to `enabled`, restoring the
@@ -919,8 +930,8 @@ pub fn with_jit_override<R>(enabled: bool, f: impl FnOnce() -> R) -> R {
 
 /// Run `f` with the JIT verbose flag forced to `verbose`. Mirrors
 /// [`with_jit_override`] for verbose-log assertions in in-process
-/// integration tests (the binary's `-v` argv probe is unreachable
-/// from a library caller).
+/// integration tests, without touching the process-wide
+/// [`crate::logger`] threshold.
 pub fn with_jit_verbose_override<R>(verbose: bool, f: impl FnOnce() -> R) -> R {
     let prev = JIT_VERBOSE_OVERRIDE.with(|c| c.replace(Some(verbose)));
     struct Guard(Option<bool>);
@@ -941,11 +952,11 @@ fn jit_enabled_via_env() -> bool {
     matches!(std::env::var("INTERPRETER_JIT").as_deref(), Ok("1"))
 }
 
-fn verbose_via_argv() -> bool {
+fn verbose_enabled() -> bool {
     if let Some(forced) = JIT_VERBOSE_OVERRIDE.with(|c| c.get()) {
         return forced;
     }
-    std::env::args().any(|a| a == "-v")
+    crate::logger::enabled(crate::logger::LogLevel::Info)
 }
 
 fn find_main(program: &Program, interner: &DefaultStringInterner) -> Option<Rc<Function>> {
@@ -1003,10 +1014,11 @@ pub fn try_execute_main(
     program: &Program,
     interner: &DefaultStringInterner,
 ) -> Option<RcObject> {
+    LAST_CODEGEN_TIME.with(|c| c.set(None));
     if !jit_enabled_via_env() {
         return None;
     }
-    let verbose = verbose_via_argv();
+    let verbose = verbose_enabled();
 
     let main_fn = find_main(program, interner)?;
 
@@ -1027,7 +1039,10 @@ pub fn try_execute_main(
     let (main_ptr, main_ret) = match cache_lookup(program_id) {
         Some(hit) => hit,
         None => {
-            let eligible = match eligibility::analyze(program, &main_fn, interner) {
+            let start = std::time::Instant::now();
+            let eligible = eligibility::analyze(program, &main_fn, interner);
+            LAST_CODEGEN_TIME.with(|c| c.set(Some(start.elapsed())));
+            let eligible = match eligible {
                 Ok(e) => e,
                 Err(reason) => {
                     if verbose {
@@ -1036,14 +1051,16 @@ pub fn try_execute_main(
                     return None;
                 }
             };
-            let cached = match build_cache_entry(
+            let cached = build_cache_entry(
                 program,
                 interner,
                 &main_fn,
                 &eligible,
                 program_id,
                 verbose,
-            ) {
+            );
+            LAST_CODEGEN_TIME.with(|c| c.set(Some(start.elapsed())));
+            let cached = match cached {
                 Ok(c) => c,
                 Err(err) => {
                     if verbose {
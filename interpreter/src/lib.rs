@@ -5,10 +5,14 @@ pub mod evaluation;
 pub mod error;
 pub mod error_formatter;
 pub mod heap;
+pub mod comptime;
+pub mod coverage;
 #[cfg(feature = "jit")]
 pub mod jit;
+pub mod logger;
 pub mod module_integration;
 pub mod output;
+pub mod watch;
 
 use std::rc::Rc;
 use std::collections::HashMap;
@@ -27,6 +31,8 @@ use crate::module_integration::load_and_integrate_module;
 // `interpreter::integrate_module_into_program` symbol they did when the
 // implementation lived inline in this file.
 pub use crate::module_integration::integrate_module_into_program;
+pub use crate::evaluation::capabilities::{Capabilities, Capability};
+pub use crate::evaluation::script_log::{LogConfig, LogLevel, LogTarget};
 
 /// Common setup for TypeCheckerVisitor with struct and impl registration
 fn setup_type_checker<'a>(program: &'a mut Program, string_interner: &'a mut DefaultStringInterner) -> TypeCheckerVisitor<'a> {
@@ -239,6 +245,7 @@ fn integrate_modules(
                 ));
                 continue;
             }
+            logger::debug(&format!("Loaded core module `{dotted}`"));
             program.imports.push(ImportDecl {
                 module_path: path_syms,
                 alias: None,
@@ -281,6 +288,7 @@ fn integrate_modules(
         ) {
             errors.push(format!("Module integration error: {}", err));
         } else {
+            logger::debug(&format!("Loaded imported module `{module_name}`"));
             loaded_modules.insert(module_name);
         }
     }
@@ -348,11 +356,98 @@ pub fn check_typing_with_core_modules(
     filename: Option<&str>,
     core_modules_dir: Option<&std::path::Path>,
 ) -> Result<(), Vec<String>> {
+    check_typing_with_results(program, string_interner, source_code, filename, core_modules_dir)
+        .map(|_| ())
+}
+
+/// Same as `check_typing_with_core_modules`, but hands back the
+/// checker's per-expression types (`TypeCheckResults::expr_types`) so
+/// callers such as `--emit-typed-ast` can show what inference decided.
+/// Number literals are already finalized in `program.expression` by the
+/// time this returns.
+pub fn check_typing_with_results(
+    program: &mut Program,
+    string_interner: &mut DefaultStringInterner,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    core_modules_dir: Option<&std::path::Path>,
+) -> Result<compiler_core::TypeCheckResults, Vec<String>> {
+    let cfg = frontend::CfgEnv::for_target(frontend::cfg::TARGET_INTERPRETER);
+    check_typing_cached(program, string_interner, None, source_code, filename, core_modules_dir, &cfg, None, None)
+}
+
+/// Same as `check_typing_with_results`, but prunes `@cfg(...)` items
+/// against `cfg` instead of the interpreter's default environment
+/// (`target = "interpreter"`). Backends call this with their own
+/// `target` plus the user's `--define`s.
+pub fn check_typing_with_cfg(
+    program: &mut Program,
+    string_interner: &mut DefaultStringInterner,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    core_modules_dir: Option<&std::path::Path>,
+    cfg: &frontend::CfgEnv,
+) -> Result<compiler_core::TypeCheckResults, Vec<String>> {
+    check_typing_cached(program, string_interner, None, source_code, filename, core_modules_dir, cfg, None, None)
+}
+
+/// Same as `check_typing_with_results`, but checks inside `session`:
+/// the program must have been parsed with the session's interner, and
+/// user functions unchanged since an earlier check in the same session
+/// are served from its function check cache instead of being re-walked.
+pub fn check_typing_in_session(
+    program: &mut Program,
+    session: &mut compiler_core::CompilerSession,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    core_modules_dir: Option<&std::path::Path>,
+    cfg: &frontend::CfgEnv,
+) -> Result<compiler_core::TypeCheckResults, Vec<String>> {
+    let start = std::time::Instant::now();
+    let mut resolution = std::time::Duration::ZERO;
+    let (string_interner, cache) = session.interner_and_check_cache_mut();
+    let result = check_typing_cached(
+        program,
+        string_interner,
+        Some(cache),
+        source_code,
+        filename,
+        core_modules_dir,
+        cfg,
+        None,
+        Some(&mut resolution),
+    );
+    session.record_phase(compiler_core::Phase::ModuleResolution, resolution);
+    session.record_phase(compiler_core::Phase::TypeCheck, start.elapsed().saturating_sub(resolution));
+    result
+}
+
+/// `fixes`, when given, receives the fix-its attached to the errors of
+/// user function bodies (see [`import_fixes`]). `resolution_time`,
+/// when given, receives the time spent integrating modules and
+/// resolving type aliases, the part of the check that isn't checking.
+#[allow(clippy::too_many_arguments)]
+fn check_typing_cached(
+    program: &mut Program,
+    string_interner: &mut DefaultStringInterner,
+    mut cache: Option<&mut FunctionCheckCache>,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    core_modules_dir: Option<&std::path::Path>,
+    cfg: &frontend::CfgEnv,
+    mut fixes: Option<&mut Vec<FixIt>>,
+    resolution_time: Option<&mut std::time::Duration>,
+) -> Result<compiler_core::TypeCheckResults, Vec<String>> {
     let mut errors: Vec<String> = vec![];
     
     // Clone string_interner for later use
     let string_interner_for_names = string_interner.clone();
 
+    // Drop the user's `@cfg` items that don't apply to this target
+    // before anything counts or scans the program. Module items are
+    // pruned the same way once integration has carried them over.
+    frontend::prune_cfg(program, cfg);
+
     // Snapshot user-function count BEFORE integration so we can
     // re-extract the user-authored slice once integration + alias
     // resolution have run. The type-checker only walks bodies the
@@ -367,10 +462,15 @@ pub fn check_typing_with_core_modules(
     // etc. must be visible to the type-checker registration pass and
     // to `build_method_registry` so `x.abs()` resolves through the
     // extension-trait machinery.
+    let resolution_start = std::time::Instant::now();
     if let Err(module_errors) = integrate_modules(program, string_interner, core_modules_dir) {
+        if let Some(elapsed) = resolution_time {
+            *elapsed = resolution_start.elapsed();
+        }
         errors.extend(module_errors);
         return Err(errors);
     }
+    frontend::prune_cfg(program, cfg);
 
     // Cross-module type-alias resolution. A `type String = Vec<u8>`
     // declaration in `core/std/string.t` is parsed by that file's
@@ -382,6 +482,9 @@ pub fn check_typing_with_core_modules(
     // substitutes every alias reference (chains and generic
     // aliases included) before any type-check work runs.
     frontend::resolve_type_aliases(program);
+    if let Some(elapsed) = resolution_time {
+        *elapsed = resolution_start.elapsed();
+    }
 
     // Pull the user-authored function slice from the resolved
     // `program.function`. Integration appends stdlib functions
@@ -391,6 +494,9 @@ pub fn check_typing_with_core_modules(
     let functions: Vec<std::rc::Rc<frontend::ast::Function>> =
         program.function.iter().take(user_func_count).cloned().collect();
 
+    // Fingerprint the user bodies while the pools are still unchecked.
+    let fingerprints = cache.as_ref().map(|c| c.fingerprint(program, &functions));
+
     // The impl_blocks walk runs over all statements (user +
     // integrated module + prelude) so impl blocks from every source
     // contribute methods to `context.struct_methods`.
@@ -404,8 +510,20 @@ pub fn check_typing_with_core_modules(
         }
     }
 
+    // An `alias::f(...)` call on a module nobody imported gets an
+    // `import` fix-it when the module sits where imports resolve from.
+    let mut module_resolver = frontend::ModuleResolver::with_search_paths(
+        core_modules_dir
+            .map(std::path::Path::to_path_buf)
+            .into_iter()
+            .chain([std::path::PathBuf::from("modules")])
+            .collect(),
+    );
+
     // Setup TypeChecker now that imports and prelude are integrated.
     let mut tc = setup_type_checker(program, string_interner);
+    tc.core.module_resolver = Some(&mut module_resolver);
+    tc.inline_lua_allowed = cfg.get("target") == Some(frontend::cfg::TARGET_LUA);
 
     // Create error formatter if we have source code and filename
     let formatter = if let (Some(source), Some(file)) = (source_code, filename) {
@@ -414,6 +532,19 @@ pub fn check_typing_with_core_modules(
         None
     };
 
+    // Two functions (or structs) with the same name: registration keeps
+    // only the last one, so stop before anything is checked against it.
+    tc.source_code = source_code;
+    for err in tc.duplicate_definition_errors() {
+        errors.push(match formatter {
+            Some(ref fmt) => fmt.format_type_check_error(&err),
+            None => format!("Declaration validation error: {err}"),
+        });
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     // Validate struct field types and register enum declarations. Running
     // visit_stmt on an EnumDecl populates `context.enum_definitions`, which
     // later passes (impl blocks, function bodies) consult when resolving
@@ -476,6 +607,12 @@ pub fn check_typing_with_core_modules(
     // Process impl blocks and collect errors
     errors.extend(process_impl_blocks_extracted(&mut tc, &impl_blocks, &formatter));
 
+    // Functions unchanged since an earlier check in this session are
+    // marked checked up front, so `type_check` skips their bodies.
+    if let (Some(cache), Some(prints)) = (cache.as_deref_mut(), fingerprints.as_ref()) {
+        cache.replay(&mut tc, &functions, prints);
+    }
+
     // Process functions
     functions.iter().for_each(|func| {
         let name = string_interner_for_names.resolve(func.name).unwrap_or("<NOT_FOUND>");
@@ -494,6 +631,11 @@ pub fn check_typing_with_core_modules(
                     offset: location.offset,
                 });
             }
+            if let (Some(fixes), Some(fix)) = (fixes.as_deref_mut(), error.fix()) {
+                if !fixes.contains(fix) {
+                    fixes.push(fix.clone());
+                }
+            }
             
             // Use formatter if available, otherwise fallback to simple format
             let formatted_error = if let Some(ref fmt) = formatter {
@@ -506,8 +648,15 @@ pub fn check_typing_with_core_modules(
         }
     });
 
+    if let (Some(cache), Some(prints)) = (cache, fingerprints.as_ref()) {
+        cache.record(&tc, &functions, prints);
+    }
+
     if errors.is_empty() {
-        Ok(())
+        let expr_types = tc.get_expr_types();
+        let struct_types = tc.get_struct_var_mappings(&string_interner_for_names);
+        let purity = frontend::purity::infer_purity(program, &expr_types, string_interner);
+        Ok(compiler_core::TypeCheckResults { expr_types, struct_types, purity })
     } else {
         Err(errors)
     }
@@ -699,14 +848,112 @@ fn register_methods(
 }
 
 pub fn execute_program(program: &Program, string_interner: &DefaultStringInterner, source_code: Option<&str>, filename: Option<&str>) -> Result<RcObject, String> {
+    execute_program_with_capabilities(program, string_interner, source_code, filename, Capabilities::default())
+}
+
+/// [`execute_program`] under an explicit capability set. Embedders
+/// running untrusted code pass `Capabilities::none()` plus whatever
+/// the script legitimately needs; `run_source` routes
+/// [`RunOptions::capabilities`] through here.
+pub fn execute_program_with_capabilities(
+    program: &Program,
+    string_interner: &DefaultStringInterner,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    capabilities: Capabilities,
+) -> Result<RcObject, String> {
+    execute_main(program, string_interner, source_code, filename, capabilities, None, None, None, None)
+}
+
+/// [`execute_program_with_capabilities`] that also reports the run's
+/// heap counters, typed stores included. A `main` the JIT runs
+/// natively never touches the interpreter heap, so its counters stay
+/// zero.
+pub fn execute_program_with_heap_stats(
+    program: &Program,
+    string_interner: &DefaultStringInterner,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    capabilities: Capabilities,
+) -> (Result<RcObject, String>, heap::HeapStats) {
+    let mut stats = heap::HeapStats::default();
+    let result = execute_main(program, string_interner, source_code, filename, capabilities, None, None, Some(&mut stats), None);
+    (result, stats)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_main(
+    program: &Program,
+    string_interner: &DefaultStringInterner,
+    source_code: Option<&str>,
+    filename: Option<&str>,
+    capabilities: Capabilities,
+    deterministic_seed: Option<u64>,
+    log: Option<&LogConfig>,
+    heap_stats: Option<&mut heap::HeapStats>,
+    mut coverage: Option<&mut coverage::Coverage>,
+) -> Result<RcObject, String> {
     let main_function = match find_main_function(program, string_interner) {
         Ok(func) => func,
         Err(e) => return Err(format!("Runtime Error: {e}")),
     };
     
+    let mut string_interner_mut = string_interner.clone();
+    let mut eval = prepare_evaluation(program, string_interner, &mut string_interner_mut, capabilities)?;
+    eval.set_trace_alloc(heap_stats.is_some());
+    if let Some(seed) = deterministic_seed {
+        eval.set_deterministic(seed);
+    }
+    if let Some(log) = log {
+        eval.log = log.clone();
+    }
+
+    // Coverage counts tree-walker evaluations, so a covered run never
+    // hands `main` to the JIT.
+    #[cfg(feature = "jit")]
+    if coverage.is_none() {
+        if let Some(result) = jit::try_execute_main(program, string_interner) {
+            return Ok(result);
+        }
+    }
+
+    let no_args = vec![];
+    eval.coverage = coverage.as_deref_mut().map(std::mem::take);
+    let result = eval.evaluate_function(main_function, &no_args);
+    if let Some(stats) = heap_stats {
+        *stats = eval.heap_stats();
+    }
+    if let (Some(coverage), Some(counts)) = (coverage, eval.coverage.take()) {
+        *coverage = counts;
+    }
+    match result {
+        Ok(result) => Ok(result),
+        Err(runtime_error) => {
+            // Format runtime error with source location if available
+            let formatted_error = if let (Some(source), Some(file)) = (source_code, filename) {
+                let formatter = ErrorFormatter::new(source, file);
+                // Try to extract location from runtime error if possible
+                formatter.format_runtime_error(&runtime_error.to_string(), None)
+            } else {
+                format!("Runtime Error: {runtime_error}")
+            };
+            Err(formatted_error)
+        }
+    }
+}
+
+/// Build an evaluation context for `program` with its functions,
+/// methods, enum / struct registries and top-level consts in place,
+/// ready to call into. `string_interner_mut` is the context's own
+/// copy of `string_interner`.
+pub(crate) fn prepare_evaluation<'a>(
+    program: &'a Program,
+    string_interner: &DefaultStringInterner,
+    string_interner_mut: &'a mut DefaultStringInterner,
+    capabilities: Capabilities,
+) -> Result<EvaluationContext<'a>, String> {
     let func_map = build_function_map(program, string_interner);
     let func_qualified = build_function_qualified_map(program);
-    let mut string_interner_mut = string_interner.clone();
     let method_registry = build_method_registry(program, string_interner)
         .map_err(|e| format!("Runtime Error: {}", e))?;
     let drop_trait_structs = collect_drop_trait_structs(program, string_interner);
@@ -714,7 +961,7 @@ pub fn execute_program(program: &Program, string_interner: &DefaultStringInterne
     let mut eval = EvaluationContext::new_with_qualified(
         &program.statement,
         &program.expression,
-        &mut string_interner_mut,
+        string_interner_mut,
         func_map,
         func_qualified,
     );
@@ -724,6 +971,7 @@ pub fn execute_program(program: &Program, string_interner: &DefaultStringInterne
 
     register_methods(&mut eval, method_registry);
     eval.drop_trait_structs = drop_trait_structs;
+    eval.capabilities = capabilities;
 
     // Register enum and struct declarations so runtime lookup of
     // `Enum::Variant` paths works and so `Object::{Struct,EnumVariant}`
@@ -789,28 +1037,7 @@ pub fn execute_program(program: &Program, string_interner: &DefaultStringInterne
         eval.environment.set_val(c.name, (value).into());
     }
 
-    #[cfg(feature = "jit")]
-    {
-        if let Some(result) = jit::try_execute_main(program, string_interner) {
-            return Ok(result);
-        }
-    }
-
-    let no_args = vec![];
-    match eval.evaluate_function(main_function, &no_args) {
-        Ok(result) => Ok(result),
-        Err(runtime_error) => {
-            // Format runtime error with source location if available
-            let formatted_error = if let (Some(source), Some(file)) = (source_code, filename) {
-                let formatter = ErrorFormatter::new(source, file);
-                // Try to extract location from runtime error if possible
-                formatter.format_runtime_error(&runtime_error.to_string(), None)
-            } else {
-                format!("Runtime Error: {runtime_error}")
-            };
-            Err(formatted_error)
-        }
-    }
+    Ok(eval)
 }
 
 /// Options for [`run_source`]: parameters that the `interpreter` binary
@@ -819,11 +1046,221 @@ pub fn execute_program(program: &Program, string_interner: &DefaultStringInterne
 /// `jit` mirrors the `INTERPRETER_JIT=1` env var but is per-call so
 /// in-process callers can drive the JIT and tree-walker paths in the
 /// same process without poisoning a sibling thread's run. `core_modules_dir`
-/// mirrors `--core-modules` / `TOYLANG_CORE_MODULES`.
+/// mirrors `--core-modules` / `TOYLANG_CORE_MODULES`. `capabilities`
+/// mirrors `--sandbox` / `--allow-<capability>`. `defines` mirrors
+/// `--define NAME=value`; `target` is always `"interpreter"`.
+/// `trace_alloc` mirrors `--trace-alloc`: print the heap counters
+/// ([`heap::HeapStats`]) to stderr once the program has run.
+/// `deterministic` mirrors `--deterministic[=SEED]`: seed the random
+/// numbers from `SEED` and run the time builtins on a simulated
+/// clock, so two runs of the same program print the same bytes.
+/// `coverage` mirrors `--coverage[=FILE]`: count which of the program's
+/// lines ran and write an lcov report ([`coverage::Coverage::lcov`]) to
+/// the path; a covered run never uses the JIT.
+/// `log` mirrors `TOYLANG_LOG` / `TOYLANG_LOG_FILE`: the threshold and
+/// destination of the script's `log_*` calls (`None` writes `info` and
+/// above to stderr).
 #[derive(Debug, Default, Clone, Copy)]
 pub struct RunOptions<'a> {
     pub jit: bool,
     pub core_modules_dir: Option<&'a std::path::Path>,
+    pub capabilities: Capabilities,
+    pub defines: Option<&'a frontend::CfgEnv>,
+    pub trace_alloc: bool,
+    pub deterministic: Option<u64>,
+    pub coverage: Option<&'a std::path::Path>,
+    pub log: Option<&'a LogConfig>,
+}
+
+/// Output format for [`emit_ast`]. Selected on the command line with
+/// `--emit-ast` (pretty), `--emit-ast=json`, and `--emit-ast-dot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AstDumpFormat {
+    Pretty,
+    Json,
+    Dot,
+}
+
+/// Parse `source` and render the resulting AST in `format`. Only the
+/// user's file is dumped — the prelude and core modules are not
+/// integrated, so the output maps one-to-one onto the source text.
+pub fn emit_ast(source: &str, filename: &str, format: AstDumpFormat) -> Result<String, String> {
+    let mut session = compiler_core::CompilerSession::new();
+    let program = match session.parse_named_source(filename, source) {
+        Ok(p) => p,
+        Err(err) => {
+            ErrorFormatter::new(source, filename).display_parse_errors(std::slice::from_ref(&err));
+            return Err(format!("parse error: {err:?}"));
+        }
+    };
+    let tree = frontend::ast::dump::AstDumper::new(&program, session.string_interner()).build();
+    Ok(render_dump(&tree, format))
+}
+
+/// Like [`emit_ast`], but runs the full type-check pipeline first and
+/// annotates every expression with its resolved type. Generic calls show
+/// their substituted result type and Number literals show the concrete
+/// integer type inference settled on. Only user-authored functions are
+/// dumped; integrated prelude / core-module items are skipped.
+pub fn emit_typed_ast(
+    source: &str,
+    filename: &str,
+    format: AstDumpFormat,
+    core_modules_dir: Option<&std::path::Path>,
+) -> Result<String, String> {
+    let formatter = ErrorFormatter::new(source, filename);
+    let mut session = compiler_core::CompilerSession::new();
+    let mut program = match session.parse_named_source(filename, source) {
+        Ok(p) => p,
+        Err(err) => {
+            formatter.display_parse_errors(std::slice::from_ref(&err));
+            return Err(format!("parse error: {err:?}"));
+        }
+    };
+    let user_func_count = program.function.len();
+    let user_stmt_count = program.statement.len();
+    let user_import_count = program.imports.len();
+    let results = match check_typing_with_results(
+        &mut program,
+        session.string_interner_mut(),
+        Some(source),
+        Some(filename),
+        core_modules_dir,
+    ) {
+        Ok(r) => r,
+        Err(errors) => {
+            formatter.display_type_check_errors(&errors);
+            return Err(format!("{} type-check error(s)", errors.len()));
+        }
+    };
+    // Integration appends module functions / statements after the
+    // user's own, so truncating to the pre-integration counts keeps
+    // the dump focused on the input file.
+    program.function.truncate(user_func_count);
+    program.imports.truncate(user_import_count);
+    let tree = frontend::ast::dump::AstDumper::new(&program, session.string_interner())
+        .with_expr_types(&results.expr_types)
+        .with_stmt_limit(user_stmt_count)
+        .build();
+    Ok(render_dump(&tree, format))
+}
+
+/// `--xref SYMBOL`: the definition, uses and call sites of `SYMBOL` in
+/// `source`, one `file:line:column: what` line each. Symbols are named
+/// as in `compiler_core::XrefIndex` (`main`, `Point`, `Point::x`).
+pub fn xref(source: &str, filename: &str, symbol: &str) -> Result<String, String> {
+    let formatter = ErrorFormatter::new(source, filename);
+    let mut session = compiler_core::CompilerSession::new();
+    let program = match session.parse_named_source(filename, source) {
+        Ok(p) => p,
+        Err(err) => {
+            formatter.display_parse_errors(std::slice::from_ref(&err));
+            return Err(format!("parse error: {err:?}"));
+        }
+    };
+    // Only the index this builds is wanted. Without the core modules
+    // integrated the check itself may well fail.
+    let _ = session.type_check_program(&program);
+    let references = session
+        .references(symbol)
+        .ok_or_else(|| format!("no symbol `{symbol}` in {filename}"))?;
+    let callers = session.callers(symbol);
+    let line = |loc: &compiler_core::SymbolLocation, what: &str| {
+        format!("{}:{}:{}: {}\n", filename, loc.line, loc.column, what)
+    };
+    let mut out = String::new();
+    if let Some(definition) = &references.definition {
+        out.push_str(&line(definition, "definition"));
+    }
+    for usage in &references.uses {
+        match callers.iter().find(|call| call.location == *usage) {
+            Some(call) => out.push_str(&line(usage, &format!("call from {}", call.caller))),
+            None => out.push_str(&line(usage, "use")),
+        }
+    }
+    Ok(out)
+}
+
+/// `--lint`: type-check `source`, then run the lints `config` enables
+/// over the user's own items. Parse and type errors are printed and
+/// turn into `Err`; lint findings are returned for the caller to show
+/// (see [`ErrorFormatter::format_lint`]).
+pub fn lint(
+    source: &str,
+    filename: &str,
+    core_modules_dir: Option<&std::path::Path>,
+    config: &frontend::lints::LintConfig,
+) -> Result<Vec<frontend::lints::Lint>, String> {
+    let formatter = ErrorFormatter::new(source, filename);
+    let mut session = compiler_core::CompilerSession::new();
+    let program = match session.parse_named_source(filename, source) {
+        Ok(p) => p,
+        Err(err) => {
+            formatter.display_parse_errors(std::slice::from_ref(&err));
+            return Err(format!("parse error: {err:?}"));
+        }
+    };
+    // Checking integrates modules into the program it is given; the
+    // lints only look at what the user wrote.
+    let mut checked = program.clone();
+    if let Err(errors) = check_typing_with_results(
+        &mut checked,
+        session.string_interner_mut(),
+        Some(source),
+        Some(filename),
+        core_modules_dir,
+    ) {
+        formatter.display_type_check_errors(&errors);
+        return Err(format!("{} type-check error(s)", errors.len()));
+    }
+    Ok(frontend::lints::run_lints(&program, session.string_interner(), source, config))
+}
+
+/// The fix-its attached to the type errors in `source`, for `--fix`.
+/// Nothing is printed; a source that fails to parse, checks cleanly or
+/// has nothing fixable yields none.
+pub fn import_fixes(source: &str, filename: &str, core_modules_dir: Option<&std::path::Path>) -> Vec<FixIt> {
+    let mut session = compiler_core::CompilerSession::new();
+    let Ok(mut program) = session.parse_named_source(filename, source) else {
+        return Vec::new();
+    };
+    let cfg = frontend::CfgEnv::for_target(frontend::cfg::TARGET_INTERPRETER);
+    let mut fixes = Vec::new();
+    let _ = check_typing_cached(
+        &mut program,
+        session.string_interner_mut(),
+        None,
+        Some(source),
+        Some(filename),
+        core_modules_dir,
+        &cfg,
+        Some(&mut fixes),
+        None,
+    );
+    fixes
+}
+
+/// `source` with every fix-it in `fixes` applied.
+pub fn apply_fixes(source: &str, fixes: &[FixIt]) -> String {
+    let mut fixes: Vec<&FixIt> = fixes.iter().collect();
+    fixes.sort_by_key(|fix| fix.offset);
+    let mut out = String::with_capacity(source.len());
+    let mut copied = 0;
+    for fix in fixes {
+        out.push_str(&source[copied..fix.offset]);
+        out.push_str(&fix.text);
+        copied = fix.offset;
+    }
+    out.push_str(&source[copied..]);
+    out
+}
+
+fn render_dump(tree: &frontend::ast::dump::DumpNode, format: AstDumpFormat) -> String {
+    match format {
+        AstDumpFormat::Pretty => frontend::ast::dump::to_pretty(tree),
+        AstDumpFormat::Json => frontend::ast::dump::to_json(tree),
+        AstDumpFormat::Dot => frontend::ast::dump::to_dot(tree),
+    }
 }
 
 /// Outcome of [`run_source`]. `exit_code` mirrors the value the
@@ -848,38 +1285,98 @@ pub fn run_source(
     filename: &str,
     options: &RunOptions<'_>,
 ) -> Result<RunOutcome, String> {
-    let formatter = ErrorFormatter::new(source, filename);
     let mut session = compiler_core::CompilerSession::new();
-    let mut program = match session.parse_program_with_source(source, filename) {
+    run_source_in_session(&mut session, source, filename, options)
+}
+
+/// [`run_source`] inside a caller-owned session. Repeated runs through
+/// the same session skip re-checking functions that did not change
+/// (see [`check_typing_in_session`]). Each phase's time is added to
+/// [`CompilerSession::timings`](compiler_core::CompilerSession::timings).
+pub fn run_source_in_session(
+    session: &mut compiler_core::CompilerSession,
+    source: &str,
+    filename: &str,
+    options: &RunOptions<'_>,
+) -> Result<RunOutcome, String> {
+    let formatter = ErrorFormatter::new(source, filename);
+    logger::info(&format!("Parsing source file: {filename}"));
+    let mut program = match session.parse_named_source(filename, source) {
         Ok(p) => p,
         Err(err) => {
             // Print the same diagnostic the binary used to emit, then
             // hand a short summary back to the caller so it can decide
             // how to surface it (e.g. test assertions vs. process exit).
-            formatter.format_parse_error(&err);
+            formatter.display_parse_errors(std::slice::from_ref(&err));
             return Err(format!("parse error: {err:?}"));
         }
     };
-    if let Err(errors) = check_typing_with_core_modules(
+    // Sized before the core modules are integrated, so only the user's
+    // own nodes are counted.
+    let coverage = options.coverage.map(|_| coverage::Coverage::new(&program));
+    let mut cfg = options.defines.cloned().unwrap_or_default();
+    cfg.define("target", frontend::cfg::TARGET_INTERPRETER);
+    logger::info("Performing type checking");
+    if let Err(errors) = check_typing_in_session(
         &mut program,
-        session.string_interner_mut(),
+        session,
         Some(source),
         Some(filename),
         options.core_modules_dir,
+        &cfg,
     ) {
         formatter.display_type_check_errors(&errors);
         return Err(format!("{} type-check error(s)", errors.len()));
     }
 
+    logger::info("Executing program");
+    let execution_start = std::time::Instant::now();
+    let execute = || {
+        let interner = session.string_interner();
+        let mut coverage = coverage;
+        let mut stats = options.trace_alloc.then(heap::HeapStats::default);
+        let result = execute_main(
+            &program,
+            interner,
+            Some(source),
+            Some(filename),
+            options.capabilities,
+            options.deterministic,
+            options.log,
+            stats.as_mut(),
+            coverage.as_mut(),
+        );
+        if let Some(stats) = stats {
+            eprint!("{}", stats.report(interner));
+        }
+        if let (Some(path), Some(coverage)) = (options.coverage, &coverage) {
+            if let Err(e) = std::fs::write(path, coverage.lcov(&program, interner, source, filename)) {
+                eprintln!("failed to write coverage to {}: {}", path.display(), e);
+            }
+        }
+        result
+    };
     #[cfg(feature = "jit")]
-    let exec_result = jit::with_jit_override(options.jit, || {
-        execute_program(&program, session.string_interner(), Some(source), Some(filename))
-    });
+    let exec_result = jit::with_jit_override(options.jit, execute);
     #[cfg(not(feature = "jit"))]
     let exec_result = {
         let _ = options.jit;
-        execute_program(&program, session.string_interner(), Some(source), Some(filename))
+        execute()
     };
+    // The JIT compiles `main` inside the run; report that as codegen
+    // rather than execution.
+    let execution_time = execution_start.elapsed();
+    #[cfg(feature = "jit")]
+    let codegen_time = jit::take_codegen_time();
+    #[cfg(not(feature = "jit"))]
+    let codegen_time: Option<std::time::Duration> = None;
+    if let Some(codegen_time) = codegen_time {
+        session.record_phase(compiler_core::Phase::Codegen, codegen_time);
+    }
+    session.record_phase(
+        compiler_core::Phase::Execution,
+        execution_time.saturating_sub(codegen_time.unwrap_or_default()),
+    );
 
     let result = match exec_result {
         Ok(r) => r,
@@ -0,0 +1,79 @@
+//! The interpreter's own diagnostics: which core modules were loaded,
+//! how far the pipeline got, what the JIT compiled or skipped.
+//!
+//! Nothing here is meant for the program's user, so it stays out of
+//! stderr unless asked for. The threshold is process-wide and defaults
+//! to `warn`; the `interpreter` binary raises it with `-v` (`info`) or
+//! `--log-level <LEVEL>`. Lines are written to the stderr sink in
+//! [`crate::output`] without a level tag, so an in-process test that
+//! captures stderr sees exactly what the binary prints.
+//!
+//! This is separate from the `log_*` builtins (`evaluation::script_log`),
+//! which are the program's messages and have their own threshold. Both
+//! share [`LogLevel`].
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub use crate::evaluation::script_log::LogLevel;
+
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Warn as u8);
+
+thread_local! {
+    /// Per-thread threshold that wins over the process-wide one, so
+    /// parallel in-process tests can turn diagnostics on without
+    /// affecting each other.
+    static LEVEL_OVERRIDE: Cell<Option<LogLevel>> = const { Cell::new(None) };
+}
+
+/// Set the process-wide threshold.
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// The threshold in effect on this thread.
+pub fn level() -> LogLevel {
+    if let Some(level) = LEVEL_OVERRIDE.with(|c| c.get()) {
+        return level;
+    }
+    let raw = LEVEL.load(Ordering::Relaxed);
+    LogLevel::ALL.into_iter().find(|l| *l as u8 == raw).unwrap_or(LogLevel::Warn)
+}
+
+/// Run `f` with this thread's threshold forced to `level`, restoring
+/// the previous override on return — even if `f` panics.
+pub fn with_level<R>(level: LogLevel, f: impl FnOnce() -> R) -> R {
+    let prev = LEVEL_OVERRIDE.with(|c| c.replace(Some(level)));
+    struct Guard(Option<LogLevel>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let prev = self.0;
+            LEVEL_OVERRIDE.with(|c| c.set(prev));
+        }
+    }
+    let _guard = Guard(prev);
+    f()
+}
+
+pub fn enabled(level: LogLevel) -> bool {
+    level != LogLevel::Off && level >= self::level()
+}
+
+/// Write `message` to stderr if `level` passes the threshold.
+pub fn log(level: LogLevel, message: &str) {
+    if enabled(level) {
+        crate::output::eprintln_text(message);
+    }
+}
+
+pub fn debug(message: &str) {
+    log(LogLevel::Debug, message);
+}
+
+pub fn info(message: &str) {
+    log(LogLevel::Info, message);
+}
+
+pub fn warn(message: &str) {
+    log(LogLevel::Warn, message);
+}
@@ -1,8 +1,8 @@
 use std::env;
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
-use interpreter::{RunOptions, RunOutcome};
+use compiler_core::TimingFormat;
+use interpreter::{logger, AstDumpFormat, Capabilities, Capability, LogConfig, LogLevel, RunOptions, RunOutcome};
 
 /// Resolve the core-modules directory using a small priority chain:
 ///
@@ -57,18 +57,96 @@ fn resolve_core_modules_dir(cli_override: Option<PathBuf>) -> Option<PathBuf> {
 /// `resolve_core_modules_dir`.
 struct CliArgs {
     filename: String,
-    verbose: bool,
+    /// Threshold for the interpreter's own diagnostics: `warn` by
+    /// default, `info` with `-v`, anything with `--log-level <LEVEL>`.
+    log_level: LogLevel,
     core_modules_cli: Option<PathBuf>,
+    /// `--emit-ast[=pretty|json]` / `--emit-ast-dot`: print the parsed
+    /// AST instead of running the program.
+    emit_ast: Option<AstDumpFormat>,
+    /// `--emit-typed-ast[=pretty|json]`: like `emit_ast`, but after type
+    /// checking, with each expression annotated with its type.
+    emit_typed_ast: Option<AstDumpFormat>,
+    /// Host access for the run: the default set, narrowed to nothing
+    /// by `--sandbox`, then widened by each `--allow-<capability>`.
+    capabilities: Capabilities,
+    /// `--define NAME=value`: extra names for `@cfg(...)` predicates.
+    defines: frontend::CfgEnv,
+    /// `--trace-alloc`: print heap counters to stderr after the run.
+    trace_alloc: bool,
+    /// `--deterministic[=SEED]`: seed for a reproducible run.
+    deterministic: Option<u64>,
+    /// `--watch`: rerun whenever the file or a module source changes.
+    watch: bool,
+    /// `--xref SYMBOL`: list where `SYMBOL` is defined, used and called
+    /// instead of running the program.
+    xref: Option<String>,
+    /// `--fix`: apply the suggested fixes (missing imports) to the file
+    /// before running it.
+    fix: bool,
+    /// `--lint`: report lint findings instead of running the program.
+    lint: bool,
+    /// `--coverage[=FILE]`: write an lcov report of the lines the run
+    /// executed to `FILE` (`lcov.info` by default).
+    coverage: Option<PathBuf>,
+    /// `--timings[=text|json]`: print per-phase times to stderr after
+    /// the run. `-v` implies `text`.
+    timings: Option<TimingFormat>,
+}
+
+fn parse_log_level(name: &str) -> Result<LogLevel, String> {
+    LogLevel::from_name(name)
+        .ok_or_else(|| format!("invalid --log-level `{name}` (expected debug, info, warn, error or off)"))
 }
 
 fn parse_cli(raw: &[String]) -> Result<CliArgs, String> {
     let mut filename: Option<String> = None;
-    let mut verbose = false;
+    let mut log_level = LogLevel::Warn;
     let mut core_modules_cli: Option<PathBuf> = None;
+    let mut emit_ast: Option<AstDumpFormat> = None;
+    let mut emit_typed_ast: Option<AstDumpFormat> = None;
+    let mut sandbox = false;
+    let mut trace_alloc = false;
+    let mut deterministic: Option<u64> = None;
+    let mut watch = false;
+    let mut fix = false;
+    let mut lint = false;
+    let mut xref: Option<String> = None;
+    let mut coverage: Option<PathBuf> = None;
+    let mut timings: Option<TimingFormat> = None;
+    let mut granted: Vec<Capability> = Vec::new();
+    let mut defines = frontend::CfgEnv::new();
     let mut iter = raw.iter().skip(1);
     while let Some(arg) = iter.next() {
         match arg.as_str() {
-            "-v" | "--verbose" => verbose = true,
+            "-v" | "--verbose" => log_level = log_level.min(LogLevel::Info),
+            "--log-level" => {
+                let v = iter
+                    .next()
+                    .ok_or_else(|| "--log-level needs a level argument".to_string())?;
+                log_level = parse_log_level(v)?;
+            }
+            s if s.starts_with("--log-level=") => log_level = parse_log_level(&s["--log-level=".len()..])?,
+            "--sandbox" => sandbox = true,
+            "--trace-alloc" => trace_alloc = true,
+            "--watch" => watch = true,
+            "--fix" => fix = true,
+            "--lint" => lint = true,
+            "--coverage" => coverage = Some(PathBuf::from(interpreter::coverage::DEFAULT_REPORT_FILE)),
+            s if s.starts_with("--coverage=") => coverage = Some(PathBuf::from(&s["--coverage=".len()..])),
+            "--timings" => timings = Some(TimingFormat::Text),
+            s if s.starts_with("--timings=") => timings = Some(TimingFormat::parse(&s["--timings=".len()..])?),
+            "--deterministic" => deterministic = Some(0),
+            s if s.starts_with("--deterministic=") => {
+                let seed = &s["--deterministic=".len()..];
+                deterministic = Some(seed.parse().map_err(|_| format!("invalid --deterministic seed: {seed}"))?);
+            }
+            s if s.starts_with("--allow-") => {
+                let name = &s["--allow-".len()..];
+                let cap = Capability::from_flag_name(name)
+                    .ok_or_else(|| format!("unknown capability: {name}"))?;
+                granted.push(cap);
+            }
             "--core-modules" => {
                 let v = iter
                     .next()
@@ -78,7 +156,32 @@ fn parse_cli(raw: &[String]) -> Result<CliArgs, String> {
             s if s.starts_with("--core-modules=") => {
                 core_modules_cli = Some(PathBuf::from(&s["--core-modules=".len()..]));
             }
-            s if s.starts_with('-') => {
+            "--define" => {
+                let v = iter
+                    .next()
+                    .ok_or_else(|| "--define needs a NAME=value argument".to_string())?;
+                defines.define_arg(v)?;
+            }
+            s if s.starts_with("--define=") => defines.define_arg(&s["--define=".len()..])?,
+            "--xref" => {
+                let v = iter
+                    .next()
+                    .ok_or_else(|| "--xref needs a symbol argument".to_string())?;
+                xref = Some(v.clone());
+            }
+            s if s.starts_with("--xref=") => xref = Some(s["--xref=".len()..].to_string()),
+            "--emit-ast" | "--emit-ast=pretty" => emit_ast = Some(AstDumpFormat::Pretty),
+            "--emit-ast=json" => emit_ast = Some(AstDumpFormat::Json),
+            "--emit-ast-dot" => emit_ast = Some(AstDumpFormat::Dot),
+            "--emit-typed-ast" | "--emit-typed-ast=pretty" => emit_typed_ast = Some(AstDumpFormat::Pretty),
+            "--emit-typed-ast=json" => emit_typed_ast = Some(AstDumpFormat::Json),
+            s if s.starts_with("--emit-typed-ast=") => {
+                return Err(format!("unknown --emit-typed-ast format: {}", &s["--emit-typed-ast=".len()..]));
+            }
+            s if s.starts_with("--emit-ast=") => {
+                return Err(format!("unknown --emit-ast format: {}", &s["--emit-ast=".len()..]));
+            }
+            s if s.starts_with('-') && s != "-" => {
                 return Err(format!("unknown flag: {s}"));
             }
             _ => {
@@ -90,54 +193,237 @@ fn parse_cli(raw: &[String]) -> Result<CliArgs, String> {
         }
     }
     let filename = filename.ok_or_else(|| "no input file".to_string())?;
-    Ok(CliArgs { filename, verbose, core_modules_cli })
+    if watch && filename == "-" {
+        return Err("--watch needs a file, not standard input".to_string());
+    }
+    if fix && filename == "-" {
+        return Err("--fix needs a file, not standard input".to_string());
+    }
+    let mut capabilities = if sandbox { Capabilities::none() } else { Capabilities::default() };
+    for cap in granted {
+        capabilities.set(cap, true);
+    }
+    Ok(CliArgs { filename, log_level, core_modules_cli, emit_ast, emit_typed_ast, capabilities, defines, trace_alloc, deterministic, watch, xref, fix, lint, coverage, timings })
+}
+
+/// `--explain <CODE>`: print the long-form explanation for a
+/// diagnostic code (`E0101` etc.) and exit. Mirrors `rustc --explain`.
+fn explain(code: &str) -> i32 {
+    match frontend::error_code::lookup(code) {
+        Some(info) => {
+            println!("{}: {}\n", info.code, info.title);
+            println!("{}", info.explanation);
+            0
+        }
+        None => {
+            eprintln!("error: no extended information for `{code}`");
+            1
+        }
+    }
+}
+
+/// `--lint`: print the lint findings for the program, configured by the
+/// nearest `toylang.toml`. Exits 1 when a `deny` lint fired or the
+/// program doesn't type-check.
+fn run_lints(source: &str, filename: &str, core_modules_dir: Option<&Path>) -> i32 {
+    let dir = match Path::new(filename).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let config = match frontend::lints::LintConfig::for_dir(dir) {
+        Ok(config) => config,
+        Err(msg) => {
+            eprintln!("{msg}");
+            return 1;
+        }
+    };
+    let Ok(lints) = interpreter::lint(source, filename, core_modules_dir, &config) else {
+        return 1;
+    };
+    let formatter = interpreter::error_formatter::ErrorFormatter::new(source, filename);
+    for lint in &lints {
+        eprintln!("{}", formatter.format_lint(lint));
+    }
+    let denied = lints.iter().any(|lint| lint.level == frontend::lints::LintLevel::Deny);
+    i32::from(denied)
+}
+
+/// `--fix`: write the suggested fixes into `filename` and hand back the
+/// fixed source, which is then run as usual.
+fn fix_file(source: String, filename: &str, core_modules_dir: Option<&Path>) -> String {
+    let fixes = interpreter::import_fixes(&source, filename, core_modules_dir);
+    if fixes.is_empty() {
+        return source;
+    }
+    let fixed = interpreter::apply_fixes(&source, &fixes);
+    if let Err(e) = std::fs::write(filename, &fixed) {
+        eprintln!("Failed to write file {}: {}", filename, e);
+        process::exit(1);
+    }
+    for fix in &fixes {
+        eprintln!("fixed {}: {}", filename, fix.message);
+    }
+    fixed
+}
+
+/// `--watch`: run the program, then poll the file and its module
+/// directories and run it again after every change, until killed.
+/// One `CompilerSession` lives across the reruns, so functions whose
+/// source did not change skip type checking.
+fn watch_and_rerun(mut source: String, filename: &str, options: &RunOptions<'_>, timings: Option<TimingFormat>) -> ! {
+    let mut session = compiler_core::CompilerSession::new();
+    let mut watcher = interpreter::watch::Watcher::new(Path::new(filename), options.core_modules_dir);
+    loop {
+        session.reset_timings();
+        let result = interpreter::run_source_in_session(&mut session, &source, filename, options);
+        if let Some(format) = timings {
+            eprint!("{}", session.timings().render(format));
+        }
+        match result {
+            Ok(RunOutcome { exit_code: Some(code) }) => eprintln!("[watch] exited with {code}"),
+            Ok(RunOutcome { exit_code: None }) => eprintln!("[watch] finished"),
+            Err(_) => eprintln!("[watch] failed"),
+        }
+        eprintln!("[watch] waiting for changes to {} file(s)", watcher.len());
+        source = loop {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            let changed = watcher.changed();
+            if changed.is_empty() {
+                continue;
+            }
+            for path in &changed {
+                eprintln!("[watch] changed: {}", path.display());
+                if let Some(dir) = watcher.module_dir_of(path) {
+                    interpreter::module_integration::forget_core_modules(dir);
+                }
+            }
+            // A file caught mid-save (or deleted) is retried on the
+            // next change instead of rerunning stale source.
+            match std::fs::read_to_string(filename) {
+                Ok(text) => break text,
+                Err(e) => eprintln!("[watch] failed to read {filename}: {e}"),
+            }
+        };
+    }
 }
 
 fn main() {
     let raw: Vec<String> = env::args().collect();
+    if let Some(flag) = raw.get(1) {
+        if flag == "--explain" {
+            let code = raw.get(2).map(String::as_str).unwrap_or("");
+            process::exit(explain(code));
+        }
+        if let Some(code) = flag.strip_prefix("--explain=") {
+            process::exit(explain(code));
+        }
+    }
     let cli = match parse_cli(&raw) {
         Ok(c) => c,
         Err(msg) => {
             eprintln!("{msg}");
             println!("Usage:");
-            println!("  {} <file>", raw.first().map(String::as_str).unwrap_or("interpreter"));
-            println!("  {} <file> [-v] [--core-modules <DIR>]", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|->", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|-> [-v] [--log-level <LEVEL>] [--core-modules <DIR>] [--define NAME[=value]] [--sandbox] [--allow-<fs-read|fs-write|exec|net|clock>] [--trace-alloc] [--deterministic[=SEED]] [--coverage[=FILE]] [--timings[=text|json]] [--watch] [--fix]", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|-> --emit-ast[=pretty|json] | --emit-ast-dot", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|-> --emit-typed-ast[=pretty|json]", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|-> --xref <SYMBOL>", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} <file|-> --lint", raw.first().map(String::as_str).unwrap_or("interpreter"));
+            println!("  {} --explain <CODE>", raw.first().map(String::as_str).unwrap_or("interpreter"));
             return;
         }
     };
-    let CliArgs { filename, verbose, core_modules_cli } = cli;
+    let CliArgs { filename, log_level, core_modules_cli, emit_ast, emit_typed_ast, capabilities, defines, trace_alloc, deterministic, watch, xref, fix, lint, coverage, timings } = cli;
     let core_modules_dir = resolve_core_modules_dir(core_modules_cli);
-    if verbose {
-        if let Some(dir) = &core_modules_dir {
-            println!("Core modules directory: {}", dir.display());
-        } else {
-            println!("Core modules directory: <none> (auto-load disabled)");
-        }
+    logger::set_level(log_level);
+    let timings = timings.or_else(|| logger::enabled(LogLevel::Info).then_some(TimingFormat::Text));
+    if let Some(dir) = &core_modules_dir {
+        logger::info(&format!("Core modules directory: {}", dir.display()));
+    } else {
+        logger::info("Core modules directory: <none> (auto-load disabled)");
     }
 
-    let source = match fs::read_to_string(&filename) {
-        Ok(content) => content,
+    // `-` reads the program from standard input; diagnostics then
+    // name it `<stdin>` instead of the argument.
+    let (source, filename) = match compiler_core::read_source_arg(Path::new(&filename)) {
+        Ok(read) => read,
         Err(e) => {
             eprintln!("Failed to read file {}: {}", filename, e);
             return;
         }
     };
 
+    if let Some(format) = emit_ast {
+        match interpreter::emit_ast(&source, &filename, format) {
+            Ok(dump) => print!("{dump}"),
+            Err(_) => process::exit(1),
+        }
+        return;
+    }
+
+    if let Some(format) = emit_typed_ast {
+        match interpreter::emit_typed_ast(&source, &filename, format, core_modules_dir.as_deref()) {
+            Ok(dump) => print!("{dump}"),
+            Err(_) => process::exit(1),
+        }
+        return;
+    }
+
+    if let Some(symbol) = xref {
+        match interpreter::xref(&source, &filename, &symbol) {
+            Ok(listing) => print!("{listing}"),
+            Err(msg) => {
+                eprintln!("{msg}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if lint {
+        process::exit(run_lints(&source, &filename, core_modules_dir.as_deref()));
+    }
+
+    let source = if fix {
+        fix_file(source, &filename, core_modules_dir.as_deref())
+    } else {
+        source
+    };
+
     let jit = matches!(env::var("INTERPRETER_JIT").as_deref(), Ok("1"));
+    let log = match LogConfig::from_env() {
+        Ok(log) => log,
+        Err(msg) => {
+            eprintln!("{msg}");
+            process::exit(1);
+        }
+    };
     let options = RunOptions {
         jit,
         core_modules_dir: core_modules_dir.as_deref(),
+        capabilities,
+        defines: Some(&defines),
+        trace_alloc,
+        deterministic,
+        coverage: coverage.as_deref(),
+        log: Some(&log),
     };
-    match interpreter::run_source(&source, &filename, &options) {
+    if watch {
+        watch_and_rerun(source, &filename, &options, timings);
+    }
+    let mut session = compiler_core::CompilerSession::new();
+    let result = interpreter::run_source_in_session(&mut session, &source, &filename, &options);
+    if let Some(format) = timings {
+        eprint!("{}", session.timings().render(format));
+    }
+    match result {
         Ok(RunOutcome { exit_code: Some(code) }) => process::exit(code),
         Ok(RunOutcome { exit_code: None }) => {}
         Err(_diagnostic) => {
             // `run_source` already routed the diagnostic through
             // `ErrorFormatter::display_*`, matching the binary's prior
             // behavior. Just propagate the failure exit code.
-            if verbose {
-                println!("Execution failed");
-            }
+            logger::info("Execution failed");
             process::exit(1);
         }
     }
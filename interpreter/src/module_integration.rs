@@ -133,15 +133,17 @@ impl<'a> AstIntegrationContext<'a> {
             Expr::Float64(v) => Ok(Expr::Float64(*v)),
             Expr::Number(symbol) => Ok(Expr::Number(self.remap_symbol(*symbol)?)),
             Expr::String(symbol) => Ok(Expr::String(self.remap_symbol(*symbol)?)),
+            Expr::Bytes(symbol) => Ok(Expr::Bytes(self.remap_symbol(*symbol)?)),
             Expr::Identifier(symbol) => Ok(Expr::Identifier(self.remap_symbol(*symbol)?)),
             Expr::Binary(op, lhs, rhs) => Ok(Expr::Binary(
                 op.clone(),
                 self.map_expr(lhs, "Binary LHS")?,
                 self.map_expr(rhs, "Binary RHS")?,
             )),
-            Expr::Call(symbol, args) => Ok(Expr::Call(
+            Expr::Call(symbol, args, type_args) => Ok(Expr::Call(
                 self.remap_symbol(*symbol)?,
                 self.map_expr(args, "Call args")?,
+                self.remap_type_decls(type_args)?,
             )),
             Expr::ExprList(exprs) => Ok(Expr::ExprList(self.map_exprs(exprs, "ExprList")?)),
             Expr::Block(stmts) => {
@@ -239,7 +241,7 @@ impl<'a> AstIntegrationContext<'a> {
                 }
                 Ok(Expr::Match(new_scrutinee, new_arms))
             }
-            Expr::StructLiteral(name, fields) => {
+            Expr::StructLiteral(name, fields, type_args) => {
                 // `Point { x: 10, y: 20 }` — both the struct's
                 // type symbol and each field name need re-interning,
                 // and the per-field value ExprRefs follow the
@@ -256,7 +258,7 @@ impl<'a> AstIntegrationContext<'a> {
                         self.map_expr(fexpr, "StructLiteral field expression")?,
                     ));
                 }
-                Ok(Expr::StructLiteral(new_name, new_fields))
+                Ok(Expr::StructLiteral(new_name, new_fields, self.remap_type_decls(type_args)?))
             }
             Expr::FieldAccess(receiver, field) => Ok(Expr::FieldAccess(
                 self.map_expr(receiver, "FieldAccess receiver")?,
@@ -269,6 +271,15 @@ impl<'a> AstIntegrationContext<'a> {
                 self.map_expr(obj, "TupleAccess obj")?,
                 *idx,
             )),
+            Expr::SliceAccess(obj, info) => Ok(Expr::SliceAccess(
+                self.map_expr(obj, "SliceAccess obj")?,
+                SliceInfo {
+                    start: self.map_opt_expr(info.start.as_ref(), "SliceAccess start")?,
+                    end: self.map_opt_expr(info.end.as_ref(), "SliceAccess end")?,
+                    has_dotdot: info.has_dotdot,
+                    slice_type: info.slice_type.clone(),
+                },
+            )),
             Expr::Unary(op, operand) => Ok(Expr::Unary(
                 op.clone(),
                 self.map_expr(operand, "Unary operand")?,
@@ -286,6 +297,16 @@ impl<'a> AstIntegrationContext<'a> {
                     self.map_expr(body, "With body expression")?,
                 ))
             }
+            Expr::WithResource { name, resource, body } => Ok(Expr::WithResource {
+                name: self.remap_symbol(*name)?,
+                resource: self.map_expr(resource, "WithResource resource expression")?,
+                body: self.map_expr(body, "WithResource body expression")?,
+            }),
+            Expr::TryCatch { body, error, handler } => Ok(Expr::TryCatch {
+                body: self.map_expr(body, "TryCatch body expression")?,
+                error: self.remap_symbol(*error)?,
+                handler: self.map_expr(handler, "TryCatch handler expression")?,
+            }),
             Expr::Cast(value, ty) => {
                 // `expr as Type`. The inner ExprRef goes through the
                 // standard expr_mapping; the TypeDecl can carry struct
@@ -419,8 +440,12 @@ impl<'a> AstIntegrationContext<'a> {
                 self.map_expr(expr_ref, "Expression")?,
             )),
             Stmt::Return(opt) => Ok(Stmt::Return(self.map_opt_expr(opt.as_ref(), "Return")?)),
-            Stmt::Break(label) => Ok(Stmt::Break(self.remap_optional_label(*label)?)),
+            Stmt::Break(label, value) => Ok(Stmt::Break(
+                self.remap_optional_label(*label)?,
+                self.map_opt_expr(value.as_ref(), "Break value")?,
+            )),
             Stmt::Continue(label) => Ok(Stmt::Continue(self.remap_optional_label(*label)?)),
+            Stmt::Yield(value) => Ok(Stmt::Yield(self.map_expr(value, "Yield value")?)),
             Stmt::Var(name, typ, value) => Ok(Stmt::Var(
                 self.remap_symbol(*name)?,
                 self.remap_opt_type_decl(typ.as_ref())?,
@@ -491,6 +516,10 @@ impl<'a> AstIntegrationContext<'a> {
                 }
                 let mut new_fields: Vec<StructField> = Vec::with_capacity(fields.len());
                 for f in fields {
+                    // Tuple struct fields (`"0"`, ..) are looked up by
+                    // symbol when `Name(..)` / `.0` is type-checked, so
+                    // the main interner has to know them too.
+                    self.main_string_interner.get_or_intern(&f.name);
                     new_fields.push(StructField {
                         name: f.name.clone(),
                         type_decl: self.remap_type_decl(&f.type_decl)?,
@@ -722,6 +751,11 @@ impl<'a> AstIntegrationContext<'a> {
             .ok_or_else(|| format!("Cannot find {} statement mapping", ctx))
     }
 
+    /// List form of `remap_type_decl`, for explicit call / literal type arguments.
+    fn remap_type_decls(&mut self, tys: &[TypeDecl]) -> Result<Vec<TypeDecl>, String> {
+        tys.iter().map(|t| self.remap_type_decl(t)).collect()
+    }
+
     /// Optional form of `remap_type_decl` — `None` passes through unchanged.
     fn remap_opt_type_decl(&mut self, ty: Option<&TypeDecl>) -> Result<Option<TypeDecl>, String> {
         ty.map(|t| self.remap_type_decl(t)).transpose()
@@ -796,7 +830,8 @@ impl<'a> AstIntegrationContext<'a> {
             ensures: new_ensures,
             code: new_code,
             is_extern: function.is_extern,
-            visibility: function.visibility.clone()
+            visibility: function.visibility.clone(),
+            inline: function.inline,
         })
     }
 
@@ -857,7 +892,8 @@ impl<'a> AstIntegrationContext<'a> {
             code: new_code,
             has_self_param: method.has_self_param,
             self_is_mut: method.self_is_mut,
-            visibility: method.visibility.clone()
+            visibility: method.visibility.clone(),
+            inline: method.inline,
         }))
     }
 
@@ -897,6 +933,27 @@ impl<'a> AstIntegrationContext<'a> {
         Ok(integrated_functions)
     }
 
+    /// The module's `@cfg` items re-targeted at the main program, for
+    /// functions that will be appended after the current last entry.
+    /// Module consts are not integrated, so their items are dropped.
+    fn remap_cfg_items(&self) -> Vec<CfgItem> {
+        let function_offset = self.main_program.function.len();
+        self.module_program
+            .cfg_items
+            .iter()
+            .filter_map(|item| {
+                let target = match item.target {
+                    CfgTarget::Function(idx) => CfgTarget::Function(function_offset + idx),
+                    CfgTarget::Decl(stmt_ref) => {
+                        CfgTarget::Decl(*self.stmt_mapping.get(&stmt_ref.0)?)
+                    }
+                    CfgTarget::Const(_) => return None,
+                };
+                Some(CfgItem { predicate: item.predicate.clone(), target })
+            })
+            .collect()
+    }
+
     /// Phase 1: Create placeholder mappings for all expressions and statements
     fn create_placeholder_mappings(&mut self) -> Result<(), String> {
         // Create placeholder mappings for all expressions
@@ -908,7 +965,7 @@ impl<'a> AstIntegrationContext<'a> {
 
         // Create placeholder mappings for all statements
         for index in 0..self.module_program.statement.len() {
-            let placeholder_stmt = Stmt::Break(None);
+            let placeholder_stmt = Stmt::Break(None, None);
             let main_stmt_ref = self.main_program.statement.add(placeholder_stmt);
             self.stmt_mapping.insert(index as u32, main_stmt_ref);
         }
@@ -1069,22 +1126,32 @@ pub fn discover_core_modules(
     // walk + read once per process. Each consumer still gets its own
     // owned Vec via clone; mutating a discovered module's source is
     // not exposed in the public API.
-    use std::collections::HashMap;
-    use std::sync::{Mutex, OnceLock};
-    static CACHE: OnceLock<Mutex<HashMap<std::path::PathBuf, Vec<DiscoveredCoreModule>>>> =
-        OnceLock::new();
-    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
     let key = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
-    if let Some(hit) = cache.lock().unwrap().get(&key).cloned() {
+    if let Some(hit) = core_module_cache().lock().unwrap().get(&key).cloned() {
         return Ok(hit);
     }
     let mut out: Vec<DiscoveredCoreModule> = Vec::new();
     walk_core_dir(dir, &mut Vec::new(), &mut out)?;
     out.sort_by(|a, b| a.segments.cmp(&b.segments));
-    cache.lock().unwrap().insert(key, out.clone());
+    core_module_cache().lock().unwrap().insert(key, out.clone());
     Ok(out)
 }
 
+/// Drop the cached [`discover_core_modules`] result for `dir`, so the
+/// next call reads the files again. `--watch` calls this after a core
+/// module changed on disk.
+pub fn forget_core_modules(dir: &std::path::Path) {
+    let key = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    core_module_cache().lock().unwrap().remove(&key);
+}
+
+type CoreModuleCache = std::sync::Mutex<std::collections::HashMap<std::path::PathBuf, Vec<DiscoveredCoreModule>>>;
+
+fn core_module_cache() -> &'static CoreModuleCache {
+    static CACHE: std::sync::OnceLock<CoreModuleCache> = std::sync::OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
 fn walk_core_dir(
     dir: &std::path::Path,
     prefix: &mut Vec<String>,
@@ -1287,6 +1354,8 @@ pub fn integrate_module_into_program_with_options_full(
     );
 
     let integrated_functions = integration_context.integrate()?;
+    let cfg_items = integration_context.remap_cfg_items();
+    main_program.cfg_items.extend(cfg_items);
     for function in integrated_functions {
         // Track imported names so the type-checker can enforce the
         // namespace-only contract: imported `pub fn`s are only
@@ -34,6 +34,7 @@ pub enum Object {
     Float64(f64),
     ConstString(DefaultSymbol),  // String literals and interned strings (immutable, memory efficient)
     String(String),              // Runtime generated strings (mutable, direct data storage)
+    Bytes(Box<Vec<u8>>),         // `bytes` buffer (literal, slice, or file contents); immutable at the language level
     Array(Box<Vec<RcObject>>),
     Struct {
         type_name: DefaultSymbol,
@@ -96,6 +97,10 @@ pub enum Object {
         body: ExprRef,
         captures: Vec<(DefaultSymbol, RcObject)>,
     },
+    /// Suspended body of a generator function; see
+    /// `evaluation::generator`. Identity is the shared cell, like
+    /// `Allocator`.
+    Generator(Rc<RefCell<crate::evaluation::generator::Generator>>),
 }
 
 pub type RcObject = Rc<RefCell<Object>>;
@@ -203,6 +208,7 @@ impl Ord for ObjectKey {
             (Object::Float64(a), Object::Float64(b)) => a.to_bits().cmp(&b.to_bits()),
             (Object::ConstString(a), Object::ConstString(b)) => a.cmp(b),
             (Object::String(a), Object::String(b)) => a.cmp(b),
+            (Object::Bytes(a), Object::Bytes(b)) => a.cmp(b),
             (Object::Pointer(a), Object::Pointer(b)) => a.cmp(b),
             (Object::Null(_), Object::Null(_)) => Ordering::Equal,
             (Object::Unit, Object::Unit) => Ordering::Equal,
@@ -231,6 +237,8 @@ impl Ord for ObjectKey {
             (_, Object::ConstString(_)) => Ordering::Greater,
             (Object::String(_), _) => Ordering::Less,
             (_, Object::String(_)) => Ordering::Greater,
+            (Object::Bytes(_), _) => Ordering::Less,
+            (_, Object::Bytes(_)) => Ordering::Greater,
             (Object::Array(_), _) => Ordering::Less,
             (_, Object::Array(_)) => Ordering::Greater,
             (Object::Struct { .. }, _) => Ordering::Less,
@@ -270,6 +278,11 @@ impl Ord for ObjectKey {
             }
             (Object::Closure { .. }, _) => Ordering::Less,
             (_, Object::Closure { .. }) => Ordering::Greater,
+            (Object::Generator(a), Object::Generator(b)) => {
+                (Rc::as_ptr(a) as usize).cmp(&(Rc::as_ptr(b) as usize))
+            }
+            (Object::Generator(_), _) => Ordering::Less,
+            (_, Object::Generator(_)) => Ordering::Greater,
         }
     }
 }
@@ -292,6 +305,7 @@ impl PartialEq for Object {
             (Object::Float64(a), Object::Float64(b)) => a.to_bits() == b.to_bits(),
             (Object::ConstString(a), Object::ConstString(b)) => a == b,
             (Object::String(a), Object::String(b)) => a == b,
+            (Object::Bytes(a), Object::Bytes(b)) => a == b,
             (Object::Array(a), Object::Array(b)) => {
                 a.len() == b.len() && 
                 a.iter().zip(b.iter()).all(|(x, y)| x.borrow().eq(&*y.borrow()))
@@ -318,6 +332,7 @@ impl PartialEq for Object {
             (Object::Null(_), Object::Null(_)) => true,
             (Object::Unit, Object::Unit) => true,
             (Object::Allocator(a), Object::Allocator(b)) => Rc::ptr_eq(a, b),
+            (Object::Generator(a), Object::Generator(b)) => Rc::ptr_eq(a, b),
             (Object::EnumVariant { enum_name: e1, variant_name: v1, values: vs1, .. },
              Object::EnumVariant { enum_name: e2, variant_name: v2, values: vs2, .. }) => {
                 e1 == e2 && v1 == v2 && vs1.len() == vs2.len()
@@ -384,6 +399,10 @@ impl Hash for Object {
                 4u8.hash(state);
                 v.hash(state);
             }
+            Object::Bytes(v) => {
+                16u8.hash(state);
+                v.hash(state);
+            }
             Object::Array(v) => {
                 5u8.hash(state);
                 v.len().hash(state);
@@ -466,6 +485,10 @@ impl Hash for Object {
                 40u8.hash(state);
                 body.0.hash(state);
             }
+            Object::Generator(rc) => {
+                41u8.hash(state);
+                (Rc::as_ptr(rc) as usize).hash(state);
+            }
         }
     }
 }
@@ -493,7 +516,7 @@ fn format_type_header(
 /// instantiations so `Option<Cell<i64>>` prints with the inner args
 /// expanded. Falls back to a debug-style fragment for shapes we
 /// don't normally surface in display.
-fn format_type_decl_for_display(
+pub(crate) fn format_type_decl_for_display(
     ty: &TypeDecl,
     string_interner: &string_interner::StringInterner<string_interner::DefaultBackend>,
 ) -> String {
@@ -503,6 +526,7 @@ fn format_type_decl_for_display(
         TypeDecl::Float64 => "f64".to_string(),
         TypeDecl::Bool => "bool".to_string(),
         TypeDecl::String => "str".to_string(),
+        TypeDecl::Bytes => "bytes".to_string(),
         TypeDecl::Unit => "()".to_string(),
         TypeDecl::Ptr => "ptr".to_string(),
         TypeDecl::Identifier(sym) => string_interner
@@ -580,6 +604,7 @@ impl Object {
             Object::Int8(_) => TypeDecl::Int8,
             Object::Float64(_) => TypeDecl::Float64,
             Object::ConstString(_) | Object::String(_) => TypeDecl::String,
+            Object::Bytes(_) => TypeDecl::Bytes,
             Object::Array(elements) => {
                 if elements.is_empty() {
                     TypeDecl::Array(vec![], 0)
@@ -620,6 +645,88 @@ impl Object {
                 params.iter().map(|(_, t)| t.clone()).collect(),
                 Box::new(return_ty.clone()),
             ),
+            Object::Generator(generator) => TypeDecl::Generator(Box::new(generator.borrow().item_type())),
+        }
+    }
+
+    /// Runtime type name reported by `type_of`. Containers report
+    /// their kind only (`"array"`, `"dict"`, ...); structs and enums
+    /// report their declared name.
+    pub fn type_name(
+        &self,
+        string_interner: &string_interner::StringInterner<string_interner::DefaultBackend>,
+    ) -> String {
+        let name = match self {
+            Object::Unit => "unit",
+            Object::Null(_) => "null",
+            Object::Bool(_) => "bool",
+            Object::UInt64(_) => "u64",
+            Object::Int64(_) => "i64",
+            Object::UInt32(_) => "u32",
+            Object::Int32(_) => "i32",
+            Object::UInt16(_) => "u16",
+            Object::Int16(_) => "i16",
+            Object::UInt8(_) => "u8",
+            Object::Int8(_) => "i8",
+            Object::Float64(_) => "f64",
+            Object::ConstString(_) | Object::String(_) => "str",
+            Object::Bytes(_) => "bytes",
+            Object::Array(_) => "array",
+            Object::Dict(_) => "dict",
+            Object::Tuple(_) => "tuple",
+            Object::Pointer(_) => "ptr",
+            Object::Allocator(_) => "allocator",
+            Object::Range { .. } => "range",
+            Object::Closure { .. } => "fn",
+            Object::Generator(_) => "generator",
+            Object::Struct { type_name: name, .. } | Object::EnumVariant { enum_name: name, .. } => {
+                return string_interner.resolve(*name).unwrap_or("?").to_string();
+            }
+        };
+        name.to_string()
+    }
+
+    /// Whether this value can be viewed as `ty`, the run-time half of
+    /// `is<T>` / `downcast<T>`. Containers are checked element by
+    /// element (fixed array lengths included); `any` matches anything.
+    /// Generic arguments of structs and enums are not compared.
+    pub fn matches_type(&self, ty: &TypeDecl) -> bool {
+        match (self, ty) {
+            (_, TypeDecl::Unknown) => true,
+            (_, TypeDecl::Ref { inner, .. }) => self.matches_type(inner),
+            (Object::Array(elems), TypeDecl::Array(elem_types, len)) => {
+                if *len != 0 && elems.len() != *len {
+                    return false;
+                }
+                match elem_types.first() {
+                    Some(elem_ty) => elems.iter().all(|e| e.borrow().matches_type(elem_ty)),
+                    None => true,
+                }
+            }
+            (Object::Dict(map), TypeDecl::Dict(key_ty, value_ty)) => map
+                .iter()
+                .all(|(k, v)| k.as_object().matches_type(key_ty) && v.borrow().matches_type(value_ty)),
+            (Object::Tuple(elems), TypeDecl::Tuple(elem_types)) => {
+                elems.len() == elem_types.len()
+                    && elems.iter().zip(elem_types).all(|(e, t)| e.borrow().matches_type(t))
+            }
+            (Object::Struct { type_name: name, .. }, TypeDecl::Struct(expected, _) | TypeDecl::Identifier(expected))
+            | (Object::EnumVariant { enum_name: name, .. }, TypeDecl::Enum(expected, _) | TypeDecl::Identifier(expected)) => {
+                name == expected
+            }
+            (Object::Null(_), _) => false,
+            (Object::ConstString(_) | Object::String(_), TypeDecl::String) => true,
+            (Object::Closure { .. }, TypeDecl::Function(..)) => true,
+            (Object::Range { .. }, TypeDecl::Range(_)) => true,
+            (Object::Generator(_), TypeDecl::Generator(_)) => true,
+            _ => {
+                let actual = self.get_type();
+                !matches!(
+                    actual,
+                    TypeDecl::Array(..) | TypeDecl::Dict(..) | TypeDecl::Tuple(_)
+                        | TypeDecl::Struct(..) | TypeDecl::Enum(..)
+                ) && actual == *ty
+            }
         }
     }
 
@@ -671,6 +778,20 @@ impl Object {
         &self,
         string_interner: &string_interner::StringInterner<string_interner::DefaultBackend>,
     ) -> String {
+        self.to_display_string_with(string_interner, &mut |_| None)
+    }
+
+    /// `to_display_string` with an override: `hook` sees every value
+    /// before it is rendered (outermost first) and can supply its text.
+    /// The evaluator uses this to splice in user `__str__` results.
+    pub fn to_display_string_with(
+        &self,
+        string_interner: &string_interner::StringInterner<string_interner::DefaultBackend>,
+        hook: &mut dyn FnMut(&Object) -> Option<String>,
+    ) -> String {
+        if let Some(text) = hook(self) {
+            return text;
+        }
         match self {
             Object::Unit => "()".to_string(),
             Object::Bool(b) => b.to_string(),
@@ -696,18 +817,21 @@ impl Object {
                 string_interner.resolve(*sym).unwrap_or("").to_string()
             }
             Object::String(s) => s.clone(),
+            // Rust's byte-string spelling, so the output can be pasted
+            // back as a `b"..."` literal.
+            Object::Bytes(v) => format!("b\"{}\"", v.escape_ascii()),
             Object::Null(_) => "null".to_string(),
             Object::Pointer(addr) => format!("ptr(0x{:x})", addr),
             Object::Allocator(rc) => format!("allocator(@{:p})", Rc::as_ptr(rc)),
             Object::Array(elements) => {
                 let parts: Vec<String> = elements.iter()
-                    .map(|e| e.borrow().to_display_string(string_interner))
+                    .map(|e| e.borrow().to_display_string_with(string_interner, hook))
                     .collect();
                 format!("[{}]", parts.join(", "))
             }
             Object::Tuple(elements) => {
                 let parts: Vec<String> = elements.iter()
-                    .map(|e| e.borrow().to_display_string(string_interner))
+                    .map(|e| e.borrow().to_display_string_with(string_interner, hook))
                     .collect();
                 if parts.len() == 1 {
                     format!("({},)", parts[0])
@@ -719,8 +843,8 @@ impl Object {
                 let mut parts: Vec<String> = map.iter()
                     .map(|(k, v)| format!(
                         "{}: {}",
-                        k.as_object().to_display_string(string_interner),
-                        v.borrow().to_display_string(string_interner),
+                        k.as_object().to_display_string_with(string_interner, hook),
+                        v.borrow().to_display_string_with(string_interner, hook),
                     ))
                     .collect();
                 // Stable ordering so output is deterministic for tests.
@@ -733,7 +857,7 @@ impl Object {
                 let mut parts: Vec<String> = fields.iter()
                     .map(|(k, v)| {
                         let name = string_interner.resolve(*k).unwrap_or("<field>");
-                        format!("{}: {}", name, v.borrow().to_display_string(string_interner))
+                        format!("{}: {}", name, v.borrow().to_display_string_with(string_interner, hook))
                     })
                     .collect();
                 parts.sort();
@@ -742,8 +866,8 @@ impl Object {
             Object::Range { start, end } => {
                 format!(
                     "{}..{}",
-                    start.borrow().to_display_string(string_interner),
-                    end.borrow().to_display_string(string_interner),
+                    start.borrow().to_display_string_with(string_interner, hook),
+                    end.borrow().to_display_string_with(string_interner, hook),
                 )
             }
             Object::EnumVariant { enum_name, variant_name, values, type_args } => {
@@ -752,7 +876,7 @@ impl Object {
                 let variant_str = string_interner.resolve(*variant_name).unwrap_or("<variant>");
                 if !values.is_empty() {
                     let parts: Vec<String> = values.iter()
-                        .map(|v| v.borrow().to_display_string(string_interner))
+                        .map(|v| v.borrow().to_display_string_with(string_interner, hook))
                         .collect();
                     return format!("{}::{}({})", header, variant_str, parts.join(", "));
                 }
@@ -769,6 +893,7 @@ impl Object {
                 // about.
                 format!("<closure/{}>", params.len())
             }
+            Object::Generator(rc) => format!("generator(@{:p})", Rc::as_ptr(rc)),
         }
     }
 
@@ -894,6 +1019,10 @@ impl Object {
                     found: TypeDecl::String 
                 })
             }
+            (Object::Bytes(self_val), Object::Bytes(v)) => {
+                *self_val = v.clone();
+                Ok(())
+            }
             (Object::Array(self_val), Object::Array(v)) => {
                 self_val.clear();
                 self_val.extend(v.iter().cloned());
@@ -11,9 +11,14 @@
 //! the OS level (which would race with parallel test threads). The
 //! capture is scoped to the current thread, so concurrent JIT and
 //! tree-walker runs in different threads don't contaminate each other.
+//!
+//! The input side works the same way: `read_line` / `read_all` read
+//! through [`read_line_text`] / [`read_all_text`], which consume the
+//! process stdin unless [`with_input`] has installed a per-thread
+//! buffer.
 
 use std::cell::RefCell;
-use std::io::Write;
+use std::io::{BufRead, Read, Write};
 
 thread_local! {
     static OUTPUT_SINK: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
@@ -23,6 +28,65 @@ thread_local! {
     /// in-process integration tests that want to assert on stderr
     /// content without spawning the interpreter binary.
     static ERROR_SINK: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+    /// Per-thread stdin override for the `read_line` / `read_all`
+    /// builtins. `None` reads the process stdin.
+    static INPUT_SOURCE: RefCell<Option<std::io::Cursor<Vec<u8>>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `input` standing in for stdin. Restores the previous
+/// input source on return, even if `f` panics.
+pub fn with_input<R>(input: &str, f: impl FnOnce() -> R) -> R {
+    let prev = INPUT_SOURCE.with(|cell| {
+        cell.replace(Some(std::io::Cursor::new(input.as_bytes().to_vec())))
+    });
+    struct Guard(Option<std::io::Cursor<Vec<u8>>>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            INPUT_SOURCE.with(|cell| {
+                *cell.borrow_mut() = prev;
+            });
+        }
+    }
+    let _guard = Guard(prev);
+    f()
+}
+
+/// Read one line, including its trailing `\n` if present. Returns an
+/// empty string only at end of input, so callers can tell a blank
+/// line (`"\n"`) from EOF. Pending stdout is flushed first so a
+/// `print("> ")` prompt shows up before the read blocks. Invalid
+/// UTF-8 is replaced with U+FFFD.
+pub fn read_line_text() -> std::io::Result<String> {
+    let mut buf = Vec::new();
+    let overridden = INPUT_SOURCE.with(|cell| -> std::io::Result<bool> {
+        match cell.borrow_mut().as_mut() {
+            Some(cursor) => cursor.read_until(b'\n', &mut buf).map(|_| true),
+            None => Ok(false),
+        }
+    })?;
+    if !overridden {
+        std::io::stdout().flush()?;
+        std::io::stdin().lock().read_until(b'\n', &mut buf)?;
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Read everything up to end of input. Same source selection and
+/// UTF-8 handling as [`read_line_text`].
+pub fn read_all_text() -> std::io::Result<String> {
+    let mut buf = Vec::new();
+    let overridden = INPUT_SOURCE.with(|cell| -> std::io::Result<bool> {
+        match cell.borrow_mut().as_mut() {
+            Some(cursor) => cursor.read_to_end(&mut buf).map(|_| true),
+            None => Ok(false),
+        }
+    })?;
+    if !overridden {
+        std::io::stdout().flush()?;
+        std::io::stdin().lock().read_to_end(&mut buf)?;
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
 }
 
 /// Run `f` with stdout captured into a string. Restores the previous
@@ -0,0 +1,129 @@
+//! File polling behind `interpreter --watch`.
+//!
+//! The watched set is the program file plus every `.t` file under the
+//! directories `import` resolves against: the core-modules directory
+//! and the legacy cwd-relative `modules/`. Polling modification times
+//! keeps this dependency-free; a rerun only happens after something
+//! in the set changed, appeared or disappeared.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification times of the watched files as of the last poll.
+#[derive(Debug)]
+pub struct Watcher {
+    program: PathBuf,
+    module_dirs: Vec<PathBuf>,
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(program: &Path, core_modules_dir: Option<&Path>) -> Self {
+        let module_dirs = core_modules_dir
+            .into_iter()
+            .map(Path::to_path_buf)
+            .chain(Some(PathBuf::from("modules")))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        let mut watcher = Watcher { program: program.to_path_buf(), module_dirs, stamps: Vec::new() };
+        watcher.stamps = watcher.scan();
+        watcher
+    }
+
+    /// Number of files currently watched.
+    pub fn len(&self) -> usize {
+        self.stamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stamps.is_empty()
+    }
+
+    /// Poll the file system. Returns the files that changed since the
+    /// previous poll (including ones added or removed), in path order.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let now = self.scan();
+        let mut changed: Vec<PathBuf> = now
+            .iter()
+            .filter(|entry| !self.stamps.contains(entry))
+            .chain(self.stamps.iter().filter(|(path, _)| !now.iter().any(|(p, _)| p == path)))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.sort();
+        changed.dedup();
+        self.stamps = now;
+        changed
+    }
+
+    /// The module directory `path` lives under, if any. A change there
+    /// means the cached core-module sources are stale.
+    pub fn module_dir_of(&self, path: &Path) -> Option<&Path> {
+        self.module_dirs.iter().find(|dir| path.starts_with(dir)).map(PathBuf::as_path)
+    }
+
+    fn scan(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        let mut files = vec![self.program.clone()];
+        for dir in &self.module_dirs {
+            collect_sources(dir, &mut files);
+        }
+        files.sort();
+        files
+            .into_iter()
+            .map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+}
+
+fn collect_sources(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_sources(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "t") {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn touch(path: &Path, contents: &str, age: u64) {
+        std::fs::write(path, contents).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + age)).unwrap();
+    }
+
+    #[test]
+    fn reports_edited_added_and_removed_files() {
+        let root = std::env::temp_dir().join(format!("toylang_watch_{}", std::process::id()));
+        let core = root.join("core");
+        std::fs::create_dir_all(core.join("std")).unwrap();
+        let program = root.join("main.t");
+        touch(&program, "fn main() -> u64 { 0u64 }", 0);
+        touch(&core.join("std/math.t"), "", 0);
+
+        let mut watcher = Watcher::new(&program, Some(&core));
+        assert_eq!(watcher.len(), 2);
+        assert!(watcher.changed().is_empty());
+
+        touch(&program, "fn main() -> u64 { 1u64 }", 1);
+        assert_eq!(watcher.changed(), vec![program.clone()]);
+        assert!(watcher.changed().is_empty(), "a change is reported once");
+
+        let added = core.join("std/str.t");
+        touch(&added, "", 0);
+        std::fs::remove_file(core.join("std/math.t")).unwrap();
+        assert_eq!(watcher.changed(), vec![core.join("std/math.t"), added.clone()]);
+        assert_eq!(watcher.module_dir_of(&added), Some(core.as_path()));
+        assert_eq!(watcher.module_dir_of(&program), None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
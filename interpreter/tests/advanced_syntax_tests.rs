@@ -328,6 +328,62 @@ fn main() -> bool {
     assert!(result.borrow().unwrap_bool());
 }
 
+#[test]
+fn test_hex_literal_negative_min_i8() {
+    test_expr(
+        "fn main() -> i8 { -0x80i8 }",
+        "Int8(-128)"
+    );
+}
+
+// ============================================================================
+// Octal / binary literal tests
+// ============================================================================
+
+#[test]
+fn test_octal_binary_literal_suffixed() {
+    test_expr("fn main() -> u64 { 0o755u64 }", "UInt64(493)");
+    test_expr("fn main() -> u8 { 0b1111_0000u8 }", "UInt8(240)");
+    test_expr("fn main() -> i8 { -0b1000_0000i8 }", "Int8(-128)");
+    test_expr("fn main() -> i32 { 0O17i32 }", "Int32(15)");
+}
+
+#[test]
+fn test_octal_binary_literal_number_inference() {
+    let program = r#"
+fn main() -> u64 {
+    val perms = 0o644
+    val flags: u64 = 0b1010
+    val big = 1_000_000
+    perms + flags + big
+}
+    "#;
+
+    let result = test_program(program).unwrap();
+    assert_eq!(result.borrow().unwrap_uint64(), 420 + 10 + 1_000_000);
+}
+
+#[test]
+fn test_binary_literal_negative_number_inference() {
+    let program = r#"
+fn main() -> i64 {
+    val x: i64 = -0b101
+    x
+}
+    "#;
+
+    let result = test_program(program).unwrap();
+    assert_eq!(result.borrow().unwrap_int64(), -5);
+}
+
+#[test]
+fn test_integer_literal_overflow_rejected() {
+    for literal in ["256u8", "0b1_0000_0000u8", "-0o1u32", "0x1_0000_0000_0000_0000"] {
+        let program = format!("fn main() -> u64 {{\n    val x = {literal}\n    0u64\n}}\n");
+        assert!(test_program(&program).is_err(), "`{literal}` should be rejected");
+    }
+}
+
 // ============================================================================
 // Multiline comment tests
 // ============================================================================
@@ -605,3 +661,119 @@ fn main() -> u64 {
     let result = test_program(program).unwrap();
     assert_eq!(result.borrow().unwrap_uint64(), 43);
 }
+
+const WITH_RESOURCE_LOG: &str = r#"
+struct Handle { id: u64, log: ptr }
+impl Handle {
+    fn open(id: u64, log: ptr) -> Self {
+        Handle { id: id, log: log }
+    }
+    fn __close__(&mut self) {
+        val cur: u64 = __builtin_ptr_read(self.log, 0u64)
+        __builtin_ptr_write(self.log, 0u64, cur * 10u64 + self.id)
+    }
+}
+"#;
+
+#[test]
+fn test_with_resource_closes_at_block_exit() {
+    // `with name = expr { body }` closes the resource when the body
+    // ends, innermost first, and yields the body's value.
+    let program = format!(
+        "{WITH_RESOURCE_LOG}
+fn main() -> u64 {{
+    val log: ptr = __builtin_heap_alloc(8u64)
+    __builtin_ptr_write(log, 0u64, 0u64)
+    val v = with a = Handle::open(1u64, log) {{
+        with b = Handle::open(2u64, log) {{
+            a.id + b.id
+        }}
+    }}
+    val recorded: u64 = __builtin_ptr_read(log, 0u64)
+    recorded * 10u64 + v
+}}"
+    );
+    let result = test_program(&program).unwrap();
+    assert_eq!(result.borrow().unwrap_uint64(), 213);
+}
+
+#[test]
+fn test_with_resource_closes_on_early_exit() {
+    // `return` out of the block and `break` out of a loop around it
+    // both close the resource exactly once.
+    let program = format!(
+        "{WITH_RESOURCE_LOG}
+fn early(log: ptr) -> u64 {{
+    with h = Handle::open(3u64, log) {{
+        if h.id == 3u64 {{
+            return 7u64
+        }}
+        0u64
+    }}
+}}
+fn main() -> u64 {{
+    val log: ptr = __builtin_heap_alloc(8u64)
+    __builtin_ptr_write(log, 0u64, 0u64)
+    val r = early(log)
+    for i in 4u64 to 6u64 {{
+        with h = Handle::open(i, log) {{
+            if h.id == 5u64 {{
+                break
+            }}
+        }}
+    }}
+    val recorded: u64 = __builtin_ptr_read(log, 0u64)
+    recorded * 10u64 + r
+}}"
+    );
+    let result = test_program(&program).unwrap();
+    assert_eq!(result.borrow().unwrap_uint64(), 3457);
+}
+
+#[test]
+fn test_with_resource_reports_body_error_before_close_error() {
+    // A failing body's error is the one reported. Once the first body
+    // succeeds, the panic raised by `Loud::__close__` surfaces instead.
+    let program = format!(
+        "{WITH_RESOURCE_LOG}
+struct Loud {{ id: u64 }}
+impl Loud {{
+    fn __close__(&mut self) {{
+        panic(\"closed loud\")
+    }}
+}}
+fn main() -> u64 {{
+    val log: ptr = __builtin_heap_alloc(8u64)
+    with h = Handle::open(1u64, log) {{
+        panic(\"body failed\")
+    }}
+    val loud = Loud {{ id: 2u64 }}
+    with l = loud {{
+        l.id
+    }}
+}}"
+    );
+    let err = test_program(&program).unwrap_err();
+    assert!(err.contains("body failed"), "{err}");
+
+    let program = program.replace("panic(\"body failed\")", "h.id");
+    let err = test_program(&program).unwrap_err();
+    assert!(err.contains("closed loud"), "{err}");
+}
+
+#[test]
+fn test_with_resource_requires_close_method() {
+    let err = test_program(
+        r#"
+struct Plain { id: u64 }
+fn main() -> u64 {
+    val plain = Plain { id: 1u64 }
+    with p = plain {
+        p.id
+    }
+}
+    "#,
+    )
+    .unwrap_err();
+    assert!(err.contains("__close__"), "{err}");
+}
@@ -0,0 +1,131 @@
+// `any` type plus the `type_of` / `is<T>` / `downcast<T>` builtins.
+// `any` is the checker's dynamic type, so these tests mostly pin the
+// run-time side: type names, container matching and downcast panics.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64, test_program};
+use interpreter::object::Object;
+use interpreter::output::with_input;
+
+fn str_result(src: &str) -> String {
+    match &*test_program(src).expect("program should run").borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected a string result, got {other:?}"),
+    }
+}
+
+#[test]
+fn type_of_names_runtime_types() {
+    let src = r#"
+        struct Point { x: u64 }
+        fn name(v: any) -> str { type_of(v) }
+        fn main() -> u64 {
+            val p = Point { x: 1u64 }
+            var hits = 0u64
+            if name(1i64) == "i64" { hits = hits + 1u64 }
+            if name(2u64) == "u64" { hits = hits + 1u64 }
+            if name(1.5f64) == "f64" { hits = hits + 1u64 }
+            if name(true) == "bool" { hits = hits + 1u64 }
+            if name("s") == "str" { hits = hits + 1u64 }
+            if name([1u64]) == "array" { hits = hits + 1u64 }
+            if name((1u64, 2u64)) == "tuple" { hits = hits + 1u64 }
+            if name(p) == "Point" { hits = hits + 1u64 }
+            hits
+        }
+    "#;
+    assert_program_result_u64(src, 8);
+}
+
+#[test]
+fn type_of_literal_uses_default_integer_type() {
+    let src = r#"
+        fn main() -> str {
+            type_of(5)
+        }
+    "#;
+    assert_eq!(str_result(src), "u64");
+}
+
+#[test]
+fn is_checks_primitives_and_structs() {
+    let src = r#"
+        struct Point { x: u64 }
+        fn classify(v: any) -> u64 {
+            if is<i64>(v) { 1u64 } elif is<str>(v) { 2u64 } elif is<Point>(v) { 3u64 } else { 0u64 }
+        }
+        fn main() -> u64 {
+            classify(-1i64) * 1000u64 + classify("a") * 100u64
+                + classify(Point { x: 1u64 }) * 10u64 + classify(false)
+        }
+    "#;
+    assert_program_result_u64(src, 1230);
+}
+
+#[test]
+fn is_checks_container_shapes() {
+    let src = r#"
+        fn main() -> u64 {
+            var hits = 0u64
+            if is<[u64; 2]>([1u64, 2u64]) { hits = hits + 1u64 }
+            if is<[u64; 3]>([1u64, 2u64]) { hits = hits + 10u64 }
+            if is<[i64; 2]>([1u64, 2u64]) { hits = hits + 100u64 }
+            if is<(i64, str)>((1i64, "a")) { hits = hits + 1000u64 }
+            if is<(i64, i64)>((1i64, "a")) { hits = hits + 10000u64 }
+            hits
+        }
+    "#;
+    assert_program_result_u64(src, 1001);
+}
+
+#[test]
+fn downcast_returns_typed_value() {
+    let src = r#"
+        fn main() -> u64 {
+            val a: any = 41u64
+            downcast<u64>(a) + 1u64
+        }
+    "#;
+    assert_program_result_u64(src, 42);
+}
+
+#[test]
+fn downcast_mismatch_panics() {
+    let src = r#"
+        fn main() -> u64 {
+            val a: any = "x"
+            downcast<u64>(a)
+        }
+    "#;
+    assert_program_fails(src);
+}
+
+#[test]
+fn inspects_parsed_json() {
+    let src = r#"
+        fn main() -> u64 {
+            val doc = json_parse(read_all())
+            var score = 0u64
+            if is<i64>(doc["n"]) { score = score + 1u64 }
+            if is<str>(doc["s"]) { score = score + 10u64 }
+            if type_of(doc["a"]) == "array" { score = score + 100u64 }
+            if type_of(doc["o"]) == "dict" { score = score + 1000u64 }
+            score + downcast<i64>(doc["n"]) as u64 * 10000u64
+        }
+    "#;
+    let input = r#"{"n": 7, "s": "x", "a": [1], "o": {}}"#;
+    let result = with_input(input, || test_program(src)).expect("program should run");
+    assert_eq!(result.borrow().unwrap_uint64(), 71111);
+}
+
+#[test]
+fn typecheck_downcast_result_uses_target_type() {
+    let src = r#"
+        fn main() -> u64 {
+            val a: any = 1i64
+            val s: str = downcast<i64>(a)
+            0u64
+        }
+    "#;
+    assert!(test_program(src).is_err(), "downcast<i64> result should not be a str");
+}
@@ -0,0 +1,112 @@
+// `binary_search` / `lower_bound` / `reverse` / `min_of` / `max_of` /
+// `sum_of` — array builtins typed by the array's element type.
+
+mod common;
+
+use common::{
+    assert_program_fails, assert_program_result_array_i64, assert_program_result_f64,
+    assert_program_result_i64, assert_program_result_u64, test_program,
+};
+
+#[test]
+fn binary_search_finds_the_first_match() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val xs = [1u64, 3u64, 3u64, 3u64, 8u64]
+            val hit = match binary_search(xs, 3u64) {
+                Option::Some(i) => i,
+                Option::None => 99u64,
+            }
+            val miss = match binary_search(xs, 4u64) {
+                Option::Some(i) => i,
+                Option::None => 99u64,
+            }
+            hit * 1000u64 + miss
+        }",
+        1099,
+    );
+}
+
+#[test]
+fn lower_bound_gives_the_insertion_point() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val xs = [-4i64, -1i64, 2i64, 2i64, 9i64]
+            lower_bound(xs, -5) * 1000u64 + lower_bound(xs, 2) * 100u64
+                + lower_bound(xs, 3i64) * 10u64 + lower_bound(xs, 10i64)
+        }",
+        245,
+    );
+}
+
+#[test]
+fn reverse_returns_a_reversed_copy() {
+    assert_program_result_array_i64(
+        "fn main() -> [i64; 4] {
+            val xs = [1i64, 2i64, 3i64, 4i64]
+            val r = reverse(xs)
+            if xs[0] != 1i64 { return [0i64, 0i64, 0i64, 0i64] }
+            r
+        }",
+        vec![4, 3, 2, 1],
+    );
+}
+
+#[test]
+fn min_max_and_sum_keep_the_element_type() {
+    assert_program_result_i64(
+        "fn main() -> i64 {
+            val xs = [5i64, -7i64, 12i64, 0i64]
+            min_of(xs) * 10000i64 + max_of(xs) * 100i64 + sum_of(xs)
+        }",
+        -68790,
+    );
+    assert_program_result_f64(
+        "fn main() -> f64 {
+            sum_of([0.5f64, 1.25f64, 2.0f64]) + max_of([1.5f64, -3.0f64])
+        }",
+        5.25,
+    );
+}
+
+#[test]
+fn works_on_slices() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val xs = [9u64, 4u64, 6u64, 2u64, 7u64]
+            sum_of(xs[1..4]) * 10u64 + min_of(xs[3..])
+        }",
+        122,
+    );
+}
+
+#[test]
+fn min_of_an_empty_slice_panics() {
+    let err = test_program(
+        "fn main() -> u64 {
+            val xs = [1u64, 2u64]
+            min_of(xs[1..1])
+        }",
+    )
+    .unwrap_err();
+    assert!(err.contains("min_of of an empty array"), "{err}");
+}
+
+#[test]
+fn needle_must_match_the_element_type() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            lower_bound([1u64, 2u64], 1i64)
+        }",
+    );
+}
+
+#[test]
+fn folds_reject_non_numeric_elements() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            val s = sum_of([\"a\", \"b\"])
+            0u64
+        }",
+    );
+}
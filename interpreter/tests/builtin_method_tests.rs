@@ -0,0 +1,82 @@
+// Builtin receiver methods on `str`, arrays and `bytes`: one table
+// (`BuiltinMethod::registry_entries`) drives both the type checker's
+// `builtin_methods` registry and the interpreter's dispatch, so the
+// two agree on which methods exist and how many arguments they take.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64, test_program_no_core};
+
+#[test]
+fn str_methods_resolve_through_the_shared_table() {
+    let src = r#"
+        fn main() -> u64 {
+            val s = "hello world"
+            val first = s.substring(0u64, 5u64)
+            val parts = s.split(" ")
+            var n = first.len() + parts.len()
+            if s.contains("wor") { n = n + 10u64 }
+            n + "  pad ".trim().len()
+        }
+    "#;
+    assert_program_result_u64(src, 20);
+}
+
+#[test]
+fn str_methods_accept_runtime_built_strings() {
+    // `concat` produces a heap string rather than an interned
+    // literal; `substring` / `contains` must read either.
+    let src = r#"
+        fn main() -> u64 {
+            val s = "ab".concat("cd")
+            var n = s.substring(1u64, 3u64).len()
+            if s.contains("bc".to_upper().to_lower()) { n = n + 1u64 }
+            n
+        }
+    "#;
+    assert_program_result_u64(src, 3);
+}
+
+#[test]
+fn array_and_bytes_len_are_builtin_methods() {
+    let src = r#"
+        fn main() -> u64 {
+            val a = [1u64, 2u64, 3u64]
+            val words = "a b c d".split(" ")
+            val raw = b"\x00\x01"
+            a.len() + words.len() + raw.len()
+        }
+    "#;
+    let result = test_program_no_core(src).expect("program runs without the core modules");
+    assert_eq!(result.borrow().unwrap_uint64(), 9);
+}
+
+#[test]
+fn builtin_method_arity_is_type_checked() {
+    assert_program_fails(
+        r#"
+        fn main() -> u64 {
+            [1u64, 2u64].len(1u64)
+        }
+    "#,
+    );
+    assert_program_fails(
+        r#"
+        fn main() -> u64 {
+            "abc".substring(1u64).len()
+        }
+    "#,
+    );
+}
+
+#[test]
+fn unknown_str_method_is_rejected() {
+    assert_program_fails(
+        r#"
+        fn main() -> u64 {
+            "abc".reverse()
+            0u64
+        }
+    "#,
+    );
+}
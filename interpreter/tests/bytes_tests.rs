@@ -0,0 +1,153 @@
+// `bytes` values: `b"..."` literals, indexing (u64 per byte), `len`,
+// slicing, equality, and whole-file binary I/O through the
+// `read_bytes` / `write_bytes` builtins. Bytes are immutable, so
+// index assignment is rejected by the type checker (see the
+// `typecheck_*_rejected` cases at the bottom).
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+use interpreter::object::Object;
+
+#[test]
+fn bytes_literal_len_counts_escapes_as_single_bytes() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = b"GET \x00\xff\n"
+            b.len()
+        }
+    "#;
+    assert_program_result_u64(src, 7);
+}
+
+#[test]
+fn bytes_index_returns_u64() {
+    let src = r#"
+        fn main() -> u64 {
+            val b: bytes = b"A\xff"
+            b[0u64] + b[1u64]
+        }
+    "#;
+    assert_program_result_u64(src, 65 + 255);
+}
+
+#[test]
+fn bytes_negative_index_counts_from_end() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = b"xyz"
+            b[-1]
+        }
+    "#;
+    assert_program_result_u64(src, b'z' as u64);
+}
+
+#[test]
+fn bytes_slice_produces_bytes() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = b"hello world"
+            val word = b[6u64..]
+            if word == b"world" { word.len() } else { 0u64 }
+        }
+    "#;
+    assert_program_result_u64(src, 5);
+}
+
+#[test]
+fn bytes_as_function_parameter() {
+    let src = r#"
+        fn checksum(data: bytes) -> u64 {
+            var sum = 0u64
+            for i in 0u64..data.len() {
+                sum = sum + data[i]
+            }
+            sum
+        }
+
+        fn main() -> u64 {
+            checksum(b"\x01\x02\x03\x04")
+        }
+    "#;
+    assert_program_result_u64(src, 10);
+}
+
+#[test]
+fn bytes_equality_compares_contents() {
+    let src = r#"
+        fn main() -> u64 {
+            var score = 0u64
+            if b"ab" == b"ab" { score = score + 1u64 }
+            if b"ab" != b"abc" { score = score + 10u64 }
+            if b"\x00" != b"\x01" { score = score + 100u64 }
+            score
+        }
+    "#;
+    assert_program_result_u64(src, 111);
+}
+
+#[test]
+fn bytes_index_out_of_bounds_fails() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = b"ab"
+            b[5u64]
+        }
+    "#;
+    assert!(test_program(src).is_err());
+}
+
+#[test]
+fn bytes_file_round_trip() {
+    let path = std::env::temp_dir().join(format!("toylang_bytes_{}.bin", std::process::id()));
+    let path_str = path.to_string_lossy().replace('\\', "/");
+    let src = format!(
+        r#"
+        fn main() -> bytes {{
+            write_bytes("{path_str}", b"\x00\x01\xfe\xff")
+            read_bytes("{path_str}")
+        }}
+    "#
+    );
+    let result = test_program(&src).expect("program should run");
+    let _ = std::fs::remove_file(&path);
+    let obj = result.borrow();
+    match &*obj {
+        Object::Bytes(data) => assert_eq!(data.as_slice(), &[0x00, 0x01, 0xfe, 0xff]),
+        other => panic!("expected bytes, got {other:?}"),
+    }
+}
+
+#[test]
+fn read_bytes_missing_file_fails() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = read_bytes("/nonexistent/toylang/bytes.bin")
+            b.len()
+        }
+    "#;
+    assert!(test_program(src).is_err());
+}
+
+#[test]
+fn typecheck_bytes_index_assignment_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            val b = b"ab"
+            b[0u64] = 1u64
+            0u64
+        }
+    "#;
+    assert!(test_program(src).is_err());
+}
+
+#[test]
+fn typecheck_write_bytes_with_str_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            write_bytes("/tmp/never_written.bin", "not bytes")
+            0u64
+        }
+    "#;
+    assert!(test_program(src).is_err());
+}
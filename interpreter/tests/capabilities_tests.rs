@@ -0,0 +1,104 @@
+// Capability checks on side-effecting builtins. Each denied call must
+// abort before touching the host, and the panic names the flag that
+// would have allowed it.
+
+mod common;
+
+use common::{test_program, test_program_with_capabilities};
+use interpreter::object::Object;
+use interpreter::{Capabilities, Capability};
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("toylang_caps_{}_{name}", std::process::id()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn expect_denied(src: &str, caps: Capabilities, expected: &str) {
+    let err = test_program_with_capabilities(src, caps).expect_err("call should be denied");
+    assert!(err.contains(expected), "unexpected error: {err}");
+}
+
+#[test]
+fn default_capabilities_allow_files_and_clock_only() {
+    let caps = Capabilities::default();
+    assert!(caps.fs_read && caps.fs_write && caps.clock);
+    assert!(!caps.exec && !caps.net);
+    assert_eq!(Capabilities::none().with(Capability::Net), Capabilities { net: true, ..Capabilities::none() });
+    assert_eq!(Capability::from_flag_name("fs-write"), Some(Capability::FsWrite));
+    assert_eq!(Capability::from_flag_name("fs_write"), None);
+}
+
+#[test]
+fn sandbox_denies_file_reads() {
+    let src = r#"
+        fn main() -> u64 {
+            read_bytes("/etc/hostname").len()
+        }
+    "#;
+    expect_denied(src, Capabilities::none(), "read_bytes: file reading is disabled (run with --allow-fs-read)");
+}
+
+#[test]
+fn sandbox_denies_file_writes_before_touching_disk() {
+    let path = temp_path("denied.bin");
+    let src = format!(
+        r#"
+        fn main() -> u64 {{
+            write_bytes("{path}", b"x")
+            0u64
+        }}
+    "#
+    );
+    let caps = Capabilities::none().with(Capability::FsRead);
+    expect_denied(&src, caps, "write_bytes: file writing is disabled (run with --allow-fs-write)");
+    assert!(!std::path::Path::new(&path).exists());
+}
+
+#[test]
+fn sandbox_denies_clock_builtins() {
+    for call in ["now_millis()", "clock()", "sleep(1)"] {
+        let src = format!(
+            r#"
+            fn main() -> u64 {{
+                val t = {call}
+                0u64
+            }}
+        "#
+        );
+        let name = &call[..call.find('(').unwrap()];
+        expect_denied(&src, Capabilities::none(), &format!("{name}: clock access is disabled"));
+    }
+}
+
+#[test]
+fn granted_capabilities_are_honoured() {
+    let path = temp_path("granted.bin");
+    let src = format!(
+        r#"
+        fn main() -> u64 {{
+            write_bytes("{path}", b"abc")
+            read_bytes("{path}").len()
+        }}
+    "#
+    );
+    let caps = Capabilities::none().with(Capability::FsRead).with(Capability::FsWrite);
+    let result = test_program_with_capabilities(&src, caps).expect("program should run");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(*result.borrow(), Object::UInt64(3));
+}
+
+#[test]
+fn pure_programs_run_without_capabilities() {
+    let src = r#"
+        fn main() -> u64 {
+            seed(1)
+            val r = random_range(0u64, 10u64)
+            if r < 10u64 { 1u64 } else { 0u64 }
+        }
+    "#;
+    let result = test_program_with_capabilities(src, Capabilities::none()).expect("program should run");
+    assert_eq!(*result.borrow(), Object::UInt64(1));
+    assert!(test_program(src).is_ok());
+}
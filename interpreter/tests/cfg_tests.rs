@@ -0,0 +1,101 @@
+// `@cfg(...)` items are pruned against `target = "interpreter"` plus the
+// run's `--define`s before type checking, so backend-specific variants
+// of the same function can live side by side in one source.
+
+mod common;
+
+use common::core_modules_dir;
+use frontend::CfgEnv;
+use interpreter::RunOptions;
+
+const SOURCE: &str = r#"
+@cfg(target = "lua")
+fn backend() -> u64 {
+    1u64
+}
+
+@cfg(target = "interpreter")
+fn backend() -> u64 {
+    2u64
+}
+
+@cfg(fast)
+const SCALE: u64 = 100u64
+
+@cfg(not(fast))
+const SCALE: u64 = 10u64
+
+@cfg(any(mode = "debug", mode = "trace"))
+fn extra() -> u64 {
+    5u64
+}
+
+@cfg(not(any(mode = "debug", mode = "trace")))
+fn extra() -> u64 {
+    0u64
+}
+
+fn main() -> u64 {
+    backend() * SCALE + extra()
+}
+"#;
+
+fn run(defines: &[&str]) -> Option<i32> {
+    let core = core_modules_dir();
+    let mut env = CfgEnv::new();
+    for define in defines {
+        env.define_arg(define).expect("valid define");
+    }
+    let opts = RunOptions {
+        core_modules_dir: Some(core.as_path()),
+        defines: Some(&env),
+        ..Default::default()
+    };
+    interpreter::run_source(SOURCE, "test.t", &opts)
+        .expect("program should run")
+        .exit_code
+}
+
+#[test]
+fn interpreter_target_selects_its_variant() {
+    assert_eq!(run(&[]), Some(20));
+}
+
+#[test]
+fn defines_select_variants() {
+    assert_eq!(run(&["fast"]), Some(200));
+    assert_eq!(run(&["mode=trace"]), Some(25));
+    assert_eq!(run(&["fast", "mode=debug"]), Some(205));
+}
+
+#[test]
+fn defines_cannot_override_target() {
+    assert_eq!(run(&["target=lua"]), Some(20));
+}
+
+#[test]
+fn inline_lua_behind_a_lua_guard_is_pruned() {
+    let core = core_modules_dir();
+    let opts = RunOptions { core_modules_dir: Some(core.as_path()), ..Default::default() };
+    let guarded = r#"
+@cfg(target = "lua")
+fn now() -> u64 {
+    val t: u64 = lua!("os.time()")
+    t
+}
+
+@cfg(not(target = "lua"))
+fn now() -> u64 {
+    7u64
+}
+
+fn main() -> u64 {
+    now()
+}
+"#;
+    let outcome = interpreter::run_source(guarded, "test.t", &opts).expect("program should run");
+    assert_eq!(outcome.exit_code, Some(7));
+
+    let unguarded = "fn main() -> u64 {\n    val t: u64 = lua!(\"os.time()\")\n    t\n}\n";
+    assert!(interpreter::run_source(unguarded, "test.t", &opts).is_err());
+}
@@ -0,0 +1,79 @@
+// Session-wide function check cache. Re-running the same source through
+// one `CompilerSession` must serve unchanged functions from the cache and
+// still produce the same program behaviour; an edited body must miss.
+
+mod common;
+
+use common::core_modules_dir;
+use compiler_core::CompilerSession;
+use interpreter::RunOptions;
+
+const SOURCE: &str = r#"
+fn square(x: u64) -> u64 {
+    x * x
+}
+
+fn sum_to(n: u64) -> u64 {
+    var total = 0u64
+    var i = 0u64
+    while i < n {
+        total = total + i
+        i = i + 1
+    }
+    total
+}
+
+fn main() -> u64 {
+    val s = square(7u64)
+    val bonus: u64 = 3
+    sum_to(10u64) + s + bonus
+}
+"#;
+
+fn try_run(session: &mut CompilerSession, src: &str) -> Result<Option<i32>, String> {
+    let core = core_modules_dir();
+    let opts = RunOptions { core_modules_dir: Some(core.as_path()), ..Default::default() };
+    interpreter::run_source_in_session(session, src, "test.t", &opts).map(|outcome| outcome.exit_code)
+}
+
+fn run(session: &mut CompilerSession, src: &str) -> Option<i32> {
+    try_run(session, src).expect("program should run")
+}
+
+#[test]
+fn rerun_serves_unchanged_functions_from_cache() {
+    let mut session = CompilerSession::new();
+    assert_eq!(run(&mut session, SOURCE), Some(97));
+    let cache = session.function_check_cache();
+    assert_eq!((cache.len(), cache.hits(), cache.misses()), (3, 0, 3));
+
+    // The second run replays finalized literals (`1`, `3`) from the cache,
+    // so the result has to match the first run exactly.
+    assert_eq!(run(&mut session, SOURCE), Some(97));
+    let cache = session.function_check_cache();
+    assert_eq!((cache.len(), cache.hits(), cache.misses()), (3, 3, 3));
+}
+
+#[test]
+fn edited_body_is_checked_again() {
+    let mut session = CompilerSession::new();
+    assert_eq!(run(&mut session, SOURCE), Some(97));
+    let edited = SOURCE.replace("square(7u64)", "square(8u64)");
+    assert_eq!(run(&mut session, &edited), Some(112));
+    let cache = session.function_check_cache();
+    assert_eq!(cache.misses(), 4);
+    assert_eq!(cache.hits(), 2);
+}
+
+#[test]
+fn type_errors_are_not_cached() {
+    let mut session = CompilerSession::new();
+    let broken = SOURCE.replace("x * x", "x * true");
+    assert!(try_run(&mut session, &broken).is_err());
+    assert!(try_run(&mut session, &broken).is_err());
+    // `square` fails both times, so it never enters the cache; whatever
+    // did get cached on the first run is what the second run hits.
+    let cache = session.function_check_cache();
+    assert!(cache.len() < 3);
+    assert_eq!(cache.hits(), cache.len());
+}
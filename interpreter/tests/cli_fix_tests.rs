@@ -0,0 +1,57 @@
+//! A call through a module nobody imported gets an `import` fix-it
+//! when the module sits under `modules/`; `--fix` writes it into the
+//! file and runs the result.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_interpreter");
+
+const MAIN: &str = "fn main() -> u64 {\n    utils::add(1u64, 2u64)\n}\n";
+
+fn project(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("toylang_fix_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(dir.join("modules/math")).unwrap();
+    std::fs::write(
+        dir.join("modules/math/utils.t"),
+        "package math.utils\n\npub fn add(a: u64, b: u64) -> u64 {\n    a + b\n}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("main.t"), MAIN).unwrap();
+    dir
+}
+
+fn run(dir: &PathBuf, args: &[&str]) -> (i32, String) {
+    let out = Command::new(BIN)
+        .arg("main.t")
+        .args(args)
+        .current_dir(dir)
+        .env("TOYLANG_CORE_MODULES", "")
+        .env_remove("INTERPRETER_JIT")
+        .output()
+        .expect("failed to run interpreter binary");
+    (out.status.code().unwrap_or(-1), String::from_utf8_lossy(&out.stderr).into_owned())
+}
+
+#[test]
+fn missing_import_is_suggested() {
+    let dir = project("suggest");
+    let (code, stderr) = run(&dir, &[]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains("= help: add `import math.utils`"), "stderr was: {stderr}");
+    assert_eq!(std::fs::read_to_string(dir.join("main.t")).unwrap(), MAIN);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fix_inserts_the_import_and_runs() {
+    let dir = project("apply");
+    let (code, stderr) = run(&dir, &["--fix"]);
+    assert_eq!(code, 3, "stderr was: {stderr}");
+    assert!(stderr.contains("fixed main.t: add `import math.utils`"), "stderr was: {stderr}");
+    assert_eq!(
+        std::fs::read_to_string(dir.join("main.t")).unwrap(),
+        format!("import math.utils\n\n{MAIN}")
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
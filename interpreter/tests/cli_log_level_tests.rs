@@ -0,0 +1,90 @@
+//! The interpreter's own diagnostics stay off stderr unless `-v` or
+//! `--log-level` asks for them, so a plain run's stderr carries only
+//! the program's output and its errors. `--timings` (and `-v`) add the
+//! per-phase report.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const BIN: &str = env!("CARGO_BIN_EXE_interpreter");
+
+const PROGRAM: &str = "fn main() -> u64 {\n    println(\"hi\")\n    0u64\n}\n";
+
+fn run_stdin(args: &[&str], source: &str) -> (i32, String, String) {
+    let mut child = Command::new(BIN)
+        .arg("-")
+        .args(args)
+        .env_remove("INTERPRETER_JIT")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn interpreter binary");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(source.as_bytes())
+        .expect("failed to write program to stdin");
+    let out = child.wait_with_output().expect("interpreter did not finish");
+    (
+        out.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&out.stdout).into_owned(),
+        String::from_utf8_lossy(&out.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn default_run_keeps_stderr_clean() {
+    let (code, stdout, stderr) = run_stdin(&[], PROGRAM);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "hi\n");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn verbose_prints_phases_on_stderr_only() {
+    let (code, stdout, stderr) = run_stdin(&["-v"], PROGRAM);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "hi\n");
+    assert!(stderr.contains("Core modules directory:"), "stderr was: {stderr}");
+    assert!(stderr.contains("Parsing source file: <stdin>"), "stderr was: {stderr}");
+    assert!(stderr.contains("Executing program"), "stderr was: {stderr}");
+    assert!(stderr.contains("Phase timings:"), "stderr was: {stderr}");
+    assert!(!stderr.contains("Loaded core module"), "stderr was: {stderr}");
+}
+
+#[test]
+fn debug_level_lists_loaded_modules() {
+    let (code, _, stderr) = run_stdin(&["--log-level=debug"], PROGRAM);
+    assert_eq!(code, 0);
+    assert!(stderr.contains("Loaded core module `std.math`"), "stderr was: {stderr}");
+}
+
+#[test]
+fn errors_still_reach_stderr_with_logging_off() {
+    let (code, _, stderr) = run_stdin(&["--log-level", "off"], "fn main() -> u64 {\n    1u64 + true\n}\n");
+    assert_eq!(code, 1);
+    assert!(stderr.contains("<stdin>:2:"), "stderr was: {stderr}");
+}
+
+#[test]
+fn unknown_log_level_is_rejected() {
+    let (_, stdout, stderr) = run_stdin(&["--log-level", "loud"], PROGRAM);
+    assert!(!stdout.contains("hi"), "stdout was: {stdout}");
+    assert!(stderr.contains("invalid --log-level `loud`"), "stderr was: {stderr}");
+}
+
+#[test]
+fn timings_json_lists_phases_in_order() {
+    let (code, stdout, stderr) = run_stdin(&["--timings=json"], PROGRAM);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "hi\n");
+    let line = stderr.lines().find(|l| l.starts_with("{\"phases\"")).unwrap_or_else(|| panic!("stderr was: {stderr}"));
+    let order: Vec<usize> = ["parse", "module_resolution", "type_check", "execution"]
+        .iter()
+        .map(|phase| line.find(&format!("\"phase\": \"{phase}\"")).unwrap_or_else(|| panic!("no {phase} in {line}")))
+        .collect();
+    assert!(order.windows(2).all(|w| w[0] < w[1]), "line was: {line}");
+    assert!(line.contains("\"total_ms\": "), "line was: {line}");
+}
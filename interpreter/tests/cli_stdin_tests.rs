@@ -0,0 +1,41 @@
+//! `interpreter -` reads the program from standard input. The run itself
+//! behaves exactly like a file argument; diagnostics name the source
+//! `<stdin>` instead of the `-` that was passed.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const BIN: &str = env!("CARGO_BIN_EXE_interpreter");
+
+fn run_stdin(source: &str) -> (i32, String) {
+    let mut child = Command::new(BIN)
+        .arg("-")
+        .env_remove("INTERPRETER_JIT")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn interpreter binary");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(source.as_bytes())
+        .expect("failed to write program to stdin");
+    let out = child.wait_with_output().expect("interpreter did not finish");
+    let stderr = String::from_utf8_lossy(&out.stderr).into_owned();
+    (out.status.code().unwrap_or(-1), stderr)
+}
+
+#[test]
+fn dash_runs_program_from_stdin() {
+    let (code, _) = run_stdin("fn main() -> u64 { 6u64 * 7u64 }\n");
+    assert_eq!(code, 42);
+}
+
+#[test]
+fn stdin_diagnostics_use_virtual_name() {
+    let (code, stderr) = run_stdin("fn main() -> u64 {\n    1u64 + true\n}\n");
+    assert_eq!(code, 1);
+    assert!(stderr.contains("<stdin>:2:"), "stderr was: {stderr}");
+}
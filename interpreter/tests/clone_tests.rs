@@ -0,0 +1,72 @@
+// `clone(x)` deep copy: mutating the copy leaves the original intact
+// at every nesting level, and aliasing inside the value is kept.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64};
+
+#[test]
+fn nested_array_copy_is_independent() {
+    let src = r#"
+        fn main() -> u64 {
+            var a = [[1u64, 2u64], [3u64, 4u64]]
+            var b = clone(a)
+            b[0][0] = 100u64
+            a[0][0] + b[0][0]
+        }
+    "#;
+    assert_program_result_u64(src, 101);
+}
+
+#[test]
+fn nested_struct_copy_is_independent() {
+    let src = r#"
+        struct Inner { n: u64 }
+        struct Outer { inner: Inner, items: [u64] }
+        fn main() -> u64 {
+            var o = Outer { inner: Inner { n: 5u64 }, items: [1u64, 2u64] }
+            var p = clone(o)
+            p.inner.n = 50u64
+            p.items[0] = 9u64
+            o.inner.n + o.items[0] + p.inner.n + p.items[0]
+        }
+    "#;
+    assert_program_result_u64(src, 65);
+}
+
+#[test]
+fn dict_values_are_copied() {
+    let src = r#"
+        fn main() -> u64 {
+            var d = dict{"k": [1u64]}
+            var e = clone(d)
+            e["k"][0] = 7u64
+            d["k"][0] * 10u64 + e["k"][0]
+        }
+    "#;
+    assert_program_result_u64(src, 17);
+}
+
+#[test]
+fn shared_elements_stay_shared_in_the_copy() {
+    let src = r#"
+        fn main() -> u64 {
+            var x = [1u64]
+            var a = [x, x]
+            var b = clone(a)
+            b[0][0] = 9u64
+            a[1][0] * 10u64 + b[1][0]
+        }
+    "#;
+    assert_program_result_u64(src, 19);
+}
+
+#[test]
+fn clone_takes_one_argument() {
+    let src = r#"
+        fn main() -> u64 {
+            clone(1u64, 2u64)
+        }
+    "#;
+    assert_program_fails(src);
+}
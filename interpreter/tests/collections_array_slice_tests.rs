@@ -54,6 +54,114 @@ mod array_tests {
         }
         "); // Should return error for out of bounds access
     }
+
+    #[test]
+    fn test_array_element_compound_assignment() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var a: [u64; 3] = [1u64, 2u64, 3u64]
+            a[1u64] += 5u64
+            a[2u64] *= 4u64
+            a[0u64] -= 1u64
+            a[0u64] + a[1u64] + a[2u64]
+        }
+        ", 19);
+    }
+
+    #[test]
+    fn test_array_compound_assignment_out_of_bounds_reports_index_and_length() {
+        let err = common::test_program(r"
+        fn main() -> u64 {
+            var a: [u64; 2] = [1u64, 2u64]
+            var i = 0u64
+            while i < 3u64 {
+                a[i] += 1u64
+                i = i + 1u64
+            }
+            a[0u64]
+        }
+        ").expect_err("index 2 is past the end");
+        assert!(err.contains("index 2 out of bounds for array of size 2"), "got: {err}");
+    }
+
+    #[test]
+    fn test_array_range_assignment() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var a: [u64; 5] = [1u64, 2u64, 3u64, 4u64, 5u64]
+            val b: [u64; 2] = [20u64, 30u64]
+            a[1..3] = b
+            a[3..] = [40u64, 50u64]
+            a[..1] = [a[4]]
+            a[-2..] = [7u64, 8u64]
+            a[0] + a[1] + a[2] + a[3] + a[4]
+        }
+        ", 115);
+    }
+
+    #[test]
+    fn test_array_range_assignment_length_mismatch() {
+        let err = common::test_program(r"
+        fn main() -> u64 {
+            var a: [u64; 3] = [1u64, 2u64, 3u64]
+            a[0..2] = [9u64]
+            a[0]
+        }
+        ").expect_err("range has two elements, value has one");
+        assert!(err.contains("range 0..2 has 2 elements, but the value has 1"), "got: {err}");
+    }
+
+    #[test]
+    fn test_nested_array_indexing() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var m: [[u64; 3]; 3] = [[1u64, 2u64, 3u64], [4u64, 5u64, 6u64], [7u64, 8u64, 9u64]]
+            m[1][2] = 60u64
+            m[2][0] += 3u64
+            m[-1i64][-1i64] = 90u64
+            var s = 0u64
+            var i = 0u64
+            while i < 3u64 {
+                var j = 0u64
+                while j < 3u64 {
+                    s = s + m[i][j]
+                    j = j + 1u64
+                }
+                i = i + 1u64
+            }
+            s
+        }
+        ", 183);
+    }
+
+    #[test]
+    fn test_sized_array_element_assignment_is_type_checked() {
+        for program in [
+            "fn main() -> u64 {\n var a: [u64; 2] = [1u64, 2u64]\n a[0] = true\n a[1]\n}",
+            "fn main() -> u64 {\n var m: [[u64; 2]; 2] = [[1u64, 2u64], [3u64, 4u64]]\n m[0][1] = true\n m[0][0]\n}",
+        ] {
+            let err = common::test_program(program).expect_err("bool written into a u64 array");
+            assert!(err.contains("expected UInt64, but got Bool"), "got: {err}");
+        }
+    }
+
+    #[test]
+    fn test_array_comprehension() {
+        common::assert_program_result_array_u64(r"
+        fn main() -> [u64; 5] {
+            val evens = [x * x for x in 0..10 if x % 2 == 0]
+            val sized: [u64; 5] = [v + 1 for v in evens]
+            sized
+        }
+        ", vec![1, 5, 17, 37, 65]);
+    }
+
+    #[test]
+    fn test_comprehension_filter_must_be_bool() {
+        let err = common::test_program("fn main() -> u64 {\n val a = [x for x in 0..3 if x]\n 0\n}")
+            .expect_err("u64 filter");
+        assert!(err.contains("comprehension filter"), "got: {err}");
+    }
 }
 
 #[cfg(test)]
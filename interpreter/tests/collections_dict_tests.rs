@@ -23,6 +23,18 @@ fn main() -> str {
         }
     }
 
+    #[test]
+    fn test_dict_comprehension() {
+        let source = r#"
+fn main() -> u64 {
+    val squares = dict{x: x * x for x in 1..6 if x != 3}
+    squares[1u64] + squares[2u64] + squares[5u64]
+}
+"#;
+        let result = test_program(source).expect("Program should execute successfully");
+        assert_eq!(&*result.borrow(), &Object::UInt64(30));
+    }
+
     #[test]
     fn test_dict_literal_with_entries() {
         let source = r#"
@@ -59,7 +71,7 @@ fn main() -> str {
     fn test_dict_index_assignment() {
         let source = r#"
 fn main() -> str {
-    val data = dict{"key": "old_value"}
+    var data = dict{"key": "old_value"}
     data["key"] = "new_value"
     data["key"]
 }
@@ -76,7 +88,7 @@ fn main() -> str {
     fn test_dict_new_key_assignment() {
         let source = r#"
 fn main() -> str {
-    val data = dict{"existing": "value"}
+    var data = dict{"existing": "value"}
     data["new_key"] = "new_value"
     data["new_key"]
 }
@@ -1199,3 +1199,80 @@ fn main() -> u64 {
         assert!(result.is_err(), "assigning to a missing field must fail");
     }
 }
+
+// Tuple structs / newtypes: `struct Meters(u64)` is a struct whose
+// fields are positional, built with `Meters(5)` and read with `.0`.
+mod newtype_struct_tests {
+    use super::*;
+
+    #[test]
+    fn test_newtype_construct_access_and_methods() {
+        let program = r#"
+struct Meters(u64)
+
+impl Meters {
+    fn double(self: Self) -> Meters { Meters(self.0 * 2u64) }
+}
+
+fn add(a: Meters, b: Meters) -> Meters { Meters(a.0 + b.0) }
+
+fn main() -> u64 {
+    val a = Meters(5u64)
+    val b = add(a, Meters(7u64))
+    b.double().0
+}
+"#;
+        let result = test_program(program).expect("newtype program should run");
+        assert_eq!(result.borrow().unwrap_uint64(), 24);
+    }
+
+    #[test]
+    fn test_tuple_struct_fields_assign_and_generics() {
+        let program = r#"
+struct Pair(u64, i64)
+struct Wrap<T>(T)
+
+fn main() -> u64 {
+    var p = Pair(1u64, -2i64)
+    p.0 = 10u64
+    val w = Wrap(3u64)
+    val v = Wrap<u64>(4)
+    p.0 + (p.1 + 2i64) as u64 + w.0 + v.0
+}
+"#;
+        let result = test_program(program).expect("tuple struct program should run");
+        assert_eq!(result.borrow().unwrap_uint64(), 17);
+    }
+
+    #[test]
+    fn test_newtype_does_not_mix_with_inner_type() {
+        let cases = [
+            "val x: u64 = Meters(5u64)\n    x",
+            "Meters(5u64) + 1u64",
+            "take(5u64)",
+        ];
+        for body in cases {
+            let program = format!(
+                "struct Meters(u64)\nfn take(m: Meters) -> u64 {{ m.0 }}\nfn main() -> u64 {{\n    {body}\n}}\n"
+            );
+            assert!(test_program(&program).is_err(), "`{body}` should not type-check");
+        }
+    }
+
+    #[test]
+    fn test_tuple_struct_arity_and_index_errors() {
+        let wrong_arity = r#"
+struct Meters(u64)
+fn main() -> u64 { Meters(1u64, 2u64).0 }
+"#;
+        let err = test_program(wrong_arity).unwrap_err();
+        assert!(err.contains("takes 1 field(s), found 2"), "{err}");
+
+        let bad_index = r#"
+struct Meters(u64)
+fn main() -> u64 { Meters(1u64).1 }
+"#;
+        let err = test_program(bad_index).unwrap_err();
+        assert!(err.contains("out of bounds"), "{err}");
+    }
+}
@@ -42,9 +42,26 @@ pub fn test_program_no_core(source_code: &str) -> Result<Rc<RefCell<Object>>, St
     test_program_with_core(source_code, None)
 }
 
+/// Like `test_program`, but the run holds exactly `capabilities`
+/// instead of the default set.
+pub fn test_program_with_capabilities(
+    source_code: &str,
+    capabilities: interpreter::Capabilities,
+) -> Result<Rc<RefCell<Object>>, String> {
+    test_program_with_core_and_capabilities(source_code, Some(core_modules_dir()), capabilities)
+}
+
 fn test_program_with_core(
     source_code: &str,
     core: Option<std::path::PathBuf>,
+) -> Result<Rc<RefCell<Object>>, String> {
+    test_program_with_core_and_capabilities(source_code, core, interpreter::Capabilities::default())
+}
+
+fn test_program_with_core_and_capabilities(
+    source_code: &str,
+    core: Option<std::path::PathBuf>,
+    capabilities: interpreter::Capabilities,
 ) -> Result<Rc<RefCell<Object>>, String> {
     let mut parser = frontend::ParserWithInterner::new(source_code);
     parser.set_source_file("test.t");
@@ -63,7 +80,13 @@ fn test_program_with_core(
     .map_err(|errors| format!("Type check errors: {errors:?}"))?;
 
     // Execute program
-    interpreter::execute_program(&program, string_interner, Some(source_code), Some("test.t"))
+    interpreter::execute_program_with_capabilities(
+        &program,
+        string_interner,
+        Some(source_code),
+        Some("test.t"),
+        capabilities,
+    )
 }
 
 /// Helper function to execute a program and assert the result is a u64 value
@@ -160,6 +183,7 @@ pub fn assert_object_type(obj: &Object, expected_type: &str) {
         Object::Float64(_) => "Float64",
         Object::Bool(_) => "Bool",
         Object::String(_) => "String",
+        Object::Bytes(_) => "Bytes",
         Object::ConstString(_) => "ConstString",
         Object::Struct { .. } => "Struct",
         Object::Array(_) => "Array",
@@ -171,6 +195,7 @@ pub fn assert_object_type(obj: &Object, expected_type: &str) {
         Object::EnumVariant { .. } => "EnumVariant",
         Object::Range { .. } => "Range",
         Object::Closure { .. } => "Closure",
+        Object::Generator(_) => "Generator",
     };
     assert_eq!(actual_type, expected_type, "Expected {} but got {}", expected_type, actual_type);
 }
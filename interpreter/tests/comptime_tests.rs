@@ -0,0 +1,119 @@
+// Compile-time evaluation of pure calls (`interpreter::comptime`).
+// Folding must not change what the program computes, and calls that
+// can't finish cleanly in the sandbox stay in place.
+
+use compiler_core::CompilerSession;
+use frontend::ast::{Expr, ExprRef, Program};
+
+const SOURCE: &str = r#"
+fn fact(n: u64) -> u64 {
+    if n == 0u64 { 1u64 } else { n * fact(n - 1u64) }
+}
+
+fn spin(n: u64) -> u64 {
+    var i = 0u64
+    while true { i = i + 1u64 }
+    n
+}
+
+fn fail(n: u64) -> u64 {
+    panic("no")
+    n
+}
+
+fn loud(n: u64) -> u64 {
+    println(n)
+    n
+}
+
+fn main() -> u64 {
+    val a = fact(fact(3u64) - 1u64)
+    if a == 0u64 {
+        spin(1u64) + fail(2u64)
+    } else {
+        a - loud(100u64)
+    }
+}
+"#;
+
+/// Parse, check and fold `source`, returning the session that owns
+/// its interner, the folded program and the folded count.
+fn fold_source(source: &str) -> (CompilerSession, Program, usize) {
+    let mut session = CompilerSession::new();
+    let mut program = session.parse_named_source("test.t", source).expect("parse");
+    let results = interpreter::check_typing_with_results(
+        &mut program,
+        session.string_interner_mut(),
+        Some(source),
+        Some("test.t"),
+        None,
+    )
+    .expect("type check");
+    let folded = interpreter::comptime::fold_pure_calls(
+        &mut program,
+        &results.expr_types,
+        &results.purity,
+        session.string_interner(),
+    );
+    (session, program, folded)
+}
+
+/// The calls left in `program`, by callee name.
+fn remaining_calls(session: &CompilerSession, program: &Program) -> Vec<String> {
+    let interner = session.string_interner();
+    let mut calls: Vec<String> = (0..program.expression.len())
+        .filter_map(|i| match program.expression.get(&ExprRef(i as u32)) {
+            Some(Expr::Call(name, ..)) => Some(interner.resolve(name).unwrap().to_string()),
+            _ => None,
+        })
+        .collect();
+    calls.sort();
+    calls
+}
+
+/// Fold `SOURCE`, returning the folded count, the value `main` returns
+/// afterwards and the remaining calls by callee name.
+fn fold() -> (usize, u64, Vec<String>) {
+    let (session, program, folded) = fold_source(SOURCE);
+    let value = interpreter::execute_program(&program, session.string_interner(), Some(SOURCE), Some("test.t"))
+        .expect("run")
+        .borrow()
+        .unwrap_uint64();
+    (folded, value, remaining_calls(&session, &program))
+}
+
+#[test]
+fn constant_calls_fold_to_their_results() {
+    let (folded, value, calls) = fold();
+    // `fact(3u64)` and the outer `fact(...)` both fold; the recursive
+    // call inside `fact` has a non-constant argument.
+    assert_eq!(folded, 2);
+    assert_eq!(value, 20);
+    assert_eq!(calls.iter().filter(|c| *c == "fact").count(), 1, "{calls:?}");
+}
+
+#[test]
+fn unfinished_and_impure_calls_are_kept() {
+    let (_, _, calls) = fold();
+    for name in ["spin", "fail", "loud"] {
+        assert!(calls.iter().any(|c| c == name), "`{name}` should still be called: {calls:?}");
+    }
+}
+
+#[test]
+fn deep_recursion_is_kept_instead_of_overflowing() {
+    let source = r#"
+fn depth(n: u64) -> u64 {
+    if n == 0u64 { 0u64 } else { 1u64 + depth(n - 1u64) }
+}
+
+fn main() -> u64 {
+    depth(100000u64) + depth(3u64)
+}
+"#;
+    let (session, program, folded) = fold_source(source);
+    // `depth(3u64)` stays under the call depth limit and folds.
+    assert_eq!(folded, 1);
+    let calls = remaining_calls(&session, &program);
+    assert_eq!(calls.iter().filter(|c| *c == "depth").count(), 2, "{calls:?}");
+}
@@ -0,0 +1,61 @@
+// `--coverage`: lcov line and function counts for the user's program,
+// written through `RunOptions::coverage`.
+
+mod common;
+
+use interpreter::RunOptions;
+
+const SOURCE: &str = "fn unused(x: u64) -> u64 {
+    x * 3u64
+}
+
+fn classify(n: u64) -> u64 {
+    if n > 5u64 {
+        val big = n * 2u64
+        big
+    } else {
+        n
+    }
+}
+
+fn main() -> u64 {
+    var sum = 0u64
+    for i in 0u64 to 3u64 {
+        sum = sum + classify(i)
+    }
+    sum
+}
+";
+
+fn covered_run(source: &str, tag: &str) -> String {
+    let core = common::core_modules_dir();
+    let report = std::env::temp_dir().join(format!("toylang_coverage_{}_{tag}.info", std::process::id()));
+    let opts = RunOptions { core_modules_dir: Some(core.as_path()), coverage: Some(report.as_path()), ..RunOptions::default() };
+    interpreter::run_source(source, "cov.t", &opts).expect("program should run");
+    let lcov = std::fs::read_to_string(&report).expect("coverage report written");
+    let _ = std::fs::remove_file(&report);
+    lcov
+}
+
+#[test]
+fn lcov_reports_line_and_function_hits() {
+    let lcov = covered_run(SOURCE, "hits");
+    assert!(lcov.starts_with("TN:\nSF:cov.t\n"), "{lcov}");
+    for line in ["FN:1,unused", "FN:5,classify", "FNDA:0,unused", "FNDA:3,classify", "FNDA:1,main", "FNF:3", "FNH:2"] {
+        assert!(lcov.lines().any(|l| l == line), "missing `{line}` in:\n{lcov}");
+    }
+    // Not taken: the never-called `unused` and the `n > 5` branch.
+    for line in ["DA:2,0", "DA:7,0", "DA:8,0", "DA:6,3", "DA:10,3", "DA:17,3", "DA:19,1"] {
+        assert!(lcov.lines().any(|l| l == line), "missing `{line}` in:\n{lcov}");
+    }
+    assert!(lcov.ends_with("end_of_record\n"), "{lcov}");
+}
+
+#[test]
+fn closing_braces_are_not_instrumented() {
+    let lcov = covered_run(SOURCE, "braces");
+    for brace_line in [3, 9, 11, 12, 18, 20] {
+        let prefix = format!("DA:{brace_line},");
+        assert!(!lcov.lines().any(|l| l.starts_with(&prefix)), "line {brace_line} instrumented:\n{lcov}");
+    }
+}
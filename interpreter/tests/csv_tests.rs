@@ -0,0 +1,135 @@
+// `core/std/csv.t` — `parse_csv` / `parse_tsv` / `format_csv` /
+// `read_csv` / `write_csv`. String literals spell `"` as `\u{22}`.
+// Formatted output comes back as `bytes` through
+// `__builtin_bytes_from_ptr` so the tests can compare it as text.
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+use interpreter::object::Object;
+
+fn run_bytes(src: &str) -> String {
+    match &*test_program(src).expect("program should run").borrow() {
+        Object::Bytes(data) => String::from_utf8(data.to_vec()).unwrap(),
+        other => panic!("expected bytes, got {other:?}"),
+    }
+}
+
+#[test]
+fn parses_records_and_fields() {
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            val rows: Vec<Vec<String>> = csv::parse_csv("id,name\n1,ada\n2,grace\n")
+            val last: Vec<String> = rows.get(2u64)
+            val name: String = last.get(1u64)
+            assert(name.eq(String::from_str("grace")), "last name")
+            rows.size() * 10u64 + last.size()
+        }"#,
+        32,
+    );
+}
+
+#[test]
+fn quoted_fields_keep_separators_newlines_and_quotes() {
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            val rows: Vec<Vec<String>> =
+                parse_csv("\u{22}Smith, J\u{22},\u{22}a\nb\u{22},\u{22}say \u{22}\u{22}hi\u{22}\u{22}\u{22}")
+            val row: Vec<String> = rows.get(0u64)
+            val a: String = row.get(0u64)
+            val b: String = row.get(1u64)
+            val c: String = row.get(2u64)
+            assert(a.eq(String::from_str("Smith, J")), "separator inside quotes")
+            assert(b.eq(String::from_str("a\nb")), "newline inside quotes")
+            assert(c.eq(String::from_str("say \u{22}hi\u{22}")), "doubled quotes")
+            rows.size() * 10u64 + row.size()
+        }"#,
+        13,
+    );
+}
+
+#[test]
+fn crlf_blank_lines_and_empty_fields() {
+    // Blank lines are skipped; a trailing separator is an empty last
+    // field; `""` alone is one empty field.
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            val rows: Vec<Vec<String>> = parse_csv("a,b\r\n\r\n\nc,\r\n\u{22}\u{22}")
+            val second: Vec<String> = rows.get(1u64)
+            val third: Vec<String> = rows.get(2u64)
+            val empty: String = second.get(1u64)
+            assert(empty.is_empty(), "trailing separator")
+            rows.size() * 100u64 + second.size() * 10u64 + third.size()
+        }"#,
+        321,
+    );
+}
+
+#[test]
+fn tsv_splits_on_tabs() {
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            val rows: Vec<Vec<String>> = csv::parse_tsv("a,b\tc\n")
+            val row: Vec<String> = rows.get(0u64)
+            val first: String = row.get(0u64)
+            assert(first.eq(String::from_str("a,b")), "comma is data in TSV")
+            row.size()
+        }"#,
+        2,
+    );
+}
+
+#[test]
+fn format_quotes_only_when_needed() {
+    assert_eq!(
+        run_bytes(
+            r#"fn main() -> bytes {
+                var rows: Vec<Vec<String>> = Vec::new()
+                var row: Vec<String> = Vec::new()
+                row.push(String::from_str("plain"))
+                row.push(String::from_str("x,y"))
+                row.push(String::from_str("say \u{22}hi\u{22}"))
+                row.push(String::from_str(""))
+                rows.push(row)
+                var lone: Vec<String> = Vec::new()
+                lone.push(String::from_str(""))
+                rows.push(lone)
+                val out: String = csv::format_csv(rows)
+                __builtin_bytes_from_ptr(out.as_ptr(), out.len())
+            }"#
+        ),
+        "plain,\"x,y\",\"say \"\"hi\"\"\",\n\"\"\n"
+    );
+}
+
+#[test]
+fn file_round_trip() {
+    let path = std::env::temp_dir().join(format!("toylang_csv_{}.csv", std::process::id()));
+    let path_str = path.to_string_lossy().replace('\\', "/");
+    let src = format!(
+        r#"fn main() -> bytes {{
+            val rows: Vec<Vec<String>> = parse_csv("k,v\nline,\u{{22}}1\n2\u{{22}}\n")
+            write_csv("{path_str}", rows)
+            val back: Vec<Vec<String>> = read_csv("{path_str}")
+            val out: String = format_csv(back)
+            __builtin_bytes_from_ptr(out.as_ptr(), out.len())
+        }}"#
+    );
+    let text = run_bytes(&src);
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(written, "k,v\nline,\"1\n2\"\n");
+    assert_eq!(text, written);
+}
+
+#[test]
+fn unterminated_quote_panics() {
+    let err = test_program(
+        r#"fn main() -> u64 {
+            val rows: Vec<Vec<String>> = parse_csv("a,\u{22}b\n")
+            rows.size()
+        }"#,
+    )
+    .unwrap_err();
+    assert!(err.contains("parse_csv: unterminated quoted field"), "{err}");
+}
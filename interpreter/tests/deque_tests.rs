@@ -0,0 +1,92 @@
+// Stdlib `Deque<T>` (`core/std/collections/deque.t`) — ring-buffer
+// queue / stack. The AOT and compiler-JIT paths are covered by
+// `compiler/tests/consistency.rs`.
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+
+#[test]
+fn queue_is_first_in_first_out_across_a_grow() {
+    // Popping before the first grow moves `head` off slot 0, so the
+    // grow has to copy a wrapped buffer in order.
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val q: Deque<u64> = Deque::new()
+            q.push_back(1u64)
+            q.push_back(2u64)
+            q.push_back(3u64)
+            var order = q.pop_front() * 100u64
+            for i in 4u64 to 10u64 { q.push_back(i) }
+            order = order + q.pop_front() * 10u64 + q.pop_front()
+            order * 1000u64 + q.size()
+        }",
+        123_006,
+    );
+}
+
+#[test]
+fn stack_is_last_in_first_out() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val s: Deque<u64> = Deque::new()
+            for i in 1u64 to 6u64 { s.push_back(i) }
+            var digits = 0u64
+            while !s.is_empty() { digits = digits * 10u64 + s.pop_back() }
+            digits
+        }",
+        54321,
+    );
+}
+
+#[test]
+fn both_ends_and_random_access() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val d: Deque<u64> = Deque::new()
+            d.push_back(2u64)
+            d.push_front(1u64)
+            d.push_back(3u64)
+            d.push_front(0u64)
+            d.push_front(9u64)
+            # 9 0 1 2 3
+            if d.front() != 9u64 || d.back() != 3u64 { return 1u64 }
+            if d.get(2u64) != 1u64 { return 2u64 }
+            d.clear()
+            if !d.is_empty() { return 3u64 }
+            d.push_front(7u64)
+            d.front() * 10u64 + d.back()
+        }",
+        77,
+    );
+}
+
+#[test]
+fn copies_share_one_queue() {
+    assert_program_result_u64(
+        "fn fill(q: Deque<u64>) {
+            q.push_back(5u64)
+            q.push_back(6u64)
+        }
+        fn main() -> u64 {
+            val q: Deque<u64> = Deque::new()
+            fill(q)
+            q.size()
+        }",
+        2,
+    );
+}
+
+#[test]
+fn popping_an_empty_deque_panics() {
+    let err = test_program(
+        "fn main() -> u64 {
+            val q: Deque<u64> = Deque::new()
+            q.push_back(1u64)
+            q.pop_front()
+            q.pop_front()
+        }",
+    )
+    .unwrap_err();
+    assert!(err.contains("Deque::pop_front on an empty deque"), "{err}");
+}
@@ -0,0 +1,120 @@
+// Struct values as dict keys: structural equality by default, the
+// struct's own `eq` / `hash` when it defines them, and the type
+// checker's rejection of key types that cannot be compared.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64};
+
+#[test]
+fn struct_keys_compare_structurally() {
+    let src = r#"
+        struct Point { x: i64, y: i64 }
+        fn main() -> u64 {
+            var d: dict[Point, u64] = dict{Point { x: 1i64, y: 2i64 }: 10u64}
+            d[Point { x: 3i64, y: 4i64 }] = 20u64
+            d[Point { x: 1i64, y: 2i64 }] + d[Point { x: 3i64, y: 4i64 }]
+        }
+    "#;
+    assert_program_result_u64(src, 30);
+}
+
+#[test]
+fn struct_key_eq_method_selects_existing_key() {
+    let src = r#"
+        struct Name { id: u64, tag: u64 }
+        impl Name {
+            fn eq(&self, other: &Self) -> bool { self.id == other.id }
+        }
+        fn main() -> u64 {
+            var d = dict{Name { id: 1u64, tag: 1u64 }: 1u64}
+            d[Name { id: 1u64, tag: 2u64 }] = 5u64
+            d[Name { id: 1u64, tag: 3u64 }]
+        }
+    "#;
+    assert_program_result_u64(src, 5);
+}
+
+#[test]
+fn struct_key_hash_method_filters_candidates() {
+    // `hash` agrees with `eq`, so equal keys share a bucket and
+    // unequal ones never reach `eq`.
+    let src = r#"
+        struct Mod { n: u64 }
+        impl Mod {
+            fn eq(&self, other: &Self) -> bool { self.n % 10u64 == other.n % 10u64 }
+            fn hash(self: Self) -> u64 { self.n % 10u64 }
+        }
+        fn main() -> u64 {
+            var d = dict{Mod { n: 1u64 }: 100u64, Mod { n: 2u64 }: 200u64}
+            d[Mod { n: 12u64 }] = 7u64
+            d[Mod { n: 21u64 }] + d[Mod { n: 32u64 }]
+        }
+    "#;
+    assert_program_result_u64(src, 107);
+}
+
+#[test]
+fn dict_literal_merges_keys_equal_under_eq() {
+    let src = r#"
+        struct Name { id: u64, tag: u64 }
+        impl Name {
+            fn eq(&self, other: &Self) -> bool { self.id == other.id }
+        }
+        fn main() -> u64 {
+            val d = dict{Name { id: 1u64, tag: 1u64 }: 1u64, Name { id: 1u64, tag: 2u64 }: 2u64}
+            d[Name { id: 1u64, tag: 9u64 }]
+        }
+    "#;
+    assert_program_result_u64(src, 2);
+}
+
+#[test]
+fn closure_key_is_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            val f = fn(x: u64) -> u64 { x }
+            val d = dict{f: 1u64}
+            0u64
+        }
+    "#;
+    assert_program_fails(src);
+}
+
+#[test]
+fn struct_with_closure_field_is_rejected_as_key() {
+    let src = r#"
+        struct Handler { f: fn (u64) -> u64 }
+        fn main() -> u64 {
+            val d = dict{Handler { f: fn(x: u64) -> u64 { x } }: 1u64}
+            0u64
+        }
+    "#;
+    assert_program_fails(src);
+}
+
+#[test]
+fn struct_eq_does_not_lift_closure_field_rejection() {
+    let src = r#"
+        struct Handler { id: u64, f: fn (u64) -> u64 }
+        impl Handler {
+            fn eq(&self, other: &Self) -> bool { self.id == other.id }
+        }
+        fn main() -> u64 {
+            val d = dict{Handler { id: 1u64, f: fn(x: u64) -> u64 { x } }: 4u64}
+            0u64
+        }
+    "#;
+    assert_program_fails(src);
+}
+
+#[test]
+fn dict_key_annotation_is_checked() {
+    let src = r#"
+        fn main() -> u64 {
+            val d: dict[dict[u64, u64], u64] = dict{}
+            0u64
+        }
+    "#;
+    assert_program_fails(src);
+}
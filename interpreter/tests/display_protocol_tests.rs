@@ -0,0 +1,120 @@
+// `__str__` display protocol: user structs and enums that define
+// `fn __str__(self: Self) -> str` are rendered through it by
+// `print` / `println` and string interpolation, including when they
+// are nested inside arrays or other structs.
+
+mod common;
+
+use common::{core_modules_dir, test_program};
+use interpreter::object::Object;
+use interpreter::output::with_stdout_stderr_capture;
+use interpreter::{Capabilities, RunOptions};
+
+const POINT: &str = r#"
+    struct Point { x: i64, y: i64 }
+    impl Point {
+        fn __str__(self: Self) -> str { "({self.x}, {self.y})" }
+    }
+"#;
+
+fn str_result(src: &str) -> String {
+    match &*test_program(src).expect("program should run").borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected a string result, got {other:?}"),
+    }
+}
+
+fn run_stdout(src: &str) -> String {
+    let core = core_modules_dir();
+    let opts = RunOptions {
+        jit: false,
+        core_modules_dir: Some(core.as_path()),
+        capabilities: Capabilities::default(),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
+    };
+    let (result, stdout, _stderr) =
+        with_stdout_stderr_capture(|| interpreter::run_source(src, "test.t", &opts));
+    result.expect("program should run");
+    stdout
+}
+
+#[test]
+fn interpolation_uses_str() {
+    let src = format!("{POINT}{}", r#"
+        fn main() -> str {
+            val p = Point { x: 1i64, y: -2i64 }
+            "p = {p}"
+        }
+    "#);
+    assert_eq!(str_result(&src), "p = (1, -2)");
+}
+
+#[test]
+fn println_uses_str_for_nested_values() {
+    let src = format!("{POINT}{}", r#"
+        struct Segment { a: Point, b: Point }
+        fn main() -> u64 {
+            val p = Point { x: 1i64, y: 2i64 }
+            println(p)
+            println([p, Point { x: 3i64, y: 4i64 }])
+            println(Segment { a: p, b: p })
+            0u64
+        }
+    "#);
+    assert_eq!(
+        run_stdout(&src),
+        "(1, 2)\n[(1, 2), (3, 4)]\nSegment { a: (1, 2), b: (1, 2) }\n"
+    );
+}
+
+#[test]
+fn enum_str_with_ref_self_receiver() {
+    let src = format!("{POINT}{}", r#"
+        enum Shape { Dot(Point), Empty }
+        impl Shape {
+            fn __str__(&self) -> str {
+                match self {
+                    Shape::Dot(p) => "dot at {p}",
+                    Shape::Empty => "nothing",
+                }
+            }
+        }
+        fn main() -> u64 {
+            println(Shape::Dot(Point { x: 0i64, y: 5i64 }))
+            println(Shape::Empty)
+            0u64
+        }
+    "#);
+    assert_eq!(run_stdout(&src), "dot at (0, 5)\nnothing\n");
+}
+
+#[test]
+fn types_without_str_keep_default_rendering() {
+    let src = r#"
+        struct Plain { x: i64 }
+        fn main() -> str {
+            val p = Plain { x: 7i64 }
+            "{p}"
+        }
+    "#;
+    assert_eq!(str_result(src), "Plain { x: 7 }");
+}
+
+#[test]
+fn typecheck_rejects_wrong_str_signature() {
+    for decl in [
+        "fn __str__(self: Self) -> u64 { 0u64 }",
+        "fn __str__(self: Self, n: u64) -> str { \"x\" }",
+        "fn __str__() -> str { \"x\" }",
+    ] {
+        let src = format!(
+            "struct P {{ x: u64 }}\nimpl P {{ {decl} }}\nfn main() -> u64 {{ 0u64 }}\n"
+        );
+        let err = test_program(&src).expect_err("bad __str__ should be rejected");
+        assert!(err.contains("__str__ must be declared as"), "unexpected error for {decl}: {err}");
+    }
+}
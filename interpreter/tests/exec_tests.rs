@@ -0,0 +1,117 @@
+// `exec` builtin. Spawning is opt-in, so the positive cases go through
+// `run_source` with the `exec` capability granted and read the results back
+// from captured stdout; the default `test_program` path checks that a
+// plain run refuses to spawn.
+
+mod common;
+
+use common::{core_modules_dir, test_program};
+use interpreter::output::with_stdout_stderr_capture;
+use interpreter::{Capabilities, Capability, RunOptions};
+
+fn run_allowing_exec(src: &str) -> (Option<i32>, String) {
+    let core = core_modules_dir();
+    let opts = RunOptions {
+        jit: false,
+        core_modules_dir: Some(core.as_path()),
+        capabilities: Capabilities::default().with(Capability::Exec),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
+    };
+    let (result, stdout, _stderr) =
+        with_stdout_stderr_capture(|| interpreter::run_source(src, "test.t", &opts));
+    let outcome = result.expect("program should run");
+    (outcome.exit_code, stdout)
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_captures_status_stdout_and_stderr() {
+    let src = r#"
+        fn main() -> u64 {
+            val (status, out, err) = exec("sh", ["-c", "echo hi; echo oops 1>&2; exit 3"])
+            println("{status}|{out}|{err}")
+            0u64
+        }
+    "#;
+    let (code, stdout) = run_allowing_exec(src);
+    assert_eq!(code, Some(0));
+    assert_eq!(stdout, "3|hi\n|oops\n\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_accepts_empty_argument_list() {
+    let src = r#"
+        fn main() -> i64 {
+            val (status, out, err) = exec("true", [])
+            status + out.len() as i64 + err.len() as i64
+        }
+    "#;
+    assert_eq!(run_allowing_exec(src).0, Some(0));
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_passes_arguments_without_a_shell() {
+    // Arguments reach the child verbatim: no globbing, no splitting.
+    let src = r#"
+        fn main() -> u64 {
+            val (status, out, err) = exec("printf", ["%s,", "a b", "*"])
+            println(out)
+            0u64
+        }
+    "#;
+    assert_eq!(run_allowing_exec(src).1, "a b,*,\n");
+}
+
+#[test]
+fn exec_is_disabled_by_default() {
+    let src = r#"
+        fn main() -> i64 {
+            val (status, out, err) = exec("true", [])
+            status
+        }
+    "#;
+    let err = test_program(src).expect_err("exec should be refused without the exec capability");
+    assert!(err.contains("process spawning is disabled"), "unexpected error: {err}");
+}
+
+#[test]
+fn exec_missing_command_panics() {
+    let src = r#"
+        fn main() -> i64 {
+            val (status, out, err) = exec("toylang-no-such-command", [])
+            status
+        }
+    "#;
+    let core = core_modules_dir();
+    let opts = RunOptions {
+        jit: false,
+        core_modules_dir: Some(core.as_path()),
+        capabilities: Capabilities::default().with(Capability::Exec),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
+    };
+    let (result, _, _) = with_stdout_stderr_capture(|| interpreter::run_source(src, "test.t", &opts));
+    let err = result.expect_err("spawning a missing binary should fail");
+    assert!(err.contains("exec(\"toylang-no-such-command\")"), "unexpected error: {err}");
+}
+
+#[test]
+fn typecheck_exec_rejects_non_str_arguments() {
+    let src = r#"
+        fn main() -> i64 {
+            val (status, out, err) = exec("echo", [1u64, 2u64])
+            status
+        }
+    "#;
+    let err = test_program(src).expect_err("exec with [u64] args should be rejected");
+    assert!(err.contains("exec expects a [str] argument list"), "unexpected error: {err}");
+}
@@ -0,0 +1,191 @@
+// Generator functions — `fn f() -> Generator<T> { ... yield v ... }`.
+//
+// Calling a generator function runs nothing; each `next()` resumes the
+// body up to its next `yield` and hands back `Option::Some(v)`, then
+// `Option::None` once the body finishes. Generators plug into the
+// `for x in EXPR` iterator protocol through that `next()`.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_i64, assert_program_result_u64, test_program};
+
+#[test]
+fn generator_feeds_for_loop() {
+    assert_program_result_u64(
+        "fn count_to(n: u64) -> Generator<u64> {
+            var i = 0u64
+            while i < n {
+                yield i
+                i = i + 1u64
+            }
+        }
+        fn main() -> u64 {
+            var sum = 0u64
+            for x in count_to(5u64) { sum = sum + x }
+            sum
+        }",
+        10,
+    );
+}
+
+#[test]
+fn generator_is_lazy() {
+    // Only the first `yield` runs before the first item is taken; the
+    // infinite loop behind it is never reached.
+    assert_program_result_i64(
+        "fn naturals() -> Generator<i64> {
+            var n = 1i64
+            while true {
+                yield n
+                n = n + 1i64
+            }
+        }
+        fn main() -> i64 {
+            var gen = naturals()
+            var total = 0i64
+            for x in gen {
+                if x > 4i64 { break }
+                total = total + x
+            }
+            total
+        }",
+        10,
+    );
+}
+
+#[test]
+fn generator_next_returns_none_after_body_finishes() {
+    assert_program_result_u64(
+        "fn two() -> Generator<u64> {
+            yield 7u64
+            yield 8u64
+        }
+        fn main() -> u64 {
+            var g = two()
+            val a = g.next().unwrap_or(0u64)
+            val b = g.next().unwrap_or(0u64)
+            val c = g.next().unwrap_or(100u64)
+            val d = g.next().unwrap_or(1000u64)
+            a + b + c + d
+        }",
+        1115,
+    );
+}
+
+#[test]
+fn generator_resumes_inside_range_for_and_branches() {
+    // Evens pass through the `if` branch, odds through `else`; the
+    // range counter and the branch are both restored on resume.
+    assert_program_result_i64(
+        "fn tagged(n: i64) -> Generator<i64> {
+            for i in 0i64 to n {
+                if i % 2i64 == 0i64 {
+                    yield i
+                } else {
+                    val neg = 0i64 - i
+                    yield neg
+                }
+            }
+        }
+        fn main() -> i64 {
+            var acc = 0i64
+            for v in tagged(6i64) { acc = acc * 10i64 + v }
+            acc
+        }",
+        // 0, -1, 2, -3, 4, -5 folded left-to-right:
+        // ((((0 * 10 - 1) * 10 + 2) * 10 - 3) * 10 + 4) * 10 - 5.
+        -8265,
+    );
+}
+
+#[test]
+fn generator_can_consume_another_generator() {
+    assert_program_result_u64(
+        "fn upto(n: u64) -> Generator<u64> {
+            for i in 0u64 to n { yield i }
+        }
+        fn squares(n: u64) -> Generator<u64> {
+            for x in upto(n) {
+                yield x * x
+            }
+        }
+        fn main() -> u64 {
+            var sum = 0u64
+            for s in squares(4u64) { sum = sum + s }
+            sum
+        }",
+        14,
+    );
+}
+
+#[test]
+fn generator_state_is_per_call() {
+    assert_program_result_u64(
+        "fn upto(n: u64) -> Generator<u64> {
+            for i in 0u64 to n { yield i }
+        }
+        fn main() -> u64 {
+            var a = upto(3u64)
+            var b = upto(3u64)
+            a.next()
+            a.next()
+            val x = a.next().unwrap_or(99u64)
+            val y = b.next().unwrap_or(99u64)
+            x * 10u64 + y
+        }",
+        20,
+    );
+}
+
+#[test]
+fn bare_return_ends_generator() {
+    assert_program_result_u64(
+        "fn until_three() -> Generator<u64> {
+            var i = 0u64
+            while true {
+                if i == 3u64 { return }
+                yield i
+                i = i + 1u64
+            }
+        }
+        fn main() -> u64 {
+            var count = 0u64
+            for x in until_three() { count = count + 1u64 }
+            count
+        }",
+        3,
+    );
+}
+
+#[test]
+fn yield_outside_generator_is_rejected() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            yield 1u64
+            0u64
+        }",
+    );
+}
+
+#[test]
+fn yield_of_wrong_type_is_rejected() {
+    assert_program_fails(
+        "fn g() -> Generator<u64> {
+            yield true
+        }
+        fn main() -> u64 { 0u64 }",
+    );
+}
+
+#[test]
+fn yield_nested_in_expression_is_rejected() {
+    let err = test_program(
+        "fn g() -> Generator<u64> {
+            val x = { yield 1u64
+                2u64 }
+        }
+        fn main() -> u64 { 0u64 }",
+    )
+    .unwrap_err();
+    assert!(err.contains("cannot be nested inside an expression"), "{err}");
+}
@@ -674,6 +674,31 @@ mod basic {
             Err(e) => panic!("Program failed: {}", e),
         }
     }
+
+    #[test]
+    fn test_inferred_type_args_flow_through_method_calls() {
+        // No annotations: each literal's type args come from its
+        // fields, and `swap` / `first` see them through `Self` and `T`.
+        let source = r#"
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> {
+                fn first(self: Self) -> T { self.a }
+                fn swap(self: Self) -> Pair<T> { Pair { a: self.b, b: self.a } }
+                fn with_a(self: Self, v: T) -> Self { Pair { a: v, b: self.b } }
+            }
+
+            fn main() -> u64 {
+                val p = Pair { a: 3u64, b: 4u64 }
+                val q = Pair { a: 10i64, b: 20i64 }
+                val s = p.swap()
+                val t = q.with_a(-5i64)
+                if t.first() == -5i64 { s.first() + p.first() } else { 0u64 }
+            }
+        "#;
+
+        let result = test_program(source).expect("program should run");
+        assert_eq!(result.borrow().unwrap_uint64(), 7);
+    }
 }
 
 // =====================================================================
@@ -2069,6 +2094,26 @@ mod errors {
         let result = test_program(source);
         assert!(result.is_err(), "Expected arithmetic operation on non-numeric type error");
     }
+
+    #[test]
+    fn test_generic_method_argument_checked_against_inferred_type_args() {
+        let source = r#"
+            struct Pair<T> { a: T, b: T }
+            impl<T> Pair<T> {
+                fn with_a(self: Self, v: T) -> Self { Pair { a: v, b: self.b } }
+            }
+
+            fn main() -> u64 {
+                val p = Pair { a: 3u64, b: 4u64 }
+                val q = p.with_a(-1i64)
+                0u64
+            }
+        "#;
+
+        let result = test_program(source);
+        let err = result.expect_err("Pair<u64>.with_a must reject an i64 argument");
+        assert!(err.contains("argument type mismatch"), "unexpected error: {err}");
+    }
 }
 
 // =====================================================================
@@ -2554,3 +2599,83 @@ mod integration {
         assert!(result.is_err(), "Result::unwrap on Err should panic");
     }
 }
+
+// =====================================================================
+// Module: explicit_type_args - `f<T>(..)` / `Name<T> { .. }` call sites
+// =====================================================================
+mod explicit_type_args {
+    use crate::common::test_program;
+
+    #[test]
+    fn explicit_args_on_calls_and_struct_literals() {
+        let source = r#"
+            struct Container<T> { item: T }
+            fn identity<T>(x: T) -> T { x }
+            fn pick<A, B>(flag: A, value: B) -> B { value }
+
+            fn main() -> u64 {
+                val a = identity<u64>(5)
+                val b = identity::<i64>(-3)
+                val c = Container<u64> { item: 5 }
+                val d = Container::<u64> { item: 2 }
+                val e = pick<bool, u64>(true, 4)
+                a + (b + 3i64) as u64 + c.item + d.item + e
+            }
+        "#;
+        let result = test_program(source).expect("program should run");
+        assert_eq!(result.borrow().unwrap_uint64(), 16);
+    }
+
+    #[test]
+    fn less_than_comparison_is_not_type_args() {
+        let source = r#"
+            fn main() -> u64 {
+                val a = 3u64
+                val b = 10u64
+                if a < b { 1u64 } else { 0u64 }
+            }
+        "#;
+        let result = test_program(source).expect("program should run");
+        assert_eq!(result.borrow().unwrap_uint64(), 1);
+    }
+
+    #[test]
+    fn explicit_arg_conflicting_with_value_is_rejected() {
+        let source = r#"
+            fn identity<T>(x: T) -> T { x }
+            fn main() -> u64 {
+                identity<u64>(-3i64)
+            }
+        "#;
+        assert!(test_program(source).is_err());
+    }
+
+    #[test]
+    fn wrong_type_arg_count_is_rejected() {
+        let source = r#"
+            fn identity<T>(x: T) -> T { x }
+            fn main() -> u64 {
+                identity<u64, u64>(1u64)
+            }
+        "#;
+        let err = test_program(source).unwrap_err();
+        assert!(err.contains("expects 1 type argument(s), found 2"), "{err}");
+    }
+
+    #[test]
+    fn type_args_on_non_generic_items_are_rejected() {
+        let call = r#"
+            fn plain(x: u64) -> u64 { x }
+            fn main() -> u64 { plain<u64>(1u64) }
+        "#;
+        let err = test_program(call).unwrap_err();
+        assert!(err.contains("is not generic"), "{err}");
+
+        let literal = r#"
+            struct Point { x: u64 }
+            fn main() -> u64 { Point<u64> { x: 1u64 }.x }
+        "#;
+        let err = test_program(literal).unwrap_err();
+        assert!(err.contains("is not generic"), "{err}");
+    }
+}
@@ -0,0 +1,118 @@
+// `http_get(url) -> (u64, str)` against a one-shot server on a local
+// port. The server thread hands back the request it received so the
+// tests can check what went on the wire.
+
+mod common;
+
+use common::{assert_program_fails, test_program, test_program_with_capabilities};
+use interpreter::object::Object;
+use interpreter::{Capabilities, Capability};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// Serve `response` to the first connection; the handle yields the
+/// request head.
+fn serve_once(response: &'static [u8]) -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 512];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = conn.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        conn.write_all(response).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (port, handle)
+}
+
+fn get(url: &str) -> Result<(u64, String), String> {
+    let src = format!(
+        r#"fn main() -> (u64, str) {{
+            http_get("{url}")
+        }}"#
+    );
+    let caps = Capabilities::default().with(Capability::Net);
+    let result = test_program_with_capabilities(&src, caps)?;
+    let obj = result.borrow();
+    let Object::Tuple(items) = &*obj else { panic!("expected a tuple, got {obj:?}") };
+    let status = match &*items[0].borrow() {
+        Object::UInt64(n) => *n,
+        other => panic!("expected u64 status, got {other:?}"),
+    };
+    let body = match &*items[1].borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected str body, got {other:?}"),
+    };
+    Ok((status, body))
+}
+
+#[test]
+fn returns_status_and_body() {
+    let (port, server) =
+        serve_once(b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nhello");
+    let (status, body) = get(&format!("http://127.0.0.1:{port}/greeting?lang=en")).unwrap();
+    assert_eq!((status, body.as_str()), (200, "hello"));
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /greeting?lang=en HTTP/1.1\r\n"), "{request}");
+    assert!(request.contains(&format!("\r\nHost: 127.0.0.1:{port}\r\n")), "{request}");
+    assert!(request.contains("\r\nConnection: close\r\n"), "{request}");
+}
+
+#[test]
+fn decodes_chunked_bodies() {
+    let (port, server) = serve_once(
+        b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nfetc\r\n7;ext=1\r\nh-and-p\r\n4\r\narse\r\n0\r\n\r\n",
+    );
+    let (status, body) = get(&format!("http://127.0.0.1:{port}")).unwrap();
+    server.join().unwrap();
+    assert_eq!((status, body.as_str()), (200, "fetch-and-parse"));
+}
+
+#[test]
+fn error_statuses_are_returned_not_raised() {
+    let (port, server) = serve_once(b"HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nnot found");
+    let (status, body) = get(&format!("http://127.0.0.1:{port}/missing")).unwrap();
+    server.join().unwrap();
+    assert_eq!((status, body.as_str()), (404, "not found"));
+}
+
+#[test]
+fn result_destructures_in_toylang() {
+    let (port, server) = serve_once(b"HTTP/1.1 201 Created\r\nContent-Length: 2\r\n\r\nok");
+    let src = format!(
+        r#"fn main() -> u64 {{
+            val (status, body) = http_get("http://127.0.0.1:{port}/")
+            status * 10u64 + body.len()
+        }}"#
+    );
+    let result = test_program_with_capabilities(&src, Capabilities::default().with(Capability::Net)).unwrap();
+    server.join().unwrap();
+    assert!(matches!(&*result.borrow(), Object::UInt64(2012)));
+}
+
+#[test]
+fn needs_the_net_capability() {
+    let err = test_program(r#"fn main() -> u64 { http_get("http://127.0.0.1:9/").0 }"#).unwrap_err();
+    assert!(err.contains("http_get: network access is disabled (run with --allow-net)"), "{err}");
+}
+
+#[test]
+fn unsupported_urls_panic() {
+    let err = get("https://example.com/").unwrap_err();
+    assert!(err.contains("https is not supported"), "{err}");
+    let err = get("ftp://example.com/").unwrap_err();
+    assert!(err.contains("unsupported URL scheme `ftp`"), "{err}");
+}
+
+#[test]
+fn url_must_be_a_string() {
+    assert_program_fails("fn main() -> u64 { http_get(80u64).0 }");
+}
@@ -0,0 +1,127 @@
+// `parse_u64` / `parse_i64` / `to_hex` / `format_radix`. Bad input to
+// the parsers is an `Option::None`, never a panic.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_i64, assert_program_result_u64, test_program};
+
+#[test]
+fn parse_u64_reads_decimal_digits() {
+    assert_program_result_u64(
+        r#"
+        fn main() -> u64 {
+            match parse_u64("18446744073709551615") {
+                Option::Some(n) => n,
+                Option::None => 0u64,
+            }
+        }
+        "#,
+        u64::MAX,
+    );
+}
+
+#[test]
+fn parse_i64_accepts_a_sign() {
+    assert_program_result_i64(
+        r#"
+        fn get(s: str) -> i64 {
+            match parse_i64(s) {
+                Option::Some(n) => n,
+                Option::None => 1000i64,
+            }
+        }
+        fn main() -> i64 {
+            get("-42") + get("+7")
+        }
+        "#,
+        -35,
+    );
+}
+
+#[test]
+fn malformed_or_out_of_range_input_is_none() {
+    // Each rejected input adds one; all eight must fail to parse.
+    assert_program_result_u64(
+        r#"
+        fn bad_u(s: str) -> u64 {
+            match parse_u64(s) {
+                Option::Some(n) => 0u64,
+                Option::None => 1u64,
+            }
+        }
+        fn bad_i(s: str) -> u64 {
+            match parse_i64(s) {
+                Option::Some(n) => 0u64,
+                Option::None => 1u64,
+            }
+        }
+        fn main() -> u64 {
+            bad_u("") + bad_u(" 1") + bad_u("12x") + bad_u("-1")
+                + bad_u("18446744073709551616")
+                + bad_i("9223372036854775808") + bad_i("0x10") + bad_i("-")
+        }
+        "#,
+        8,
+    );
+}
+
+#[test]
+fn to_hex_is_lowercase_without_prefix() {
+    assert_program_result_u64(
+        r#"
+        fn main() -> u64 {
+            var ok = 0u64
+            if to_hex(255) == "ff" { ok = ok + 1u64 }
+            if to_hex(0u64) == "0" { ok = ok + 1u64 }
+            if to_hex(18446744073709551615u64) == "ffffffffffffffff" { ok = ok + 1u64 }
+            ok
+        }
+        "#,
+        3,
+    );
+}
+
+#[test]
+fn format_radix_handles_signs_and_bases() {
+    assert_program_result_u64(
+        r#"
+        fn main() -> u64 {
+            var ok = 0u64
+            if format_radix(10, 2) == "1010" { ok = ok + 1u64 }
+            if format_radix(-10i64, 2u64) == "-1010" { ok = ok + 1u64 }
+            if format_radix(35u64, 36u64) == "z" { ok = ok + 1u64 }
+            if format_radix(-9223372036854775807i64 - 1i64, 16u64) == "-8000000000000000" { ok = ok + 1u64 }
+            if format_radix(0i64, 7u64) == "0" { ok = ok + 1u64 }
+            ok
+        }
+        "#,
+        5,
+    );
+}
+
+#[test]
+fn format_radix_rejects_bad_bases() {
+    let err = test_program(
+        r#"
+        fn main() -> str {
+            format_radix(10u64, 37u64)
+        }
+        "#,
+    )
+    .unwrap_err();
+    assert!(err.contains("base must be between 2 and 36, got 37"), "{err}");
+}
+
+#[test]
+fn parsers_require_a_str_argument() {
+    assert_program_fails(
+        r#"
+        fn main() -> u64 {
+            match parse_u64(5u64) {
+                Option::Some(n) => n,
+                Option::None => 0u64,
+            }
+        }
+        "#,
+    );
+}
@@ -59,6 +59,12 @@ fn run(source_path: &str, jit: bool, verbose: bool) -> Run {
     let opts = interpreter::RunOptions {
         jit,
         core_modules_dir: Some(core.as_path()),
+        capabilities: Default::default(),
+        defines: None,
+        trace_alloc: false,
+        deterministic: None,
+        coverage: None,
+        log: None,
     };
     let (result, stdout, stderr) = interpreter::output::with_stdout_stderr_capture(|| {
         interpreter::jit::with_jit_verbose_override(verbose, || {
@@ -582,6 +588,28 @@ fn extern_math_jit_compiles_main() {
     );
 }
 
+#[test]
+fn bit_ops_jit_matches_interpreter() {
+    // `core/std/bits.t` `BitOps` methods forward to `__extern_*_u64`
+    // helpers; the JIT lowers them to popcnt / clz / ctz / rotl /
+    // rotr and must agree with the interpreter registry.
+    assert_match("example/bit_ops_jit.t");
+}
+
+#[cfg(feature = "jit")]
+#[test]
+fn bit_ops_jit_compiles_trait_methods() {
+    let r = run("example/bit_ops_jit.t", true, true);
+    assert_eq!(r.code, 243, "stderr: {}", r.stderr);
+    assert!(
+        r.stderr.contains("JIT compiled:")
+            && r.stderr.contains("u64__count_ones")
+            && r.stderr.contains("i64__rotate_right"),
+        "expected JIT compile log to include the BitOps impl methods, got stderr: {}",
+        r.stderr
+    );
+}
+
 #[test]
 fn float64_example_matches_between_modes() {
     assert_match("example/jit_float64.t");
@@ -0,0 +1,136 @@
+// `json_parse` / `json_stringify` builtins. JSON documents are fed
+// through stdin (`output::with_input` + `read_all()`) because string
+// literals can't carry a bare `"` or `{` yet.
+
+mod common;
+
+use common::test_program;
+use interpreter::object::Object;
+use interpreter::output::with_input;
+
+fn run_with_input(input: &str, src: &str) -> Result<Object, String> {
+    with_input(input, || test_program(src)).map(|r| r.borrow().clone())
+}
+
+fn str_result(input: &str, src: &str) -> String {
+    match run_with_input(input, src).expect("program should run") {
+        Object::String(ref s) => s.clone(),
+        other => panic!("expected a string result, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_nested_document_and_index() {
+    let src = r#"
+        fn main() -> i64 {
+            val doc = json_parse(read_all())
+            val id: i64 = doc["id"]
+            val second: i64 = doc["items"][1]["qty"]
+            val name: str = doc["items"][0]["name"]
+            val ok: bool = doc["ok"]
+            if ok && doc["missing_is_null"].is_null() { id * 100i64 + second + name.len() as i64 } else { 0i64 }
+        }
+    "#;
+    let input = r#"{
+        "id": 7,
+        "ok": true,
+        "missing_is_null": null,
+        "items": [ {"name": "apple", "qty": 3}, {"name": "kiwi", "qty": 40} ]
+    }"#;
+    assert_eq!(run_with_input(input, src).unwrap(), Object::Int64(745));
+}
+
+#[test]
+fn stringify_round_trip_sorts_keys() {
+    let src = r#"
+        fn main() -> str {
+            json_stringify(json_parse(read_all()))
+        }
+    "#;
+    let input = r#" { "b": [1, 2.5, -3e2, false], "a": {"z": null, "y": ""} } "#;
+    assert_eq!(str_result(input, src), r#"{"a":{"y":"","z":null},"b":[1,2.5,-300.0,false]}"#);
+}
+
+#[test]
+fn string_escapes_round_trip() {
+    let src = r#"
+        fn main() -> str {
+            json_stringify(json_parse(read_all()))
+        }
+    "#;
+    let input = r#"["q\"b\\s\/\n\t\u00e9\ud83d\ude00\u0001"]"#;
+    assert_eq!(str_result(input, src), "[\"q\\\"b\\\\s/\\n\\té😀\\u0001\"]");
+}
+
+#[test]
+fn numbers_keep_integer_and_float_kinds() {
+    let src = r#"
+        fn main() -> str {
+            val v = json_parse(read_all())
+            val big: f64 = v[1]
+            val nan = 0.0f64 / 0.0f64
+            json_stringify((v[0], big > 1000000.0f64, v[2], 2.0f64, nan, 7u64))
+        }
+    "#;
+    assert_eq!(str_result("[-12, 12345678901234567890, 0.5]", src), "[-12,true,0.5,2.0,null,7]");
+}
+
+#[test]
+fn stringify_struct_uses_declaration_order() {
+    let src = r#"
+        struct Dim { w: u64, h: u64 }
+        struct Item { name: str, qty: i64, size: Dim, sold: bool }
+
+        fn main() -> str {
+            val it = Item { sold: false, size: Dim { h: 3u64, w: 4u64 }, qty: 2i64, name: "pen" }
+            json_stringify(it)
+        }
+    "#;
+    assert_eq!(
+        str_result("", src),
+        r#"{"name":"pen","qty":2,"size":{"w":4,"h":3},"sold":false}"#
+    );
+}
+
+#[test]
+fn malformed_input_panics_with_position() {
+    let src = r#"
+        fn main() -> u64 {
+            val v = json_parse(read_all())
+            0u64
+        }
+    "#;
+    for (input, msg) in [
+        ("{\"a\": [1,}", "unexpected character at byte 9"),
+        ("[1, 2", "expected ',' or ']' at byte 5"),
+        ("01", "trailing characters after JSON value at byte 1"),
+        ("\"\\ud800\"", "unpaired surrogate"),
+        ("", "unexpected end of input at byte 0"),
+    ] {
+        let err = run_with_input(input, src).expect_err("malformed JSON should fail");
+        assert!(err.contains(&format!("json_parse: {msg}")), "input {input:?}: unexpected error: {err}");
+    }
+}
+
+#[test]
+fn unencodable_value_panics() {
+    let src = r#"
+        fn main() -> str {
+            json_stringify(b"raw")
+        }
+    "#;
+    let err = test_program(src).expect_err("bytes can't be encoded");
+    assert!(err.contains("json_stringify: cannot encode bytes as JSON"), "unexpected error: {err}");
+}
+
+#[test]
+fn typecheck_json_parse_requires_str() {
+    let src = r#"
+        fn main() -> u64 {
+            val v = json_parse(1u64)
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("json_parse(u64) should be rejected");
+    assert!(err.contains("json_parse expects String argument"), "unexpected error: {err}");
+}
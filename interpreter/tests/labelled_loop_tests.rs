@@ -131,6 +131,29 @@ fn labelled_break_at_outermost_label() {
     assert_program_result_u64(src, 42u64);
 }
 
+#[test]
+fn labelled_loop_inside_closure_body() {
+    let src = r#"
+        fn main() -> u64 {
+            val first_multiple = fn(n: u64) -> u64 {
+                var found = 0u64
+                @scan: for i in 1u64 to 100u64 {
+                    for j in 0u64 to 3u64 {
+                        if i * j == n * 2u64 {
+                            found = i
+                            break @scan
+                        }
+                    }
+                }
+                found
+            }
+            first_multiple(21u64)
+        }
+    "#;
+    // i * j == 42 first holds at i=21, j=2.
+    assert_program_result_u64(src, 21u64);
+}
+
 // ---------------------------------------------------------------------
 // Validation: type-checker errors for unlabelled `break` outside any
 // loop and labelled `break` / `continue` referencing an undefined name.
@@ -161,3 +184,41 @@ fn typecheck_undefined_label_rejected() {
     let err = test_program(src).expect_err("expected type-check failure for undefined label");
     assert!(err.contains("undefined loop label") && err.contains("@missing"), "actual: {err}");
 }
+
+#[test]
+fn typecheck_label_not_visible_inside_closure() {
+    // A closure body is its own function: the enclosing loop's label
+    // is out of scope there, like it would be in a separate `fn`.
+    let src = r#"
+        fn main() -> u64 {
+            var total = 0u64
+            @outer: for i in 0u64 to 3u64 {
+                val f = fn(x: u64) -> u64 {
+                    if x == 1u64 { break @outer }
+                    x
+                }
+                total = total + f(i)
+            }
+            total
+        }
+    "#;
+    let err = test_program(src).expect_err("expected type-check failure for label across closure");
+    assert!(err.contains("undefined loop label") && err.contains("@outer"), "actual: {err}");
+}
+
+#[test]
+fn typecheck_bare_break_in_closure_inside_loop_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            for i in 0u64 to 3u64 {
+                val f = fn(x: u64) -> u64 {
+                    continue
+                    x
+                }
+            }
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("expected type-check failure for continue in closure");
+    assert!(err.contains("outside") && err.contains("loop"), "actual: {err}");
+}
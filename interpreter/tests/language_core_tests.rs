@@ -87,6 +87,58 @@ mod basic_execution {
         assert_eq!(res.unwrap().borrow().unwrap_uint64(), 3, "Expected 1+2=3");
     }
 
+    #[test]
+    fn test_snapshot_restore_rewinds_bindings_and_keeps_aliasing() {
+        use interpreter::object::Object;
+        use interpreter::value::Value;
+        use std::{cell::RefCell, rc::Rc};
+
+        let stmt_pool = StmtPool::new();
+        let expr_pool = ExprPool::new();
+        let mut interner = DefaultStringInterner::new();
+        let (a, b, n) = (interner.get_or_intern("a"), interner.get_or_intern("b"), interner.get_or_intern("n"));
+        let mut ctx = EvaluationContext::new(&stmt_pool, &expr_pool, &mut interner, HashMap::new());
+
+        let one = Rc::new(RefCell::new(Object::UInt64(1)));
+        let array = Rc::new(RefCell::new(Object::Array(Box::new(vec![one]))));
+        ctx.environment.set_val_mutable(a, Value::Heap(array.clone()));
+        ctx.environment.set_val_mutable(b, Value::Heap(array.clone()));
+        ctx.environment.set_val_mutable(n, Value::UInt64(1));
+        let saved = ctx.snapshot();
+
+        let push_two = |value: &Value| {
+            let Value::Heap(rc) = value else { panic!("expected an array") };
+            if let Object::Array(items) = &mut *rc.borrow_mut() {
+                items.push(Rc::new(RefCell::new(Object::UInt64(2))));
+            }
+        };
+        let array_len = |value: &Value| match value {
+            Value::Heap(rc) => match &*rc.borrow() {
+                Object::Array(items) => items.len(),
+                other => panic!("expected an array, got {other:?}"),
+            },
+            other => panic!("expected an array, got {other:?}"),
+        };
+        push_two(&ctx.environment.get_val(a).unwrap());
+        ctx.environment.set_val_mutable(n, Value::UInt64(2));
+
+        for _ in 0..2 {
+            ctx.restore(&saved);
+            assert!(matches!(ctx.environment.get_val(n), Some(Value::UInt64(1))));
+            let (restored_a, restored_b) = (ctx.environment.get_val(a).unwrap(), ctx.environment.get_val(b).unwrap());
+            assert_eq!(array_len(&restored_a), 1);
+            match (&restored_a, &restored_b) {
+                (Value::Heap(x), Value::Heap(y)) => {
+                    assert!(Rc::ptr_eq(x, y), "a and b should still share one array");
+                    assert!(!Rc::ptr_eq(x, &array), "the restored array is a copy");
+                }
+                _ => panic!("expected arrays"),
+            }
+            // Mutating after a restore must not reach the snapshot.
+            push_two(&restored_a);
+        }
+    }
+
     #[test]
     fn test_i64_basic() {
         common::assert_program_result_i64(r"
@@ -582,6 +634,27 @@ mod basic_execution {
         assert!(err.to_string().contains("panic: fatal"));
     }
 
+    #[test]
+    fn test_panic_reports_stack_backtrace() {
+        // Frames are listed innermost first and include methods.
+        let source = r#"
+        struct Acct { bal: u64 }
+        impl Acct {
+            fn withdraw(self: Self, n: u64) -> u64 {
+                if n > self.bal { panic("insufficient funds") }
+                self.bal - n
+            }
+        }
+        fn helper(a: Acct) -> u64 { a.withdraw(10u64) }
+        fn main() -> u64 { helper(Acct { bal: 5u64 }) }
+        "#;
+        let err = common::test_program(source).expect_err("expected panic");
+        assert!(
+            err.contains("panic: insufficient funds\nstack backtrace:\n  0: withdraw\n  1: helper\n  2: main"),
+            "unexpected panic output: {err}"
+        );
+    }
+
     // ----- assert builtin -----
 
     #[test]
@@ -711,6 +784,7 @@ mod basic_execution {
 mod variables {
     //! Variable declaration and type inference tests
 
+    use super::common;
     use super::helpers::execute_test_program;
 
     #[test]
@@ -977,6 +1051,42 @@ mod variables {
         assert!(error.contains("error") || error.contains("Error"),
                 "Error message should contain 'error': {}", error);
     }
+
+    #[test]
+    fn test_assignment_through_nested_place_paths() {
+        common::assert_program_result_u64(r"
+            struct Inner { c: u64 }
+            struct Outer { b: Inner }
+            fn main() -> u64 {
+                var a = Outer { b: Inner { c: 1u64 } }
+                a.b.c = 5u64
+                var arr = [Inner { c: 1u64 }, Inner { c: 2u64 }]
+                arr[1u64].c = 7u64
+                a.b.c * 10u64 + arr[1u64].c
+            }
+        ", 57);
+    }
+
+    #[test]
+    fn test_assignment_through_val_binding_is_rejected() {
+        for body in [
+            "val a = Outer { b: Inner { c: 1u64 } }\n a.b.c = 5u64",
+            "val arr = [Inner { c: 1u64 }]\n arr[0u64].c = 5u64",
+            "val arr = [1u64, 2u64]\n arr[0u64] = 9u64",
+            "val arr = [1u64, 2u64]\n arr[0u64..2u64] = [8u64, 9u64]",
+        ] {
+            let source = format!("
+                struct Inner {{ c: u64 }}
+                struct Outer {{ b: Inner }}
+                fn main() -> u64 {{
+                    {body}
+                    0u64
+                }}
+            ");
+            let err = common::test_program(&source).expect_err("expected error");
+            assert!(err.contains("cannot assign through") && err.contains("not declared `var`"), "got: {err}");
+        }
+    }
 }
 
 mod control_flow {
@@ -985,6 +1095,54 @@ mod control_flow {
     use super::common;
     use super::helpers::execute_test_program;
 
+    // `&&` / `||` must not evaluate the rhs once the lhs decides the
+    // result. `Counter::bump` records every evaluation so a non-lazy
+    // rhs shows up in the final count.
+    const COUNTER: &str = r"
+        struct Counter { n: u64 }
+        impl Counter {
+            fn bump(&mut self, result: bool) -> bool {
+                self.n = self.n + 1u64
+                result
+            }
+        }
+    ";
+
+    #[test]
+    fn test_logical_and_skips_rhs_when_lhs_false() {
+        common::assert_program_result_u64(&format!("{COUNTER}
+        fn main() -> u64 {{
+            var c = Counter {{ n: 0u64 }}
+            val r = false && c.bump(true)
+            if r {{ 100u64 }} else {{ c.n }}
+        }}
+        "), 0);
+    }
+
+    #[test]
+    fn test_logical_or_skips_rhs_when_lhs_true() {
+        common::assert_program_result_u64(&format!("{COUNTER}
+        fn main() -> u64 {{
+            var c = Counter {{ n: 0u64 }}
+            val r = true || c.bump(false)
+            if r {{ c.n }} else {{ 100u64 }}
+        }}
+        "), 0);
+    }
+
+    #[test]
+    fn test_logical_rhs_evaluated_when_needed() {
+        common::assert_program_result_u64(&format!("{COUNTER}
+        fn main() -> u64 {{
+            var c = Counter {{ n: 0u64 }}
+            val a = true && c.bump(true)
+            val b = false || c.bump(false)
+            val d = c.bump(false) && c.bump(true)
+            if a && !b && !d {{ c.n }} else {{ 100u64 }}
+        }}
+        "), 3);
+    }
+
     #[test]
     fn test_simple_for_loop() {
         common::assert_program_result_u64(r"
@@ -1030,6 +1188,112 @@ mod control_flow {
         ", 7);
     }
 
+    #[test]
+    fn test_for_loop_with_step() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var sum = 0u64
+            for i in 0u64..10u64 step 3 {
+                sum = sum + i
+            }
+            sum
+        }
+        ", 18);
+    }
+
+    #[test]
+    fn test_for_loop_with_negative_step_counts_down() {
+        common::assert_program_result_i64(r"
+        fn main() -> i64 {
+            var sum = 0i64
+            for i in 10i64..0i64 step -3i64 {
+                sum = sum * 100i64 + i
+            }
+            sum
+        }
+        ", 10070401);
+    }
+
+    #[test]
+    fn test_for_loop_with_step_continue_and_label() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var count = 0u64
+            @outer: for i in 0u64..20u64 step 2 {
+                if i == 4u64 {
+                    continue
+                }
+                for j in 0u64..10u64 step 5 {
+                    if i == 10u64 {
+                        break @outer
+                    }
+                    count = count + 1u64
+                }
+            }
+            count
+        }
+        ", 8);
+    }
+
+    #[test]
+    fn test_for_loop_step_stops_near_type_max() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            var sum = 0u64
+            for i in 250u8..255u8 step 10u8 {
+                sum = sum + i as u64
+            }
+            for i in 0u8..255u8 step 100u8 {
+                sum = sum * 1000u64 + i as u64
+            }
+            sum
+        }
+        ", 250000100200);
+    }
+
+    #[test]
+    fn test_for_loop_negative_step_stops_near_type_min() {
+        common::assert_program_result_i64(r"
+        fn main() -> i64 {
+            var sum = 0i64
+            for i in 10i8..-128i8 step -100i8 {
+                sum = sum * 1000i64 + i as i64
+            }
+            for i in -100i8..100i8 step 60i8 {
+                sum = sum * 1000i64 + i as i64
+            }
+            sum
+        }
+        ", 9909899960020080);
+    }
+
+    #[test]
+    fn test_for_loop_step_must_be_nonzero_literal() {
+        common::assert_program_fails(r"
+        fn main() -> u64 {
+            for i in 0u64..10u64 step 0 { }
+            0u64
+        }
+        ");
+        common::assert_program_fails(r"
+        fn main() -> u64 {
+            val s = 2u64
+            for i in 0u64..10u64 step s { }
+            0u64
+        }
+        ");
+    }
+
+    #[test]
+    fn test_step_is_still_an_identifier() {
+        common::assert_program_result_u64(r"
+        fn main() -> u64 {
+            val step = 4u64
+            step
+        }
+        ", 4);
+    }
+
     #[test]
     fn test_while_loop_basic() {
         common::assert_program_result_u64(r"
@@ -1381,6 +1645,7 @@ mod function_calls {
     //! Function definition and call tests
 
     use super::common;
+    use super::helpers::execute_test_program;
 
     #[test]
     fn test_simple_function_call() {
@@ -1445,6 +1710,94 @@ mod function_calls {
         }
         ", 19);
     }
+
+    #[test]
+    fn test_items_used_before_their_declaration() {
+        common::assert_program_result_u64(r"
+        impl Outer {
+            fn total(&self) -> u64 {
+                self.inner.v + weight(Kind::Heavy)
+            }
+        }
+
+        fn main() -> u64 {
+            val o = Outer { inner: Inner { v: 5u64 } }
+            o.total() + later(1u64)
+        }
+
+        fn later(x: u64) -> u64 {
+            x + 40u64
+        }
+
+        fn weight(k: Kind) -> u64 {
+            match k {
+                Kind::Light => 1u64,
+                Kind::Heavy => 2u64,
+            }
+        }
+
+        struct Outer {
+            inner: Inner
+        }
+
+        struct Inner {
+            v: u64
+        }
+
+        enum Kind {
+            Light,
+            Heavy,
+        }
+        ", 48);
+    }
+
+    #[test]
+    fn test_duplicate_function_is_rejected() {
+        let source = "fn f() -> u64 {\n    1u64\n}\n\nfn f() -> u64 {\n    2u64\n}\n\nfn main() -> u64 {\n    f()\n}\n";
+        let error = execute_test_program(source).unwrap_err();
+        assert!(error.contains("function 'f' is already defined at line 1, column 1"), "{error}");
+    }
+
+    #[test]
+    fn test_overloads_are_rejected() {
+        let functions = r"
+        fn area(side: u64) -> u64 {
+            side * side
+        }
+
+        fn area(w: u64, h: u64) -> u64 {
+            w * h
+        }
+
+        fn main() -> u64 {
+            area(2u64) + area(2u64, 3u64)
+        }
+        ";
+        let error = execute_test_program(functions).unwrap_err();
+        assert!(error.contains("function 'area' is already defined at line 2, column 9 (functions cannot be overloaded)"), "{error}");
+
+        let methods = r"
+        struct Rect {
+            w: u64,
+            h: u64
+        }
+
+        impl Rect {
+            fn scale(&self, k: u64) -> u64 {
+                self.w * k
+            }
+            fn scale(&self, kw: u64, kh: u64) -> u64 {
+                self.w * kw + self.h * kh
+            }
+        }
+
+        fn main() -> u64 {
+            Rect { w: 1u64, h: 2u64 }.scale(3u64)
+        }
+        ";
+        let error = execute_test_program(methods).unwrap_err();
+        assert!(error.contains("method 'Rect::scale' is already defined at line 8, column 13 (methods cannot be overloaded)"), "{error}");
+    }
 }
 
 mod heap_operations {
@@ -0,0 +1,74 @@
+// `log_debug` / `log_info` / `log_warn` / `log_error`. The script
+// always logs everything; the `LogConfig` handed in through
+// `RunOptions::log` decides what comes out and where.
+
+mod common;
+
+use common::assert_program_fails;
+use interpreter::output::with_stdout_stderr_capture;
+use interpreter::{LogConfig, LogLevel, LogTarget, RunOptions};
+
+const SRC: &str = r#"
+    fn main() -> u64 {
+        val n = 3u64
+        log_debug("cache size {n}")
+        log_info("started")
+        log_warn("slow request")
+        log_error("gave up")
+        0u64
+    }
+"#;
+
+/// Run `SRC` under `log`, returning (stdout, stderr).
+fn run(log: Option<&LogConfig>) -> (String, String) {
+    let core = common::core_modules_dir();
+    let opts = RunOptions { core_modules_dir: Some(core.as_path()), log, ..RunOptions::default() };
+    let (result, stdout, stderr) = with_stdout_stderr_capture(|| interpreter::run_source(SRC, "test.t", &opts));
+    result.expect("program should run");
+    (stdout, stderr)
+}
+
+#[test]
+fn default_writes_info_and_above_to_stderr() {
+    let (stdout, stderr) = run(None);
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "[INFO] started\n[WARN] slow request\n[ERROR] gave up\n");
+}
+
+#[test]
+fn min_level_filters() {
+    let debug = LogConfig { min_level: LogLevel::Debug, ..LogConfig::default() };
+    assert!(run(Some(&debug)).1.starts_with("[DEBUG] cache size 3\n[INFO] started\n"));
+    let warn = LogConfig { min_level: LogLevel::Warn, ..LogConfig::default() };
+    assert_eq!(run(Some(&warn)).1, "[WARN] slow request\n[ERROR] gave up\n");
+    assert_eq!(run(Some(&LogConfig::off())), (String::new(), String::new()));
+}
+
+#[test]
+fn host_can_redirect_to_stdout_or_a_file() {
+    let to_stdout = LogConfig { min_level: LogLevel::Error, target: LogTarget::Stdout };
+    assert_eq!(run(Some(&to_stdout)), ("[ERROR] gave up\n".to_string(), String::new()));
+
+    let path = std::env::temp_dir().join(format!("toylang_log_{}.log", std::process::id()));
+    std::fs::write(&path, "earlier line\n").unwrap();
+    let to_file = LogConfig { min_level: LogLevel::Warn, target: LogTarget::File(path.clone()) };
+    let (stdout, stderr) = run(Some(&to_file));
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!((stdout.as_str(), stderr.as_str()), ("", ""));
+    assert_eq!(written, "earlier line\n[WARN] slow request\n[ERROR] gave up\n");
+}
+
+#[test]
+fn level_names_round_trip() {
+    for level in LogLevel::ALL {
+        assert_eq!(LogLevel::from_name(level.name()), Some(level));
+    }
+    assert_eq!(LogLevel::from_name("WARN"), Some(LogLevel::Warn));
+    assert_eq!(LogLevel::from_name("verbose"), None);
+}
+
+#[test]
+fn message_must_be_a_string() {
+    assert_program_fails("fn main() -> u64 {\n    log_info(42u64)\n    0u64\n}");
+}
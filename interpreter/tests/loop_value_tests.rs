@@ -0,0 +1,144 @@
+// `loop` as an expression (`break value`).
+//
+// `break value` / `break @label value` leaves a `loop { ... }` and
+// makes `value` the loop's result, so `val x = loop { ... }` and a
+// function body ending in `loop { ... }` both work. Only `loop`
+// (`while true`) accepts a value — `while cond` / `for` can finish
+// without hitting a `break`, so there would be nothing to yield. The
+// type checker requires every value break targeting one loop to agree
+// on a type (see `typecheck_*_rejected` cases at the bottom).
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+
+#[test]
+fn loop_break_value_binds_val() {
+    let src = r#"
+        fn main() -> u64 {
+            var i = 0u64
+            val found = loop {
+                i = i + 1u64
+                if i * i > 50u64 {
+                    break i
+                }
+            }
+            found
+        }
+    "#;
+    assert_program_result_u64(src, 8);
+}
+
+#[test]
+fn loop_as_function_tail_returns_break_value() {
+    let src = r#"
+        fn collatz_steps(start: u64) -> u64 {
+            var n = start
+            var steps = 0u64
+            loop {
+                if n == 1u64 { break steps }
+                if n % 2u64 == 0u64 { n = n / 2u64 } else { n = n * 3u64 + 1u64 }
+                steps = steps + 1u64
+            }
+        }
+
+        fn main() -> u64 { collatz_steps(6u64) }
+    "#;
+    assert_program_result_u64(src, 8);
+}
+
+#[test]
+fn labelled_break_value_exits_outer_loop() {
+    let src = r#"
+        fn main() -> u64 {
+            var n = 0u64
+            val tens = @outer: loop {
+                n = n + 1u64
+                for j in 0u64..10u64 {
+                    if n * j == 12u64 { break @outer n * 10u64 + j }
+                }
+            }
+            tens
+        }
+    "#;
+    // First hit: n=2, j=6.
+    assert_program_result_u64(src, 26);
+}
+
+#[test]
+fn break_value_can_be_a_struct() {
+    let src = r#"
+        struct Pair { a: u64, b: u64 }
+
+        fn main() -> u64 {
+            var i = 0u64
+            val p = loop {
+                i = i + 1u64
+                if i == 4u64 { break Pair { a: i, b: i * 2u64 } }
+            }
+            p.a + p.b
+        }
+    "#;
+    assert_program_result_u64(src, 12);
+}
+
+#[test]
+fn bare_break_still_yields_unit_loop() {
+    let src = r#"
+        fn main() -> u64 {
+            var i = 0u64
+            loop {
+                i = i + 1u64
+                if i == 5u64 { break }
+            }
+            i
+        }
+    "#;
+    assert_program_result_u64(src, 5);
+}
+
+// ---------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------
+
+#[test]
+fn typecheck_break_value_in_while_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            var i = 0u64
+            while i < 3u64 { break 1u64 }
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("expected type-check failure for break value in while");
+    assert!(err.contains("only allowed inside `loop`"), "actual: {err}");
+}
+
+#[test]
+fn typecheck_break_value_in_for_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            for i in 0u64..3u64 { break i }
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("expected type-check failure for break value in for");
+    assert!(err.contains("only allowed inside `loop`"), "actual: {err}");
+}
+
+#[test]
+fn typecheck_break_value_type_mismatch_rejected() {
+    let src = r#"
+        fn main() -> u64 {
+            var i = 0u64
+            val x = loop {
+                i = i + 1u64
+                if i == 2u64 { break 1u64 }
+                if i == 3u64 { break true }
+            }
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("expected type-check failure for disagreeing break values");
+    assert!(err.contains("break value"), "actual: {err}");
+}
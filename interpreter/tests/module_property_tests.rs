@@ -187,6 +187,40 @@ fn test_module_qualified_call_executes() {
     assert_eq!(result.unwrap().borrow().unwrap_uint64(), 30);
 }
 
+#[test]
+fn test_math_gcd_and_clamp() {
+    // `math::gcd` / `math::clamp_*` are plain toylang bodies in
+    // `core/std/math.t`; an explicit `import std.math` is accepted
+    // alongside the auto-load. gcd(48, 18) = 6, gcd(0, 0) = 0,
+    // clamp_i64(-5, 0, 10) = 0, clamp_u64(50, 0, 10) = 10,
+    // clamp_f64(2.5, 0, 1) = 1.0 → 6 + 0 + 0 + 10 + 1 = 17.
+    let source = r"
+        import std.math
+
+        fn main() -> u64 {
+            val g = math::gcd(48u64, 18u64) + math::gcd(0u64, 0u64)
+            val c = math::clamp_i64(-5i64, 0i64, 10i64) as u64 + math::clamp_u64(50u64, 0u64, 10u64)
+            g + c + math::clamp_f64(2.5f64, 0f64, 1f64) as u64
+        }
+        ";
+
+    let result = test_program_with_core_modules(source);
+    assert!(result.is_ok(), "math::gcd / clamp should run: {:?}", result.err());
+    assert_eq!(result.unwrap().borrow().unwrap_uint64(), 17);
+}
+
+#[test]
+fn test_math_clamp_inverted_range_panics() {
+    let source = r"
+        fn main() -> u64 {
+            math::clamp_u64(5u64, 10u64, 0u64)
+        }
+        ";
+
+    let result = test_program_with_core_modules(source);
+    assert!(result.is_err(), "inverted clamp range should panic");
+}
+
 #[test]
 fn test_module_package_and_no_import_needed() {
     // `package main` declaration alongside auto-loaded core modules
@@ -0,0 +1,88 @@
+// `format` / `format_fixed` / `format_exp`. The expected strings are
+// exact: a change to padding or rounding changes report output.
+
+mod common;
+
+use common::{assert_program_fails, get_program_result, test_program};
+use interpreter::object::Object;
+
+fn run_str(src: &str) -> String {
+    match &*get_program_result(src).borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected Object::String, got {other:?}"),
+    }
+}
+
+#[test]
+fn format_pads_integers_on_the_left() {
+    assert_eq!(run_str(r#"fn main() -> str { format(42, 6, ' ') }"#), "    42");
+    assert_eq!(run_str(r#"fn main() -> str { format(7u64, 3u64, '*') }"#), "**7");
+}
+
+#[test]
+fn zero_padding_goes_after_the_sign() {
+    assert_eq!(run_str(r#"fn main() -> str { format(-42i64, 6u64, '0') }"#), "-00042");
+    assert_eq!(run_str(r#"fn main() -> str { format(-42i64, 6u64, ' ') }"#), "   -42");
+}
+
+#[test]
+fn values_wider_than_the_column_are_unchanged() {
+    assert_eq!(run_str(r#"fn main() -> str { format(123456, 3, ' ') }"#), "123456");
+}
+
+#[test]
+fn format_accepts_floats_and_strings() {
+    assert_eq!(run_str(r#"fn main() -> str { format(2.5f64, 5, ' ') }"#), "  2.5");
+    // Width counts characters, not bytes.
+    assert_eq!(run_str(r#"fn main() -> str { format("\u{e9}", 3, '.') }"#), "..é");
+    assert_eq!(
+        run_str(r#"fn main() -> str { format(format_fixed(3.14159f64, 2), 8, ' ') }"#),
+        "    3.14"
+    );
+}
+
+#[test]
+fn format_fixed_rounds_the_binary_value() {
+    let src = r#"
+        fn main() -> str {
+            format_fixed(0.125f64, 2).concat("|").concat(format_fixed(2.675f64, 2))
+                .concat("|").concat(format_fixed(-0.04f64, 1))
+                .concat("|").concat(format_fixed(1.0f64, 0))
+        }
+    "#;
+    assert_eq!(run_str(src), "0.12|2.67|-0.0|1");
+}
+
+#[test]
+fn format_exp_uses_a_bare_exponent() {
+    let src = r#"
+        fn main() -> str {
+            format_exp(1500.0f64, 2).concat("|").concat(format_exp(0.00012f64, 1))
+        }
+    "#;
+    assert_eq!(run_str(src), "1.50e3|1.2e-4");
+}
+
+#[test]
+fn huge_digit_counts_panic() {
+    let err = test_program(r#"fn main() -> str { format_fixed(1.0f64, 5000) }"#).unwrap_err();
+    assert!(err.contains("at most 1000 digits"), "{err}");
+}
+
+#[test]
+fn pad_must_be_a_char() {
+    assert_program_fails(r#"fn main() -> str { format(1u64, 4u64, "0") }"#);
+}
+
+#[test]
+fn char_typed_pad_parameter_is_accepted() {
+    let src = r#"
+        fn cell(n: u64, pad: char) -> str {
+            format(n, 4u64, pad)
+        }
+        fn main() -> str {
+            cell(5u64, '0')
+        }
+    "#;
+    assert_eq!(run_str(src), "0005");
+}
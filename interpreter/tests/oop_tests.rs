@@ -196,6 +196,72 @@ mod associated_functions {
             Err(e) => panic!("Program failed: {}", e),
         }
     }
+
+    #[test]
+    fn test_associated_function_not_shadowed_by_top_level_fn() {
+        let source = r#"
+            struct Scale {
+                factor: u64
+            }
+
+            impl Scale {
+                fn helper(a: u64) -> u64 {
+                    a * 10u64
+                }
+            }
+
+            fn helper(a: u64) -> u64 {
+                a + 1u64
+            }
+
+            fn main() -> u64 {
+                Scale::helper(2u64) + helper(2u64)
+            }
+        "#;
+
+        let result = test_program(source);
+        match result {
+            Ok(val) => {
+                assert_eq!(val.borrow().unwrap_uint64(), 23); // 20 + 3
+            }
+            Err(e) => panic!("Program failed: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_associated_function_calls_sibling_helper() {
+        let source = r#"
+            struct Area {
+                value: u64
+            }
+
+            impl Area {
+                fn square(side: u64) -> u64 {
+                    side * side
+                }
+
+                fn of_square(side: u64) -> Self {
+                    Area { value: Area::square(side) }
+                }
+
+                fn get(self: Self) -> u64 {
+                    self.value
+                }
+            }
+
+            fn main() -> u64 {
+                Area::of_square(4u64).get() + Area::square(3u64)
+            }
+        "#;
+
+        let result = test_program(source);
+        match result {
+            Ok(val) => {
+                assert_eq!(val.borrow().unwrap_uint64(), 25); // 16 + 9
+            }
+            Err(e) => panic!("Program failed: {}", e),
+        }
+    }
 }
 
 // =============================================================================
@@ -393,6 +459,83 @@ fn main() -> str {
             other => panic!("Expected String or ConstString but got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_self_struct_literal_in_constructor() {
+        let source = r#"
+struct Counter {
+    count: u64
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self { count: 1u64 }
+    }
+
+    fn bumped(self: Self) -> Self {
+        Self { count: self.count + 1u64 }
+    }
+}
+
+fn main() -> u64 {
+    val c = Counter::new()
+    val d = c.bumped()
+    d.bumped().count
+}
+"#;
+        let result = test_program(source).expect("Program should execute successfully");
+        assert_eq!(result.borrow().unwrap_uint64(), 3);
+    }
+
+    #[test]
+    fn test_self_constructor_in_generic_impl() {
+        let source = r#"
+struct Wrapper<T> {
+    value: T
+}
+
+impl<T> Wrapper<T> {
+    fn new(value: T) -> Self {
+        Self { value: value }
+    }
+
+    fn replaced(self: Self, value: T) -> Self {
+        Self::new(value)
+    }
+
+    fn get(self: Self) -> T {
+        self.value
+    }
+}
+
+fn main() -> u64 {
+    val w = Wrapper::new(5u64)
+    val flag = Wrapper::new(true)
+    if flag.get() {
+        w.replaced(7u64).get()
+    } else {
+        0u64
+    }
+}
+"#;
+        let result = test_program(source).expect("Program should execute successfully");
+        assert_eq!(result.borrow().unwrap_uint64(), 7);
+    }
+
+    #[test]
+    fn test_self_expression_outside_impl_is_rejected() {
+        let source = r#"
+struct Point {
+    x: u64
+}
+
+fn main() -> u64 {
+    val p = Self { x: 1u64 }
+    p.x
+}
+"#;
+        assert!(test_program(source).is_err());
+    }
 }
 
 // =============================================================================
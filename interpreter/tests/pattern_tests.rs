@@ -0,0 +1,133 @@
+// `matches` / `find` / `replace_pattern` — Lua-style patterns. The
+// expected results are what Lua 5.4's `string.find` / `string.match` /
+// `string.gsub` give for the same pattern.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64, get_program_result, test_program};
+use interpreter::object::Object;
+
+fn run_str(src: &str) -> String {
+    match &*get_program_result(src).borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected Object::String, got {other:?}"),
+    }
+}
+
+#[test]
+fn matches_classes_sets_and_anchors() {
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            var bits = 0u64
+            if matches("order 66", "%d+") { bits = bits + 1u64 }
+            if matches("2024-06-01", "^%d%d%d%d%-%d%d%-%d%d$") { bits = bits + 2u64 }
+            if matches("x2024-06-01", "^%d") { bits = bits + 4u64 }
+            if matches("hello", "[^%l]") { bits = bits + 8u64 }
+            if matches("a.b", "a%.b") { bits = bits + 16u64 }
+            bits
+        }"#,
+        19,
+    );
+}
+
+#[test]
+fn find_returns_the_first_capture_or_the_whole_match() {
+    assert_eq!(
+        run_str(
+            r#"fn main() -> str {
+                match find("key = value", "(%w+)%s*=") {
+                    Option::Some(k) => k,
+                    Option::None => "",
+                }
+            }"#
+        ),
+        "key"
+    );
+    assert_eq!(
+        run_str(
+            r#"fn main() -> str {
+                match find("v1.42.7", "%d+%.%d+") {
+                    Option::Some(m) => m,
+                    Option::None => "",
+                }
+            }"#
+        ),
+        "1.42"
+    );
+    assert_program_result_u64(
+        r#"fn main() -> u64 {
+            match find("abc", "%d") {
+                Option::Some(_) => 1u64,
+                Option::None => 0u64,
+            }
+        }"#,
+        0,
+    );
+}
+
+#[test]
+fn lazy_and_greedy_repetition() {
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("<a><b>", "<.->", "[]") }"#),
+        "[][]"
+    );
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("<a><b>", "<.*>", "[]") }"#),
+        "[]"
+    );
+}
+
+#[test]
+fn replacement_uses_captures() {
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("hello world", "(%w+) (%w+)", "%2 %1") }"#),
+        "world hello"
+    );
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("a1b22", "%d+", "<%0>") }"#),
+        "a<1>b<22>"
+    );
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("50", "%d+", "%1%%") }"#),
+        "50%"
+    );
+}
+
+#[test]
+fn empty_matches_follow_lua_5_4() {
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("hello world", "%w*", "x") }"#),
+        "x x"
+    );
+    assert_eq!(run_str(r#"fn main() -> str { replace_pattern("abc", "", "-") }"#), "-a-b-c-");
+}
+
+#[test]
+fn balance_frontier_and_back_references() {
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("f(a(b)c) g(d)", "%b()", "()") }"#),
+        "f() g()"
+    );
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("THE (quick) fox", "%f[%a]%a+", "W") }"#),
+        "W (W) W"
+    );
+    assert_eq!(
+        run_str(r#"fn main() -> str { replace_pattern("a 'b' *c*", "(%p)(.-)%1", "<%2>") }"#),
+        "a <b> <c>"
+    );
+}
+
+#[test]
+fn malformed_patterns_panic() {
+    let err = test_program(r#"fn main() -> bool { matches("abc", "[a") }"#).unwrap_err();
+    assert!(err.contains("matches: malformed pattern (missing ']')"), "{err}");
+    let err = test_program(r#"fn main() -> str { replace_pattern("abc", "b", "%x") }"#).unwrap_err();
+    assert!(err.contains("invalid use of '%' in replacement string"), "{err}");
+}
+
+#[test]
+fn operands_must_be_strings() {
+    assert_program_fails(r#"fn main() -> bool { matches(42u64, "%d") }"#);
+    assert_program_fails(r#"fn main() -> str { replace_pattern("a", "a") }"#);
+}
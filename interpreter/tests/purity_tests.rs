@@ -0,0 +1,108 @@
+// Purity inference over the checked program. `TypeCheckResults::purity`
+// lists functions and methods with no side effects; anything the
+// analysis can't see through counts as impure.
+
+mod common;
+
+use common::core_modules_dir;
+use compiler_core::CompilerSession;
+
+const SOURCE: &str = r#"
+struct Point {
+    x: i64,
+    y: i64
+}
+
+impl Point {
+    fn dist2(&self) -> i64 {
+        self.x * self.x + self.y * self.y
+    }
+
+    fn shift(&mut self, d: i64) {
+        self.x = self.x + d
+    }
+}
+
+fn square(v: i64) -> i64 {
+    v * v
+}
+
+fn fact(n: u64) -> u64 {
+    if n == 0u64 { 1u64 } else { n * fact(n - 1u64) }
+}
+
+fn make(x: i64) -> Point {
+    var p = Point { x: 0i64, y: 0i64 }
+    p.x = square(x)
+    p
+}
+
+fn loud(v: i64) -> i64 {
+    println(v)
+    v
+}
+
+fn calls_loud(v: i64) -> i64 {
+    square(loud(v))
+}
+
+fn moves(p: &mut Point) -> i64 {
+    p.x = 1i64
+    p.x
+}
+
+fn uses_method(p: Point) -> i64 {
+    p.dist2()
+}
+
+fn bumps(p: Point) -> i64 {
+    var q = p
+    q.shift(1i64)
+    q.x
+}
+
+fn main() -> u64 {
+    var p = make(3i64)
+    (calls_loud(p.dist2()) + moves(&mut p) + uses_method(p) + bumps(p)) as u64 + fact(3u64)
+}
+"#;
+
+fn purity_of(names: &[&str]) -> Vec<bool> {
+    let mut session = CompilerSession::new();
+    let mut program = session.parse_named_source("test.t", SOURCE).expect("parse");
+    let core = core_modules_dir();
+    let interner = session.string_interner_mut();
+    let results =
+        interpreter::check_typing_with_results(&mut program, interner, Some(SOURCE), Some("test.t"), Some(&core))
+            .expect("type check");
+    let sym = |name: &str| interner.get(name).unwrap();
+    names
+        .iter()
+        .map(|name| match name.split_once("::") {
+            Some((ty, method)) => results.purity.is_pure_method(sym(ty), sym(method)),
+            None => results.purity.is_pure_function(sym(name)),
+        })
+        .collect()
+}
+
+#[test]
+fn arithmetic_recursion_and_fresh_locals_are_pure() {
+    assert_eq!(purity_of(&["square", "fact", "make"]), [true, true, true]);
+}
+
+#[test]
+fn io_taints_callers() {
+    assert_eq!(purity_of(&["loud", "calls_loud", "main"]), [false, false, false]);
+}
+
+#[test]
+fn writing_into_parameters_is_impure() {
+    assert_eq!(purity_of(&["moves", "Point::shift", "bumps"]), [false, false, false]);
+}
+
+#[test]
+fn methods_resolve_through_receiver_types() {
+    // `dist2` only reads through `&self`; `p.dist2()` resolves to it
+    // through the checked type of `p`.
+    assert_eq!(purity_of(&["Point::dist2", "uses_method"]), [true, true]);
+}
@@ -459,3 +459,31 @@ fn main() -> u64 {
     let result = test_program(source).expect("Program should execute successfully");
     assert_eq!(result.borrow().unwrap_uint64(), 42);
 }
+
+/// Regression test: a bare `return` directly before the closing brace
+/// (`if c { return }`) was parsed as `return <expr>`, failed on the `}`
+/// and silently dropped out of the block.
+#[test]
+fn test_bare_return_before_closing_brace() {
+    let source = r#"
+struct Counter {
+    n: u64
+}
+
+impl Counter {
+    fn bump(&mut self, skip: bool) {
+        if skip { return }
+        self.n = self.n + 1u64
+    }
+}
+
+fn main() -> u64 {
+    var c = Counter { n: 0u64 }
+    c.bump(true)
+    c.bump(false)
+    c.n
+}
+"#;
+    let result = test_program(source).expect("Program should execute successfully");
+    assert_eq!(result.borrow().unwrap_uint64(), 1);
+}
@@ -0,0 +1,132 @@
+// `tcp_*` builtins and the `net::` wrappers. The toylang side is the
+// server; a client thread on the test side connects once the program
+// is listening, retrying until the port accepts.
+
+mod common;
+
+use common::{assert_program_fails, test_program, test_program_with_capabilities};
+use interpreter::object::Object;
+use interpreter::{Capabilities, Capability};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// Send `request`, half-close, and hand back everything the server
+/// answered.
+fn client(port: u16, request: &'static [u8]) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut conn = loop {
+            match TcpStream::connect(("127.0.0.1", port)) {
+                Ok(conn) => break conn,
+                Err(_) => std::thread::sleep(Duration::from_millis(20)),
+            }
+        };
+        conn.write_all(request).unwrap();
+        conn.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut reply = String::new();
+        conn.read_to_string(&mut reply).unwrap();
+        reply
+    })
+}
+
+fn run_with_net(src: &str) -> Result<u64, String> {
+    let result = test_program_with_capabilities(src, Capabilities::default().with(Capability::Net))?;
+    let n = match &*result.borrow() {
+        Object::UInt64(n) => *n,
+        other => panic!("expected u64, got {other:?}"),
+    };
+    Ok(n)
+}
+
+#[test]
+fn echo_server_with_builtins() {
+    let port = free_port();
+    let peer = client(port, b"ping");
+    let src = format!(
+        r#"fn main() -> u64 {{
+            val listener = tcp_listen({port}u64)
+            val conn = tcp_accept(listener)
+            val msg = tcp_read(conn)
+            tcp_write(conn, "pong:")
+            tcp_write(conn, msg)
+            val rest = tcp_read(conn)
+            tcp_close(conn)
+            tcp_close(listener)
+            msg.len() * 10u64 + rest.len()
+        }}"#
+    );
+    assert_eq!(run_with_net(&src).unwrap(), 40);
+    assert_eq!(peer.join().unwrap(), "pong:ping");
+}
+
+#[test]
+fn net_module_wraps_the_builtins() {
+    let port = free_port();
+    let peer = client(port, b"hello");
+    let src = format!(
+        r#"fn main() -> u64 {{
+            val listener = net::listen({port}u64)
+            val conn = net::accept(listener)
+            val msg = net::read(conn)
+            net::write(conn, msg)
+            net::close(conn)
+            net::close(listener)
+            msg.len()
+        }}"#
+    );
+    assert_eq!(run_with_net(&src).unwrap(), 5);
+    assert_eq!(peer.join().unwrap(), "hello");
+}
+
+#[test]
+fn port_zero_picks_a_free_port() {
+    let port = run_with_net(
+        r#"fn main() -> u64 {
+            val listener = tcp_listen(0u64)
+            tcp_local_port(listener)
+        }"#,
+    )
+    .unwrap();
+    assert!(port > 0 && port <= 65535, "{port}");
+}
+
+#[test]
+fn needs_the_net_capability() {
+    let err = test_program("fn main() -> u64 { tcp_listen(0u64) }").unwrap_err();
+    assert!(err.contains("tcp_listen: network access is disabled (run with --allow-net)"), "{err}");
+}
+
+#[test]
+fn bad_handles_panic() {
+    let err = run_with_net(r#"fn main() -> u64 { tcp_read(42u64).len() }"#).unwrap_err();
+    assert!(err.contains("tcp_read(42): no open socket with handle 42"), "{err}");
+    let err = run_with_net(
+        r#"fn main() -> u64 {
+            val listener = tcp_listen(0u64)
+            tcp_write(listener, "x")
+            0u64
+        }"#,
+    )
+    .unwrap_err();
+    assert!(err.contains("is a listener, not a connection"), "{err}");
+    let err = run_with_net(
+        r#"fn main() -> u64 {
+            val listener = tcp_listen(0u64)
+            tcp_close(listener)
+            tcp_close(listener)
+            0u64
+        }"#,
+    )
+    .unwrap_err();
+    assert!(err.contains("no open socket with handle 1"), "{err}");
+}
+
+#[test]
+fn payload_must_be_a_string() {
+    assert_program_fails("fn main() -> u64 { tcp_write(1u64, 5u64)\n 0u64 }");
+}
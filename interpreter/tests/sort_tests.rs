@@ -0,0 +1,101 @@
+// `sort(arr, less)` — stable sort of an array copy with a closure
+// comparator, and the type checks on the comparator's shape.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_array_i64, assert_program_result_u64};
+
+#[test]
+fn sorts_with_the_given_order() {
+    assert_program_result_array_i64(
+        "fn main() -> [i64; 5] {
+            sort([3i64, -1i64, 7i64, 0i64, -5i64], fn(a: i64, b: i64) -> bool { a > b })
+        }",
+        vec![7, 3, 0, -1, -5],
+    );
+}
+
+#[test]
+fn untyped_literals_take_the_comparator_type() {
+    assert_program_result_array_i64(
+        "fn main() -> [i64; 4] {
+            sort([4, 2, 3, 1], fn(a: i64, b: i64) -> bool { a < b })
+        }",
+        vec![1, 2, 3, 4],
+    );
+}
+
+#[test]
+fn equal_keys_keep_their_order() {
+    // Sorted by `k` only; the tags record the input order.
+    assert_program_result_u64(
+        "struct P { k: u64, tag: u64 }
+        fn main() -> u64 {
+            val ps = [
+                P { k: 2u64, tag: 1u64 }, P { k: 1u64, tag: 2u64 }, P { k: 2u64, tag: 3u64 },
+                P { k: 1u64, tag: 4u64 }, P { k: 0u64, tag: 5u64 },
+            ]
+            val s = sort(ps, fn(a: P, b: P) -> bool { a.k < b.k })
+            var digits = 0u64
+            for i in 0u64 to 5u64 { digits = digits * 10u64 + s[i].tag }
+            digits
+        }",
+        52413,
+    );
+}
+
+#[test]
+fn input_array_is_left_unchanged() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val xs = [3u64, 1u64, 2u64]
+            val sorted = sort(xs, fn(a: u64, b: u64) -> bool { a < b })
+            xs[0] * 10u64 + sorted[0]
+        }",
+        31,
+    );
+}
+
+#[test]
+fn comparator_can_capture() {
+    // Sort by distance from a captured pivot.
+    assert_program_result_array_i64(
+        "fn main() -> [i64; 4] {
+            val pivot = 10i64
+            sort([1i64, 12i64, 9i64, 20i64], fn(a: i64, b: i64) -> bool {
+                abs(a - pivot) < abs(b - pivot)
+            })
+        }",
+        vec![9, 12, 1, 20],
+    );
+}
+
+#[test]
+fn comparator_must_take_the_element_type() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            val s = sort([\"a\", \"b\"], fn(a: u64, b: u64) -> bool { a < b })
+            0u64
+        }",
+    );
+}
+
+#[test]
+fn comparator_must_return_bool() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            val s = sort([1u64, 2u64], fn(a: u64, b: u64) -> u64 { a })
+            0u64
+        }",
+    );
+}
+
+#[test]
+fn comparator_must_take_two_parameters() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            val s = sort([1u64, 2u64], fn(a: u64) -> bool { true })
+            0u64
+        }",
+    );
+}
@@ -0,0 +1,81 @@
+// `read_line` / `read_all` builtins. Input is fed through
+// `output::with_input`, which swaps a thread-local buffer in for the
+// process stdin for the duration of the closure.
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+use interpreter::object::Object;
+use interpreter::output::with_input;
+
+fn run_with_input(input: &str, src: &str) -> Object {
+    let result = with_input(input, || test_program(src)).expect("program should run");
+    let value = result.borrow().clone();
+    value
+}
+
+#[test]
+fn read_line_keeps_newline_and_returns_empty_at_eof() {
+    // Lines come back with their terminator; a blank line is "\n"
+    // (length 1) and only end of input yields "" (length 0).
+    let src = r#"
+        fn main() -> u64 {
+            val a = read_line()
+            val b = read_line()
+            val c = read_line()
+            val d = read_line()
+            a.len() * 1000u64 + b.len() * 100u64 + c.len() * 10u64 + d.len()
+        }
+    "#;
+    assert_eq!(run_with_input("abc\n\nxy", src), Object::UInt64(4120));
+}
+
+#[test]
+fn read_line_loop_until_eof() {
+    let src = r#"
+        fn main() -> u64 {
+            var total = 0u64
+            var line = read_line()
+            while line.len() > 0u64 {
+                total = total + line.trim().len()
+                line = read_line()
+            }
+            total
+        }
+    "#;
+    assert_eq!(run_with_input("one\ntwo\nthree\n", src), Object::UInt64(11));
+}
+
+#[test]
+fn read_all_returns_remaining_input() {
+    let src = r#"
+        fn main() -> str {
+            val first = read_line()
+            val rest = read_all()
+            if read_all() == "" { rest } else { first }
+        }
+    "#;
+    assert_eq!(run_with_input("skip\nkeep\nthis\n", src), Object::String("keep\nthis\n".to_string()));
+}
+
+#[test]
+fn read_all_on_empty_input_is_empty() {
+    let src = r#"
+        fn main() -> u64 {
+            read_all().len()
+        }
+    "#;
+    with_input("", || assert_program_result_u64(src, 0));
+}
+
+#[test]
+fn typecheck_read_line_rejects_arguments() {
+    let src = r#"
+        fn main() -> u64 {
+            read_line(1u64)
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("read_line(x) should be rejected");
+    assert!(err.contains("read_line"), "unexpected error: {err}");
+}
@@ -0,0 +1,115 @@
+// `to_dict` / `from_dict<T>` builtins: struct <-> dict conversion,
+// including the JSON round trip they exist for.
+
+mod common;
+
+use common::{assert_program_result_u64, test_program};
+use interpreter::output::with_input;
+
+fn run_with_input_u64(input: &str, src: &str) -> u64 {
+    let result = with_input(input, || test_program(src)).expect("program should run");
+    let n = result.borrow().unwrap_uint64();
+    n
+}
+
+const NESTED: &str = r#"
+    struct Inner { v: u64 }
+    struct Point { x: u64, label: str, inner: Inner }
+"#;
+
+#[test]
+fn to_dict_exposes_fields_by_name() {
+    let src = format!("{NESTED}{}", r#"
+        fn main() -> u64 {
+            val p = Point { x: 3u64, label: "a", inner: Inner { v: 9u64 } }
+            val d = to_dict(p)
+            val x: u64 = d["x"]
+            val label: str = d["label"]
+            if label == "a" { x } else { 0u64 }
+        }
+    "#);
+    assert_program_result_u64(&src, 3);
+}
+
+#[test]
+fn from_dict_round_trips_to_dict() {
+    let src = format!("{NESTED}{}", r#"
+        fn main() -> u64 {
+            val p = Point { x: 3u64, label: "a", inner: Inner { v: 9u64 } }
+            match from_dict<Point>(to_dict(p)) {
+                Option::Some(q) => q.x * 10u64 + q.inner.v,
+                Option::None => 0u64,
+            }
+        }
+    "#);
+    assert_program_result_u64(&src, 39);
+}
+
+#[test]
+fn from_dict_fills_nested_struct_from_json() {
+    let src = format!("{NESTED}{}", r#"
+        fn main() -> u64 {
+            match from_dict<Point>(json_parse(read_all())) {
+                Option::Some(q) => q.x * 10u64 + q.inner.v,
+                Option::None => 0u64,
+            }
+        }
+    "#);
+    let input = r#"{"x": 4, "label": "n", "inner": {"v": 5}}"#;
+    assert_eq!(run_with_input_u64(input, &src), 45);
+}
+
+#[test]
+fn from_dict_rejects_missing_extra_and_mistyped_fields() {
+    let src = format!("{NESTED}{}", r#"
+        fn main() -> u64 {
+            match from_dict<Point>(json_parse(read_line())) {
+                Option::Some(_) => 1u64,
+                Option::None => 0u64,
+            }
+        }
+    "#);
+    for input in [
+        r#"{"x": 4, "label": "n"}"#,
+        r#"{"x": 4, "label": "n", "inner": {"v": 5}, "y": 1}"#,
+        r#"{"x": "4", "label": "n", "inner": {"v": 5}}"#,
+        r#"{"x": -4, "label": "n", "inner": {"v": 5}}"#,
+        r#"{"x": 4, "label": "n", "inner": {"w": 5}}"#,
+    ] {
+        assert_eq!(run_with_input_u64(input, &src), 0, "input {input} should be rejected");
+    }
+}
+
+#[test]
+fn typecheck_from_dict_result_is_option() {
+    let src = r#"
+        struct Point { x: u64 }
+        fn main() -> u64 {
+            val d = dict{"x": 1u64}
+            val p: Point = from_dict<Point>(d)
+            p.x
+        }
+    "#;
+    assert!(test_program(src).is_err(), "from_dict should return Option<Point>");
+}
+
+#[test]
+fn typecheck_rejects_non_struct_arguments() {
+    let to_dict_src = r#"
+        fn main() -> u64 {
+            val d = to_dict(1u64)
+            0u64
+        }
+    "#;
+    let err = test_program(to_dict_src).expect_err("to_dict(u64) should be rejected");
+    assert!(err.contains("to_dict expects a struct argument"), "unexpected error: {err}");
+
+    let from_dict_src = r#"
+        fn main() -> u64 {
+            val p = from_dict<u64>(dict{"x": 1u64})
+            0u64
+        }
+    "#;
+    let err = test_program(from_dict_src).expect_err("from_dict<u64> should be rejected");
+    assert!(err.contains("from_dict target must be a struct type"), "unexpected error: {err}");
+}
@@ -0,0 +1,136 @@
+// Cooperative tasks — `fn f() -> Task`, `spawn`, `yield_now` and the
+// stdlib `Channel<T>`.
+//
+// A task is a `Generator<()>` whose `yield_now()` statements suspend it.
+// `spawn` queues it; a `yield_now()` outside a task body runs every
+// queued task once and reports whether any of them ran.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64, test_program};
+
+#[test]
+fn producer_consumer_pipeline() {
+    assert_program_result_u64(
+        "fn produce(out: Channel<u64>, n: u64) -> Task {
+            for i in 0u64 to n {
+                out.send(i)
+                yield_now()
+            }
+            out.close()
+        }
+        fn double(input: Channel<u64>, out: Channel<u64>) -> Task {
+            while val Option::Some(x) = input.recv() {
+                out.send(x * 2u64)
+                yield_now()
+            }
+            out.close()
+        }
+        fn main() -> u64 {
+            val a: Channel<u64> = Channel::new()
+            val b: Channel<u64> = Channel::new()
+            spawn(produce(a, 5u64))
+            spawn(double(a, b))
+            var sum = 0u64
+            while val Option::Some(x) = b.recv() { sum = sum + x }
+            sum
+        }",
+        20,
+    );
+}
+
+#[test]
+fn tasks_interleave_at_yield_now() {
+    // Each round resumes the queued tasks in spawn order, so the two
+    // workers' entries alternate.
+    assert_program_result_u64(
+        "fn worker(id: u64, log: Channel<u64>) -> Task {
+            for i in 0u64 to 3u64 {
+                log.send(id * 10u64 + i)
+                yield_now()
+            }
+        }
+        fn main() -> u64 {
+            val log: Channel<u64> = Channel::new()
+            spawn(worker(1u64, log))
+            spawn(worker(2u64, log))
+            while yield_now() { continue }
+            var acc = 0u64
+            while val Option::Some(x) = log.try_recv() { acc = acc * 100u64 + x }
+            acc
+        }",
+        102011211222,
+    );
+}
+
+#[test]
+fn spawned_task_waits_for_yield_now() {
+    assert_program_result_u64(
+        "fn bump(ch: Channel<u64>) -> Task {
+            ch.send(1u64)
+        }
+        fn main() -> u64 {
+            val ch: Channel<u64> = Channel::new()
+            spawn(bump(ch))
+            val before = ch.len()
+            val ran = yield_now()
+            val idle = yield_now()
+            if ran && !idle { before * 10u64 + ch.len() } else { 99u64 }
+        }",
+        1,
+    );
+}
+
+#[test]
+fn recv_returns_none_when_no_task_can_send() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val ch: Channel<u64> = Channel::new()
+            ch.send(7u64)
+            var got = 0u64
+            while val Option::Some(x) = ch.recv() { got = got + x }
+            got
+        }",
+        7,
+    );
+}
+
+#[test]
+fn send_on_closed_channel_panics() {
+    let err = test_program(
+        "fn main() -> u64 {
+            val ch: Channel<u64> = Channel::new()
+            ch.close()
+            ch.send(1u64)
+            0u64
+        }",
+    )
+    .unwrap_err();
+    assert!(err.contains("closed channel"), "{err}");
+}
+
+#[test]
+fn spawn_rejects_a_generator_with_items() {
+    let err = test_program(
+        "fn g() -> Generator<u64> { yield 1u64 }
+        fn main() -> u64 {
+            spawn(g())
+            0u64
+        }",
+    )
+    .unwrap_err();
+    assert!(err.contains("spawn expects a Task"), "{err}");
+}
+
+#[test]
+fn yield_now_nested_in_task_expression_is_rejected() {
+    assert_program_fails(
+        "fn t() -> Task {
+            val more = yield_now()
+        }
+        fn main() -> u64 {
+            spawn(t())
+            0u64
+        }",
+    );
+}
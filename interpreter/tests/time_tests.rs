@@ -0,0 +1,132 @@
+// `now_millis` / `clock` / `sleep` builtins. Wall-clock values can't
+// be pinned, so these check monotonic lower bounds and the types the
+// type checker assigns, except under `--deterministic`. The `time`
+// module tests at the end work on fixed timestamps instead.
+
+mod common;
+
+use common::{assert_program_result_array_i64, assert_program_result_u64, get_program_result, test_program};
+use interpreter::object::Object;
+
+#[test]
+fn now_millis_is_after_2020() {
+    let src = r#"
+        fn main() -> u64 {
+            if now_millis() > 1577836800000u64 { 1u64 } else { 0u64 }
+        }
+    "#;
+    assert_program_result_u64(src, 1);
+}
+
+#[test]
+fn sleep_advances_both_clocks() {
+    let src = r#"
+        fn main() -> u64 {
+            val t0 = now_millis()
+            val c0: f64 = clock()
+            sleep(20)
+            val waited_ms = now_millis() - t0
+            val waited_s = clock() - c0
+            if waited_ms >= 20u64 && waited_s >= 0.02f64 { 1u64 } else { 0u64 }
+        }
+    "#;
+    assert_program_result_u64(src, 1);
+}
+
+#[test]
+fn clock_starts_near_zero() {
+    let src = r#"
+        fn main() -> u64 {
+            if clock() < 60f64 { 1u64 } else { 0u64 }
+        }
+    "#;
+    assert_program_result_u64(src, 1);
+}
+
+#[test]
+fn typecheck_sleep_rejects_float_argument() {
+    let src = r#"
+        fn main() -> u64 {
+            sleep(1.5f64)
+            0u64
+        }
+    "#;
+    let err = test_program(src).expect_err("sleep(f64) should be rejected");
+    assert!(err.contains("sleep expects a u64 argument"), "unexpected error: {err}");
+}
+
+#[test]
+fn deterministic_run_uses_a_simulated_clock_and_the_given_seed() {
+    use interpreter::output::with_stdout_stderr_capture;
+    use interpreter::RunOptions;
+
+    let src = r#"
+        fn main() -> u64 {
+            val t0 = now_millis()
+            sleep(1500)
+            println("{t0} {now_millis()} {clock()} {random_range(0u64, 1000000u64)}")
+            0u64
+        }
+    "#;
+    let core = common::core_modules_dir();
+    let run = |src: &str, deterministic| {
+        let opts = RunOptions { core_modules_dir: Some(core.as_path()), deterministic, ..RunOptions::default() };
+        let (result, stdout, _stderr) = with_stdout_stderr_capture(|| interpreter::run_source(src, "test.t", &opts));
+        result.expect("program should run");
+        stdout
+    };
+    let first = run(src, Some(7));
+    assert_eq!(first, run(src, Some(7)));
+    assert!(first.starts_with("0 1500 1.5 "), "unexpected output: {first}");
+
+    let reseeded = src.replace("val t0 = now_millis()", "seed(7u64)\n            val t0 = now_millis()");
+    assert_eq!(run(&reseeded, Some(0)), first, "--deterministic=7 should match seed(7)");
+}
+
+fn format_timestamp(epoch: &str, fmt: &str) -> String {
+    let src = format!("fn main() -> str {{ time::format_timestamp({epoch}, \"{fmt}\") }}");
+    match &*get_program_result(&src).borrow() {
+        Object::String(s) => s.clone(),
+        other => panic!("expected str, got {other:?}"),
+    }
+}
+
+#[test]
+fn format_timestamp_covers_the_strftime_subset() {
+    assert_eq!(format_timestamp("1700000000i64", "%F %T"), "2023-11-14 22:13:20");
+    assert_eq!(
+        format_timestamp("1700000000i64", "%a %A %b %B %d %e %j %u %w %y %Z %z %s %%"),
+        "Tue Tuesday Nov November 14 14 318 2 2 23 UTC +0000 1700000000 %"
+    );
+    assert_eq!(format_timestamp("0i64", "%D %R %A"), "01/01/70 00:00 Thursday");
+    assert_eq!(format_timestamp("951782400i64", "%F %j %a"), "2000-02-29 060 Tue");
+}
+
+#[test]
+fn format_timestamp_handles_times_before_the_epoch() {
+    assert_eq!(format_timestamp("-1i64", "%F %T %A %j"), "1969-12-31 23:59:59 Wednesday 365");
+    assert_eq!(format_timestamp("-2208988800i64", "%F %a"), "1900-01-01 Mon");
+}
+
+#[test]
+fn format_timestamp_rejects_unknown_conversions() {
+    let err = test_program(r#"fn main() -> str { time::format_timestamp(0i64, "%Q") }"#).unwrap_err();
+    assert!(err.contains("format_timestamp(0, \"%Q\"): unknown conversion `%Q`"), "{err}");
+    let err = test_program(r#"fn main() -> str { time::format_timestamp(0i64, "100%") }"#).unwrap_err();
+    assert!(err.contains("lone `%`"), "{err}");
+}
+
+#[test]
+fn component_functions_agree_with_the_formatter() {
+    let parts = "[time::year(t), time::month(t), time::day(t), time::hour(t), time::minute(t), \
+                 time::second(t), time::weekday(t), time::day_of_year(t)]";
+    for (epoch, expected) in [
+        ("1700000000i64", vec![2023, 11, 14, 22, 13, 20, 2, 318]),
+        ("-1i64", vec![1969, 12, 31, 23, 59, 59, 3, 365]),
+        ("951868799i64", vec![2000, 2, 29, 23, 59, 59, 2, 60]),
+        ("-62135596800i64", vec![1, 1, 1, 0, 0, 0, 1, 1]),
+    ] {
+        let src = format!("fn main() -> [i64; 8] {{\n    val t: i64 = {epoch}\n    {parts}\n}}");
+        assert_program_result_array_i64(&src, expected);
+    }
+}
@@ -132,6 +132,29 @@ mod basic {
         assert_eq!(result.unwrap().borrow().unwrap_uint64(), 16);
     }
 
+    #[test]
+    fn test_prelude_bit_ops_edge_cases() {
+        // `core/std/bits.t`: zero inputs count 64 leading / trailing
+        // zeros, rotate amounts wrap modulo 64, and the i64 impl
+        // works on the two's-complement bit pattern.
+        let source = r#"
+            fn main() -> u64 {
+                val z: u64 = 0u64
+                val x: u64 = 0x8000000000000001u64
+                val n: i64 = -1i64
+                val a: u64 = z.leading_zeros() + z.trailing_zeros()   # 128
+                val b: u64 = z.count_ones() + n.count_ones()          # 64
+                val c: u64 = x.rotate_left(64u64) - x                 # 0
+                val d: u64 = x.rotate_left(1u64)                      # 3
+                val e: u64 = n.rotate_right(7u64) as u64 - 0xFFFFFFFFFFFFFFFFu64  # 0
+                a + b + c + d + e
+            }
+        "#;
+        let result = test_program(source);
+        assert!(result.is_ok(), "prelude bit ops should run: {:?}", result.err());
+        assert_eq!(result.unwrap().borrow().unwrap_uint64(), 195);
+    }
+
     #[test]
     fn test_extension_trait_method_dispatch_on_primitive() {
         // Step B of the extension-trait work: a user `impl Trait for
@@ -0,0 +1,151 @@
+// `try { body } catch e { handler }` — the handler runs with the
+// failure's message when the body fails at runtime.
+
+mod common;
+
+use common::{assert_program_fails, assert_program_result_u64, test_program};
+
+#[test]
+fn body_value_is_kept_when_nothing_fails() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            try { 6u64 * 7u64 } catch e { 0u64 }
+        }",
+        42,
+    );
+}
+
+#[test]
+fn panic_in_a_callee_runs_the_handler() {
+    assert_program_result_u64(
+        "fn checked_div(a: u64, b: u64) -> u64 {
+            if b == 0u64 {
+                panic(\"division by zero\")
+            }
+            a / b
+        }
+        fn main() -> u64 {
+            val q = try { checked_div(10u64, 0u64) } catch e {
+                if e == \"division by zero\" { 99u64 } else { 1u64 }
+            }
+            q + checked_div(10u64, 5u64)
+        }",
+        101,
+    );
+}
+
+#[test]
+fn runtime_errors_are_caught_with_their_description() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            val arr = [1u64, 2u64]
+            var msg = \"\"
+            val v = try { arr[5u64] } catch e {
+                msg = e
+                0u64
+            }
+            if msg.contains(\"out of bounds\") { v + 1u64 } else { 50u64 }
+        }",
+        1,
+    );
+}
+
+#[test]
+fn slice_assignment_length_mismatch_is_caught() {
+    assert_program_result_u64(
+        "fn main() -> u64 {
+            var a = [1u64, 2u64, 3u64]
+            val v = try {
+                a[0u64..2u64] = [9u64]
+                0u64
+            } catch e {
+                if e.contains(\"length mismatch\") { 7u64 } else { 1u64 }
+            }
+            v + a[0u64]
+        }",
+        8,
+    );
+}
+
+#[test]
+fn scopes_unwound_by_the_failure_are_discarded() {
+    // The failure happens several blocks and calls deep; the bindings
+    // visible after the `try` are the ones from before it.
+    assert_program_result_u64(
+        "fn deep(n: u64) -> u64 {
+            val local = n + 1u64
+            if local > 3u64 {
+                panic(\"deep\")
+            }
+            deep(local)
+        }
+        fn main() -> u64 {
+            val x = 5u64
+            var caught = 0u64
+            for i in 0u64 to 3u64 {
+                val r = try {
+                    val inner = i
+                    deep(inner)
+                } catch e {
+                    caught = caught + 1u64
+                    0u64
+                }
+            }
+            x * 10u64 + caught
+        }",
+        53,
+    );
+}
+
+#[test]
+fn handler_failure_propagates() {
+    let err = test_program(
+        "fn main() -> u64 {
+            try { panic(\"first\") } catch e { panic(\"second\") }
+        }",
+    )
+    .unwrap_err();
+    assert!(err.contains("second"), "{err}");
+}
+
+#[test]
+fn return_inside_try_leaves_the_function() {
+    assert_program_result_u64(
+        "fn pick(flag: bool) -> u64 {
+            try {
+                if flag {
+                    return 1u64
+                }
+                2u64
+            } catch e { 3u64 }
+        }
+        fn main() -> u64 {
+            pick(true) * 10u64 + pick(false)
+        }",
+        12,
+    );
+}
+
+#[test]
+fn catch_binding_is_a_str() {
+    assert_program_fails(
+        "fn main() -> u64 {
+            try { 1u64 } catch e { e + 1u64 }
+        }",
+    );
+}
+
+#[test]
+fn internal_errors_are_not_caught() {
+    // Skipping the type checker leaves the literal `5` unresolved, which
+    // the evaluator reports as an internal error. That signals a broken
+    // invariant, not a program failure, so the handler must not see it.
+    let source = "fn main() -> u64 {
+        try { 5 } catch e { 1u64 }
+    }";
+    let mut parser = frontend::ParserWithInterner::new(source);
+    let program = parser.parse_program().expect("parses");
+    let result = interpreter::execute_program(&program, parser.get_string_interner(), Some(source), Some("test.t"));
+    let err = result.expect_err("internal error escapes the try");
+    assert!(err.contains("should be transformed"), "error was: {err}");
+}
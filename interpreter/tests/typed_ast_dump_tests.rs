@@ -0,0 +1,46 @@
+// `--emit-typed-ast`: the AST dump annotated with the types the
+// checker inferred. Goes through `interpreter::emit_typed_ast` so the
+// prelude / core modules are integrated exactly like a normal run.
+
+mod common;
+
+use interpreter::AstDumpFormat;
+
+fn typed_dump(source: &str, format: AstDumpFormat) -> String {
+    let core = common::core_modules_dir();
+    interpreter::emit_typed_ast(source, "test.t", format, Some(core.as_path()))
+        .expect("type check failed")
+}
+
+const SOURCE: &str = r#"
+fn id<T>(x: T) -> T { x }
+
+fn main() -> i64 {
+    val a: i64 = 5i64
+    val b = id(a) + a
+    b
+}
+"#;
+
+#[test]
+fn typed_dump_annotates_inferred_types() {
+    let text = typed_dump(SOURCE, AstDumpFormat::Pretty);
+    assert!(text.contains("Binary IAdd : i64\n"), "{text}");
+    // The generic call resolves to the instantiated return type.
+    assert!(text.contains("Call id : i64\n"), "{text}");
+    assert!(text.contains("Identifier b : i64\n"), "{text}");
+}
+
+#[test]
+fn typed_dump_hides_integrated_modules() {
+    let text = typed_dump(SOURCE, AstDumpFormat::Pretty);
+    // Only the user's two functions; nothing from the prelude or `core/`.
+    assert_eq!(text.matches("\n  Function ").count(), 2, "{text}");
+    assert!(!text.contains("Import "), "{text}");
+}
+
+#[test]
+fn typed_dump_json_carries_type_field() {
+    let json = typed_dump(SOURCE, AstDumpFormat::Json);
+    assert!(json.contains("\"type\": \"i64\""), "{json}");
+}
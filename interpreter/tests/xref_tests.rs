@@ -0,0 +1,30 @@
+//! `interpreter --xref SYMBOL` (`interpreter::xref`): where a symbol is
+//! defined, used and called.
+
+const SOURCE: &str = "fn sq(x: u64) -> u64 {
+    x * x
+}
+
+fn main() -> u64 {
+    val f = sq
+    sq(2u64) + sq(3u64)
+}
+";
+
+#[test]
+fn lists_definition_uses_and_call_sites() {
+    let listing = interpreter::xref(SOURCE, "main.t", "sq").unwrap();
+    assert_eq!(
+        listing,
+        "main.t:1:4: definition\n\
+         main.t:6:13: use\n\
+         main.t:7:5: call from main\n\
+         main.t:7:16: call from main\n"
+    );
+}
+
+#[test]
+fn unknown_symbols_are_reported() {
+    let err = interpreter::xref(SOURCE, "main.t", "cube").unwrap_err();
+    assert_eq!(err, "no symbol `cube` in main.t");
+}